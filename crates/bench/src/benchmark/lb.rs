@@ -1,10 +1,19 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::OnceLock};
 
 use spooky_config::{
     config::{Backend, Config, HealthCheck, Listen, LoadBalancing, RouteMatch, Tls, Upstream},
     runtime::{RuntimeConfig, RuntimeUpstream},
 };
-use spooky_lb::upstream_pool::UpstreamPool;
+use spooky_lb::{
+    algorithms::{
+        bounded_consistent_hash::BoundedConsistentHash, consistent_hash::ConsistentHash,
+        maglev::Maglev, p2c::PowerOfTwoChoices, round_robin::RoundRobin,
+    },
+    backend::BackendState,
+    backend_pool::BackendPool,
+    balancer::register_balancer,
+    upstream_pool::UpstreamPool,
+};
 
 use crate::{
     benchmark::{connection::fast_iterations, runner::run_case_aggregate},
@@ -15,11 +24,34 @@ pub fn benchmark_lb(scale: usize) -> Result<Vec<BenchCase>, String> {
     let mut rr_pool = build_lb_pool(scale, "round-robin")?;
     let mut random_pool = build_lb_pool(scale, "random")?;
     let mut ch_pool = build_lb_pool(scale, "consistent-hash")?;
+    let weights = bench_weights(scale);
+    let mut weighted_rr_pool = build_weighted_lb_pool(&weights, "round-robin")?;
+    let mut weighted_random_pool = build_weighted_lb_pool(&weights, "random")?;
+    let mut lc_pool = build_lb_pool(scale, "least-connections")?;
+    let mut p2c_pool = build_lb_pool(scale, "p2c")?;
+    let mut maglev_pool = build_lb_pool(scale, "maglev")?;
+    let mut bounded_pool = build_lb_pool(scale, "consistent-hash-bounded")?;
+    register_bench_balancers()?;
+    let mut dyn_rr_pool = build_lb_pool(scale, "custom:bench-round-robin")?;
+    let mut dyn_p2c_pool = build_lb_pool(scale, "custom:bench-p2c")?;
+    let mut dyn_maglev_pool = build_lb_pool(scale, "custom:bench-maglev")?;
+    let (full, degraded) = membership_pools(scale);
+    let mut ch_churn = ConsistentHash::new(64);
+    let mut maglev_churn = Maglev::new(65_537);
+    let mut bounded_churn = BoundedConsistentHash::new(64, 125);
 
     let keys = [
         "user:1", "user:2", "user:3", "user:4", "user:5", "user:6", "user:7", "user:8",
     ];
     let mut ch_key_idx = 0usize;
+    let mut maglev_key_idx = 0usize;
+    let mut dyn_maglev_key_idx = 0usize;
+    let mut bounded_key_idx = 0usize;
+    let mut churn_idx = 0usize;
+    let mut churn_pool = || {
+        churn_idx = churn_idx.wrapping_add(1);
+        if churn_idx & 1 == 0 { &full } else { &degraded }
+    };
 
     Ok(vec![
         run_case_aggregate(
@@ -36,6 +68,34 @@ pub fn benchmark_lb(scale: usize) -> Result<Vec<BenchCase>, String> {
             fast_iterations(scale),
             || random_pool.pick("ignored").unwrap_or(usize::MAX),
         ),
+        run_case_aggregate(
+            "micro",
+            "lb_weighted_round_robin_pick",
+            scale,
+            fast_iterations(scale),
+            || weighted_rr_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_weighted_random_pick",
+            scale,
+            fast_iterations(scale),
+            || weighted_random_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_least_connections_pick",
+            scale,
+            lb_ch_iterations(scale),
+            || lc_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_p2c_pick",
+            scale,
+            fast_iterations(scale),
+            || p2c_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
         run_case_aggregate(
             "micro",
             "lb_consistent_hash_pick",
@@ -47,15 +107,171 @@ pub fn benchmark_lb(scale: usize) -> Result<Vec<BenchCase>, String> {
                 ch_pool.pick(key).unwrap_or(usize::MAX)
             },
         ),
+        run_case_aggregate(
+            "micro",
+            "lb_maglev_pick",
+            scale,
+            lb_ch_iterations(scale),
+            || {
+                let key = keys[maglev_key_idx & 7];
+                maglev_key_idx = maglev_key_idx.wrapping_add(1);
+                maglev_pool.pick(key).unwrap_or(usize::MAX)
+            },
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_bounded_consistent_hash_pick",
+            scale,
+            lb_ch_iterations(scale),
+            || {
+                let key = keys[bounded_key_idx & 7];
+                bounded_key_idx = bounded_key_idx.wrapping_add(1);
+                bounded_pool.pick(key).unwrap_or(usize::MAX)
+            },
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_dyn_round_robin_pick",
+            scale,
+            fast_iterations(scale),
+            || dyn_rr_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_dyn_p2c_pick",
+            scale,
+            fast_iterations(scale),
+            || dyn_p2c_pool.pick("ignored").unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_dyn_maglev_pick",
+            scale,
+            lb_ch_iterations(scale),
+            || {
+                let key = keys[dyn_maglev_key_idx & 7];
+                dyn_maglev_key_idx = dyn_maglev_key_idx.wrapping_add(1);
+                dyn_maglev_pool.pick(key).unwrap_or(usize::MAX)
+            },
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_consistent_hash_membership_change",
+            scale,
+            lb_membership_iterations(scale),
+            || ch_churn.pick("user:1", churn_pool()).unwrap_or(usize::MAX),
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_maglev_membership_change",
+            scale,
+            lb_membership_iterations(scale),
+            || {
+                maglev_churn
+                    .pick("user:1", churn_pool())
+                    .unwrap_or(usize::MAX)
+            },
+        ),
+        run_case_aggregate(
+            "micro",
+            "lb_bounded_consistent_hash_membership_change",
+            scale,
+            lb_membership_iterations(scale),
+            || {
+                bounded_churn
+                    .pick("user:1", churn_pool())
+                    .unwrap_or(usize::MAX)
+            },
+        ),
     ])
 }
 
-fn build_lb_upstream(scale: usize, lb_type: &str) -> Upstream {
-    let backends = (0..scale.max(1))
-        .map(|idx| Backend {
+/// Built-in algorithms registered as custom strategies, so the `lb_dyn_*`
+/// cases measure the same picks as their enum-dispatched counterparts
+/// behind `Box<dyn Balancer>`.
+fn register_bench_balancers() -> Result<(), String> {
+    static REGISTERED: OnceLock<Result<(), String>> = OnceLock::new();
+    REGISTERED
+        .get_or_init(|| {
+            let registered = [
+                register_balancer("bench-round-robin", |_| Box::new(RoundRobin::new())),
+                register_balancer("bench-p2c", |policy| {
+                    Box::new(PowerOfTwoChoices::new(
+                        policy.p2c.choices,
+                        policy.p2c.metric,
+                        policy.p2c.weighted,
+                    ))
+                }),
+                register_balancer("bench-maglev", |policy| {
+                    Box::new(Maglev::new(policy.table_size))
+                }),
+            ];
+            registered
+                .into_iter()
+                .collect::<Result<(), String>>()
+                .map_err(|err| format!("failed to register benchmark balancer: {err}"))
+        })
+        .clone()
+}
+
+/// The same backends twice, the second with backend 0 ejected, so picks
+/// alternating between them rebuild hash tables on every call.
+fn membership_pools(scale: usize) -> (BackendPool, BackendPool) {
+    let states = || {
+        build_lb_upstream(&vec![1; scale.max(1)], "consistent-hash")
+            .backends
+            .iter()
+            .map(BackendState::new)
+            .collect::<Vec<_>>()
+    };
+    let full = BackendPool::new_from_states(states());
+    let mut degraded = BackendPool::new_from_states(states());
+    while degraded.is_healthy_index(0) {
+        let _ = degraded.mark_failure(0);
+    }
+    (full, degraded)
+}
+
+/// Share of keys that change backend when backend 0 leaves `lb_type`'s pool,
+/// not counting the keys backend 0 owned. Ideal hashing moves none.
+pub fn membership_disruption(lb_type: &str, scale: usize, keys: usize) -> Result<f64, String> {
+    let mut pool = build_lb_pool(scale, lb_type)?;
+    let keys: Vec<String> = (0..keys).map(|idx| format!("user:{idx}")).collect();
+    let before: Vec<Option<usize>> = keys
+        .iter()
+        .map(|key| pool.pick_without_begin(key))
+        .collect();
+
+    while pool.is_backend_healthy(0) {
+        let _ = pool.mark_backend_failure_from_active_check(0);
+    }
+    let (kept, moved) = keys
+        .iter()
+        .zip(&before)
+        .filter(|(_, before)| **before != Some(0))
+        .fold((0usize, 0usize), |(kept, moved), (key, before)| {
+            if pool.pick_without_begin(key) == *before {
+                (kept + 1, moved)
+            } else {
+                (kept, moved + 1)
+            }
+        });
+    Ok(moved as f64 / (kept + moved).max(1) as f64)
+}
+
+/// Backend weights cycling through 1, 2, 3 and 4.
+pub fn bench_weights(scale: usize) -> Vec<u32> {
+    (0..scale.max(1)).map(|idx| 1 + (idx % 4) as u32).collect()
+}
+
+fn build_lb_upstream(weights: &[u32], lb_type: &str) -> Upstream {
+    let backends = weights
+        .iter()
+        .enumerate()
+        .map(|(idx, &weight)| Backend {
             id: format!("backend-{idx:05}"),
             address: format!("127.0.0.1:{}", 10_000 + (idx % 50_000)),
-            weight: 1,
+            weight,
             health_check: Some(HealthCheck {
                 path: "/health".to_string(),
                 interval: 5_000,
@@ -64,6 +280,8 @@ fn build_lb_upstream(scale: usize, lb_type: &str) -> Upstream {
                 success_threshold: 2,
                 cooldown_ms: 5_000,
             }),
+            priority: 0,
+            zone: None,
         })
         .collect();
 
@@ -71,9 +289,19 @@ fn build_lb_upstream(scale: usize, lb_type: &str) -> Upstream {
         load_balancing: LoadBalancing {
             lb_type: lb_type.to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -85,9 +313,9 @@ fn build_lb_upstream(scale: usize, lb_type: &str) -> Upstream {
     }
 }
 
-fn build_runtime_lb_upstream(scale: usize, lb_type: &str) -> Result<RuntimeUpstream, String> {
+fn build_runtime_lb_upstream(weights: &[u32], lb_type: &str) -> Result<RuntimeUpstream, String> {
     let mut upstreams = HashMap::new();
-    upstreams.insert("bench".to_string(), build_lb_upstream(scale, lb_type));
+    upstreams.insert("bench".to_string(), build_lb_upstream(weights, lb_type));
 
     RuntimeConfig::from_config(&Config {
         version: 1,
@@ -102,6 +330,7 @@ fn build_runtime_lb_upstream(scale: usize, lb_type: &str) -> Result<RuntimeUpstr
         },
         listeners: Vec::new(),
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: None,
         upstream_tls: Default::default(),
         log: Default::default(),
@@ -109,6 +338,7 @@ fn build_runtime_lb_upstream(scale: usize, lb_type: &str) -> Result<RuntimeUpstr
         observability: Default::default(),
         resilience: Default::default(),
         security: Default::default(),
+        locality: Default::default(),
     })
     .map_err(|err| format!("failed to normalize benchmark upstream '{lb_type}': {err}"))?
     .upstreams
@@ -117,9 +347,25 @@ fn build_runtime_lb_upstream(scale: usize, lb_type: &str) -> Result<RuntimeUpstr
 }
 
 pub fn build_lb_pool(scale: usize, lb_type: &str) -> Result<UpstreamPool, String> {
-    let runtime_upstream = build_runtime_lb_upstream(scale, lb_type)?;
-    UpstreamPool::from_runtime_upstream(&runtime_upstream)
-        .map_err(|err| format!("failed to build LB pool '{lb_type}' for scale {scale}: {err}"))
+    build_weighted_lb_pool(&vec![1; scale.max(1)], lb_type)
+}
+
+pub fn build_weighted_lb_pool(weights: &[u32], lb_type: &str) -> Result<UpstreamPool, String> {
+    let runtime_upstream = build_runtime_lb_upstream(weights, lb_type)?;
+    UpstreamPool::from_runtime_upstream(&runtime_upstream).map_err(|err| {
+        format!(
+            "failed to build LB pool '{lb_type}' for scale {}: {err}",
+            weights.len()
+        )
+    })
+}
+
+fn lb_membership_iterations(scale: usize) -> u64 {
+    match scale {
+        100 => 2_000,
+        1_000 => 200,
+        _ => 20,
+    }
 }
 
 fn lb_ch_iterations(scale: usize) -> u64 {
@@ -0,0 +1,32 @@
+use spooky_bench::benchmark::lb::{build_weighted_lb_pool, membership_disruption};
+
+#[test]
+fn hash_strategies_keep_most_keys_when_a_backend_leaves() {
+    for (lb_type, max_moved) in [
+        ("consistent-hash", 0.0),
+        ("maglev", 0.02),
+        ("consistent-hash-bounded", 0.0),
+    ] {
+        let moved = membership_disruption(lb_type, 50, 5_000).expect("pool");
+        assert!(moved <= max_moved, "{lb_type} moved {moved}");
+    }
+}
+
+#[test]
+fn maglev_key_share_follows_weights() {
+    let mut pool = build_weighted_lb_pool(&[1, 3], "maglev").expect("pool");
+    let heavy = (0..4_000)
+        .filter(|idx| pool.pick_without_begin(&format!("user:{idx}")) == Some(1))
+        .count();
+    assert!((2_800..=3_200).contains(&heavy), "{heavy}");
+}
+
+#[test]
+fn bounded_consistent_hash_spills_a_hot_key_under_load() {
+    let mut pool = build_weighted_lb_pool(&[1, 1, 1, 1], "consistent-hash-bounded").expect("pool");
+    let mut counts = [0usize; 4];
+    for _ in 0..40 {
+        counts[pool.pick("hot").expect("pick")] += 1;
+    }
+    assert!(counts.iter().all(|&count| count <= 13), "{counts:?}");
+}
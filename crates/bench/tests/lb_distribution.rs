@@ -0,0 +1,71 @@
+use spooky_bench::benchmark::lb::{bench_weights, build_weighted_lb_pool};
+
+fn pick_counts(lb_type: &str, weights: &[u32], picks: usize) -> Vec<usize> {
+    let mut pool = build_weighted_lb_pool(weights, lb_type).expect("pool");
+    let mut counts = vec![0; weights.len()];
+    for _ in 0..picks {
+        counts[pool.pick("ignored").expect("pick")] += 1;
+    }
+    counts
+}
+
+#[test]
+fn weighted_round_robin_matches_weights_exactly() {
+    let weights = bench_weights(8);
+    let counts = pick_counts("round-robin", &weights, 2_000);
+    assert_eq!(counts, vec![100, 200, 300, 400, 100, 200, 300, 400]);
+}
+
+#[test]
+fn weighted_round_robin_never_repeats_a_light_backend() {
+    let mut pool = build_weighted_lb_pool(&[4, 1, 1], "round-robin").expect("pool");
+    let picks: Vec<usize> = (0..12).filter_map(|_| pool.pick("ignored")).collect();
+    assert!(
+        picks
+            .windows(2)
+            .all(|pair| pair[0] == 0 || pair[0] != pair[1]),
+        "{picks:?}"
+    );
+    assert_eq!(picks.iter().filter(|&&idx| idx == 0).count(), 8);
+}
+
+#[test]
+fn weighted_random_tracks_weights() {
+    let weights = bench_weights(4);
+    let counts = pick_counts("random", &weights, 40_000);
+    for (count, weight) in counts.iter().zip(&weights) {
+        let expected = 4_000 * *weight as usize;
+        assert!(
+            count.abs_diff(expected) < expected / 10,
+            "{counts:?} vs weights {weights:?}"
+        );
+    }
+}
+
+#[test]
+fn weighted_least_connections_spreads_in_flight_requests_by_weight() {
+    let weights = bench_weights(4);
+    let counts = pick_counts("least-connections", &weights, 1_000);
+    assert_eq!(counts, vec![100, 200, 300, 400]);
+}
+
+#[test]
+fn weighted_p2c_keeps_in_flight_requests_near_weight_shares() {
+    let weights = bench_weights(8);
+    let counts = pick_counts("p2c", &weights, 2_000);
+    for (count, weight) in counts.iter().zip(&weights) {
+        let expected = 100 * *weight as usize;
+        assert!(count.abs_diff(expected) <= expected / 5, "{counts:?}");
+    }
+}
+
+#[test]
+fn weighted_readonly_pick_tracks_weights() {
+    let weights = bench_weights(4);
+    let pool = build_weighted_lb_pool(&weights, "round-robin").expect("pool");
+    let mut counts = vec![0; weights.len()];
+    for _ in 0..1_000 {
+        counts[pool.pick_readonly("ignored").expect("pick")] += 1;
+    }
+    assert_eq!(counts, vec![100, 200, 300, 400]);
+}
@@ -11,6 +11,7 @@ pub(crate) struct ForwardedHeaderChains<'a> {
     pub(crate) x_forwarded_for: &'a [Vec<u8>],
     pub(crate) x_forwarded_proto: &'a [Vec<u8>],
     pub(crate) x_forwarded_host: &'a [Vec<u8>],
+    pub(crate) x_forwarded_prefix: &'a [Vec<u8>],
 }
 
 #[derive(Debug, Default)]
@@ -19,6 +20,7 @@ pub(crate) struct ForwardedHeaderValues {
     pub(crate) x_forwarded_for: Option<HeaderValue>,
     pub(crate) x_forwarded_proto: Option<HeaderValue>,
     pub(crate) x_forwarded_host: Option<HeaderValue>,
+    pub(crate) x_forwarded_prefix: Option<HeaderValue>,
 }
 
 pub fn build_forwarded_header_values(
@@ -26,6 +28,7 @@ pub fn build_forwarded_header_values(
     inbound: ForwardedHeaderChains<'_>,
     client_ip: IpAddr,
     host_value: &str,
+    forwarded_prefix: Option<&str>,
 ) -> Result<ForwardedHeaderValues, BridgeError> {
     let forwarded_current = format!(
         "for={};proto=https;host=\"{}\"",
@@ -57,6 +60,12 @@ pub fn build_forwarded_header_values(
             inbound.x_forwarded_host,
             Some(x_forwarded_host_current.as_bytes()),
         )?,
+        // Only a prefix-stripping path rewrite contributes a current hop.
+        x_forwarded_prefix: merge_forwarded_chain(
+            policy.mode,
+            inbound.x_forwarded_prefix,
+            forwarded_prefix.map(str::as_bytes),
+        )?,
     })
 }
 
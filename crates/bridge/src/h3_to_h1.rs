@@ -9,6 +9,7 @@ use crate::{
     request::{
         RequestBuildInput, RequestBuildTarget, RequestHeaderAssembly, RequestHeaderPolicyInput,
         apply_request_header_assembly, apply_request_header_policies,
+        resolve_upstream_request_path,
     },
     websocket::{H3WebsocketRequestKind, h3_websocket_request_kind},
 };
@@ -36,6 +37,8 @@ pub(crate) fn build_h1_request(
     } = input;
 
     let method = Method::from_bytes(method.as_bytes()).map_err(|_| BridgeError::InvalidMethod)?;
+    let upstream_path = resolve_upstream_request_path(path, policies.path_rewrite);
+    let path = &*upstream_path.path;
     let websocket_kind = h3_websocket_request_kind(method.as_str(), headers);
     let preserve_upgrade = websocket_kind == H3WebsocketRequestKind::LegacyUpgrade;
 
@@ -46,6 +49,7 @@ pub(crate) fn build_h1_request(
         headers,
         preserve_upgrade,
         forwarded,
+        forwarded_prefix: upstream_path.forwarded_prefix,
     })?;
     for (header_name, header_value) in &resolved_headers.passthrough_headers {
         builder = builder.header(header_name, header_value);
@@ -10,6 +10,7 @@ use crate::{
     request::{
         RequestBuildInput, RequestBuildTarget, RequestHeaderAssembly, RequestHeaderPolicyInput,
         apply_request_header_assembly, apply_request_header_policies,
+        resolve_upstream_request_path,
     },
     websocket::{H3WebsocketRequestKind, h3_websocket_request_kind},
 };
@@ -32,6 +33,8 @@ pub(crate) fn build_h2_request_for_target(
     } = input;
 
     let method = Method::from_bytes(method.as_bytes()).map_err(|_| BridgeError::InvalidMethod)?;
+    let upstream_path = resolve_upstream_request_path(path, policies.path_rewrite);
+    let path = &*upstream_path.path;
     let websocket_kind = h3_websocket_request_kind(method.as_str(), headers);
     // Extended CONNECT is the H2 websocket path (RFC 8441).
     let websocket_extended_connect = websocket_kind == H3WebsocketRequestKind::ExtendedConnect
@@ -49,6 +52,7 @@ pub(crate) fn build_h2_request_for_target(
         headers,
         preserve_upgrade: false,
         forwarded,
+        forwarded_prefix: upstream_path.forwarded_prefix,
     })?;
     for (header_name, header_value) in &resolved_headers.passthrough_headers {
         builder = builder.header(header_name, header_value);
@@ -4,7 +4,7 @@
 //! application, and the stable entrypoints callers should use. Protocol-specific
 //! H1/H2 encoding details are delegated to internal builder modules.
 
-use std::{convert::Infallible, net::SocketAddr};
+use std::{borrow::Cow, convert::Infallible, net::SocketAddr};
 
 use bytes::Bytes;
 use http::{HeaderName, HeaderValue};
@@ -12,6 +12,7 @@ use http_body_util::combinators::BoxBody;
 use spooky_config::{
     backend_endpoint::BackendEndpoint,
     config::{ForwardedHeaderPolicy, UpstreamHostPolicy},
+    runtime::RuntimePathRewrite,
 };
 
 use crate::{
@@ -58,6 +59,8 @@ pub struct RequestForwardedContext {
 pub struct RequestBuildPolicies<'a> {
     pub host_policy: &'a UpstreamHostPolicy,
     pub forwarded_header_policy: &'a ForwardedHeaderPolicy,
+    /// Route path rewrite applied to the upstream request target.
+    pub path_rewrite: Option<&'a RuntimePathRewrite>,
 }
 
 #[derive(Debug)]
@@ -85,6 +88,7 @@ pub(crate) struct RequestHeaderPolicyInput<'a> {
     pub(crate) headers: &'a [quiche::h3::Header],
     pub(crate) preserve_upgrade: bool,
     pub(crate) forwarded: RequestForwardedContext,
+    pub(crate) forwarded_prefix: Option<&'a str>,
 }
 
 #[derive(Debug)]
@@ -94,6 +98,32 @@ pub(crate) struct ResolvedRequestHeaderPolicy {
     pub(crate) forwarded_values: ForwardedHeaderValues,
 }
 
+/// Request target sent upstream after the route's path rewrite.
+#[derive(Debug)]
+pub(crate) struct UpstreamRequestPath<'a> {
+    pub(crate) path: Cow<'a, str>,
+    /// Client-side prefix removed by the rewrite, for `X-Forwarded-Prefix`.
+    pub(crate) forwarded_prefix: Option<&'a str>,
+}
+
+/// Applies the route's path rewrite, falling back to the client path when no
+/// rule is configured or the rule does not match.
+pub(crate) fn resolve_upstream_request_path<'a>(
+    path: &'a str,
+    rewrite: Option<&'a RuntimePathRewrite>,
+) -> UpstreamRequestPath<'a> {
+    match rewrite.and_then(|rewrite| rewrite.apply(path)) {
+        Some(rewritten) => UpstreamRequestPath {
+            path: Cow::Owned(rewritten.path),
+            forwarded_prefix: rewritten.forwarded_prefix,
+        },
+        None => UpstreamRequestPath {
+            path: Cow::Borrowed(path),
+            forwarded_prefix: None,
+        },
+    }
+}
+
 pub(crate) struct RequestHeaderAssembly<'a> {
     pub(crate) resolved_headers: ResolvedRequestHeaderPolicy,
     pub(crate) trace: RequestTraceContext<'a>,
@@ -124,6 +154,7 @@ pub(crate) fn apply_request_header_policies(
         headers,
         preserve_upgrade,
         forwarded,
+        forwarded_prefix,
     } = input;
     let RequestBuildTarget { endpoint, policies } = target;
     let connection_tokens = connection_header_tokens(headers);
@@ -133,6 +164,7 @@ pub(crate) fn apply_request_header_policies(
     let mut x_forwarded_for_from_headers: Vec<Vec<u8>> = Vec::new();
     let mut x_forwarded_proto_from_headers: Vec<Vec<u8>> = Vec::new();
     let mut x_forwarded_host_from_headers: Vec<Vec<u8>> = Vec::new();
+    let mut x_forwarded_prefix_from_headers: Vec<Vec<u8>> = Vec::new();
 
     for header in headers {
         let name = header.name();
@@ -155,6 +187,10 @@ pub(crate) fn apply_request_header_policies(
             x_forwarded_host_from_headers.push(header.value().to_vec());
             continue;
         }
+        if name.eq_ignore_ascii_case(b"x-forwarded-prefix") {
+            x_forwarded_prefix_from_headers.push(header.value().to_vec());
+            continue;
+        }
 
         let header_name = HeaderName::from_bytes(name).map_err(|_| BridgeError::InvalidHeader)?;
         if should_strip_request_header(&header_name, &connection_tokens, preserve_upgrade) {
@@ -184,9 +220,11 @@ pub(crate) fn apply_request_header_policies(
             x_forwarded_for: &x_forwarded_for_from_headers,
             x_forwarded_proto: &x_forwarded_proto_from_headers,
             x_forwarded_host: &x_forwarded_host_from_headers,
+            x_forwarded_prefix: &x_forwarded_prefix_from_headers,
         },
         forwarded.client_addr.ip(),
         &host_value,
+        forwarded_prefix,
     )?;
 
     Ok(ResolvedRequestHeaderPolicy {
@@ -257,6 +295,9 @@ pub(crate) fn apply_request_header_assembly(
     if let Some(value) = resolved_headers.forwarded_values.x_forwarded_host {
         builder = builder.header(HeaderName::from_static("x-forwarded-host"), value);
     }
+    if let Some(value) = resolved_headers.forwarded_values.x_forwarded_prefix {
+        builder = builder.header(HeaderName::from_static("x-forwarded-prefix"), value);
+    }
 
     if add_te_trailers {
         builder = builder.header(http::header::TE, "trailers");
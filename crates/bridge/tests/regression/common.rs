@@ -33,6 +33,7 @@ pub fn request_target<'a>(
         policies: RequestBuildPolicies {
             host_policy,
             forwarded_header_policy,
+            path_rewrite: None,
         },
     }
 }
@@ -5,10 +5,11 @@ use http::{
     header::{CONTENT_LENGTH, HOST, TE},
 };
 use quiche::h3::Header;
-use spooky_bridge::request::build_h1_request;
+use spooky_bridge::request::{RequestBuildPolicies, RequestBuildTarget, build_h1_request};
 use spooky_config::{
     backend_endpoint::BackendEndpoint,
     config::{ForwardedHeaderPolicy, UpstreamHostPolicy},
+    runtime::RuntimePathRewrite,
 };
 
 use crate::common::{RequestInputMeta, request_input, request_target};
@@ -104,6 +105,57 @@ fn plain_requests_strip_hop_headers_and_add_te_trailers() {
     assert!(req.headers().get("x-secret").is_none());
 }
 
+#[test]
+fn path_rewrite_changes_upstream_target_and_sets_forwarded_prefix() {
+    let endpoint = BackendEndpoint::parse("http://billing.internal:8080").expect("endpoint");
+    let rewrite = RuntimePathRewrite::StripPrefix("/api/billing".to_string());
+    let host_policy = UpstreamHostPolicy::default();
+    let forwarded_policy = ForwardedHeaderPolicy::default();
+    let headers = vec![Header::new(b"x-forwarded-prefix", b"/spoofed")];
+    let meta = RequestInputMeta {
+        authority: Some("api.example.com"),
+        content_length: None,
+        request_id: 9,
+        traceparent: None,
+        client_addr: "203.0.113.10:44321".parse().expect("client"),
+    };
+    let target = || RequestBuildTarget {
+        endpoint: &endpoint,
+        policies: RequestBuildPolicies {
+            host_policy: &host_policy,
+            forwarded_header_policy: &forwarded_policy,
+            path_rewrite: Some(&rewrite),
+        },
+    };
+
+    let req = build_h1_request(
+        target(),
+        request_input(
+            "GET",
+            "/api/billing/invoices/7?expand=lines",
+            &headers,
+            meta,
+        ),
+    )
+    .expect("rewritten request");
+    assert_eq!(
+        req.uri().to_string(),
+        "http://billing.internal:8080/invoices/7?expand=lines"
+    );
+    assert_eq!(
+        req.headers()
+            .get("x-forwarded-prefix")
+            .and_then(|value| value.to_str().ok()),
+        Some("/api/billing")
+    );
+
+    // Paths outside the prefix pass through and drop the client-supplied prefix.
+    let req = build_h1_request(target(), request_input("GET", "/health", &headers, meta))
+        .expect("unrewritten request");
+    assert_eq!(req.uri().to_string(), "http://billing.internal:8080/health");
+    assert!(req.headers().get("x-forwarded-prefix").is_none());
+}
+
 #[test]
 fn legacy_websocket_requests_preserve_upgrade_headers() {
     let endpoint = BackendEndpoint::parse("http://backend.internal:8080").expect("endpoint");
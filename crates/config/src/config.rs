@@ -8,14 +8,15 @@ use crate::default::{
     get_default_failure_threshold, get_default_health_timeout, get_default_interval,
     get_default_load_balancing, get_default_log, get_default_log_file_path, get_default_log_level,
     get_default_path, get_default_port, get_default_protocol, get_default_success_threshold,
-    get_default_version, get_default_weight, observe_default_address,
-    observe_default_control_api_address, observe_default_control_api_connection_timeout_ms,
+    get_default_version, get_default_weight, locality_default_zone_env, observe_default_address,
+    observe_default_control_api_address, observe_default_control_api_backends_path,
+    observe_default_control_api_connection_timeout_ms, observe_default_control_api_explain_path,
     observe_default_control_api_health_path, observe_default_control_api_max_connections,
     observe_default_control_api_port, observe_default_control_api_ready_path,
     observe_default_control_api_reload_certs_path, observe_default_control_api_reload_path,
-    observe_default_control_api_restart_path, observe_default_control_api_runtime_path,
-    observe_default_metrics_connection_timeout_ms, observe_default_metrics_max_connections,
-    observe_default_metrics_path, observe_default_port,
+    observe_default_control_api_restart_path, observe_default_control_api_rollouts_path,
+    observe_default_control_api_runtime_path, observe_default_metrics_connection_timeout_ms,
+    observe_default_metrics_max_connections, observe_default_metrics_path, observe_default_port,
     observe_default_routing_transparency_enabled,
     observe_default_routing_transparency_expose_header,
     observe_default_routing_transparency_header_name,
@@ -41,12 +42,19 @@ use crate::default::{
     perf_default_worker_threads, resilience_default_adaptive_decrease_step,
     resilience_default_adaptive_enabled, resilience_default_adaptive_high_latency_ms,
     resilience_default_adaptive_increase_step, resilience_default_adaptive_min_limit,
-    resilience_default_brownout_enabled, resilience_default_brownout_recover_inflight_percent,
+    resilience_default_adaptive_min_rtt_window_ms, resilience_default_adaptive_queue_size,
+    resilience_default_adaptive_rtt_tolerance_percent,
+    resilience_default_adaptive_smoothing_percent, resilience_default_brownout_enabled,
+    resilience_default_brownout_recover_inflight_percent,
     resilience_default_brownout_trigger_inflight_percent, resilience_default_cb_enabled,
     resilience_default_cb_failure_threshold, resilience_default_cb_half_open_max_probes,
     resilience_default_cb_open_ms, resilience_default_hedging_delay_ms,
-    resilience_default_hedging_enabled, resilience_default_protocol_allow_0rtt,
-    resilience_default_protocol_allow_connect,
+    resilience_default_hedging_enabled, resilience_default_hedging_max_delay_ms,
+    resilience_default_hedging_max_hedged_percent, resilience_default_hedging_max_hedges,
+    resilience_default_hedging_min_delay_ms, resilience_default_hedging_min_samples,
+    resilience_default_hedging_percentile, resilience_default_hedging_window_ms,
+    resilience_default_priority_default_class, resilience_default_priority_max_inflight_percent,
+    resilience_default_protocol_allow_0rtt, resilience_default_protocol_allow_connect,
     resilience_default_protocol_enforce_authority_host_match,
     resilience_default_protocol_max_headers_bytes, resilience_default_protocol_max_headers_count,
     resilience_default_retry_budget_enabled, resilience_default_retry_budget_ratio_percent,
@@ -59,14 +67,87 @@ use crate::default::{
     resilience_default_watchdog_poll_stall_timeout_ms,
     resilience_default_watchdog_restart_cooldown_ms,
     resilience_default_watchdog_timeout_error_rate_percent,
-    resilience_default_watchdog_unhealthy_consecutive_windows, security_default_drop_privileges,
-    security_default_group, security_default_user, upstream_tls_default_strict_sni,
+    resilience_default_watchdog_unhealthy_consecutive_windows,
+    route_default_direct_response_status, route_default_mirror_max_body_bytes,
+    route_default_mirror_max_inflight, route_default_mirror_percent, route_default_redirect_status,
+    route_default_rollout_max_error_rate_increase_percent,
+    route_default_rollout_max_p95_latency_ratio_percent, route_default_rollout_min_requests,
+    route_default_rollout_step_interval_ms, route_default_rollout_steps,
+    security_default_drop_privileges, security_default_group, security_default_user,
+    upstream_default_lb_p2c_choices, upstream_default_lb_p2c_weighted,
+    upstream_default_outlier_base_ejection_ms, upstream_default_outlier_interval_ms,
+    upstream_default_outlier_max_ejection_ms, upstream_default_outlier_max_ejection_percent,
+    upstream_default_outlier_min_backends, upstream_default_outlier_min_requests,
+    upstream_default_session_affinity_cookie_name, upstream_default_session_affinity_path,
+    upstream_default_session_affinity_secure, upstream_default_slow_start_min_weight_percent,
+    upstream_default_zone_aware_enabled, upstream_tls_default_strict_sni,
     upstream_tls_default_verify_certificates,
 };
 
 pub const CURRENT_CONFIG_VERSION: u32 = 1;
 pub const SUPPORTED_CONFIG_VERSIONS: &[u32] = &[CURRENT_CONFIG_VERSION];
 
+/// Largest `direct_response` body, inline or from `body_file`.
+pub const MAX_DIRECT_RESPONSE_BODY_BYTES: usize = 64 * 1024;
+
+pub const VALID_REDIRECT_STATUSES: &[u16] = &[301, 302, 303, 307, 308];
+
+/// Largest request body a route `mirror` may buffer for its copy.
+pub const MAX_MIRROR_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest `policy.max_retry_attempts` an upstream or route may set.
+pub const MAX_ROUTE_RETRY_ATTEMPTS: u8 = 3;
+
+/// Largest `resilience.hedging.max_hedges`.
+pub const MAX_HEDGES_PER_REQUEST: u8 = 3;
+
+/// `load_balancing.table_size` used by `maglev` when unset.
+pub const DEFAULT_MAGLEV_TABLE_SIZE: u64 = 65_537;
+
+/// Largest `load_balancing.table_size`.
+pub const MAX_MAGLEV_TABLE_SIZE: u64 = 5_000_011;
+
+/// `load_balancing.hash_balance_factor` used by `consistent-hash-bounded`
+/// when unset.
+pub const DEFAULT_HASH_BALANCE_FACTOR: u32 = 125;
+
+/// Largest `load_balancing.p2c.choices`.
+pub const MAX_P2C_CHOICES: u32 = 8;
+
+/// `load_balancing.type` prefix selecting a strategy registered with
+/// `spooky_lb::balancer::register_balancer`, as in `custom:shadow-rr`.
+pub const CUSTOM_LB_TYPE_PREFIX: &str = "custom:";
+
+/// Registered strategy name from a `custom:<name>` lb type; `None` for
+/// built-in types or an empty name.
+pub fn custom_lb_name(lb_type: &str) -> Option<&str> {
+    let lb_type = lb_type.trim();
+    let prefix = lb_type.get(..CUSTOM_LB_TYPE_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(CUSTOM_LB_TYPE_PREFIX) {
+        return None;
+    }
+    let name = lb_type[CUSTOM_LB_TYPE_PREFIX.len()..].trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Trial division; Maglev table sizes stay below [`MAX_MAGLEV_TABLE_SIZE`].
+pub(crate) fn is_prime(value: u64) -> bool {
+    if value < 2 {
+        return false;
+    }
+    let mut divisor = 2;
+    while divisor * divisor <= value {
+        if value.is_multiple_of(divisor) {
+            return false;
+        }
+        divisor += 1;
+    }
+    true
+}
+
+/// Statuses `policy.retry_on` may list.
+pub const RETRYABLE_STATUS_RANGE: std::ops::RangeInclusive<u16> = 400..=599;
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
@@ -80,6 +161,9 @@ pub struct Config {
 
     pub upstream: HashMap<String, Upstream>,
 
+    #[serde(default)]
+    pub routes: Vec<Route>, // Named routes that target an upstream pool by name
+
     #[serde(default)]
     pub load_balancing: Option<LoadBalancing>, // Global fallback load balancing
 
@@ -100,6 +184,9 @@ pub struct Config {
 
     #[serde(default)]
     pub security: Security,
+
+    #[serde(default)]
+    pub locality: Locality,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -130,6 +217,44 @@ impl Default for PrivilegeDrop {
     }
 }
 
+/// Where this instance runs, for zone-aware load balancing.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Locality {
+    /// Availability zone of this instance. Takes precedence over `zone_env`.
+    #[serde(default)]
+    pub zone: Option<String>,
+
+    /// Environment variable read for the zone when `zone` is unset.
+    #[serde(default = "locality_default_zone_env")]
+    pub zone_env: String,
+}
+
+impl Default for Locality {
+    fn default() -> Self {
+        Self {
+            zone: None,
+            zone_env: locality_default_zone_env(),
+        }
+    }
+}
+
+impl Locality {
+    /// Instance zone from `zone`, else from `zone_env` via `lookup`. Blank
+    /// values count as unset.
+    pub fn resolve_zone(&self, lookup: impl FnOnce(&str) -> Option<String>) -> Option<String> {
+        let non_blank = |zone: String| {
+            let zone = zone.trim();
+            (!zone.is_empty()).then(|| zone.to_string())
+        };
+        match self.zone.clone().and_then(non_blank) {
+            Some(zone) => Some(zone),
+            None if self.zone_env.trim().is_empty() => None,
+            None => lookup(self.zone_env.trim()).and_then(non_blank),
+        }
+    }
+}
+
 pub fn effective_listens(config: &Config) -> Vec<Listen> {
     if config.listeners.is_empty() {
         vec![config.listen.clone()]
@@ -138,6 +263,23 @@ pub fn effective_listens(config: &Config) -> Vec<Listen> {
     }
 }
 
+/// Returns the configured `routes` followed by one shorthand route per upstream
+/// that sets `upstream.<name>.route`. Shorthand routes are named after their
+/// upstream and carry no policy overrides.
+pub fn effective_routes(config: &Config) -> Vec<Route> {
+    let mut shorthand = config
+        .upstream
+        .iter()
+        .filter(|(_, upstream)| !upstream.route.is_empty())
+        .map(|(name, upstream)| Route::shorthand(name, &upstream.route))
+        .collect::<Vec<_>>();
+    shorthand.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let mut routes = config.routes.clone();
+    routes.extend(shorthand);
+    routes
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Listen {
@@ -220,13 +362,38 @@ pub struct Upstream {
     #[serde(default)]
     pub host_policy: UpstreamHostPolicy,
 
+    /// Overrides of global timeouts, body cap, retries and hedging delay.
+    #[serde(default)]
+    pub policy: RoutePolicy,
+
+    #[serde(default)]
+    pub outlier_detection: Option<OutlierDetection>,
+
+    /// Ramps traffic to backends that just became healthy or were added.
+    #[serde(default)]
+    pub slow_start: Option<SlowStart>,
+
+    /// Zone-aware balancing; on by default once the instance knows its zone.
+    #[serde(default)]
+    pub zone_aware: Option<ZoneAware>,
+
+    /// Pins clients to a backend with a cookie the edge issues.
+    #[serde(default)]
+    pub session_affinity: Option<SessionAffinity>,
+
+    /// Gives the upstream its own adaptive concurrency limiter instead of the
+    /// global `resilience.adaptive_admission` one.
+    #[serde(default)]
+    pub adaptive_admission: Option<AdaptiveAdmission>,
+
     #[serde(default)]
     pub forwarded_headers: ForwardedHeaderPolicy,
 
     #[serde(default)]
     pub tls: Option<UpstreamTls>,
 
-    pub route: RouteMatch, // Route matching criteria for this upstream
+    #[serde(default)]
+    pub route: RouteMatch, // Shorthand route; leave empty when only `routes` target this upstream
 
     pub backends: Vec<Backend>,
 }
@@ -382,7 +549,7 @@ pub struct UpstreamHostPolicy {
     pub host: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Backend {
     pub id: String, // "backend1"
@@ -396,6 +563,15 @@ pub struct Backend {
     pub weight: u32, // 100
     #[serde(default)]
     pub health_check: Option<HealthCheck>,
+
+    /// Failover tier. Lower tiers take traffic first; higher ones receive the
+    /// overflow as the tiers below lose healthy capacity.
+    #[serde(default)]
+    pub priority: u32,
+
+    /// Availability zone the backend runs in.
+    #[serde(default)]
+    pub zone: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -411,8 +587,588 @@ pub struct RouteMatch {
     pub method: Option<String>, // Optional HTTP method filtering (GET, POST, etc.)
 }
 
+impl RouteMatch {
+    pub fn is_empty(&self) -> bool {
+        self.host.is_none() && self.path_prefix.is_none() && self.method.is_none()
+    }
+}
+
+/// Named route targeting an upstream pool. Unset overrides inherit the
+/// upstream's `auth`, `host_policy` and `forwarded_headers`. A route with a
+/// `redirect` or `direct_response` action is answered by the edge and leaves
+/// `upstream` empty.
 #[derive(Debug, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
+pub struct Route {
+    pub name: String,
+    #[serde(default)]
+    pub upstream: String,
+
+    #[serde(rename = "match")]
+    pub matcher: RouteMatch,
+
+    #[serde(default)]
+    pub auth: Option<RouteAuth>,
+
+    #[serde(default)]
+    pub host_policy: Option<UpstreamHostPolicy>,
+
+    #[serde(default)]
+    pub forwarded_headers: Option<ForwardedHeaderPolicy>,
+
+    /// Rewrites the upstream request path; logs keep the client path.
+    #[serde(default)]
+    pub rewrite: Option<PathRewrite>,
+
+    /// Weighted split across several upstreams. `upstream` must be one of the
+    /// targets; its policy (plus route overrides) applies to every target.
+    #[serde(default)]
+    pub split: Option<TrafficSplit>,
+
+    /// Answers with a redirect instead of forwarding.
+    #[serde(default)]
+    pub redirect: Option<RouteRedirect>,
+
+    /// Answers with a fixed response instead of forwarding.
+    #[serde(default)]
+    pub direct_response: Option<DirectResponse>,
+
+    /// Copies a sample of requests to a shadow upstream; its responses are
+    /// discarded.
+    #[serde(default)]
+    pub mirror: Option<RouteMirror>,
+
+    /// Injects delays, aborts or truncated responses for resilience testing.
+    #[serde(default)]
+    pub fault: Option<RouteFault>,
+
+    /// Field-by-field overrides of the upstream's `policy`.
+    #[serde(default)]
+    pub policy: Option<RoutePolicy>,
+}
+
+/// Per-upstream or per-route overrides of global request limits. Unset
+/// fields fall back to the upstream's policy, then to `performance` and
+/// `resilience.hedging`.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RoutePolicy {
+    #[serde(default)]
+    pub backend_timeout_ms: Option<u64>,
+
+    #[serde(default)]
+    pub backend_total_request_timeout_ms: Option<u64>,
+
+    #[serde(default)]
+    pub max_request_body_bytes: Option<usize>,
+
+    /// Retries after a failed primary attempt; `0` disables retries.
+    #[serde(default)]
+    pub max_retry_attempts: Option<u8>,
+
+    /// Hedge delay when `resilience.hedging` applies to the route.
+    #[serde(default)]
+    pub hedging_delay_ms: Option<u64>,
+
+    /// Statuses and failure modes that trigger a retry. Unset retries
+    /// failures before response headers and no statuses.
+    #[serde(default)]
+    pub retry_on: Option<Vec<RetryOn>>,
+
+    /// Backoff cap for the first retry; it doubles per retry up to
+    /// `retry_backoff_max_ms`, and each wait is drawn below the cap.
+    #[serde(default)]
+    pub retry_backoff_base_ms: Option<u64>,
+
+    #[serde(default)]
+    pub retry_backoff_max_ms: Option<u64>,
+
+    /// Longest `Retry-After` on a 503 that is waited out before retrying.
+    #[serde(default)]
+    pub retry_after_max_ms: Option<u64>,
+
+    /// Request bodies up to this size are kept so retries and hedges can
+    /// resend them; larger bodies stream without replay. `0` disables.
+    #[serde(default)]
+    pub replay_body_max_bytes: Option<usize>,
+
+    /// Header that makes POST and PATCH requests safe to retry and hedge
+    /// when replay is enabled.
+    #[serde(default)]
+    pub idempotency_header: Option<String>,
+}
+
+/// One `policy.retry_on` entry: a response status or a failure mode.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RetryOn {
+    Status(u16),
+    Condition(RetryCondition),
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryCondition {
+    /// Timeouts, resets and pool rejections before response headers.
+    #[serde(alias = "reset-before-headers")]
+    ResetBeforeHeaders,
+    /// Only failures where the request never reached the backend.
+    #[serde(alias = "connect-failure")]
+    ConnectFailure,
+}
+
+impl RoutePolicy {
+    /// Fills fields unset here from `base`.
+    pub fn or(&self, base: &RoutePolicy) -> RoutePolicy {
+        RoutePolicy {
+            backend_timeout_ms: self.backend_timeout_ms.or(base.backend_timeout_ms),
+            backend_total_request_timeout_ms: self
+                .backend_total_request_timeout_ms
+                .or(base.backend_total_request_timeout_ms),
+            max_request_body_bytes: self.max_request_body_bytes.or(base.max_request_body_bytes),
+            max_retry_attempts: self.max_retry_attempts.or(base.max_retry_attempts),
+            hedging_delay_ms: self.hedging_delay_ms.or(base.hedging_delay_ms),
+            retry_on: self.retry_on.clone().or_else(|| base.retry_on.clone()),
+            retry_backoff_base_ms: self.retry_backoff_base_ms.or(base.retry_backoff_base_ms),
+            retry_backoff_max_ms: self.retry_backoff_max_ms.or(base.retry_backoff_max_ms),
+            retry_after_max_ms: self.retry_after_max_ms.or(base.retry_after_max_ms),
+            replay_body_max_bytes: self.replay_body_max_bytes.or(base.replay_body_max_bytes),
+            idempotency_header: self
+                .idempotency_header
+                .clone()
+                .or_else(|| base.idempotency_header.clone()),
+        }
+    }
+}
+
+/// Shadow traffic for a route. Copies are sent fire-and-forget after the
+/// primary is dispatched and never affect the client response. Request bodies
+/// are only mirrored when their declared length fits in `max_body_bytes`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RouteMirror {
+    pub upstream: String,
+
+    /// Share of matching requests that are copied, 1-100.
+    #[serde(default = "route_default_mirror_percent")]
+    pub percent: u32,
+
+    /// Only mirror these methods; empty mirrors every method.
+    #[serde(default)]
+    pub methods: Vec<String>,
+
+    #[serde(default = "route_default_mirror_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Mirror copies in flight for this route; separate from
+    /// `performance.global_inflight_limit`.
+    #[serde(default = "route_default_mirror_max_inflight")]
+    pub max_inflight: usize,
+}
+
+/// Faults injected on a route. Each fault is sampled independently; an abort
+/// wins over the others. A `percent` of 0 keeps the fault configured but idle.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RouteFault {
+    /// Only requests carrying this header are eligible.
+    #[serde(default)]
+    pub header: Option<FaultHeaderMatch>,
+
+    #[serde(default)]
+    pub delay: Option<FaultDelay>,
+
+    #[serde(default)]
+    pub abort: Option<FaultAbort>,
+
+    #[serde(default)]
+    pub truncate: Option<FaultTruncate>,
+}
+
+/// Header gate for route faults; without `value` any value matches.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FaultHeaderMatch {
+    pub name: String,
+
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// Holds the primary attempt before it is sent. With `max_duration_ms` the
+/// delay is drawn uniformly from `duration_ms..=max_duration_ms`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FaultDelay {
+    pub percent: u32,
+    pub duration_ms: u64,
+
+    #[serde(default)]
+    pub max_duration_ms: Option<u64>,
+}
+
+/// Answers without contacting the upstream, either with `status` or by
+/// resetting the HTTP/3 stream. Exactly one must be set.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FaultAbort {
+    pub percent: u32,
+
+    #[serde(default)]
+    pub status: Option<u16>,
+
+    #[serde(default)]
+    pub reset: bool,
+}
+
+/// Fails the upstream response body after `after_bytes` bytes.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FaultTruncate {
+    pub percent: u32,
+    pub after_bytes: usize,
+}
+
+/// Redirect answered by the edge. Unset parts come from the request: the path
+/// (after `rewrite`, if the route has one), the host and the query string.
+/// An empty `query` drops the request query.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RouteRedirect {
+    #[serde(default = "route_default_redirect_status")]
+    pub status: u16,
+
+    /// Defaults to `https`.
+    #[serde(default)]
+    pub scheme: Option<String>,
+
+    #[serde(default)]
+    pub host: Option<String>,
+
+    #[serde(default)]
+    pub path: Option<String>,
+
+    #[serde(default)]
+    pub query: Option<String>,
+}
+
+/// Fixed response answered by the edge. At most one of `body` and `body_file`
+/// may be set; `body_file` is read when the config is loaded.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DirectResponse {
+    #[serde(default = "route_default_direct_response_status")]
+    pub status: u16,
+
+    #[serde(default)]
+    pub headers: Vec<ResponseHeader>,
+
+    #[serde(default)]
+    pub body: Option<String>,
+
+    #[serde(default)]
+    pub body_file: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// Path rewrite applied to the upstream request. Exactly one rule must be set;
+/// the query string is carried over unchanged.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PathRewrite {
+    /// Removes a leading path prefix (`/api/billing/x` -> `/x`).
+    #[serde(default)]
+    pub strip_prefix: Option<String>,
+
+    /// Swaps a leading path prefix for another one.
+    #[serde(default)]
+    pub replace_prefix: Option<PrefixRewrite>,
+
+    /// Regex substitution over the path; `$1`/`${name}` expand capture groups.
+    #[serde(default)]
+    pub regex: Option<RegexRewrite>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PrefixRewrite {
+    pub prefix: String,
+    pub replacement: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RegexRewrite {
+    pub pattern: String,
+    pub substitution: String,
+}
+
+/// Splits route traffic across upstreams by weight. With `key` set, requests
+/// carrying the same key value stick to one target; otherwise selection is
+/// random per request.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TrafficSplit {
+    #[serde(default)]
+    pub key: Option<String>,
+    pub targets: Vec<SplitTarget>,
+
+    /// Progressive rollout that steps one target's share up on a schedule
+    /// instead of using its static weight.
+    #[serde(default)]
+    pub rollout: Option<CanaryRollout>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SplitTarget {
+    pub upstream: String,
+    pub weight: u32,
+}
+
+/// Canary rollout schedule for a traffic split. `canary` receives `steps[i]`
+/// percent of route traffic; the remaining targets share the rest by weight.
+/// Between steps the canary is compared with those baseline targets and rolled
+/// back to 0% on an error-rate or p95 latency regression.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CanaryRollout {
+    pub canary: String,
+
+    #[serde(default = "route_default_rollout_steps")]
+    pub steps: Vec<u32>,
+
+    #[serde(default = "route_default_rollout_step_interval_ms")]
+    pub step_interval_ms: u64,
+
+    /// Canary requests required in a step before it is judged.
+    #[serde(default = "route_default_rollout_min_requests")]
+    pub min_requests: u64,
+
+    /// Allowed canary error-rate excess over baseline, in percentage points.
+    #[serde(default = "route_default_rollout_max_error_rate_increase_percent")]
+    pub max_error_rate_increase_percent: u32,
+
+    /// Allowed canary p95 latency as a percentage of baseline p95.
+    #[serde(default = "route_default_rollout_max_p95_latency_ratio_percent")]
+    pub max_p95_latency_ratio_percent: u32,
+}
+
+impl Route {
+    fn shorthand(upstream_name: &str, matcher: &RouteMatch) -> Self {
+        Self {
+            name: upstream_name.to_string(),
+            upstream: upstream_name.to_string(),
+            matcher: matcher.clone(),
+            auth: None,
+            host_policy: None,
+            forwarded_headers: None,
+            rewrite: None,
+            split: None,
+            redirect: None,
+            direct_response: None,
+            mirror: None,
+            fault: None,
+            policy: None,
+        }
+    }
+
+    /// True when the route is answered by the edge rather than an upstream.
+    pub fn has_action(&self) -> bool {
+        self.redirect.is_some() || self.direct_response.is_some()
+    }
+}
+
+/// Same-zone preference for an upstream. Backends in `locality.zone` take
+/// the traffic of each priority tier; other zones get the overflow once the
+/// local ones lack healthy capacity or in-flight headroom.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ZoneAware {
+    #[serde(default = "upstream_default_zone_aware_enabled")]
+    pub enabled: bool,
+
+    /// In-flight requests per healthy local backend at which traffic spills
+    /// to every zone, until the local load falls below 80% of it. Unset
+    /// spills on health only.
+    #[serde(default)]
+    pub max_inflight_per_backend: Option<u32>,
+}
+
+impl Default for ZoneAware {
+    fn default() -> Self {
+        Self {
+            enabled: upstream_default_zone_aware_enabled(),
+            max_inflight_per_backend: None,
+        }
+    }
+}
+
+/// Cookie-based session affinity. The first response to a client carries an
+/// encrypted cookie naming the backend that served it; later requests with the
+/// cookie go to that backend while it is healthy and are balanced normally,
+/// with a fresh cookie, once it is not. The cookie names the backend by `id`,
+/// so it survives reordering and reloads.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SessionAffinity {
+    #[serde(default = "upstream_default_session_affinity_cookie_name")]
+    pub cookie_name: String,
+
+    /// Cookie lifetime (`Max-Age`). Unset issues a session cookie.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+
+    #[serde(default = "upstream_default_session_affinity_path")]
+    pub path: String,
+
+    #[serde(default)]
+    pub same_site: CookieSameSite,
+
+    #[serde(default = "upstream_default_session_affinity_secure")]
+    pub secure: bool,
+
+    /// Key that encrypts and authenticates the cookie. Unset uses a key
+    /// generated at startup, so cookies stop matching after a restart and
+    /// across instances.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl Default for SessionAffinity {
+    fn default() -> Self {
+        Self {
+            cookie_name: upstream_default_session_affinity_cookie_name(),
+            ttl_secs: None,
+            path: upstream_default_session_affinity_path(),
+            same_site: CookieSameSite::default(),
+            secure: upstream_default_session_affinity_secure(),
+            secret: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CookieSameSite {
+    #[default]
+    Lax,
+    Strict,
+    /// Requires `secure`.
+    None,
+}
+
+impl SessionAffinity {
+    /// `cookie_name` is an RFC 6265 cookie name (an HTTP token).
+    pub fn has_valid_cookie_name(&self) -> bool {
+        !self.cookie_name.is_empty()
+            && self
+                .cookie_name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+    }
+
+    /// `path` is absolute and fits in a `Path` cookie attribute.
+    pub fn has_valid_path(&self) -> bool {
+        self.path.starts_with('/')
+            && self
+                .path
+                .bytes()
+                .all(|byte| byte.is_ascii_graphic() && byte != b';')
+    }
+}
+
+impl CookieSameSite {
+    /// Value of the `SameSite` cookie attribute.
+    pub fn as_attribute(self) -> &'static str {
+        match self {
+            Self::Lax => "Lax",
+            Self::Strict => "Strict",
+            Self::None => "None",
+        }
+    }
+}
+
+/// Traffic ramp for backends that just became healthy or were added. The
+/// backend's effective weight grows from `min_weight_percent` of its weight
+/// to all of it over `window_ms`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SlowStart {
+    /// How long a backend takes to reach its full weight.
+    pub window_ms: u64,
+
+    /// Share of its weight a backend starts the window with, 1 to 100.
+    #[serde(default = "upstream_default_slow_start_min_weight_percent")]
+    pub min_weight_percent: u32,
+
+    #[serde(default)]
+    pub curve: SlowStartCurve,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowStartCurve {
+    /// Weight grows in proportion to elapsed time.
+    #[default]
+    Linear,
+    /// Weight grows with the square root of elapsed time, reaching half of
+    /// the ramp a quarter of the way through the window.
+    Aggressive,
+}
+
+/// Passive outlier detection for an upstream's backends. Every interval each
+/// backend's requests in that interval are judged by the enabled checks; a
+/// backend failing one is ejected from rotation, for longer on each repeat.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OutlierDetection {
+    #[serde(default = "upstream_default_outlier_interval_ms")]
+    pub interval_ms: u64,
+
+    /// Requests a backend must serve in an interval before it is judged.
+    #[serde(default = "upstream_default_outlier_min_requests")]
+    pub min_requests: u64,
+
+    /// Judged backends required before the pool-relative checks run.
+    #[serde(default = "upstream_default_outlier_min_backends")]
+    pub min_backends: usize,
+
+    /// Eject a backend whose success rate is below this percentage.
+    #[serde(default)]
+    pub success_rate_min_percent: Option<u32>,
+
+    /// Eject a backend whose success rate is more than this many standard
+    /// deviations below the mean of the judged backends.
+    #[serde(default)]
+    pub success_rate_stdev_factor: Option<f64>,
+
+    /// Eject a backend whose p95 latency exceeds the judged backends' median
+    /// p95 by this ratio, in percent (300 is three times the median).
+    #[serde(default)]
+    pub latency_p95_ratio_percent: Option<u32>,
+
+    /// Share of the upstream's backends that may be out of rotation at once.
+    /// At least one backend can always be ejected while another stays healthy.
+    #[serde(default = "upstream_default_outlier_max_ejection_percent")]
+    pub max_ejection_percent: u32,
+
+    /// First ejection time; each repeat ejection doubles it up to `max_ejection_ms`.
+    #[serde(default = "upstream_default_outlier_base_ejection_ms")]
+    pub base_ejection_ms: u64,
+
+    #[serde(default = "upstream_default_outlier_max_ejection_ms")]
+    pub max_ejection_ms: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct HealthCheck {
     #[serde(default = "get_default_path")]
     pub path: String, // "/health"
@@ -437,11 +1193,62 @@ pub struct HealthCheck {
 #[serde(deny_unknown_fields)]
 pub struct LoadBalancing {
     #[serde(rename = "type")]
-    pub lb_type: String, // "random","round_robin","consistent_hash","least_connections","latency_aware","sticky_cid"
+    pub lb_type: String, // "random","round_robin","consistent_hash","consistent_hash_bounded","maglev","least_connections","latency_aware","p2c","sticky_cid","custom:<name>"
 
     // Configurable key source for hash-based/sticky load balancing.
     #[serde(default)]
     pub key: Option<String>, // Examples: header:x-user-id, cookie:session_id, query:user_id
+
+    /// Maglev lookup table entries; must be prime. `maglev` only.
+    #[serde(default)]
+    pub table_size: Option<u64>,
+
+    /// Most a backend may carry under `consistent-hash-bounded`, as a
+    /// percentage of its weighted share of the active requests; at least 100.
+    #[serde(default)]
+    pub hash_balance_factor: Option<u32>,
+
+    /// Sampling settings for `p2c`.
+    #[serde(default)]
+    pub p2c: Option<PowerOfTwoChoices>,
+
+    /// Balance across every backend, healthy or not, once the healthy share
+    /// of the upstream drops below this percentage. Unset never panics.
+    #[serde(default)]
+    pub panic_threshold_percent: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum P2cMetric {
+    /// Fewest active requests per unit of weight.
+    #[default]
+    LeastRequest,
+    /// Lowest EWMA latency plus in-flight penalty, scored like `latency-aware`.
+    Latency,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PowerOfTwoChoices {
+    /// Healthy backends sampled per pick, 2 to [`MAX_P2C_CHOICES`].
+    #[serde(default = "upstream_default_lb_p2c_choices")]
+    pub choices: u32,
+    #[serde(default)]
+    pub metric: P2cMetric,
+    /// Sample backends in proportion to their weight.
+    #[serde(default = "upstream_default_lb_p2c_weighted")]
+    pub weighted: bool,
+}
+
+impl Default for PowerOfTwoChoices {
+    fn default() -> Self {
+        Self {
+            choices: upstream_default_lb_p2c_choices(),
+            metric: P2cMetric::default(),
+            weighted: upstream_default_lb_p2c_weighted(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -687,6 +1494,8 @@ pub struct Resilience {
     #[serde(default)]
     pub brownout: Brownout,
     #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
     pub watchdog: Watchdog,
 }
 
@@ -705,6 +1514,33 @@ pub struct AdaptiveAdmission {
     pub increase_step: usize,
     #[serde(default = "resilience_default_adaptive_high_latency_ms")]
     pub high_latency_ms: u64,
+    #[serde(default)]
+    pub algorithm: AdaptiveAdmissionAlgorithm,
+    /// Gradient only: how long the no-load RTT estimate keeps its minimum.
+    #[serde(default = "resilience_default_adaptive_min_rtt_window_ms")]
+    pub min_rtt_window_ms: u64,
+    /// Gradient only: smoothed RTT may exceed the minimum by this much
+    /// before the limit starts to shrink.
+    #[serde(default = "resilience_default_adaptive_rtt_tolerance_percent")]
+    pub rtt_tolerance_percent: u32,
+    /// Gradient only: share of each new limit estimate blended into the
+    /// current limit.
+    #[serde(default = "resilience_default_adaptive_smoothing_percent")]
+    pub smoothing_percent: u32,
+    /// Gradient only: headroom added on top of the scaled limit so it can
+    /// grow while latency stays at the minimum.
+    #[serde(default = "resilience_default_adaptive_queue_size")]
+    pub queue_size: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AdaptiveAdmissionAlgorithm {
+    /// Additive increase, step decrease past `high_latency_ms`.
+    #[default]
+    Aimd,
+    /// Scales the limit by the ratio of minimum to smoothed RTT.
+    Gradient,
 }
 
 impl Resilience {
@@ -859,6 +1695,11 @@ impl Default for AdaptiveAdmission {
             decrease_step: resilience_default_adaptive_decrease_step(),
             increase_step: resilience_default_adaptive_increase_step(),
             high_latency_ms: resilience_default_adaptive_high_latency_ms(),
+            algorithm: AdaptiveAdmissionAlgorithm::default(),
+            min_rtt_window_ms: resilience_default_adaptive_min_rtt_window_ms(),
+            rtt_tolerance_percent: resilience_default_adaptive_rtt_tolerance_percent(),
+            smoothing_percent: resilience_default_adaptive_smoothing_percent(),
+            queue_size: resilience_default_adaptive_queue_size(),
         }
     }
 }
@@ -983,12 +1824,38 @@ impl Default for CircuitBreaker {
 pub struct Hedging {
     #[serde(default = "resilience_default_hedging_enabled")]
     pub enabled: bool,
+    /// Fixed delay before the first hedge; in percentile mode, the delay
+    /// used until a route has enough latency samples.
     #[serde(default = "resilience_default_hedging_delay_ms")]
     pub delay_ms: u64,
     #[serde(default)]
     pub safe_methods: Vec<String>,
     #[serde(default)]
     pub route_allowlist: Vec<String>,
+    #[serde(default)]
+    pub mode: HedgingMode,
+    /// Percentile only: route latency percentile the delay tracks.
+    #[serde(default = "resilience_default_hedging_percentile")]
+    pub percentile: u8,
+    /// Percentile only: floor of the tracked delay.
+    #[serde(default = "resilience_default_hedging_min_delay_ms")]
+    pub min_delay_ms: u64,
+    /// Percentile only: ceiling of the tracked delay.
+    #[serde(default = "resilience_default_hedging_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Percentile only: latency samples a window needs before it moves the
+    /// delay.
+    #[serde(default = "resilience_default_hedging_min_samples")]
+    pub min_samples: u64,
+    /// How often the delay is re-estimated and the hedged share reset.
+    #[serde(default = "resilience_default_hedging_window_ms")]
+    pub window_ms: u64,
+    /// Share of a route's requests per window that may be hedged.
+    #[serde(default = "resilience_default_hedging_max_hedged_percent")]
+    pub max_hedged_percent: u8,
+    /// Hedges per request, each sent one delay after the previous attempt.
+    #[serde(default = "resilience_default_hedging_max_hedges")]
+    pub max_hedges: u8,
 }
 
 impl Default for Hedging {
@@ -998,10 +1865,28 @@ impl Default for Hedging {
             delay_ms: resilience_default_hedging_delay_ms(),
             safe_methods: vec!["GET".to_string(), "HEAD".to_string()],
             route_allowlist: Vec::new(),
+            mode: HedgingMode::default(),
+            percentile: resilience_default_hedging_percentile(),
+            min_delay_ms: resilience_default_hedging_min_delay_ms(),
+            max_delay_ms: resilience_default_hedging_max_delay_ms(),
+            min_samples: resilience_default_hedging_min_samples(),
+            window_ms: resilience_default_hedging_window_ms(),
+            max_hedged_percent: resilience_default_hedging_max_hedged_percent(),
+            max_hedges: resilience_default_hedging_max_hedges(),
         }
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HedgingMode {
+    /// Hedge after `delay_ms`.
+    #[default]
+    Fixed,
+    /// Hedge after a percentile of the route's recent latency.
+    Percentile,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RetryBudget {
@@ -1047,6 +1932,60 @@ impl Default for Brownout {
     }
 }
 
+/// Request priority classes used by overload shedding.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Priority {
+    /// Class of requests no rule matches.
+    #[serde(default = "resilience_default_priority_default_class")]
+    pub default_class: String,
+    #[serde(default)]
+    pub classes: Vec<PriorityClass>,
+    /// Evaluated in order; the first matching rule assigns the class.
+    #[serde(default)]
+    pub rules: Vec<PriorityRule>,
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self {
+            default_class: resilience_default_priority_default_class(),
+            classes: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PriorityClass {
+    pub name: String,
+    /// Share of each admission limiter this class may fill; the remainder
+    /// is reserved for classes with a higher share.
+    #[serde(default = "resilience_default_priority_max_inflight_percent")]
+    pub max_inflight_percent: u8,
+    /// Keep admitting this class while brownout is active.
+    #[serde(default)]
+    pub brownout_exempt: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PriorityRule {
+    pub class: String,
+    /// Routes the rule applies to; empty matches every route.
+    #[serde(default)]
+    pub routes: Vec<String>,
+    #[serde(default)]
+    pub header: Option<String>,
+    /// Claim of the verified bearer JWT; only matches on routes with `auth.jwt`.
+    #[serde(default)]
+    pub jwt_claim: Option<String>,
+    /// Accepted values of `header` or `jwt_claim`.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Watchdog {
@@ -1186,6 +2125,26 @@ pub struct ControlApi {
     #[serde(default = "observe_default_control_api_reload_certs_path")]
     pub reload_certs_path: String,
 
+    #[serde(default = "observe_default_control_api_rollouts_path")]
+    pub rollouts_path: String,
+
+    #[serde(default = "observe_default_control_api_explain_path")]
+    pub explain_path: String,
+
+    #[serde(default = "observe_default_control_api_backends_path")]
+    pub backends_path: String,
+
+    /// JSON file backend drain/disable overrides are written to and restored
+    /// from at startup. Unset keeps them in memory only.
+    #[serde(default)]
+    pub backend_state_file: Option<String>,
+
+    /// JSON file backends added, changed or removed through the control API
+    /// are written to and replayed from at startup and reload. Unset keeps
+    /// them until the next reload only.
+    #[serde(default)]
+    pub membership_state_file: Option<String>,
+
     // Admin credential: never emitted by Serialize (e.g. the /admin/runtime
     // dump) and redacted in Debug; still accepted on deserialize.
     #[serde(default, skip_serializing)]
@@ -1211,6 +2170,11 @@ impl Default for ControlApi {
             restart_path: observe_default_control_api_restart_path(),
             reload_path: observe_default_control_api_reload_path(),
             reload_certs_path: observe_default_control_api_reload_certs_path(),
+            rollouts_path: observe_default_control_api_rollouts_path(),
+            explain_path: observe_default_control_api_explain_path(),
+            backends_path: observe_default_control_api_backends_path(),
+            backend_state_file: None,
+            membership_state_file: None,
             auth_token: None,
             max_connections: observe_default_control_api_max_connections(),
             connection_timeout_ms: observe_default_control_api_connection_timeout_ms(),
@@ -1231,6 +2195,11 @@ impl std::fmt::Debug for ControlApi {
             .field("restart_path", &self.restart_path)
             .field("reload_path", &self.reload_path)
             .field("reload_certs_path", &self.reload_certs_path)
+            .field("rollouts_path", &self.rollouts_path)
+            .field("explain_path", &self.explain_path)
+            .field("backends_path", &self.backends_path)
+            .field("backend_state_file", &self.backend_state_file)
+            .field("membership_state_file", &self.membership_state_file)
             // Redacted: show presence, never the value.
             .field(
                 "auth_token",
@@ -57,6 +57,10 @@ pub fn get_default_load_balancing() -> LoadBalancing {
     LoadBalancing {
         lb_type: String::from("round-robin"),
         key: None,
+        table_size: None,
+        hash_balance_factor: None,
+        p2c: None,
+        panic_threshold_percent: None,
     }
 }
 
@@ -83,6 +87,10 @@ pub fn security_default_group() -> String {
     String::from("nogroup")
 }
 
+pub fn locality_default_zone_env() -> String {
+    String::from("SPOOKY_ZONE")
+}
+
 pub fn auth_default_api_key_header_name() -> String {
     String::from("x-api-key")
 }
@@ -267,6 +275,22 @@ pub fn resilience_default_adaptive_high_latency_ms() -> u64 {
     500
 }
 
+pub fn resilience_default_adaptive_min_rtt_window_ms() -> u64 {
+    30_000
+}
+
+pub fn resilience_default_adaptive_rtt_tolerance_percent() -> u32 {
+    150
+}
+
+pub fn resilience_default_adaptive_smoothing_percent() -> u32 {
+    20
+}
+
+pub fn resilience_default_adaptive_queue_size() -> usize {
+    4
+}
+
 pub fn resilience_default_route_queue_default_cap() -> usize {
     512
 }
@@ -323,6 +347,50 @@ pub fn resilience_default_hedging_delay_ms() -> u64 {
     100
 }
 
+pub fn resilience_default_hedging_percentile() -> u8 {
+    95
+}
+
+pub fn resilience_default_hedging_min_delay_ms() -> u64 {
+    5
+}
+
+pub fn resilience_default_hedging_max_delay_ms() -> u64 {
+    1_000
+}
+
+pub fn resilience_default_hedging_min_samples() -> u64 {
+    50
+}
+
+pub fn resilience_default_hedging_window_ms() -> u64 {
+    10_000
+}
+
+pub fn resilience_default_hedging_max_hedged_percent() -> u8 {
+    100
+}
+
+pub fn resilience_default_hedging_max_hedges() -> u8 {
+    1
+}
+
+pub fn route_default_retry_backoff_base_ms() -> u64 {
+    25
+}
+
+pub fn route_default_retry_backoff_max_ms() -> u64 {
+    250
+}
+
+pub fn route_default_retry_after_max_ms() -> u64 {
+    1_000
+}
+
+pub fn route_default_idempotency_header() -> String {
+    "idempotency-key".to_string()
+}
+
 pub fn resilience_default_retry_budget_enabled() -> bool {
     true
 }
@@ -343,6 +411,14 @@ pub fn resilience_default_brownout_recover_inflight_percent() -> u8 {
     60
 }
 
+pub fn resilience_default_priority_default_class() -> String {
+    String::from("default")
+}
+
+pub fn resilience_default_priority_max_inflight_percent() -> u8 {
+    100
+}
+
 pub fn resilience_default_watchdog_enabled() -> bool {
     false
 }
@@ -471,6 +547,110 @@ pub fn upstream_tls_default_strict_sni() -> bool {
     true
 }
 
+pub fn route_default_rollout_steps() -> Vec<u32> {
+    vec![1, 5, 25, 100]
+}
+
+pub fn route_default_rollout_step_interval_ms() -> u64 {
+    300_000
+}
+
+pub fn route_default_rollout_min_requests() -> u64 {
+    100
+}
+
+pub fn route_default_rollout_max_error_rate_increase_percent() -> u32 {
+    1
+}
+
+pub fn route_default_rollout_max_p95_latency_ratio_percent() -> u32 {
+    150
+}
+
+pub fn route_default_redirect_status() -> u16 {
+    301
+}
+
+pub fn route_default_direct_response_status() -> u16 {
+    200
+}
+
+pub fn route_default_mirror_percent() -> u32 {
+    100
+}
+
+pub fn route_default_mirror_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+pub fn route_default_mirror_max_inflight() -> usize {
+    64
+}
+
+pub fn observe_default_control_api_rollouts_path() -> String {
+    String::from("/admin/rollouts")
+}
+
+pub fn observe_default_control_api_explain_path() -> String {
+    String::from("/admin/routes/explain")
+}
+
+pub fn observe_default_control_api_backends_path() -> String {
+    String::from("/admin/backends")
+}
+
+pub fn upstream_default_lb_p2c_choices() -> u32 {
+    2
+}
+
+pub fn upstream_default_lb_p2c_weighted() -> bool {
+    true
+}
+
+pub fn upstream_default_slow_start_min_weight_percent() -> u32 {
+    10
+}
+
+pub fn upstream_default_zone_aware_enabled() -> bool {
+    true
+}
+
+pub fn upstream_default_session_affinity_cookie_name() -> String {
+    String::from("spooky_affinity")
+}
+
+pub fn upstream_default_session_affinity_path() -> String {
+    String::from("/")
+}
+
+pub fn upstream_default_session_affinity_secure() -> bool {
+    true
+}
+
+pub fn upstream_default_outlier_interval_ms() -> u64 {
+    10_000
+}
+
+pub fn upstream_default_outlier_min_requests() -> u64 {
+    20
+}
+
+pub fn upstream_default_outlier_min_backends() -> usize {
+    3
+}
+
+pub fn upstream_default_outlier_max_ejection_percent() -> u32 {
+    10
+}
+
+pub fn upstream_default_outlier_base_ejection_ms() -> u64 {
+    30_000
+}
+
+pub fn upstream_default_outlier_max_ejection_ms() -> u64 {
+    300_000
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,12 +4,32 @@
 //! normalized outputs that downstream crates should consume. Interpreter-only
 //! shaping details stay internal to the runtime lowering modules.
 
-use std::{collections::HashMap, fmt, net::IpAddr};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    net::IpAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use regex::Regex;
 
-use crate::config::{
-    Backend, ClientAuth, Config, ForwardedHeaderPolicy, Listen, Observability, Performance,
-    ProtocolPolicy, Resilience, Security, TlsCertificate, Upstream, UpstreamHostPolicy,
-    UpstreamHostPolicyMode, UpstreamTls,
+use crate::{
+    config::{
+        Backend, CanaryRollout, ClientAuth, Config, CookieSameSite, DirectResponse,
+        ForwardedHeaderPolicy, Listen, MAX_DIRECT_RESPONSE_BODY_BYTES, MAX_MIRROR_BODY_BYTES,
+        MAX_ROUTE_RETRY_ATTEMPTS, Observability, OutlierDetection, PathRewrite, Performance,
+        Priority, ProtocolPolicy, RETRYABLE_STATUS_RANGE, Resilience, RetryCondition, RetryOn,
+        Route, RouteAuth, RouteFault, RouteMirror, RoutePolicy, RouteRedirect, Security,
+        SessionAffinity, SlowStart, SlowStartCurve, TlsCertificate, TrafficSplit, Upstream,
+        UpstreamHostPolicy, UpstreamHostPolicyMode, UpstreamTls, VALID_REDIRECT_STATUSES,
+        ZoneAware, effective_routes,
+    },
+    default::{
+        route_default_idempotency_header, route_default_retry_after_max_ms,
+        route_default_retry_backoff_base_ms, route_default_retry_backoff_max_ms,
+    },
 };
 
 mod listeners;
@@ -17,17 +37,17 @@ mod policies;
 mod upstreams;
 
 pub use self::policies::{
-    RuntimeAdmissionPolicy, RuntimeAlternateBackendPolicy, RuntimeApiKeyAuth, RuntimeAuthPolicy,
-    RuntimeBackendAddressKind, RuntimeBackendConnectionPolicy, RuntimeBackendDnsPolicy,
-    RuntimeBackendEndpoint, RuntimeBackendHealthCheck, RuntimeBackendTlsPolicy,
-    RuntimeBackendTransportKind, RuntimeBrownoutPolicy, RuntimeCircuitBreakerPolicy,
-    RuntimeConnectionLimits, RuntimeExternalAuth, RuntimeExternalAuthFailureMode,
-    RuntimeExternalAuthRequestHeader, RuntimeHedgingPolicy, RuntimeJwtAuth,
-    RuntimeListenerPolicySet, RuntimeLoadBalancingPolicy, RuntimeLoadBalancingStrategy,
-    RuntimePolicySet, RuntimeRateLimitPolicy, RuntimeRequestKeySpec, RuntimeRetryBudgetPolicy,
-    RuntimeRouteHostPattern, RuntimeRouteMatchPolicy, RuntimeRouteQueuePolicy,
-    RuntimeScopedRateLimitPolicy, RuntimeTimeoutPolicy, RuntimeTransportPolicy,
-    RuntimeWatchdogPolicy,
+    RuntimeAdaptiveAdmissionPolicy, RuntimeAdmissionPolicy, RuntimeAlternateBackendPolicy,
+    RuntimeApiKeyAuth, RuntimeAuthPolicy, RuntimeBackendAddressKind,
+    RuntimeBackendConnectionPolicy, RuntimeBackendDnsPolicy, RuntimeBackendEndpoint,
+    RuntimeBackendHealthCheck, RuntimeBackendTlsPolicy, RuntimeBackendTransportKind,
+    RuntimeBrownoutPolicy, RuntimeCircuitBreakerPolicy, RuntimeConnectionLimits,
+    RuntimeExternalAuth, RuntimeExternalAuthFailureMode, RuntimeExternalAuthRequestHeader,
+    RuntimeHedgingPolicy, RuntimeJwtAuth, RuntimeListenerPolicySet, RuntimeLoadBalancingPolicy,
+    RuntimeLoadBalancingStrategy, RuntimeP2cPolicy, RuntimePolicySet, RuntimeRateLimitPolicy,
+    RuntimeRequestKeySpec, RuntimeRetryBudgetPolicy, RuntimeRouteHostPattern,
+    RuntimeRouteMatchPolicy, RuntimeRouteQueuePolicy, RuntimeScopedRateLimitPolicy,
+    RuntimeTimeoutPolicy, RuntimeTransportPolicy, RuntimeWatchdogPolicy,
 };
 
 #[derive(Debug, Clone)]
@@ -35,6 +55,7 @@ pub struct RuntimeConfig {
     pub version: u32,
     pub listeners: Vec<RuntimeListener>,
     pub upstreams: HashMap<String, RuntimeUpstream>,
+    pub routes: HashMap<String, RuntimeRoute>,
     pub policies: RuntimePolicySet,
     pub performance: Performance,
     pub observability: Observability,
@@ -45,10 +66,14 @@ pub struct RuntimeConfig {
 impl RuntimeConfig {
     pub fn from_config(config: &Config) -> Result<Self, RuntimeConfigError> {
         let policies = RuntimePolicySet::from_config(config)?;
+        let upstreams = upstreams::normalize_upstreams(config, &policies)?;
+        let routes = upstreams::normalize_routes(config, &upstreams, &policies)?;
+        upstreams::validate_backend_origins(&upstreams)?;
         Ok(Self {
             version: config.version,
             listeners: listeners::runtime_listeners(config)?,
-            upstreams: upstreams::normalize_upstreams(config, &policies)?,
+            upstreams,
+            routes,
             policies,
             performance: config.performance.clone(),
             observability: config.observability.clone(),
@@ -86,11 +111,41 @@ impl RuntimeConfig {
             .collect()
     }
 
+    /// Upstream adaptive limiters, with `high_latency` capped at
+    /// `high_latency_limit`.
+    pub fn upstream_adaptive_admission(
+        &self,
+        high_latency_limit: Duration,
+    ) -> HashMap<String, RuntimeAdaptiveAdmissionPolicy> {
+        self.upstreams
+            .iter()
+            .filter_map(|(name, upstream)| {
+                let policy = upstream.adaptive_admission.as_ref()?;
+                Some((
+                    name.clone(),
+                    policy.with_high_latency_limit(high_latency_limit),
+                ))
+            })
+            .collect()
+    }
+
     pub fn policies(&self) -> RuntimePolicySet {
         self.policies.clone()
     }
 }
 
+/// Two routes that normalize to the same matcher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteAmbiguity {
+    pub route: String,
+    pub upstream: String,
+    pub existing_route: String,
+    pub existing_upstream: String,
+    pub host: Option<String>,
+    pub path_prefix: Option<String>,
+    pub method: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RuntimeConfigError {
     ConfigInvalid(String),
@@ -101,13 +156,7 @@ pub enum RuntimeConfigError {
         address: String,
         reason: String,
     },
-    DuplicateRouteAmbiguity {
-        upstream: String,
-        existing_upstream: String,
-        host: Option<String>,
-        path_prefix: Option<String>,
-        method: Option<String>,
-    },
+    DuplicateRouteAmbiguity(Box<RouteAmbiguity>),
     ListenerBindConflict {
         current: String,
         existing: String,
@@ -123,7 +172,7 @@ impl RuntimeConfigError {
             Self::ConfigInvalid(_) => "config_invalid",
             Self::TlsMaterialInvalid(_) => "tls_material_invalid",
             Self::BackendAddressInvalid { .. } => "backend_address_invalid",
-            Self::DuplicateRouteAmbiguity { .. } => "duplicate_route_ambiguity",
+            Self::DuplicateRouteAmbiguity(_) => "duplicate_route_ambiguity",
             Self::ListenerBindConflict { .. } => "listener_bind_conflict",
             Self::UnsupportedPolicyCombination(_) => "unsupported_policy_combination",
         }
@@ -152,21 +201,17 @@ impl fmt::Display for RuntimeConfigError {
                 address,
                 reason
             ),
-            Self::DuplicateRouteAmbiguity {
-                upstream,
-                existing_upstream,
-                host,
-                path_prefix,
-                method,
-            } => write!(
+            Self::DuplicateRouteAmbiguity(ambiguity) => write!(
                 f,
-                "{}: upstream '{}' conflicts with upstream '{}' for host={:?} path_prefix={:?} method={:?}",
+                "{}: route '{}' for upstream '{}' conflicts with upstream '{}' route '{}' for host={:?} path_prefix={:?} method={:?}",
                 self.category(),
-                upstream,
-                existing_upstream,
-                host,
-                path_prefix,
-                method
+                ambiguity.route,
+                ambiguity.upstream,
+                ambiguity.existing_upstream,
+                ambiguity.existing_route,
+                ambiguity.host,
+                ambiguity.path_prefix,
+                ambiguity.method
             ),
             Self::ListenerBindConflict {
                 current,
@@ -237,6 +282,14 @@ pub struct RuntimeUpstream {
     pub policy: RuntimeUpstreamPolicy,
     pub effective_tls: UpstreamTls,
     pub backends: Vec<RuntimeBackend>,
+    pub outlier_detection: Option<RuntimeOutlierDetection>,
+    pub slow_start: Option<RuntimeSlowStart>,
+    /// Instance zone and zone-aware settings; `None` when the zone is unknown.
+    pub locality: Option<RuntimeLocality>,
+    pub session_affinity: Option<RuntimeSessionAffinity>,
+    /// Upstream-scoped adaptive concurrency limiter, replacing the global one
+    /// for requests to this upstream.
+    pub adaptive_admission: Option<RuntimeAdaptiveAdmissionPolicy>,
     pub(crate) backend_tls_policy: RuntimeBackendTlsPolicy,
 }
 
@@ -246,6 +299,231 @@ impl RuntimeUpstream {
     }
 }
 
+/// Outlier detection lowered from `upstream.<name>.outlier_detection`. At
+/// least one of the three checks is enabled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeOutlierDetection {
+    pub interval: Duration,
+    pub min_requests: u64,
+    pub min_backends: usize,
+    pub success_rate_min_percent: Option<u32>,
+    pub success_rate_stdev_factor: Option<f64>,
+    pub latency_p95_ratio_percent: Option<u32>,
+    pub max_ejection_percent: u32,
+    pub base_ejection: Duration,
+    pub max_ejection: Duration,
+}
+
+/// Slow-start ramp lowered from `upstream.<name>.slow_start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeSlowStart {
+    pub window: Duration,
+    pub min_weight_percent: u32,
+    pub curve: SlowStartCurve,
+}
+
+/// Locality lowered from `locality` and `upstream.<name>.zone_aware`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeLocality {
+    /// Zone this instance runs in.
+    pub zone: String,
+    /// Prefer backends in `zone`; when false the zone only labels metrics.
+    pub zone_aware: bool,
+    /// Local in-flight requests per healthy local backend that spill traffic
+    /// to every zone.
+    pub max_inflight_per_backend: Option<u32>,
+}
+
+/// Cookie affinity lowered from `upstream.<name>.session_affinity`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeSessionAffinity {
+    pub cookie_name: String,
+    /// `None` issues session cookies.
+    pub ttl: Option<Duration>,
+    pub path: String,
+    pub same_site: CookieSameSite,
+    pub secure: bool,
+    /// Signing key; `None` leaves the choice of key to the edge.
+    pub secret: Option<String>,
+}
+
+/// Route lowered from `routes` or an `upstream.<name>.route` shorthand. The
+/// policy starts from the target upstream's policy with route overrides applied.
+/// Routes with an `action` have an empty `upstream` and never select a backend.
+#[derive(Debug, Clone)]
+pub struct RuntimeRoute {
+    pub name: String,
+    pub upstream: String,
+    pub route: RuntimeRouteMatchPolicy,
+    pub policy: RuntimeUpstreamPolicy,
+    pub split: Option<RuntimeTrafficSplit>,
+    pub action: Option<Arc<RuntimeRouteAction>>,
+}
+
+/// Response the edge generates for a route instead of forwarding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeRouteAction {
+    Redirect(RuntimeRedirect),
+    DirectResponse(RuntimeDirectResponse),
+}
+
+/// Redirect lowered from `routes[].redirect`. `path_rewrite` is the route's
+/// `rewrite`, applied to the request path when `path` is unset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeRedirect {
+    pub status: u16,
+    pub scheme: String,
+    pub host: Option<String>,
+    pub path: Option<String>,
+    pub path_rewrite: Option<RuntimePathRewrite>,
+    /// `Some("")` drops the request query; `None` keeps it.
+    pub query: Option<String>,
+}
+
+/// Fixed response lowered from `routes[].direct_response`; header names are
+/// lowercase and `body_file` has already been read into `body`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeDirectResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RuntimeRedirect {
+    /// Builds the `Location` for a request target (path plus query). The host
+    /// falls back to the request authority; without either the location is
+    /// relative.
+    pub fn location(&self, target: &str, authority: Option<&str>) -> String {
+        let (request_path, request_query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (target, None),
+        };
+        let request_path = if request_path.is_empty() {
+            "/"
+        } else {
+            request_path
+        };
+        let path = match (self.path.as_deref(), self.path_rewrite.as_ref()) {
+            (Some(path), _) => Cow::Borrowed(path),
+            (None, Some(rewrite)) => rewrite
+                .apply(request_path)
+                .map_or(Cow::Borrowed(request_path), |rewritten| {
+                    Cow::Owned(rewritten.path)
+                }),
+            (None, None) => Cow::Borrowed(request_path),
+        };
+
+        let mut location = String::new();
+        if let Some(host) = self
+            .host
+            .as_deref()
+            .or(authority)
+            .filter(|host| !host.is_empty())
+        {
+            location.push_str(&self.scheme);
+            location.push_str("://");
+            location.push_str(host);
+        }
+        location.push_str(&path);
+        if let Some(query) = self
+            .query
+            .as_deref()
+            .or(request_query)
+            .filter(|query| !query.is_empty())
+        {
+            location.push(if path.contains('?') { '&' } else { '?' });
+            location.push_str(query);
+        }
+        location
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeSplitTarget {
+    pub upstream: String,
+    pub weight: u32,
+}
+
+/// Weighted upstream split for a route. Targets keep config order so a given
+/// selection point maps to the same upstream across reloads with equal weights.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeTrafficSplit {
+    pub key_spec: Option<RuntimeRequestKeySpec>,
+    pub targets: Vec<RuntimeSplitTarget>,
+    pub total_weight: u64,
+    pub rollout: Option<RuntimeCanaryRollout>,
+}
+
+/// Canary rollout schedule lowered from `split.rollout`. `baselines` are the
+/// split targets other than `canary`, in config order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeCanaryRollout {
+    pub canary: String,
+    pub baselines: Vec<String>,
+    pub steps: Vec<u32>,
+    pub step_interval: Duration,
+    pub min_requests: u64,
+    pub max_error_rate_increase_percent: u32,
+    pub max_p95_latency_ratio_percent: u32,
+}
+
+impl RuntimeTrafficSplit {
+    /// Maps a selection point (a key hash or a random draw) onto a target by
+    /// cumulative weight.
+    pub fn pick(&self, point: u64) -> &str {
+        let mut slot = point % self.total_weight.max(1);
+        for target in &self.targets {
+            let weight = u64::from(target.weight);
+            if slot < weight {
+                return target.upstream.as_str();
+            }
+            slot -= weight;
+        }
+        self.targets
+            .first()
+            .map(|target| target.upstream.as_str())
+            .unwrap_or_default()
+    }
+
+    /// Like [`Self::pick`], but sends `canary_percent` percent of points to the
+    /// rollout canary and spreads the rest over the baselines by weight.
+    /// Splits without a rollout ignore `canary_percent`.
+    pub fn pick_with_canary_percent(&self, point: u64, canary_percent: u32) -> &str {
+        let Some(rollout) = self.rollout.as_ref() else {
+            return self.pick(point);
+        };
+        let baseline_weight = self
+            .targets
+            .iter()
+            .filter(|target| target.upstream != rollout.canary)
+            .map(|target| u64::from(target.weight))
+            .sum::<u64>()
+            .max(1);
+        let canary_slots = u64::from(canary_percent.min(100)) * baseline_weight;
+        let slot = point % (100 * baseline_weight);
+        if slot < canary_slots {
+            return rollout.canary.as_str();
+        }
+
+        let mut slot = (slot - canary_slots) % baseline_weight;
+        for target in &self.targets {
+            if target.upstream == rollout.canary {
+                continue;
+            }
+            let weight = u64::from(target.weight);
+            if slot < weight {
+                return target.upstream.as_str();
+            }
+            slot -= weight;
+        }
+        rollout
+            .baselines
+            .first()
+            .unwrap_or(&rollout.canary)
+            .as_str()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RuntimeBackend {
     pub backend: Backend,
@@ -262,13 +540,342 @@ pub struct RuntimeForwardedHeaderPolicy(pub ForwardedHeaderPolicy);
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeProtocolPolicy(pub ProtocolPolicy);
 
+/// Validated `resilience.priority` with names trimmed and header names
+/// lowercased. `classes` always contains the default class.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimePriorityPolicy(pub Priority);
+
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeUpstreamPolicy {
-    /// Upstream-owned auth policy selected after route lookup resolves an upstream.
+    /// Auth policy selected after route lookup; routes may override the upstream's.
     pub upstream_auth: RuntimeAuthPolicy,
     pub host: RuntimeHostPolicy,
     pub forwarded_headers: RuntimeForwardedHeaderPolicy,
     pub protocol: RuntimeProtocolPolicy,
+    /// Upstream path rewrite; only named routes set one.
+    pub path_rewrite: Option<RuntimePathRewrite>,
+    /// Shadow copy target; only named routes set one.
+    pub mirror: Option<Arc<RuntimeMirrorPolicy>>,
+    /// Injected faults; only named routes set one.
+    pub fault: Option<Arc<RuntimeFaultPolicy>>,
+    pub limits: RuntimeRouteLimits,
+}
+
+/// Request limits lowered from upstream and route `policy` blocks. Unset
+/// fields keep the listener-wide value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeRouteLimits {
+    pub backend_timeout: Option<Duration>,
+    pub backend_total_request_timeout: Option<Duration>,
+    pub max_request_body_bytes: Option<usize>,
+    pub max_retry_attempts: Option<u8>,
+    pub hedging_delay: Option<Duration>,
+    pub retry: RuntimeRetryPolicy,
+    /// Body replay for retries and hedges; `None` when disabled.
+    pub replay: Option<RuntimeReplayPolicy>,
+}
+
+/// Request body replay lowered from `policy.replay_body_max_bytes` and
+/// `policy.idempotency_header`. `idempotency_header` is lowercase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeReplayPolicy {
+    pub max_body_bytes: usize,
+    pub idempotency_header: String,
+}
+
+/// Retry triggers and pacing lowered from `policy.retry_on` and the backoff
+/// fields. `statuses` is sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeRetryPolicy {
+    pub statuses: Vec<u16>,
+    pub reset_before_headers: bool,
+    pub connect_failure: bool,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    pub retry_after_max: Duration,
+}
+
+impl Default for RuntimeRetryPolicy {
+    fn default() -> Self {
+        Self {
+            statuses: Vec::new(),
+            reset_before_headers: true,
+            connect_failure: false,
+            backoff_base: Duration::from_millis(route_default_retry_backoff_base_ms()),
+            backoff_max: Duration::from_millis(route_default_retry_backoff_max_ms()),
+            retry_after_max: Duration::from_millis(route_default_retry_after_max_ms()),
+        }
+    }
+}
+
+impl RuntimeRetryPolicy {
+    pub fn retries_status(&self, status: u16) -> bool {
+        self.statuses.binary_search(&status).is_ok()
+    }
+
+    /// Wait before retry number `retry` (1-based): full jitter below
+    /// `backoff_base * 2^(retry - 1)`, capped at `backoff_max`. `point` is a
+    /// random draw.
+    pub fn backoff(&self, retry: u8, point: u64) -> Duration {
+        let shift = u32::from(retry.saturating_sub(1)).min(16);
+        let base_ms = u64::try_from(self.backoff_base.as_millis()).unwrap_or(u64::MAX);
+        let max_ms = u64::try_from(self.backoff_max.as_millis()).unwrap_or(u64::MAX);
+        let cap_ms = base_ms.saturating_mul(1 << shift).min(max_ms);
+        if cap_ms == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(point % (cap_ms + 1))
+    }
+
+    /// Parses a delta-seconds `Retry-After` value. HTTP dates are ignored.
+    pub fn parse_retry_after(value: &str) -> Option<Duration> {
+        value.trim().parse::<u64>().ok().map(Duration::from_secs)
+    }
+}
+
+/// Request mirroring lowered from `routes[].mirror`. `methods` are uppercase;
+/// empty mirrors every method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeMirrorPolicy {
+    pub upstream: String,
+    pub percent: u32,
+    pub methods: Vec<String>,
+    pub max_body_bytes: usize,
+    pub max_inflight: usize,
+}
+
+impl RuntimeMirrorPolicy {
+    /// True when a request with `method` is sampled for mirroring. `point` is a
+    /// random draw; the sample keeps `percent` of 100 slots.
+    pub fn samples(&self, method: &str, point: u64) -> bool {
+        let method_allowed = self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method));
+        method_allowed && point % 100 < u64::from(self.percent)
+    }
+
+    /// True when a request body of `declared_length` bytes may be buffered
+    /// for the copy. Bodies without a declared length are never mirrored.
+    pub fn body_fits(&self, declared_length: Option<usize>) -> bool {
+        declared_length.is_some_and(|length| length <= self.max_body_bytes)
+    }
+}
+
+/// Fault injection lowered from `routes[].fault`. The header gate name is
+/// lowercase; faults with a zero percent are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeFaultPolicy {
+    pub header: Option<RuntimeFaultHeader>,
+    pub delay: Option<RuntimeFaultDelay>,
+    pub abort: Option<RuntimeFaultAbort>,
+    pub truncate: Option<RuntimeFaultTruncate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeFaultHeader {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFaultDelay {
+    pub percent: u32,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFaultAbort {
+    pub percent: u32,
+    pub action: RuntimeFaultAbortAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeFaultAbortAction {
+    Status(u16),
+    Reset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFaultTruncate {
+    pub percent: u32,
+    pub after_bytes: usize,
+}
+
+/// Faults drawn for one request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeFaultSample {
+    pub delay: Option<Duration>,
+    pub abort: Option<RuntimeFaultAbortAction>,
+    pub truncate_after_bytes: Option<usize>,
+}
+
+impl RuntimeFaultSample {
+    pub fn is_empty(&self) -> bool {
+        self.delay.is_none() && self.abort.is_none() && self.truncate_after_bytes.is_none()
+    }
+}
+
+impl RuntimeFaultPolicy {
+    /// True when a request whose gate header has `value` is eligible. Routes
+    /// without a gate admit every request.
+    pub fn admits(&self, value: Option<&str>) -> bool {
+        match (self.header.as_ref(), value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(header), Some(value)) => header.value.as_deref().is_none_or(|want| want == value),
+        }
+    }
+
+    /// Draws the faults for one request; `draw` yields random points. Each
+    /// fault keeps `percent` of 100 slots, and an abort clears the others.
+    pub fn sample(&self, mut draw: impl FnMut() -> u64) -> RuntimeFaultSample {
+        let mut hit = |percent: u32| draw() % 100 < u64::from(percent);
+        if let Some(abort) = self.abort.filter(|abort| hit(abort.percent)) {
+            return RuntimeFaultSample {
+                abort: Some(abort.action),
+                ..RuntimeFaultSample::default()
+            };
+        }
+        let delay = self.delay.filter(|delay| hit(delay.percent));
+        let truncate_after_bytes = self
+            .truncate
+            .filter(|truncate| hit(truncate.percent))
+            .map(|truncate| truncate.after_bytes);
+        let delay = delay.map(|delay| {
+            let spread = (delay.max - delay.min).as_millis() as u64;
+            if spread == 0 {
+                delay.min
+            } else {
+                delay.min + Duration::from_millis(draw() % (spread + 1))
+            }
+        });
+        RuntimeFaultSample {
+            delay,
+            abort: None,
+            truncate_after_bytes,
+        }
+    }
+}
+
+/// Path rewrite lowered from `routes[].rewrite`. Prefixes are stored without a
+/// trailing `/` and only match on path-segment boundaries.
+#[derive(Debug, Clone)]
+pub enum RuntimePathRewrite {
+    StripPrefix(String),
+    ReplacePrefix {
+        prefix: String,
+        replacement: String,
+    },
+    Regex {
+        pattern: Regex,
+        substitution: String,
+    },
+}
+
+impl PartialEq for RuntimePathRewrite {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::StripPrefix(left), Self::StripPrefix(right)) => left == right,
+            (
+                Self::ReplacePrefix {
+                    prefix: left_prefix,
+                    replacement: left_replacement,
+                },
+                Self::ReplacePrefix {
+                    prefix: right_prefix,
+                    replacement: right_replacement,
+                },
+            ) => left_prefix == right_prefix && left_replacement == right_replacement,
+            (
+                Self::Regex {
+                    pattern: left_pattern,
+                    substitution: left_substitution,
+                },
+                Self::Regex {
+                    pattern: right_pattern,
+                    substitution: right_substitution,
+                },
+            ) => {
+                left_pattern.as_str() == right_pattern.as_str()
+                    && left_substitution == right_substitution
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for RuntimePathRewrite {}
+
+/// Upstream request target produced by [`RuntimePathRewrite::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewrittenPath<'a> {
+    pub path: String,
+    /// Client-side prefix the rewrite removed, advertised upstream as
+    /// `X-Forwarded-Prefix`. Regex rewrites have none.
+    pub forwarded_prefix: Option<&'a str>,
+}
+
+impl RuntimePathRewrite {
+    /// Rewrites the path part of a request target and carries its query string
+    /// over. Returns `None` when the rule does not match, in which case the
+    /// client path is forwarded unchanged.
+    pub fn apply(&self, target: &str) -> Option<RewrittenPath<'_>> {
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (target, None),
+        };
+        let (rewritten, forwarded_prefix) = match self {
+            Self::StripPrefix(prefix) => (
+                replace_path_prefix(path, prefix, "")?,
+                Some(prefix.as_str()),
+            ),
+            Self::ReplacePrefix {
+                prefix,
+                replacement,
+            } => (
+                replace_path_prefix(path, prefix, replacement)?,
+                Some(prefix.as_str()),
+            ),
+            Self::Regex {
+                pattern,
+                substitution,
+            } => {
+                if !pattern.is_match(path) {
+                    return None;
+                }
+                (
+                    pattern.replace(path, substitution.as_str()).into_owned(),
+                    None,
+                )
+            }
+        };
+
+        let mut path = if rewritten.starts_with('/') {
+            rewritten
+        } else {
+            format!("/{rewritten}")
+        };
+        if let Some(query) = query {
+            // A regex substitution may already have produced a query string.
+            path.push(if path.contains('?') { '&' } else { '?' });
+            path.push_str(query);
+        }
+        Some(RewrittenPath {
+            path,
+            forwarded_prefix,
+        })
+    }
+}
+
+fn replace_path_prefix(path: &str, prefix: &str, replacement: &str) -> Option<String> {
+    let rest = path.strip_prefix(prefix)?;
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return None;
+    }
+    Some(format!("{}{}", replacement.trim_end_matches('/'), rest))
 }
 
 // NOTE: Public-API `RuntimeConfig::from_config` contract tests live in the
@@ -278,8 +885,6 @@ pub struct RuntimeUpstreamPolicy {
 // helper — which are not reachable from an external integration-test crate.
 #[cfg(test)]
 mod tests {
-    use std::time::Duration;
-
     use super::{listeners::runtime_listeners, *};
     use crate::config::{
         Config, ForwardedHeaderPolicyMode, Listen, LoadBalancing, RouteMatch, Tls, TlsCertificate,
@@ -302,6 +907,7 @@ mod tests {
             },
             listeners: Vec::new(),
             upstream: HashMap::new(),
+            routes: Vec::new(),
             load_balancing: None,
             upstream_tls: UpstreamTls::default(),
             log: crate::config::Log::default(),
@@ -309,6 +915,7 @@ mod tests {
             observability: Observability::default(),
             resilience: Resilience::default(),
             security: Security::default(),
+            locality: Default::default(),
         };
 
         config.upstream.insert(
@@ -317,12 +924,22 @@ mod tests {
                 load_balancing: LoadBalancing {
                     lb_type: "round-robin".to_string(),
                     key: None,
+                    table_size: None,
+                    hash_balance_factor: None,
+                    p2c: None,
+                    panic_threshold_percent: None,
                 },
                 auth: Default::default(),
                 host_policy: UpstreamHostPolicy {
                     mode: UpstreamHostPolicyMode::Rewrite,
                     host: Some("api.internal".to_string()),
                 },
+                policy: Default::default(),
+                outlier_detection: None,
+                slow_start: None,
+                zone_aware: None,
+                session_affinity: None,
+                adaptive_admission: None,
                 forwarded_headers: ForwardedHeaderPolicy {
                     mode: ForwardedHeaderPolicyMode::Append,
                 },
@@ -337,6 +954,8 @@ mod tests {
                     address: "https://api.internal:8443".to_string(),
                     weight: 100,
                     health_check: None,
+                    priority: 0,
+                    zone: None,
                 }],
             },
         );
@@ -351,6 +970,10 @@ mod tests {
         upstream.load_balancing = LoadBalancing {
             lb_type: "sticky-cid".to_string(),
             key: Some("header:x-user-id".to_string()),
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         };
         upstream.auth.api_key = Some(crate::config::ApiKeyAuth {
             header_name: "x-api-key".to_string(),
@@ -414,6 +1037,10 @@ mod tests {
         upstream.load_balancing = LoadBalancing {
             lb_type: "cid_sticky".to_string(),
             key: Some("header:x-user-id".to_string()),
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         };
         upstream.route = RouteMatch {
             host: Some("API.EXAMPLE.COM:443.".to_string()),
@@ -8,8 +8,11 @@ use super::{
     watchdog::normalize_watchdog_policy,
 };
 use crate::{
-    config::Resilience,
-    runtime::{RuntimeConfigError, RuntimeProtocolPolicy},
+    config::{
+        AdaptiveAdmission, AdaptiveAdmissionAlgorithm, Priority, PriorityClass, PriorityRule,
+        Resilience,
+    },
+    runtime::{RuntimeConfigError, RuntimePriorityPolicy, RuntimeProtocolPolicy},
 };
 
 fn require_nonzero_usize(name: &str, value: usize) -> Result<(), RuntimeConfigError> {
@@ -187,11 +190,113 @@ impl RuntimeScopedRateLimitPolicy {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeAdaptiveAdmissionPolicy {
     pub enabled: bool,
+    pub algorithm: AdaptiveAdmissionAlgorithm,
     pub min_limit: usize,
     pub max_limit: usize,
     pub decrease_step: usize,
     pub increase_step: usize,
     pub high_latency: Duration,
+    pub min_rtt_window: Duration,
+    pub rtt_tolerance_percent: u32,
+    pub smoothing_percent: u32,
+    pub queue_size: usize,
+}
+
+impl RuntimeAdaptiveAdmissionPolicy {
+    /// Normalizes the limiter at `scope`; `max_limit` defaults to, and may not
+    /// exceed, the `limit_name` inflight limit.
+    pub(crate) fn normalize(
+        scope: &str,
+        adaptive: &AdaptiveAdmission,
+        limit_name: &str,
+        inflight_limit: usize,
+    ) -> Result<Self, RuntimeConfigError> {
+        if adaptive.min_limit == 0 {
+            return Err(config_invalid(format!(
+                "{scope}.min_limit must be greater than 0"
+            )));
+        }
+        if let Some(max_limit) = adaptive.max_limit {
+            if max_limit == 0 {
+                return Err(config_invalid(format!(
+                    "{scope}.max_limit must be greater than 0"
+                )));
+            }
+            if max_limit < adaptive.min_limit {
+                return Err(config_invalid(format!(
+                    "{scope}.max_limit ({}) must be >= min_limit ({})",
+                    max_limit, adaptive.min_limit
+                )));
+            }
+            if max_limit > inflight_limit {
+                return Err(config_invalid(format!(
+                    "{scope}.max_limit ({}) must be <= {limit_name} ({})",
+                    max_limit, inflight_limit
+                )));
+            }
+        }
+        require_nonzero_usize(&format!("{scope}.decrease_step"), adaptive.decrease_step)?;
+        require_nonzero_usize(&format!("{scope}.increase_step"), adaptive.increase_step)?;
+        if adaptive.algorithm == AdaptiveAdmissionAlgorithm::Gradient {
+            if adaptive.min_rtt_window_ms == 0 {
+                return Err(config_invalid(format!(
+                    "{scope}.min_rtt_window_ms must be greater than 0"
+                )));
+            }
+            if adaptive.rtt_tolerance_percent < 100 {
+                return Err(config_invalid(format!(
+                    "{scope}.rtt_tolerance_percent must be >= 100"
+                )));
+            }
+            if !(1..=100).contains(&adaptive.smoothing_percent) {
+                return Err(config_invalid(format!(
+                    "{scope}.smoothing_percent must be in range 1-100"
+                )));
+            }
+            require_nonzero_usize(&format!("{scope}.queue_size"), adaptive.queue_size)?;
+        }
+
+        Ok(Self {
+            enabled: adaptive.enabled,
+            algorithm: adaptive.algorithm,
+            min_limit: adaptive.min_limit,
+            max_limit: adaptive
+                .max_limit
+                .unwrap_or(inflight_limit)
+                .max(adaptive.min_limit),
+            decrease_step: adaptive.decrease_step,
+            increase_step: adaptive.increase_step,
+            high_latency: Duration::from_millis(adaptive.high_latency_ms),
+            min_rtt_window: Duration::from_millis(adaptive.min_rtt_window_ms),
+            rtt_tolerance_percent: adaptive.rtt_tolerance_percent,
+            smoothing_percent: adaptive.smoothing_percent,
+            queue_size: adaptive.queue_size,
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn as_config(&self) -> AdaptiveAdmission {
+        AdaptiveAdmission {
+            enabled: self.enabled,
+            min_limit: self.min_limit,
+            max_limit: Some(self.max_limit),
+            decrease_step: self.decrease_step,
+            increase_step: self.increase_step,
+            high_latency_ms: u64::try_from(self.high_latency.as_millis()).unwrap_or(u64::MAX),
+            algorithm: self.algorithm,
+            min_rtt_window_ms: u64::try_from(self.min_rtt_window.as_millis()).unwrap_or(u64::MAX),
+            rtt_tolerance_percent: self.rtt_tolerance_percent,
+            smoothing_percent: self.smoothing_percent,
+            queue_size: self.queue_size,
+        }
+    }
+
+    /// Caps `high_latency` at `limit`.
+    pub fn with_high_latency_limit(&self, limit: Duration) -> Self {
+        let mut updated = self.clone();
+        updated.high_latency = updated.high_latency.min(limit);
+        updated
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -222,6 +327,104 @@ pub struct RuntimeBrownoutPolicy {
     pub core_routes: Vec<String>,
 }
 
+fn normalize_priority_policy(
+    priority: &Priority,
+) -> Result<RuntimePriorityPolicy, RuntimeConfigError> {
+    let default_class = priority.default_class.trim().to_string();
+    if default_class.is_empty() {
+        return Err(config_invalid(
+            "resilience.priority.default_class must be non-empty",
+        ));
+    }
+
+    let mut classes = Vec::with_capacity(priority.classes.len().max(1));
+    let mut seen_names = std::collections::HashSet::new();
+    for class in &priority.classes {
+        let name = class.name.trim().to_string();
+        if name.is_empty() {
+            return Err(config_invalid(
+                "resilience.priority.classes[].name must be non-empty",
+            ));
+        }
+        if !seen_names.insert(name.clone()) {
+            return Err(config_invalid(format!(
+                "resilience.priority.classes contains duplicate class '{name}'"
+            )));
+        }
+        if !(1..=100).contains(&class.max_inflight_percent) {
+            return Err(config_invalid(format!(
+                "resilience.priority.classes['{name}'].max_inflight_percent must be in range 1-100"
+            )));
+        }
+        classes.push(PriorityClass {
+            name,
+            ..class.clone()
+        });
+    }
+    if classes.is_empty() {
+        seen_names.insert(default_class.clone());
+        classes.push(PriorityClass {
+            name: default_class.clone(),
+            max_inflight_percent: 100,
+            brownout_exempt: false,
+        });
+    } else if !seen_names.contains(&default_class) {
+        return Err(config_invalid(format!(
+            "resilience.priority.default_class '{default_class}' is not a configured class"
+        )));
+    }
+
+    let mut rules = Vec::with_capacity(priority.rules.len());
+    for (index, rule) in priority.rules.iter().enumerate() {
+        let scope = format!("resilience.priority.rules[{index}]");
+        let class = rule.class.trim().to_string();
+        if !seen_names.contains(&class) {
+            return Err(config_invalid(format!(
+                "{scope}.class '{class}' is not a configured class"
+            )));
+        }
+        let routes = normalize_nonempty_string_vec(&format!("{scope}.routes"), &rule.routes)?;
+        let header = normalize_optional_string(rule.header.as_deref())
+            .map(|header| header.to_ascii_lowercase());
+        let jwt_claim = normalize_optional_string(rule.jwt_claim.as_deref());
+        let values = normalize_nonempty_string_vec(&format!("{scope}.values"), &rule.values)?;
+        if header.is_some() && jwt_claim.is_some() {
+            return Err(config_invalid(format!(
+                "{scope} must set only one of header or jwt_claim"
+            )));
+        }
+        if header.is_none() && jwt_claim.is_none() {
+            if routes.is_empty() {
+                return Err(config_invalid(format!(
+                    "{scope} must match on routes, header or jwt_claim"
+                )));
+            }
+            if !values.is_empty() {
+                return Err(config_invalid(format!(
+                    "{scope}.values requires header or jwt_claim"
+                )));
+            }
+        } else if values.is_empty() {
+            return Err(config_invalid(format!(
+                "{scope}.values must be non-empty when header or jwt_claim is set"
+            )));
+        }
+        rules.push(PriorityRule {
+            class,
+            routes,
+            header,
+            jwt_claim,
+            values,
+        });
+    }
+
+    Ok(RuntimePriorityPolicy(Priority {
+        default_class,
+        classes,
+        rules,
+    }))
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct RuntimeRateLimitPolicy {
     pub scoped_limits: Vec<RuntimeScopedRateLimitPolicy>,
@@ -254,6 +457,7 @@ pub struct RuntimeAdmissionPolicy {
     pub hedging: super::RuntimeHedgingPolicy,
     pub retry_budget: super::RuntimeRetryBudgetPolicy,
     pub brownout: RuntimeBrownoutPolicy,
+    pub priority: RuntimePriorityPolicy,
     pub watchdog: super::RuntimeWatchdogPolicy,
     pub protocol: RuntimeProtocolPolicy,
 }
@@ -263,37 +467,11 @@ impl RuntimeAdmissionPolicy {
         resilience: &Resilience,
         global_inflight_limit: usize,
     ) -> Result<Self, RuntimeConfigError> {
-        if resilience.adaptive_admission.min_limit == 0 {
-            return Err(config_invalid(
-                "resilience.adaptive_admission.min_limit must be greater than 0",
-            ));
-        }
-        if let Some(max_limit) = resilience.adaptive_admission.max_limit {
-            if max_limit == 0 {
-                return Err(config_invalid(
-                    "resilience.adaptive_admission.max_limit must be greater than 0",
-                ));
-            }
-            if max_limit < resilience.adaptive_admission.min_limit {
-                return Err(config_invalid(format!(
-                    "resilience.adaptive_admission.max_limit ({}) must be >= min_limit ({})",
-                    max_limit, resilience.adaptive_admission.min_limit
-                )));
-            }
-            if max_limit > global_inflight_limit {
-                return Err(config_invalid(format!(
-                    "resilience.adaptive_admission.max_limit ({}) must be <= performance.global_inflight_limit ({})",
-                    max_limit, global_inflight_limit
-                )));
-            }
-        }
-        require_nonzero_usize(
-            "resilience.adaptive_admission.decrease_step",
-            resilience.adaptive_admission.decrease_step,
-        )?;
-        require_nonzero_usize(
-            "resilience.adaptive_admission.increase_step",
-            resilience.adaptive_admission.increase_step,
+        let adaptive_admission = RuntimeAdaptiveAdmissionPolicy::normalize(
+            "resilience.adaptive_admission",
+            &resilience.adaptive_admission,
+            "performance.global_inflight_limit",
+            global_inflight_limit,
         )?;
 
         require_nonzero_usize(
@@ -403,18 +581,7 @@ impl RuntimeAdmissionPolicy {
         let hedging_route_allowlist = normalize_string_vec(&resilience.hedging.route_allowlist);
 
         Ok(Self {
-            adaptive_admission: RuntimeAdaptiveAdmissionPolicy {
-                enabled: resilience.adaptive_admission.enabled,
-                min_limit: resilience.adaptive_admission.min_limit,
-                max_limit: resilience
-                    .adaptive_admission
-                    .max_limit
-                    .unwrap_or(global_inflight_limit)
-                    .max(resilience.adaptive_admission.min_limit),
-                decrease_step: resilience.adaptive_admission.decrease_step,
-                increase_step: resilience.adaptive_admission.increase_step,
-                high_latency: Duration::from_millis(resilience.adaptive_admission.high_latency_ms),
-            },
+            adaptive_admission,
             route_queue: RuntimeRouteQueuePolicy {
                 default_cap: resilience.route_queue.default_cap,
                 global_cap: resilience.route_queue.global_cap,
@@ -434,6 +601,7 @@ impl RuntimeAdmissionPolicy {
                 recover_inflight_percent: resilience.brownout.recover_inflight_percent,
                 core_routes: normalize_string_vec(&resilience.brownout.core_routes),
             },
+            priority: normalize_priority_policy(&resilience.priority)?,
             watchdog: normalize_watchdog_policy(resilience)?,
             protocol: RuntimeProtocolPolicy(protocol),
         })
@@ -449,9 +617,9 @@ impl RuntimeAdmissionPolicy {
         updated.route_queue = updated
             .route_queue
             .clamped(default_route_cap_limit, global_route_cap_limit);
-        if updated.adaptive_admission.high_latency > adaptive_high_latency_limit {
-            updated.adaptive_admission.high_latency = adaptive_high_latency_limit;
-        }
+        updated.adaptive_admission = updated
+            .adaptive_admission
+            .with_high_latency_limit(adaptive_high_latency_limit);
         updated
     }
 }
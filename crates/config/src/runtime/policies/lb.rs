@@ -1,25 +1,46 @@
 use super::{config_invalid, normalize_optional_string};
-use crate::{config::LoadBalancing, runtime::RuntimeConfigError};
+use crate::{
+    config::{
+        DEFAULT_HASH_BALANCE_FACTOR, DEFAULT_MAGLEV_TABLE_SIZE, LoadBalancing,
+        MAX_MAGLEV_TABLE_SIZE, MAX_P2C_CHOICES, P2cMetric, PowerOfTwoChoices, custom_lb_name,
+        is_prime,
+    },
+    runtime::RuntimeConfigError,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuntimeLoadBalancingStrategy {
     RoundRobin,
     ConsistentHash,
+    BoundedConsistentHash,
+    Maglev,
     Random,
     LeastConnections,
     LatencyAware,
+    P2c,
     StickyCid,
+    /// A strategy registered by the embedding binary; the name lives in
+    /// [`RuntimeLoadBalancingPolicy::custom`].
+    Custom,
     Other,
 }
 
 impl RuntimeLoadBalancingStrategy {
     pub fn from_lb_type(lb_type: &str) -> Self {
+        if custom_lb_name(lb_type).is_some() {
+            return Self::Custom;
+        }
         match lb_type.trim().to_ascii_lowercase().as_str() {
             "round-robin" | "round_robin" | "rr" => Self::RoundRobin,
             "consistent-hash" | "consistent_hash" | "ch" => Self::ConsistentHash,
+            "consistent-hash-bounded" | "consistent_hash_bounded" | "chb" => {
+                Self::BoundedConsistentHash
+            }
+            "maglev" => Self::Maglev,
             "random" => Self::Random,
             "least-connections" | "least_connections" | "lc" => Self::LeastConnections,
             "latency-aware" | "latency_aware" | "la" => Self::LatencyAware,
+            "p2c" | "power-of-two-choices" | "power_of_two_choices" => Self::P2c,
             "sticky-cid" | "sticky_cid" | "cid-sticky" | "cid_sticky" => Self::StickyCid,
             _ => Self::Other,
         }
@@ -29,10 +50,14 @@ impl RuntimeLoadBalancingStrategy {
         match self {
             Self::RoundRobin => "round-robin",
             Self::ConsistentHash => "consistent-hash",
+            Self::BoundedConsistentHash => "consistent-hash-bounded",
+            Self::Maglev => "maglev",
             Self::Random => "random",
             Self::LeastConnections => "least-connections",
             Self::LatencyAware => "latency-aware",
+            Self::P2c => "p2c",
             Self::StickyCid => "sticky-cid",
+            Self::Custom => "custom",
             Self::Other => "unsupported",
         }
     }
@@ -102,12 +127,54 @@ pub struct RuntimeAlternateBackendPolicy {
     pub healthy_fallback: bool,
 }
 
+/// Sampling settings for the `p2c` strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeP2cPolicy {
+    pub choices: usize,
+    pub metric: P2cMetric,
+    pub weighted: bool,
+}
+
+impl RuntimeP2cPolicy {
+    fn normalize(p2c: &PowerOfTwoChoices) -> Result<Self, RuntimeConfigError> {
+        if !(2..=MAX_P2C_CHOICES).contains(&p2c.choices) {
+            return Err(config_invalid(format!(
+                "load_balancing.p2c.choices ({}) must be in range 2-{MAX_P2C_CHOICES}",
+                p2c.choices
+            )));
+        }
+        Ok(Self {
+            choices: p2c.choices as usize,
+            metric: p2c.metric,
+            weighted: p2c.weighted,
+        })
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> PowerOfTwoChoices {
+        PowerOfTwoChoices {
+            choices: self.choices as u32,
+            metric: self.metric,
+            weighted: self.weighted,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeLoadBalancingPolicy {
     pub strategy: RuntimeLoadBalancingStrategy,
     pub key: Option<String>,
     pub key_spec: Option<RuntimeRequestKeySpec>,
     pub alternate_backend: RuntimeAlternateBackendPolicy,
+    /// Maglev lookup table entries; a prime.
+    pub table_size: u64,
+    /// Bounded-load cap as a percentage of a backend's weighted share.
+    pub hash_balance_factor: u32,
+    pub p2c: RuntimeP2cPolicy,
+    /// Healthy percentage below which picks ignore health; `0` never panics.
+    pub panic_threshold_percent: u32,
+    /// Registered balancer name for the `custom` strategy.
+    pub custom: Option<String>,
 }
 
 impl RuntimeLoadBalancingPolicy {
@@ -120,6 +187,42 @@ impl RuntimeLoadBalancingPolicy {
             )));
         }
 
+        if let Some(table_size) = load_balancing.table_size {
+            if strategy != RuntimeLoadBalancingStrategy::Maglev {
+                return Err(config_invalid(
+                    "load_balancing.table_size only applies to 'maglev'",
+                ));
+            }
+            if table_size > MAX_MAGLEV_TABLE_SIZE || !is_prime(table_size) {
+                return Err(config_invalid(format!(
+                    "load_balancing.table_size ({table_size}) must be a prime no larger than {MAX_MAGLEV_TABLE_SIZE}"
+                )));
+            }
+        }
+        if let Some(factor) = load_balancing.hash_balance_factor {
+            if strategy != RuntimeLoadBalancingStrategy::BoundedConsistentHash {
+                return Err(config_invalid(
+                    "load_balancing.hash_balance_factor only applies to 'consistent-hash-bounded'",
+                ));
+            }
+            if factor < 100 {
+                return Err(config_invalid(format!(
+                    "load_balancing.hash_balance_factor ({factor}) must be at least 100"
+                )));
+            }
+        }
+
+        if load_balancing.p2c.is_some() && strategy != RuntimeLoadBalancingStrategy::P2c {
+            return Err(config_invalid("load_balancing.p2c only applies to 'p2c'"));
+        }
+        let p2c = RuntimeP2cPolicy::normalize(&load_balancing.p2c.clone().unwrap_or_default())?;
+        let panic_threshold_percent = load_balancing.panic_threshold_percent.unwrap_or(0);
+        if panic_threshold_percent > 100 {
+            return Err(config_invalid(format!(
+                "load_balancing.panic_threshold_percent ({panic_threshold_percent}) must be in range 0-100"
+            )));
+        }
+
         Ok(Self {
             strategy,
             key: normalize_optional_string(load_balancing.key.as_deref()),
@@ -132,14 +235,42 @@ impl RuntimeLoadBalancingPolicy {
                 readonly_lb_pick: strategy.supports_readonly_alternate_pick(),
                 healthy_fallback: true,
             },
+            table_size: load_balancing
+                .table_size
+                .unwrap_or(DEFAULT_MAGLEV_TABLE_SIZE),
+            hash_balance_factor: load_balancing
+                .hash_balance_factor
+                .unwrap_or(DEFAULT_HASH_BALANCE_FACTOR),
+            p2c,
+            panic_threshold_percent,
+            custom: custom_lb_name(&load_balancing.lb_type).map(str::to_string),
         })
     }
 
+    /// Strategy name for logs and diagnostics: the registered name for
+    /// custom strategies, the canonical one otherwise.
+    pub fn strategy_name(&self) -> &str {
+        self.custom
+            .as_deref()
+            .unwrap_or_else(|| self.strategy.canonical_name())
+    }
+
     #[cfg(test)]
     pub(crate) fn as_config(&self) -> LoadBalancing {
         LoadBalancing {
-            lb_type: self.strategy.canonical_name().to_string(),
+            lb_type: match &self.custom {
+                Some(name) => format!("{}{name}", crate::config::CUSTOM_LB_TYPE_PREFIX),
+                None => self.strategy.canonical_name().to_string(),
+            },
             key: self.key.clone(),
+            table_size: (self.strategy == RuntimeLoadBalancingStrategy::Maglev)
+                .then_some(self.table_size),
+            hash_balance_factor: (self.strategy
+                == RuntimeLoadBalancingStrategy::BoundedConsistentHash)
+                .then_some(self.hash_balance_factor),
+            p2c: (self.strategy == RuntimeLoadBalancingStrategy::P2c).then(|| self.p2c.as_config()),
+            panic_threshold_percent: (self.panic_threshold_percent > 0)
+                .then_some(self.panic_threshold_percent),
         }
     }
 }
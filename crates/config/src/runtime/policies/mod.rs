@@ -15,8 +15,8 @@ mod watchdog;
 
 pub use self::{
     admission::{
-        RuntimeAdmissionPolicy, RuntimeBrownoutPolicy, RuntimeRateLimitPolicy,
-        RuntimeRouteQueuePolicy, RuntimeScopedRateLimitPolicy,
+        RuntimeAdaptiveAdmissionPolicy, RuntimeAdmissionPolicy, RuntimeBrownoutPolicy,
+        RuntimeRateLimitPolicy, RuntimeRouteQueuePolicy, RuntimeScopedRateLimitPolicy,
     },
     auth::{
         RuntimeApiKeyAuth, RuntimeAuthPolicy, RuntimeExternalAuth, RuntimeExternalAuthFailureMode,
@@ -28,7 +28,7 @@ pub use self::{
     },
     lb::{
         RuntimeAlternateBackendPolicy, RuntimeLoadBalancingPolicy, RuntimeLoadBalancingStrategy,
-        RuntimeRequestKeySpec,
+        RuntimeP2cPolicy, RuntimeRequestKeySpec,
     },
     resilience::{RuntimeCircuitBreakerPolicy, RuntimeHedgingPolicy, RuntimeRetryBudgetPolicy},
     timeouts::RuntimeTimeoutPolicy,
@@ -104,7 +104,7 @@ pub struct RuntimeRouteMatchPolicy {
 
 impl RuntimeRouteMatchPolicy {
     pub(crate) fn normalize(
-        upstream_name: &str,
+        scope: &str,
         route: &crate::config::RouteMatch,
     ) -> Result<Self, RuntimeConfigError> {
         let path_prefix = normalize_optional_string(route.path_prefix.as_deref());
@@ -112,7 +112,7 @@ impl RuntimeRouteMatchPolicy {
             && !path_prefix.starts_with('/')
         {
             return Err(config_invalid(format!(
-                "upstream '{upstream_name}' has an invalid route.path_prefix '{}'",
+                "{scope} has an invalid route.path_prefix '{}'",
                 path_prefix
             )));
         }
@@ -1,7 +1,10 @@
 use std::{collections::HashMap, time::Duration};
 
 use super::config_invalid;
-use crate::{config::Resilience, runtime::RuntimeConfigError};
+use crate::{
+    config::{HedgingMode, MAX_HEDGES_PER_REQUEST, Resilience},
+    runtime::RuntimeConfigError,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeCircuitBreakerPolicy {
@@ -17,6 +20,14 @@ pub struct RuntimeHedgingPolicy {
     pub delay: Duration,
     pub safe_methods: Vec<String>,
     pub route_allowlist: Vec<String>,
+    pub mode: HedgingMode,
+    pub percentile: u8,
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    pub min_samples: u64,
+    pub window: Duration,
+    pub max_hedged_percent: u8,
+    pub max_hedges: u8,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,17 +69,64 @@ pub(crate) fn normalize_hedging_policy(
     safe_methods: Vec<String>,
     route_allowlist: Vec<String>,
 ) -> Result<RuntimeHedgingPolicy, RuntimeConfigError> {
-    if resilience.hedging.enabled && resilience.hedging.delay_ms == 0 {
+    let hedging = &resilience.hedging;
+    if hedging.enabled && hedging.delay_ms == 0 {
         return Err(config_invalid(
             "resilience.hedging: delay_ms must be > 0 when hedging is enabled",
         ));
     }
+    if hedging.window_ms == 0 {
+        return Err(config_invalid(
+            "resilience.hedging.window_ms must be greater than 0",
+        ));
+    }
+    if !(1..=100).contains(&hedging.max_hedged_percent) {
+        return Err(config_invalid(
+            "resilience.hedging.max_hedged_percent must be in range 1-100",
+        ));
+    }
+    if !(1..=MAX_HEDGES_PER_REQUEST).contains(&hedging.max_hedges) {
+        return Err(config_invalid(format!(
+            "resilience.hedging.max_hedges must be in range 1-{MAX_HEDGES_PER_REQUEST}"
+        )));
+    }
+    if hedging.mode == HedgingMode::Percentile {
+        if !(1..=99).contains(&hedging.percentile) {
+            return Err(config_invalid(
+                "resilience.hedging.percentile must be in range 1-99",
+            ));
+        }
+        if hedging.min_delay_ms == 0 {
+            return Err(config_invalid(
+                "resilience.hedging.min_delay_ms must be greater than 0",
+            ));
+        }
+        if hedging.max_delay_ms < hedging.min_delay_ms {
+            return Err(config_invalid(format!(
+                "resilience.hedging.max_delay_ms ({}) must be >= min_delay_ms ({})",
+                hedging.max_delay_ms, hedging.min_delay_ms
+            )));
+        }
+        if hedging.min_samples == 0 {
+            return Err(config_invalid(
+                "resilience.hedging.min_samples must be greater than 0",
+            ));
+        }
+    }
 
     Ok(RuntimeHedgingPolicy {
-        enabled: resilience.hedging.enabled,
-        delay: Duration::from_millis(resilience.hedging.delay_ms),
+        enabled: hedging.enabled,
+        delay: Duration::from_millis(hedging.delay_ms),
         safe_methods,
         route_allowlist,
+        mode: hedging.mode,
+        percentile: hedging.percentile,
+        min_delay: Duration::from_millis(hedging.min_delay_ms),
+        max_delay: Duration::from_millis(hedging.max_delay_ms),
+        min_samples: hedging.min_samples,
+        window: Duration::from_millis(hedging.window_ms),
+        max_hedged_percent: hedging.max_hedged_percent,
+        max_hedges: hedging.max_hedges,
     })
 }
 
@@ -8,18 +8,28 @@ impl RuntimeUpstream {
         name: &str,
         upstream: &Upstream,
         base_policies: &RuntimePolicySet,
+        zone: Option<&str>,
     ) -> Result<Self, RuntimeConfigError> {
         let effective_tls = upstream
             .tls
             .clone()
             .unwrap_or_else(|| config.upstream_tls.clone());
         let load_balancing = RuntimeLoadBalancingPolicy::normalize(&upstream.load_balancing)?;
-        let route = RuntimeRouteMatchPolicy::normalize(name, &upstream.route)?;
+        let route =
+            RuntimeRouteMatchPolicy::normalize(&format!("upstream '{name}'"), &upstream.route)?;
         let policy = RuntimeUpstreamPolicy {
             upstream_auth: RuntimeAuthPolicy::normalize(&upstream.auth, name)?,
             host: RuntimeHostPolicy(upstream.host_policy.clone()),
             forwarded_headers: RuntimeForwardedHeaderPolicy(upstream.forwarded_headers.clone()),
             protocol: base_policies.admission.protocol.clone(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: RuntimeRouteLimits::normalize(
+                &format!("upstream '{name}'"),
+                &upstream.policy,
+                &base_policies.timeouts,
+            )?,
         };
         let runtime_upstream = Self {
             name: name.to_string(),
@@ -28,11 +38,49 @@ impl RuntimeUpstream {
             policy,
             effective_tls: effective_tls.clone(),
             backend_tls_policy: RuntimeBackendTlsPolicy::from_effective_tls(&effective_tls),
-            backends: upstream
-                .backends
-                .iter()
-                .map(|backend| RuntimeBackend::normalize(name, backend))
-                .collect::<Result<Vec<_>, _>>()?,
+            backends: RuntimeBackend::normalize_all(name, &upstream.backends)?,
+            outlier_detection: upstream
+                .outlier_detection
+                .as_ref()
+                .map(|detection| {
+                    RuntimeOutlierDetection::normalize(&format!("upstream '{name}'"), detection)
+                })
+                .transpose()?,
+            slow_start: upstream
+                .slow_start
+                .as_ref()
+                .map(|slow_start| {
+                    RuntimeSlowStart::normalize(&format!("upstream '{name}'"), slow_start)
+                })
+                .transpose()?,
+            locality: zone
+                .map(|zone| {
+                    RuntimeLocality::normalize(
+                        &format!("upstream '{name}'"),
+                        zone,
+                        &upstream.zone_aware.clone().unwrap_or_default(),
+                    )
+                })
+                .transpose()?,
+            session_affinity: upstream
+                .session_affinity
+                .as_ref()
+                .map(|affinity| {
+                    RuntimeSessionAffinity::normalize(&format!("upstream '{name}'"), affinity)
+                })
+                .transpose()?,
+            adaptive_admission: upstream
+                .adaptive_admission
+                .as_ref()
+                .map(|adaptive| {
+                    RuntimeAdaptiveAdmissionPolicy::normalize(
+                        &format!("upstream '{name}' adaptive_admission"),
+                        adaptive,
+                        "performance.per_upstream_inflight_limit",
+                        base_policies.transport.per_upstream_inflight_limit,
+                    )
+                })
+                .transpose()?,
         };
 
         Ok(runtime_upstream)
@@ -44,6 +92,21 @@ impl RuntimeUpstream {
             load_balancing: self.load_balancing.as_config(),
             auth: self.policy.upstream_auth.as_config(),
             host_policy: self.policy.host.0.clone(),
+            policy: self.policy.limits.as_config(),
+            outlier_detection: self
+                .outlier_detection
+                .as_ref()
+                .map(RuntimeOutlierDetection::as_config),
+            slow_start: self.slow_start.as_ref().map(RuntimeSlowStart::as_config),
+            zone_aware: self.locality.as_ref().map(RuntimeLocality::as_config),
+            session_affinity: self
+                .session_affinity
+                .as_ref()
+                .map(RuntimeSessionAffinity::as_config),
+            adaptive_admission: self
+                .adaptive_admission
+                .as_ref()
+                .map(RuntimeAdaptiveAdmissionPolicy::as_config),
             forwarded_headers: self.policy.forwarded_headers.0.clone(),
             tls: Some(self.effective_tls.clone()),
             route: self.route.as_config(),
@@ -63,6 +126,175 @@ impl RuntimeUpstream {
     }
 }
 
+impl RuntimeOutlierDetection {
+    fn normalize(scope: &str, detection: &OutlierDetection) -> Result<Self, RuntimeConfigError> {
+        if detection.success_rate_min_percent.is_none()
+            && detection.success_rate_stdev_factor.is_none()
+            && detection.latency_p95_ratio_percent.is_none()
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} outlier_detection needs success_rate_min_percent, \
+                 success_rate_stdev_factor or latency_p95_ratio_percent"
+            )));
+        }
+        if detection
+            .success_rate_stdev_factor
+            .is_some_and(|factor| !factor.is_finite() || factor <= 0.0)
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} outlier_detection.success_rate_stdev_factor must be a positive number"
+            )));
+        }
+        let base_ejection = Duration::from_millis(detection.base_ejection_ms.max(1));
+
+        Ok(Self {
+            interval: Duration::from_millis(detection.interval_ms.max(1)),
+            min_requests: detection.min_requests.max(1),
+            min_backends: detection.min_backends.max(2),
+            success_rate_min_percent: detection
+                .success_rate_min_percent
+                .map(|percent| percent.min(100)),
+            success_rate_stdev_factor: detection.success_rate_stdev_factor,
+            latency_p95_ratio_percent: detection
+                .latency_p95_ratio_percent
+                .map(|percent| percent.max(100)),
+            max_ejection_percent: detection.max_ejection_percent.min(100),
+            base_ejection,
+            max_ejection: Duration::from_millis(detection.max_ejection_ms).max(base_ejection),
+        })
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> OutlierDetection {
+        let millis = |duration: Duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        OutlierDetection {
+            interval_ms: millis(self.interval),
+            min_requests: self.min_requests,
+            min_backends: self.min_backends,
+            success_rate_min_percent: self.success_rate_min_percent,
+            success_rate_stdev_factor: self.success_rate_stdev_factor,
+            latency_p95_ratio_percent: self.latency_p95_ratio_percent,
+            max_ejection_percent: self.max_ejection_percent,
+            base_ejection_ms: millis(self.base_ejection),
+            max_ejection_ms: millis(self.max_ejection),
+        }
+    }
+}
+
+impl RuntimeSlowStart {
+    fn normalize(scope: &str, slow_start: &SlowStart) -> Result<Self, RuntimeConfigError> {
+        if slow_start.window_ms == 0 {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} slow_start.window_ms must be greater than 0"
+            )));
+        }
+        if !(1..=100).contains(&slow_start.min_weight_percent) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} slow_start.min_weight_percent must be in range 1-100"
+            )));
+        }
+
+        Ok(Self {
+            window: Duration::from_millis(slow_start.window_ms),
+            min_weight_percent: slow_start.min_weight_percent,
+            curve: slow_start.curve,
+        })
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> SlowStart {
+        SlowStart {
+            window_ms: u64::try_from(self.window.as_millis()).unwrap_or(u64::MAX),
+            min_weight_percent: self.min_weight_percent,
+            curve: self.curve,
+        }
+    }
+}
+
+impl RuntimeLocality {
+    fn normalize(
+        scope: &str,
+        zone: &str,
+        zone_aware: &ZoneAware,
+    ) -> Result<Self, RuntimeConfigError> {
+        if zone_aware.max_inflight_per_backend == Some(0) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} zone_aware.max_inflight_per_backend must be greater than 0"
+            )));
+        }
+
+        Ok(Self {
+            zone: zone.to_string(),
+            zone_aware: zone_aware.enabled,
+            max_inflight_per_backend: zone_aware.max_inflight_per_backend,
+        })
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> ZoneAware {
+        ZoneAware {
+            enabled: self.zone_aware,
+            max_inflight_per_backend: self.max_inflight_per_backend,
+        }
+    }
+}
+
+impl RuntimeSessionAffinity {
+    fn normalize(scope: &str, affinity: &SessionAffinity) -> Result<Self, RuntimeConfigError> {
+        if !affinity.has_valid_cookie_name() {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} session_affinity.cookie_name must be a valid cookie name"
+            )));
+        }
+        if !affinity.has_valid_path() {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} session_affinity.path must start with '/' and contain no spaces, \
+                 control characters or ';'"
+            )));
+        }
+        if affinity.ttl_secs == Some(0) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} session_affinity.ttl_secs must be greater than 0"
+            )));
+        }
+        if affinity.same_site == CookieSameSite::None && !affinity.secure {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} session_affinity.same_site none requires secure"
+            )));
+        }
+        if affinity
+            .secret
+            .as_ref()
+            .is_some_and(|secret| secret.trim().is_empty())
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} session_affinity.secret must be non-empty when provided"
+            )));
+        }
+
+        Ok(Self {
+            cookie_name: affinity.cookie_name.clone(),
+            ttl: affinity.ttl_secs.map(Duration::from_secs),
+            path: affinity.path.clone(),
+            same_site: affinity.same_site,
+            secure: affinity.secure,
+            secret: affinity.secret.clone(),
+        })
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> SessionAffinity {
+        SessionAffinity {
+            cookie_name: self.cookie_name.clone(),
+            ttl_secs: self.ttl.map(|ttl| ttl.as_secs()),
+            path: self.path.clone(),
+            same_site: self.same_site,
+            secure: self.secure,
+            secret: self.secret.clone(),
+        }
+    }
+}
+
 pub(super) fn normalize_upstreams(
     config: &Config,
     base_policies: &RuntimePolicySet,
@@ -75,37 +307,48 @@ pub(super) fn normalize_upstreams(
 
     validate_protocol_policy(&config.resilience.protocol)?;
 
-    let mut seen_route_matchers: HashMap<RouteMatcherKey, String> = HashMap::new();
-    let mut seen_backend_origins: HashMap<String, (String, String)> = HashMap::new();
+    let zone = config.locality.resolve_zone(|key| std::env::var(key).ok());
     let mut normalized = HashMap::new();
 
     for (upstream_name, upstream) in &config.upstream {
-        validate_upstream_policy(config, upstream_name, upstream)?;
+        validate_upstream_policy(upstream_name, upstream)?;
 
-        let route_key = RuntimeRouteMatchPolicy::normalize(upstream_name, &upstream.route)?;
-        if let Some(existing) = seen_route_matchers.insert(route_key.clone(), upstream_name.clone())
-        {
-            return Err(RuntimeConfigError::DuplicateRouteAmbiguity {
-                upstream: upstream_name.clone(),
-                existing_upstream: existing,
-                host: route_key.host.clone(),
-                path_prefix: route_key.path_prefix.clone(),
-                method: route_key.method.clone(),
-            });
+        let runtime_upstream = RuntimeUpstream::from_config(
+            config,
+            upstream_name.as_str(),
+            upstream,
+            base_policies,
+            zone.as_deref(),
+        )?;
+        let upstream_uses_https_backends = runtime_upstream.backends.iter().any(|backend| {
+            matches!(
+                backend.endpoint.transport_kind,
+                RuntimeBackendTransportKind::H2
+            )
+        });
+
+        if upstream_uses_https_backends {
+            validate_runtime_upstream_tls(
+                upstream_name,
+                &runtime_upstream.backend_tls_policy.as_upstream_tls(),
+            )?;
         }
 
-        let runtime_upstream =
-            RuntimeUpstream::from_config(config, upstream_name.as_str(), upstream, base_policies)?;
-        let mut upstream_uses_https_backends = false;
+        normalized.insert(upstream_name.clone(), runtime_upstream);
+    }
 
-        for backend in &runtime_upstream.backends {
-            if matches!(
-                backend.endpoint.transport_kind,
-                RuntimeBackendTransportKind::H2
-            ) {
-                upstream_uses_https_backends = true;
-            }
+    Ok(normalized)
+}
+
+/// Rejects backend origins shared across upstreams. Runs after route lowering so
+/// route ambiguity is reported first.
+pub(super) fn validate_backend_origins(
+    upstreams: &HashMap<String, RuntimeUpstream>,
+) -> Result<(), RuntimeConfigError> {
+    let mut seen_backend_origins: HashMap<String, (String, String)> = HashMap::new();
 
+    for (upstream_name, upstream) in upstreams {
+        for backend in &upstream.backends {
             if let Some((existing_upstream, existing_backend)) = seen_backend_origins.insert(
                 backend.endpoint.origin.clone(),
                 (upstream_name.clone(), backend.backend.id.clone()),
@@ -121,25 +364,838 @@ pub(super) fn normalize_upstreams(
                 });
             }
         }
+    }
 
-        if upstream_uses_https_backends {
-            validate_runtime_upstream_tls(
-                upstream_name,
-                &runtime_upstream.backend_tls_policy.as_upstream_tls(),
+    Ok(())
+}
+
+pub(super) fn normalize_routes(
+    config: &Config,
+    upstreams: &HashMap<String, RuntimeUpstream>,
+    base_policies: &RuntimePolicySet,
+) -> Result<HashMap<String, RuntimeRoute>, RuntimeConfigError> {
+    let mut seen_route_matchers: HashMap<RouteMatcherKey, (String, String)> = HashMap::new();
+    let mut normalized = HashMap::new();
+
+    for route in effective_routes(config) {
+        let runtime_route = RuntimeRoute::from_config(config, &route, upstreams, base_policies)?;
+        if let Some((existing_route, existing_upstream)) = seen_route_matchers.insert(
+            runtime_route.route.clone(),
+            (route.name.clone(), route.upstream.clone()),
+        ) {
+            return Err(RuntimeConfigError::DuplicateRouteAmbiguity(Box::new(
+                RouteAmbiguity {
+                    route: route.name.clone(),
+                    upstream: route.upstream.clone(),
+                    existing_route,
+                    existing_upstream,
+                    host: runtime_route.route.host.clone(),
+                    path_prefix: runtime_route.route.path_prefix.clone(),
+                    method: runtime_route.route.method.clone(),
+                },
+            )));
+        }
+        if normalized
+            .insert(route.name.clone(), runtime_route)
+            .is_some()
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "route '{}' is defined more than once",
+                route.name
+            )));
+        }
+    }
+
+    Ok(normalized)
+}
+
+impl RuntimeRoute {
+    fn from_config(
+        config: &Config,
+        route: &Route,
+        upstreams: &HashMap<String, RuntimeUpstream>,
+        base_policies: &RuntimePolicySet,
+    ) -> Result<Self, RuntimeConfigError> {
+        if route.name.trim().is_empty() {
+            return Err(RuntimeConfigError::ConfigInvalid(
+                "routes contains an empty route name".to_string(),
+            ));
+        }
+        let scope = format!("route '{}'", route.name);
+
+        if route.matcher.host.is_none() && route.matcher.path_prefix.is_none() {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} must set a host or path_prefix matcher"
+            )));
+        }
+        if let Some(path) = route.matcher.path_prefix.as_deref()
+            && (path.is_empty() || !path.starts_with('/'))
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} has an invalid route.path_prefix '{}'",
+                path
+            )));
+        }
+        if normalized_route_method(route.matcher.method.as_deref()).as_deref() == Some("CONNECT")
+            && !config.resilience.protocol.allow_connect
+        {
+            return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
+                "{scope} routes CONNECT but resilience.protocol.allow_connect=false"
+            )));
+        }
+
+        if route.has_action() {
+            return Self::from_action_config(route, &scope, base_policies);
+        }
+
+        let upstream = upstreams.get(&route.upstream).ok_or_else(|| {
+            RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} references unknown upstream '{}'",
+                route.upstream
+            ))
+        })?;
+        let mut policy = upstream.policy.clone();
+        if let Some(auth) = route.auth.as_ref() {
+            validate_runtime_auth_policy(&scope, auth)?;
+            policy.upstream_auth = RuntimeAuthPolicy::normalize(auth, &route.name)?;
+        }
+        if let Some(host_policy) = route.host_policy.as_ref() {
+            validate_runtime_host_policy(&scope, host_policy)?;
+            policy.host = RuntimeHostPolicy(host_policy.clone());
+        }
+        if let Some(forwarded_headers) = route.forwarded_headers.as_ref() {
+            policy.forwarded_headers = RuntimeForwardedHeaderPolicy(forwarded_headers.clone());
+        }
+        if let Some(rewrite) = route.rewrite.as_ref() {
+            policy.path_rewrite = Some(RuntimePathRewrite::normalize(&scope, rewrite)?);
+        }
+        if let Some(mirror) = route.mirror.as_ref() {
+            policy.mirror = Some(Arc::new(RuntimeMirrorPolicy::normalize(
+                &scope,
+                &route.upstream,
+                mirror,
+                upstreams,
+            )?));
+        }
+        if let Some(fault) = route.fault.as_ref() {
+            policy.fault = RuntimeFaultPolicy::normalize(&scope, fault)?.map(Arc::new);
+        }
+        if let Some(route_policy) = route.policy.as_ref() {
+            let upstream_policy = config
+                .upstream
+                .get(&route.upstream)
+                .map(|upstream| upstream.policy.clone())
+                .unwrap_or_default();
+            policy.limits = RuntimeRouteLimits::normalize(
+                &scope,
+                &route_policy.or(&upstream_policy),
+                &base_policies.timeouts,
             )?;
         }
 
-        normalized.insert(upstream_name.clone(), runtime_upstream);
+        let split = route
+            .split
+            .as_ref()
+            .map(|split| RuntimeTrafficSplit::normalize(&scope, &route.upstream, split, upstreams))
+            .transpose()?;
+
+        Ok(Self {
+            name: route.name.clone(),
+            upstream: route.upstream.clone(),
+            route: RuntimeRouteMatchPolicy::normalize(&scope, &route.matcher)?,
+            policy,
+            split,
+            action: None,
+        })
     }
 
-    Ok(normalized)
+    /// Lowers a route answered by the edge. Only local route auth carries over;
+    /// the forwarding policies have nothing to act on.
+    fn from_action_config(
+        route: &Route,
+        scope: &str,
+        base_policies: &RuntimePolicySet,
+    ) -> Result<Self, RuntimeConfigError> {
+        if !route.upstream.is_empty()
+            || route.split.is_some()
+            || route.mirror.is_some()
+            || route.fault.is_some()
+            || route.policy.is_some()
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} is answered by the edge and cannot set upstream, split, mirror, fault, or policy"
+            )));
+        }
+        let mut policy = RuntimeUpstreamPolicy {
+            protocol: base_policies.admission.protocol.clone(),
+            ..RuntimeUpstreamPolicy::default()
+        };
+        if let Some(auth) = route.auth.as_ref() {
+            if auth.external_auth.is_some() {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} is answered by the edge and cannot use auth.external_auth"
+                )));
+            }
+            validate_runtime_auth_policy(scope, auth)?;
+            policy.upstream_auth = RuntimeAuthPolicy::normalize(auth, &route.name)?;
+        }
+
+        let action = match (route.redirect.as_ref(), route.direct_response.as_ref()) {
+            (Some(redirect), None) => RuntimeRouteAction::Redirect(RuntimeRedirect::normalize(
+                scope,
+                redirect,
+                route.rewrite.as_ref(),
+            )?),
+            (None, Some(direct)) if route.rewrite.is_none() => {
+                RuntimeRouteAction::DirectResponse(RuntimeDirectResponse::normalize(scope, direct)?)
+            }
+            (None, Some(_)) => {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} direct_response cannot be combined with rewrite"
+                )));
+            }
+            _ => {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} must set only one of redirect or direct_response"
+                )));
+            }
+        };
+
+        Ok(Self {
+            name: route.name.clone(),
+            upstream: String::new(),
+            route: RuntimeRouteMatchPolicy::normalize(scope, &route.matcher)?,
+            policy,
+            split: None,
+            action: Some(Arc::new(action)),
+        })
+    }
 }
 
-impl RuntimeBackend {
-    pub(super) fn normalize(
-        upstream_name: &str,
-        backend: &Backend,
+impl RuntimeRedirect {
+    fn normalize(
+        scope: &str,
+        redirect: &RouteRedirect,
+        rewrite: Option<&PathRewrite>,
+    ) -> Result<Self, RuntimeConfigError> {
+        if !VALID_REDIRECT_STATUSES.contains(&redirect.status) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} redirect.status must be one of {VALID_REDIRECT_STATUSES:?}"
+            )));
+        }
+        let scheme = redirect.scheme.as_deref().unwrap_or("https");
+        if scheme != "http" && scheme != "https" {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} redirect.scheme must be http or https"
+            )));
+        }
+        if redirect
+            .path
+            .as_deref()
+            .is_some_and(|path| !path.starts_with('/'))
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} redirect.path must start with '/'"
+            )));
+        }
+
+        Ok(Self {
+            status: redirect.status,
+            scheme: scheme.to_string(),
+            host: redirect.host.clone(),
+            path: redirect.path.clone(),
+            path_rewrite: rewrite
+                .map(|rewrite| RuntimePathRewrite::normalize(scope, rewrite))
+                .transpose()?,
+            query: redirect.query.clone(),
+        })
+    }
+}
+
+impl RuntimeDirectResponse {
+    fn normalize(scope: &str, direct: &DirectResponse) -> Result<Self, RuntimeConfigError> {
+        if !(200..=599).contains(&direct.status) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} direct_response.status must be within 200..=599"
+            )));
+        }
+        let headers = direct
+            .headers
+            .iter()
+            .map(|header| {
+                let name = http::HeaderName::from_bytes(header.name.as_bytes())
+                    .ok()
+                    .filter(|name| {
+                        *name != http::header::CONTENT_LENGTH
+                            && *name != http::header::TRANSFER_ENCODING
+                    })
+                    .ok_or_else(|| {
+                        RuntimeConfigError::ConfigInvalid(format!(
+                            "{scope} direct_response header name '{}' is not allowed",
+                            header.name
+                        ))
+                    })?;
+                if http::HeaderValue::from_str(&header.value).is_err() {
+                    return Err(RuntimeConfigError::ConfigInvalid(format!(
+                        "{scope} direct_response header '{}' has an invalid value",
+                        header.name
+                    )));
+                }
+                Ok((name.as_str().to_string(), header.value.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let body = match (direct.body.as_deref(), direct.body_file.as_deref()) {
+            (Some(_), Some(_)) => {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} direct_response must set at most one of body and body_file"
+                )));
+            }
+            (Some(body), None) => body.as_bytes().to_vec(),
+            (None, Some(path)) => std::fs::read(path).map_err(|err| {
+                RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} direct_response.body_file '{path}' cannot be read: {err}"
+                ))
+            })?,
+            (None, None) => Vec::new(),
+        };
+        if body.len() > MAX_DIRECT_RESPONSE_BODY_BYTES {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} direct_response body exceeds {MAX_DIRECT_RESPONSE_BODY_BYTES} bytes"
+            )));
+        }
+
+        Ok(Self {
+            status: direct.status,
+            headers,
+            body,
+        })
+    }
+}
+
+impl RuntimeMirrorPolicy {
+    fn normalize(
+        scope: &str,
+        primary: &str,
+        mirror: &RouteMirror,
+        upstreams: &HashMap<String, RuntimeUpstream>,
+    ) -> Result<Self, RuntimeConfigError> {
+        if !upstreams.contains_key(&mirror.upstream) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} mirror references unknown upstream '{}'",
+                mirror.upstream
+            )));
+        }
+        if mirror.upstream == primary {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} mirror.upstream must differ from the route upstream"
+            )));
+        }
+        if mirror.percent == 0 || mirror.percent > 100 {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} mirror.percent must be in 1..=100"
+            )));
+        }
+        if mirror.max_body_bytes > MAX_MIRROR_BODY_BYTES {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} mirror.max_body_bytes must be <= {MAX_MIRROR_BODY_BYTES}"
+            )));
+        }
+        if mirror.max_inflight == 0 {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} mirror.max_inflight must be greater than 0"
+            )));
+        }
+        let mut methods = Vec::with_capacity(mirror.methods.len());
+        for method in &mirror.methods {
+            let Some(method) = normalized_route_method(Some(method)) else {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} mirror.methods must not contain empty entries"
+                )));
+            };
+            if method == "CONNECT" {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} mirror.methods cannot include CONNECT"
+                )));
+            }
+            if !methods.contains(&method) {
+                methods.push(method);
+            }
+        }
+
+        Ok(Self {
+            upstream: mirror.upstream.clone(),
+            percent: mirror.percent,
+            methods,
+            max_body_bytes: mirror.max_body_bytes,
+            max_inflight: mirror.max_inflight,
+        })
+    }
+}
+
+impl RuntimeRouteLimits {
+    /// Lowers a merged `policy` block. Effective timeouts must keep the
+    /// global ordering `backend_connect <= backend_timeout <= total_request`.
+    fn normalize(
+        scope: &str,
+        policy: &RoutePolicy,
+        timeouts: &RuntimeTimeoutPolicy,
+    ) -> Result<Self, RuntimeConfigError> {
+        let invalid = |message: String| {
+            Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} policy.{message}"
+            )))
+        };
+        let nonzero = [
+            ("backend_timeout_ms", policy.backend_timeout_ms),
+            (
+                "backend_total_request_timeout_ms",
+                policy.backend_total_request_timeout_ms,
+            ),
+            (
+                "max_request_body_bytes",
+                policy.max_request_body_bytes.map(|bytes| bytes as u64),
+            ),
+            ("hedging_delay_ms", policy.hedging_delay_ms),
+        ];
+        for (field, value) in nonzero {
+            if value == Some(0) {
+                return invalid(format!("{field} must be greater than 0"));
+            }
+        }
+        if policy
+            .max_retry_attempts
+            .is_some_and(|attempts| attempts > MAX_ROUTE_RETRY_ATTEMPTS)
+        {
+            return invalid(format!(
+                "max_retry_attempts must be <= {MAX_ROUTE_RETRY_ATTEMPTS}"
+            ));
+        }
+
+        let limits = Self {
+            backend_timeout: policy.backend_timeout_ms.map(Duration::from_millis),
+            backend_total_request_timeout: policy
+                .backend_total_request_timeout_ms
+                .map(Duration::from_millis),
+            max_request_body_bytes: policy.max_request_body_bytes,
+            max_retry_attempts: policy.max_retry_attempts,
+            hedging_delay: policy.hedging_delay_ms.map(Duration::from_millis),
+            retry: RuntimeRetryPolicy::normalize(scope, policy)?,
+            replay: RuntimeReplayPolicy::normalize(scope, policy)?,
+        };
+        let backend_timeout = limits.backend_timeout.unwrap_or(timeouts.backend_request);
+        let total_request_timeout = limits
+            .backend_total_request_timeout
+            .unwrap_or(timeouts.backend_total_request);
+        if backend_timeout < timeouts.backend_connect {
+            return invalid(
+                "backend_timeout_ms must be >= performance.backend_connect_timeout_ms".to_string(),
+            );
+        }
+        if backend_timeout > total_request_timeout {
+            return invalid(
+                "backend_timeout_ms must be <= backend_total_request_timeout_ms".to_string(),
+            );
+        }
+        Ok(limits)
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> RoutePolicy {
+        let millis = |duration: Duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        RoutePolicy {
+            backend_timeout_ms: self.backend_timeout.map(millis),
+            backend_total_request_timeout_ms: self.backend_total_request_timeout.map(millis),
+            max_request_body_bytes: self.max_request_body_bytes,
+            max_retry_attempts: self.max_retry_attempts,
+            hedging_delay_ms: self.hedging_delay.map(millis),
+            replay_body_max_bytes: self.replay.as_ref().map(|replay| replay.max_body_bytes),
+            idempotency_header: self
+                .replay
+                .as_ref()
+                .map(|replay| replay.idempotency_header.clone()),
+            ..self.retry.as_config()
+        }
+    }
+}
+
+impl RuntimeReplayPolicy {
+    fn normalize(scope: &str, policy: &RoutePolicy) -> Result<Option<Self>, RuntimeConfigError> {
+        let idempotency_header = policy
+            .idempotency_header
+            .as_deref()
+            .map(|name| name.trim().to_ascii_lowercase())
+            .unwrap_or_else(route_default_idempotency_header);
+        if http::HeaderName::from_bytes(idempotency_header.as_bytes()).is_err() {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} policy.idempotency_header is invalid"
+            )));
+        }
+        Ok(policy
+            .replay_body_max_bytes
+            .filter(|&max_body_bytes| max_body_bytes > 0)
+            .map(|max_body_bytes| Self {
+                max_body_bytes,
+                idempotency_header,
+            }))
+    }
+}
+
+impl RuntimeRetryPolicy {
+    fn normalize(scope: &str, policy: &RoutePolicy) -> Result<Self, RuntimeConfigError> {
+        let invalid = |message: String| {
+            Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} policy.{message}"
+            )))
+        };
+        let mut retry = Self::default();
+        if let Some(retry_on) = policy.retry_on.as_ref() {
+            if retry_on.is_empty() {
+                return invalid(
+                    "retry_on must list at least one entry; set max_retry_attempts: 0 to disable retries"
+                        .to_string(),
+                );
+            }
+            retry.reset_before_headers = false;
+            for entry in retry_on {
+                match *entry {
+                    RetryOn::Status(status) if RETRYABLE_STATUS_RANGE.contains(&status) => {
+                        retry.statuses.push(status);
+                    }
+                    RetryOn::Status(status) => {
+                        return invalid(format!(
+                            "retry_on status {status} must be within 400..=599"
+                        ));
+                    }
+                    RetryOn::Condition(RetryCondition::ResetBeforeHeaders) => {
+                        retry.reset_before_headers = true;
+                    }
+                    RetryOn::Condition(RetryCondition::ConnectFailure) => {
+                        retry.connect_failure = true;
+                    }
+                }
+            }
+            retry.statuses.sort_unstable();
+            retry.statuses.dedup();
+        }
+        if let Some(base_ms) = policy.retry_backoff_base_ms {
+            retry.backoff_base = Duration::from_millis(base_ms);
+        }
+        if let Some(max_ms) = policy.retry_backoff_max_ms {
+            retry.backoff_max = Duration::from_millis(max_ms);
+        }
+        if retry.backoff_base > retry.backoff_max {
+            return invalid("retry_backoff_base_ms must be <= retry_backoff_max_ms".to_string());
+        }
+        if let Some(retry_after_max_ms) = policy.retry_after_max_ms {
+            retry.retry_after_max = Duration::from_millis(retry_after_max_ms);
+        }
+        Ok(retry)
+    }
+
+    #[cfg(test)]
+    fn as_config(&self) -> RoutePolicy {
+        if *self == Self::default() {
+            return RoutePolicy::default();
+        }
+        let millis = |duration: Duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let mut retry_on: Vec<RetryOn> =
+            self.statuses.iter().copied().map(RetryOn::Status).collect();
+        if self.reset_before_headers {
+            retry_on.push(RetryOn::Condition(RetryCondition::ResetBeforeHeaders));
+        }
+        if self.connect_failure {
+            retry_on.push(RetryOn::Condition(RetryCondition::ConnectFailure));
+        }
+        RoutePolicy {
+            retry_on: Some(retry_on),
+            retry_backoff_base_ms: Some(millis(self.backoff_base)),
+            retry_backoff_max_ms: Some(millis(self.backoff_max)),
+            retry_after_max_ms: Some(millis(self.retry_after_max)),
+            ..RoutePolicy::default()
+        }
+    }
+}
+
+impl RuntimeFaultPolicy {
+    /// Returns `None` when every configured fault has a zero percent, so an
+    /// idle fault block costs nothing per request.
+    fn normalize(scope: &str, fault: &RouteFault) -> Result<Option<Self>, RuntimeConfigError> {
+        let invalid = |message: &str| {
+            Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} fault.{message}"
+            )))
+        };
+        let header = match fault.header.as_ref() {
+            Some(header) => {
+                let name = header.name.trim().to_ascii_lowercase();
+                if http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    return invalid("header.name is invalid");
+                }
+                let value = header.value.as_deref().map(str::trim);
+                if value.is_some_and(str::is_empty) {
+                    return invalid("header.value must be non-empty");
+                }
+                Some(RuntimeFaultHeader {
+                    name,
+                    value: value.map(str::to_string),
+                })
+            }
+            None => None,
+        };
+        let percent_ok = |percent: u32| percent <= 100;
+
+        let delay = match fault.delay.as_ref() {
+            Some(delay) if !percent_ok(delay.percent) => {
+                return invalid("delay.percent must be in 0..=100");
+            }
+            Some(delay) if delay.duration_ms == 0 => {
+                return invalid("delay.duration_ms must be greater than 0");
+            }
+            Some(delay) => {
+                let max_duration_ms = delay.max_duration_ms.unwrap_or(delay.duration_ms);
+                if max_duration_ms < delay.duration_ms {
+                    return invalid("delay.max_duration_ms must be >= duration_ms");
+                }
+                Some(RuntimeFaultDelay {
+                    percent: delay.percent,
+                    min: Duration::from_millis(delay.duration_ms),
+                    max: Duration::from_millis(max_duration_ms),
+                })
+            }
+            None => None,
+        };
+        let abort = match fault.abort.as_ref() {
+            Some(abort) if !percent_ok(abort.percent) => {
+                return invalid("abort.percent must be in 0..=100");
+            }
+            Some(abort) => {
+                let action = match (abort.status, abort.reset) {
+                    (None, true) => RuntimeFaultAbortAction::Reset,
+                    (Some(status), false) if (400..=599).contains(&status) => {
+                        RuntimeFaultAbortAction::Status(status)
+                    }
+                    (Some(_), false) => return invalid("abort.status must be in 400..=599"),
+                    _ => return invalid("abort must set exactly one of status or reset"),
+                };
+                Some(RuntimeFaultAbort {
+                    percent: abort.percent,
+                    action,
+                })
+            }
+            None => None,
+        };
+        let truncate = match fault.truncate.as_ref() {
+            Some(truncate) if !percent_ok(truncate.percent) => {
+                return invalid("truncate.percent must be in 0..=100");
+            }
+            Some(truncate) if truncate.after_bytes == 0 => {
+                return invalid("truncate.after_bytes must be greater than 0");
+            }
+            Some(truncate) => Some(RuntimeFaultTruncate {
+                percent: truncate.percent,
+                after_bytes: truncate.after_bytes,
+            }),
+            None => None,
+        };
+        if delay.is_none() && abort.is_none() && truncate.is_none() {
+            return invalid("must set at least one of delay, abort, or truncate");
+        }
+
+        let policy = Self {
+            header,
+            delay: delay.filter(|delay| delay.percent > 0),
+            abort: abort.filter(|abort| abort.percent > 0),
+            truncate: truncate.filter(|truncate| truncate.percent > 0),
+        };
+        if policy.delay.is_none() && policy.abort.is_none() && policy.truncate.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(policy))
+    }
+}
+
+impl RuntimeTrafficSplit {
+    fn normalize(
+        scope: &str,
+        primary: &str,
+        split: &TrafficSplit,
+        upstreams: &HashMap<String, RuntimeUpstream>,
+    ) -> Result<Self, RuntimeConfigError> {
+        let mut targets: Vec<RuntimeSplitTarget> = Vec::with_capacity(split.targets.len());
+        for target in &split.targets {
+            if !upstreams.contains_key(&target.upstream) {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} split target references unknown upstream '{}'",
+                    target.upstream
+                )));
+            }
+            if targets
+                .iter()
+                .any(|existing| existing.upstream == target.upstream)
+            {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} split targets upstream '{}' more than once",
+                    target.upstream
+                )));
+            }
+            targets.push(RuntimeSplitTarget {
+                upstream: target.upstream.clone(),
+                weight: target.weight,
+            });
+        }
+        if !targets.iter().any(|target| target.upstream == primary) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} split.targets must include the route upstream '{primary}'"
+            )));
+        }
+        let total_weight = targets
+            .iter()
+            .map(|target| u64::from(target.weight))
+            .sum::<u64>();
+        if total_weight == 0 {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} split weights must not all be zero"
+            )));
+        }
+
+        let rollout = split
+            .rollout
+            .as_ref()
+            .map(|rollout| RuntimeCanaryRollout::normalize(scope, &targets, rollout))
+            .transpose()?;
+
+        Ok(Self {
+            key_spec: split
+                .key
+                .as_deref()
+                .map(RuntimeRequestKeySpec::normalize)
+                .transpose()?,
+            targets,
+            total_weight,
+            rollout,
+        })
+    }
+}
+
+impl RuntimeCanaryRollout {
+    fn normalize(
+        scope: &str,
+        targets: &[RuntimeSplitTarget],
+        rollout: &CanaryRollout,
     ) -> Result<Self, RuntimeConfigError> {
+        if !targets
+            .iter()
+            .any(|target| target.upstream == rollout.canary)
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} split.rollout.canary '{}' must be one of the split targets",
+                rollout.canary
+            )));
+        }
+        let baselines = targets
+            .iter()
+            .filter(|target| target.upstream != rollout.canary)
+            .map(|target| target.upstream.clone())
+            .collect::<Vec<_>>();
+        if baselines.is_empty() {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} split.rollout needs at least one baseline target besides the canary"
+            )));
+        }
+        if rollout.steps.is_empty()
+            || rollout
+                .steps
+                .iter()
+                .zip(std::iter::once(&0).chain(rollout.steps.iter()))
+                .any(|(step, previous)| *step == 0 || *step > 100 || step <= previous)
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "{scope} split.rollout.steps must be strictly increasing percentages in 1..=100"
+            )));
+        }
+
+        Ok(Self {
+            canary: rollout.canary.clone(),
+            baselines,
+            steps: rollout.steps.clone(),
+            step_interval: Duration::from_millis(rollout.step_interval_ms.max(1)),
+            min_requests: rollout.min_requests,
+            max_error_rate_increase_percent: rollout.max_error_rate_increase_percent.min(100),
+            max_p95_latency_ratio_percent: rollout.max_p95_latency_ratio_percent.max(100),
+        })
+    }
+}
+
+impl RuntimePathRewrite {
+    fn normalize(scope: &str, rewrite: &PathRewrite) -> Result<Self, RuntimeConfigError> {
+        let rule = match (
+            rewrite.strip_prefix.as_deref(),
+            rewrite.replace_prefix.as_ref(),
+            rewrite.regex.as_ref(),
+        ) {
+            (Some(prefix), None, None) => {
+                Self::StripPrefix(normalize_rewrite_prefix(scope, "strip_prefix", prefix)?)
+            }
+            (None, Some(replace), None) => {
+                if !replace.replacement.starts_with('/') {
+                    return Err(RuntimeConfigError::ConfigInvalid(format!(
+                        "{scope} rewrite.replace_prefix.replacement must start with '/'"
+                    )));
+                }
+                Self::ReplacePrefix {
+                    prefix: normalize_rewrite_prefix(
+                        scope,
+                        "replace_prefix.prefix",
+                        &replace.prefix,
+                    )?,
+                    replacement: replace.replacement.clone(),
+                }
+            }
+            (None, None, Some(regex)) => {
+                let pattern = Regex::new(&regex.pattern).map_err(|err| {
+                    RuntimeConfigError::ConfigInvalid(format!(
+                        "{scope} rewrite.regex.pattern is invalid: {err}"
+                    ))
+                })?;
+                if regex.substitution.is_empty() {
+                    return Err(RuntimeConfigError::ConfigInvalid(format!(
+                        "{scope} rewrite.regex.substitution cannot be empty"
+                    )));
+                }
+                Self::Regex {
+                    pattern,
+                    substitution: regex.substitution.clone(),
+                }
+            }
+            _ => {
+                return Err(RuntimeConfigError::ConfigInvalid(format!(
+                    "{scope} rewrite must set exactly one of strip_prefix, replace_prefix, or regex"
+                )));
+            }
+        };
+        Ok(rule)
+    }
+}
+
+fn normalize_rewrite_prefix(
+    scope: &str,
+    field: &str,
+    prefix: &str,
+) -> Result<String, RuntimeConfigError> {
+    let trimmed = prefix.trim_end_matches('/');
+    if !prefix.starts_with('/') || trimmed.is_empty() {
+        return Err(RuntimeConfigError::ConfigInvalid(format!(
+            "{scope} rewrite.{field} must start with '/' and not be '/'"
+        )));
+    }
+    Ok(trimmed.to_string())
+}
+
+impl RuntimeBackend {
+    /// Lower one backend of `upstream_name`. Also used for backends added
+    /// through the control API, which skip config validation.
+    pub fn normalize(upstream_name: &str, backend: &Backend) -> Result<Self, RuntimeConfigError> {
         if backend.id.trim().is_empty() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
                 "upstream '{upstream_name}' contains an empty backend id"
@@ -151,6 +1207,22 @@ impl RuntimeBackend {
                 backend.id, upstream_name
             )));
         }
+        if !(1..=1000).contains(&backend.weight) {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "backend '{}' in upstream '{}' has invalid weight {} (must be 1–1000)",
+                backend.id, upstream_name, backend.weight
+            )));
+        }
+        if backend
+            .zone
+            .as_deref()
+            .is_some_and(|zone| zone.trim().is_empty())
+        {
+            return Err(RuntimeConfigError::ConfigInvalid(format!(
+                "backend '{}' in upstream '{}' has an empty zone",
+                backend.id, upstream_name
+            )));
+        }
 
         Ok(Self {
             backend: backend.clone(),
@@ -172,6 +1244,27 @@ impl RuntimeBackend {
                 .transpose()?,
         })
     }
+
+    /// Lower every backend of `upstream_name`, rejecting ids used twice:
+    /// affinity cookies and admin overrides address backends by id.
+    pub fn normalize_all(
+        upstream_name: &str,
+        backends: &[Backend],
+    ) -> Result<Vec<Self>, RuntimeConfigError> {
+        let mut seen_ids = HashSet::new();
+        backends
+            .iter()
+            .map(|backend| {
+                if !seen_ids.insert(backend.id.as_str()) {
+                    return Err(RuntimeConfigError::ConfigInvalid(format!(
+                        "upstream '{}' contains duplicate backend id '{}'",
+                        upstream_name, backend.id
+                    )));
+                }
+                Self::normalize(upstream_name, backend)
+            })
+            .collect()
+    }
 }
 
 fn validate_protocol_policy(policy: &ProtocolPolicy) -> Result<(), RuntimeConfigError> {
@@ -238,7 +1331,7 @@ fn validate_protocol_policy(policy: &ProtocolPolicy) -> Result<(), RuntimeConfig
 }
 
 fn validate_runtime_external_auth_headers(
-    upstream_name: &str,
+    scope: &str,
     field_prefix: &str,
     request_headers: &[crate::config::ExternalAuthRequestHeader],
     response_header_allowlist: &[String],
@@ -248,22 +1341,22 @@ fn validate_runtime_external_auth_headers(
         let header_name = header.name.trim();
         if header_name.is_empty() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.request_headers[].name must be non-empty"
+                "{scope} {field_prefix}.request_headers[].name must be non-empty"
             )));
         }
         if http::header::HeaderName::from_bytes(header_name.as_bytes()).is_err() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.request_headers[].name must be a valid HTTP header name"
+                "{scope} {field_prefix}.request_headers[].name must be a valid HTTP header name"
             )));
         }
         if http::HeaderValue::from_str(header.value.as_str()).is_err() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.request_headers[].value must be a valid HTTP header value"
+                "{scope} {field_prefix}.request_headers[].value must be a valid HTTP header value"
             )));
         }
         if !seen_request_headers.insert(header_name.to_ascii_lowercase()) {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.request_headers contains duplicate header names"
+                "{scope} {field_prefix}.request_headers contains duplicate header names"
             )));
         }
     }
@@ -273,17 +1366,17 @@ fn validate_runtime_external_auth_headers(
         let header_name = header_name.trim();
         if header_name.is_empty() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.response_header_allowlist[] must be non-empty"
+                "{scope} {field_prefix}.response_header_allowlist[] must be non-empty"
             )));
         }
         if http::header::HeaderName::from_bytes(header_name.as_bytes()).is_err() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.response_header_allowlist[] must be a valid HTTP header name"
+                "{scope} {field_prefix}.response_header_allowlist[] must be a valid HTTP header name"
             )));
         }
         if !seen_allowed_headers.insert(header_name.to_ascii_lowercase()) {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' {field_prefix}.response_header_allowlist contains duplicate header names"
+                "{scope} {field_prefix}.response_header_allowlist contains duplicate header names"
             )));
         }
     }
@@ -292,80 +1385,75 @@ fn validate_runtime_external_auth_headers(
 }
 
 fn validate_upstream_policy(
-    config: &Config,
     upstream_name: &str,
     upstream: &Upstream,
 ) -> Result<(), RuntimeConfigError> {
-    match upstream.host_policy.mode {
+    let scope = format!("upstream '{upstream_name}'");
+    validate_runtime_host_policy(&scope, &upstream.host_policy)?;
+    validate_runtime_auth_policy(&scope, &upstream.auth)
+}
+
+fn validate_runtime_host_policy(
+    scope: &str,
+    host_policy: &UpstreamHostPolicy,
+) -> Result<(), RuntimeConfigError> {
+    match host_policy.mode {
         UpstreamHostPolicyMode::PassThrough | UpstreamHostPolicyMode::Upstream => {
-            if upstream.host_policy.host.is_some() {
+            if host_policy.host.is_some() {
                 return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
-                    "upstream '{upstream_name}' sets host_policy.host but mode is not rewrite"
+                    "{scope} sets host_policy.host but mode is not rewrite"
                 )));
             }
         }
-        UpstreamHostPolicyMode::Rewrite => match upstream.host_policy.host.as_deref() {
+        UpstreamHostPolicyMode::Rewrite => match host_policy.host.as_deref() {
             Some(host) if valid_static_host_header(host) => {}
             _ => {
                 return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
-                    "upstream '{upstream_name}' requires a valid non-empty host_policy.host when mode=rewrite"
+                    "{scope} requires a valid non-empty host_policy.host when mode=rewrite"
                 )));
             }
         },
     }
 
-    if let Some(path) = upstream.route.path_prefix.as_deref()
-        && (path.is_empty() || !path.starts_with('/'))
-    {
-        return Err(RuntimeConfigError::ConfigInvalid(format!(
-            "upstream '{upstream_name}' has an invalid route.path_prefix '{}'",
-            path
-        )));
-    }
-
-    if normalized_route_method(upstream.route.method.as_deref()).as_deref() == Some("CONNECT")
-        && !config.resilience.protocol.allow_connect
-    {
-        return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
-            "upstream '{upstream_name}' routes CONNECT but resilience.protocol.allow_connect=false"
-        )));
-    }
+    Ok(())
+}
 
-    if let Some(api_key) = upstream.auth.api_key.as_ref() {
+fn validate_runtime_auth_policy(scope: &str, auth: &RouteAuth) -> Result<(), RuntimeConfigError> {
+    if let Some(api_key) = auth.api_key.as_ref() {
         if api_key.header_name.trim().is_empty() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.api_key.header_name must be non-empty"
+                "{scope} auth.api_key.header_name must be non-empty"
             )));
         }
         if http::header::HeaderName::from_bytes(api_key.header_name.trim().as_bytes()).is_err() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.api_key.header_name must be a valid HTTP header name"
+                "{scope} auth.api_key.header_name must be a valid HTTP header name"
             )));
         }
         if api_key.keys.is_empty() || api_key.keys.iter().any(|value| value.trim().is_empty()) {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.api_key.keys must contain at least one non-empty key"
+                "{scope} auth.api_key.keys must contain at least one non-empty key"
             )));
         }
         let mut seen_api_keys = std::collections::HashSet::new();
         for key in &api_key.keys {
             if !seen_api_keys.insert(key.trim().to_string()) {
                 return Err(RuntimeConfigError::ConfigInvalid(format!(
-                    "upstream '{upstream_name}' auth.api_key.keys contains duplicate values"
+                    "{scope} auth.api_key.keys contains duplicate values"
                 )));
             }
         }
     }
 
-    if let Some(external_auth) = upstream.auth.external_auth.as_ref() {
-        if upstream.auth.api_key.is_some() || upstream.auth.jwt.is_some() {
+    if let Some(external_auth) = auth.external_auth.as_ref() {
+        if auth.api_key.is_some() || auth.jwt.is_some() {
             return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
-                "upstream '{upstream_name}' auth.external_auth cannot be combined with auth.api_key or auth.jwt in v1"
+                "{scope} auth.external_auth cannot be combined with auth.api_key or auth.jwt in v1"
             )));
         }
-        if !upstream.auth.required_scopes.is_empty() || !upstream.auth.required_roles.is_empty() {
+        if !auth.required_scopes.is_empty() || !auth.required_roles.is_empty() {
             return Err(RuntimeConfigError::UnsupportedPolicyCombination(format!(
-                "upstream '{upstream_name}' auth.external_auth cannot be combined with auth.required_scopes or auth.required_roles in v1"
+                "{scope} auth.external_auth cannot be combined with auth.required_scopes or auth.required_roles in v1"
             )));
         }
 
@@ -387,18 +1475,18 @@ fn validate_upstream_policy(
                     });
                 if !valid_endpoint {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.http.endpoint must be an absolute http(s) URL"
+                        "{scope} auth.external_auth.http.endpoint must be an absolute http(s) URL"
                     )));
                 }
                 validate_runtime_external_auth_headers(
-                    upstream_name,
+                    scope,
                     "auth.external_auth.http",
                     request_headers,
                     response_header_allowlist,
                 )?;
                 if *timeout_ms == 0 {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.http.timeout_ms must be greater than 0"
+                        "{scope} auth.external_auth.http.timeout_ms must be greater than 0"
                     )));
                 }
             }
@@ -422,7 +1510,7 @@ fn validate_upstream_policy(
                     .is_some_and(|value| !value.trim().is_empty());
                 if !has_discovery_url && !has_issuer_url {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc requires discovery_url or issuer_url"
+                        "{scope} auth.external_auth.oidc requires discovery_url or issuer_url"
                     )));
                 }
                 if let Some(discovery_url) = discovery_url.as_deref() {
@@ -436,7 +1524,7 @@ fn validate_upstream_policy(
                         });
                     if !discovery_url.trim().is_empty() && !valid_discovery_url {
                         return Err(RuntimeConfigError::ConfigInvalid(format!(
-                            "upstream '{upstream_name}' auth.external_auth.oidc.discovery_url must be an absolute http(s) URL"
+                            "{scope} auth.external_auth.oidc.discovery_url must be an absolute http(s) URL"
                         )));
                     }
                 }
@@ -452,13 +1540,13 @@ fn validate_upstream_policy(
                             });
                     if !issuer_url.trim().is_empty() && !valid_issuer_url {
                         return Err(RuntimeConfigError::ConfigInvalid(format!(
-                            "upstream '{upstream_name}' auth.external_auth.oidc.issuer_url must be an absolute http(s) URL"
+                            "{scope} auth.external_auth.oidc.issuer_url must be an absolute http(s) URL"
                         )));
                     }
                 }
                 if client_id.trim().is_empty() {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc.client_id must be non-empty"
+                        "{scope} auth.external_auth.oidc.client_id must be non-empty"
                     )));
                 }
                 if client_secret
@@ -466,7 +1554,7 @@ fn validate_upstream_policy(
                     .is_some_and(|value| value.trim().is_empty())
                 {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc.client_secret must be non-empty when provided"
+                        "{scope} auth.external_auth.oidc.client_secret must be non-empty when provided"
                     )));
                 }
                 if audience
@@ -474,33 +1562,33 @@ fn validate_upstream_policy(
                     .is_some_and(|value| value.trim().is_empty())
                 {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc.audience must be non-empty when provided"
+                        "{scope} auth.external_auth.oidc.audience must be non-empty when provided"
                     )));
                 }
                 if scopes.iter().any(|scope| scope.trim().is_empty()) {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc.scopes must not contain empty values"
+                        "{scope} auth.external_auth.oidc.scopes must not contain empty values"
                     )));
                 }
                 validate_runtime_external_auth_headers(
-                    upstream_name,
+                    scope,
                     "auth.external_auth.oidc",
                     request_headers,
                     response_header_allowlist,
                 )?;
                 if *timeout_ms == 0 {
                     return Err(RuntimeConfigError::ConfigInvalid(format!(
-                        "upstream '{upstream_name}' auth.external_auth.oidc.timeout_ms must be greater than 0"
+                        "{scope} auth.external_auth.oidc.timeout_ms must be greater than 0"
                     )));
                 }
             }
         }
     }
 
-    if let Some(jwt) = upstream.auth.jwt.as_ref() {
+    if let Some(jwt) = auth.jwt.as_ref() {
         if jwt.secret.trim().is_empty() {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.jwt.secret must be non-empty"
+                "{scope} auth.jwt.secret must be non-empty"
             )));
         }
         if jwt
@@ -509,7 +1597,7 @@ fn validate_upstream_policy(
             .is_some_and(|value| value.trim().is_empty())
         {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.jwt.issuer must be non-empty when provided"
+                "{scope} auth.jwt.issuer must be non-empty when provided"
             )));
         }
         if jwt
@@ -518,35 +1606,31 @@ fn validate_upstream_policy(
             .is_some_and(|value| value.trim().is_empty())
         {
             return Err(RuntimeConfigError::ConfigInvalid(format!(
-                "upstream '{upstream_name}' auth.jwt.audience must be non-empty when provided"
+                "{scope} auth.jwt.audience must be non-empty when provided"
             )));
         }
     }
-    if upstream
-        .auth
+    if auth
         .required_scopes
         .iter()
         .any(|value| value.trim().is_empty())
     {
         return Err(RuntimeConfigError::ConfigInvalid(format!(
-            "upstream '{upstream_name}' auth.required_scopes must not contain empty values"
+            "{scope} auth.required_scopes must not contain empty values"
         )));
     }
-    if upstream
-        .auth
+    if auth
         .required_roles
         .iter()
         .any(|value| value.trim().is_empty())
     {
         return Err(RuntimeConfigError::ConfigInvalid(format!(
-            "upstream '{upstream_name}' auth.required_roles must not contain empty values"
+            "{scope} auth.required_roles must not contain empty values"
         )));
     }
-    if (!upstream.auth.required_scopes.is_empty() || !upstream.auth.required_roles.is_empty())
-        && upstream.auth.jwt.is_none()
-    {
+    if (!auth.required_scopes.is_empty() || !auth.required_roles.is_empty()) && auth.jwt.is_none() {
         return Err(RuntimeConfigError::ConfigInvalid(format!(
-            "upstream '{upstream_name}' auth.required_scopes/auth.required_roles require auth.jwt"
+            "{scope} auth.required_scopes/auth.required_roles require auth.jwt"
         )));
     }
 
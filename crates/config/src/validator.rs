@@ -6,9 +6,17 @@ use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
 use crate::{
     backend_endpoint::{BackendEndpoint, BackendScheme},
     config::{
-        CURRENT_CONFIG_VERSION, Config, ExternalAuth, Listen, SUPPORTED_CONFIG_VERSIONS,
-        ScopedRateLimitScope, UpstreamHostPolicyMode, UpstreamTls,
+        AdaptiveAdmission, AdaptiveAdmissionAlgorithm, CURRENT_CONFIG_VERSION, CanaryRollout,
+        Config, CookieSameSite, DirectResponse, ExternalAuth, Hedging, HedgingMode, Listen,
+        LoadBalancing, MAX_DIRECT_RESPONSE_BODY_BYTES, MAX_HEDGES_PER_REQUEST,
+        MAX_MAGLEV_TABLE_SIZE, MAX_MIRROR_BODY_BYTES, MAX_P2C_CHOICES, MAX_ROUTE_RETRY_ATTEMPTS,
+        OutlierDetection, PathRewrite, Priority, RETRYABLE_STATUS_RANGE, RetryOn, Route, RouteAuth,
+        RouteFault, RouteMatch, RouteMirror, RoutePolicy, RouteRedirect, SUPPORTED_CONFIG_VERSIONS,
+        ScopedRateLimitScope, SessionAffinity, SlowStart, TrafficSplit, UpstreamHostPolicy,
+        UpstreamHostPolicyMode, UpstreamTls, VALID_REDIRECT_STATUSES, custom_lb_name,
+        effective_routes, is_prime,
     },
+    default::{route_default_retry_backoff_base_ms, route_default_retry_backoff_max_ms},
 };
 
 #[path = "validator/helpers.rs"]
@@ -38,12 +46,19 @@ pub const VALID_LB_TYPES: &[&str] = &[
     "consistent-hash",
     "consistent_hash",
     "ch",
+    "consistent-hash-bounded",
+    "consistent_hash_bounded",
+    "chb",
+    "maglev",
     "least-connections",
     "least_connections",
     "lc",
     "latency-aware",
     "latency_aware",
     "la",
+    "p2c",
+    "power-of-two-choices",
+    "power_of_two_choices",
     "sticky-cid",
     "sticky_cid",
     "cid-sticky",
@@ -103,7 +118,7 @@ macro_rules! validation_error {
 type RouteMatcherKey = (Option<String>, Option<String>, Option<String>);
 
 fn validate_external_auth_headers(
-    upstream_name: &str,
+    scope: &str,
     field_prefix: &str,
     request_headers: &[crate::config::ExternalAuthRequestHeader],
     response_header_allowlist: &[String],
@@ -113,8 +128,8 @@ fn validate_external_auth_headers(
         let header_name = header.name.trim();
         if header_name.is_empty() {
             validation_error!(
-                "upstream '{}' {}.request_headers[{}].name must be non-empty",
-                upstream_name,
+                "{} {}.request_headers[{}].name must be non-empty",
+                scope,
                 field_prefix,
                 idx
             );
@@ -122,8 +137,8 @@ fn validate_external_auth_headers(
         }
         if http::header::HeaderName::from_bytes(header_name.as_bytes()).is_err() {
             validation_error!(
-                "upstream '{}' {}.request_headers[{}].name must be a valid HTTP header name",
-                upstream_name,
+                "{} {}.request_headers[{}].name must be a valid HTTP header name",
+                scope,
                 field_prefix,
                 idx
             );
@@ -131,8 +146,8 @@ fn validate_external_auth_headers(
         }
         if http::HeaderValue::from_str(header.value.as_str()).is_err() {
             validation_error!(
-                "upstream '{}' {}.request_headers[{}].value must be a valid HTTP header value",
-                upstream_name,
+                "{} {}.request_headers[{}].value must be a valid HTTP header value",
+                scope,
                 field_prefix,
                 idx
             );
@@ -141,8 +156,8 @@ fn validate_external_auth_headers(
         let normalized_name = header_name.to_ascii_lowercase();
         if !seen_request_headers.insert(normalized_name) {
             validation_error!(
-                "upstream '{}' {}.request_headers contains duplicate header names",
-                upstream_name,
+                "{} {}.request_headers contains duplicate header names",
+                scope,
                 field_prefix
             );
             return false;
@@ -154,8 +169,8 @@ fn validate_external_auth_headers(
         let header_name = header_name.trim();
         if header_name.is_empty() {
             validation_error!(
-                "upstream '{}' {}.response_header_allowlist[{}] must be non-empty",
-                upstream_name,
+                "{} {}.response_header_allowlist[{}] must be non-empty",
+                scope,
                 field_prefix,
                 idx
             );
@@ -163,8 +178,8 @@ fn validate_external_auth_headers(
         }
         if http::header::HeaderName::from_bytes(header_name.as_bytes()).is_err() {
             validation_error!(
-                "upstream '{}' {}.response_header_allowlist[{}] must be a valid HTTP header name",
-                upstream_name,
+                "{} {}.response_header_allowlist[{}] must be a valid HTTP header name",
+                scope,
                 field_prefix,
                 idx
             );
@@ -173,8 +188,8 @@ fn validate_external_auth_headers(
         let normalized_name = header_name.to_ascii_lowercase();
         if !seen_allowed_headers.insert(normalized_name) {
             validation_error!(
-                "upstream '{}' {}.response_header_allowlist contains duplicate header names",
-                upstream_name,
+                "{} {}.response_header_allowlist contains duplicate header names",
+                scope,
                 field_prefix
             );
             return false;
@@ -184,6 +199,1393 @@ fn validate_external_auth_headers(
     true
 }
 
+fn validate_route_match(scope: &str, route: &RouteMatch) -> bool {
+    if route.host.is_none() && route.path_prefix.is_none() {
+        validation_error!(
+            "Route for {} must have either 'host' or 'path_prefix' matcher",
+            scope
+        );
+        return false;
+    }
+
+    if let Some(ref path) = route.path_prefix {
+        if path.is_empty() {
+            validation_error!("Route path_prefix cannot be empty for {}", scope);
+            return false;
+        }
+        if !path.starts_with('/') {
+            validation_error!(
+                "Route path_prefix must start with '/' for {}: {}",
+                scope,
+                path
+            );
+            return false;
+        }
+    }
+
+    true
+}
+
+fn validate_path_rewrite(scope: &str, rewrite: &PathRewrite) -> bool {
+    let rules = [
+        rewrite.strip_prefix.is_some(),
+        rewrite.replace_prefix.is_some(),
+        rewrite.regex.is_some(),
+    ];
+    if rules.iter().filter(|set| **set).count() != 1 {
+        validation_error!(
+            "{} rewrite must set exactly one of strip_prefix, replace_prefix, or regex",
+            scope
+        );
+        return false;
+    }
+
+    if let Some(prefix) = rewrite.strip_prefix.as_deref()
+        && !valid_rewrite_prefix(prefix)
+    {
+        validation_error!(
+            "{} rewrite.strip_prefix must start with '/' and not be '/': {}",
+            scope,
+            prefix
+        );
+        return false;
+    }
+    if let Some(replace) = rewrite.replace_prefix.as_ref() {
+        if !valid_rewrite_prefix(&replace.prefix) {
+            validation_error!(
+                "{} rewrite.replace_prefix.prefix must start with '/' and not be '/': {}",
+                scope,
+                replace.prefix
+            );
+            return false;
+        }
+        if !replace.replacement.starts_with('/') {
+            validation_error!(
+                "{} rewrite.replace_prefix.replacement must start with '/': {}",
+                scope,
+                replace.replacement
+            );
+            return false;
+        }
+    }
+    if let Some(regex) = rewrite.regex.as_ref() {
+        if let Err(err) = regex::Regex::new(&regex.pattern) {
+            validation_error!(
+                "{} rewrite.regex.pattern is not a valid regex: {}",
+                scope,
+                err
+            );
+            return false;
+        }
+        if regex.substitution.is_empty() {
+            validation_error!("{} rewrite.regex.substitution cannot be empty", scope);
+            return false;
+        }
+    }
+
+    true
+}
+
+fn valid_rewrite_prefix(prefix: &str) -> bool {
+    prefix.starts_with('/') && !prefix.trim_end_matches('/').is_empty()
+}
+
+/// Checks that a route either targets an upstream or carries exactly one edge
+/// action, and that action-only routes set no forwarding options.
+fn validate_route_target(config: &Config, scope: &str, route: &Route) -> bool {
+    if !route.has_action() {
+        if route.upstream.is_empty() {
+            validation_error!(
+                "{} must set one of upstream, redirect, or direct_response",
+                scope
+            );
+            return false;
+        }
+        if !config.upstream.contains_key(&route.upstream) {
+            validation_error!("{} references unknown upstream '{}'", scope, route.upstream);
+            return false;
+        }
+        return true;
+    }
+
+    if !route.upstream.is_empty() || (route.redirect.is_some() && route.direct_response.is_some()) {
+        validation_error!(
+            "{} must set only one of upstream, redirect, or direct_response",
+            scope
+        );
+        return false;
+    }
+    let forwarding_options = [
+        ("split", route.split.is_some()),
+        ("mirror", route.mirror.is_some()),
+        ("fault", route.fault.is_some()),
+        ("policy", route.policy.is_some()),
+        ("host_policy", route.host_policy.is_some()),
+        ("forwarded_headers", route.forwarded_headers.is_some()),
+        (
+            "rewrite",
+            route.rewrite.is_some() && route.direct_response.is_some(),
+        ),
+        (
+            "auth.external_auth",
+            route
+                .auth
+                .as_ref()
+                .is_some_and(|auth| auth.external_auth.is_some()),
+        ),
+    ];
+    if let Some((field, _)) = forwarding_options.iter().find(|(_, set)| *set) {
+        validation_error!("{} is answered by the edge and cannot set {}", scope, field);
+        return false;
+    }
+
+    match (route.redirect.as_ref(), route.direct_response.as_ref()) {
+        (Some(redirect), None) => validate_route_redirect(scope, redirect),
+        (None, Some(direct)) => validate_direct_response(scope, direct),
+        _ => true,
+    }
+}
+
+fn validate_route_redirect(scope: &str, redirect: &RouteRedirect) -> bool {
+    if !VALID_REDIRECT_STATUSES.contains(&redirect.status) {
+        validation_error!(
+            "{} redirect.status must be one of {:?}: {}",
+            scope,
+            VALID_REDIRECT_STATUSES,
+            redirect.status
+        );
+        return false;
+    }
+    if let Some(scheme) = redirect.scheme.as_deref()
+        && scheme != "http"
+        && scheme != "https"
+    {
+        validation_error!(
+            "{} redirect.scheme must be http or https: {}",
+            scope,
+            scheme
+        );
+        return false;
+    }
+    if let Some(host) = redirect.host.as_deref()
+        && !valid_static_host_header(host)
+    {
+        validation_error!("{} redirect.host is not a valid host: {}", scope, host);
+        return false;
+    }
+    if let Some(path) = redirect.path.as_deref()
+        && (!path.starts_with('/') || !valid_redirect_component(path))
+    {
+        validation_error!(
+            "{} redirect.path must start with '/' and hold no '?', '#' or whitespace: {}",
+            scope,
+            path
+        );
+        return false;
+    }
+    if let Some(query) = redirect.query.as_deref()
+        && (query.starts_with('?') || !valid_redirect_component(query))
+    {
+        validation_error!(
+            "{} redirect.query must not start with '?' or hold '#' or whitespace: {}",
+            scope,
+            query
+        );
+        return false;
+    }
+
+    true
+}
+
+fn valid_redirect_component(value: &str) -> bool {
+    !value.contains('#')
+        && !value.contains('?')
+        && !value.chars().any(|ch| ch.is_whitespace())
+        && http::HeaderValue::from_str(value).is_ok()
+}
+
+fn validate_direct_response(scope: &str, direct: &DirectResponse) -> bool {
+    if !(200..=599).contains(&direct.status) {
+        validation_error!(
+            "{} direct_response.status must be within 200..=599: {}",
+            scope,
+            direct.status
+        );
+        return false;
+    }
+    for header in &direct.headers {
+        let Ok(name) = http::HeaderName::from_bytes(header.name.as_bytes()) else {
+            validation_error!(
+                "{} direct_response header name is invalid: {}",
+                scope,
+                header.name
+            );
+            return false;
+        };
+        if name == http::header::CONTENT_LENGTH || name == http::header::TRANSFER_ENCODING {
+            validation_error!("{} direct_response cannot set the {} header", scope, name);
+            return false;
+        }
+        if http::HeaderValue::from_str(&header.value).is_err() {
+            validation_error!(
+                "{} direct_response header '{}' has an invalid value",
+                scope,
+                header.name
+            );
+            return false;
+        }
+    }
+
+    match (direct.body.as_deref(), direct.body_file.as_deref()) {
+        (Some(_), Some(_)) => {
+            validation_error!(
+                "{} direct_response must set at most one of body and body_file",
+                scope
+            );
+            false
+        }
+        (Some(body), None) if body.len() > MAX_DIRECT_RESPONSE_BODY_BYTES => {
+            validation_error!(
+                "{} direct_response.body exceeds {} bytes",
+                scope,
+                MAX_DIRECT_RESPONSE_BODY_BYTES
+            );
+            false
+        }
+        (None, Some(path)) => match std::fs::metadata(path) {
+            Ok(metadata) if !metadata.is_file() => {
+                validation_error!(
+                    "{} direct_response.body_file is not a file: {}",
+                    scope,
+                    path
+                );
+                false
+            }
+            Ok(metadata) if metadata.len() > MAX_DIRECT_RESPONSE_BODY_BYTES as u64 => {
+                validation_error!(
+                    "{} direct_response.body_file exceeds {} bytes: {}",
+                    scope,
+                    MAX_DIRECT_RESPONSE_BODY_BYTES,
+                    path
+                );
+                false
+            }
+            Ok(_) => true,
+            Err(err) => {
+                validation_error!(
+                    "{} direct_response.body_file '{}' cannot be read: {}",
+                    scope,
+                    path,
+                    err
+                );
+                false
+            }
+        },
+        _ => true,
+    }
+}
+
+fn validate_host_policy(field_prefix: &str, host_policy: &UpstreamHostPolicy) -> bool {
+    match host_policy.mode {
+        UpstreamHostPolicyMode::PassThrough | UpstreamHostPolicyMode::Upstream => {
+            if host_policy.host.is_some() {
+                validation_error!(
+                    "{}.host_policy.host is invalid unless mode is rewrite",
+                    field_prefix
+                );
+                return false;
+            }
+        }
+        UpstreamHostPolicyMode::Rewrite => match host_policy.host.as_deref() {
+            Some(host) if valid_static_host_header(host) => {}
+            _ => {
+                validation_error!(
+                    "{}.host_policy.mode=rewrite requires a valid non-empty host_policy.host",
+                    field_prefix
+                );
+                return false;
+            }
+        },
+    }
+
+    true
+}
+
+fn validate_traffic_split(
+    config: &Config,
+    scope: &str,
+    primary: &str,
+    split: &TrafficSplit,
+) -> bool {
+    if split.targets.is_empty() {
+        validation_error!("{} split.targets must not be empty", scope);
+        return false;
+    }
+    if let Some(key) = split.key.as_deref()
+        && !is_valid_request_key_spec(key)
+    {
+        validation_error!("{} split.key '{}' is not a valid request key", scope, key);
+        return false;
+    }
+
+    let mut seen_targets = std::collections::HashSet::new();
+    let mut total_weight = 0u64;
+    for target in &split.targets {
+        if !config.upstream.contains_key(&target.upstream) {
+            validation_error!(
+                "{} split target references unknown upstream '{}'",
+                scope,
+                target.upstream
+            );
+            return false;
+        }
+        if !seen_targets.insert(target.upstream.as_str()) {
+            validation_error!(
+                "{} split targets upstream '{}' more than once",
+                scope,
+                target.upstream
+            );
+            return false;
+        }
+        total_weight += u64::from(target.weight);
+    }
+    if total_weight == 0 {
+        validation_error!("{} split weights must not all be zero", scope);
+        return false;
+    }
+    if !seen_targets.contains(primary) {
+        validation_error!(
+            "{} split.targets must include the route upstream '{}'",
+            scope,
+            primary
+        );
+        return false;
+    }
+    if let Some(rollout) = split.rollout.as_ref()
+        && !validate_canary_rollout(scope, &seen_targets, rollout)
+    {
+        return false;
+    }
+
+    true
+}
+
+fn validate_canary_rollout(
+    scope: &str,
+    split_targets: &std::collections::HashSet<&str>,
+    rollout: &CanaryRollout,
+) -> bool {
+    if !split_targets.contains(rollout.canary.as_str()) {
+        validation_error!(
+            "{} split.rollout.canary '{}' must be one of the split targets",
+            scope,
+            rollout.canary
+        );
+        return false;
+    }
+    if split_targets.len() < 2 {
+        validation_error!(
+            "{} split.rollout needs at least one baseline target besides the canary",
+            scope
+        );
+        return false;
+    }
+    if rollout.steps.is_empty() {
+        validation_error!("{} split.rollout.steps must not be empty", scope);
+        return false;
+    }
+    let mut previous = 0;
+    for step in &rollout.steps {
+        if *step == 0 || *step > 100 || *step <= previous {
+            validation_error!(
+                "{} split.rollout.steps must be strictly increasing percentages in 1..=100",
+                scope
+            );
+            return false;
+        }
+        previous = *step;
+    }
+    if rollout.step_interval_ms == 0 {
+        validation_error!(
+            "{} split.rollout.step_interval_ms must be greater than 0",
+            scope
+        );
+        return false;
+    }
+    if rollout.max_error_rate_increase_percent > 100 {
+        validation_error!(
+            "{} split.rollout.max_error_rate_increase_percent must be <= 100",
+            scope
+        );
+        return false;
+    }
+    if rollout.max_p95_latency_ratio_percent < 100 {
+        validation_error!(
+            "{} split.rollout.max_p95_latency_ratio_percent must be >= 100",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_adaptive_admission(
+    scope: &str,
+    adaptive: &AdaptiveAdmission,
+    limit_name: &str,
+    inflight_limit: usize,
+) -> bool {
+    if adaptive.min_limit == 0 {
+        validation_error!("{}.min_limit must be greater than 0", scope);
+        return false;
+    }
+    if let Some(max_limit) = adaptive.max_limit {
+        if max_limit == 0 {
+            validation_error!("{}.max_limit must be greater than 0", scope);
+            return false;
+        }
+        if max_limit < adaptive.min_limit {
+            validation_error!(
+                "{}.max_limit ({}) must be >= min_limit ({})",
+                scope,
+                max_limit,
+                adaptive.min_limit
+            );
+            return false;
+        }
+        if max_limit > inflight_limit {
+            validation_error!(
+                "{}.max_limit ({}) must be <= {} ({})",
+                scope,
+                max_limit,
+                limit_name,
+                inflight_limit
+            );
+            return false;
+        }
+    }
+
+    if adaptive.decrease_step == 0 {
+        validation_error!("{}.decrease_step must be greater than 0", scope);
+        return false;
+    }
+
+    if adaptive.increase_step == 0 {
+        validation_error!("{}.increase_step must be greater than 0", scope);
+        return false;
+    }
+
+    if adaptive.algorithm == AdaptiveAdmissionAlgorithm::Gradient {
+        if adaptive.min_rtt_window_ms == 0 {
+            validation_error!("{}.min_rtt_window_ms must be greater than 0", scope);
+            return false;
+        }
+        if adaptive.rtt_tolerance_percent < 100 {
+            validation_error!(
+                "{}.rtt_tolerance_percent ({}) must be >= 100",
+                scope,
+                adaptive.rtt_tolerance_percent
+            );
+            return false;
+        }
+        if !(1..=100).contains(&adaptive.smoothing_percent) {
+            validation_error!(
+                "{}.smoothing_percent ({}) must be in range 1-100",
+                scope,
+                adaptive.smoothing_percent
+            );
+            return false;
+        }
+        if adaptive.queue_size == 0 {
+            validation_error!("{}.queue_size must be greater than 0", scope);
+            return false;
+        }
+    }
+
+    true
+}
+
+fn validate_hedging(hedging: &Hedging) -> bool {
+    if hedging.enabled && hedging.delay_ms == 0 {
+        validation_error!(
+            "resilience.hedging.delay_ms must be greater than 0 when hedging is enabled"
+        );
+        return false;
+    }
+    if hedging.window_ms == 0 {
+        validation_error!("resilience.hedging.window_ms must be greater than 0");
+        return false;
+    }
+    if !(1..=100).contains(&hedging.max_hedged_percent) {
+        validation_error!(
+            "resilience.hedging.max_hedged_percent ({}) must be in range 1-100",
+            hedging.max_hedged_percent
+        );
+        return false;
+    }
+    if !(1..=MAX_HEDGES_PER_REQUEST).contains(&hedging.max_hedges) {
+        validation_error!(
+            "resilience.hedging.max_hedges ({}) must be in range 1-{}",
+            hedging.max_hedges,
+            MAX_HEDGES_PER_REQUEST
+        );
+        return false;
+    }
+
+    if hedging.mode == HedgingMode::Percentile {
+        if !(1..=99).contains(&hedging.percentile) {
+            validation_error!(
+                "resilience.hedging.percentile ({}) must be in range 1-99",
+                hedging.percentile
+            );
+            return false;
+        }
+        if hedging.min_delay_ms == 0 {
+            validation_error!("resilience.hedging.min_delay_ms must be greater than 0");
+            return false;
+        }
+        if hedging.max_delay_ms < hedging.min_delay_ms {
+            validation_error!(
+                "resilience.hedging.max_delay_ms ({}) must be >= min_delay_ms ({})",
+                hedging.max_delay_ms,
+                hedging.min_delay_ms
+            );
+            return false;
+        }
+        if hedging.min_samples == 0 {
+            validation_error!("resilience.hedging.min_samples must be greater than 0");
+            return false;
+        }
+    }
+
+    true
+}
+
+fn validate_priority(priority: &Priority) -> bool {
+    let default_class = priority.default_class.trim();
+    if default_class.is_empty() {
+        validation_error!("resilience.priority.default_class must be non-empty");
+        return false;
+    }
+
+    let mut class_names = std::collections::HashSet::new();
+    for class in &priority.classes {
+        let name = class.name.trim();
+        if name.is_empty() {
+            validation_error!("resilience.priority.classes[].name must be non-empty");
+            return false;
+        }
+        if !class_names.insert(name) {
+            validation_error!(
+                "resilience.priority.classes contains duplicate class '{}'",
+                name
+            );
+            return false;
+        }
+        if !(1..=100).contains(&class.max_inflight_percent) {
+            validation_error!(
+                "resilience.priority.classes['{}'].max_inflight_percent must be in range 1-100",
+                name
+            );
+            return false;
+        }
+    }
+    if class_names.is_empty() {
+        class_names.insert(default_class);
+    } else if !class_names.contains(default_class) {
+        validation_error!(
+            "resilience.priority.default_class '{}' is not a configured class",
+            default_class
+        );
+        return false;
+    }
+
+    for (index, rule) in priority.rules.iter().enumerate() {
+        if !class_names.contains(rule.class.trim()) {
+            validation_error!(
+                "resilience.priority.rules[{}].class '{}' is not a configured class",
+                index,
+                rule.class.trim()
+            );
+            return false;
+        }
+        if rule.routes.iter().any(|route| route.trim().is_empty()) {
+            validation_error!(
+                "resilience.priority.rules[{}].routes must not contain empty values",
+                index
+            );
+            return false;
+        }
+        if rule.values.iter().any(|value| value.trim().is_empty()) {
+            validation_error!(
+                "resilience.priority.rules[{}].values must not contain empty values",
+                index
+            );
+            return false;
+        }
+        let header = rule
+            .header
+            .as_deref()
+            .is_some_and(|header| !header.trim().is_empty());
+        let jwt_claim = rule
+            .jwt_claim
+            .as_deref()
+            .is_some_and(|claim| !claim.trim().is_empty());
+        if header && jwt_claim {
+            validation_error!(
+                "resilience.priority.rules[{}] must set only one of header or jwt_claim",
+                index
+            );
+            return false;
+        }
+        if !header && !jwt_claim {
+            if rule.routes.is_empty() {
+                validation_error!(
+                    "resilience.priority.rules[{}] must match on routes, header or jwt_claim",
+                    index
+                );
+                return false;
+            }
+            if !rule.values.is_empty() {
+                validation_error!(
+                    "resilience.priority.rules[{}].values requires header or jwt_claim",
+                    index
+                );
+                return false;
+            }
+        } else if rule.values.is_empty() {
+            validation_error!(
+                "resilience.priority.rules[{}].values must be non-empty when header or jwt_claim is set",
+                index
+            );
+            return false;
+        }
+    }
+
+    true
+}
+
+/// A built-in strategy or `custom:<name>`. Custom names are resolved against
+/// the balancer registry when the upstream pool is built.
+fn is_valid_lb_type(lb_type: &str) -> bool {
+    VALID_LB_TYPES
+        .iter()
+        .any(|valid| valid.eq_ignore_ascii_case(lb_type))
+        || custom_lb_name(lb_type).is_some()
+}
+
+fn validate_load_balancing_options(
+    scope: &str,
+    lb: &LoadBalancing,
+    backend_count: Option<usize>,
+) -> bool {
+    let lb_type = lb.lb_type.trim().to_ascii_lowercase().replace('_', "-");
+    if let Some(table_size) = lb.table_size {
+        if lb_type != "maglev" {
+            validation_error!(
+                "{} load_balancing.table_size only applies to 'maglev'",
+                scope
+            );
+            return false;
+        }
+        if table_size > MAX_MAGLEV_TABLE_SIZE || !is_prime(table_size) {
+            validation_error!(
+                "{} load_balancing.table_size ({}) must be a prime no larger than {}",
+                scope,
+                table_size,
+                MAX_MAGLEV_TABLE_SIZE
+            );
+            return false;
+        }
+        if backend_count.is_some_and(|count| table_size < count as u64) {
+            validation_error!(
+                "{} load_balancing.table_size ({}) must be at least the number of backends",
+                scope,
+                table_size
+            );
+            return false;
+        }
+    }
+    if let Some(factor) = lb.hash_balance_factor {
+        if !matches!(lb_type.as_str(), "consistent-hash-bounded" | "chb") {
+            validation_error!(
+                "{} load_balancing.hash_balance_factor only applies to 'consistent-hash-bounded'",
+                scope
+            );
+            return false;
+        }
+        if factor < 100 {
+            validation_error!(
+                "{} load_balancing.hash_balance_factor ({}) must be at least 100",
+                scope,
+                factor
+            );
+            return false;
+        }
+    }
+    if let Some(p2c) = &lb.p2c {
+        if !matches!(lb_type.as_str(), "p2c" | "power-of-two-choices") {
+            validation_error!("{} load_balancing.p2c only applies to 'p2c'", scope);
+            return false;
+        }
+        if !(2..=MAX_P2C_CHOICES).contains(&p2c.choices) {
+            validation_error!(
+                "{} load_balancing.p2c.choices ({}) must be in range 2-{}",
+                scope,
+                p2c.choices,
+                MAX_P2C_CHOICES
+            );
+            return false;
+        }
+    }
+    if let Some(threshold) = lb.panic_threshold_percent
+        && threshold > 100
+    {
+        validation_error!(
+            "{} load_balancing.panic_threshold_percent must be in range 0-100",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_slow_start(scope: &str, slow_start: &SlowStart) -> bool {
+    if slow_start.window_ms == 0 {
+        validation_error!("{} slow_start.window_ms must be greater than 0", scope);
+        return false;
+    }
+    if !(1..=100).contains(&slow_start.min_weight_percent) {
+        validation_error!(
+            "{} slow_start.min_weight_percent must be in range 1-100",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_session_affinity(scope: &str, affinity: &SessionAffinity) -> bool {
+    if !affinity.has_valid_cookie_name() {
+        validation_error!(
+            "{} session_affinity.cookie_name must be a valid cookie name",
+            scope
+        );
+        return false;
+    }
+    if !affinity.has_valid_path() {
+        validation_error!(
+            "{} session_affinity.path must start with '/' and contain no spaces, control characters or ';'",
+            scope
+        );
+        return false;
+    }
+    if affinity.ttl_secs == Some(0) {
+        validation_error!("{} session_affinity.ttl_secs must be greater than 0", scope);
+        return false;
+    }
+    if affinity.same_site == CookieSameSite::None && !affinity.secure {
+        validation_error!("{} session_affinity.same_site none requires secure", scope);
+        return false;
+    }
+    if affinity
+        .secret
+        .as_ref()
+        .is_some_and(|secret| secret.trim().is_empty())
+    {
+        validation_error!(
+            "{} session_affinity.secret must be non-empty when provided",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_outlier_detection(scope: &str, detection: &OutlierDetection) -> bool {
+    if detection.interval_ms == 0 {
+        validation_error!(
+            "{} outlier_detection.interval_ms must be greater than 0",
+            scope
+        );
+        return false;
+    }
+    if detection.min_requests == 0 {
+        validation_error!(
+            "{} outlier_detection.min_requests must be greater than 0",
+            scope
+        );
+        return false;
+    }
+    if detection.min_backends < 2 {
+        validation_error!("{} outlier_detection.min_backends must be >= 2", scope);
+        return false;
+    }
+    if detection.success_rate_min_percent.is_none()
+        && detection.success_rate_stdev_factor.is_none()
+        && detection.latency_p95_ratio_percent.is_none()
+    {
+        validation_error!(
+            "{} outlier_detection needs success_rate_min_percent, success_rate_stdev_factor or latency_p95_ratio_percent",
+            scope
+        );
+        return false;
+    }
+    if detection
+        .success_rate_min_percent
+        .is_some_and(|percent| percent > 100)
+    {
+        validation_error!(
+            "{} outlier_detection.success_rate_min_percent must be <= 100",
+            scope
+        );
+        return false;
+    }
+    if detection
+        .success_rate_stdev_factor
+        .is_some_and(|factor| !factor.is_finite() || factor <= 0.0)
+    {
+        validation_error!(
+            "{} outlier_detection.success_rate_stdev_factor must be a positive number",
+            scope
+        );
+        return false;
+    }
+    if detection
+        .latency_p95_ratio_percent
+        .is_some_and(|percent| percent < 100)
+    {
+        validation_error!(
+            "{} outlier_detection.latency_p95_ratio_percent must be >= 100",
+            scope
+        );
+        return false;
+    }
+    if detection.max_ejection_percent > 100 {
+        validation_error!(
+            "{} outlier_detection.max_ejection_percent must be <= 100",
+            scope
+        );
+        return false;
+    }
+    if detection.base_ejection_ms == 0 {
+        validation_error!(
+            "{} outlier_detection.base_ejection_ms must be greater than 0",
+            scope
+        );
+        return false;
+    }
+    if detection.max_ejection_ms < detection.base_ejection_ms {
+        validation_error!(
+            "{} outlier_detection.max_ejection_ms must be >= base_ejection_ms",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_route_mirror(
+    config: &Config,
+    scope: &str,
+    route: &Route,
+    mirror: &RouteMirror,
+) -> bool {
+    if !config.upstream.contains_key(&mirror.upstream) {
+        validation_error!(
+            "{} mirror references unknown upstream '{}'",
+            scope,
+            mirror.upstream
+        );
+        return false;
+    }
+    if mirror.upstream == route.upstream {
+        validation_error!(
+            "{} mirror.upstream must differ from the route upstream '{}'",
+            scope,
+            route.upstream
+        );
+        return false;
+    }
+    if mirror.percent == 0 || mirror.percent > 100 {
+        validation_error!("{} mirror.percent must be in 1..=100", scope);
+        return false;
+    }
+    for method in &mirror.methods {
+        if !is_valid_http_token(method.trim()) {
+            validation_error!("{} mirror.methods has invalid method '{}'", scope, method);
+            return false;
+        }
+        if method.trim().eq_ignore_ascii_case("CONNECT") {
+            validation_error!("{} mirror.methods cannot include CONNECT", scope);
+            return false;
+        }
+    }
+    if mirror.max_body_bytes > MAX_MIRROR_BODY_BYTES {
+        validation_error!(
+            "{} mirror.max_body_bytes must be <= {}",
+            scope,
+            MAX_MIRROR_BODY_BYTES
+        );
+        return false;
+    }
+    if mirror.max_inflight == 0 {
+        validation_error!("{} mirror.max_inflight must be greater than 0", scope);
+        return false;
+    }
+
+    true
+}
+
+fn validate_route_fault(scope: &str, fault: &RouteFault) -> bool {
+    if fault.delay.is_none() && fault.abort.is_none() && fault.truncate.is_none() {
+        validation_error!(
+            "{} fault must set at least one of delay, abort, or truncate",
+            scope
+        );
+        return false;
+    }
+    if let Some(header) = fault.header.as_ref() {
+        if http::HeaderName::from_bytes(header.name.trim().as_bytes()).is_err() {
+            validation_error!("{} fault.header.name is invalid: '{}'", scope, header.name);
+            return false;
+        }
+        if header.value.as_ref().is_some_and(|value| {
+            value.trim().is_empty() || http::HeaderValue::from_str(value).is_err()
+        }) {
+            validation_error!(
+                "{} fault.header.value must be a non-empty header value",
+                scope
+            );
+            return false;
+        }
+    }
+    let percents = [
+        ("delay", fault.delay.as_ref().map(|delay| delay.percent)),
+        ("abort", fault.abort.as_ref().map(|abort| abort.percent)),
+        (
+            "truncate",
+            fault.truncate.as_ref().map(|truncate| truncate.percent),
+        ),
+    ];
+    for (field, percent) in percents {
+        if percent.is_some_and(|percent| percent > 100) {
+            validation_error!("{} fault.{}.percent must be in 0..=100", scope, field);
+            return false;
+        }
+    }
+    if let Some(delay) = fault.delay.as_ref() {
+        if delay.duration_ms == 0 {
+            validation_error!("{} fault.delay.duration_ms must be greater than 0", scope);
+            return false;
+        }
+        if delay
+            .max_duration_ms
+            .is_some_and(|max_duration_ms| max_duration_ms < delay.duration_ms)
+        {
+            validation_error!(
+                "{} fault.delay.max_duration_ms must be >= duration_ms",
+                scope
+            );
+            return false;
+        }
+    }
+    if let Some(abort) = fault.abort.as_ref() {
+        match (abort.status, abort.reset) {
+            (None, true) => {}
+            (Some(status), false) if (400..=599).contains(&status) => {}
+            (Some(status), false) => {
+                validation_error!(
+                    "{} fault.abort.status must be in 400..=599, got {}",
+                    scope,
+                    status
+                );
+                return false;
+            }
+            _ => {
+                validation_error!(
+                    "{} fault.abort must set exactly one of status or reset",
+                    scope
+                );
+                return false;
+            }
+        }
+    }
+    if fault
+        .truncate
+        .as_ref()
+        .is_some_and(|truncate| truncate.after_bytes == 0)
+    {
+        validation_error!(
+            "{} fault.truncate.after_bytes must be greater than 0",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
+/// Checks an effective (route merged over upstream) `policy` block against
+/// the global timeouts it overrides.
+fn validate_route_policy(config: &Config, scope: &str, policy: &RoutePolicy) -> bool {
+    let nonzero = [
+        ("backend_timeout_ms", policy.backend_timeout_ms),
+        (
+            "backend_total_request_timeout_ms",
+            policy.backend_total_request_timeout_ms,
+        ),
+        (
+            "max_request_body_bytes",
+            policy.max_request_body_bytes.map(|bytes| bytes as u64),
+        ),
+        ("hedging_delay_ms", policy.hedging_delay_ms),
+    ];
+    for (field, value) in nonzero {
+        if value == Some(0) {
+            validation_error!("{} policy.{} must be greater than 0", scope, field);
+            return false;
+        }
+    }
+    if let Some(attempts) = policy.max_retry_attempts
+        && attempts > MAX_ROUTE_RETRY_ATTEMPTS
+    {
+        validation_error!(
+            "{} policy.max_retry_attempts must be <= {}, got {}",
+            scope,
+            MAX_ROUTE_RETRY_ATTEMPTS,
+            attempts
+        );
+        return false;
+    }
+    if let Some(retry_on) = policy.retry_on.as_ref() {
+        if retry_on.is_empty() {
+            validation_error!(
+                "{} policy.retry_on must list at least one entry; set max_retry_attempts: 0 to disable retries",
+                scope
+            );
+            return false;
+        }
+        for entry in retry_on {
+            if let RetryOn::Status(status) = entry
+                && !RETRYABLE_STATUS_RANGE.contains(status)
+            {
+                validation_error!(
+                    "{} policy.retry_on status {} must be within 400..=599",
+                    scope,
+                    status
+                );
+                return false;
+            }
+        }
+    }
+    let backoff_base_ms = policy
+        .retry_backoff_base_ms
+        .unwrap_or_else(route_default_retry_backoff_base_ms);
+    let backoff_max_ms = policy
+        .retry_backoff_max_ms
+        .unwrap_or_else(route_default_retry_backoff_max_ms);
+    if backoff_base_ms > backoff_max_ms {
+        validation_error!(
+            "{} policy.retry_backoff_base_ms ({}) must be <= retry_backoff_max_ms ({})",
+            scope,
+            backoff_base_ms,
+            backoff_max_ms
+        );
+        return false;
+    }
+    if let Some(name) = policy.idempotency_header.as_deref()
+        && http::HeaderName::from_bytes(name.trim().to_ascii_lowercase().as_bytes()).is_err()
+    {
+        validation_error!("{} policy.idempotency_header '{}' is invalid", scope, name);
+        return false;
+    }
+
+    let performance = &config.performance;
+    if let Some(replay_bytes) = policy.replay_body_max_bytes
+        && replay_bytes > performance.request_buffer_global_cap_bytes
+    {
+        validation_error!(
+            "{} policy.replay_body_max_bytes ({}) must be <= performance.request_buffer_global_cap_bytes ({})",
+            scope,
+            replay_bytes,
+            performance.request_buffer_global_cap_bytes
+        );
+        return false;
+    }
+    let backend_timeout_ms = policy
+        .backend_timeout_ms
+        .unwrap_or(performance.backend_timeout_ms);
+    let total_request_timeout_ms = policy
+        .backend_total_request_timeout_ms
+        .unwrap_or(performance.backend_total_request_timeout_ms);
+    if backend_timeout_ms < performance.backend_connect_timeout_ms {
+        validation_error!(
+            "{} policy.backend_timeout_ms ({}) must be >= performance.backend_connect_timeout_ms ({})",
+            scope,
+            backend_timeout_ms,
+            performance.backend_connect_timeout_ms
+        );
+        return false;
+    }
+    if backend_timeout_ms > total_request_timeout_ms {
+        validation_error!(
+            "{} policy.backend_timeout_ms ({}) must be <= backend_total_request_timeout_ms ({})",
+            scope,
+            backend_timeout_ms,
+            total_request_timeout_ms
+        );
+        return false;
+    }
+
+    true
+}
+
+fn validate_route_auth(scope: &str, auth: &RouteAuth) -> bool {
+    if let Some(api_key) = auth.api_key.as_ref() {
+        let header_name = api_key.header_name.trim();
+        if header_name.is_empty() {
+            validation_error!("{} auth.api_key.header_name must be non-empty", scope);
+            return false;
+        }
+        if !is_valid_http_token(header_name) {
+            validation_error!(
+                "{} auth.api_key.header_name must be a valid HTTP header name",
+                scope
+            );
+            return false;
+        }
+        if api_key.keys.is_empty() || api_key.keys.iter().any(|value| value.trim().is_empty()) {
+            validation_error!(
+                "{} auth.api_key.keys must contain at least one non-empty key",
+                scope
+            );
+            return false;
+        }
+        let mut seen_api_keys = std::collections::HashSet::new();
+        for key in &api_key.keys {
+            if !seen_api_keys.insert(key.trim().to_string()) {
+                validation_error!("{} auth.api_key.keys contains duplicate values", scope);
+                return false;
+            }
+        }
+    }
+
+    if let Some(external_auth) = auth.external_auth.as_ref() {
+        if auth.api_key.is_some() || auth.jwt.is_some() {
+            validation_error!(
+                "{} auth.external_auth cannot be combined with auth.api_key or auth.jwt in v1",
+                scope
+            );
+            return false;
+        }
+        if !auth.required_scopes.is_empty() || !auth.required_roles.is_empty() {
+            validation_error!(
+                "{} auth.external_auth cannot be combined with auth.required_scopes or auth.required_roles in v1",
+                scope
+            );
+            return false;
+        }
+
+        match external_auth {
+            ExternalAuth::Http {
+                endpoint,
+                request_headers,
+                response_header_allowlist,
+                timeout_ms,
+                ..
+            } => {
+                if !is_valid_http_url(endpoint) {
+                    validation_error!(
+                        "{} auth.external_auth.http.endpoint must be an absolute http(s) URL",
+                        scope
+                    );
+                    return false;
+                }
+                if !validate_external_auth_headers(
+                    scope,
+                    "auth.external_auth.http",
+                    request_headers,
+                    response_header_allowlist,
+                ) {
+                    return false;
+                }
+                if *timeout_ms == 0 {
+                    validation_error!(
+                        "{} auth.external_auth.http.timeout_ms must be greater than 0",
+                        scope
+                    );
+                    return false;
+                }
+            }
+            ExternalAuth::Oidc {
+                discovery_url,
+                issuer_url,
+                client_id,
+                client_secret,
+                audience,
+                scopes,
+                request_headers,
+                response_header_allowlist,
+                timeout_ms,
+                ..
+            } => {
+                let has_discovery_url = discovery_url
+                    .as_deref()
+                    .is_some_and(|value| !value.trim().is_empty());
+                let has_issuer_url = issuer_url
+                    .as_deref()
+                    .is_some_and(|value| !value.trim().is_empty());
+                if !has_discovery_url && !has_issuer_url {
+                    validation_error!(
+                        "{} auth.external_auth.oidc requires discovery_url or issuer_url",
+                        scope
+                    );
+                    return false;
+                }
+                if let Some(discovery_url) = discovery_url.as_deref()
+                    && !discovery_url.trim().is_empty()
+                    && !is_valid_https_url(discovery_url)
+                {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.discovery_url must be an absolute https URL",
+                        scope
+                    );
+                    return false;
+                }
+                if let Some(issuer_url) = issuer_url.as_deref()
+                    && !issuer_url.trim().is_empty()
+                    && !is_valid_https_url(issuer_url)
+                {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.issuer_url must be an absolute https URL",
+                        scope
+                    );
+                    return false;
+                }
+                if client_id.trim().is_empty() {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.client_id must be non-empty",
+                        scope
+                    );
+                    return false;
+                }
+                if client_secret
+                    .as_deref()
+                    .is_some_and(|value| value.trim().is_empty())
+                {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.client_secret must be non-empty when provided",
+                        scope
+                    );
+                    return false;
+                }
+                if audience
+                    .as_deref()
+                    .is_some_and(|value| value.trim().is_empty())
+                {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.audience must be non-empty when provided",
+                        scope
+                    );
+                    return false;
+                }
+                if scopes.iter().any(|scope| scope.trim().is_empty()) {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.scopes must not contain empty values",
+                        scope
+                    );
+                    return false;
+                }
+                if !validate_external_auth_headers(
+                    scope,
+                    "auth.external_auth.oidc",
+                    request_headers,
+                    response_header_allowlist,
+                ) {
+                    return false;
+                }
+                if !response_header_allowlist.is_empty() {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.response_header_allowlist is not supported in v1",
+                        scope
+                    );
+                    return false;
+                }
+                if *timeout_ms == 0 {
+                    validation_error!(
+                        "{} auth.external_auth.oidc.timeout_ms must be greater than 0",
+                        scope
+                    );
+                    return false;
+                }
+            }
+        }
+    }
+
+    if let Some(jwt) = auth.jwt.as_ref() {
+        if jwt.secret.trim().is_empty() {
+            validation_error!("{} auth.jwt.secret must be non-empty", scope);
+            return false;
+        }
+        if jwt
+            .issuer
+            .as_deref()
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            validation_error!("{} auth.jwt.issuer must be non-empty when provided", scope);
+            return false;
+        }
+        if jwt
+            .audience
+            .as_deref()
+            .is_some_and(|value| value.trim().is_empty())
+        {
+            validation_error!(
+                "{} auth.jwt.audience must be non-empty when provided",
+                scope
+            );
+            return false;
+        }
+    }
+    if auth
+        .required_scopes
+        .iter()
+        .any(|value| value.trim().is_empty())
+    {
+        validation_error!(
+            "{} auth.required_scopes must not contain empty values",
+            scope
+        );
+        return false;
+    }
+    if auth
+        .required_roles
+        .iter()
+        .any(|value| value.trim().is_empty())
+    {
+        validation_error!(
+            "{} auth.required_roles must not contain empty values",
+            scope
+        );
+        return false;
+    }
+    if (!auth.required_scopes.is_empty() || !auth.required_roles.is_empty()) && auth.jwt.is_none() {
+        validation_error!(
+            "{} auth.required_scopes/auth.required_roles require auth.jwt",
+            scope
+        );
+        return false;
+    }
+
+    true
+}
+
 pub fn validate(config: &Config) -> Result<(), ValidationError> {
     clear_validation_error();
     if validate_inner(config) {
@@ -270,13 +1672,16 @@ fn validate_inner(config: &Config) -> bool {
 
     // --- Validate global load balancing type (if present) ---
     if let Some(ref lb) = config.load_balancing
-        && !VALID_LB_TYPES
-            .iter()
-            .any(|lb_type| lb_type.eq_ignore_ascii_case(&lb.lb_type))
+        && !is_valid_lb_type(&lb.lb_type)
     {
         validation_error!("Invalid global load balancing type: {}", lb.lb_type);
         return false;
     }
+    if let Some(ref lb) = config.load_balancing
+        && !validate_load_balancing_options("global", lb, None)
+    {
+        return false;
+    }
 
     // --- Validate performance controls ---
     if config.performance.worker_threads == 0 {
@@ -547,40 +1952,12 @@ fn validate_inner(config: &Config) -> bool {
         return false;
     }
 
-    if config.resilience.adaptive_admission.min_limit == 0 {
-        validation_error!("resilience.adaptive_admission.min_limit must be greater than 0");
-        return false;
-    }
-    if let Some(max_limit) = config.resilience.adaptive_admission.max_limit {
-        if max_limit == 0 {
-            validation_error!("resilience.adaptive_admission.max_limit must be greater than 0");
-            return false;
-        }
-        if max_limit < config.resilience.adaptive_admission.min_limit {
-            validation_error!(
-                "resilience.adaptive_admission.max_limit ({}) must be >= min_limit ({})",
-                max_limit,
-                config.resilience.adaptive_admission.min_limit
-            );
-            return false;
-        }
-        if max_limit > config.performance.global_inflight_limit {
-            validation_error!(
-                "resilience.adaptive_admission.max_limit ({}) must be <= performance.global_inflight_limit ({})",
-                max_limit,
-                config.performance.global_inflight_limit
-            );
-            return false;
-        }
-    }
-
-    if config.resilience.adaptive_admission.decrease_step == 0 {
-        validation_error!("resilience.adaptive_admission.decrease_step must be greater than 0");
-        return false;
-    }
-
-    if config.resilience.adaptive_admission.increase_step == 0 {
-        validation_error!("resilience.adaptive_admission.increase_step must be greater than 0");
+    if !validate_adaptive_admission(
+        "resilience.adaptive_admission",
+        &config.resilience.adaptive_admission,
+        "performance.global_inflight_limit",
+        config.performance.global_inflight_limit,
+    ) {
         return false;
     }
 
@@ -859,6 +2236,14 @@ fn validate_inner(config: &Config) -> bool {
         return false;
     }
 
+    if !validate_hedging(&config.resilience.hedging) {
+        return false;
+    }
+
+    if !validate_priority(&config.resilience.priority) {
+        return false;
+    }
+
     if config.resilience.watchdog.check_interval_ms == 0 {
         validation_error!("resilience.watchdog.check_interval_ms must be greater than 0");
         return false;
@@ -994,6 +2379,18 @@ fn validate_inner(config: &Config) -> bool {
                 "observability.control_api.reload_certs_path",
                 config.observability.control_api.reload_certs_path.as_str(),
             ),
+            (
+                "observability.control_api.rollouts_path",
+                config.observability.control_api.rollouts_path.as_str(),
+            ),
+            (
+                "observability.control_api.explain_path",
+                config.observability.control_api.explain_path.as_str(),
+            ),
+            (
+                "observability.control_api.backends_path",
+                config.observability.control_api.backends_path.as_str(),
+            ),
         ];
         for (name, path) in paths {
             if !path.starts_with('/') {
@@ -1021,6 +2418,35 @@ fn validate_inner(config: &Config) -> bool {
             return false;
         }
 
+        if let Some(path) = config.observability.control_api.backend_state_file.as_ref()
+            && path.trim().is_empty()
+        {
+            validation_error!(
+                "observability.control_api.backend_state_file cannot be empty when provided"
+            );
+            return false;
+        }
+
+        if let Some(path) = config
+            .observability
+            .control_api
+            .membership_state_file
+            .as_ref()
+        {
+            if path.trim().is_empty() {
+                validation_error!(
+                    "observability.control_api.membership_state_file cannot be empty when provided"
+                );
+                return false;
+            }
+            if config.observability.control_api.backend_state_file.as_ref() == Some(path) {
+                validation_error!(
+                    "observability.control_api.membership_state_file must differ from backend_state_file"
+                );
+                return false;
+            }
+        }
+
         if config.observability.control_api.auth_token.is_none() {
             validation_error!(
                 "observability.control_api.auth_token is required when control_api.enabled=true"
@@ -1073,59 +2499,175 @@ fn validate_inner(config: &Config) -> bool {
         }
     }
 
+    // --- Validate locality ---
+    if config
+        .locality
+        .zone
+        .as_ref()
+        .is_some_and(|zone| zone.trim().is_empty())
+    {
+        validation_error!("locality.zone cannot be empty when provided");
+        return false;
+    }
+
     // --- Validate upstream routes ---
     for (upstream_name, upstream) in &config.upstream {
-        // Validate route matcher has at least one condition
-        let has_host = upstream.route.host.is_some();
-        let has_path = upstream.route.path_prefix.is_some();
+        // An empty shorthand route means the upstream is only reachable via `routes`.
+        if !upstream.route.is_empty()
+            && !validate_route_match(&format!("upstream '{}'", upstream_name), &upstream.route)
+        {
+            return false;
+        }
+
+        if !validate_host_policy(
+            &format!("upstream {}", upstream_name),
+            &upstream.host_policy,
+        ) {
+            return false;
+        }
+
+        if !validate_route_policy(
+            config,
+            &format!("upstream '{}'", upstream_name),
+            &upstream.policy,
+        ) {
+            return false;
+        }
+
+        if let Some(detection) = upstream.outlier_detection.as_ref()
+            && !validate_outlier_detection(&format!("upstream '{}'", upstream_name), detection)
+        {
+            return false;
+        }
+
+        if let Some(slow_start) = upstream.slow_start.as_ref()
+            && !validate_slow_start(&format!("upstream '{}'", upstream_name), slow_start)
+        {
+            return false;
+        }
 
-        if !has_host && !has_path {
+        if let Some(zone_aware) = upstream.zone_aware.as_ref()
+            && zone_aware.max_inflight_per_backend == Some(0)
+        {
             validation_error!(
-                "Upstream '{}' must have either 'host' or 'path_prefix' route matcher",
+                "upstream '{}' zone_aware.max_inflight_per_backend must be greater than 0",
                 upstream_name
             );
             return false;
         }
 
-        // Validate path_prefix is not empty if present
-        if let Some(ref path) = upstream.route.path_prefix {
-            if path.is_empty() {
-                validation_error!(
-                    "Route path_prefix cannot be empty for upstream '{}'",
-                    upstream_name
-                );
-                return false;
-            }
-            if !path.starts_with('/') {
-                validation_error!(
-                    "Route path_prefix must start with '/' for upstream '{}': {}",
-                    upstream_name,
-                    path
-                );
-                return false;
-            }
+        if let Some(affinity) = upstream.session_affinity.as_ref()
+            && !validate_session_affinity(&format!("upstream '{}'", upstream_name), affinity)
+        {
+            return false;
         }
 
-        match upstream.host_policy.mode {
-            UpstreamHostPolicyMode::PassThrough | UpstreamHostPolicyMode::Upstream => {
-                if upstream.host_policy.host.is_some() {
-                    validation_error!(
-                        "upstream {}.host_policy.host is invalid unless mode is rewrite",
-                        upstream_name
-                    );
-                    return false;
-                }
-            }
-            UpstreamHostPolicyMode::Rewrite => match upstream.host_policy.host.as_deref() {
-                Some(host) if valid_static_host_header(host) => {}
-                _ => {
-                    validation_error!(
-                        "upstream {}.host_policy.mode=rewrite requires a valid non-empty host_policy.host",
-                        upstream_name
-                    );
-                    return false;
-                }
-            },
+        if let Some(adaptive) = upstream.adaptive_admission.as_ref()
+            && !validate_adaptive_admission(
+                &format!("upstream '{}' adaptive_admission", upstream_name),
+                adaptive,
+                "performance.per_upstream_inflight_limit",
+                config.performance.per_upstream_inflight_limit,
+            )
+        {
+            return false;
+        }
+    }
+
+    // --- Validate named routes ---
+    let mut seen_route_names: HashMap<&str, String> = config
+        .upstream
+        .iter()
+        .filter(|(_, upstream)| !upstream.route.is_empty())
+        .map(|(name, _)| {
+            (
+                name.as_str(),
+                format!("upstream '{}' shorthand route", name),
+            )
+        })
+        .collect();
+    for (idx, route) in config.routes.iter().enumerate() {
+        if route.name.trim().is_empty() {
+            validation_error!("routes[{}].name cannot be empty", idx);
+            return false;
+        }
+        if let Some(existing) =
+            seen_route_names.insert(route.name.as_str(), format!("routes[{}]", idx))
+        {
+            validation_error!(
+                "routes[{}].name '{}' duplicates {}",
+                idx,
+                route.name,
+                existing
+            );
+            return false;
+        }
+        let scope = format!("route '{}'", route.name);
+        if !validate_route_target(config, &scope, route) {
+            return false;
+        }
+        if !validate_route_match(&scope, &route.matcher) {
+            return false;
+        }
+        if let Some(host_policy) = route.host_policy.as_ref()
+            && !validate_host_policy(&format!("route {}", route.name), host_policy)
+        {
+            return false;
+        }
+        if let Some(auth) = route.auth.as_ref()
+            && !validate_route_auth(&scope, auth)
+        {
+            return false;
+        }
+        if let Some(rewrite) = route.rewrite.as_ref()
+            && !validate_path_rewrite(&scope, rewrite)
+        {
+            return false;
+        }
+        if let Some(split) = route.split.as_ref()
+            && !validate_traffic_split(config, &scope, &route.upstream, split)
+        {
+            return false;
+        }
+        if let Some(mirror) = route.mirror.as_ref()
+            && !validate_route_mirror(config, &scope, route, mirror)
+        {
+            return false;
+        }
+        if let Some(fault) = route.fault.as_ref()
+            && !validate_route_fault(&scope, fault)
+        {
+            return false;
+        }
+        if let Some(policy) = route.policy.as_ref()
+            && let Some(upstream) = config.upstream.get(&route.upstream)
+            && !validate_route_policy(config, &scope, &policy.or(&upstream.policy))
+        {
+            return false;
+        }
+    }
+
+    for (upstream_name, upstream) in &config.upstream {
+        if upstream.route.is_empty()
+            && !config.routes.iter().any(|route| {
+                route.upstream == *upstream_name
+                    || route.split.as_ref().is_some_and(|split| {
+                        split
+                            .targets
+                            .iter()
+                            .any(|target| target.upstream == *upstream_name)
+                    })
+                    || route
+                        .mirror
+                        .as_ref()
+                        .is_some_and(|mirror| mirror.upstream == *upstream_name)
+            })
+        {
+            validation_error!(
+                "Upstream '{}' is unreachable: set upstream.route or reference it from routes",
+                upstream_name
+            );
+            return false;
         }
     }
 
@@ -1137,20 +2679,20 @@ fn validate_inner(config: &Config) -> bool {
 
     let mut seen_route_matchers: HashMap<RouteMatcherKey, String> = HashMap::new();
 
-    for (upstream_name, upstream) in &config.upstream {
+    for route in effective_routes(config) {
         let route_key = (
-            upstream.route.host.as_deref().map(normalize_route_host),
-            upstream.route.path_prefix.clone(),
-            normalized_route_method(upstream.route.method.as_deref()),
+            route.matcher.host.as_deref().map(normalize_route_host),
+            route.matcher.path_prefix.clone(),
+            normalized_route_method(route.matcher.method.as_deref()),
         );
 
-        if let Some(existing_upstream) =
-            seen_route_matchers.insert(route_key.clone(), upstream_name.clone())
+        if let Some(existing_route) =
+            seen_route_matchers.insert(route_key.clone(), route.name.clone())
         {
             validation_error!(
-                "Ambiguous route matcher detected: upstream '{}' conflicts with upstream '{}' for host={:?} path_prefix={:?} method={:?}",
-                upstream_name,
-                existing_upstream,
+                "Ambiguous route matcher detected: route '{}' conflicts with route '{}' for host={:?} path_prefix={:?} method={:?}",
+                route.name,
+                existing_route,
                 route_key.0,
                 route_key.1,
                 route_key.2
@@ -1169,10 +2711,7 @@ fn validate_inner(config: &Config) -> bool {
         }
 
         // Validate load balancing type for this upstream
-        if !VALID_LB_TYPES
-            .iter()
-            .any(|lb_type| lb_type.eq_ignore_ascii_case(&upstream.load_balancing.lb_type))
-        {
+        if !is_valid_lb_type(&upstream.load_balancing.lb_type) {
             validation_error!(
                 "Invalid load balancing type '{}' for upstream '{}'",
                 upstream.load_balancing.lb_type,
@@ -1181,256 +2720,15 @@ fn validate_inner(config: &Config) -> bool {
             return false;
         }
 
-        if let Some(api_key) = upstream.auth.api_key.as_ref() {
-            let header_name = api_key.header_name.trim();
-            if header_name.is_empty() {
-                validation_error!(
-                    "upstream '{}' auth.api_key.header_name must be non-empty",
-                    upstream_name
-                );
-                return false;
-            }
-            if !is_valid_http_token(header_name) {
-                validation_error!(
-                    "upstream '{}' auth.api_key.header_name must be a valid HTTP header name",
-                    upstream_name
-                );
-                return false;
-            }
-            if api_key.keys.is_empty() || api_key.keys.iter().any(|value| value.trim().is_empty()) {
-                validation_error!(
-                    "upstream '{}' auth.api_key.keys must contain at least one non-empty key",
-                    upstream_name
-                );
-                return false;
-            }
-            let mut seen_api_keys = std::collections::HashSet::new();
-            for key in &api_key.keys {
-                if !seen_api_keys.insert(key.trim().to_string()) {
-                    validation_error!(
-                        "upstream '{}' auth.api_key.keys contains duplicate values",
-                        upstream_name
-                    );
-                    return false;
-                }
-            }
-        }
-
-        if let Some(external_auth) = upstream.auth.external_auth.as_ref() {
-            if upstream.auth.api_key.is_some() || upstream.auth.jwt.is_some() {
-                validation_error!(
-                    "upstream '{}' auth.external_auth cannot be combined with auth.api_key or auth.jwt in v1",
-                    upstream_name
-                );
-                return false;
-            }
-            if !upstream.auth.required_scopes.is_empty() || !upstream.auth.required_roles.is_empty()
-            {
-                validation_error!(
-                    "upstream '{}' auth.external_auth cannot be combined with auth.required_scopes or auth.required_roles in v1",
-                    upstream_name
-                );
-                return false;
-            }
-
-            match external_auth {
-                ExternalAuth::Http {
-                    endpoint,
-                    request_headers,
-                    response_header_allowlist,
-                    timeout_ms,
-                    ..
-                } => {
-                    if !is_valid_http_url(endpoint) {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.http.endpoint must be an absolute http(s) URL",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if !validate_external_auth_headers(
-                        upstream_name,
-                        "auth.external_auth.http",
-                        request_headers,
-                        response_header_allowlist,
-                    ) {
-                        return false;
-                    }
-                    if *timeout_ms == 0 {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.http.timeout_ms must be greater than 0",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                }
-                ExternalAuth::Oidc {
-                    discovery_url,
-                    issuer_url,
-                    client_id,
-                    client_secret,
-                    audience,
-                    scopes,
-                    request_headers,
-                    response_header_allowlist,
-                    timeout_ms,
-                    ..
-                } => {
-                    let has_discovery_url = discovery_url
-                        .as_deref()
-                        .is_some_and(|value| !value.trim().is_empty());
-                    let has_issuer_url = issuer_url
-                        .as_deref()
-                        .is_some_and(|value| !value.trim().is_empty());
-                    if !has_discovery_url && !has_issuer_url {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc requires discovery_url or issuer_url",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if let Some(discovery_url) = discovery_url.as_deref()
-                        && !discovery_url.trim().is_empty()
-                        && !is_valid_https_url(discovery_url)
-                    {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.discovery_url must be an absolute https URL",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if let Some(issuer_url) = issuer_url.as_deref()
-                        && !issuer_url.trim().is_empty()
-                        && !is_valid_https_url(issuer_url)
-                    {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.issuer_url must be an absolute https URL",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if client_id.trim().is_empty() {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.client_id must be non-empty",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if client_secret
-                        .as_deref()
-                        .is_some_and(|value| value.trim().is_empty())
-                    {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.client_secret must be non-empty when provided",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if audience
-                        .as_deref()
-                        .is_some_and(|value| value.trim().is_empty())
-                    {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.audience must be non-empty when provided",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if scopes.iter().any(|scope| scope.trim().is_empty()) {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.scopes must not contain empty values",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if !validate_external_auth_headers(
-                        upstream_name,
-                        "auth.external_auth.oidc",
-                        request_headers,
-                        response_header_allowlist,
-                    ) {
-                        return false;
-                    }
-                    if !response_header_allowlist.is_empty() {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.response_header_allowlist is not supported in v1",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                    if *timeout_ms == 0 {
-                        validation_error!(
-                            "upstream '{}' auth.external_auth.oidc.timeout_ms must be greater than 0",
-                            upstream_name
-                        );
-                        return false;
-                    }
-                }
-            }
-        }
-
-        if let Some(jwt) = upstream.auth.jwt.as_ref() {
-            if jwt.secret.trim().is_empty() {
-                validation_error!(
-                    "upstream '{}' auth.jwt.secret must be non-empty",
-                    upstream_name
-                );
-                return false;
-            }
-            if jwt
-                .issuer
-                .as_deref()
-                .is_some_and(|value| value.trim().is_empty())
-            {
-                validation_error!(
-                    "upstream '{}' auth.jwt.issuer must be non-empty when provided",
-                    upstream_name
-                );
-                return false;
-            }
-            if jwt
-                .audience
-                .as_deref()
-                .is_some_and(|value| value.trim().is_empty())
-            {
-                validation_error!(
-                    "upstream '{}' auth.jwt.audience must be non-empty when provided",
-                    upstream_name
-                );
-                return false;
-            }
-        }
-        if upstream
-            .auth
-            .required_scopes
-            .iter()
-            .any(|value| value.trim().is_empty())
-        {
-            validation_error!(
-                "upstream '{}' auth.required_scopes must not contain empty values",
-                upstream_name
-            );
-            return false;
-        }
-        if upstream
-            .auth
-            .required_roles
-            .iter()
-            .any(|value| value.trim().is_empty())
-        {
-            validation_error!(
-                "upstream '{}' auth.required_roles must not contain empty values",
-                upstream_name
-            );
+        if !validate_load_balancing_options(
+            &format!("upstream '{}'", upstream_name),
+            &upstream.load_balancing,
+            Some(upstream.backends.len()),
+        ) {
             return false;
         }
-        if (!upstream.auth.required_scopes.is_empty() || !upstream.auth.required_roles.is_empty())
-            && upstream.auth.jwt.is_none()
-        {
-            validation_error!(
-                "upstream '{}' auth.required_scopes/auth.required_roles require auth.jwt",
-                upstream_name
-            );
+
+        if !validate_route_auth(&format!("upstream '{}'", upstream_name), &upstream.auth) {
             return false;
         }
 
@@ -1441,12 +2739,21 @@ fn validate_inner(config: &Config) -> bool {
         }
 
         let mut upstream_uses_https_backends = false;
+        let mut seen_backend_ids = std::collections::HashSet::new();
         for backend in &upstream.backends {
             // Validate backend ID
             if backend.id.is_empty() {
                 validation_error!("Backend ID is empty in upstream '{}'", upstream_name);
                 return false;
             }
+            if !seen_backend_ids.insert(backend.id.as_str()) {
+                validation_error!(
+                    "Duplicate backend ID '{}' in upstream '{}'",
+                    backend.id,
+                    upstream_name
+                );
+                return false;
+            }
 
             // Validate backend address
             if backend.address.is_empty() {
@@ -1505,6 +2812,19 @@ fn validate_inner(config: &Config) -> bool {
                 return false;
             }
 
+            if backend
+                .zone
+                .as_ref()
+                .is_some_and(|zone| zone.trim().is_empty())
+            {
+                validation_error!(
+                    "Backend '{}' in upstream '{}' has an empty zone",
+                    backend.id,
+                    upstream_name
+                );
+                return false;
+            }
+
             // Validate health check (optional — omitting it disables active health checks)
             if let Some(hc) = &backend.health_check {
                 if hc.interval == 0 {
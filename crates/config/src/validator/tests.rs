@@ -5,11 +5,15 @@ use tempfile::tempdir;
 
 use super::validate;
 use crate::config::{
-    ApiKeyAuth, Backend, ClientAuth, Config, ControlApi, ExternalAuth, ExternalAuthFailureMode,
-    ExternalAuthRequestHeader, HealthCheck, JwtAuth, Listen, LoadBalancing, Log, LogFormat,
-    MetricsEndpoint, Observability, Performance, Resilience, RouteAuth, RouteMatch,
-    ScopedRateLimit, ScopedRateLimitScope, Security, Tls, TlsCertificate, Tracing, Upstream,
-    UpstreamTls,
+    AdaptiveAdmission, AdaptiveAdmissionAlgorithm, ApiKeyAuth, Backend, CanaryRollout, ClientAuth,
+    Config, ControlApi, CookieSameSite, DirectResponse, ExternalAuth, ExternalAuthFailureMode,
+    ExternalAuthRequestHeader, FaultAbort, FaultDelay, FaultHeaderMatch, FaultTruncate,
+    HealthCheck, Hedging, HedgingMode, JwtAuth, Listen, LoadBalancing, Log, LogFormat,
+    MetricsEndpoint, Observability, OutlierDetection, PathRewrite, Performance, PowerOfTwoChoices,
+    PrefixRewrite, Priority, PriorityClass, PriorityRule, Resilience, ResponseHeader, RetryOn,
+    Route, RouteAuth, RouteFault, RouteMatch, RouteMirror, RoutePolicy, RouteRedirect,
+    ScopedRateLimit, ScopedRateLimitScope, Security, SessionAffinity, SlowStart, SlowStartCurve,
+    SplitTarget, Tls, TlsCertificate, Tracing, TrafficSplit, Upstream, UpstreamTls, ZoneAware,
 };
 
 fn write_test_certs(dir: &std::path::Path) -> (std::path::PathBuf, std::path::PathBuf) {
@@ -36,9 +40,19 @@ fn base_config(cert: &str, key: &str) -> Config {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -58,6 +72,8 @@ fn base_config(cert: &str, key: &str) -> Config {
                     success_threshold: 1,
                     cooldown_ms: 1000,
                 }),
+                priority: 0,
+                zone: None,
             }],
         },
     );
@@ -77,9 +93,14 @@ fn base_config(cert: &str, key: &str) -> Config {
         },
         listeners: vec![],
         upstream,
+        routes: vec![],
         load_balancing: Some(LoadBalancing {
             lb_type: "random".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log {
@@ -91,6 +112,7 @@ fn base_config(cert: &str, key: &str) -> Config {
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -658,6 +680,31 @@ fn rejects_invalid_performance_and_observability_values() {
     cfg.observability.control_api.auth_token = Some("token".to_string());
     assert!(validate(&cfg).is_err());
 
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.observability.control_api.enabled = true;
+    cfg.observability.control_api.backend_state_file = Some(" ".to_string());
+    cfg.observability.control_api.auth_token = Some("token".to_string());
+    assert!(validate(&cfg).is_err());
+
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.observability.control_api.enabled = true;
+    cfg.observability.control_api.membership_state_file = Some(String::new());
+    cfg.observability.control_api.auth_token = Some("token".to_string());
+    assert!(validate(&cfg).is_err());
+
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.observability.control_api.enabled = true;
+    cfg.observability.control_api.backend_state_file = Some("/var/lib/spooky/state.json".into());
+    cfg.observability.control_api.membership_state_file = Some("/var/lib/spooky/state.json".into());
+    cfg.observability.control_api.auth_token = Some("token".to_string());
+    assert!(validate(&cfg).is_err());
+
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.observability.control_api.enabled = true;
+    cfg.observability.control_api.backends_path = "admin/backends".to_string();
+    cfg.observability.control_api.auth_token = Some("token".to_string());
+    assert!(validate(&cfg).is_err());
+
     cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
     cfg.observability.routing.expose_header = true;
     cfg.observability.routing.header_name = "   ".to_string();
@@ -894,6 +941,24 @@ fn rejects_duplicate_backend_addresses_across_upstreams() {
     assert!(validate(&cfg).is_err());
 }
 
+#[test]
+fn rejects_duplicate_backend_ids_within_an_upstream() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let upstream = cfg.upstream.get_mut("test_upstream").expect("upstream");
+    let mut duplicate = upstream.backends[0].clone();
+    duplicate.address = "127.0.0.1:9090".to_string();
+    upstream.backends.push(duplicate);
+    assert!(validate(&cfg).is_err());
+
+    let upstream = cfg.upstream.get_mut("test_upstream").expect("upstream");
+
+    upstream.backends[1].id = "backend-2".to_string();
+    assert!(validate(&cfg).is_ok());
+}
+
 #[test]
 fn rejects_non_loopback_control_api_without_auth_token() {
     let dir = tempdir().expect("tempdir");
@@ -976,9 +1041,19 @@ fn rejects_ambiguous_route_matchers_with_same_host_path_and_method() {
         load_balancing: LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -991,6 +1066,8 @@ fn rejects_ambiguous_route_matchers_with_same_host_path_and_method() {
             address: "127.0.0.1:9002".to_string(),
             weight: 1,
             health_check: None,
+            priority: 0,
+            zone: None,
         }],
     };
     cfg.upstream
@@ -1017,9 +1094,19 @@ fn allows_same_host_and_path_when_methods_differ() {
         load_balancing: LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -1032,6 +1119,8 @@ fn allows_same_host_and_path_when_methods_differ() {
             address: "127.0.0.1:9002".to_string(),
             weight: 1,
             health_check: None,
+            priority: 0,
+            zone: None,
         }],
     };
     cfg.upstream
@@ -1169,6 +1258,499 @@ fn rejects_scoped_rate_limit_with_empty_allowlist_entry() {
     assert!(validate(&cfg).is_err());
 }
 
+#[test]
+fn validates_upstream_slow_start() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |window_ms: u64, min_weight_percent: u32| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .slow_start = Some(SlowStart {
+            window_ms,
+            min_weight_percent,
+            curve: SlowStartCurve::Aggressive,
+        });
+        cfg
+    };
+
+    assert!(validate(&with(30_000, 10)).is_ok());
+    assert!(validate(&with(30_000, 100)).is_ok());
+    assert!(validate(&with(0, 10)).is_err());
+    assert!(validate(&with(30_000, 0)).is_err());
+    assert!(validate(&with(30_000, 101)).is_err());
+}
+
+#[test]
+fn validates_upstream_outlier_detection() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let detection = OutlierDetection {
+        interval_ms: 10_000,
+        min_requests: 20,
+        min_backends: 3,
+        success_rate_min_percent: None,
+        success_rate_stdev_factor: Some(1.9),
+        latency_p95_ratio_percent: Some(300),
+        max_ejection_percent: 10,
+        base_ejection_ms: 30_000,
+        max_ejection_ms: 300_000,
+    };
+    let with = |detection: OutlierDetection| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .outlier_detection = Some(detection);
+        cfg
+    };
+
+    assert!(validate(&with(detection.clone())).is_ok());
+    assert!(
+        validate(&with(OutlierDetection {
+            success_rate_stdev_factor: None,
+            latency_p95_ratio_percent: None,
+            ..detection.clone()
+        }))
+        .is_err(),
+        "at least one check is required"
+    );
+    assert!(
+        validate(&with(OutlierDetection {
+            success_rate_stdev_factor: Some(-1.0),
+            ..detection.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(OutlierDetection {
+            latency_p95_ratio_percent: Some(50),
+            ..detection.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(OutlierDetection {
+            max_ejection_percent: 101,
+            ..detection.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(OutlierDetection {
+            max_ejection_ms: 1_000,
+            ..detection
+        }))
+        .is_err()
+    );
+}
+
+#[test]
+fn validates_gradient_adaptive_admission() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let gradient = AdaptiveAdmission {
+        algorithm: AdaptiveAdmissionAlgorithm::Gradient,
+        ..AdaptiveAdmission::default()
+    };
+    let global = |adaptive: AdaptiveAdmission| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.resilience.adaptive_admission = adaptive;
+        cfg
+    };
+    let upstream = |adaptive: AdaptiveAdmission| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .adaptive_admission = Some(adaptive);
+        cfg
+    };
+
+    assert!(validate(&global(gradient.clone())).is_ok());
+    assert!(validate(&upstream(gradient.clone())).is_ok());
+    assert!(
+        validate(&global(AdaptiveAdmission {
+            rtt_tolerance_percent: 90,
+            ..gradient.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&global(AdaptiveAdmission {
+            smoothing_percent: 0,
+            ..gradient.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&upstream(AdaptiveAdmission {
+            min_rtt_window_ms: 0,
+            ..gradient.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&global(AdaptiveAdmission {
+            smoothing_percent: 0,
+            ..AdaptiveAdmission::default()
+        }))
+        .is_ok(),
+        "gradient settings are ignored by aimd"
+    );
+
+    let per_upstream_limit = base_config(&cert.to_string_lossy(), &key.to_string_lossy())
+        .performance
+        .per_upstream_inflight_limit;
+    assert!(
+        validate(&upstream(AdaptiveAdmission {
+            max_limit: Some(per_upstream_limit + 1),
+            ..gradient
+        }))
+        .is_err(),
+        "upstream limiters are capped by the per-upstream inflight limit"
+    );
+}
+
+#[test]
+fn validates_hash_load_balancing_options() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |lb_type: &str, table_size: Option<u64>, hash_balance_factor: Option<u32>| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        let lb = &mut cfg
+            .upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .load_balancing;
+        lb.lb_type = lb_type.to_string();
+        lb.table_size = table_size;
+        lb.hash_balance_factor = hash_balance_factor;
+        cfg
+    };
+
+    assert!(validate(&with("maglev", None, None)).is_ok());
+    assert!(validate(&with("maglev", Some(65_537), None)).is_ok());
+    assert!(validate(&with("maglev", Some(65_536), None)).is_err());
+    assert!(validate(&with("maglev", Some(10_000_019), None)).is_err());
+    assert!(validate(&with("round-robin", Some(65_537), None)).is_err());
+    assert!(validate(&with("consistent_hash_bounded", None, Some(150))).is_ok());
+    assert!(validate(&with("consistent-hash-bounded", None, Some(99))).is_err());
+    assert!(validate(&with("consistent-hash", None, Some(150))).is_err());
+}
+
+#[test]
+fn validates_p2c_load_balancing_options() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |lb_type: &str, choices: Option<u32>| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        let lb = &mut cfg
+            .upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .load_balancing;
+        lb.lb_type = lb_type.to_string();
+        lb.p2c = choices.map(|choices| PowerOfTwoChoices {
+            choices,
+            ..PowerOfTwoChoices::default()
+        });
+        cfg
+    };
+
+    assert!(validate(&with("p2c", None)).is_ok());
+    assert!(validate(&with("power_of_two_choices", Some(8))).is_ok());
+    assert!(validate(&with("p2c", Some(1))).is_err());
+    assert!(validate(&with("p2c", Some(9))).is_err());
+    assert!(validate(&with("least-connections", Some(2))).is_err());
+}
+
+#[test]
+fn validates_custom_load_balancing_type() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |lb_type: &str, table_size: Option<u64>| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        let lb = &mut cfg
+            .upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .load_balancing;
+        lb.lb_type = lb_type.to_string();
+        lb.table_size = table_size;
+        cfg
+    };
+
+    assert!(validate(&with("custom:shadow-rr", None)).is_ok());
+    assert!(validate(&with("CUSTOM:shadow-rr", None)).is_ok());
+    assert!(validate(&with("custom:", None)).is_err());
+    assert!(validate(&with("custom", None)).is_err());
+    assert!(validate(&with("custom:shadow-rr", Some(65_537))).is_err());
+}
+
+#[test]
+fn validates_panic_threshold_percent() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |threshold: u32| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        let upstream = cfg.upstream.get_mut("test_upstream").expect("upstream");
+        upstream.load_balancing.panic_threshold_percent = Some(threshold);
+        upstream.backends[0].priority = 1;
+        cfg
+    };
+
+    assert!(validate(&with(0)).is_ok());
+    assert!(validate(&with(100)).is_ok());
+    assert!(validate(&with(101)).is_err());
+}
+
+#[test]
+fn validates_zone_settings() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |instance: &str, backend: &str, max_inflight: u32| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.locality.zone = Some(instance.to_string());
+        let upstream = cfg.upstream.get_mut("test_upstream").expect("upstream");
+        upstream.backends[0].zone = Some(backend.to_string());
+        upstream.zone_aware = Some(ZoneAware {
+            enabled: true,
+            max_inflight_per_backend: Some(max_inflight),
+        });
+        cfg
+    };
+
+    assert!(validate(&with("us-east-1a", "us-east-1b", 64)).is_ok());
+    assert!(validate(&with(" ", "us-east-1b", 64)).is_err());
+    assert!(validate(&with("us-east-1a", "", 64)).is_err());
+    assert!(validate(&with("us-east-1a", "us-east-1b", 0)).is_err());
+}
+
+#[test]
+fn validates_session_affinity_cookie() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let with = |affinity: SessionAffinity| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.upstream
+            .get_mut("test_upstream")
+            .expect("upstream")
+            .session_affinity = Some(affinity);
+        cfg
+    };
+
+    assert!(validate(&with(SessionAffinity::default())).is_ok());
+    assert!(
+        validate(&with(SessionAffinity {
+            cookie_name: "sid;x".to_string(),
+            ..SessionAffinity::default()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(SessionAffinity {
+            path: "app".to_string(),
+            ..SessionAffinity::default()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(SessionAffinity {
+            ttl_secs: Some(0),
+            ..SessionAffinity::default()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(SessionAffinity {
+            same_site: CookieSameSite::None,
+            secure: false,
+            ..SessionAffinity::default()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(SessionAffinity {
+            secret: Some(" ".to_string()),
+            ..SessionAffinity::default()
+        }))
+        .is_err()
+    );
+}
+
+#[test]
+fn validates_percentile_hedging() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let hedging = Hedging {
+        enabled: true,
+        mode: HedgingMode::Percentile,
+        max_hedges: 2,
+        max_hedged_percent: 10,
+        ..Hedging::default()
+    };
+    let with = |hedging: Hedging| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.resilience.hedging = hedging;
+        cfg
+    };
+
+    assert!(validate(&with(hedging.clone())).is_ok());
+    assert!(
+        validate(&with(Hedging {
+            percentile: 100,
+            ..hedging.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Hedging {
+            min_delay_ms: 200,
+            max_delay_ms: 100,
+            ..hedging.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Hedging {
+            max_hedges: 4,
+            ..hedging.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Hedging {
+            max_hedged_percent: 0,
+            ..hedging.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Hedging {
+            mode: HedgingMode::Fixed,
+            percentile: 100,
+            ..hedging
+        }))
+        .is_ok(),
+        "percentile settings are ignored in fixed mode"
+    );
+}
+
+#[test]
+fn validates_priority_classes_and_rules() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+    let class = |name: &str, max_inflight_percent: u8| PriorityClass {
+        name: name.to_string(),
+        max_inflight_percent,
+        brownout_exempt: false,
+    };
+    let rule = |class: &str| PriorityRule {
+        class: class.to_string(),
+        routes: Vec::new(),
+        header: None,
+        jwt_claim: None,
+        values: Vec::new(),
+    };
+    let priority = Priority {
+        default_class: "standard".to_string(),
+        classes: vec![
+            class("critical", 100),
+            class("standard", 80),
+            class("bulk", 50),
+        ],
+        rules: vec![
+            PriorityRule {
+                routes: vec!["checkout".to_string()],
+                ..rule("critical")
+            },
+            PriorityRule {
+                header: Some("x-request-class".to_string()),
+                values: vec!["analytics".to_string()],
+                ..rule("bulk")
+            },
+            PriorityRule {
+                jwt_claim: Some("tier".to_string()),
+                values: vec!["gold".to_string()],
+                ..rule("critical")
+            },
+        ],
+    };
+    let with = |priority: Priority| {
+        let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+        cfg.resilience.priority = priority;
+        cfg
+    };
+
+    assert!(validate(&with(priority.clone())).is_ok());
+    assert!(validate(&with(Priority::default())).is_ok());
+    assert!(
+        validate(&with(Priority {
+            default_class: "unknown".to_string(),
+            ..priority.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Priority {
+            classes: vec![class("standard", 0)],
+            rules: Vec::new(),
+            ..priority.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Priority {
+            classes: vec![class("standard", 100), class("standard", 50)],
+            rules: Vec::new(),
+            ..priority.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Priority {
+            rules: vec![PriorityRule {
+                routes: vec!["checkout".to_string()],
+                ..rule("missing")
+            }],
+            ..priority.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Priority {
+            rules: vec![rule("bulk")],
+            ..priority.clone()
+        }))
+        .is_err(),
+        "a rule must match on something"
+    );
+    assert!(
+        validate(&with(Priority {
+            rules: vec![PriorityRule {
+                header: Some("x-request-class".to_string()),
+                jwt_claim: Some("tier".to_string()),
+                values: vec!["analytics".to_string()],
+                ..rule("bulk")
+            }],
+            ..priority.clone()
+        }))
+        .is_err()
+    );
+    assert!(
+        validate(&with(Priority {
+            rules: vec![PriorityRule {
+                header: Some("x-request-class".to_string()),
+                ..rule("bulk")
+            }],
+            ..priority
+        }))
+        .is_err()
+    );
+}
+
 #[test]
 fn accepts_upstream_api_key_auth_with_default_header() {
     let dir = tempdir().expect("tempdir");
@@ -1694,3 +2276,441 @@ fn rejects_rbac_requirements_without_jwt_auth() {
 
     assert!(validate(&cfg).is_err());
 }
+
+fn named_route(name: &str, upstream: &str, path_prefix: &str) -> Route {
+    Route {
+        name: name.to_string(),
+        upstream: upstream.to_string(),
+        matcher: RouteMatch {
+            host: None,
+            path_prefix: Some(path_prefix.to_string()),
+            method: None,
+        },
+        auth: None,
+        host_policy: None,
+        forwarded_headers: None,
+        rewrite: None,
+        split: None,
+        redirect: None,
+        direct_response: None,
+        mirror: None,
+        fault: None,
+        policy: None,
+    }
+}
+
+#[test]
+fn accepts_upstream_reachable_only_through_named_routes() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.upstream
+        .get_mut("test_upstream")
+        .expect("upstream")
+        .route = RouteMatch::default();
+    cfg.routes = vec![
+        named_route("checkout", "test_upstream", "/checkout"),
+        named_route("cart", "test_upstream", "/cart"),
+    ];
+
+    assert!(validate(&cfg).is_ok());
+}
+
+#[test]
+fn rejects_upstream_without_shorthand_or_named_route() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.upstream
+        .get_mut("test_upstream")
+        .expect("upstream")
+        .route = RouteMatch::default();
+
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_named_route_with_unknown_upstream_or_duplicate_name() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.routes = vec![named_route("checkout", "missing", "/checkout")];
+    assert!(validate(&cfg).is_err());
+
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.routes = vec![named_route("test_upstream", "test_upstream", "/checkout")];
+    assert!(validate(&cfg).is_err());
+
+    cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.routes = vec![
+        named_route("checkout", "test_upstream", "/checkout"),
+        named_route("checkout", "test_upstream", "/cart"),
+    ];
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_named_route_conflicting_with_upstream_shorthand_route() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.routes = vec![named_route("root", "test_upstream", "/")];
+
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_named_route_with_invalid_auth_override() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut route = named_route("admin", "test_upstream", "/admin");
+    route.auth = Some(RouteAuth {
+        required_roles: vec!["admin".to_string()],
+        ..Default::default()
+    });
+    cfg.routes = vec![route];
+
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_split_targeting_unknown_upstream() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut route = named_route("checkout", "test_upstream", "/checkout");
+    route.split = Some(TrafficSplit {
+        key: None,
+        targets: vec![
+            SplitTarget {
+                upstream: "test_upstream".to_string(),
+                weight: 90,
+            },
+            SplitTarget {
+                upstream: "missing".to_string(),
+                weight: 10,
+            },
+        ],
+        rollout: None,
+    });
+    cfg.routes.push(route);
+
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_rollout_with_non_increasing_steps() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut canary = cfg.upstream["test_upstream"].clone();
+    canary.route = RouteMatch::default();
+    canary.backends[0].id = "canary-1".to_string();
+    canary.backends[0].address = "127.0.0.1:8081".to_string();
+    cfg.upstream.insert("canary".to_string(), canary);
+
+    let mut route = named_route("checkout", "test_upstream", "/checkout");
+    route.split = Some(TrafficSplit {
+        key: None,
+        targets: vec![
+            SplitTarget {
+                upstream: "test_upstream".to_string(),
+                weight: 1,
+            },
+            SplitTarget {
+                upstream: "canary".to_string(),
+                weight: 1,
+            },
+        ],
+        rollout: Some(CanaryRollout {
+            canary: "canary".to_string(),
+            steps: vec![5, 50, 100],
+            step_interval_ms: 60_000,
+            min_requests: 10,
+            max_error_rate_increase_percent: 1,
+            max_p95_latency_ratio_percent: 150,
+        }),
+    });
+    cfg.routes.push(route);
+    assert!(validate(&cfg).is_ok());
+
+    let rollout = cfg.routes[0]
+        .split
+        .as_mut()
+        .and_then(|split| split.rollout.as_mut())
+        .expect("rollout");
+    rollout.steps = vec![5, 5, 100];
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn rejects_route_rewrite_without_exactly_one_valid_rule() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut route = named_route("billing", "test_upstream", "/api/billing");
+    route.rewrite = Some(PathRewrite {
+        strip_prefix: Some("/api/billing".to_string()),
+        replace_prefix: None,
+        regex: None,
+    });
+    cfg.routes.push(route);
+    assert!(validate(&cfg).is_ok());
+
+    let rewrite = cfg.routes[0].rewrite.as_mut().expect("rewrite");
+    rewrite.replace_prefix = Some(PrefixRewrite {
+        prefix: "/api/billing".to_string(),
+        replacement: "/billing".to_string(),
+    });
+    assert!(validate(&cfg).is_err());
+
+    let rewrite = cfg.routes[0].rewrite.as_mut().expect("rewrite");
+    rewrite.strip_prefix = None;
+    assert!(validate(&cfg).is_ok());
+
+    let rewrite = cfg.routes[0].rewrite.as_mut().expect("rewrite");
+    rewrite.replace_prefix = None;
+    rewrite.strip_prefix = Some("/".to_string());
+    assert!(validate(&cfg).is_err());
+}
+
+#[test]
+fn validates_route_actions_answered_by_the_edge() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut route = named_route("legacy", "", "/legacy");
+    route.redirect = Some(RouteRedirect {
+        status: 308,
+        scheme: None,
+        host: Some("www.example.com".to_string()),
+        path: None,
+        query: None,
+    });
+    cfg.routes.push(route);
+    assert!(validate(&cfg).is_ok());
+
+    cfg.routes[0].upstream = "test_upstream".to_string();
+    assert!(
+        validate(&cfg).is_err(),
+        "upstream and redirect are exclusive"
+    );
+    cfg.routes[0].upstream.clear();
+
+    cfg.routes[0].redirect.as_mut().expect("redirect").status = 200;
+    assert!(validate(&cfg).is_err(), "redirect needs a 3xx status");
+
+    let body_path = dir.path().join("maintenance.html");
+    std::fs::write(&body_path, "<h1>back soon</h1>").expect("write body");
+    cfg.routes[0].redirect = None;
+    cfg.routes[0].direct_response = Some(DirectResponse {
+        status: 503,
+        headers: vec![ResponseHeader {
+            name: "content-type".to_string(),
+            value: "text/html".to_string(),
+        }],
+        body: None,
+        body_file: Some(body_path.to_string_lossy().into_owned()),
+    });
+    assert!(validate(&cfg).is_ok());
+
+    let direct = cfg.routes[0].direct_response.as_mut().expect("direct");
+    direct.body = Some("inline".to_string());
+    assert!(validate(&cfg).is_err(), "body and body_file are exclusive");
+    let direct = cfg.routes[0].direct_response.as_mut().expect("direct");
+    direct.body_file = None;
+    direct.headers[0].name = "content-length".to_string();
+    assert!(validate(&cfg).is_err(), "content-length is set by the edge");
+
+    cfg.routes[0].direct_response = None;
+    assert!(
+        validate(&cfg).is_err(),
+        "route needs an upstream or an action"
+    );
+}
+
+#[test]
+fn validates_route_mirror_targets_and_bounds() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut shadow = cfg.upstream["test_upstream"].clone();
+    shadow.route = RouteMatch::default();
+    shadow.backends[0].id = "shadow-1".to_string();
+    shadow.backends[0].address = "127.0.0.1:8081".to_string();
+    cfg.upstream.insert("shadow".to_string(), shadow);
+
+    let mut route = named_route("checkout", "test_upstream", "/checkout");
+    route.mirror = Some(RouteMirror {
+        upstream: "shadow".to_string(),
+        percent: 10,
+        methods: vec!["GET".to_string(), "post".to_string()],
+        max_body_bytes: 4096,
+        max_inflight: 8,
+    });
+    cfg.routes.push(route);
+    assert!(
+        validate(&cfg).is_ok(),
+        "an upstream used only as a mirror is reachable"
+    );
+
+    let mirror = cfg.routes[0].mirror.as_mut().expect("mirror");
+    mirror.percent = 0;
+    assert!(validate(&cfg).is_err(), "percent must be positive");
+
+    let mirror = cfg.routes[0].mirror.as_mut().expect("mirror");
+    mirror.percent = 10;
+    mirror.methods.push("CONNECT".to_string());
+    assert!(validate(&cfg).is_err(), "tunnels are never mirrored");
+
+    let mirror = cfg.routes[0].mirror.as_mut().expect("mirror");
+    mirror.methods.pop();
+    mirror.max_body_bytes = crate::config::MAX_MIRROR_BODY_BYTES + 1;
+    assert!(validate(&cfg).is_err(), "mirror body buffer is bounded");
+
+    let mirror = cfg.routes[0].mirror.as_mut().expect("mirror");
+    mirror.max_body_bytes = 4096;
+    mirror.max_inflight = 0;
+    assert!(validate(&cfg).is_err(), "mirror needs an inflight budget");
+
+    let mirror = cfg.routes[0].mirror.as_mut().expect("mirror");
+    mirror.max_inflight = 8;
+    mirror.upstream = "test_upstream".to_string();
+    assert!(validate(&cfg).is_err(), "mirror cannot target the primary");
+}
+
+#[test]
+fn validates_route_fault_shapes() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    let mut route = named_route("checkout", "test_upstream", "/checkout");
+    route.fault = Some(RouteFault {
+        header: Some(FaultHeaderMatch {
+            name: "x-fault".to_string(),
+            value: Some("on".to_string()),
+        }),
+        delay: Some(FaultDelay {
+            percent: 50,
+            duration_ms: 100,
+            max_duration_ms: Some(400),
+        }),
+        abort: Some(FaultAbort {
+            percent: 0,
+            status: Some(503),
+            reset: false,
+        }),
+        truncate: Some(FaultTruncate {
+            percent: 10,
+            after_bytes: 512,
+        }),
+    });
+    cfg.routes.push(route);
+    assert!(validate(&cfg).is_ok(), "zero percent keeps a fault idle");
+
+    let fault = cfg.routes[0].fault.as_mut().expect("fault");
+    fault.abort.as_mut().expect("abort").reset = true;
+    assert!(validate(&cfg).is_err(), "abort takes a status or a reset");
+
+    let fault = cfg.routes[0].fault.as_mut().expect("fault");
+    let abort = fault.abort.as_mut().expect("abort");
+    abort.status = Some(302);
+    abort.reset = false;
+    assert!(validate(&cfg).is_err(), "abort status must be an error");
+
+    let fault = cfg.routes[0].fault.as_mut().expect("fault");
+    fault.abort = None;
+    fault.delay.as_mut().expect("delay").max_duration_ms = Some(50);
+    assert!(validate(&cfg).is_err(), "delay range must be ordered");
+
+    let fault = cfg.routes[0].fault.as_mut().expect("fault");
+    fault.delay.as_mut().expect("delay").max_duration_ms = None;
+    fault.truncate.as_mut().expect("truncate").percent = 101;
+    assert!(validate(&cfg).is_err(), "percent is capped at 100");
+
+    let fault = cfg.routes[0].fault.as_mut().expect("fault");
+    fault.delay = None;
+    fault.truncate = None;
+    assert!(validate(&cfg).is_err(), "an empty fault block is rejected");
+}
+
+#[test]
+fn validates_route_policy_bounds_against_effective_timeouts() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(dir.path());
+
+    let mut cfg = base_config(&cert.to_string_lossy(), &key.to_string_lossy());
+    cfg.upstream
+        .get_mut("test_upstream")
+        .expect("upstream")
+        .policy
+        .backend_total_request_timeout_ms = Some(3_000);
+    let mut route = named_route("checkout", "test_upstream", "/checkout");
+    route.policy = Some(RoutePolicy {
+        backend_timeout_ms: Some(2_500),
+        max_retry_attempts: Some(3),
+        ..RoutePolicy::default()
+    });
+    cfg.routes.push(route);
+    assert!(validate(&cfg).is_ok());
+
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.backend_timeout_ms = Some(3_500);
+    assert!(
+        validate(&cfg).is_err(),
+        "route timeout exceeds the inherited total timeout"
+    );
+
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.backend_timeout_ms = None;
+    policy.hedging_delay_ms = Some(0);
+    assert!(validate(&cfg).is_err(), "zero hedging delay is rejected");
+
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.hedging_delay_ms = None;
+    policy.max_retry_attempts = Some(4);
+    assert!(validate(&cfg).is_err(), "retries are capped");
+
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.max_retry_attempts = None;
+    policy.retry_on = Some(vec![RetryOn::Status(503)]);
+    assert!(validate(&cfg).is_ok());
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.retry_on = Some(vec![RetryOn::Status(200)]);
+    assert!(validate(&cfg).is_err(), "retry statuses must be errors");
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.retry_on = Some(Vec::new());
+    assert!(validate(&cfg).is_err(), "empty retry_on is rejected");
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.retry_on = None;
+    policy.retry_backoff_max_ms = Some(10);
+    assert!(validate(&cfg).is_err(), "backoff max below default base");
+
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.retry_backoff_max_ms = None;
+    policy.replay_body_max_bytes = Some(cfg.performance.request_buffer_global_cap_bytes);
+    assert!(validate(&cfg).is_ok());
+    let cap = cfg.performance.request_buffer_global_cap_bytes;
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.replay_body_max_bytes = Some(cap + 1);
+    assert!(validate(&cfg).is_err(), "replay buffer exceeds global cap");
+    let policy = cfg.routes[0].policy.as_mut().expect("policy");
+    policy.replay_body_max_bytes = None;
+    policy.idempotency_header = Some("bad header".to_string());
+    assert!(
+        validate(&cfg).is_err(),
+        "idempotency header must be a header name"
+    );
+}
@@ -2,7 +2,10 @@
 
 use std::time::Duration;
 
-use spooky_config::{config::HealthCheck, runtime::RuntimeConfig};
+use spooky_config::{
+    config::{Backend, HealthCheck},
+    runtime::{RuntimeBackend, RuntimeBackendTransportKind, RuntimeConfig},
+};
 
 use crate::common::sample_config;
 
@@ -29,3 +32,44 @@ fn runtime_backend_health_check_and_endpoint_are_canonicalized() {
     assert_eq!(health.failure_threshold, 4);
     assert_eq!(health.success_threshold, 3);
 }
+
+#[test]
+fn standalone_backend_normalization_applies_config_rules() {
+    let mut backend = Backend {
+        id: "extra".to_string(),
+        address: "http://10.0.0.9:8080".to_string(),
+        weight: 10,
+        health_check: None,
+        priority: 0,
+        zone: Some("us-east-1a".to_string()),
+    };
+    let runtime = RuntimeBackend::normalize("api", &backend).expect("valid backend");
+    assert_eq!(
+        runtime.endpoint.transport_kind,
+        RuntimeBackendTransportKind::Http1
+    );
+
+    backend.weight = 0;
+    assert!(RuntimeBackend::normalize("api", &backend).is_err());
+    backend.weight = 10;
+    backend.zone = Some(" ".to_string());
+    assert!(RuntimeBackend::normalize("api", &backend).is_err());
+}
+
+#[test]
+fn runtime_config_rejects_duplicate_backend_ids_within_an_upstream() {
+    let mut config = sample_config();
+    let backends = &mut config.upstream.get_mut("api").expect("api").backends;
+    let mut duplicate = backends[0].clone();
+    duplicate.address = "https://api-2.internal:8443".to_string();
+    let original = backends[0].clone();
+    backends.push(duplicate.clone());
+
+    let err = RuntimeConfig::from_config(&config).expect_err("duplicate backend id");
+    assert_eq!(err.category(), "config_invalid");
+
+    duplicate.id = "api-2".to_string();
+    let runtime =
+        RuntimeBackend::normalize_all("api", &[original, duplicate]).expect("distinct ids");
+    assert_eq!(runtime.len(), 2);
+}
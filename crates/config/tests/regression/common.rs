@@ -25,6 +25,7 @@ pub fn sample_config() -> Config {
         },
         listeners: Vec::new(),
         upstream: HashMap::new(),
+        routes: Vec::new(),
         load_balancing: None,
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -32,6 +33,7 @@ pub fn sample_config() -> Config {
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     };
 
     config.upstream.insert(
@@ -40,12 +42,22 @@ pub fn sample_config() -> Config {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: UpstreamHostPolicy {
                 mode: UpstreamHostPolicyMode::Rewrite,
                 host: Some("api.internal".to_string()),
             },
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: ForwardedHeaderPolicy {
                 mode: ForwardedHeaderPolicyMode::Append,
             },
@@ -60,6 +72,8 @@ pub fn sample_config() -> Config {
                 address: "https://api.internal:8443".to_string(),
                 weight: 100,
                 health_check: None,
+                priority: 0,
+                zone: None,
             }],
         },
     );
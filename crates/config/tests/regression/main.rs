@@ -14,5 +14,6 @@ mod common;
 mod auth;
 mod backends;
 mod policy;
+mod routes;
 mod timeouts;
 mod tls;
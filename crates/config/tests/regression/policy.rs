@@ -1,6 +1,17 @@
 //! Policy-combination and route-matcher rejection cases.
 
-use spooky_config::{config::UpstreamHostPolicyMode, runtime::RuntimeConfig};
+use std::time::Duration;
+
+use spooky_config::{
+    config::{
+        CookieSameSite, DEFAULT_HASH_BALANCE_FACTOR, HedgingMode, Locality, P2cMetric,
+        PowerOfTwoChoices, PriorityClass, PriorityRule, SessionAffinity, SlowStart, SlowStartCurve,
+        UpstreamHostPolicyMode, ZoneAware,
+    },
+    runtime::{
+        RuntimeConfig, RuntimeLoadBalancingStrategy, RuntimeLocality, RuntimeSessionAffinity,
+    },
+};
 
 use crate::common::sample_config;
 
@@ -53,6 +64,224 @@ fn runtime_config_rejects_invalid_lb_key_spec() {
     assert!(err.to_string().contains("unsupported request key spec"));
 }
 
+#[test]
+fn runtime_config_normalizes_maglev_and_bounded_hash_options() {
+    let mut config = sample_config();
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = "maglev".to_string();
+    lb.table_size = Some(251);
+
+    let runtime = RuntimeConfig::from_config(&config).expect("maglev");
+    let policy = &runtime.upstreams["api"].load_balancing;
+    assert_eq!(policy.strategy, RuntimeLoadBalancingStrategy::Maglev);
+    assert_eq!(policy.table_size, 251);
+    assert!(policy.alternate_backend.readonly_lb_pick);
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.table_size = Some(250);
+    let err = RuntimeConfig::from_config(&config).expect_err("non-prime table");
+    assert!(err.to_string().contains("must be a prime"));
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = "consistent-hash-bounded".to_string();
+    lb.table_size = None;
+    let runtime = RuntimeConfig::from_config(&config).expect("bounded");
+    let policy = &runtime.upstreams["api"].load_balancing;
+    assert_eq!(
+        policy.strategy,
+        RuntimeLoadBalancingStrategy::BoundedConsistentHash
+    );
+    assert_eq!(policy.hash_balance_factor, DEFAULT_HASH_BALANCE_FACTOR);
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.hash_balance_factor = Some(90);
+    let err = RuntimeConfig::from_config(&config).expect_err("factor below 100");
+    assert!(err.to_string().contains("must be at least 100"));
+}
+
+#[test]
+fn runtime_config_normalizes_p2c_options() {
+    let mut config = sample_config();
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = "power_of_two_choices".to_string();
+
+    let runtime = RuntimeConfig::from_config(&config).expect("default p2c");
+    let policy = &runtime.upstreams["api"].load_balancing;
+    assert_eq!(policy.strategy, RuntimeLoadBalancingStrategy::P2c);
+    assert_eq!(policy.p2c.choices, 2);
+    assert_eq!(policy.p2c.metric, P2cMetric::LeastRequest);
+    assert!(policy.p2c.weighted);
+    assert!(policy.alternate_backend.readonly_lb_pick);
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.p2c = Some(PowerOfTwoChoices {
+        choices: 3,
+        metric: P2cMetric::Latency,
+        weighted: false,
+    });
+    let runtime = RuntimeConfig::from_config(&config).expect("tuned p2c");
+    let policy = &runtime.upstreams["api"].load_balancing;
+    assert_eq!(policy.p2c.choices, 3);
+    assert_eq!(policy.p2c.metric, P2cMetric::Latency);
+    assert!(!policy.p2c.weighted);
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = "least-connections".to_string();
+    let err = RuntimeConfig::from_config(&config).expect_err("p2c block on other strategy");
+    assert!(err.to_string().contains("only applies to 'p2c'"));
+}
+
+#[test]
+fn runtime_config_carries_custom_strategy_names() {
+    let mut config = sample_config();
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = " Custom: shadow-rr ".to_string();
+
+    let runtime = RuntimeConfig::from_config(&config).expect("custom strategy");
+    let policy = &runtime.upstreams["api"].load_balancing;
+    assert_eq!(policy.strategy, RuntimeLoadBalancingStrategy::Custom);
+    assert_eq!(policy.custom.as_deref(), Some("shadow-rr"));
+    assert_eq!(policy.strategy_name(), "shadow-rr");
+    assert!(policy.alternate_backend.readonly_lb_pick);
+
+    let lb = &mut config.upstream.get_mut("api").expect("api").load_balancing;
+    lb.lb_type = "custom:".to_string();
+    let err = RuntimeConfig::from_config(&config).expect_err("empty custom name");
+    assert!(err.to_string().contains("unsupported load balancing type"));
+}
+
+#[test]
+fn runtime_config_normalizes_upstream_slow_start() {
+    let mut config = sample_config();
+    config.upstream.get_mut("api").expect("api").slow_start = Some(SlowStart {
+        window_ms: 45_000,
+        min_weight_percent: 20,
+        curve: SlowStartCurve::Linear,
+    });
+
+    let runtime = RuntimeConfig::from_config(&config).expect("slow start");
+    let slow_start = runtime.upstreams["api"].slow_start.expect("normalized");
+    assert_eq!(slow_start.window, Duration::from_secs(45));
+    assert_eq!(slow_start.min_weight_percent, 20);
+    assert_eq!(slow_start.curve, SlowStartCurve::Linear);
+
+    config
+        .upstream
+        .get_mut("api")
+        .expect("api")
+        .slow_start
+        .as_mut()
+        .expect("slow start")
+        .window_ms = 0;
+    let err = RuntimeConfig::from_config(&config).expect_err("zero window");
+    assert!(err.to_string().contains("slow_start.window_ms"));
+}
+
+#[test]
+fn runtime_config_normalizes_panic_threshold_and_priorities() {
+    let mut config = sample_config();
+    let runtime = RuntimeConfig::from_config(&config).expect("defaults");
+    let upstream = &runtime.upstreams["api"];
+    assert_eq!(upstream.load_balancing.panic_threshold_percent, 0);
+    assert_eq!(upstream.backends[0].backend.priority, 0);
+
+    let api = config.upstream.get_mut("api").expect("api");
+    api.load_balancing.panic_threshold_percent = Some(40);
+    api.backends[0].priority = 2;
+    let runtime = RuntimeConfig::from_config(&config).expect("tiers");
+    let upstream = &runtime.upstreams["api"];
+    assert_eq!(upstream.load_balancing.panic_threshold_percent, 40);
+    assert_eq!(upstream.backends[0].backend.priority, 2);
+
+    config
+        .upstream
+        .get_mut("api")
+        .expect("api")
+        .load_balancing
+        .panic_threshold_percent = Some(150);
+    let err = RuntimeConfig::from_config(&config).expect_err("threshold above 100");
+    assert!(err.to_string().contains("panic_threshold_percent"));
+}
+
+#[test]
+fn locality_zone_prefers_config_over_environment() {
+    let env = |key: &str| (key == "SPOOKY_ZONE").then(|| " eu-west-1b ".to_string());
+    let mut locality = Locality::default();
+    assert_eq!(locality.resolve_zone(env).as_deref(), Some("eu-west-1b"));
+
+    locality.zone = Some("eu-west-1a".to_string());
+    assert_eq!(locality.resolve_zone(env).as_deref(), Some("eu-west-1a"));
+
+    locality.zone = None;
+    locality.zone_env = "OTHER_ZONE".to_string();
+    assert_eq!(locality.resolve_zone(env), None);
+}
+
+#[test]
+fn runtime_config_lowers_zone_aware_policy() {
+    let mut config = sample_config();
+    config.locality.zone_env = "SPOOKY_REGRESSION_ZONE_UNSET".to_string();
+    let runtime = RuntimeConfig::from_config(&config).expect("no zone");
+    assert_eq!(runtime.upstreams["api"].locality, None);
+
+    config.locality.zone = Some("eu-west-1a".to_string());
+    let runtime = RuntimeConfig::from_config(&config).expect("zone");
+    assert_eq!(
+        runtime.upstreams["api"].locality,
+        Some(RuntimeLocality {
+            zone: "eu-west-1a".to_string(),
+            zone_aware: true,
+            max_inflight_per_backend: None,
+        })
+    );
+
+    config.upstream.get_mut("api").expect("api").zone_aware = Some(ZoneAware {
+        enabled: false,
+        max_inflight_per_backend: Some(0),
+    });
+    let err = RuntimeConfig::from_config(&config).expect_err("zero in-flight limit");
+    assert!(err.to_string().contains("max_inflight_per_backend"));
+}
+
+#[test]
+fn runtime_config_lowers_session_affinity() {
+    let mut config = sample_config();
+    config
+        .upstream
+        .get_mut("api")
+        .expect("api")
+        .session_affinity = Some(SessionAffinity {
+        ttl_secs: Some(600),
+        same_site: CookieSameSite::Strict,
+        secret: Some("s3cret".to_string()),
+        ..SessionAffinity::default()
+    });
+    let runtime = RuntimeConfig::from_config(&config).expect("valid affinity");
+    assert_eq!(
+        runtime.upstreams["api"].session_affinity,
+        Some(RuntimeSessionAffinity {
+            cookie_name: "spooky_affinity".to_string(),
+            ttl: Some(Duration::from_secs(600)),
+            path: "/".to_string(),
+            same_site: CookieSameSite::Strict,
+            secure: true,
+            secret: Some("s3cret".to_string()),
+        })
+    );
+
+    config
+        .upstream
+        .get_mut("api")
+        .expect("api")
+        .session_affinity = Some(SessionAffinity {
+        same_site: CookieSameSite::None,
+        secure: false,
+        ..SessionAffinity::default()
+    });
+    let err = RuntimeConfig::from_config(&config).expect_err("insecure SameSite=None");
+    assert!(err.to_string().contains("same_site none requires secure"));
+}
+
 #[test]
 fn runtime_config_rejects_connect_route_when_protocol_disallows_connect() {
     let mut config = sample_config();
@@ -68,3 +297,69 @@ fn runtime_config_rejects_connect_route_when_protocol_disallows_connect() {
     assert_eq!(err.category(), "unsupported_policy_combination");
     assert!(err.to_string().contains("allow_connect=false"));
 }
+
+#[test]
+fn runtime_config_normalizes_percentile_hedging() {
+    let mut config = sample_config();
+    config.resilience.hedging.enabled = true;
+    config.resilience.hedging.mode = HedgingMode::Percentile;
+    config.resilience.hedging.percentile = 99;
+    config.resilience.hedging.min_delay_ms = 20;
+    config.resilience.hedging.max_delay_ms = 400;
+    config.resilience.hedging.max_hedges = 2;
+    let runtime = RuntimeConfig::from_config(&config).expect("valid percentile hedging");
+    let hedging = &runtime.policies.admission.hedging;
+    assert_eq!(hedging.mode, HedgingMode::Percentile);
+    assert_eq!(hedging.percentile, 99);
+    assert_eq!(hedging.min_delay, Duration::from_millis(20));
+    assert_eq!(hedging.max_delay, Duration::from_millis(400));
+    assert_eq!(hedging.max_hedges, 2);
+    assert_eq!(hedging.max_hedged_percent, 100);
+
+    config.resilience.hedging.max_delay_ms = 10;
+    let err = RuntimeConfig::from_config(&config).expect_err("inverted delay bounds must fail");
+    assert_eq!(err.category(), "config_invalid");
+    assert!(err.to_string().contains("max_delay_ms"));
+}
+
+#[test]
+fn runtime_config_normalizes_priority_classes() {
+    let config = sample_config();
+    let runtime = RuntimeConfig::from_config(&config).expect("default priority");
+    let priority = &runtime.policies.admission.priority.0;
+    assert_eq!(priority.default_class, "default");
+    assert_eq!(priority.classes.len(), 1);
+    assert_eq!(priority.classes[0].name, "default");
+    assert_eq!(priority.classes[0].max_inflight_percent, 100);
+
+    let mut config = sample_config();
+    config.resilience.priority.default_class = " standard ".to_string();
+    config.resilience.priority.classes = vec![
+        PriorityClass {
+            name: "standard".to_string(),
+            max_inflight_percent: 80,
+            brownout_exempt: false,
+        },
+        PriorityClass {
+            name: "bulk".to_string(),
+            max_inflight_percent: 40,
+            brownout_exempt: false,
+        },
+    ];
+    config.resilience.priority.rules = vec![PriorityRule {
+        class: "bulk".to_string(),
+        routes: Vec::new(),
+        header: Some("X-Request-Class".to_string()),
+        jwt_claim: None,
+        values: vec!["analytics".to_string()],
+    }];
+    let runtime = RuntimeConfig::from_config(&config).expect("valid priority classes");
+    let priority = &runtime.policies.admission.priority.0;
+    assert_eq!(priority.default_class, "standard");
+    assert_eq!(priority.rules[0].header.as_deref(), Some("x-request-class"));
+
+    config.resilience.priority.rules[0].class = "analytics".to_string();
+    let err = RuntimeConfig::from_config(&config).expect_err("unknown class must fail");
+    assert_eq!(err.category(), "config_invalid");
+    assert!(err.to_string().contains("is not a configured class"));
+}
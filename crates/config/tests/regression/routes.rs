@@ -0,0 +1,774 @@
+//! Named route lowering: shorthand routes, policy overrides and ambiguity.
+
+use std::time::Duration;
+
+use spooky_config::{
+    config::{
+        CanaryRollout, Config, DirectResponse, FaultAbort, FaultDelay, FaultHeaderMatch,
+        FaultTruncate, ForwardedHeaderPolicy, ForwardedHeaderPolicyMode, JwtAuth, PathRewrite,
+        PrefixRewrite, RegexRewrite, ResponseHeader, RetryCondition, RetryOn, Route, RouteAuth,
+        RouteFault, RouteMatch, RouteMirror, RoutePolicy, RouteRedirect, SplitTarget, TrafficSplit,
+        UpstreamHostPolicyMode,
+    },
+    runtime::{
+        RuntimeConfig, RuntimeFaultAbortAction, RuntimeReplayPolicy, RuntimeRequestKeySpec,
+        RuntimeRetryPolicy, RuntimeRouteAction,
+    },
+};
+
+use crate::common::sample_config;
+
+fn checkout_route() -> Route {
+    Route {
+        name: "checkout".to_string(),
+        upstream: "api".to_string(),
+        matcher: RouteMatch {
+            host: Some("shop.example.com".to_string()),
+            path_prefix: Some("/checkout".to_string()),
+            method: Some("post".to_string()),
+        },
+        auth: None,
+        host_policy: None,
+        forwarded_headers: None,
+        rewrite: None,
+        split: None,
+        redirect: None,
+        direct_response: None,
+        mirror: None,
+        fault: None,
+        policy: None,
+    }
+}
+
+#[test]
+fn runtime_config_lowers_upstream_route_shorthand_into_named_route() {
+    let runtime = RuntimeConfig::from_config(&sample_config()).expect("runtime config");
+
+    let route = runtime.routes.get("api").expect("shorthand route");
+    assert_eq!(route.upstream, "api");
+    assert_eq!(route.route.host.as_deref(), Some("api.example.com"));
+    assert_eq!(route.route.path_prefix.as_deref(), Some("/"));
+    assert_eq!(
+        route.policy.host.0.mode,
+        runtime.upstreams["api"].policy.host.0.mode
+    );
+}
+
+#[test]
+fn runtime_config_applies_route_overrides_on_top_of_upstream_policy() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.auth = Some(RouteAuth {
+        jwt: Some(JwtAuth {
+            secret: "route-secret".to_string(),
+            ..Default::default()
+        }),
+        required_scopes: vec!["checkout:write".to_string()],
+        ..Default::default()
+    });
+    route.forwarded_headers = Some(ForwardedHeaderPolicy {
+        mode: ForwardedHeaderPolicyMode::Preserve,
+    });
+    config.routes.push(route);
+
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+
+    let checkout = runtime.routes.get("checkout").expect("checkout route");
+    assert_eq!(checkout.upstream, "api");
+    assert_eq!(checkout.route.method.as_deref(), Some("POST"));
+    assert_eq!(
+        checkout
+            .policy
+            .upstream_auth
+            .jwt
+            .as_ref()
+            .map(|jwt| jwt.secret.as_str()),
+        Some("route-secret")
+    );
+    assert_eq!(
+        checkout.policy.forwarded_headers.0.mode,
+        ForwardedHeaderPolicyMode::Preserve
+    );
+    assert_eq!(checkout.policy.host.0.mode, UpstreamHostPolicyMode::Rewrite);
+
+    let shorthand = runtime.routes.get("api").expect("shorthand route");
+    assert!(shorthand.policy.upstream_auth.jwt.is_none());
+}
+
+#[test]
+fn runtime_config_allows_upstream_without_shorthand_route() {
+    let mut config = sample_config();
+    config.upstream.get_mut("api").expect("api").route = RouteMatch::default();
+    config.routes.push(checkout_route());
+
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+
+    assert!(!runtime.routes.contains_key("api"));
+    assert!(runtime.routes.contains_key("checkout"));
+}
+
+#[test]
+fn runtime_config_rejects_unknown_route_target() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.upstream = "missing".to_string();
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("unknown upstream");
+    assert!(err.to_string().contains("unknown upstream 'missing'"));
+}
+
+#[test]
+fn runtime_config_rejects_named_route_ambiguous_with_shorthand() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.matcher = RouteMatch {
+        host: Some("API.EXAMPLE.COM".to_string()),
+        path_prefix: Some("/".to_string()),
+        method: None,
+    };
+    config.routes.push(route);
+
+    let err = RuntimeConfig::from_config(&config).expect_err("ambiguous route");
+    assert_eq!(err.category(), "duplicate_route_ambiguity");
+}
+
+fn rewritten_route_path(rewrite: PathRewrite, path: &str) -> Option<(String, Option<String>)> {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.rewrite = Some(rewrite);
+    config.routes.push(route);
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    let rewrite = runtime.routes["checkout"]
+        .policy
+        .path_rewrite
+        .as_ref()
+        .expect("path rewrite");
+    rewrite.apply(path).map(|rewritten| {
+        (
+            rewritten.path,
+            rewritten.forwarded_prefix.map(str::to_string),
+        )
+    })
+}
+
+#[test]
+fn runtime_config_lowers_route_path_rewrites() {
+    let strip = || PathRewrite {
+        strip_prefix: Some("/checkout/".to_string()),
+        replace_prefix: None,
+        regex: None,
+    };
+    assert_eq!(
+        rewritten_route_path(strip(), "/checkout/cart?id=7"),
+        Some(("/cart?id=7".to_string(), Some("/checkout".to_string())))
+    );
+    assert_eq!(
+        rewritten_route_path(strip(), "/checkout"),
+        Some(("/".to_string(), Some("/checkout".to_string())))
+    );
+    assert_eq!(rewritten_route_path(strip(), "/checkouts"), None);
+
+    let replace = PathRewrite {
+        strip_prefix: None,
+        replace_prefix: Some(PrefixRewrite {
+            prefix: "/checkout".to_string(),
+            replacement: "/v2/orders".to_string(),
+        }),
+        regex: None,
+    };
+    assert_eq!(
+        rewritten_route_path(replace, "/checkout/42"),
+        Some(("/v2/orders/42".to_string(), Some("/checkout".to_string())))
+    );
+
+    let regex = PathRewrite {
+        strip_prefix: None,
+        replace_prefix: None,
+        regex: Some(RegexRewrite {
+            pattern: "^/checkout/(?P<cart>[0-9]+)/items$".to_string(),
+            substitution: "/carts/${cart}?view=items".to_string(),
+        }),
+    };
+    assert_eq!(
+        rewritten_route_path(regex, "/checkout/42/items?page=2"),
+        Some(("/carts/42?view=items&page=2".to_string(), None))
+    );
+}
+
+#[test]
+fn runtime_config_rejects_ambiguous_or_invalid_path_rewrite() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.rewrite = Some(PathRewrite {
+        strip_prefix: Some("/checkout".to_string()),
+        replace_prefix: None,
+        regex: Some(RegexRewrite {
+            pattern: "^/checkout".to_string(),
+            substitution: "/".to_string(),
+        }),
+    });
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("two rewrite rules");
+    assert!(err.to_string().contains("exactly one of"));
+
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.rewrite = Some(PathRewrite {
+        strip_prefix: None,
+        replace_prefix: None,
+        regex: Some(RegexRewrite {
+            pattern: "^/checkout/(".to_string(),
+            substitution: "/".to_string(),
+        }),
+    });
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("invalid regex");
+    assert!(err.to_string().contains("rewrite.regex.pattern"));
+}
+
+fn redirect_route(redirect: RouteRedirect) -> Route {
+    let mut route = checkout_route();
+    route.upstream.clear();
+    route.redirect = Some(redirect);
+    route
+}
+
+fn route_action(route: Route) -> RuntimeRouteAction {
+    let mut config = sample_config();
+    config.routes.push(route);
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    let route = &runtime.routes["checkout"];
+    assert!(route.upstream.is_empty());
+    route.action.as_deref().cloned().expect("route action")
+}
+
+#[test]
+fn runtime_config_lowers_redirect_and_builds_location() {
+    let RuntimeRouteAction::Redirect(redirect) = route_action(redirect_route(RouteRedirect {
+        status: 301,
+        scheme: None,
+        host: None,
+        path: None,
+        query: None,
+    })) else {
+        panic!("expected redirect");
+    };
+    assert_eq!(
+        redirect.location("/checkout/cart?id=7", Some("shop.example.com")),
+        "https://shop.example.com/checkout/cart?id=7"
+    );
+    assert_eq!(redirect.location("/checkout", None), "/checkout");
+
+    let mut route = redirect_route(RouteRedirect {
+        status: 308,
+        scheme: Some("http".to_string()),
+        host: Some("new.example.com".to_string()),
+        path: None,
+        query: Some(String::new()),
+    });
+    route.rewrite = Some(PathRewrite {
+        strip_prefix: None,
+        replace_prefix: Some(PrefixRewrite {
+            prefix: "/checkout".to_string(),
+            replacement: "/orders".to_string(),
+        }),
+        regex: None,
+    });
+    let RuntimeRouteAction::Redirect(redirect) = route_action(route) else {
+        panic!("expected redirect");
+    };
+    assert_eq!(redirect.status, 308);
+    assert_eq!(
+        redirect.location("/checkout/42?id=7", Some("shop.example.com")),
+        "http://new.example.com/orders/42"
+    );
+
+    let RuntimeRouteAction::Redirect(redirect) = route_action(redirect_route(RouteRedirect {
+        status: 302,
+        scheme: None,
+        host: None,
+        path: Some("/maintenance".to_string()),
+        query: Some("from=checkout".to_string()),
+    })) else {
+        panic!("expected redirect");
+    };
+    assert_eq!(
+        redirect.location("/checkout/42?id=7", Some("shop.example.com:8443")),
+        "https://shop.example.com:8443/maintenance?from=checkout"
+    );
+}
+
+#[test]
+fn runtime_config_lowers_direct_response_from_inline_or_file_body() {
+    let mut route = checkout_route();
+    route.upstream.clear();
+    route.direct_response = Some(DirectResponse {
+        status: 503,
+        headers: vec![ResponseHeader {
+            name: "Content-Type".to_string(),
+            value: "text/plain".to_string(),
+        }],
+        body: Some("back soon\n".to_string()),
+        body_file: None,
+    });
+    let RuntimeRouteAction::DirectResponse(direct) = route_action(route.clone()) else {
+        panic!("expected direct response");
+    };
+    assert_eq!(direct.status, 503);
+    assert_eq!(
+        direct.headers,
+        vec![("content-type".to_string(), "text/plain".to_string())]
+    );
+    assert_eq!(direct.body, b"back soon\n");
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let body_path = dir.path().join("maintenance.html");
+    std::fs::write(&body_path, "<h1>back soon</h1>").expect("write body");
+    let direct_config = route.direct_response.as_mut().expect("direct");
+    direct_config.body = None;
+    direct_config.body_file = Some(body_path.to_string_lossy().into_owned());
+    let RuntimeRouteAction::DirectResponse(direct) = route_action(route.clone()) else {
+        panic!("expected direct response");
+    };
+    assert_eq!(direct.body, b"<h1>back soon</h1>");
+
+    let mut config = sample_config();
+    route.direct_response.as_mut().expect("direct").body = Some("x".repeat(64 * 1024 + 1));
+    route.direct_response.as_mut().expect("direct").body_file = None;
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("oversized body");
+    assert!(err.to_string().contains("exceeds"));
+}
+
+#[test]
+fn runtime_config_rejects_route_with_upstream_and_action() {
+    let mut config = sample_config();
+    let mut route = redirect_route(RouteRedirect {
+        status: 301,
+        scheme: None,
+        host: None,
+        path: None,
+        query: None,
+    });
+    route.upstream = "api".to_string();
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("upstream and redirect");
+    assert!(err.to_string().contains("answered by the edge"));
+}
+
+fn mirrored_route_config(mirror: RouteMirror) -> Config {
+    let mut config = sample_config();
+    let mut shadow = config.upstream["api"].clone();
+    shadow.route = RouteMatch::default();
+    shadow.backends[0].id = "api-shadow-1".to_string();
+    shadow.backends[0].address = "https://api-shadow.internal:8443".to_string();
+    config.upstream.insert("api-shadow".to_string(), shadow);
+
+    let mut route = checkout_route();
+    route.mirror = Some(mirror);
+    config.routes.push(route);
+    config
+}
+
+#[test]
+fn runtime_config_lowers_route_mirror_and_samples_by_method() {
+    let runtime = RuntimeConfig::from_config(&mirrored_route_config(RouteMirror {
+        upstream: "api-shadow".to_string(),
+        percent: 25,
+        methods: vec!["get".to_string(), " POST ".to_string(), "GET".to_string()],
+        max_body_bytes: 1024,
+        max_inflight: 4,
+    }))
+    .expect("runtime config");
+
+    let mirror = runtime.routes["checkout"]
+        .policy
+        .mirror
+        .as_deref()
+        .expect("mirror policy");
+    assert_eq!(mirror.upstream, "api-shadow");
+    assert_eq!(mirror.methods, vec!["GET".to_string(), "POST".to_string()]);
+    assert_eq!(mirror.max_inflight, 4);
+    assert!(mirror.samples("POST", 24));
+    assert!(!mirror.samples("POST", 25));
+    assert!(mirror.samples("get", 124));
+    assert!(!mirror.samples("DELETE", 0));
+    assert!(mirror.body_fits(Some(0)));
+    assert!(mirror.body_fits(Some(1024)));
+    assert!(!mirror.body_fits(Some(1025)));
+    assert!(
+        !mirror.body_fits(None),
+        "unknown lengths are never buffered"
+    );
+    assert!(
+        runtime.routes["api"].policy.mirror.is_none(),
+        "shorthand routes keep mirroring off"
+    );
+}
+
+#[test]
+fn runtime_config_rejects_mirror_to_route_upstream() {
+    let mut config = mirrored_route_config(RouteMirror {
+        upstream: "api".to_string(),
+        percent: 100,
+        methods: Vec::new(),
+        max_body_bytes: 1024,
+        max_inflight: 4,
+    });
+    let err = RuntimeConfig::from_config(&config).expect_err("mirror to primary");
+    assert!(err.to_string().contains("mirror.upstream must differ"));
+
+    let mirror = config.routes[0].mirror.as_mut().expect("mirror");
+    mirror.upstream = "api-shadow".to_string();
+    mirror.methods = vec!["CONNECT".to_string()];
+    let err = RuntimeConfig::from_config(&config).expect_err("mirror CONNECT");
+    assert!(err.to_string().contains("CONNECT"));
+}
+
+fn canary_split_config() -> Config {
+    let mut config = sample_config();
+    let mut canary = config.upstream["api"].clone();
+    canary.route = RouteMatch::default();
+    canary.backends[0].id = "api-canary-1".to_string();
+    canary.backends[0].address = "https://api-canary.internal:8443".to_string();
+    config.upstream.insert("api-canary".to_string(), canary);
+
+    let mut route = checkout_route();
+    route.split = Some(TrafficSplit {
+        key: Some("cookie:session".to_string()),
+        targets: vec![
+            SplitTarget {
+                upstream: "api".to_string(),
+                weight: 95,
+            },
+            SplitTarget {
+                upstream: "api-canary".to_string(),
+                weight: 5,
+            },
+        ],
+        rollout: None,
+    });
+    config.routes.push(route);
+    config
+}
+
+#[test]
+fn runtime_config_lowers_weighted_split_in_target_order() {
+    let runtime = RuntimeConfig::from_config(&canary_split_config()).expect("runtime config");
+
+    let split = runtime.routes["checkout"]
+        .split
+        .as_ref()
+        .expect("checkout split");
+    assert_eq!(
+        split.key_spec,
+        Some(RuntimeRequestKeySpec::Cookie("session".to_string()))
+    );
+    assert_eq!(split.total_weight, 100);
+    assert_eq!(split.pick(0), "api");
+    assert_eq!(split.pick(94), "api");
+    assert_eq!(split.pick(95), "api-canary");
+    assert_eq!(split.pick(199), "api-canary");
+
+    let canary_share = (0..10_000u64)
+        .filter(|point| split.pick(*point) == "api-canary")
+        .count();
+    assert_eq!(canary_share, 500);
+}
+
+#[test]
+fn runtime_config_rejects_split_without_route_upstream_or_weight() {
+    let mut config = canary_split_config();
+    let split = config.routes[0].split.as_mut().expect("split");
+    split.targets.remove(0);
+    let err = RuntimeConfig::from_config(&config).expect_err("missing primary");
+    assert!(
+        err.to_string()
+            .contains("must include the route upstream 'api'")
+    );
+
+    let mut config = canary_split_config();
+    for target in &mut config.routes[0].split.as_mut().expect("split").targets {
+        target.weight = 0;
+    }
+    let err = RuntimeConfig::from_config(&config).expect_err("zero weights");
+    assert!(err.to_string().contains("must not all be zero"));
+}
+
+#[test]
+fn runtime_config_lowers_canary_rollout_and_overrides_static_weights() {
+    let mut config = canary_split_config();
+    let split = config.routes[0].split.as_mut().expect("split");
+    split.rollout = Some(CanaryRollout {
+        canary: "api-canary".to_string(),
+        steps: vec![1, 5, 25, 100],
+        step_interval_ms: 60_000,
+        min_requests: 50,
+        max_error_rate_increase_percent: 2,
+        max_p95_latency_ratio_percent: 150,
+    });
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+
+    let split = runtime.routes["checkout"]
+        .split
+        .as_ref()
+        .expect("checkout split");
+    let rollout = split.rollout.as_ref().expect("rollout");
+    assert_eq!(rollout.canary, "api-canary");
+    assert_eq!(rollout.baselines, vec!["api".to_string()]);
+    assert_eq!(rollout.step_interval, Duration::from_secs(60));
+
+    // One full cycle: 100 percent slots times the baseline weight of 95.
+    let canary_share = |percent: u32| {
+        (0..9_500u64)
+            .filter(|point| split.pick_with_canary_percent(*point, percent) == "api-canary")
+            .count()
+    };
+    assert_eq!(canary_share(0), 0);
+    assert_eq!(canary_share(25), 2_375);
+    assert_eq!(canary_share(100), 9_500);
+
+    let mut config = canary_split_config();
+    config.routes[0].split.as_mut().expect("split").rollout = Some(CanaryRollout {
+        canary: "api-canary".to_string(),
+        steps: vec![50, 10],
+        step_interval_ms: 60_000,
+        min_requests: 50,
+        max_error_rate_increase_percent: 2,
+        max_p95_latency_ratio_percent: 150,
+    });
+    let err = RuntimeConfig::from_config(&config).expect_err("decreasing steps");
+    assert!(err.to_string().contains("strictly increasing"));
+}
+
+fn faulted_route_config(fault: RouteFault) -> Config {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.fault = Some(fault);
+    config.routes.push(route);
+    config
+}
+
+#[test]
+fn runtime_config_lowers_route_fault_and_samples_each_fault() {
+    let runtime = RuntimeConfig::from_config(&faulted_route_config(RouteFault {
+        header: Some(FaultHeaderMatch {
+            name: " X-Fault ".to_string(),
+            value: Some("on".to_string()),
+        }),
+        delay: Some(FaultDelay {
+            percent: 50,
+            duration_ms: 100,
+            max_duration_ms: Some(300),
+        }),
+        abort: Some(FaultAbort {
+            percent: 10,
+            status: Some(503),
+            reset: false,
+        }),
+        truncate: Some(FaultTruncate {
+            percent: 0,
+            after_bytes: 64,
+        }),
+    }))
+    .expect("runtime config");
+
+    let fault = runtime.routes["checkout"]
+        .policy
+        .fault
+        .as_deref()
+        .expect("fault policy");
+    let header = fault.header.as_ref().expect("header gate");
+    assert_eq!(header.name, "x-fault");
+    assert!(fault.truncate.is_none(), "zero-percent faults are dropped");
+    assert!(fault.admits(Some("on")));
+    assert!(!fault.admits(Some("off")));
+    assert!(!fault.admits(None));
+
+    let mut points = [9_u64].into_iter();
+    let sample = fault.sample(|| points.next().expect("draw"));
+    assert_eq!(
+        sample.abort,
+        Some(RuntimeFaultAbortAction::Status(503)),
+        "an abort skips the other faults"
+    );
+    assert!(sample.delay.is_none());
+
+    let mut points = [10_u64, 49, 150].into_iter();
+    let sample = fault.sample(|| points.next().expect("draw"));
+    assert!(sample.abort.is_none());
+    assert_eq!(sample.delay, Some(Duration::from_millis(250)));
+
+    let mut points = [10_u64, 50].into_iter();
+    assert!(fault.sample(|| points.next().expect("draw")).is_empty());
+    assert!(runtime.routes["api"].policy.fault.is_none());
+}
+
+#[test]
+fn runtime_config_drops_idle_faults_and_rejects_ambiguous_aborts() {
+    let mut config = faulted_route_config(RouteFault {
+        header: None,
+        delay: None,
+        abort: Some(FaultAbort {
+            percent: 0,
+            status: None,
+            reset: true,
+        }),
+        truncate: None,
+    });
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    assert!(
+        runtime.routes["checkout"].policy.fault.is_none(),
+        "a fault block with only idle faults lowers to nothing"
+    );
+
+    let abort = config.routes[0]
+        .fault
+        .as_mut()
+        .and_then(|fault| fault.abort.as_mut())
+        .expect("abort");
+    abort.percent = 100;
+    abort.status = Some(500);
+    let err = RuntimeConfig::from_config(&config).expect_err("status and reset");
+    assert!(err.to_string().contains("exactly one of status or reset"));
+}
+
+#[test]
+fn runtime_config_merges_route_policy_over_upstream_policy() {
+    let mut config = sample_config();
+    config.upstream.get_mut("api").expect("api upstream").policy = RoutePolicy {
+        backend_timeout_ms: Some(1_000),
+        max_retry_attempts: Some(2),
+        ..RoutePolicy::default()
+    };
+    let mut route = checkout_route();
+    route.policy = Some(RoutePolicy {
+        max_request_body_bytes: Some(4_096),
+        max_retry_attempts: Some(0),
+        ..RoutePolicy::default()
+    });
+    config.routes.push(route);
+
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    let limits = &runtime.routes["checkout"].policy.limits;
+    assert_eq!(limits.backend_timeout, Some(Duration::from_millis(1_000)));
+    assert_eq!(limits.max_request_body_bytes, Some(4_096));
+    assert_eq!(limits.max_retry_attempts, Some(0), "route fields win");
+    assert!(limits.hedging_delay.is_none());
+
+    let shorthand = &runtime.routes["api"].policy.limits;
+    assert_eq!(shorthand.max_retry_attempts, Some(2));
+    assert!(shorthand.max_request_body_bytes.is_none());
+}
+
+#[test]
+fn runtime_config_rejects_route_policy_outside_global_timeouts() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.policy = Some(RoutePolicy {
+        backend_timeout_ms: Some(100),
+        ..RoutePolicy::default()
+    });
+    config.routes.push(route);
+    let err = RuntimeConfig::from_config(&config).expect_err("below connect timeout");
+    assert!(err.to_string().contains("backend_connect_timeout_ms"));
+
+    let policy = config.routes[0].policy.as_mut().expect("policy");
+    policy.backend_timeout_ms = Some(5_000);
+    policy.backend_total_request_timeout_ms = Some(4_000);
+    let err = RuntimeConfig::from_config(&config).expect_err("above total timeout");
+    assert!(
+        err.to_string()
+            .contains("<= backend_total_request_timeout_ms")
+    );
+
+    let policy = config.routes[0].policy.as_mut().expect("policy");
+    policy.backend_total_request_timeout_ms = None;
+    policy.max_retry_attempts = Some(9);
+    let err = RuntimeConfig::from_config(&config).expect_err("too many retries");
+    assert!(err.to_string().contains("max_retry_attempts"));
+}
+
+#[test]
+fn runtime_config_lowers_retry_on_statuses_and_backoff() {
+    let mut config = sample_config();
+    let mut route = checkout_route();
+    route.policy = Some(RoutePolicy {
+        retry_on: Some(vec![
+            RetryOn::Status(503),
+            RetryOn::Condition(RetryCondition::ConnectFailure),
+            RetryOn::Status(502),
+        ]),
+        retry_backoff_base_ms: Some(10),
+        retry_backoff_max_ms: Some(30),
+        ..RoutePolicy::default()
+    });
+    config.routes.push(route);
+
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    let retry = &runtime.routes["checkout"].policy.limits.retry;
+    assert!(retry.retries_status(502) && retry.retries_status(503));
+    assert!(!retry.retries_status(500));
+    assert!(retry.connect_failure);
+    assert!(
+        !retry.reset_before_headers,
+        "explicit list replaces default"
+    );
+    assert_eq!(retry.backoff(1, 999), Duration::from_millis(999 % 11));
+    assert_eq!(retry.backoff(3, 30), Duration::from_millis(30), "capped");
+    assert!(
+        runtime.routes["api"]
+            .policy
+            .limits
+            .retry
+            .reset_before_headers
+    );
+
+    assert_eq!(
+        RuntimeRetryPolicy::parse_retry_after(" 2 "),
+        Some(Duration::from_secs(2))
+    );
+    assert!(RuntimeRetryPolicy::parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT").is_none());
+
+    let policy = config.routes[0].policy.as_mut().expect("policy");
+    policy.retry_on = Some(vec![RetryOn::Status(302)]);
+    let err = RuntimeConfig::from_config(&config).expect_err("non-error status");
+    assert!(err.to_string().contains("retry_on"));
+
+    let policy = config.routes[0].policy.as_mut().expect("policy");
+    policy.retry_on = None;
+    policy.retry_backoff_base_ms = Some(500);
+    let err = RuntimeConfig::from_config(&config).expect_err("base above max");
+    assert!(err.to_string().contains("retry_backoff_base_ms"));
+}
+
+#[test]
+fn runtime_config_lowers_body_replay_and_lets_routes_disable_it() {
+    let mut config = sample_config();
+    config.upstream.get_mut("api").expect("api upstream").policy = RoutePolicy {
+        replay_body_max_bytes: Some(65_536),
+        idempotency_header: Some(" X-Request-Key ".to_string()),
+        ..RoutePolicy::default()
+    };
+    let mut route = checkout_route();
+    route.policy = Some(RoutePolicy {
+        replay_body_max_bytes: Some(0),
+        ..RoutePolicy::default()
+    });
+    config.routes.push(route);
+
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    assert_eq!(
+        runtime.routes["api"].policy.limits.replay,
+        Some(RuntimeReplayPolicy {
+            max_body_bytes: 65_536,
+            idempotency_header: "x-request-key".to_string(),
+        })
+    );
+    assert!(runtime.routes["checkout"].policy.limits.replay.is_none());
+
+    let policy = config.routes[0].policy.as_mut().expect("policy");
+    policy.idempotency_header = Some("bad header".to_string());
+    let err = RuntimeConfig::from_config(&config).expect_err("invalid header name");
+    assert!(err.to_string().contains("idempotency_header"));
+}
@@ -16,9 +16,19 @@ pub fn build_benchmark_upstream(host: Option<String>, path_prefix: String) -> Up
         load_balancing: LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -32,6 +42,8 @@ pub fn build_benchmark_upstream(host: Option<String>, path_prefix: String) -> Up
             address: "127.0.0.1:1".to_string(),
             weight: 1,
             health_check: Some(default_health_check()),
+            priority: 0,
+            zone: None,
         }],
     }
 }
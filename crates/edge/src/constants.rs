@@ -54,6 +54,10 @@ pub const MAX_STREAMS_PER_CONNECTION: usize = QUIC_INITIAL_MAX_STREAMS_BIDI as u
 /// sent; no unbounded memory growth is possible.
 pub const MAX_RESPONSE_BODY_BYTES: usize = 100 * 1024 * 1024; // 100 MiB
 
+/// HTTP/3 H3_REQUEST_CANCELLED (RFC 9114 §8.1), sent when a route fault
+/// resets a request stream.
+pub const H3_REQUEST_CANCELLED: u64 = 0x10c;
+
 pub const MAX_INFLIGHT_PER_BACKEND: usize = 64;
 pub const DEFAULT_SCID_LEN_BYTES: usize = 16;
 pub const RESET_TOKEN_LEN_BYTES: usize = 16;
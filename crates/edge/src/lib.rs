@@ -12,6 +12,7 @@ pub mod hash;
 pub mod metrics;
 mod quic_listener;
 pub mod resilience;
+pub mod rollout;
 pub mod routing;
 pub mod runtime;
 pub mod watchdog;
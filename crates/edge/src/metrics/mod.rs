@@ -9,11 +9,12 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use spooky_config::config::MAX_HEDGES_PER_REQUEST;
 use spooky_errors::{
     HedgeOutcomeTelemetryReason, HedgeTriggerTelemetryReason, RetryAttemptTelemetryReason,
     RetryPolicyDenialReason,
 };
-use spooky_lb::health::HealthFailureReason;
+use spooky_lb::{health::HealthFailureReason, outlier::OutlierEjectionReason};
 
 pub struct Metrics {
     pub requests_total: AtomicU64,
@@ -51,6 +52,8 @@ pub struct Metrics {
     pub connection_cap_rejects: AtomicU64,
     pub hedge_triggered: AtomicU64,
     pub hedge_won: AtomicU64,
+    /// Hedge wins by attempt, the first hedge at index 0.
+    pub hedge_won_by_attempt: [AtomicU64; MAX_HEDGES_PER_REQUEST as usize],
     pub hedge_wasted: AtomicU64,
     pub hedge_primary_won_after_trigger: AtomicU64,
     pub hedge_primary_late_ms_total: AtomicU64,
@@ -68,6 +71,7 @@ pub struct Metrics {
     pub request_buffered_bytes: AtomicU64,
     pub request_buffered_high_watermark_bytes: AtomicU64,
     pub request_buffer_limit_rejects: AtomicU64,
+    pub request_replay_fallbacks: AtomicU64,
     pub response_prebuffer_limit_rejects: AtomicU64,
     pub scid_rotations: AtomicU64,
     pub control_api_connection_limit_drops: AtomicU64,
@@ -82,6 +86,8 @@ pub struct Metrics {
     pub retry_reason_timeout: AtomicU64,
     pub retry_reason_transport: AtomicU64,
     pub retry_reason_pool: AtomicU64,
+    pub retry_reason_connect_failure: AtomicU64,
+    pub retry_reason_status: AtomicU64,
     pub circuit_breaker_rejected_total: AtomicU64,
     pub brownout_active: AtomicU64,
     pub health_failure_5xx: AtomicU64,
@@ -105,6 +111,13 @@ pub struct Metrics {
     backend_rotation_state: RwLock<HashMap<String, BackendRotationState>>,
     backend_connect_attempts: RwLock<HashMap<BackendConnectAttemptKey, u64>>,
     upstream_request_counts: RwLock<HashMap<UpstreamRequestCountKey, u64>>,
+    route_split_counts: RwLock<HashMap<RouteSplitKey, u64>>,
+    mirror_request_counts: RwLock<HashMap<MirrorRequestKey, u64>>,
+    mirror_request_latency: RwLock<HashMap<MirrorRequestKey, RequestLatencyStats>>,
+    fault_injections: RwLock<HashMap<FaultInjectionKey, u64>>,
+    overload_shed_by_priority: RwLock<HashMap<OverloadShedKey, u64>>,
+    rollout_transitions: RwLock<HashMap<RolloutTransitionKey, u64>>,
+    rollout_canary_percent: RwLock<HashMap<String, u32>>,
     backend_request_counts: RwLock<HashMap<BackendRequestCountKey, u64>>,
     upstream_request_latency: RwLock<HashMap<UpstreamRequestLatencyKey, RequestLatencyStats>>,
     downstream_tls_handshake_failures: RwLock<HashMap<DownstreamTlsHandshakeFailureKey, u64>>,
@@ -112,6 +125,9 @@ pub struct Metrics {
     downstream_tls_alpn_negotiated: RwLock<HashMap<DownstreamTlsAlpnKey, u64>>,
     downstream_tls_cert_expiry: RwLock<HashMap<DownstreamTlsCertExpiryKey, i64>>,
     upstream_tls_failures: RwLock<HashMap<UpstreamTlsFailureKey, u64>>,
+    outlier_ejections: RwLock<HashMap<OutlierEjectionKey, u64>>,
+    upstream_zone_requests: RwLock<HashMap<UpstreamZoneRequestKey, u64>>,
+    adaptive_concurrency: RwLock<HashMap<Option<String>, AdaptiveConcurrencyGauges>>,
 }
 
 #[derive(Default, Clone)]
@@ -139,6 +155,39 @@ pub(crate) struct UpstreamRequestCountKey {
     pub(crate) outcome: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RouteSplitKey {
+    pub(crate) route: String,
+    pub(crate) upstream: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct MirrorRequestKey {
+    pub(crate) route: String,
+    pub(crate) upstream: String,
+    pub(crate) outcome: String,
+}
+
+/// Shed decisions are keyed by request priority class; `priority` is empty
+/// when the request was shed before it was classified.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OverloadShedKey {
+    pub(crate) reason: OverloadShedReason,
+    pub(crate) priority: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct FaultInjectionKey {
+    pub(crate) route: String,
+    pub(crate) fault: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RolloutTransitionKey {
+    pub(crate) route: String,
+    pub(crate) transition: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct BackendRequestCountKey {
     pub(crate) upstream: String,
@@ -178,6 +227,34 @@ pub(crate) struct UpstreamTlsFailureKey {
     pub(crate) reason: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct OutlierEjectionKey {
+    pub(crate) upstream: String,
+    pub(crate) reason: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct UpstreamZoneRequestKey {
+    pub(crate) upstream: String,
+    pub(crate) zone: String,
+    pub(crate) locality: &'static str,
+}
+
+const ZONE_LOCALITY_LOCAL: &str = "local";
+const ZONE_LOCALITY_REMOTE: &str = "remote";
+// Instance or backend zone unset.
+const ZONE_LOCALITY_UNKNOWN: &str = "unknown";
+
+/// Last sampled state of one adaptive concurrency limiter. RTT and gradient
+/// are only reported by gradient limiters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveConcurrencyGauges {
+    pub limit: usize,
+    pub min_rtt: Option<Duration>,
+    pub smoothed_rtt: Option<Duration>,
+    pub gradient: Option<f64>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct DownstreamTlsCertExpiryKey {
     pub(crate) listener: String,
@@ -218,6 +295,63 @@ pub(crate) struct RequestLatencyStats {
     pub(crate) count: u64,
 }
 
+/// Cumulative request, error and latency counters for one route or upstream.
+/// Diff two samples with [`RouteHealthSample::since`] to judge a time window.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RouteHealthSample {
+    pub requests: u64,
+    /// Failures, timeouts and backend errors; rate-limited and shed requests
+    /// are not counted against the route.
+    pub errors: u64,
+    latency_buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl RouteHealthSample {
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            requests: self.requests.saturating_sub(earlier.requests),
+            errors: self.errors.saturating_sub(earlier.errors),
+            latency_buckets: std::array::from_fn(|idx| {
+                self.latency_buckets[idx].saturating_sub(earlier.latency_buckets[idx])
+            }),
+        }
+    }
+
+    pub fn accumulate(&mut self, other: &Self) {
+        self.requests = self.requests.saturating_add(other.requests);
+        self.errors = self.errors.saturating_add(other.errors);
+        for (bucket, count) in self.latency_buckets.iter_mut().zip(other.latency_buckets) {
+            *bucket = bucket.saturating_add(count);
+        }
+    }
+
+    /// Errors per 10,000 requests.
+    pub fn error_rate_basis_points(&self) -> u64 {
+        self.errors
+            .saturating_mul(10_000)
+            .checked_div(self.requests)
+            .unwrap_or(0)
+    }
+
+    pub fn latency_samples(&self) -> u64 {
+        self.latency_buckets.iter().sum()
+    }
+
+    /// Upper bound of the bucket holding the 95th percentile sample.
+    pub fn p95_ms(&self) -> u64 {
+        self.percentile_ms(95)
+    }
+
+    /// Upper bound of the bucket holding the `percentile`th sample.
+    pub fn percentile_ms(&self, percentile: u8) -> u64 {
+        let stats = RouteStats {
+            latency_buckets: self.latency_buckets,
+            ..RouteStats::default()
+        };
+        prometheus::percentile_ms(&stats, f64::from(percentile.min(100)) / 100.0) as u64
+    }
+}
+
 struct RouteStatsAtomic {
     requests_total: AtomicU64,
     success: AtomicU64,
@@ -337,7 +471,17 @@ fn route_outcome_label(outcome: RouteOutcome) -> &'static str {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+fn mirror_request_key_order(
+    left: &MirrorRequestKey,
+    right: &MirrorRequestKey,
+) -> std::cmp::Ordering {
+    left.route
+        .cmp(&right.route)
+        .then_with(|| left.upstream.cmp(&right.upstream))
+        .then_with(|| left.outcome.cmp(&right.outcome))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum OverloadShedReason {
     Brownout,
     AdaptiveAdmission,
@@ -352,6 +496,38 @@ pub enum OverloadShedReason {
     ConnectionCap,
 }
 
+impl OverloadShedReason {
+    pub(crate) const ALL: [Self; 11] = [
+        Self::Brownout,
+        Self::AdaptiveAdmission,
+        Self::RouteCap,
+        Self::RouteGlobalCap,
+        Self::GlobalInflight,
+        Self::UpstreamInflight,
+        Self::BackendInflight,
+        Self::CircuitOpen,
+        Self::RequestBufferCap,
+        Self::ResponsePrebufferCap,
+        Self::ConnectionCap,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Brownout => "brownout",
+            Self::AdaptiveAdmission => "adaptive_admission",
+            Self::RouteCap => "route_cap",
+            Self::RouteGlobalCap => "route_global_cap",
+            Self::GlobalInflight => "global_inflight",
+            Self::UpstreamInflight => "upstream_inflight",
+            Self::BackendInflight => "backend_inflight",
+            Self::CircuitOpen => "circuit_open",
+            Self::RequestBufferCap => "request_buffer_cap",
+            Self::ResponsePrebufferCap => "response_prebuffer_cap",
+            Self::ConnectionCap => "connection_cap",
+        }
+    }
+}
+
 impl Default for Metrics {
     fn default() -> Self {
         Self::new(1, [String::from("unrouted")])
@@ -439,6 +615,7 @@ impl Metrics {
             connection_cap_rejects: AtomicU64::new(0),
             hedge_triggered: AtomicU64::new(0),
             hedge_won: AtomicU64::new(0),
+            hedge_won_by_attempt: std::array::from_fn(|_| AtomicU64::new(0)),
             hedge_wasted: AtomicU64::new(0),
             hedge_primary_won_after_trigger: AtomicU64::new(0),
             hedge_primary_late_ms_total: AtomicU64::new(0),
@@ -456,6 +633,7 @@ impl Metrics {
             request_buffered_bytes: AtomicU64::new(0),
             request_buffered_high_watermark_bytes: AtomicU64::new(0),
             request_buffer_limit_rejects: AtomicU64::new(0),
+            request_replay_fallbacks: AtomicU64::new(0),
             response_prebuffer_limit_rejects: AtomicU64::new(0),
             scid_rotations: AtomicU64::new(0),
             control_api_connection_limit_drops: AtomicU64::new(0),
@@ -470,6 +648,8 @@ impl Metrics {
             retry_reason_timeout: AtomicU64::new(0),
             retry_reason_transport: AtomicU64::new(0),
             retry_reason_pool: AtomicU64::new(0),
+            retry_reason_connect_failure: AtomicU64::new(0),
+            retry_reason_status: AtomicU64::new(0),
             circuit_breaker_rejected_total: AtomicU64::new(0),
             brownout_active: AtomicU64::new(0),
             health_failure_5xx: AtomicU64::new(0),
@@ -493,6 +673,13 @@ impl Metrics {
             backend_rotation_state: RwLock::new(HashMap::new()),
             backend_connect_attempts: RwLock::new(HashMap::new()),
             upstream_request_counts: RwLock::new(HashMap::new()),
+            route_split_counts: RwLock::new(HashMap::new()),
+            mirror_request_counts: RwLock::new(HashMap::new()),
+            mirror_request_latency: RwLock::new(HashMap::new()),
+            fault_injections: RwLock::new(HashMap::new()),
+            overload_shed_by_priority: RwLock::new(HashMap::new()),
+            rollout_transitions: RwLock::new(HashMap::new()),
+            rollout_canary_percent: RwLock::new(HashMap::new()),
             backend_request_counts: RwLock::new(HashMap::new()),
             upstream_request_latency: RwLock::new(HashMap::new()),
             downstream_tls_handshake_failures: RwLock::new(HashMap::new()),
@@ -500,6 +687,9 @@ impl Metrics {
             downstream_tls_alpn_negotiated: RwLock::new(HashMap::new()),
             downstream_tls_cert_expiry: RwLock::new(HashMap::new()),
             upstream_tls_failures: RwLock::new(HashMap::new()),
+            outlier_ejections: RwLock::new(HashMap::new()),
+            upstream_zone_requests: RwLock::new(HashMap::new()),
+            adaptive_concurrency: RwLock::new(HashMap::new()),
         }
     }
 
@@ -582,8 +772,16 @@ impl Metrics {
         self.overload_shed.fetch_add(1, Ordering::Relaxed);
     }
 
-    pub fn inc_overload_shed_reason(&self, reason: OverloadShedReason) {
+    pub fn inc_overload_shed_reason(&self, reason: OverloadShedReason, priority: Option<&str>) {
         self.overload_shed.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut guard) = self.overload_shed_by_priority.write() {
+            *guard
+                .entry(OverloadShedKey {
+                    reason,
+                    priority: priority.unwrap_or_default().to_string(),
+                })
+                .or_default() += 1;
+        }
         match reason {
             OverloadShedReason::Brownout => {
                 self.overload_shed_brownout.fetch_add(1, Ordering::Relaxed);
@@ -652,17 +850,24 @@ impl Metrics {
         self.hedge_triggered.fetch_add(1, Ordering::Relaxed);
     }
 
-    pub fn inc_hedge_outcome(&self, reason: HedgeOutcomeTelemetryReason) {
-        match reason {
+    /// Records who won a request that sent `hedges` hedges; every hedge
+    /// but a winning one is counted as wasted.
+    pub fn inc_hedge_outcome(&self, reason: HedgeOutcomeTelemetryReason, hedges: u8) {
+        let wasted = match reason {
             HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger => {
                 self.hedge_primary_won_after_trigger
                     .fetch_add(1, Ordering::Relaxed);
-                self.hedge_wasted.fetch_add(1, Ordering::Relaxed);
+                hedges.max(1)
             }
-            HedgeOutcomeTelemetryReason::HedgeWon => {
+            HedgeOutcomeTelemetryReason::HedgeWon { attempt } => {
                 self.hedge_won.fetch_add(1, Ordering::Relaxed);
+                let slot = usize::from(attempt.max(1) - 1).min(self.hedge_won_by_attempt.len() - 1);
+                self.hedge_won_by_attempt[slot].fetch_add(1, Ordering::Relaxed);
+                hedges.saturating_sub(1)
             }
-        }
+        };
+        self.hedge_wasted
+            .fetch_add(u64::from(wasted), Ordering::Relaxed);
     }
 
     pub fn observe_hedge_primary_late_ms(&self, late_ms: u64) {
@@ -832,6 +1037,61 @@ impl Metrics {
         }
     }
 
+    pub fn record_route_split(&self, route: &str, upstream: &str) {
+        if let Ok(mut guard) = self.route_split_counts.write() {
+            *guard
+                .entry(RouteSplitKey {
+                    route: route.to_string(),
+                    upstream: upstream.to_string(),
+                })
+                .or_default() += 1;
+        }
+    }
+
+    /// Counts a mirror copy for `route`. `latency` is set only for copies that
+    /// were sent to the mirror upstream.
+    pub fn record_mirror_request(
+        &self,
+        route: &str,
+        upstream: &str,
+        outcome: &str,
+        latency: Option<Duration>,
+    ) {
+        let key = MirrorRequestKey {
+            route: route.to_string(),
+            upstream: upstream.to_string(),
+            outcome: outcome.to_string(),
+        };
+        if let Some(latency) = latency {
+            let latency_ms = latency.as_millis() as u64;
+            let bucket = LATENCY_BUCKETS_MS
+                .iter()
+                .position(|cutoff| latency_ms <= *cutoff)
+                .unwrap_or(LATENCY_BUCKETS_MS.len());
+            if let Ok(mut guard) = self.mirror_request_latency.write() {
+                let stats = guard.entry(key.clone()).or_default();
+                stats.count = stats.count.saturating_add(1);
+                stats.latency_ms_sum = stats.latency_ms_sum.saturating_add(latency_ms);
+                stats.latency_buckets[bucket] = stats.latency_buckets[bucket].saturating_add(1);
+            }
+        }
+        if let Ok(mut guard) = self.mirror_request_counts.write() {
+            *guard.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Counts a fault drawn for a request on `route`.
+    pub fn record_fault_injection(&self, route: &str, fault: &str) {
+        if let Ok(mut guard) = self.fault_injections.write() {
+            *guard
+                .entry(FaultInjectionKey {
+                    route: route.to_string(),
+                    fault: fault.to_string(),
+                })
+                .or_default() += 1;
+        }
+    }
+
     pub(crate) fn snapshot_backend_dns_state(&self) -> Vec<(String, BackendDnsState)> {
         self.backend_dns_state
             .read()
@@ -898,6 +1158,107 @@ impl Metrics {
             .unwrap_or_default()
     }
 
+    /// Health of the requests sent to `upstream`, across every route that
+    /// targets it. `None` until the upstream has served a request.
+    pub fn upstream_health_sample(&self, upstream: &str) -> Option<RouteHealthSample> {
+        let guard = self.upstream_request_latency.read().ok()?;
+        let mut sample = None::<RouteHealthSample>;
+        for (key, stats) in guard.iter().filter(|(key, _)| key.upstream == upstream) {
+            let sample = sample.get_or_insert_with(RouteHealthSample::default);
+            sample.requests = sample.requests.saturating_add(stats.count);
+            if matches!(
+                key.outcome.as_str(),
+                "failure" | "timeout" | "backend_error"
+            ) {
+                sample.errors = sample.errors.saturating_add(stats.count);
+            }
+            for (bucket, count) in sample.latency_buckets.iter_mut().zip(stats.latency_buckets) {
+                *bucket = bucket.saturating_add(count);
+            }
+        }
+        sample
+    }
+
+    pub fn route_health_sample(&self, route: &str) -> Option<RouteHealthSample> {
+        let route_id = self.route_label_to_id.get(route).copied()?;
+        let stats = self.route_stats.get(route_id)?.snapshot();
+        Some(RouteHealthSample {
+            requests: stats.requests_total,
+            errors: stats
+                .failure
+                .saturating_add(stats.timeout)
+                .saturating_add(stats.backend_error),
+            latency_buckets: stats.latency_buckets,
+        })
+    }
+
+    pub fn record_rollout_transition(&self, route: &str, transition: &str) {
+        if let Ok(mut guard) = self.rollout_transitions.write() {
+            *guard
+                .entry(RolloutTransitionKey {
+                    route: route.to_string(),
+                    transition: transition.to_string(),
+                })
+                .or_default() += 1;
+        }
+    }
+
+    pub fn set_rollout_canary_percent(&self, route: &str, percent: u32) {
+        if let Ok(mut guard) = self.rollout_canary_percent.write() {
+            guard.insert(route.to_string(), percent);
+        }
+    }
+
+    pub(crate) fn snapshot_rollout_transitions(&self) -> Vec<(RolloutTransitionKey, u64)> {
+        self.rollout_transitions
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.route
+                        .cmp(&right.route)
+                        .then_with(|| left.transition.cmp(&right.transition))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_rollout_canary_percent(&self) -> Vec<(String, u32)> {
+        self.rollout_canary_percent
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(route, percent)| (route.clone(), *percent))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_route_split_counts(&self) -> Vec<(RouteSplitKey, u64)> {
+        self.route_split_counts
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.route
+                        .cmp(&right.route)
+                        .then_with(|| left.upstream.cmp(&right.upstream))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
     pub(crate) fn snapshot_backend_request_counts(&self) -> Vec<(BackendRequestCountKey, u64)> {
         self.backend_request_counts
             .read()
@@ -918,6 +1279,72 @@ impl Metrics {
             .unwrap_or_default()
     }
 
+    pub(crate) fn snapshot_mirror_request_counts(&self) -> Vec<(MirrorRequestKey, u64)> {
+        self.mirror_request_counts
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| mirror_request_key_order(left, right));
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_mirror_request_latency(
+        &self,
+    ) -> Vec<(MirrorRequestKey, RequestLatencyStats)> {
+        self.mirror_request_latency
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| mirror_request_key_order(left, right));
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_overload_shed_by_priority(&self) -> Vec<(OverloadShedKey, u64)> {
+        self.overload_shed_by_priority
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.reason
+                        .cmp(&right.reason)
+                        .then_with(|| left.priority.cmp(&right.priority))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_fault_injections(&self) -> Vec<(FaultInjectionKey, u64)> {
+        self.fault_injections
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.route
+                        .cmp(&right.route)
+                        .then_with(|| left.fault.cmp(&right.fault))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
     pub(crate) fn snapshot_upstream_request_latency(
         &self,
     ) -> Vec<(UpstreamRequestLatencyKey, RequestLatencyStats)> {
@@ -1015,6 +1442,58 @@ impl Metrics {
             .unwrap_or_default()
     }
 
+    pub(crate) fn snapshot_outlier_ejections(&self) -> Vec<(OutlierEjectionKey, u64)> {
+        self.outlier_ejections
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.upstream
+                        .cmp(&right.upstream)
+                        .then_with(|| left.reason.cmp(right.reason))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_upstream_zone_requests(&self) -> Vec<(UpstreamZoneRequestKey, u64)> {
+        self.upstream_zone_requests
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(key, value)| (key.clone(), *value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| {
+                    left.upstream
+                        .cmp(&right.upstream)
+                        .then_with(|| left.zone.cmp(&right.zone))
+                });
+                entries
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot_adaptive_concurrency(
+        &self,
+    ) -> Vec<(Option<String>, AdaptiveConcurrencyGauges)> {
+        self.adaptive_concurrency
+            .read()
+            .map(|guard| {
+                let mut entries = guard
+                    .iter()
+                    .map(|(upstream, gauges)| (upstream.clone(), *gauges))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+                entries
+            })
+            .unwrap_or_default()
+    }
+
     pub(crate) fn snapshot_downstream_tls_cert_expiry(
         &self,
     ) -> Vec<(DownstreamTlsCertExpiryKey, i64)> {
@@ -1120,6 +1599,11 @@ impl Metrics {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn inc_request_replay_fallback(&self) {
+        self.request_replay_fallbacks
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn inc_response_prebuffer_limit_reject(&self) {
         self.response_prebuffer_limit_rejects
             .fetch_add(1, Ordering::Relaxed);
@@ -1182,6 +1666,13 @@ impl Metrics {
             RetryAttemptTelemetryReason::Pool => {
                 self.retry_reason_pool.fetch_add(1, Ordering::Relaxed);
             }
+            RetryAttemptTelemetryReason::ConnectFailure => {
+                self.retry_reason_connect_failure
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+            RetryAttemptTelemetryReason::Status => {
+                self.retry_reason_status.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 
@@ -1200,7 +1691,8 @@ impl Metrics {
                     .fetch_add(1, Ordering::Relaxed);
             }
             RetryPolicyDenialReason::TerminalError(_)
-            | RetryPolicyDenialReason::AttemptLimitReached => {}
+            | RetryPolicyDenialReason::AttemptLimitReached
+            | RetryPolicyDenialReason::ConditionNotEnabled => {}
         }
     }
 
@@ -1229,7 +1721,7 @@ impl Metrics {
             HealthFailureReason::Tls => {
                 self.health_failure_tls.fetch_add(1, Ordering::Relaxed);
             }
-            HealthFailureReason::CircuitOpen => {}
+            HealthFailureReason::CircuitOpen | HealthFailureReason::Outlier => {}
         }
     }
 
@@ -1271,6 +1763,52 @@ impl Metrics {
         }
     }
 
+    pub fn inc_outlier_ejection(&self, upstream: &str, reason: OutlierEjectionReason) {
+        if let Ok(mut guard) = self.outlier_ejections.write() {
+            *guard
+                .entry(OutlierEjectionKey {
+                    upstream: upstream.to_string(),
+                    reason: reason.as_str(),
+                })
+                .or_default() += 1;
+        }
+    }
+
+    /// Counts a request sent to a backend in `zone` (`None` when the backend
+    /// has no zone), labelled by whether it stayed in `local_zone`.
+    pub fn record_upstream_zone_request(
+        &self,
+        upstream: &str,
+        zone: Option<&str>,
+        local_zone: Option<&str>,
+    ) {
+        let locality = match (zone, local_zone) {
+            (Some(zone), Some(local)) if zone == local => ZONE_LOCALITY_LOCAL,
+            (Some(_), Some(_)) => ZONE_LOCALITY_REMOTE,
+            _ => ZONE_LOCALITY_UNKNOWN,
+        };
+        if let Ok(mut guard) = self.upstream_zone_requests.write() {
+            *guard
+                .entry(UpstreamZoneRequestKey {
+                    upstream: upstream.to_string(),
+                    zone: zone.unwrap_or(ZONE_LOCALITY_UNKNOWN).to_string(),
+                    locality,
+                })
+                .or_default() += 1;
+        }
+    }
+
+    /// Publishes the global limiter (`upstream` `None`) or an upstream's own.
+    pub fn set_adaptive_concurrency(
+        &self,
+        upstream: Option<&str>,
+        gauges: AdaptiveConcurrencyGauges,
+    ) {
+        if let Ok(mut guard) = self.adaptive_concurrency.write() {
+            guard.insert(upstream.map(ToOwned::to_owned), gauges);
+        }
+    }
+
     pub fn record_upstream_tls_failure(&self, backend: &str, phase: &str, reason: &str) {
         if let Ok(mut guard) = self.upstream_tls_failures.write() {
             *guard
@@ -148,54 +148,31 @@ impl Metrics {
         ));
 
         out.push_str(
-            "# HELP spooky_overload_shed_by_reason_total Total overload shed decisions grouped by reason.\n",
+            "# HELP spooky_overload_shed_by_reason_total Total overload shed decisions grouped by reason and request priority class.\n",
         );
         out.push_str("# TYPE spooky_overload_shed_by_reason_total counter\n");
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"brownout\"}} {}\n",
-            self.overload_shed_brownout.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"adaptive_admission\"}} {}\n",
-            self.overload_shed_adaptive.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"route_cap\"}} {}\n",
-            self.overload_shed_route_cap.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"route_global_cap\"}} {}\n",
-            self.overload_shed_route_global_cap.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"global_inflight\"}} {}\n",
-            self.overload_shed_global_inflight.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"upstream_inflight\"}} {}\n",
-            self.overload_shed_upstream_inflight.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"backend_inflight\"}} {}\n",
-            self.overload_shed_backend_inflight.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"circuit_open\"}} {}\n",
-            self.overload_shed_circuit_open.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"request_buffer_cap\"}} {}\n",
-            self.overload_shed_request_buffer.load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"response_prebuffer_cap\"}} {}\n",
-            self.overload_shed_response_prebuffer
-                .load(Ordering::Relaxed)
-        ));
-        out.push_str(&format!(
-            "spooky_overload_shed_by_reason_total{{reason=\"connection_cap\"}} {}\n",
-            self.overload_shed_connection_cap.load(Ordering::Relaxed)
-        ));
+        let shed_by_priority = self.snapshot_overload_shed_by_priority();
+        for reason in OverloadShedReason::ALL {
+            let mut rendered = false;
+            for (key, count) in shed_by_priority
+                .iter()
+                .filter(|(key, _)| key.reason == reason)
+            {
+                out.push_str(&format!(
+                    "spooky_overload_shed_by_reason_total{{reason=\"{}\",priority=\"{}\"}} {}\n",
+                    reason.label(),
+                    escape_prometheus_label(&key.priority),
+                    count
+                ));
+                rendered = true;
+            }
+            if !rendered {
+                out.push_str(&format!(
+                    "spooky_overload_shed_by_reason_total{{reason=\"{}\",priority=\"\"}} 0\n",
+                    reason.label()
+                ));
+            }
+        }
 
         out.push_str(
             "# HELP spooky_inflight_wait_admit_total Successful inflight admissions after micro-wait.\n",
@@ -242,6 +219,18 @@ impl Metrics {
             self.hedge_won.load(Ordering::Relaxed)
         ));
 
+        out.push_str(
+            "# HELP spooky_hedge_won_by_attempt_total Requests won by a hedge, by hedge attempt number.\n",
+        );
+        out.push_str("# TYPE spooky_hedge_won_by_attempt_total counter\n");
+        for (index, won) in self.hedge_won_by_attempt.iter().enumerate() {
+            out.push_str(&format!(
+                "spooky_hedge_won_by_attempt_total{{attempt=\"{}\"}} {}\n",
+                index + 1,
+                won.load(Ordering::Relaxed)
+            ));
+        }
+
         out.push_str(
             "# HELP spooky_hedge_wasted_total Total hedge attempts that did not win the race.\n",
         );
@@ -398,6 +387,15 @@ impl Metrics {
             self.request_buffer_limit_rejects.load(Ordering::Relaxed)
         ));
 
+        out.push_str(
+            "# HELP spooky_request_replay_fallbacks Total replayable request bodies that outgrew the replay buffer and streamed without replay.\n",
+        );
+        out.push_str("# TYPE spooky_request_replay_fallbacks counter\n");
+        out.push_str(&format!(
+            "spooky_request_replay_fallbacks {}\n",
+            self.request_replay_fallbacks.load(Ordering::Relaxed)
+        ));
+
         out.push_str(
             "# HELP spooky_response_prebuffer_limit_rejects Total unknown-length upstream responses rejected due to prebuffer cap.\n",
         );
@@ -497,6 +495,14 @@ impl Metrics {
             "spooky_retry_attempts_total{{reason=\"pool\"}} {}\n",
             self.retry_reason_pool.load(Ordering::Relaxed)
         ));
+        out.push_str(&format!(
+            "spooky_retry_attempts_total{{reason=\"connect_failure\"}} {}\n",
+            self.retry_reason_connect_failure.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "spooky_retry_attempts_total{{reason=\"status\"}} {}\n",
+            self.retry_reason_status.load(Ordering::Relaxed)
+        ));
 
         out.push_str("# HELP spooky_circuit_breaker_rejected_total Requests rejected by an open circuit breaker.\n");
         out.push_str("# TYPE spooky_circuit_breaker_rejected_total counter\n");
@@ -512,6 +518,65 @@ impl Metrics {
             self.brownout_active.load(Ordering::Relaxed)
         ));
 
+        let adaptive_concurrency = self.snapshot_adaptive_concurrency();
+        let adaptive_labels = |upstream: &Option<String>| match upstream {
+            Some(upstream) => format!(
+                "scope=\"upstream\",upstream=\"{}\"",
+                escape_prometheus_label(upstream)
+            ),
+            None => "scope=\"global\",upstream=\"\"".to_string(),
+        };
+        out.push_str(
+            "# HELP spooky_adaptive_concurrency_limit Current in-flight limit of each adaptive admission limiter.\n",
+        );
+        out.push_str("# TYPE spooky_adaptive_concurrency_limit gauge\n");
+        for (upstream, gauges) in &adaptive_concurrency {
+            out.push_str(&format!(
+                "spooky_adaptive_concurrency_limit{{{}}} {}\n",
+                adaptive_labels(upstream),
+                gauges.limit
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_adaptive_concurrency_min_rtt_ms Estimated no-load RTT of each gradient limiter.\n",
+        );
+        out.push_str("# TYPE spooky_adaptive_concurrency_min_rtt_ms gauge\n");
+        for (upstream, gauges) in &adaptive_concurrency {
+            if let Some(min_rtt) = gauges.min_rtt {
+                out.push_str(&format!(
+                    "spooky_adaptive_concurrency_min_rtt_ms{{{}}} {:.3}\n",
+                    adaptive_labels(upstream),
+                    min_rtt.as_secs_f64() * 1_000.0
+                ));
+            }
+        }
+        out.push_str(
+            "# HELP spooky_adaptive_concurrency_smoothed_rtt_ms Smoothed RTT of each gradient limiter.\n",
+        );
+        out.push_str("# TYPE spooky_adaptive_concurrency_smoothed_rtt_ms gauge\n");
+        for (upstream, gauges) in &adaptive_concurrency {
+            if let Some(smoothed_rtt) = gauges.smoothed_rtt {
+                out.push_str(&format!(
+                    "spooky_adaptive_concurrency_smoothed_rtt_ms{{{}}} {:.3}\n",
+                    adaptive_labels(upstream),
+                    smoothed_rtt.as_secs_f64() * 1_000.0
+                ));
+            }
+        }
+        out.push_str(
+            "# HELP spooky_adaptive_concurrency_gradient Last limit scaling factor of each gradient limiter.\n",
+        );
+        out.push_str("# TYPE spooky_adaptive_concurrency_gradient gauge\n");
+        for (upstream, gauges) in &adaptive_concurrency {
+            if let Some(gradient) = gauges.gradient {
+                out.push_str(&format!(
+                    "spooky_adaptive_concurrency_gradient{{{}}} {:.3}\n",
+                    adaptive_labels(upstream),
+                    gradient
+                ));
+            }
+        }
+
         out.push_str(
             "# HELP spooky_health_failures_total Backend health failures, by failure reason.\n",
         );
@@ -532,6 +597,31 @@ impl Metrics {
             "spooky_health_failures_total{{reason=\"tls\"}} {}\n",
             self.health_failure_tls.load(Ordering::Relaxed)
         ));
+        out.push_str(
+            "# HELP spooky_outlier_ejections_total Backends ejected by outlier detection, by upstream and reason.\n",
+        );
+        out.push_str("# TYPE spooky_outlier_ejections_total counter\n");
+        for (key, value) in self.snapshot_outlier_ejections() {
+            out.push_str(&format!(
+                "spooky_outlier_ejections_total{{upstream=\"{}\",reason=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.upstream),
+                key.reason,
+                value
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_upstream_zone_requests_total Requests sent to backends, by upstream, backend zone and locality.\n",
+        );
+        out.push_str("# TYPE spooky_upstream_zone_requests_total counter\n");
+        for (key, value) in self.snapshot_upstream_zone_requests() {
+            out.push_str(&format!(
+                "spooky_upstream_zone_requests_total{{upstream=\"{}\",zone=\"{}\",locality=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.upstream),
+                escape_prometheus_label(&key.zone),
+                key.locality,
+                value
+            ));
+        }
         out.push_str(
             "# HELP spooky_downstream_tls_handshake_success_total Successful downstream TLS handshakes.\n",
         );
@@ -706,6 +796,95 @@ impl Metrics {
                 count
             ));
         }
+        out.push_str(
+            "# HELP spooky_route_split_total Requests assigned to each upstream by a route traffic split.\n",
+        );
+        out.push_str("# TYPE spooky_route_split_total counter\n");
+        for (key, count) in self.snapshot_route_split_counts() {
+            out.push_str(&format!(
+                "spooky_route_split_total{{route=\"{}\",upstream=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.route),
+                escape_prometheus_label(&key.upstream),
+                count
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_mirror_requests_total Mirror copies grouped by route, mirror upstream, and outcome.\n",
+        );
+        out.push_str("# TYPE spooky_mirror_requests_total counter\n");
+        for (key, count) in self.snapshot_mirror_request_counts() {
+            out.push_str(&format!(
+                "spooky_mirror_requests_total{{route=\"{}\",upstream=\"{}\",outcome=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.route),
+                escape_prometheus_label(&key.upstream),
+                escape_prometheus_label(&key.outcome),
+                count
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_mirror_request_latency_ms Mirror copy latency histogram grouped by route, mirror upstream, and outcome.\n",
+        );
+        out.push_str("# TYPE spooky_mirror_request_latency_ms histogram\n");
+        for (key, stats) in self.snapshot_mirror_request_latency() {
+            let route = escape_prometheus_label(&key.route);
+            let upstream = escape_prometheus_label(&key.upstream);
+            let outcome = escape_prometheus_label(&key.outcome);
+            let mut cumulative = 0u64;
+            for (idx, bucket_value) in stats.latency_buckets.iter().enumerate() {
+                cumulative = cumulative.saturating_add(*bucket_value);
+                let le = LATENCY_BUCKETS_MS
+                    .get(idx)
+                    .map(u64::to_string)
+                    .unwrap_or_else(|| "+Inf".to_string());
+                out.push_str(&format!(
+                    "spooky_mirror_request_latency_ms_bucket{{route=\"{}\",upstream=\"{}\",outcome=\"{}\",le=\"{}\"}} {}\n",
+                    route, upstream, outcome, le, cumulative
+                ));
+            }
+            out.push_str(&format!(
+                "spooky_mirror_request_latency_ms_sum{{route=\"{}\",upstream=\"{}\",outcome=\"{}\"}} {}\n",
+                route, upstream, outcome, stats.latency_ms_sum
+            ));
+            out.push_str(&format!(
+                "spooky_mirror_request_latency_ms_count{{route=\"{}\",upstream=\"{}\",outcome=\"{}\"}} {}\n",
+                route, upstream, outcome, stats.count
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_fault_injections_total Injected faults grouped by route and fault.\n",
+        );
+        out.push_str("# TYPE spooky_fault_injections_total counter\n");
+        for (key, count) in self.snapshot_fault_injections() {
+            out.push_str(&format!(
+                "spooky_fault_injections_total{{route=\"{}\",fault=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.route),
+                escape_prometheus_label(&key.fault),
+                count
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_rollout_canary_percent Current canary share of each route with a progressive rollout.\n",
+        );
+        out.push_str("# TYPE spooky_rollout_canary_percent gauge\n");
+        for (route, percent) in self.snapshot_rollout_canary_percent() {
+            out.push_str(&format!(
+                "spooky_rollout_canary_percent{{route=\"{}\"}} {}\n",
+                escape_prometheus_label(&route),
+                percent
+            ));
+        }
+        out.push_str(
+            "# HELP spooky_rollout_transitions_total Canary rollout transitions grouped by route and transition.\n",
+        );
+        out.push_str("# TYPE spooky_rollout_transitions_total counter\n");
+        for (key, count) in self.snapshot_rollout_transitions() {
+            out.push_str(&format!(
+                "spooky_rollout_transitions_total{{route=\"{}\",transition=\"{}\"}} {}\n",
+                escape_prometheus_label(&key.route),
+                escape_prometheus_label(&key.transition),
+                count
+            ));
+        }
         out.push_str(
             "# HELP spooky_backend_requests_total Total completed requests grouped by upstream, backend, status class, and outcome.\n",
         );
@@ -885,7 +1064,7 @@ impl Metrics {
     }
 }
 
-fn percentile_ms(stats: &RouteStats, quantile: f64) -> f64 {
+pub(super) fn percentile_ms(stats: &RouteStats, quantile: f64) -> f64 {
     // Percentiles are computed over the recorded latency samples, which under
     // latency sampling (SAMPLE_EVERY > 1) are only a subset of requests_total.
     // Using requests_total as the denominator would push the target past the
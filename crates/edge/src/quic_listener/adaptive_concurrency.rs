@@ -0,0 +1,65 @@
+use super::*;
+use crate::{
+    metrics::AdaptiveConcurrencyGauges, resilience::adaptive_admission::AdaptiveAdmission,
+};
+
+const ADAPTIVE_CONCURRENCY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl QUICListener {
+    /// Periodically publishes the limit, RTT estimates and gradient of every
+    /// enabled adaptive admission limiter.
+    pub(super) fn spawn_adaptive_concurrency_gauges(
+        resilience: Arc<RuntimeResilience>,
+        metrics: Arc<Metrics>,
+        task_registry: Arc<RuntimeTaskRegistry>,
+    ) {
+        let limiters = std::iter::once((None, Arc::clone(&resilience.adaptive_admission)))
+            .chain(
+                resilience
+                    .upstream_adaptive_admission
+                    .iter()
+                    .map(|(upstream, limiter)| (Some(upstream.clone()), Arc::clone(limiter))),
+            )
+            .filter(|(_, limiter)| limiter.enabled())
+            .collect::<Vec<(Option<String>, Arc<AdaptiveAdmission>)>>();
+        if limiters.is_empty() {
+            return;
+        }
+
+        let handle = match runtime_handle() {
+            Some(handle) => handle,
+            None => {
+                error!("Adaptive concurrency gauges disabled: no Tokio runtime available");
+                return;
+            }
+        };
+
+        let task_metrics = Arc::clone(&metrics);
+        let registration = spawn_supervised_async_task(
+            &handle,
+            "adaptive-concurrency-gauges",
+            Some(metrics),
+            async move {
+                let mut ticker = tokio::time::interval(ADAPTIVE_CONCURRENCY_SAMPLE_INTERVAL);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+                loop {
+                    ticker.tick().await;
+                    for (upstream, limiter) in &limiters {
+                        let snapshot = limiter.gradient_snapshot();
+                        task_metrics.set_adaptive_concurrency(
+                            upstream.as_deref(),
+                            AdaptiveConcurrencyGauges {
+                                limit: limiter.current_limit(),
+                                min_rtt: snapshot.map(|snapshot| snapshot.min_rtt),
+                                smoothed_rtt: snapshot.map(|snapshot| snapshot.smoothed_rtt),
+                                gradient: snapshot.map(|snapshot| snapshot.gradient),
+                            },
+                        );
+                    }
+                }
+            },
+        );
+        task_registry.register(registration);
+    }
+}
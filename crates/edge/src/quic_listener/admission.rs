@@ -21,6 +21,7 @@ use crate::{
     resilience::{
         adaptive_admission::AdaptivePermit,
         brownout::BrownoutController,
+        priority::RequestPriority,
         route_queue::{RouteQueuePermit, RouteQueueRejection},
         runtime::RuntimeResilience,
         scoped_rate_limit::{ScopedRateLimitRule, ScopedRateLimiters},
@@ -147,6 +148,29 @@ pub(super) enum AdmissionPolicyDecision {
     Overloaded(OverloadDecision),
 }
 
+/// Assigns the request its priority class. JWT claim rules only see the
+/// bearer token of routes whose auth policy verifies it.
+pub(super) fn classify_request_priority<'a>(
+    resilience: &'a RuntimeResilience,
+    policy: &RuntimeUpstreamPolicy,
+    header_lookup: Option<&LbHeaderLookup<'_>>,
+    route: &str,
+) -> &'a RequestPriority {
+    let mut claims = None;
+    resilience.priority.classify(
+        route,
+        |name| header_lookup.and_then(|lookup| lookup(name)),
+        |claim| {
+            let claims = claims.get_or_insert_with(|| verified_jwt_claims(policy, header_lookup));
+            claims
+                .as_ref()
+                .and_then(|claims| claims.get(claim))
+                .map(jwt_claim_values)
+                .unwrap_or_default()
+        },
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(super) fn evaluate_forwarding_pre_admission_policy<F>(
     policy: &RuntimeUpstreamPolicy,
@@ -154,6 +178,7 @@ pub(super) fn evaluate_forwarding_pre_admission_policy<F>(
     brownout: &BrownoutController,
     inflight_percent: u8,
     route: &str,
+    priority: &RequestPriority,
     retry_after_seconds: u32,
     scoped_rate_limits: &ScopedRateLimiters,
     key_for_rule: F,
@@ -166,7 +191,13 @@ where
         return auth;
     }
 
-    let brownout = evaluate_brownout_policy(brownout, inflight_percent, route, retry_after_seconds);
+    let brownout = evaluate_brownout_policy(
+        brownout,
+        inflight_percent,
+        route,
+        priority,
+        retry_after_seconds,
+    );
     if brownout != AdmissionPolicyDecision::AdmitReady {
         return brownout;
     }
@@ -222,10 +253,11 @@ pub(super) fn evaluate_brownout_policy(
     brownout: &BrownoutController,
     inflight_percent: u8,
     route: &str,
+    priority: &RequestPriority,
     retry_after_seconds: u32,
 ) -> AdmissionPolicyDecision {
     brownout.observe_admission_pressure(inflight_percent);
-    if brownout.route_allowed(route) {
+    if brownout.request_allowed(route, priority) {
         return AdmissionPolicyDecision::AdmitReady;
     }
 
@@ -285,6 +317,7 @@ pub(super) fn admission_rejection_response(
 pub(super) fn execute_forwarding_post_auth_admission(
     resilience: &RuntimeResilience,
     upstream_name: &str,
+    priority: &RequestPriority,
     upstream_pool: Option<&Arc<RwLock<UpstreamPool>>>,
     backend_index: Option<usize>,
     pending_forward_backend_index: usize,
@@ -292,7 +325,10 @@ pub(super) fn execute_forwarding_post_auth_admission(
     global_inflight: Arc<Semaphore>,
     inflight_acquire_wait: Duration,
 ) -> PostAuthAdmissionExecution {
-    let adaptive_permit = match resilience.adaptive_admission.try_acquire() {
+    let adaptive_permit = match resilience
+        .adaptive_admission_for(Some(upstream_name))
+        .try_acquire_share(priority.max_inflight_percent)
+    {
         Some(permit) => permit,
         None => {
             return PostAuthAdmissionExecution::Rejected(PostAuthAdmissionRejection::Overloaded(
@@ -304,7 +340,10 @@ pub(super) fn execute_forwarding_post_auth_admission(
         }
     };
 
-    let route_queue_permit = match resilience.route_queue.try_acquire(upstream_name) {
+    let route_queue_permit = match resilience
+        .route_queue
+        .try_acquire_share(upstream_name, priority.max_inflight_percent)
+    {
         Ok(permit) => permit,
         Err(rejection) => {
             return PostAuthAdmissionExecution::Rejected(PostAuthAdmissionRejection::Overloaded(
@@ -423,6 +462,31 @@ pub(super) fn api_key_is_authorized(
             .any(|expected| bool::from(provided.as_bytes().ct_eq(expected.as_bytes())))
 }
 
+/// Claims of the bearer token when the policy verifies JWTs and the token
+/// passes verification.
+fn verified_jwt_claims(
+    policy: &RuntimeUpstreamPolicy,
+    header_lookup: Option<&LbHeaderLookup<'_>>,
+) -> Option<Value> {
+    let jwt = policy.upstream_auth.jwt.as_ref()?;
+    let raw = header_lookup.and_then(|lookup| lookup(http::header::AUTHORIZATION.as_str()))?;
+    let token = QUICListener::bearer_token_from_authorization_value(&raw)?;
+    validated_hs256_jwt_claims(token.as_str(), jwt, SystemTime::now())
+}
+
+fn jwt_claim_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(value) => vec![value.clone()],
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToOwned::to_owned)
+            .collect(),
+        Value::Number(_) | Value::Bool(_) => vec![value.to_string()],
+        _ => Vec::new(),
+    }
+}
+
 pub(super) fn jwt_is_authorized(
     policy: &RuntimeUpstreamPolicy,
     header_lookup: Option<&LbHeaderLookup<'_>>,
@@ -11,6 +11,7 @@ use crate::runtime::backend::lifecycle::{
 impl QUICListener {
     pub(super) fn spawn_backend_dns_refresh(
         config: &RuntimeConfig,
+        upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
         transport_pool: Arc<UpstreamTransportPool>,
         backend_lifecycle: Arc<BackendLifecycleCoordinator>,
         backend_dns_resolver: SharedDnsResolver,
@@ -63,6 +64,7 @@ impl QUICListener {
                         )
                         .await;
                         observe_backend_dns_refresh(task_metrics.as_ref(), &outcome);
+                        start_slow_start_for_added_addrs(&upstream_pools, &outcome);
                         log_backend_dns_refresh(&outcome);
                     }
                 }
@@ -72,6 +74,42 @@ impl QUICListener {
     }
 }
 
+/// New addresses behind a hostname backend are cold; ramp the backend again
+/// in every upstream that has slow start configured.
+fn start_slow_start_for_added_addrs(
+    upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
+    outcome: &BackendDnsRefreshApplication,
+) {
+    let BackendDnsRefreshApplication::Updated {
+        backend_addr,
+        previous_addrs,
+        current_addrs,
+        ..
+    } = outcome
+    else {
+        return;
+    };
+    if current_addrs
+        .iter()
+        .all(|addr| previous_addrs.contains(addr))
+    {
+        return;
+    }
+
+    let now = Instant::now();
+    for (upstream_name, upstream_pool) in upstream_pools {
+        let Ok(mut pool) = upstream_pool.write() else {
+            continue;
+        };
+        if pool.start_backend_slow_start(backend_addr, now) {
+            debug!(
+                "slow start restarted for backend '{}' in upstream '{}' after DNS refresh",
+                backend_addr, upstream_name
+            );
+        }
+    }
+}
+
 async fn refresh_backend_hostname(
     backend: &RuntimeBackendLifecycleState,
     transport_pool: &UpstreamTransportPool,
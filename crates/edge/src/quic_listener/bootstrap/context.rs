@@ -5,12 +5,15 @@ use std::{
     time::{Duration, Instant},
 };
 
-use spooky_config::{backend_endpoint::BackendEndpoint, runtime::RuntimeUpstreamPolicy};
+use spooky_config::runtime::RuntimeUpstreamPolicy;
 use spooky_lb::upstream_pool::UpstreamPool;
 use spooky_transport::UpstreamTransportPool;
 
 use super::state::BootstrapConnectionState;
-use crate::{Metrics, resilience::runtime::RuntimeResilience, routing::index::RouteIndex};
+use crate::{
+    Metrics, resilience::runtime::RuntimeResilience, routing::index::RouteIndex,
+    runtime::backend::endpoints::BackendEndpoints,
+};
 
 pub(in crate::quic_listener) struct BootstrapBodyLimits {
     pub(in crate::quic_listener) max_request_body_bytes: usize,
@@ -22,8 +25,8 @@ pub(in crate::quic_listener) struct BootstrapRuntimeCtx {
     pub(in crate::quic_listener) backend_timeout: Duration,
     pub(in crate::quic_listener) body_limits: BootstrapBodyLimits,
     pub(in crate::quic_listener) transport_pool: Arc<UpstreamTransportPool>,
-    pub(in crate::quic_listener) backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
-    pub(in crate::quic_listener) upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    pub(in crate::quic_listener) backend_endpoints: Arc<BackendEndpoints>,
+    pub(in crate::quic_listener) route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
     pub(in crate::quic_listener) metrics: Arc<Metrics>,
     pub(in crate::quic_listener) resilience: Arc<RuntimeResilience>,
     pub(in crate::quic_listener) upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
@@ -43,7 +46,7 @@ impl BootstrapRuntimeCtx {
             },
             transport_pool: Arc::clone(&state.transport_pool),
             backend_endpoints: Arc::clone(&state.backend_endpoints),
-            upstream_policies: Arc::clone(&state.upstream_policies),
+            route_policies: Arc::clone(&state.route_policies),
             metrics: Arc::clone(&state.metrics),
             resilience: Arc::clone(&state.resilience),
             upstream_pools: state.upstream_pools.clone(),
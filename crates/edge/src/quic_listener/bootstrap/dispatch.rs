@@ -32,7 +32,13 @@ async fn dispatch_bootstrap_http(
         .request
         .runtime
         .transport_pool
-        .send_backend_request(&input.prepared_route.backend_addr, input.upstream_req)
+        .send_backend_request_with_timeout(
+            &input.prepared_route.backend_addr,
+            input.upstream_req,
+            input
+                .prepared_route
+                .backend_timeout(input.dispatch_ctx.request.runtime.backend_timeout),
+        )
         .await
     {
         Ok(response) => Ok(response),
@@ -277,7 +277,11 @@ pub(in crate::quic_listener) fn spawn_bootstrap_tls_listener(
                                 RequestBodyGuardrailConfig {
                                     idle_timeout: Duration::ZERO,
                                     total_timeout: Duration::ZERO,
-                                    max_body_bytes: runtime_ctx.body_limits.max_request_body_bytes,
+                                    max_body_bytes: prepared_route
+                                        .route_policy
+                                        .limits
+                                        .max_request_body_bytes
+                                        .unwrap_or(runtime_ctx.body_limits.max_request_body_bytes),
                                     max_buffered_bytes: usize::MAX,
                                 },
                                 RequestBodyGuardrailInput {
@@ -298,6 +302,7 @@ pub(in crate::quic_listener) fn spawn_bootstrap_tls_listener(
                             ) {
                                 observe_bootstrap_request_proxy_error(
                                     runtime_ctx.metrics.as_ref(),
+                                    &prepared_route.route_name,
                                     &prepared_route.upstream_name,
                                     &prepared_route.backend_addr,
                                     prepared_route.backend_index,
@@ -369,6 +374,7 @@ pub(in crate::quic_listener) fn spawn_bootstrap_tls_listener(
                                     let proxy_err = ProxyError::from(err);
                                     observe_bootstrap_request_proxy_error(
                                         runtime_ctx.metrics.as_ref(),
+                                        &prepared_route.route_name,
                                         &prepared_route.upstream_name,
                                         &prepared_route.backend_addr,
                                         prepared_route.backend_index,
@@ -16,7 +16,10 @@ use crate::{
 pub(in crate::quic_listener) fn bootstrap_route_target<'a>(
     route: &'a str,
 ) -> OutcomeRouteTarget<'a> {
-    OutcomeRouteTarget { route }
+    OutcomeRouteTarget {
+        route,
+        priority: None,
+    }
 }
 
 pub(in crate::quic_listener) fn bootstrap_backend_target<'a>(
@@ -34,7 +37,7 @@ pub(in crate::quic_listener) fn bootstrap_backend_target<'a>(
 pub(in crate::quic_listener) fn bootstrap_route_target_for_prepared(
     prepared_route: &BootstrapPreparedRoute,
 ) -> OutcomeRouteTarget<'_> {
-    bootstrap_route_target(&prepared_route.upstream_name)
+    bootstrap_route_target(&prepared_route.route_name)
 }
 
 pub(in crate::quic_listener) fn bootstrap_backend_target_for_prepared(
@@ -47,18 +50,24 @@ pub(in crate::quic_listener) fn bootstrap_backend_target_for_prepared(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(in crate::quic_listener) fn observe_bootstrap_admission_outcome(
     metrics: &Metrics,
+    route_name: &str,
     upstream_name: &str,
     backend_addr: &str,
     backend_index: usize,
+    priority: &str,
     request_start: Instant,
     status: StatusCode,
     outcome: AdmissionOutcomeClass,
 ) {
     let _ = observe_admission_outcome(
         metrics,
-        bootstrap_route_target(upstream_name),
+        OutcomeRouteTarget {
+            route: route_name,
+            priority: Some(priority),
+        },
         Some(bootstrap_backend_target(
             upstream_name,
             backend_addr,
@@ -70,8 +79,10 @@ pub(in crate::quic_listener) fn observe_bootstrap_admission_outcome(
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(in crate::quic_listener) fn observe_bootstrap_request_proxy_error(
     metrics: &Metrics,
+    route_name: &str,
     upstream_name: &str,
     backend_addr: &str,
     backend_index: usize,
@@ -81,7 +92,7 @@ pub(in crate::quic_listener) fn observe_bootstrap_request_proxy_error(
 ) {
     let _ = observe_proxy_error_outcome(
         metrics,
-        bootstrap_route_target(upstream_name),
+        bootstrap_route_target(route_name),
         Some(bootstrap_backend_target(
             upstream_name,
             backend_addr,
@@ -132,7 +143,7 @@ pub(in crate::quic_listener) fn observe_bootstrap_dispatch_failure(
     } else {
         log::warn!(
             "Bootstrap upstream error route={} backend={}: {}",
-            prepared_route.upstream_name,
+            prepared_route.route_name,
             prepared_route.backend_addr,
             proxy_err
         );
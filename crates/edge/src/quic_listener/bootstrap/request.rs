@@ -28,10 +28,13 @@ use super::{
     super::{
         QUICListener,
         admission::{
-            AdmissionPolicyDecision, admission_rejection_response,
+            AdmissionPolicyDecision, admission_rejection_response, classify_request_priority,
             evaluate_forwarding_pre_admission_policy,
         },
-        forwarding::BootstrapResolutionInput,
+        forwarding::{BootstrapResolutionInput, LocalRoute, RouteResolution},
+        route_action::{
+            evaluate_route_action_admission, observe_route_action_response, route_action_response,
+        },
     },
     context::BootstrapRequestCtx,
     intake::{BootstrapRequestIntake, bootstrap_error_response},
@@ -129,9 +132,19 @@ pub(in crate::quic_listener) struct BootstrapPreparedRoute {
     pub(in crate::quic_listener) endpoint: BackendEndpoint,
     pub(in crate::quic_listener) backend_addr: String,
     pub(in crate::quic_listener) backend_index: usize,
+    pub(in crate::quic_listener) route_name: String,
     pub(in crate::quic_listener) upstream_name: String,
-    pub(in crate::quic_listener) upstream_policy: RuntimeUpstreamPolicy,
+    pub(in crate::quic_listener) route_policy: RuntimeUpstreamPolicy,
     pub(in crate::quic_listener) upstream_pool: Arc<RwLock<UpstreamPool>>,
+    /// `Set-Cookie` value for session affinity, added to the response.
+    pub(in crate::quic_listener) affinity_cookie: Option<String>,
+}
+
+impl BootstrapPreparedRoute {
+    /// Route backend timeout, falling back to the listener-wide one.
+    pub(in crate::quic_listener) fn backend_timeout(&self, default: Duration) -> Duration {
+        self.route_policy.limits.backend_timeout.unwrap_or(default)
+    }
 }
 
 pub(in crate::quic_listener) struct BootstrapPolicyEvaluationInput<'a> {
@@ -158,13 +171,14 @@ fn bootstrap_bridge_headers(headers: &HeaderMap) -> Vec<quiche::h3::Header> {
 
 fn bootstrap_request_build_target<'a>(
     endpoint: &'a BackendEndpoint,
-    upstream_policy: &'a RuntimeUpstreamPolicy,
+    route_policy: &'a RuntimeUpstreamPolicy,
 ) -> RequestBuildTarget<'a> {
     RequestBuildTarget {
         endpoint,
         policies: RequestBuildPolicies {
-            host_policy: &upstream_policy.host.0,
-            forwarded_header_policy: &upstream_policy.forwarded_headers.0,
+            host_policy: &route_policy.host.0,
+            forwarded_header_policy: &route_policy.forwarded_headers.0,
+            path_rewrite: route_policy.path_rewrite.as_ref(),
         },
     }
 }
@@ -208,14 +222,14 @@ fn internal_proxy_error_response(alt_svc: &str) -> Response<BoxBody<Bytes, Infal
 
 fn resolve_scoped_rate_limit_key_for_bootstrap(
     rule: &crate::resilience::scoped_rate_limit::ScopedRateLimitRule,
-    upstream_name: &str,
+    route_name: &str,
     intake: &BootstrapRequestIntake,
     peer: SocketAddr,
     lb_header_lookup: &dyn Fn(&str) -> Option<String>,
 ) -> Option<String> {
     QUICListener::resolve_scoped_rate_limit_key(
         rule,
-        upstream_name,
+        route_name,
         &intake.method,
         &intake.path,
         intake.authority.as_deref(),
@@ -239,14 +253,22 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         method: &input.intake.method,
         path: &input.intake.path,
         authority: input.intake.authority.as_deref(),
+        client_addr: input.request_ctx.peer,
         header_lookup: Some(&lb_header_lookup),
         routing_index: &input.request_ctx.runtime.routing_index,
         upstream_pools: &input.request_ctx.runtime.upstream_pools,
-        upstream_policies: &input.request_ctx.runtime.upstream_policies,
+        route_policies: &input.request_ctx.runtime.route_policies,
         metrics: input.request_ctx.runtime.metrics.as_ref(),
         elapsed: Duration::ZERO,
     }) {
-        Ok(value) => value,
+        Ok(RouteResolution::Backend(value)) => value,
+        Ok(RouteResolution::Local(local)) => {
+            return Err(bootstrap_route_action_response(
+                &input,
+                &local,
+                &lb_header_lookup,
+            ));
+        }
         Err(err) => {
             let (status, body) = QUICListener::bootstrap_route_resolution_error_response(&err);
             return Err(BootstrapTerminalResponse::new(
@@ -259,8 +281,14 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         }
     };
 
+    let priority = classify_request_priority(
+        &input.request_ctx.runtime.resilience,
+        &resolved.route_policy,
+        Some(&lb_header_lookup),
+        &resolved.route_name,
+    );
     let admission = evaluate_forwarding_pre_admission_policy(
-        &resolved.upstream_policy,
+        &resolved.route_policy,
         Some(&lb_header_lookup),
         &input.request_ctx.runtime.resilience.brownout,
         input
@@ -269,7 +297,8 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             .resilience
             .adaptive_admission
             .inflight_percent(),
-        &resolved.upstream_name,
+        &resolved.route_name,
+        priority,
         input
             .request_ctx
             .runtime
@@ -279,7 +308,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         |rule| {
             resolve_scoped_rate_limit_key_for_bootstrap(
                 rule,
-                &resolved.upstream_name,
+                &resolved.route_name,
                 input.intake,
                 input.request_ctx.peer,
                 &lb_header_lookup,
@@ -299,21 +328,23 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             input.request_ctx.runtime.metrics.inc_policy_denied();
             observe_bootstrap_admission_outcome(
                 input.request_ctx.runtime.metrics.as_ref(),
+                &resolved.route_name,
                 &resolved.upstream_name,
                 &resolved.backend_addr,
                 resolved.backend_index,
+                &priority.name,
                 input.request_ctx.request_start,
                 StatusCode::UNAUTHORIZED,
                 AdmissionOutcomeClass::AuthDenied,
             );
             warn!(
                 "Bootstrap request route={} denied by auth policy",
-                resolved.upstream_name
+                resolved.route_name
             );
             let Some(response) = rejection_response.as_ref() else {
                 warn!(
                     "Bootstrap request route={} missing admission rejection response for unauthorized decision",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -324,7 +355,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             let Some(challenge) = response.www_authenticate else {
                 warn!(
                     "Bootstrap request route={} missing auth challenge in admission rejection response",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -347,9 +378,11 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             input.request_ctx.runtime.metrics.inc_request_rate_limited();
             observe_bootstrap_admission_outcome(
                 input.request_ctx.runtime.metrics.as_ref(),
+                &resolved.route_name,
                 &resolved.upstream_name,
                 &resolved.backend_addr,
                 resolved.backend_index,
+                &priority.name,
                 input.request_ctx.request_start,
                 StatusCode::TOO_MANY_REQUESTS,
                 AdmissionOutcomeClass::RateLimited,
@@ -361,7 +394,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             let Some(response) = rejection_response.as_ref() else {
                 warn!(
                     "Bootstrap request route={} missing admission rejection response for rate-limited decision",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -372,7 +405,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             let Some(retry_after_seconds) = response.retry_after_seconds else {
                 warn!(
                     "Bootstrap request route={} missing retry-after in rate-limited admission rejection response",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -394,9 +427,11 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         AdmissionPolicyDecision::Overloaded(decision) => {
             observe_bootstrap_admission_outcome(
                 input.request_ctx.runtime.metrics.as_ref(),
+                &resolved.route_name,
                 &resolved.upstream_name,
                 &resolved.backend_addr,
                 resolved.backend_index,
+                &priority.name,
                 input.request_ctx.request_start,
                 StatusCode::SERVICE_UNAVAILABLE,
                 AdmissionOutcomeClass::OverloadShed {
@@ -412,7 +447,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             let Some(response) = rejection_response.as_ref() else {
                 warn!(
                     "Bootstrap request route={} missing admission rejection response for overload decision",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -423,7 +458,7 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
             let Some(retry_after_seconds) = response.retry_after_seconds else {
                 warn!(
                     "Bootstrap request route={} missing retry-after in overload admission rejection response",
-                    resolved.upstream_name
+                    resolved.route_name
                 );
                 return Err(BootstrapTerminalResponse::new(
                     BootstrapLifecycleStage::AdmitOrReject,
@@ -450,10 +485,11 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         .backend_endpoints
         .get(&resolved.backend_addr)
     {
-        Some(endpoint) => endpoint.clone(),
+        Some(endpoint) => endpoint,
         None => {
             observe_bootstrap_request_proxy_error(
                 input.request_ctx.runtime.metrics.as_ref(),
+                &resolved.route_name,
                 &resolved.upstream_name,
                 &resolved.backend_addr,
                 resolved.backend_index,
@@ -479,19 +515,95 @@ pub(in crate::quic_listener) fn evaluate_bootstrap_request_policy(
         endpoint,
         backend_addr: resolved.backend_addr,
         backend_index: resolved.backend_index,
+        route_name: resolved.route_name,
         upstream_name: resolved.upstream_name,
-        upstream_policy: resolved.upstream_policy,
+        route_policy: resolved.route_policy,
         upstream_pool: resolved.upstream_pool,
+        affinity_cookie: resolved.affinity_cookie,
     })
 }
 
+/// Answers an edge-answered route on the bootstrap listener, after the same
+/// pre-admission checks forwarded requests get.
+fn bootstrap_route_action_response(
+    input: &BootstrapPolicyEvaluationInput<'_>,
+    local: &LocalRoute,
+    lb_header_lookup: &dyn Fn(&str) -> Option<String>,
+) -> Box<BootstrapTerminalResponse> {
+    let runtime = input.request_ctx.runtime;
+    if let Some(rejection) = evaluate_route_action_admission(
+        local,
+        Some(lb_header_lookup),
+        runtime.resilience.as_ref(),
+        runtime.metrics.as_ref(),
+        input.request_ctx.request_start,
+        |rule| {
+            resolve_scoped_rate_limit_key_for_bootstrap(
+                rule,
+                &local.route_name,
+                input.intake,
+                input.request_ctx.peer,
+                lb_header_lookup,
+            )
+        },
+    ) {
+        let reason = match rejection.status {
+            StatusCode::UNAUTHORIZED => BootstrapRejectionReason::AuthDenied,
+            StatusCode::TOO_MANY_REQUESTS => BootstrapRejectionReason::RateLimited,
+            _ => BootstrapRejectionReason::Overloaded,
+        };
+        let mut response = Response::builder()
+            .status(rejection.status)
+            .header("alt-svc", &runtime.alt_svc);
+        if let Some(challenge) = rejection.www_authenticate {
+            response = response.header("www-authenticate", challenge);
+        }
+        if let Some(retry_after_seconds) = rejection.retry_after_seconds {
+            response = response.header("retry-after", retry_after_seconds.max(1).to_string());
+        }
+        return BootstrapTerminalResponse::new(
+            BootstrapLifecycleStage::AdmitOrReject,
+            BootstrapTerminalOutcome::Rejected(reason),
+            response
+                .body(boxed_full(Bytes::from_static(rejection.body)))
+                .unwrap_or_else(|_| Response::new(boxed_full(Bytes::from_static(b"error\n")))),
+        );
+    }
+
+    let action_response = route_action_response(
+        &local.action,
+        &input.intake.path,
+        input.intake.authority.as_deref(),
+        input.intake.suppress_downstream_body,
+    );
+    observe_route_action_response(
+        runtime.metrics.as_ref(),
+        local,
+        input.request_ctx.request_start,
+        action_response.status,
+    );
+    let mut response = Response::builder()
+        .status(action_response.status)
+        .header("alt-svc", &runtime.alt_svc);
+    for (name, value) in &action_response.headers {
+        response = response.header(name.as_str(), value.as_str());
+    }
+    BootstrapTerminalResponse::new(
+        BootstrapLifecycleStage::ResolveRoute,
+        BootstrapTerminalOutcome::AcceptedStandardResponse,
+        response
+            .body(boxed_full(action_response.body))
+            .unwrap_or_else(|_| internal_proxy_error_response(&runtime.alt_svc)),
+    )
+}
+
 pub(in crate::quic_listener) fn build_bootstrap_upstream_request(
     input: BootstrapBuildRequestInput<'_>,
 ) -> Result<Request<BoxBody<Bytes, Infallible>>, BridgeError> {
     let bridge_headers = bootstrap_bridge_headers(input.request.headers());
     let request_target = bootstrap_request_build_target(
         &input.prepared_route.endpoint,
-        &input.prepared_route.upstream_policy,
+        &input.prepared_route.route_policy,
     );
 
     if input.intake.request_mode.is_websocket_upgrade() {
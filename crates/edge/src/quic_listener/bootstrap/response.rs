@@ -330,6 +330,9 @@ pub(in crate::quic_listener) fn write_bootstrap_response(
         resp_builder = resp_builder.header(&header.name, &header.value);
     }
     resp_builder = resp_builder.header("alt-svc", &input.dispatch_ctx.request.runtime.alt_svc);
+    if let Some(cookie) = input.prepared_route.affinity_cookie.as_deref() {
+        resp_builder = resp_builder.header(http::header::SET_COOKIE, cookie);
+    }
 
     if matches!(response_mode, BootstrapResponseMode::WebsocketUpgrade) {
         return Ok(BootstrapWritebackOutcome {
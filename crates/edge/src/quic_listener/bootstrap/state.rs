@@ -4,10 +4,7 @@ use std::{
     time::Duration,
 };
 
-use spooky_config::{
-    backend_endpoint::BackendEndpoint,
-    runtime::{ListenerRuntimeConfig, RuntimeUpstreamPolicy},
-};
+use spooky_config::runtime::{ListenerRuntimeConfig, RuntimeUpstreamPolicy};
 use spooky_lb::upstream_pool::UpstreamPool;
 use spooky_transport::UpstreamTransportPool;
 
@@ -16,8 +13,8 @@ use crate::{
     resilience::runtime::RuntimeResilience,
     routing::index::RouteIndex,
     runtime::{
-        bundle::RuntimeBundleHandle, shared_state::SharedRuntimeState,
-        tls::store::ListenerTlsReloadStore,
+        backend::endpoints::BackendEndpoints, bundle::RuntimeBundleHandle,
+        shared_state::SharedRuntimeState, tls::store::ListenerTlsReloadStore,
     },
 };
 
@@ -30,8 +27,8 @@ pub(in crate::quic_listener) struct BootstrapConnectionState {
     pub(in crate::quic_listener) connection_timeout: Duration,
     pub(in crate::quic_listener) listener_tls_store: Arc<ListenerTlsReloadStore>,
     pub(in crate::quic_listener) transport_pool: Arc<UpstreamTransportPool>,
-    pub(in crate::quic_listener) backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
-    pub(in crate::quic_listener) upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    pub(in crate::quic_listener) backend_endpoints: Arc<BackendEndpoints>,
+    pub(in crate::quic_listener) route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
     pub(in crate::quic_listener) metrics: Arc<Metrics>,
     pub(in crate::quic_listener) resilience: Arc<RuntimeResilience>,
     pub(in crate::quic_listener) upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
@@ -42,8 +39,8 @@ pub(in crate::quic_listener) struct BootstrapStartupState {
     pub(in crate::quic_listener) listener_config: ListenerRuntimeConfig,
     pub(in crate::quic_listener) listener_tls_store: Arc<ListenerTlsReloadStore>,
     pub(in crate::quic_listener) transport_pool: Arc<UpstreamTransportPool>,
-    pub(in crate::quic_listener) backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
-    pub(in crate::quic_listener) upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    pub(in crate::quic_listener) backend_endpoints: Arc<BackendEndpoints>,
+    pub(in crate::quic_listener) route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
     pub(in crate::quic_listener) metrics: Arc<Metrics>,
     pub(in crate::quic_listener) resilience: Arc<RuntimeResilience>,
     pub(in crate::quic_listener) upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
@@ -61,7 +58,7 @@ pub(in crate::quic_listener) fn build_bootstrap_startup_state(
         listener_tls_store: Arc::clone(&shared.listener_tls_store),
         transport_pool: Arc::clone(&shared.transport_pool),
         backend_endpoints: Arc::clone(&generation.backend_endpoints),
-        upstream_policies: Arc::clone(&generation.upstream_policies),
+        route_policies: Arc::clone(&generation.route_policies),
         metrics: Arc::clone(&shared.metrics),
         resilience: Arc::clone(&generation.resilience),
         upstream_pools: generation.upstream_pools.clone(),
@@ -79,7 +76,7 @@ pub(in crate::quic_listener) fn bootstrap_connection_state(
         listener_tls_store,
         transport_pool,
         backend_endpoints,
-        upstream_policies,
+        route_policies,
         metrics,
         resilience,
         upstream_pools,
@@ -93,7 +90,7 @@ pub(in crate::quic_listener) fn bootstrap_connection_state(
             shared.listener_tls_store.clone(),
             shared.transport_pool.clone(),
             generation.backend_endpoints.clone(),
-            generation.upstream_policies.clone(),
+            generation.route_policies.clone(),
             shared.metrics.clone(),
             generation.resilience.clone(),
             generation.upstream_pools.clone(),
@@ -105,7 +102,7 @@ pub(in crate::quic_listener) fn bootstrap_connection_state(
             Arc::clone(&startup.listener_tls_store),
             Arc::clone(&startup.transport_pool),
             Arc::clone(&startup.backend_endpoints),
-            Arc::clone(&startup.upstream_policies),
+            Arc::clone(&startup.route_policies),
             Arc::clone(&startup.metrics),
             Arc::clone(&startup.resilience),
             startup.upstream_pools.clone(),
@@ -128,7 +125,7 @@ pub(in crate::quic_listener) fn bootstrap_connection_state(
         listener_tls_store,
         transport_pool,
         backend_endpoints,
-        upstream_policies,
+        route_policies,
         metrics,
         resilience,
         upstream_pools,
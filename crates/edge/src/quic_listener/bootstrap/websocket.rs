@@ -83,8 +83,11 @@ pub(in crate::quic_listener) async fn dispatch_bootstrap_websocket(
     parts.uri = upstream_path_uri;
     let upstream_req = Request::from_parts(parts, body);
 
+    let backend_timeout = input
+        .prepared_route
+        .backend_timeout(input.dispatch_ctx.request.runtime.backend_timeout);
     let stream = match tokio::time::timeout(
-        input.dispatch_ctx.request.runtime.backend_timeout,
+        backend_timeout,
         tokio::net::TcpStream::connect(&backend_target),
     )
     .await
@@ -153,12 +156,7 @@ pub(in crate::quic_listener) async fn dispatch_bootstrap_websocket(
         let _ = conn.with_upgrades().await;
     });
 
-    match tokio::time::timeout(
-        input.dispatch_ctx.request.runtime.backend_timeout,
-        sender.send_request(upstream_req),
-    )
-    .await
-    {
+    match tokio::time::timeout(backend_timeout, sender.send_request(upstream_req)).await {
         Ok(Ok(resp)) => Ok(resp),
         Ok(Err(err)) => {
             let proxy_err = ProxyError::Transport(err.to_string());
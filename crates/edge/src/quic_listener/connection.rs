@@ -175,7 +175,7 @@ impl QUICListener {
         if self.connections.len() >= self.max_active_connections {
             self.metrics.inc_connection_cap_reject();
             self.metrics
-                .inc_overload_shed_reason(OverloadShedReason::ConnectionCap);
+                .inc_overload_shed_reason(OverloadShedReason::ConnectionCap, None);
             debug!(
                 "Active connection cap reached (cap={}, active={}), dropping Initial packet from {}",
                 self.max_active_connections,
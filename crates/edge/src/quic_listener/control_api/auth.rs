@@ -15,6 +15,14 @@ pub(super) enum ControlApiRoute {
     ReloadCerts,
     ReloadRuntime,
     Restart,
+    Rollouts,
+    RolloutCommand,
+    RouteExplain,
+    Backends,
+    BackendStatus,
+    BackendCommand,
+    BackendUpsert,
+    BackendRemove,
 }
 
 impl ControlApiRoute {
@@ -57,6 +65,26 @@ impl QUICListener {
                 Some(ControlApiRoute::ReloadRuntime)
             }
             Method::POST if path == paths.restart_path.as_str() => Some(ControlApiRoute::Restart),
+            Method::GET if path == paths.rollouts_path.as_str() => Some(ControlApiRoute::Rollouts),
+            Method::POST if Self::rollout_command_target(path, &paths.rollouts_path).is_some() => {
+                Some(ControlApiRoute::RolloutCommand)
+            }
+            Method::POST if path == paths.explain_path.as_str() => {
+                Some(ControlApiRoute::RouteExplain)
+            }
+            Method::GET if path == paths.backends_path.as_str() => Some(ControlApiRoute::Backends),
+            Method::GET if Self::backend_target(path, &paths.backends_path).is_some() => {
+                Some(ControlApiRoute::BackendStatus)
+            }
+            Method::POST if Self::backend_command_target(path, &paths.backends_path).is_some() => {
+                Some(ControlApiRoute::BackendCommand)
+            }
+            Method::PUT if Self::backend_target(path, &paths.backends_path).is_some() => {
+                Some(ControlApiRoute::BackendUpsert)
+            }
+            Method::DELETE if Self::backend_target(path, &paths.backends_path).is_some() => {
+                Some(ControlApiRoute::BackendRemove)
+            }
             _ => None,
         }
     }
@@ -71,7 +99,11 @@ impl QUICListener {
         }
 
         let response = match route {
-            ControlApiRoute::Runtime => json!({
+            ControlApiRoute::Runtime
+            | ControlApiRoute::Rollouts
+            | ControlApiRoute::RouteExplain
+            | ControlApiRoute::Backends
+            | ControlApiRoute::BackendStatus => json!({
                 "error": "unauthorized",
             }),
             ControlApiRoute::ReloadCerts | ControlApiRoute::ReloadRuntime => json!({
@@ -82,6 +114,13 @@ impl QUICListener {
                 "accepted": false,
                 "error": "unauthorized",
             }),
+            ControlApiRoute::RolloutCommand
+            | ControlApiRoute::BackendCommand
+            | ControlApiRoute::BackendUpsert
+            | ControlApiRoute::BackendRemove => json!({
+                "applied": false,
+                "error": "unauthorized",
+            }),
             ControlApiRoute::Health | ControlApiRoute::Ready => unreachable!(),
         };
         Err(Box::new(Self::json_response(
@@ -0,0 +1,463 @@
+use std::{borrow::Cow, sync::PoisonError};
+
+use bytes::Bytes;
+use http_body_util::Full;
+use serde::{Deserialize, Serialize};
+use spooky_config::{
+    config::{Backend, HealthCheck},
+    default::get_default_weight,
+    runtime::RuntimeBackend,
+};
+use spooky_lb::{health::BackendAdminState, upstream_pool::UpstreamPool};
+
+use super::{state::ControlApiState, *};
+use crate::{
+    quic_listener::runtime_state::ControlPlaneRuntimeView,
+    runtime::backend::{admin::BackendAdminError, membership::BackendMembershipError},
+};
+
+const MAX_BACKEND_SPEC_BODY_BYTES: usize = 16 * 1024;
+
+/// Body of `PUT {backends_path}/{upstream}/{id}`. Fields match a configured
+/// backend; the id comes from the path.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ControlApiBackendSpec {
+    pub(super) address: String,
+    #[serde(default = "get_default_weight")]
+    pub(super) weight: u32,
+    #[serde(default)]
+    pub(super) priority: u32,
+    #[serde(default)]
+    pub(super) zone: Option<String>,
+    #[serde(default)]
+    pub(super) health_check: Option<HealthCheck>,
+}
+
+impl ControlApiBackendSpec {
+    pub(super) fn into_backend(self, id: &str) -> Backend {
+        Backend {
+            id: id.to_string(),
+            address: self.address,
+            weight: self.weight,
+            health_check: self.health_check,
+            priority: self.priority,
+            zone: self.zone,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ControlApiBackendsPayload {
+    backends: Vec<ControlApiBackendStatusPayload>,
+}
+
+#[derive(Serialize)]
+struct ControlApiBackendStatusPayload {
+    upstream: String,
+    id: String,
+    address: String,
+    admin_state: &'static str,
+    healthy: bool,
+    active_requests: usize,
+    /// Out of rotation with no requests left in flight.
+    drained: bool,
+}
+
+impl ControlApiBackendStatusPayload {
+    fn from_pool(upstream: &str, pool: &UpstreamPool, index: usize) -> Option<Self> {
+        let state = pool.backend_runtime_state(index)?;
+        Some(Self {
+            upstream: upstream.to_string(),
+            id: pool.backend_id(index)?.to_string(),
+            address: pool.backend_address(index)?.to_string(),
+            admin_state: state.admin_state.as_str(),
+            healthy: state.healthy,
+            active_requests: state.active_requests,
+            drained: !state.admin_state.is_enabled() && state.active_requests == 0,
+        })
+    }
+}
+
+impl QUICListener {
+    /// Splits `{backends_path}/{upstream}/{id}` into the upstream name and
+    /// backend id. The id is a single percent-decoded segment, so an id
+    /// containing `/` is addressed as `%2F`.
+    pub(super) fn backend_target<'a>(
+        path: &'a str,
+        backends_path: &str,
+    ) -> Option<(&'a str, Cow<'a, str>)> {
+        let rest = path
+            .strip_prefix(backends_path.trim_end_matches('/'))?
+            .strip_prefix('/')?;
+        let (upstream, id) = rest.split_once('/')?;
+        if upstream.is_empty() || id.is_empty() || id.contains('/') {
+            return None;
+        }
+        Some((upstream, percent_decode_segment(id)?))
+    }
+
+    /// Splits `{backends_path}/{upstream}/{id}/{command}` into the backend
+    /// and the admin state `drain`, `disable` or `enable` moves it to.
+    pub(super) fn backend_command_target<'a>(
+        path: &'a str,
+        backends_path: &str,
+    ) -> Option<(&'a str, Cow<'a, str>, BackendAdminState)> {
+        let (target, command) = path.rsplit_once('/')?;
+        let state = match command {
+            "drain" => BackendAdminState::Draining,
+            "disable" => BackendAdminState::Disabled,
+            "enable" => BackendAdminState::Enabled,
+            _ => return None,
+        };
+        let (upstream, id) = Self::backend_target(target, backends_path)?;
+        Some((upstream, id, state))
+    }
+
+    fn backend_status(
+        runtime: &ControlPlaneRuntimeView,
+        upstream: &str,
+        id: &str,
+    ) -> Option<ControlApiBackendStatusPayload> {
+        let pool = runtime.upstream_pools().get(upstream)?;
+        let pool = pool.read().unwrap_or_else(PoisonError::into_inner);
+        let index = pool.backend_index_by_id(id)?;
+        ControlApiBackendStatusPayload::from_pool(upstream, &pool, index)
+    }
+
+    pub(super) fn render_control_api_backends(state: &ControlApiState) -> Response<Full<Bytes>> {
+        let runtime = state.current_service_state().runtime;
+        let mut upstreams = runtime.upstream_pools().iter().collect::<Vec<_>>();
+        upstreams.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        let mut backends = Vec::new();
+        for (upstream, pool) in upstreams {
+            let pool = pool.read().unwrap_or_else(PoisonError::into_inner);
+            backends.extend(pool.backend_indices().into_iter().filter_map(|index| {
+                ControlApiBackendStatusPayload::from_pool(upstream, &pool, index)
+            }));
+        }
+        Self::json_response(StatusCode::OK, ControlApiBackendsPayload { backends })
+    }
+
+    pub(super) fn render_control_api_backend_status(
+        req: &Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        let runtime_state = state.current_service_state();
+        let Some((upstream, id)) =
+            Self::backend_target(req.uri().path(), &runtime_state.paths.backends_path)
+        else {
+            return Self::control_api_not_found_response();
+        };
+
+        match Self::backend_status(&runtime_state.runtime, upstream, &id) {
+            Some(status) => Self::json_response(StatusCode::OK, status),
+            None => Self::json_response(
+                StatusCode::NOT_FOUND,
+                json!({
+                    "error": BackendAdminError::UnknownBackend {
+                        upstream: upstream.to_string(),
+                        backend: id.to_string(),
+                    }
+                    .to_string(),
+                }),
+            ),
+        }
+    }
+
+    pub(super) fn handle_control_api_backend_command(
+        req: &Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        let runtime_state = state.current_service_state();
+        let Some((upstream, id, admin_state)) =
+            Self::backend_command_target(req.uri().path(), &runtime_state.paths.backends_path)
+        else {
+            return Self::control_api_not_found_response();
+        };
+
+        Self::apply_control_api_backend_command(state, upstream, &id, admin_state)
+    }
+
+    pub(super) fn apply_control_api_backend_command(
+        state: &ControlApiState,
+        upstream: &str,
+        id: &str,
+        admin_state: BackendAdminState,
+    ) -> Response<Full<Bytes>> {
+        let runtime = state.current_service_state().runtime;
+        match runtime
+            .backend_admin()
+            .set(runtime.upstream_pools(), upstream, id, admin_state)
+        {
+            Ok(changed) => Self::json_response(
+                StatusCode::OK,
+                json!({
+                    "applied": true,
+                    "changed": changed,
+                    "backend": Self::backend_status(&runtime, upstream, id),
+                }),
+            ),
+            Err(err) => Self::json_response(
+                StatusCode::NOT_FOUND,
+                json!({
+                    "applied": false,
+                    "error": err.to_string(),
+                }),
+            ),
+        }
+    }
+
+    pub(super) async fn handle_control_api_backend_upsert(
+        req: Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        let runtime_state = state.current_service_state();
+        let Some((upstream, id)) =
+            Self::backend_target(req.uri().path(), &runtime_state.paths.backends_path)
+                .map(|(upstream, id)| (upstream.to_string(), id.into_owned()))
+        else {
+            return Self::control_api_not_found_response();
+        };
+
+        match Self::read_control_api_json_body::<ControlApiBackendSpec>(
+            req,
+            MAX_BACKEND_SPEC_BODY_BYTES,
+            "backend spec",
+        )
+        .await
+        {
+            Ok(spec) => {
+                Self::apply_control_api_backend_upsert(state, &upstream, &spec.into_backend(&id))
+            }
+            Err(err) => Self::json_response(
+                StatusCode::BAD_REQUEST,
+                json!({
+                    "applied": false,
+                    "error": err,
+                }),
+            ),
+        }
+    }
+
+    /// Adds `backend` to `upstream`, or updates the backend holding its id.
+    pub(super) fn apply_control_api_backend_upsert(
+        state: &ControlApiState,
+        upstream: &str,
+        backend: &Backend,
+    ) -> Response<Full<Bytes>> {
+        let runtime = state.current_service_state().runtime;
+        let result =
+            runtime
+                .backend_membership()
+                .commit(upstream, &backend.id, Some(backend), || {
+                    Self::upsert_backend(&runtime, upstream, backend)
+                });
+        match result {
+            Ok(added) => Self::json_response(
+                if added {
+                    StatusCode::CREATED
+                } else {
+                    StatusCode::OK
+                },
+                json!({
+                    "applied": true,
+                    "added": added,
+                    "backend": Self::backend_status(&runtime, upstream, &backend.id),
+                }),
+            ),
+            Err(err) => Self::backend_membership_error_response(&err),
+        }
+    }
+
+    pub(super) fn handle_control_api_backend_remove(
+        req: &Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        let runtime_state = state.current_service_state();
+        let Some((upstream, id)) =
+            Self::backend_target(req.uri().path(), &runtime_state.paths.backends_path)
+        else {
+            return Self::control_api_not_found_response();
+        };
+
+        Self::apply_control_api_backend_remove(state, upstream, &id)
+    }
+
+    /// Takes backend `id` out of `upstream`. Requests already in flight to it
+    /// finish; new ones no longer reach it.
+    pub(super) fn apply_control_api_backend_remove(
+        state: &ControlApiState,
+        upstream: &str,
+        id: &str,
+    ) -> Response<Full<Bytes>> {
+        let runtime = state.current_service_state().runtime;
+        let result = runtime.backend_membership().commit(upstream, id, None, || {
+            Self::remove_backend(&runtime, upstream, id)
+        });
+        match result {
+            Ok(address) => Self::json_response(
+                StatusCode::OK,
+                json!({
+                    "applied": true,
+                    "removed": {
+                        "upstream": upstream,
+                        "id": id,
+                        "address": address,
+                    },
+                }),
+            ),
+            Err(err) => Self::backend_membership_error_response(&err),
+        }
+    }
+
+    /// Returns whether the backend was added rather than updated.
+    fn upsert_backend(
+        runtime: &ControlPlaneRuntimeView,
+        upstream: &str,
+        backend: &Backend,
+    ) -> Result<bool, BackendMembershipError> {
+        let unknown_upstream = || BackendMembershipError::UnknownUpstream(upstream.to_string());
+        let pool = runtime
+            .upstream_pools()
+            .get(upstream)
+            .ok_or_else(unknown_upstream)?;
+        let runtime_upstream = runtime
+            .runtime_config()
+            .upstreams
+            .get(upstream)
+            .ok_or_else(unknown_upstream)?;
+        let runtime_backend = RuntimeBackend::normalize(upstream, backend)
+            .map_err(|err| BackendMembershipError::Invalid(err.to_string()))?;
+        let endpoints = runtime.backend_endpoints();
+
+        let existing = pool
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .backend_index_by_id(&backend.id);
+        let (index, added) = match existing {
+            Some(_) => {
+                let index = pool
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .update_backend(backend)
+                    .map_err(BackendMembershipError::Conflict)?;
+                (index, false)
+            }
+            None => {
+                let endpoint = &runtime_backend.endpoint;
+                if let Some(address) = endpoints.address_for_origin(&endpoint.canonical) {
+                    return Err(BackendMembershipError::Conflict(format!(
+                        "origin {} is already served by backend address '{}'",
+                        endpoint.origin, address
+                    )));
+                }
+                runtime
+                    .transport_pool()
+                    .add_runtime_backend(runtime_upstream, &runtime_backend)
+                    .map_err(BackendMembershipError::Invalid)?;
+                endpoints.insert(backend.address.clone(), endpoint.canonical.clone());
+                let added = pool
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .add_backend(backend);
+                match added {
+                    Ok(index) => (index, true),
+                    Err(err) => {
+                        endpoints.remove(&backend.address);
+                        runtime.transport_pool().remove_backend(&backend.address);
+                        return Err(BackendMembershipError::Conflict(err));
+                    }
+                }
+            }
+        };
+
+        // A new revision retires the checks scheduled for the old one.
+        if let Some(health) = runtime_backend.health_check.as_ref() {
+            Self::spawn_backend_health_check(
+                pool,
+                index,
+                &runtime_backend.endpoint.canonical,
+                health,
+                runtime.transport_pool(),
+                runtime.backend_lifecycle(),
+                runtime.metrics(),
+                &runtime.generation_tasks(),
+            );
+        }
+        Ok(added)
+    }
+
+    /// Returns the address the removed backend was serving.
+    fn remove_backend(
+        runtime: &ControlPlaneRuntimeView,
+        upstream: &str,
+        id: &str,
+    ) -> Result<String, BackendMembershipError> {
+        let pool = runtime
+            .upstream_pools()
+            .get(upstream)
+            .ok_or_else(|| BackendMembershipError::UnknownUpstream(upstream.to_string()))?;
+        let address = {
+            let mut pool = pool.write().unwrap_or_else(PoisonError::into_inner);
+            let address = pool
+                .backend_index_by_id(id)
+                .and_then(|index| pool.backend_address(index))
+                .map(str::to_string)
+                .ok_or_else(|| BackendMembershipError::UnknownBackend {
+                    upstream: upstream.to_string(),
+                    backend: id.to_string(),
+                })?;
+            pool.remove_backend(id)
+                .map_err(BackendMembershipError::Conflict)?;
+            address
+        };
+
+        runtime.transport_pool().remove_backend(&address);
+        runtime.backend_endpoints().remove(&address);
+        runtime.backend_admin().forget(upstream, id);
+        Ok(address)
+    }
+
+    fn backend_membership_error_response(err: &BackendMembershipError) -> Response<Full<Bytes>> {
+        let status = match err {
+            BackendMembershipError::UnknownUpstream(_)
+            | BackendMembershipError::UnknownBackend { .. } => StatusCode::NOT_FOUND,
+            BackendMembershipError::Invalid(_) => StatusCode::BAD_REQUEST,
+            BackendMembershipError::Conflict(_) => StatusCode::CONFLICT,
+        };
+        Self::json_response(
+            status,
+            json!({
+                "applied": false,
+                "error": err.to_string(),
+            }),
+        )
+    }
+}
+
+/// Decodes `%XX` escapes in one path segment. `None` for a malformed escape,
+/// a result that is not UTF-8, or an empty result.
+fn percent_decode_segment(segment: &str) -> Option<Cow<'_, str>> {
+    if !segment.contains('%') {
+        return Some(Cow::Borrowed(segment));
+    }
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = bytes
+                .get(index + 1..index + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    let decoded = String::from_utf8(decoded).ok()?;
+    (!decoded.is_empty()).then_some(Cow::Owned(decoded))
+}
@@ -0,0 +1,333 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use bytes::Bytes;
+use http_body_util::Full;
+use serde::{Deserialize, Serialize};
+
+use super::{state::ControlApiState, *};
+use crate::quic_listener::forwarding::{
+    RouteExplainRequest, RouteExplainRuntime, RouteExplanation,
+};
+
+const MAX_EXPLAIN_BODY_BYTES: usize = 64 * 1024;
+
+/// Synthetic request posted to `explain_path`. Header names match
+/// case-insensitively; `config_path` evaluates a candidate config instead of
+/// the live runtime.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ControlApiExplainRequest {
+    #[serde(default = "default_explain_method")]
+    pub(super) method: String,
+    #[serde(default)]
+    pub(super) authority: Option<String>,
+    pub(super) path: String,
+    #[serde(default)]
+    pub(super) headers: HashMap<String, String>,
+    #[serde(default)]
+    pub(super) client_ip: Option<IpAddr>,
+    #[serde(default)]
+    pub(super) config_path: Option<String>,
+}
+
+fn default_explain_method() -> String {
+    "GET".to_string()
+}
+
+/// Route state built from a candidate config. Pools, breakers and rate
+/// limiters start fresh, as they would right after a reload.
+struct CandidateExplainRuntime {
+    routing_index: RouteIndex,
+    route_policies: HashMap<String, RuntimeUpstreamPolicy>,
+    upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
+    resilience: RuntimeResilience,
+}
+
+impl CandidateExplainRuntime {
+    fn from_runtime_config(config: &RuntimeConfig) -> Result<Self, String> {
+        let mut upstream_pools = HashMap::with_capacity(config.upstreams.len());
+        for (name, upstream) in &config.upstreams {
+            let pool = UpstreamPool::from_runtime_upstream(upstream)
+                .map_err(|err| format!("failed to create upstream pool '{name}': {err}"))?;
+            upstream_pools.insert(name.clone(), Arc::new(RwLock::new(pool)));
+        }
+        Ok(Self {
+            routing_index: RouteIndex::from_runtime_routes(&config.routes),
+            route_policies: config
+                .routes
+                .iter()
+                .map(|(name, route)| (name.clone(), route.policy.clone()))
+                .collect(),
+            upstream_pools,
+            resilience: RuntimeResilience::from_policies(
+                &config.policies.admission,
+                &config.policies.rate_limits,
+                &config.upstream_adaptive_admission(std::time::Duration::MAX),
+            ),
+        })
+    }
+
+    fn explain_runtime(&self) -> RouteExplainRuntime<'_> {
+        RouteExplainRuntime {
+            routing_index: &self.routing_index,
+            route_policies: &self.route_policies,
+            upstream_pools: &self.upstream_pools,
+            resilience: &self.resilience,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainPayload {
+    config: &'static str,
+    config_path: Option<String>,
+    request: ControlApiExplainRequestPayload,
+    rejected_by: Option<&'static str>,
+    error: Option<String>,
+    route: Option<ControlApiExplainRoutePayload>,
+    auth: Option<ControlApiExplainAuthPayload>,
+    load_balancing: Option<ControlApiExplainBalancingPayload>,
+    admission: Option<ControlApiExplainAdmissionPayload>,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainRequestPayload {
+    method: String,
+    authority: Option<String>,
+    path: String,
+    client_ip: String,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainRoutePayload {
+    name: String,
+    reason: String,
+    matched_path_len: usize,
+    host_specific: bool,
+    route_upstream: String,
+    upstream: Option<String>,
+    split: bool,
+    canary_percent: Option<u32>,
+    action: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainAuthPayload {
+    api_key: Option<bool>,
+    jwt: Option<bool>,
+    external_auth: bool,
+    required_scopes: Vec<String>,
+    required_roles: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainBalancingPayload {
+    strategy: String,
+    key: String,
+    key_source: &'static str,
+    backend: Option<ControlApiExplainBackendPayload>,
+    healthy_backends: usize,
+    total_backends: usize,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainBackendPayload {
+    index: usize,
+    address: String,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainAdmissionPayload {
+    scope: String,
+    priority: String,
+    brownout_active: bool,
+    brownout_allows: bool,
+    rate_limits: Vec<ControlApiExplainRateLimitPayload>,
+    circuit: Option<ControlApiExplainCircuitPayload>,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainRateLimitPayload {
+    rule: String,
+    key: String,
+    allows_request: bool,
+}
+
+#[derive(Serialize)]
+struct ControlApiExplainCircuitPayload {
+    state: &'static str,
+    allows_request: bool,
+}
+
+impl ControlApiExplainPayload {
+    fn new(
+        config_path: Option<String>,
+        request: ControlApiExplainRequestPayload,
+        explanation: RouteExplanation,
+    ) -> Self {
+        Self {
+            config: if config_path.is_some() {
+                "candidate"
+            } else {
+                "live"
+            },
+            config_path,
+            request,
+            rejected_by: explanation.rejected_by.map(|rejection| rejection.as_str()),
+            error: explanation.error,
+            route: explanation
+                .route
+                .map(|route| ControlApiExplainRoutePayload {
+                    name: route.name,
+                    reason: route.reason.to_string(),
+                    matched_path_len: route.matched_path_len,
+                    host_specific: route.host_specific,
+                    route_upstream: route.route_upstream,
+                    upstream: route.upstream,
+                    split: route.split,
+                    canary_percent: route.canary_percent,
+                    action: route.action,
+                }),
+            auth: explanation.auth.map(|auth| ControlApiExplainAuthPayload {
+                api_key: auth.api_key,
+                jwt: auth.jwt,
+                external_auth: auth.external_auth,
+                required_scopes: auth.required_scopes,
+                required_roles: auth.required_roles,
+            }),
+            load_balancing: explanation.balancing.map(|balancing| {
+                ControlApiExplainBalancingPayload {
+                    strategy: balancing.strategy,
+                    key: balancing.key,
+                    key_source: balancing.key_source,
+                    backend: balancing
+                        .backend
+                        .map(|(index, address)| ControlApiExplainBackendPayload { index, address }),
+                    healthy_backends: balancing.healthy_backends,
+                    total_backends: balancing.total_backends,
+                }
+            }),
+            admission: explanation
+                .admission
+                .map(|admission| ControlApiExplainAdmissionPayload {
+                    scope: admission.scope,
+                    priority: admission.priority,
+                    brownout_active: admission.brownout_active,
+                    brownout_allows: admission.brownout_allows,
+                    rate_limits: admission
+                        .rate_limits
+                        .into_iter()
+                        .map(|probe| ControlApiExplainRateLimitPayload {
+                            rule: probe.rule_name,
+                            key: probe.key,
+                            allows_request: probe.allows_request,
+                        })
+                        .collect(),
+                    circuit: admission
+                        .circuit
+                        .map(|circuit| ControlApiExplainCircuitPayload {
+                            state: circuit.state.as_str(),
+                            allows_request: circuit.allows_request,
+                        }),
+                }),
+        }
+    }
+}
+
+impl QUICListener {
+    pub(super) async fn handle_control_api_route_explain(
+        req: Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        match Self::read_control_api_json_body::<ControlApiExplainRequest>(
+            req,
+            MAX_EXPLAIN_BODY_BYTES,
+            "explain request",
+        )
+        .await
+        {
+            Ok(request) => Self::explain_control_api_request(state, &request),
+            Err(err) => Self::json_response(StatusCode::BAD_REQUEST, json!({ "error": err })),
+        }
+    }
+
+    pub(super) fn explain_control_api_request(
+        state: &ControlApiState,
+        request: &ControlApiExplainRequest,
+    ) -> Response<Full<Bytes>> {
+        let method = request.method.trim().to_ascii_uppercase();
+        if method.is_empty() || !request.path.starts_with('/') {
+            return Self::json_response(
+                StatusCode::BAD_REQUEST,
+                json!({ "error": "explain request needs a method and a path starting with '/'" }),
+            );
+        }
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        let header_lookup =
+            |name: &str| -> Option<String> { headers.get(&name.to_ascii_lowercase()).cloned() };
+        let client_ip = request
+            .client_ip
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+        let explain_request = RouteExplainRequest {
+            method: &method,
+            path: &request.path,
+            authority: request.authority.as_deref(),
+            client_addr: SocketAddr::new(client_ip, 0),
+            header_lookup: &header_lookup,
+        };
+
+        let explanation = match request.config_path.as_deref() {
+            Some(config_path) => {
+                let candidate =
+                    Self::load_runtime_config(config_path).and_then(|(_, runtime_config)| {
+                        CandidateExplainRuntime::from_runtime_config(&runtime_config)
+                    });
+                let candidate = match candidate {
+                    Ok(candidate) => candidate,
+                    Err(err) => {
+                        return Self::json_response(
+                            StatusCode::BAD_REQUEST,
+                            json!({
+                                "config": "candidate",
+                                "config_path": config_path,
+                                "error": err,
+                            }),
+                        );
+                    }
+                };
+                Self::explain_route_request(&explain_request, &candidate.explain_runtime())
+            }
+            None => {
+                let runtime = state.current_service_state().runtime;
+                let resilience = runtime.resilience();
+                Self::explain_route_request(
+                    &explain_request,
+                    &RouteExplainRuntime {
+                        routing_index: runtime.routing_index(),
+                        route_policies: runtime.route_policies(),
+                        upstream_pools: runtime.upstream_pools(),
+                        resilience: &resilience,
+                    },
+                )
+            }
+        };
+
+        Self::json_response(
+            StatusCode::OK,
+            ControlApiExplainPayload::new(
+                request.config_path.clone(),
+                ControlApiExplainRequestPayload {
+                    method: method.clone(),
+                    authority: request.authority.clone(),
+                    path: request.path.clone(),
+                    client_ip: client_ip.to_string(),
+                },
+                explanation,
+            ),
+        )
+    }
+}
@@ -1,7 +1,9 @@
+use serde::de::DeserializeOwned;
+
 use super::{state::ControlApiState, *};
 
 impl QUICListener {
-    pub(super) fn handle_control_api_request(
+    pub(super) async fn handle_control_api_request(
         req: Request<Incoming>,
         state: &ControlApiState,
     ) -> Response<http_body_util::Full<bytes::Bytes>> {
@@ -22,6 +24,49 @@ impl QUICListener {
                 Self::handle_control_api_runtime_reload(&req, state)
             }
             super::auth::ControlApiRoute::Restart => Self::handle_control_api_restart(state),
+            super::auth::ControlApiRoute::Rollouts => Self::render_control_api_rollouts(state),
+            super::auth::ControlApiRoute::RolloutCommand => {
+                Self::handle_control_api_rollout_command(&req, state)
+            }
+            super::auth::ControlApiRoute::RouteExplain => {
+                Self::handle_control_api_route_explain(req, state).await
+            }
+            super::auth::ControlApiRoute::Backends => Self::render_control_api_backends(state),
+            super::auth::ControlApiRoute::BackendStatus => {
+                Self::render_control_api_backend_status(&req, state)
+            }
+            super::auth::ControlApiRoute::BackendCommand => {
+                Self::handle_control_api_backend_command(&req, state)
+            }
+            super::auth::ControlApiRoute::BackendUpsert => {
+                Self::handle_control_api_backend_upsert(req, state).await
+            }
+            super::auth::ControlApiRoute::BackendRemove => {
+                Self::handle_control_api_backend_remove(&req, state)
+            }
+        }
+    }
+
+    /// Reads a JSON body of at most `max_bytes`; `what` names it in errors.
+    pub(super) async fn read_control_api_json_body<T: DeserializeOwned>(
+        req: Request<Incoming>,
+        max_bytes: usize,
+        what: &str,
+    ) -> Result<T, String> {
+        use http_body_util::BodyExt as _;
+
+        let mut body = req.into_body();
+        let mut bytes = Vec::new();
+        while let Some(frame) = body.frame().await {
+            let frame = frame.map_err(|err| err.to_string())?;
+            let Ok(chunk) = frame.into_data() else {
+                continue;
+            };
+            if bytes.len().saturating_add(chunk.len()) > max_bytes {
+                return Err(format!("{what} body exceeded {max_bytes} bytes"));
+            }
+            bytes.extend_from_slice(&chunk);
         }
+        serde_json::from_slice(&bytes).map_err(|err| format!("invalid {what}: {err}"))
     }
 }
@@ -8,10 +8,13 @@ use tokio_rustls::TlsAcceptor;
 use super::*;
 
 mod auth;
+mod backends;
 mod context;
+mod explain;
 mod http;
 mod reload;
 mod render;
+mod rollouts;
 mod service;
 mod state;
 
@@ -2,7 +2,11 @@ use bytes::Bytes;
 use http_body_util::Full;
 
 use super::*;
-use crate::runtime::bundle::{ActiveRuntimeGeneration, RuntimeBundleHandle};
+use crate::runtime::{
+    backend::membership::BackendMembershipStore,
+    bundle::{ActiveRuntimeGeneration, RuntimeBundleHandle},
+    generation::RuntimeGenerationState,
+};
 
 pub(super) struct RuntimeReloadPlan {
     pub(super) next_runtime: RuntimeBundle,
@@ -200,18 +204,37 @@ impl QUICListener {
         )
     }
 
-    pub(super) fn build_runtime_reload_plan(
-        current: &ActiveRuntimeGeneration,
-    ) -> Result<RuntimeReloadPlan, String> {
-        let config_path = current.startup().config_path.clone();
-        let config = read_config(&config_path)?;
+    /// Reads, validates and lowers the config file at `config_path`.
+    pub(super) fn load_runtime_config(
+        config_path: &str,
+    ) -> Result<(spooky_config::config::Config, RuntimeConfig), String> {
+        let config = read_config(config_path)?;
         spooky_config::validator::validate(&config)
             .map_err(|err| format!("Configuration validation failed: {err}"))?;
         let runtime_config = RuntimeConfig::from_config(&config)
             .map_err(|err| format!("Runtime configuration normalization failed: {err}"))?;
-        let next_shared_state = QUICListener::build_shared_state(&runtime_config)
-            .map(Arc::new)
-            .map_err(|err| err.to_string())?;
+        Ok((config, runtime_config))
+    }
+
+    pub(super) fn build_runtime_reload_plan(
+        current: &ActiveRuntimeGeneration,
+    ) -> Result<RuntimeReloadPlan, String> {
+        let config_path = current.startup().config_path.clone();
+        let (config, runtime_config) = Self::load_runtime_config(&config_path)?;
+        // Live membership changes are newer than the state file, if any.
+        let backend_membership = BackendMembershipStore::load(
+            runtime_config
+                .observability
+                .control_api
+                .membership_state_file
+                .as_deref(),
+        );
+        backend_membership.inherit_from(&current.state().backend_membership);
+        let next_shared_state =
+            QUICListener::build_shared_state_with_membership(&runtime_config, backend_membership)
+                .map(Arc::new)
+                .map_err(|err| err.to_string())?;
+        Self::carry_over_generation_state(current.state(), next_shared_state.generation_state());
         let current_log_level = current.startup().log_config.level.clone();
         let next_log_level = config.log.level.clone();
 
@@ -230,6 +253,18 @@ impl QUICListener {
         })
     }
 
+    /// Moves operator state that outlives a generation, rollout progress and
+    /// backend drain/disable overrides, onto the one replacing it. Backend
+    /// membership changes are carried into the build itself.
+    pub(super) fn carry_over_generation_state(
+        current: &RuntimeGenerationState,
+        next: &RuntimeGenerationState,
+    ) {
+        next.rollouts.inherit_from(&current.rollouts);
+        next.backend_admin.inherit_from(&current.backend_admin);
+        next.backend_admin.apply(&next.upstream_pools);
+    }
+
     pub(super) fn validate_runtime_reload_plan(
         current: &ActiveRuntimeGeneration,
         next: &RuntimeBundle,
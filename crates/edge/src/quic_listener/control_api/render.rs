@@ -63,6 +63,7 @@ struct ControlApiRuntimeWatchdogPayload {
 #[derive(Serialize)]
 struct ControlApiAdaptiveAdmissionPayload {
     enabled: bool,
+    algorithm: spooky_config::config::AdaptiveAdmissionAlgorithm,
     current_limit: usize,
     inflight_percent: u8,
 }
@@ -91,9 +92,15 @@ struct ControlApiBackendLifecyclePayload {
 struct ControlApiBackendPlacementPayload {
     upstream: String,
     backend_index: usize,
+    backend_id: String,
     healthy: bool,
+    admin_state: &'static str,
     active_requests: usize,
     ewma_latency_ms: Option<f64>,
+    priority: u32,
+    zone: Option<String>,
+    weight: u32,
+    effective_weight: f64,
     membership_epoch: u64,
 }
 
@@ -223,6 +230,7 @@ impl ControlApiRuntimePayload {
             },
             adaptive_admission: ControlApiAdaptiveAdmissionPayload {
                 enabled: resilience.adaptive_admission.enabled(),
+                algorithm: resilience.adaptive_admission.algorithm(),
                 current_limit: resilience.adaptive_admission.current_limit(),
                 inflight_percent: resilience.adaptive_admission.inflight_percent(),
             },
@@ -335,9 +343,15 @@ impl ControlApiBackendPlacementPayload {
         Self {
             upstream: snapshot.upstream_name,
             backend_index: snapshot.backend_index,
+            backend_id: snapshot.backend_id,
             healthy: snapshot.healthy,
+            admin_state: snapshot.admin_state.as_str(),
             active_requests: snapshot.active_requests,
             ewma_latency_ms: snapshot.ewma_latency_ms,
+            priority: snapshot.priority,
+            zone: snapshot.zone,
+            weight: snapshot.weight,
+            effective_weight: snapshot.effective_weight,
             membership_epoch: snapshot.membership_epoch,
         }
     }
@@ -0,0 +1,100 @@
+use bytes::Bytes;
+use http_body_util::Full;
+use serde::Serialize;
+
+use super::{state::ControlApiState, *};
+use crate::rollout::controller::{RolloutCommand, RolloutCommandError, RolloutStatus};
+
+#[derive(Serialize)]
+struct ControlApiRolloutsPayload {
+    rollouts: Vec<ControlApiRolloutPayload>,
+}
+
+#[derive(Serialize)]
+struct ControlApiRolloutPayload {
+    route: String,
+    canary: String,
+    baselines: Vec<String>,
+    phase: &'static str,
+    step: usize,
+    steps: Vec<u32>,
+    canary_percent: u32,
+    last_reason: Option<String>,
+}
+
+impl From<RolloutStatus> for ControlApiRolloutPayload {
+    fn from(status: RolloutStatus) -> Self {
+        Self {
+            route: status.route,
+            canary: status.canary,
+            baselines: status.baselines,
+            phase: status.phase.as_str(),
+            step: status.step,
+            steps: status.steps,
+            canary_percent: status.canary_percent,
+            last_reason: status.last_reason,
+        }
+    }
+}
+
+impl QUICListener {
+    /// Splits `{rollouts_path}/{route}/{command}` into the route name and
+    /// command. Route names may themselves contain `/`.
+    pub(super) fn rollout_command_target<'a>(
+        path: &'a str,
+        rollouts_path: &str,
+    ) -> Option<(&'a str, RolloutCommand)> {
+        let rest = path
+            .strip_prefix(rollouts_path.trim_end_matches('/'))?
+            .strip_prefix('/')?;
+        let (route, command) = rest.rsplit_once('/')?;
+        if route.is_empty() {
+            return None;
+        }
+        Some((route, RolloutCommand::parse(command)?))
+    }
+
+    pub(super) fn render_control_api_rollouts(state: &ControlApiState) -> Response<Full<Bytes>> {
+        let rollouts = state.current_service_state().runtime.rollouts();
+        let payload = ControlApiRolloutsPayload {
+            rollouts: rollouts
+                .statuses()
+                .into_iter()
+                .map(ControlApiRolloutPayload::from)
+                .collect(),
+        };
+        Self::json_response(StatusCode::OK, payload)
+    }
+
+    pub(super) fn handle_control_api_rollout_command(
+        req: &Request<Incoming>,
+        state: &ControlApiState,
+    ) -> Response<Full<Bytes>> {
+        let runtime_state = state.current_service_state();
+        let Some((route, command)) =
+            Self::rollout_command_target(req.uri().path(), &runtime_state.paths.rollouts_path)
+        else {
+            return Self::control_api_not_found_response();
+        };
+
+        match runtime_state.runtime.rollouts().apply(route, command) {
+            Ok(status) => Self::json_response(
+                StatusCode::OK,
+                json!({
+                    "applied": true,
+                    "rollout": ControlApiRolloutPayload::from(status),
+                }),
+            ),
+            Err(err) => Self::json_response(
+                match err {
+                    RolloutCommandError::UnknownRoute(_) => StatusCode::NOT_FOUND,
+                    RolloutCommandError::InvalidPhase { .. } => StatusCode::CONFLICT,
+                },
+                json!({
+                    "applied": false,
+                    "error": err.to_string(),
+                }),
+            ),
+        }
+    }
+}
@@ -109,12 +109,15 @@ impl QUICListener {
                             Ok(listener) => {
                                 info!("Control API endpoint ready bind=https://{}", desired_bind);
                                 info!(
-                                    "Control API endpoint paths bind={} health={} ready={} runtime={} reload_certs={}",
+                                    "Control API endpoint paths bind={} health={} ready={} runtime={} reload_certs={} rollouts={} explain={} backends={}",
                                     desired_bind,
                                     runtime_state.paths.health_path,
                                     runtime_state.paths.ready_path,
                                     runtime_state.paths.runtime_path,
                                     runtime_state.paths.reload_certs_path,
+                                    runtime_state.paths.rollouts_path,
+                                    runtime_state.paths.explain_path,
+                                    runtime_state.paths.backends_path,
                                 );
                                 info!(
                                     "Control API endpoint limits bind={} max_connections={} connection_timeout_ms={}",
@@ -242,7 +245,7 @@ impl QUICListener {
         let io = TokioIo::new(tls_stream);
         let service = service_fn(move |req: Request<Incoming>| {
             let state = state.clone();
-            async move { Ok::<_, hyper::Error>(Self::handle_control_api_request(req, &state)) }
+            async move { Ok::<_, hyper::Error>(Self::handle_control_api_request(req, &state).await) }
         });
 
         let serve = http1::Builder::new().serve_connection(io, service);
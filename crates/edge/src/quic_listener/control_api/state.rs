@@ -9,6 +9,9 @@ pub(super) struct ControlApiPaths {
     pub(super) restart_path: String,
     pub(super) reload_path: String,
     pub(super) reload_certs_path: String,
+    pub(super) rollouts_path: String,
+    pub(super) explain_path: String,
+    pub(super) backends_path: String,
 }
 
 impl ControlApiPaths {
@@ -20,6 +23,9 @@ impl ControlApiPaths {
             restart_path: endpoint.restart_path.clone(),
             reload_path: endpoint.reload_path.clone(),
             reload_certs_path: endpoint.reload_certs_path.clone(),
+            rollouts_path: endpoint.rollouts_path.clone(),
+            explain_path: endpoint.explain_path.clone(),
+            backends_path: endpoint.backends_path.clone(),
         }
     }
 }
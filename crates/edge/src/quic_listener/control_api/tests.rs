@@ -1,4 +1,4 @@
-use std::{collections::HashMap, ffi::OsString, path::Path, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, ffi::OsString, path::Path, sync::Arc};
 
 use http_body_util::BodyExt;
 use log::LevelFilter;
@@ -40,9 +40,19 @@ fn test_config(cert: String, key: String) -> SpookyConfigConfig {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -54,6 +64,8 @@ fn test_config(cert: String, key: String) -> SpookyConfigConfig {
                 address: "http://127.0.0.1:7001".to_string(),
                 weight: 1,
                 health_check: None,
+                priority: 0,
+                zone: None,
             }],
         },
     );
@@ -73,9 +85,14 @@ fn test_config(cert: String, key: String) -> SpookyConfigConfig {
         },
         listeners: vec![],
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -83,6 +100,7 @@ fn test_config(cert: String, key: String) -> SpookyConfigConfig {
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -184,6 +202,32 @@ fn bearer_authorization_rejects_malformed_headers() {
     );
 }
 
+#[test]
+fn rollout_command_target_splits_route_and_command() {
+    use crate::rollout::controller::RolloutCommand;
+
+    assert_eq!(
+        QUICListener::rollout_command_target("/admin/rollouts/checkout/pause", "/admin/rollouts"),
+        Some(("checkout", RolloutCommand::Pause))
+    );
+    assert_eq!(
+        QUICListener::rollout_command_target("/admin/rollouts/api/v2/abort", "/admin/rollouts/"),
+        Some(("api/v2", RolloutCommand::Abort))
+    );
+    assert_eq!(
+        QUICListener::rollout_command_target("/admin/rollouts/checkout/skip", "/admin/rollouts"),
+        None
+    );
+    assert_eq!(
+        QUICListener::rollout_command_target("/admin/rollouts//resume", "/admin/rollouts"),
+        None
+    );
+    assert_eq!(
+        QUICListener::rollout_command_target("/admin/rolloutsx/a/pause", "/admin/rollouts"),
+        None
+    );
+}
+
 #[test]
 fn control_api_state_prefers_reloaded_paths_and_auth_token() {
     let dir = tempdir().expect("tempdir");
@@ -648,3 +692,327 @@ async fn reload_listener_certs_is_atomic_when_any_listener_reload_fails() {
         generations_before
     );
 }
+
+#[tokio::test]
+async fn route_explain_reports_live_route_and_backend_without_advancing_balancer() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_cert_for_name(dir.path(), "server", "api.example.com");
+    let config = test_config(cert, key);
+    let (state, _runtime_handle) =
+        runtime_bundle_control_api_state(runtime_bundle_from_config("live.yaml", &config));
+    let request: super::explain::ControlApiExplainRequest = serde_json::from_value(json!({
+        "path": "/orders",
+        "headers": { "X-Request-Id": "abc" },
+    }))
+    .expect("explain request");
+
+    for _ in 0..2 {
+        let response = QUICListener::explain_control_api_request(&state, &request);
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .expect("collect response body")
+            .to_bytes();
+        let payload: serde_json::Value = serde_json::from_slice(&body).expect("response json");
+        assert_eq!(payload["config"], "live");
+        assert_eq!(payload["request"]["method"], "GET");
+        assert_eq!(payload["rejected_by"], serde_json::Value::Null);
+        assert_eq!(payload["route"]["upstream"], "api");
+        assert_eq!(payload["load_balancing"]["strategy"], "round-robin");
+        assert_eq!(payload["load_balancing"]["backend"]["index"], 0);
+        assert_eq!(payload["admission"]["circuit"]["state"], "closed");
+    }
+}
+
+#[tokio::test]
+async fn route_explain_rejects_unreadable_candidate_config() {
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_cert_for_name(dir.path(), "server", "api.example.com");
+    let config = test_config(cert, key);
+    let (state, _runtime_handle) =
+        runtime_bundle_control_api_state(runtime_bundle_from_config("live.yaml", &config));
+    let missing = dir.path().join("missing.yaml");
+    let request: super::explain::ControlApiExplainRequest = serde_json::from_value(json!({
+        "path": "/",
+        "config_path": missing.to_string_lossy(),
+    }))
+    .expect("explain request");
+
+    let response = QUICListener::explain_control_api_request(&state, &request);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect response body")
+        .to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).expect("response json");
+    assert_eq!(payload["config"], "candidate");
+    assert!(payload["error"].is_string());
+}
+
+#[test]
+fn backend_command_target_splits_upstream_id_and_state() {
+    use spooky_lb::health::BackendAdminState;
+
+    assert_eq!(
+        QUICListener::backend_command_target("/admin/backends/api/b1/drain", "/admin/backends"),
+        Some(("api", Cow::from("b1"), BackendAdminState::Draining))
+    );
+    assert_eq!(
+        QUICListener::backend_command_target(
+            "/admin/backends/api/pod%2F7/enable",
+            "/admin/backends/"
+        ),
+        Some(("api", Cow::from("pod/7"), BackendAdminState::Enabled))
+    );
+    // An unescaped `/` would make `pod/drain/enable` ambiguous.
+    assert_eq!(
+        QUICListener::backend_command_target("/admin/backends/api/pod/7/enable", "/admin/backends"),
+        None
+    );
+    assert_eq!(
+        QUICListener::backend_command_target("/admin/backends/api/disable", "/admin/backends"),
+        None
+    );
+    assert_eq!(
+        QUICListener::backend_command_target("/admin/backends/api/b1/pause", "/admin/backends"),
+        None
+    );
+    assert_eq!(
+        QUICListener::backend_target("/admin/backends/api/b1", "/admin/backends"),
+        Some(("api", Cow::from("b1")))
+    );
+    assert_eq!(
+        QUICListener::backend_target("/admin/backends//b1", "/admin/backends"),
+        None
+    );
+    for malformed in ["b%2", "b%zz", "b%+1", "%FF", "%"] {
+        assert_eq!(
+            QUICListener::backend_target(
+                &format!("/admin/backends/api/{malformed}"),
+                "/admin/backends"
+            ),
+            None,
+            "{malformed}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn backend_drain_reports_drained_and_survives_reload() {
+    use spooky_lb::health::BackendAdminState;
+
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_cert_for_name(dir.path(), "server", "api.example.com");
+    let mut config = test_config(cert, key);
+    let state_file = dir.path().join("backends.json");
+    config.observability.control_api.backend_state_file =
+        Some(state_file.to_string_lossy().to_string());
+    let (state, runtime_handle) =
+        runtime_bundle_control_api_state(runtime_bundle_from_config("live.yaml", &config));
+
+    let pool = state.current_service_state().runtime.upstream_pools()["api"].clone();
+    pool.read()
+        .expect("pool lock")
+        .begin_request_for_accounting(0);
+
+    let response = QUICListener::apply_control_api_backend_command(
+        &state,
+        "api",
+        "b1",
+        BackendAdminState::Draining,
+    );
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect response body")
+        .to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).expect("response json");
+    assert_eq!(payload["changed"], true);
+    assert_eq!(payload["backend"]["admin_state"], "draining");
+    assert_eq!(payload["backend"]["healthy"], false);
+    assert_eq!(payload["backend"]["active_requests"], 1);
+    assert_eq!(payload["backend"]["drained"], false);
+    assert_eq!(pool.write().expect("pool lock").pick("/"), None);
+
+    pool.write()
+        .expect("pool lock")
+        .finish_request(0, Duration::from_millis(1), Some(200));
+    let response = QUICListener::render_control_api_backends(&state);
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect response body")
+        .to_bytes();
+    let payload: serde_json::Value = serde_json::from_slice(&body).expect("response json");
+    assert_eq!(payload["backends"][0]["id"], "b1");
+    assert_eq!(payload["backends"][0]["drained"], true);
+    state
+        .current_service_state()
+        .runtime
+        .backend_admin()
+        .flush();
+    assert!(
+        std::fs::read_to_string(&state_file)
+            .expect("state file")
+            .contains("draining")
+    );
+
+    // A reload keeps the override for a backend that keeps its id, and a
+    // restart reads it back from the state file.
+    let current = runtime_handle.current_view();
+    let reloaded = runtime_bundle_from_config("live.yaml", &config);
+    QUICListener::carry_over_generation_state(
+        current.state(),
+        reloaded.shared_state.generation_state(),
+    );
+    let restarted = runtime_bundle_from_config("live.yaml", &config);
+    for bundle in [reloaded, restarted] {
+        let pool = bundle.shared_state.generation_state().upstream_pools["api"]
+            .read()
+            .expect("pool lock");
+        assert_eq!(
+            pool.backend_admin_state(0),
+            Some(BackendAdminState::Draining)
+        );
+    }
+
+    let response = QUICListener::apply_control_api_backend_command(
+        &state,
+        "api",
+        "missing",
+        BackendAdminState::Disabled,
+    );
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+async fn json_body(response: Response<http_body_util::Full<bytes::Bytes>>) -> serde_json::Value {
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .expect("collect response body")
+        .to_bytes();
+    serde_json::from_slice(&body).expect("response json")
+}
+
+#[tokio::test]
+async fn backend_membership_changes_apply_live_and_survive_reload() {
+    use crate::runtime::backend::membership::BackendMembershipStore;
+
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_cert_for_name(dir.path(), "server", "api.example.com");
+    let mut config = test_config(cert, key);
+    let state_file = dir.path().join("membership.json");
+    config.observability.control_api.membership_state_file =
+        Some(state_file.to_string_lossy().to_string());
+    let (state, runtime_handle) =
+        runtime_bundle_control_api_state(runtime_bundle_from_config("live.yaml", &config));
+    let runtime = state.current_service_state().runtime;
+    let pool = runtime.upstream_pools()["api"].clone();
+
+    let added = Backend {
+        id: "b2".to_string(),
+        address: "http://127.0.0.1:7002".to_string(),
+        weight: 3,
+        health_check: None,
+        priority: 0,
+        zone: Some("zone-a".to_string()),
+    };
+    let response = QUICListener::apply_control_api_backend_upsert(&state, "api", &added);
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let payload = json_body(response).await;
+    assert_eq!(payload["added"], true);
+    assert_eq!(payload["backend"]["id"], "b2");
+    assert_eq!(payload["backend"]["healthy"], true);
+    assert!(
+        runtime
+            .backend_endpoints()
+            .get("http://127.0.0.1:7002")
+            .is_some()
+    );
+    assert!(
+        runtime
+            .transport_pool()
+            .has_backend("http://127.0.0.1:7002")
+    );
+
+    // Another id on an origin already in use is refused.
+    let clash = Backend {
+        id: "b3".to_string(),
+        address: "http://127.0.0.1:7001".to_string(),
+        ..added.clone()
+    };
+    let response = QUICListener::apply_control_api_backend_upsert(&state, "api", &clash);
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let updated = Backend {
+        weight: 5,
+        ..added.clone()
+    };
+    let response = QUICListener::apply_control_api_backend_upsert(&state, "api", &updated);
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["added"], false);
+    assert_eq!(
+        pool.read()
+            .expect("pool lock")
+            .backend_runtime_state(1)
+            .expect("b2")
+            .weight,
+        5
+    );
+
+    let response = QUICListener::apply_control_api_backend_remove(&state, "api", "b1");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        json_body(response).await["removed"]["address"],
+        "http://127.0.0.1:7001"
+    );
+    assert!(
+        !runtime
+            .transport_pool()
+            .has_backend("http://127.0.0.1:7001")
+    );
+    for _ in 0..4 {
+        assert_eq!(pool.write().expect("pool lock").pick("/"), Some(1));
+    }
+    let response = QUICListener::apply_control_api_backend_remove(&state, "api", "b2");
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = QUICListener::apply_control_api_backend_remove(&state, "api", "b1");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = QUICListener::apply_control_api_backend_upsert(&state, "missing", &added);
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // A reload builds on the live changes, and a restart reads them back
+    // from the state file.
+    runtime.backend_membership().flush();
+    let current = runtime_handle.current_view();
+    let backend_membership = BackendMembershipStore::default();
+    backend_membership.inherit_from(&current.state().backend_membership);
+    let runtime_config = RuntimeConfig::from_config(&config).expect("runtime config");
+    let reloaded =
+        QUICListener::build_shared_state_with_membership(&runtime_config, backend_membership)
+            .expect("reloaded state");
+    let restarted = runtime_bundle_from_config("live.yaml", &config);
+    for generation in [
+        reloaded.generation_state(),
+        restarted.shared_state.generation_state(),
+    ] {
+        let pool = generation.upstream_pools["api"].read().expect("pool lock");
+        assert_eq!(pool.backend_count(), 1);
+        assert_eq!(pool.backend_id(0), Some("b2"));
+        assert_eq!(pool.backend_runtime_state(0).expect("b2").weight, 5);
+        assert!(
+            generation
+                .backend_endpoints
+                .get("http://127.0.0.1:7002")
+                .is_some()
+        );
+    }
+}
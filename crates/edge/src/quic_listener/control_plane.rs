@@ -10,7 +10,10 @@ use super::{
     spawn_supervised_async_task,
 };
 use crate::{
-    runtime::{bundle::RuntimeBundleHandle, shared_state::SharedRuntimeState},
+    rollout::{controller::RolloutController, service::run_rollout_service},
+    runtime::{
+        bundle::RuntimeBundleHandle, shared_state::SharedRuntimeState, tasks::RuntimeTaskRegistry,
+    },
     watchdog::{
         config::WatchdogRuntimeConfig,
         service::run_watchdog_service,
@@ -67,6 +70,7 @@ impl QUICListener {
             let task_registry = runtime.generation_tasks();
             Self::spawn_backend_dns_refresh(
                 runtime.runtime_config(),
+                runtime.upstream_pools().clone(),
                 runtime.transport_pool(),
                 runtime.backend_lifecycle(),
                 runtime.backend_dns_resolver(),
@@ -82,6 +86,21 @@ impl QUICListener {
                 runtime.metrics(),
                 Arc::clone(&task_registry),
             );
+            Self::spawn_outlier_detection(
+                runtime.upstream_pools().clone(),
+                runtime.metrics(),
+                Arc::clone(&task_registry),
+            );
+            Self::spawn_adaptive_concurrency_gauges(
+                runtime.resilience(),
+                runtime.metrics(),
+                Arc::clone(&task_registry),
+            );
+            Self::spawn_rollout_controller(
+                runtime.rollouts(),
+                runtime.metrics(),
+                Arc::clone(&task_registry),
+            );
             Self::spawn_watchdog_service(WatchdogServiceCtx::new(runtime, task_registry));
         });
     }
@@ -111,6 +130,32 @@ impl QUICListener {
         watchdog.set_expected_workers(worker_count.max(1));
     }
 
+    fn spawn_rollout_controller(
+        rollouts: Arc<RolloutController>,
+        metrics: Arc<crate::Metrics>,
+        task_registry: Arc<RuntimeTaskRegistry>,
+    ) {
+        if rollouts.is_empty() {
+            return;
+        }
+
+        let handle = match runtime_handle() {
+            Some(handle) => handle,
+            None => {
+                error!("Rollout controller disabled: no Tokio runtime available");
+                return;
+            }
+        };
+
+        let registration = spawn_supervised_async_task(
+            &handle,
+            "rollout_controller",
+            Some(metrics),
+            run_rollout_service(rollouts),
+        );
+        task_registry.register(registration);
+    }
+
     fn spawn_watchdog_service(service_ctx: WatchdogServiceCtx) {
         let spawn_state = WatchdogSpawnState {
             service: WatchdogServiceState {
@@ -476,7 +476,8 @@ impl QUICListener {
                 let _ = observe_admission_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                        route: req.route_name.as_deref().unwrap_or("unrouted"),
+                        priority: req.priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: req.upstream_name.as_deref().unwrap_or("unrouted"),
@@ -490,7 +491,7 @@ impl QUICListener {
                 warn!(
                     "request_id={} route={} external auth denied with status={}",
                     req.request_id,
-                    req.upstream_name.as_deref().unwrap_or("unrouted"),
+                    req.route_name.as_deref().unwrap_or("unrouted"),
                     req.response_status.unwrap_or(0)
                 );
                 Self::send_external_auth_decision_response(h3, quic, stream_id, &decision)?;
@@ -511,7 +512,7 @@ impl QUICListener {
                     debug!(
                         "request_id={} route={} external auth rejected after error: {:?}",
                         req.request_id,
-                        req.upstream_name.as_deref().unwrap_or("unrouted"),
+                        req.route_name.as_deref().unwrap_or("unrouted"),
                         error
                     );
                 }
@@ -519,7 +520,8 @@ impl QUICListener {
                 let _ = observe_admission_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                        route: req.route_name.as_deref().unwrap_or("unrouted"),
+                        priority: req.priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: req.upstream_name.as_deref().unwrap_or("unrouted"),
@@ -544,7 +546,8 @@ impl QUICListener {
                 let _ = observe_admission_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                        route: req.route_name.as_deref().unwrap_or("unrouted"),
+                        priority: req.priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: req.upstream_name.as_deref().unwrap_or("unrouted"),
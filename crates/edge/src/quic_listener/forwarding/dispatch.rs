@@ -1,7 +1,15 @@
+use std::{
+    future::{Future, poll_fn},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use spooky_config::runtime::{RuntimeFaultSample, RuntimeRetryPolicy};
 use spooky_errors::{
     HedgeOutcomeTelemetryReason, HedgePolicyDecision, HedgePolicyFacts, HedgePrimaryState,
-    RetryPolicyDecision, RetryPolicyFacts, evaluate_hedge_policy, evaluate_retry_policy,
-    is_idempotent_method,
+    RetryErrorConditions, RetryPolicyDecision, RetryPolicyDenialReason, RetryPolicyFacts,
+    UpstreamRetryReason, UpstreamRetryability, classify_retryability_for, evaluate_hedge_policy,
+    evaluate_retry_policy, is_idempotent_method,
 };
 use spooky_lb::alternate_backend::{
     AlternateBackendDecision, AlternateBackendFailureReason, choose_alternate_backend,
@@ -16,6 +24,7 @@ type UpstreamRequest = Request<BoxBody<Bytes, Infallible>>;
 #[derive(Clone, Debug)]
 enum ResolvedAlternateBackend {
     Selected {
+        index: usize,
         address: String,
     },
     Unavailable {
@@ -36,7 +45,7 @@ impl ResolvedAlternateBackend {
     }
 }
 
-struct AlternateBodylessCandidate {
+struct AlternateCandidate {
     backend: String,
     request: UpstreamRequest,
 }
@@ -47,8 +56,35 @@ struct RetryExecutionCtx<'a> {
     policy: ForwardingRetryHedgePolicy,
     policy_telemetry: &'a mut ForwardingPolicyTelemetry,
     retry_budget: &'a crate::resilience::retry_budget::RetryBudget,
-    alternate_backend: Option<&'a ResolvedAlternateBackend>,
-    backend_endpoints: &'a HashMap<String, BackendEndpoint>,
+    alternate_backend: Option<ResolvedAlternateBackend>,
+    upstream_pool: Option<&'a Arc<RwLock<UpstreamPool>>>,
+    max_attempts: u8,
+    retry: &'a RuntimeRetryPolicy,
+    replay: Option<&'a ReplayBody>,
+    result_tx: &'a oneshot::Sender<UpstreamResult>,
+    backend_timeout: Duration,
+    backend_endpoints: &'a BackendEndpoints,
+    pending_forward: &'a PendingForward,
+    circuit_breakers: Arc<crate::resilience::circuit_breaker::CircuitBreakers>,
+    transport: Arc<UpstreamTransportPool>,
+}
+
+struct HedgeExecutionCtx<'a> {
+    request_id: u64,
+    route_name: &'a str,
+    policy: ForwardingRetryHedgePolicy,
+    policy_telemetry: &'a mut ForwardingPolicyTelemetry,
+    retry_budget: &'a crate::resilience::retry_budget::RetryBudget,
+    hedging: &'a crate::resilience::hedging::HedgeController,
+    /// Delay before the first hedge and between later ones.
+    delay: Duration,
+    retry: &'a RuntimeRetryPolicy,
+    alternate_backend: Option<ResolvedAlternateBackend>,
+    upstream_pool: Option<&'a Arc<RwLock<UpstreamPool>>>,
+    replay: Option<&'a ReplayBody>,
+    result_tx: &'a oneshot::Sender<UpstreamResult>,
+    backend_timeout: Duration,
+    backend_endpoints: &'a BackendEndpoints,
     pending_forward: &'a PendingForward,
     circuit_breakers: Arc<crate::resilience::circuit_breaker::CircuitBreakers>,
     transport: Arc<UpstreamTransportPool>,
@@ -56,8 +92,9 @@ struct RetryExecutionCtx<'a> {
 
 #[derive(Clone, Copy)]
 struct ForwardingRetryHedgePolicy {
+    retry_conditions: RetryErrorConditions,
     method_idempotent: bool,
-    bodyless_mode: bool,
+    body_replayable: bool,
     hedge_method_allowed: bool,
     hedge_configured: bool,
     hedge_tunnel_request: bool,
@@ -65,15 +102,17 @@ struct ForwardingRetryHedgePolicy {
 
 impl ForwardingRetryHedgePolicy {
     fn new(
+        retry_conditions: RetryErrorConditions,
         method_idempotent: bool,
-        bodyless_mode: bool,
+        body_replayable: bool,
         hedge_method_allowed: bool,
         hedge_configured: bool,
         hedge_tunnel_request: bool,
     ) -> Self {
         Self {
+            retry_conditions,
             method_idempotent,
-            bodyless_mode,
+            body_replayable,
             hedge_method_allowed,
             hedge_configured,
             hedge_tunnel_request,
@@ -89,48 +128,66 @@ impl ForwardingRetryHedgePolicy {
         evaluate_hedge_policy(HedgePolicyFacts {
             hedging_configured: self.hedge_configured,
             method_allowed: self.hedge_method_allowed,
-            request_body_replayable: self.bodyless_mode,
+            request_body_replayable: self.body_replayable,
             tunnel_request: self.hedge_tunnel_request,
             alternate_backend_available,
             alternate_backend_failure,
+            hedge_share_available: true,
             budget_available: false,
             primary_state: HedgePrimaryState::InFlightBeforeDelay,
         })
     }
 
-    fn hedge_after_delay(self, budget_available: bool) -> HedgePolicyDecision {
+    /// `body_ready` is false while a replayable body is still uploading.
+    fn hedge_after_delay(
+        self,
+        hedge_share_available: bool,
+        budget_available: bool,
+        body_ready: bool,
+    ) -> HedgePolicyDecision {
         evaluate_hedge_policy(HedgePolicyFacts {
             hedging_configured: self.hedge_configured,
             method_allowed: self.hedge_method_allowed,
-            request_body_replayable: self.bodyless_mode,
+            request_body_replayable: body_ready,
             tunnel_request: self.hedge_tunnel_request,
             alternate_backend_available: true,
             alternate_backend_failure: None,
+            hedge_share_available,
             budget_available,
             primary_state: HedgePrimaryState::InFlightAfterDelay,
         })
     }
 
-    fn retry_after_error(
+    /// `spend_budget` is only called once every other check allows the
+    /// retry, so denied retries never consume the retry budget.
+    fn retry_after_failure(
         self,
-        primary_err: &ProxyError,
+        retryability: UpstreamRetryability,
         retry_count: u8,
         max_attempts: u8,
-        budget_available: bool,
         alternate_backend: Option<&ResolvedAlternateBackend>,
+        spend_budget: impl FnOnce() -> bool,
     ) -> RetryPolicyDecision {
         let (alternate_backend_available, alternate_backend_failure) =
             alternate_backend_policy_state(alternate_backend);
-        evaluate_retry_policy(RetryPolicyFacts {
-            retryability: spooky_errors::classify_retryability(primary_err),
+        match evaluate_retry_policy(RetryPolicyFacts {
+            retryability,
+            conditions: self.retry_conditions,
             method_idempotent: self.method_idempotent,
-            request_body_replayable: self.bodyless_mode,
+            request_body_replayable: self.body_replayable,
             attempt_count: retry_count,
             max_attempts,
-            budget_available,
+            budget_available: true,
             alternate_backend_available,
             alternate_backend_failure,
-        })
+        }) {
+            RetryPolicyDecision::Retry { .. } if !spend_budget() => {
+                RetryPolicyDecision::DoNotRetry {
+                    denial: Some(RetryPolicyDenialReason::BudgetDenied),
+                }
+            }
+            decision => decision,
+        }
     }
 }
 
@@ -152,10 +209,43 @@ fn retry_budget_available_for_error(
         || retry_budget.allow_retry(route_name).is_ok()
 }
 
+/// Outcome of one upstream attempt: a response whose status may still be
+/// listed in the route's `retry_on`, or a failure before headers.
+type AttemptOutcome = Result<Response<Incoming>, ProxyError>;
+type AttemptFuture = Pin<Box<dyn Future<Output = AttemptOutcome> + Send>>;
+
+/// Polls the racing attempts of a hedged request and removes the first one
+/// to finish, returned with its attempt number (0 for the primary).
+fn poll_first_attempt(
+    attempts: &mut Vec<(u8, AttemptFuture)>,
+    cx: &mut Context<'_>,
+) -> Poll<(u8, AttemptOutcome)> {
+    for index in 0..attempts.len() {
+        if let Poll::Ready(outcome) = attempts[index].1.as_mut().poll(cx) {
+            let (attempt, _) = attempts.swap_remove(index);
+            return Poll::Ready((attempt, outcome));
+        }
+    }
+    Poll::Pending
+}
+
+/// `Retry-After` of a 503 response, when it carries delta-seconds.
+fn retry_after_delay(response: &Response<Incoming>) -> Option<Duration> {
+    if response.status() != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+    response
+        .headers()
+        .get(http::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RuntimeRetryPolicy::parse_retry_after)
+}
+
 impl QUICListener {
     async fn send_upstream_request(
         backend: String,
         request: UpstreamRequest,
+        backend_timeout: Duration,
         circuit_breakers: Arc<crate::resilience::circuit_breaker::CircuitBreakers>,
         transport: Arc<UpstreamTransportPool>,
     ) -> Result<Response<Incoming>, ProxyError> {
@@ -163,7 +253,9 @@ impl QUICListener {
             return Err(ProxyError::Pool(PoolError::CircuitOpen(backend)));
         }
 
-        let send_result = transport.send_backend_request(&backend, request).await;
+        let send_result = transport
+            .send_backend_request_with_timeout(&backend, request, backend_timeout)
+            .await;
         match &send_result {
             Ok(_) => circuit_breakers.record_success(&backend),
             _ => circuit_breakers.record_failure(&backend),
@@ -171,6 +263,38 @@ impl QUICListener {
         send_result
     }
 
+    /// Sends the primary attempt after any injected fault delay. A delay that
+    /// reaches the backend timeout fails the attempt as a timeout, counted by
+    /// the circuit breaker like a real one.
+    async fn send_primary_request(
+        backend: String,
+        request: UpstreamRequest,
+        fault_delay: Option<Duration>,
+        backend_timeout: Duration,
+        circuit_breakers: Arc<crate::resilience::circuit_breaker::CircuitBreakers>,
+        transport: Arc<UpstreamTransportPool>,
+    ) -> Result<Response<Incoming>, ProxyError> {
+        if let Some(delay) = fault_delay {
+            if delay >= backend_timeout {
+                tokio::time::sleep(backend_timeout).await;
+                if !circuit_breakers.allow_request(&backend) {
+                    return Err(ProxyError::Pool(PoolError::CircuitOpen(backend)));
+                }
+                circuit_breakers.record_failure(&backend);
+                return Err(ProxyError::Timeout);
+            }
+            tokio::time::sleep(delay).await;
+        }
+        Self::send_upstream_request(
+            backend,
+            request,
+            backend_timeout,
+            circuit_breakers,
+            transport,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn forward_http1_websocket_tunnel(
         endpoint: BackendEndpoint,
@@ -216,6 +340,7 @@ impl QUICListener {
                 status,
                 headers,
                 body: response.into_body(),
+                truncate_after_bytes: None,
             });
         }
 
@@ -280,17 +405,18 @@ impl QUICListener {
 
     fn resolve_alternate_backend(
         upstream_pool: &Arc<RwLock<UpstreamPool>>,
-        primary_index: usize,
+        excluded_indices: &[usize],
     ) -> ResolvedAlternateBackend {
         let Ok(pool) = upstream_pool.read() else {
             return ResolvedAlternateBackend::Unavailable {
                 reason: AlternateBackendFailureReason::PoolUnavailable,
             };
         };
-        match choose_alternate_backend(&pool, &[primary_index], None) {
+        match choose_alternate_backend(&pool, excluded_indices, None) {
             AlternateBackendDecision::Select(choice) => {
                 if let Some(address) = pool.backend_address(choice.index) {
                     ResolvedAlternateBackend::Selected {
+                        index: choice.index,
                         address: address.to_string(),
                     }
                 } else {
@@ -305,16 +431,17 @@ impl QUICListener {
         }
     }
 
-    fn build_alternate_bodyless_candidate(
+    fn build_alternate_candidate(
         alternate_backend: Option<&ResolvedAlternateBackend>,
-        backend_endpoints: &HashMap<String, BackendEndpoint>,
+        backend_endpoints: &BackendEndpoints,
         pending_forward: &PendingForward,
-    ) -> Option<AlternateBodylessCandidate> {
+        body: Bytes,
+    ) -> Option<AlternateCandidate> {
         match alternate_backend? {
-            ResolvedAlternateBackend::Selected { address } => {
+            ResolvedAlternateBackend::Selected { address, .. } => {
                 let endpoint = backend_endpoints.get(address)?;
-                let request = pending_forward.build_bodyless_request(endpoint).ok()?;
-                Some(AlternateBodylessCandidate {
+                let request = pending_forward.build_replay_request(&endpoint, body).ok()?;
+                Some(AlternateCandidate {
                     backend: address.clone(),
                     request,
                 })
@@ -323,67 +450,288 @@ impl QUICListener {
         }
     }
 
-    async fn retry_primary_error(
-        primary_err: ProxyError,
+    /// Races the primary against up to `max_hedges` hedges, each sent to a
+    /// backend not tried yet one `delay` after the previous attempt. The
+    /// first response wins and the losers are dropped; a failed attempt or a
+    /// response with a `retry_on` status leaves the others racing. Once none
+    /// is left, the last such response, or else the last failure, is
+    /// returned. Once a hedge is denied, no later one is tried.
+    async fn race_hedged_attempts(
+        primary: AttemptFuture,
+        hedge_ctx: HedgeExecutionCtx<'_>,
+    ) -> AttemptOutcome {
+        let HedgeExecutionCtx {
+            request_id,
+            route_name,
+            policy,
+            policy_telemetry,
+            retry_budget,
+            hedging,
+            delay,
+            retry,
+            mut alternate_backend,
+            upstream_pool,
+            replay,
+            result_tx,
+            backend_timeout,
+            backend_endpoints,
+            pending_forward,
+            circuit_breakers,
+            transport,
+        } = hedge_ctx;
+        let primary_started = Instant::now();
+        let mut attempts = vec![(0, primary)];
+        let mut tried_indices = vec![pending_forward.backend_index];
+        let mut hedges = 0u8;
+        let mut hedging_open = true;
+        let mut retryable_response = None;
+        let next_hedge = tokio::time::sleep(delay);
+        tokio::pin!(next_hedge);
+        loop {
+            let finished = tokio::select! {
+                finished = poll_fn(|cx| poll_first_attempt(&mut attempts, cx)) => Some(finished),
+                _ = &mut next_hedge, if hedging_open => None,
+            };
+            match finished {
+                Some((_, Ok(response)))
+                    if retry.retries_status(response.status().as_u16()) && !attempts.is_empty() =>
+                {
+                    retryable_response = Some(response);
+                    continue;
+                }
+                Some((attempt, Ok(response))) => {
+                    if attempt > 0 {
+                        policy_telemetry
+                            .hedge
+                            .record_outcome(HedgeOutcomeTelemetryReason::HedgeWon { attempt });
+                        let elapsed_ms = primary_started.elapsed().as_millis() as u64;
+                        policy_telemetry.hedge.observe_primary_late_ms(
+                            elapsed_ms.saturating_sub(delay.as_millis() as u64),
+                        );
+                    } else if hedges > 0 {
+                        policy_telemetry
+                            .hedge
+                            .record_outcome(HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger);
+                    }
+                    return Ok(response);
+                }
+                Some((_, Err(err))) => {
+                    if attempts.is_empty() {
+                        return retryable_response.ok_or(err);
+                    }
+                    continue;
+                }
+                None => {}
+            }
+
+            let hedge_body = replay
+                .and_then(ReplayBody::ready)
+                .filter(|_| !result_tx.is_closed());
+            let hedge_share_available = hedges > 0 || hedging.try_hedge(route_name);
+            let hedge_decision = policy.hedge_after_delay(
+                hedge_share_available,
+                hedge_share_available
+                    && retry_budget_available_for_error(
+                        &ProxyError::Timeout,
+                        route_name,
+                        retry_budget,
+                    ),
+                hedge_body.is_some(),
+            );
+            let hedge_candidate = match hedge_decision {
+                HedgePolicyDecision::Hedge { .. } => hedge_body.and_then(|body| {
+                    Self::build_alternate_candidate(
+                        alternate_backend.as_ref(),
+                        backend_endpoints,
+                        pending_forward,
+                        body,
+                    )
+                }),
+                _ => None,
+            };
+            match (hedge_decision, hedge_candidate) {
+                (
+                    HedgePolicyDecision::Hedge { reason },
+                    Some(AlternateCandidate { backend, request }),
+                ) => {
+                    policy_telemetry.hedge.record_trigger(reason);
+                    hedges += 1;
+                    attempts.push((
+                        hedges,
+                        Box::pin(Self::send_upstream_request(
+                            backend,
+                            request,
+                            backend_timeout,
+                            Arc::clone(&circuit_breakers),
+                            Arc::clone(&transport),
+                        )),
+                    ));
+                    if let Some(ResolvedAlternateBackend::Selected { index, .. }) =
+                        &alternate_backend
+                    {
+                        tried_indices.push(*index);
+                    }
+                    hedging_open = hedges < hedging.max_hedges();
+                    if hedging_open {
+                        alternate_backend = upstream_pool.map(|upstream_pool| {
+                            Self::resolve_alternate_backend(upstream_pool, &tried_indices)
+                        });
+                        next_hedge
+                            .as_mut()
+                            .reset(tokio::time::Instant::now() + delay);
+                    }
+                }
+                (HedgePolicyDecision::DoNotHedge { denial }, _) => {
+                    debug!(
+                        "request_id={} hedge suppressed after delay: route={} reason={:?}",
+                        request_id, route_name, denial
+                    );
+                    hedging_open = false;
+                }
+                _ => hedging_open = false,
+            }
+        }
+    }
+
+    /// Retries a failed primary on alternate backends until the policy
+    /// denies, each attempt excluding every backend already tried. A response
+    /// is retried only when its status is listed in `retry_on`; when retries
+    /// stop, the last response or error is returned as is. Buffered bodies are
+    /// resent once the client has finished uploading them.
+    async fn retry_failed_attempt(
+        primary: AttemptOutcome,
         retry_ctx: RetryExecutionCtx<'_>,
-    ) -> Result<Response<Incoming>, ProxyError> {
+    ) -> AttemptOutcome {
         let RetryExecutionCtx {
             request_id,
             route_name,
             policy,
             policy_telemetry,
             retry_budget,
-            alternate_backend,
+            mut alternate_backend,
+            upstream_pool,
+            max_attempts,
+            retry,
+            replay,
+            result_tx,
+            backend_timeout,
             backend_endpoints,
             pending_forward,
             circuit_breakers,
             transport,
         } = retry_ctx;
-        let retry_decision = policy.retry_after_error(
-            &primary_err,
-            policy_telemetry.retry.count,
-            MAX_UPSTREAM_RETRY_ATTEMPTS,
-            retry_budget_available_for_error(&primary_err, route_name, retry_budget),
-            alternate_backend,
-        );
-        let retry_reason = match retry_decision {
-            RetryPolicyDecision::Retry { reason } => reason.into(),
-            RetryPolicyDecision::DoNotRetry { denial } => {
-                policy_telemetry.retry.record_denial(denial);
+        let mut tried_indices = vec![pending_forward.backend_index];
+        let mut last = primary;
+        loop {
+            let (retryability, retry_after) = match &last {
+                Ok(response) => (
+                    UpstreamRetryability::Retryable(UpstreamRetryReason::Status),
+                    retry_after_delay(response),
+                ),
+                Err(err) => (
+                    classify_retryability_for(err, policy.retry_conditions),
+                    None,
+                ),
+            };
+            let retry_decision = policy.retry_after_failure(
+                retryability,
+                policy_telemetry.retry.count,
+                max_attempts,
+                alternate_backend.as_ref(),
+                || match &last {
+                    Ok(_) => retry_budget.allow_retry(route_name).is_ok(),
+                    Err(err) => retry_budget_available_for_error(err, route_name, retry_budget),
+                },
+            );
+            let retry_reason = match retry_decision {
+                RetryPolicyDecision::Retry { reason } => reason.into(),
+                RetryPolicyDecision::DoNotRetry { denial } => {
+                    policy_telemetry.retry.record_denial(denial);
+                    debug!(
+                        "request_id={} retry denied: route={} reason={:?}",
+                        request_id, route_name, denial
+                    );
+                    return last;
+                }
+            };
+
+            // A stream that gave up may have cut the upload short, so its
+            // body is never resent.
+            let body = match replay {
+                Some(replay) if !result_tx.is_closed() => replay.wait().await,
+                _ => None,
+            };
+            let Some(body) = body.filter(|_| !result_tx.is_closed()) else {
+                let denial = RetryPolicyDenialReason::RequestBodyNotReplayable;
+                policy_telemetry.retry.record_denial(Some(denial));
                 debug!(
                     "request_id={} retry denied: route={} reason={:?}",
                     request_id, route_name, denial
                 );
-                return Err(primary_err);
-            }
-        };
+                return last;
+            };
+            let Some(AlternateCandidate {
+                backend: retry_backend,
+                request: retry_request,
+            }) = Self::build_alternate_candidate(
+                alternate_backend.as_ref(),
+                backend_endpoints,
+                pending_forward,
+                body,
+            )
+            else {
+                return last;
+            };
 
-        let Some(AlternateBodylessCandidate {
-            backend: retry_backend,
-            request: retry_request,
-        }) = Self::build_alternate_bodyless_candidate(
-            alternate_backend,
-            backend_endpoints,
-            pending_forward,
-        )
-        else {
-            return Err(primary_err);
-        };
+            policy_telemetry.retry.record_attempt(retry_reason);
+            let wait = match retry_after {
+                Some(delay) => delay.min(retry.retry_after_max),
+                None => retry.backoff(policy_telemetry.retry.count, rand::random::<u64>()),
+            };
+            info!(
+                "request_id={} retrying request on alternate backend: route={} attempt={} reason={:?} wait_ms={}",
+                request_id,
+                route_name,
+                policy_telemetry.retry.count,
+                retry_reason,
+                wait.as_millis()
+            );
+            drop(last);
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+            last = Self::send_upstream_request(
+                retry_backend,
+                retry_request,
+                backend_timeout,
+                Arc::clone(&circuit_breakers),
+                Arc::clone(&transport),
+            )
+            .await;
+            if let Ok(response) = &last
+                && !retry.retries_status(response.status().as_u16())
+            {
+                return last;
+            }
 
-        policy_telemetry.retry.record_attempt(retry_reason);
-        info!(
-            "request_id={} retrying request on alternate backend: route={} reason={:?}",
-            request_id, route_name, retry_reason
-        );
-        Self::send_upstream_request(retry_backend, retry_request, circuit_breakers, transport).await
+            if let Some(ResolvedAlternateBackend::Selected { index, .. }) = &alternate_backend {
+                tried_indices.push(*index);
+            }
+            alternate_backend = upstream_pool.map(|upstream_pool| {
+                Self::resolve_alternate_backend(upstream_pool, &tried_indices)
+            });
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn spawn_upstream_forward_task(
         req: &RequestEnvelope,
         pending_forward: Arc<PendingForward>,
         backend_endpoint: BackendEndpoint,
         request: Option<UpstreamRequest>,
         websocket_tunnel_body_rx: Option<mpsc::Receiver<Bytes>>,
+        replay: Option<ReplayBody>,
+        faults: RuntimeFaultSample,
         exec_ctx: &ForwardingExecutionCtx<'_>,
         shared_ctx: &ForwardingSharedCtx<'_>,
     ) -> Result<oneshot::Receiver<UpstreamResult>, ProxyError> {
@@ -392,27 +740,46 @@ impl QUICListener {
         let fwd_addr = pending_forward.backend_addr.to_string();
         let cb = Arc::clone(&resilience.circuit_breakers);
         let retry_budget = Arc::clone(&resilience.retry_budget);
-        let route_name = pending_forward.upstream_name.to_string();
-        let backend_timeout = exec_ctx.backend_timeout;
+        let route_name = pending_forward.route_name.to_string();
+        let limits = &pending_forward.limits;
+        let backend_timeout = limits.backend_timeout.unwrap_or(exec_ctx.backend_timeout);
         let backend_endpoints = Arc::clone(&exec_ctx.backend_endpoints);
         let transport = Arc::clone(&exec_ctx.transport_pool);
-        let hedge_delay = resilience.hedging_delay;
-        let alternate_backend = req.upstream_pool.as_ref().map(|upstream_pool| {
-            Self::resolve_alternate_backend(upstream_pool, pending_forward.backend_index)
+        let max_retry_attempts = limits
+            .max_retry_attempts
+            .unwrap_or(MAX_UPSTREAM_RETRY_ATTEMPTS);
+        let retry = limits.retry.clone();
+        let upstream_pool = req.upstream_pool.clone();
+        let alternate_backend = upstream_pool.as_ref().map(|upstream_pool| {
+            Self::resolve_alternate_backend(upstream_pool, &[pending_forward.backend_index])
         });
         let trace_span_for_upstream = req.trace_span.clone();
         let pending_forward_for_upstream = Arc::clone(&pending_forward);
         let (result_tx, result_rx) = oneshot::channel::<UpstreamResult>();
         let tunnel_mode = req.tunnel_mode;
-        let bodyless_mode = req.bodyless_mode;
         let request_id = req.request_id;
-        let method_idempotent = is_idempotent_method(&req.method);
-        let hedge_method_allowed = resilience.hedging_method_allowed(&req.method);
+        let admitted_by_key = replay.as_ref().is_some_and(ReplayBody::admitted_by_key);
+        let method_idempotent = is_idempotent_method(&req.method) || admitted_by_key;
+        let hedge_method_allowed =
+            resilience.hedging_method_allowed(&req.method) || admitted_by_key;
         let hedge_configured = resilience.hedging_route_enabled_for(&route_name);
+        let hedging = Arc::clone(&resilience.hedging);
+        let hedge_delay = if hedge_configured {
+            let delay = hedging.start_request(&route_name, Instant::now(), || {
+                metrics.route_health_sample(&route_name)
+            });
+            limits.hedging_delay.unwrap_or(delay)
+        } else {
+            Duration::ZERO
+        };
         let hedge_tunnel_request = req.tunnel_mode != TunnelMode::None;
         let policy = ForwardingRetryHedgePolicy::new(
+            RetryErrorConditions {
+                reset_before_headers: retry.reset_before_headers,
+                connect_failure: retry.connect_failure,
+            },
             method_idempotent,
-            bodyless_mode,
+            replay.is_some(),
             hedge_method_allowed,
             hedge_configured,
             hedge_tunnel_request,
@@ -444,191 +811,108 @@ impl QUICListener {
                             "missing upstream request for non-websocket forward".into(),
                         )
                     })?;
-                    let response: Response<Incoming> = match policy
-                        .hedge_before_delay(alternate_backend.as_ref())
-                    {
+                    let primary = match policy.hedge_before_delay(alternate_backend.as_ref()) {
                         HedgePolicyDecision::WaitForPrimary => {
-                            let hedge_candidate = Self::build_alternate_bodyless_candidate(
-                                alternate_backend.as_ref(),
-                                backend_endpoints.as_ref(),
-                                pending_forward_for_upstream.as_ref(),
-                            );
-
-                            if let Some(AlternateBodylessCandidate {
-                                backend: hedge_backend,
-                                request: hedge_request,
-                            }) = hedge_candidate
-                            {
-                            let primary_started = Instant::now();
-                            let primary_backend = fwd_addr.clone();
-                            let primary_fut = Self::send_upstream_request(
-                                primary_backend,
+                            let primary: AttemptFuture = Box::pin(Self::send_primary_request(
+                                fwd_addr.clone(),
                                 request,
+                                faults.delay,
+                                backend_timeout,
                                 Arc::clone(&cb),
                                 Arc::clone(&transport),
-                            );
-                            tokio::pin!(primary_fut);
-                            let hedge_sleep = tokio::time::sleep(hedge_delay);
-                            tokio::pin!(hedge_sleep);
-
-                            if let Some(result) = tokio::select! {
-                                result = &mut primary_fut => Some(result),
-                                _ = &mut hedge_sleep => None,
-                            } {
-                                result?
-                            } else {
-                                match policy.hedge_after_delay(
-                                    retry_budget_available_for_error(
-                                        &ProxyError::Timeout,
-                                        &route_name,
-                                        retry_budget.as_ref(),
-                                    ),
-                                ) {
-                                    HedgePolicyDecision::Hedge { reason } => {
-                                        policy_telemetry.hedge.record_trigger(reason);
-                                        let hedge_fut = Self::send_upstream_request(
-                                            hedge_backend,
-                                            hedge_request,
-                                            Arc::clone(&cb),
-                                            Arc::clone(&transport),
-                                        );
-                                        tokio::pin!(hedge_fut);
-                                        tokio::select! {
-                                            result = &mut primary_fut => {
-                                                match result {
-                                                    Ok(response) => {
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .record_outcome(HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger);
-                                                        response
-                                                    }
-                                                    Err(_primary_err) => {
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .record_outcome(HedgeOutcomeTelemetryReason::HedgeWon);
-                                                        let elapsed_ms = primary_started.elapsed().as_millis() as u64;
-                                                        let delay_ms = hedge_delay.as_millis() as u64;
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .observe_primary_late_ms(elapsed_ms.saturating_sub(delay_ms));
-                                                        hedge_fut.await?
-                                                    }
-                                                }
-                                            },
-                                            result = &mut hedge_fut => {
-                                                match result {
-                                                    Ok(response) => {
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .record_outcome(HedgeOutcomeTelemetryReason::HedgeWon);
-                                                        let elapsed_ms = primary_started.elapsed().as_millis() as u64;
-                                                        let delay_ms = hedge_delay.as_millis() as u64;
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .observe_primary_late_ms(elapsed_ms.saturating_sub(delay_ms));
-                                                        response
-                                                    }
-                                                    Err(_hedge_err) => {
-                                                        policy_telemetry
-                                                            .hedge
-                                                            .record_outcome(HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger);
-                                                        primary_fut.await?
-                                                    }
-                                                }
-                                            },
-                                        }
-                                    }
-                                    HedgePolicyDecision::WaitForPrimary => primary_fut.await?,
-                                    HedgePolicyDecision::DoNotHedge { denial } => {
-                                        debug!(
-                                            "request_id={} hedge suppressed after delay: route={} reason={:?}",
-                                            request_id, route_name, denial
-                                        );
-                                        primary_fut.await?
-                                    }
-                                }
-                            }
-                            } else {
-                                Self::send_upstream_request(
-                                    fwd_addr.clone(),
-                                    request,
-                                    Arc::clone(&cb),
-                                    Arc::clone(&transport),
-                                )
-                                .await?
-                            }
+                            ));
+                            Self::race_hedged_attempts(
+                                primary,
+                                HedgeExecutionCtx {
+                                    request_id,
+                                    route_name: &route_name,
+                                    policy,
+                                    policy_telemetry: &mut policy_telemetry,
+                                    retry_budget: retry_budget.as_ref(),
+                                    hedging: hedging.as_ref(),
+                                    delay: hedge_delay,
+                                    retry: &retry,
+                                    alternate_backend: alternate_backend.clone(),
+                                    upstream_pool: upstream_pool.as_ref(),
+                                    replay: replay.as_ref(),
+                                    result_tx: &result_tx,
+                                    backend_timeout,
+                                    backend_endpoints: backend_endpoints.as_ref(),
+                                    pending_forward: pending_forward_for_upstream.as_ref(),
+                                    circuit_breakers: Arc::clone(&cb),
+                                    transport: Arc::clone(&transport),
+                                },
+                            )
+                            .await
                         }
                         HedgePolicyDecision::DoNotHedge { denial } => {
                             debug!(
                                 "request_id={} hedging disabled for request: route={} reason={:?}",
                                 request_id, route_name, denial
                             );
-                            match Self::send_upstream_request(
+                            Self::send_primary_request(
                                 fwd_addr.clone(),
                                 request,
+                                faults.delay,
+                                backend_timeout,
                                 Arc::clone(&cb),
                                 Arc::clone(&transport),
                             )
                             .await
-                            {
-                                Ok(response) => response,
-                                Err(primary_err) => Self::retry_primary_error(
-                                        primary_err,
-                                        RetryExecutionCtx {
-                                            request_id,
-                                            route_name: &route_name,
-                                            policy,
-                                            policy_telemetry: &mut policy_telemetry,
-                                            retry_budget: retry_budget.as_ref(),
-                                            alternate_backend: alternate_backend.as_ref(),
-                                            backend_endpoints: backend_endpoints.as_ref(),
-                                            pending_forward: pending_forward_for_upstream.as_ref(),
-                                            circuit_breakers: Arc::clone(&cb),
-                                            transport: Arc::clone(&transport),
-                                        },
-                                    )
-                                    .await?,
-                            }
                         }
                         HedgePolicyDecision::Hedge { reason } => {
                             debug!(
                                 "request_id={} shared hedge policy triggered early: route={} reason={:?}",
                                 request_id, route_name, reason
                             );
-                            match Self::send_upstream_request(
+                            Self::send_primary_request(
                                 fwd_addr.clone(),
                                 request,
+                                faults.delay,
+                                backend_timeout,
                                 Arc::clone(&cb),
                                 Arc::clone(&transport),
                             )
                             .await
-                            {
-                                Ok(response) => response,
-                                Err(primary_err) => Self::retry_primary_error(
-                                        primary_err,
-                                        RetryExecutionCtx {
-                                            request_id,
-                                            route_name: &route_name,
-                                            policy,
-                                            policy_telemetry: &mut policy_telemetry,
-                                            retry_budget: retry_budget.as_ref(),
-                                            alternate_backend: alternate_backend.as_ref(),
-                                            backend_endpoints: backend_endpoints.as_ref(),
-                                            pending_forward: pending_forward_for_upstream.as_ref(),
-                                            circuit_breakers: Arc::clone(&cb),
-                                            transport: Arc::clone(&transport),
-                                        },
-                                    )
-                                    .await?,
-                            }
                         }
                     };
+                    // Whichever attempt answered, hedged or not, `retry_on`
+                    // statuses and retryable errors go through the same
+                    // backoff and `Retry-After` handling.
+                    let response: Response<Incoming> = match primary {
+                        Ok(response) if !retry.retries_status(response.status().as_u16()) => {
+                            response
+                        }
+                        primary => Self::retry_failed_attempt(
+                            primary,
+                            RetryExecutionCtx {
+                                request_id,
+                                route_name: &route_name,
+                                policy,
+                                policy_telemetry: &mut policy_telemetry,
+                                retry_budget: retry_budget.as_ref(),
+                                alternate_backend: alternate_backend.clone(),
+                                upstream_pool: upstream_pool.as_ref(),
+                                max_attempts: max_retry_attempts,
+                                retry: &retry,
+                                replay: replay.as_ref(),
+                                result_tx: &result_tx,
+                                backend_timeout,
+                                backend_endpoints: backend_endpoints.as_ref(),
+                                pending_forward: pending_forward_for_upstream.as_ref(),
+                                circuit_breakers: Arc::clone(&cb),
+                                transport: Arc::clone(&transport),
+                            },
+                        )
+                        .await?,
+                    };
 
                     let (parts, body) = response.into_parts();
                     ForwardSuccess::Response {
                         status: parts.status,
                         headers: parts.headers,
                         body,
+                        truncate_after_bytes: faults.truncate_after_bytes,
                     }
                 };
                 Ok(forward_success)
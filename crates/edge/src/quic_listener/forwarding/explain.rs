@@ -0,0 +1,289 @@
+//! Read-only route explanation for the control API. A synthetic request takes
+//! the same route, split and LB-key path as a live one, but balancer, breaker
+//! and rate-limit state is peeked at rather than advanced.
+
+use spooky_config::runtime::RuntimeRouteAction;
+
+use super::{
+    lb_key::ResolvedLbKey,
+    resolve::{RouteResolutionRequest, RouteTarget},
+    *,
+};
+use crate::{
+    quic_listener::admission::{
+        api_key_is_authorized, classify_request_priority, jwt_is_authorized,
+    },
+    resilience::{circuit_breaker::CircuitProbe, scoped_rate_limit::ScopedRateLimitProbe},
+};
+
+pub(in crate::quic_listener) struct RouteExplainRequest<'a> {
+    pub(in crate::quic_listener) method: &'a str,
+    pub(in crate::quic_listener) path: &'a str,
+    pub(in crate::quic_listener) authority: Option<&'a str>,
+    pub(in crate::quic_listener) client_addr: SocketAddr,
+    pub(in crate::quic_listener) header_lookup: &'a LbHeaderLookup<'a>,
+}
+
+/// Runtime state a request is explained against: the live generation or one
+/// built from a candidate config.
+pub(in crate::quic_listener) struct RouteExplainRuntime<'a> {
+    pub(in crate::quic_listener) routing_index: &'a RouteIndex,
+    pub(in crate::quic_listener) route_policies: &'a HashMap<String, RuntimeUpstreamPolicy>,
+    pub(in crate::quic_listener) upstream_pools: &'a HashMap<String, Arc<RwLock<UpstreamPool>>>,
+    pub(in crate::quic_listener) resilience: &'a RuntimeResilience,
+}
+
+/// First check that would turn the request away, in data-path order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::quic_listener) enum ExplainRejection {
+    NoRoute,
+    NoBackend,
+    ApiKey,
+    Jwt,
+    Brownout,
+    RateLimit,
+    CircuitOpen,
+}
+
+impl ExplainRejection {
+    pub(in crate::quic_listener) fn as_str(self) -> &'static str {
+        match self {
+            Self::NoRoute => "no_route",
+            Self::NoBackend => "no_backend",
+            Self::ApiKey => "api_key",
+            Self::Jwt => "jwt",
+            Self::Brownout => "brownout",
+            Self::RateLimit => "rate_limit",
+            Self::CircuitOpen => "circuit_open",
+        }
+    }
+}
+
+pub(in crate::quic_listener) struct ExplainedRoute {
+    pub(in crate::quic_listener) name: String,
+    pub(in crate::quic_listener) reason: RouteDecisionReason,
+    pub(in crate::quic_listener) matched_path_len: usize,
+    pub(in crate::quic_listener) host_specific: bool,
+    /// Upstream the route names; a split may serve from another.
+    pub(in crate::quic_listener) route_upstream: String,
+    /// Upstream that would serve the request; unset for edge-answered routes.
+    pub(in crate::quic_listener) upstream: Option<String>,
+    pub(in crate::quic_listener) split: bool,
+    pub(in crate::quic_listener) canary_percent: Option<u32>,
+    pub(in crate::quic_listener) action: Option<&'static str>,
+}
+
+/// Auth the route would apply. Local checks report whether the request's
+/// headers pass; external auth is never called.
+pub(in crate::quic_listener) struct ExplainedAuth {
+    pub(in crate::quic_listener) api_key: Option<bool>,
+    pub(in crate::quic_listener) jwt: Option<bool>,
+    pub(in crate::quic_listener) external_auth: bool,
+    pub(in crate::quic_listener) required_scopes: Vec<String>,
+    pub(in crate::quic_listener) required_roles: Vec<String>,
+}
+
+pub(in crate::quic_listener) struct ExplainedBalancing {
+    pub(in crate::quic_listener) strategy: String,
+    pub(in crate::quic_listener) key: String,
+    pub(in crate::quic_listener) key_source: &'static str,
+    /// Backend index and address the pool would pick; unset when no backend
+    /// is healthy or the strategy has no deterministic answer.
+    pub(in crate::quic_listener) backend: Option<(usize, String)>,
+    pub(in crate::quic_listener) healthy_backends: usize,
+    pub(in crate::quic_listener) total_backends: usize,
+}
+
+pub(in crate::quic_listener) struct ExplainedAdmission {
+    /// Name brownout and scoped rate limits are keyed by: the upstream for
+    /// forwarded requests, the route for edge-answered ones.
+    pub(in crate::quic_listener) scope: String,
+    pub(in crate::quic_listener) priority: String,
+    pub(in crate::quic_listener) brownout_active: bool,
+    pub(in crate::quic_listener) brownout_allows: bool,
+    pub(in crate::quic_listener) rate_limits: Vec<ScopedRateLimitProbe>,
+    pub(in crate::quic_listener) circuit: Option<CircuitProbe>,
+}
+
+#[derive(Default)]
+pub(in crate::quic_listener) struct RouteExplanation {
+    pub(in crate::quic_listener) route: Option<ExplainedRoute>,
+    pub(in crate::quic_listener) auth: Option<ExplainedAuth>,
+    pub(in crate::quic_listener) balancing: Option<ExplainedBalancing>,
+    pub(in crate::quic_listener) admission: Option<ExplainedAdmission>,
+    pub(in crate::quic_listener) rejected_by: Option<ExplainRejection>,
+    pub(in crate::quic_listener) error: Option<String>,
+}
+
+impl QUICListener {
+    pub(in crate::quic_listener) fn explain_route_request(
+        request: &RouteExplainRequest<'_>,
+        runtime: &RouteExplainRuntime<'_>,
+    ) -> RouteExplanation {
+        let mut explanation = RouteExplanation::default();
+        let resolution_request = RouteResolutionRequest::new(
+            request.method,
+            request.path,
+            request.authority,
+            None,
+            Some(request.header_lookup),
+        )
+        .with_client_addr(request.client_addr);
+
+        let Some(decision) = runtime.routing_index.lookup_with_decision_for_method(
+            request.path,
+            request.authority,
+            Some(request.method),
+        ) else {
+            explanation.rejected_by = Some(ExplainRejection::NoRoute);
+            explanation.error = Some(format!("no route for {}", request.path));
+            return explanation;
+        };
+        let mut route = ExplainedRoute {
+            name: decision.route.to_string(),
+            reason: decision.reason,
+            matched_path_len: decision.matched_path_len,
+            host_specific: decision.host_specific,
+            route_upstream: decision.upstream.to_string(),
+            upstream: None,
+            split: decision.split.is_some(),
+            canary_percent: decision.canary_percent,
+            action: decision.action.map(|action| match action.as_ref() {
+                RuntimeRouteAction::Redirect(_) => "redirect",
+                RuntimeRouteAction::DirectResponse(_) => "direct_response",
+            }),
+        };
+
+        let target = match Self::resolve_route_target(
+            &resolution_request,
+            runtime.upstream_pools,
+            runtime.route_policies,
+            runtime.routing_index,
+        ) {
+            Ok(target) => target,
+            Err(err) => {
+                explanation.route = Some(route);
+                explanation.rejected_by = Some(ExplainRejection::NoBackend);
+                explanation.error = Some(err.to_string());
+                return explanation;
+            }
+        };
+        let (route_policy, scope, upstream_pool) = match target {
+            RouteTarget::Upstream(resolved) => {
+                route.upstream = Some(resolved.upstream_name.clone());
+                (
+                    resolved.route_policy,
+                    resolved.upstream_name,
+                    Some(resolved.upstream_pool),
+                )
+            }
+            RouteTarget::Local(local) => (local.route_policy, local.route_name, None),
+        };
+        explanation.route = Some(route);
+
+        let mut backend_addr = None;
+        if let Some(upstream_pool) = upstream_pool {
+            let pool = match upstream_pool.read() {
+                Ok(pool) => pool,
+                Err(_) => {
+                    explanation.rejected_by = Some(ExplainRejection::NoBackend);
+                    explanation.error = Some("upstream pool lock poisoned".to_string());
+                    return explanation;
+                }
+            };
+            let ResolvedLbKey { value, source } = Self::resolve_lb_key_for_runtime_request(
+                pool.lb_strategy(),
+                pool.lb_key_spec(),
+                &resolution_request,
+            );
+            let summary = pool.membership_summary();
+            let backend = pool.peek(&value).and_then(|index| {
+                pool.backend_address(index)
+                    .map(|address| (index, address.to_string()))
+            });
+            if summary.healthy_backends == 0 && !summary.panic {
+                explanation.rejected_by = Some(ExplainRejection::NoBackend);
+                explanation.error = Some(if summary.total_backends == 0 {
+                    "no servers in upstream".to_string()
+                } else {
+                    "no healthy servers".to_string()
+                });
+            }
+            backend_addr = backend.as_ref().map(|(_, address)| address.clone());
+            explanation.balancing = Some(ExplainedBalancing {
+                strategy: pool.lb_policy().strategy_name().to_string(),
+                key: value,
+                key_source: source.as_str(),
+                backend,
+                healthy_backends: summary.healthy_backends,
+                total_backends: summary.total_backends,
+            });
+            if explanation.rejected_by.is_some() {
+                return explanation;
+            }
+        }
+
+        let header_lookup = Some(request.header_lookup);
+        let auth = &route_policy.upstream_auth;
+        let explained_auth = ExplainedAuth {
+            api_key: auth
+                .api_key
+                .is_some()
+                .then(|| api_key_is_authorized(&route_policy, header_lookup)),
+            jwt: auth
+                .jwt
+                .is_some()
+                .then(|| jwt_is_authorized(&route_policy, header_lookup)),
+            external_auth: auth.external_auth.is_some(),
+            required_scopes: auth.required_scopes.clone(),
+            required_roles: auth.required_roles.clone(),
+        };
+
+        let resilience = runtime.resilience;
+        let priority = classify_request_priority(resilience, &route_policy, header_lookup, &scope);
+        let admission = ExplainedAdmission {
+            priority: priority.name.clone(),
+            brownout_active: resilience.brownout.is_active(),
+            brownout_allows: resilience.brownout.request_allowed(&scope, priority),
+            rate_limits: resilience.scoped_rate_limits.probe(&scope, |rule| {
+                Self::resolve_scoped_rate_limit_key(
+                    rule,
+                    &scope,
+                    request.method,
+                    request.path,
+                    request.authority,
+                    request.client_addr,
+                    header_lookup,
+                )
+            }),
+            circuit: backend_addr
+                .as_deref()
+                .map(|address| resilience.circuit_breakers.peek(address)),
+            scope,
+        };
+
+        explanation.rejected_by = if explained_auth.api_key == Some(false) {
+            Some(ExplainRejection::ApiKey)
+        } else if explained_auth.jwt == Some(false) {
+            Some(ExplainRejection::Jwt)
+        } else if !admission.brownout_allows {
+            Some(ExplainRejection::Brownout)
+        } else if admission
+            .rate_limits
+            .iter()
+            .any(|probe| !probe.allows_request)
+        {
+            Some(ExplainRejection::RateLimit)
+        } else if admission
+            .circuit
+            .is_some_and(|circuit| !circuit.allows_request)
+        {
+            Some(ExplainRejection::CircuitOpen)
+        } else {
+            None
+        };
+        explanation.auth = Some(explained_auth);
+        explanation.admission = Some(admission);
+        explanation
+    }
+}
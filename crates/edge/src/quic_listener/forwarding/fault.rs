@@ -0,0 +1,124 @@
+//! Route fault injection. Faults are drawn once per request after admission,
+//! so a faulted request holds the same permits as a real one. Aborts are
+//! answered before dispatch; delays hold the primary attempt, so hedging,
+//! retries and circuit breakers see a slow backend; truncation fails the
+//! upstream response body the way a broken upstream stream would.
+
+use spooky_config::runtime::{RuntimeFaultAbortAction, RuntimeFaultSample};
+
+use super::*;
+use crate::constants::H3_REQUEST_CANCELLED;
+use crate::runtime::connection::{
+    outcome::observe_status_outcome,
+    stream::{CancellationReason, CompletionReason},
+};
+
+const FAULT_DELAY: &str = "delay";
+const FAULT_ABORT: &str = "abort";
+const FAULT_RESET: &str = "reset";
+const FAULT_TRUNCATE: &str = "truncate";
+
+pub(super) const FAULT_TRUNCATED_BODY_ERROR: &str = "injected fault: response body truncated";
+
+/// Draws the route's faults for `pending_forward` and counts each one drawn.
+/// Requests without the route's gate header are never faulted.
+pub(super) fn sample_faults(
+    pending_forward: &PendingForward,
+    metrics: &Metrics,
+) -> RuntimeFaultSample {
+    let Some(fault) = pending_forward.fault.as_ref() else {
+        return RuntimeFaultSample::default();
+    };
+    let policy = fault.policy.as_ref();
+    let gate_value = policy
+        .header
+        .as_ref()
+        .and_then(|header| extract_header_value(&pending_forward.headers, header.name.as_bytes()));
+    if !policy.admits(gate_value) {
+        return RuntimeFaultSample::default();
+    }
+
+    let sample = policy.sample(rand::random::<u64>);
+    if let Some(action) = sample.abort {
+        let label = match action {
+            RuntimeFaultAbortAction::Status(_) => FAULT_ABORT,
+            RuntimeFaultAbortAction::Reset => FAULT_RESET,
+        };
+        metrics.record_fault_injection(&fault.route, label);
+    }
+    if sample.delay.is_some() {
+        metrics.record_fault_injection(&fault.route, FAULT_DELAY);
+    }
+    if sample.truncate_after_bytes.is_some() {
+        metrics.record_fault_injection(&fault.route, FAULT_TRUNCATE);
+    }
+    sample
+}
+
+impl QUICListener {
+    /// Answers a request whose fault draw aborted it; the upstream is never
+    /// contacted. A status abort is recorded like any local response, a reset
+    /// cancels both directions of the stream.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn answer_fault_abort(
+        stream_id: u64,
+        req: &mut RequestEnvelope,
+        h3: &mut quiche::h3::Connection,
+        quic: &mut quiche::Connection,
+        action: RuntimeFaultAbortAction,
+        upstream_name: &str,
+        backend_index: usize,
+        pending_forward: &PendingForward,
+        shared_ctx: &ForwardingSharedCtx<'_>,
+    ) -> Result<(), quiche::h3::Error> {
+        let metrics = shared_ctx.metrics.as_ref();
+        match action {
+            RuntimeFaultAbortAction::Status(status) => {
+                let status = http::StatusCode::from_u16(status)
+                    .unwrap_or(http::StatusCode::SERVICE_UNAVAILABLE);
+                let _ = observe_status_outcome(
+                    metrics,
+                    OutcomeRouteTarget {
+                        route: &pending_forward.route_name,
+                        priority: None,
+                    },
+                    Some(OutcomeBackendTarget {
+                        upstream: upstream_name,
+                        backend_addr: Some(pending_forward.backend_addr.as_ref()),
+                        backend_index: Some(backend_index),
+                    }),
+                    req.start.elapsed(),
+                    status,
+                );
+                Self::send_simple_response(h3, quic, stream_id, status, b"fault injected\n")?;
+                Self::log_access(req, status.as_u16());
+                shared_ctx
+                    .resilience
+                    .adaptive_admission_for(req.upstream_name.as_deref())
+                    .observe(req.start.elapsed(), status.is_server_error());
+                req.mark_terminal_outcome_recorded();
+                terminalize_stream(
+                    req,
+                    TerminalReason::Completed(CompletionReason::ImmediateResponse),
+                    metrics,
+                );
+            }
+            RuntimeFaultAbortAction::Reset => {
+                let _ =
+                    quic.stream_shutdown(stream_id, quiche::Shutdown::Write, H3_REQUEST_CANCELLED);
+                let _ =
+                    quic.stream_shutdown(stream_id, quiche::Shutdown::Read, H3_REQUEST_CANCELLED);
+                debug!(
+                    "request_id={} route={} stream {} reset by fault injection",
+                    req.request_id, pending_forward.route_name, stream_id
+                );
+                terminalize_stream(
+                    req,
+                    TerminalReason::Cancelled(CancellationReason::OperatorAbort),
+                    metrics,
+                );
+            }
+        }
+        Ok(())
+    }
+}
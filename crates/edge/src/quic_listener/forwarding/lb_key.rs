@@ -54,12 +54,22 @@ pub(super) enum LbKeySource {
     DefaultFallback,
 }
 
+impl LbKeySource {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::ConfiguredSpec => "configured",
+            Self::StickyCidFallback => "sticky_cid_fallback",
+            Self::DefaultFallback => "default_fallback",
+        }
+    }
+}
+
 pub(super) struct ResolvedLbKey {
     pub(super) value: String,
     pub(super) source: LbKeySource,
 }
 
-fn extract_cookie_value(cookie_header: &str, cookie_name: &str) -> Option<String> {
+pub(super) fn extract_cookie_value(cookie_header: &str, cookie_name: &str) -> Option<String> {
     for pair in cookie_header.split(';') {
         let part = pair.trim();
         if part.is_empty() {
@@ -103,12 +113,27 @@ impl QUICListener {
             request.path,
             request.authority,
             request.cid_key,
-            None,
+            request.client_addr,
             request.header_lookup,
         );
         Self::resolve_lb_key_for_runtime_input(lb_strategy, lb_key_spec, &request)
     }
 
+    pub(in crate::quic_listener::forwarding) fn resolve_request_key_for_runtime_request(
+        key_spec: &RuntimeRequestKeySpec,
+        request: &super::resolve::RouteResolutionRequest<'_>,
+    ) -> Option<String> {
+        let request = LbKeyRequestParts::new(
+            request.method,
+            request.path,
+            request.authority,
+            request.cid_key,
+            request.client_addr,
+            request.header_lookup,
+        );
+        Self::resolve_lb_key_from_runtime_parts(key_spec, &request).filter(|key| !key.is_empty())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(in crate::quic_listener::forwarding) fn resolve_lb_key(
         lb_type: &str,
@@ -0,0 +1,267 @@
+//! Shadow copies for routes with a `mirror` policy. A copy is sampled once the
+//! primary request is dispatched and runs under the route's own inflight
+//! budget. It never touches the client stream: the mirror response is drained
+//! and dropped, and failures are only counted. Copies skip the mirror
+//! upstream's circuit breakers and load-balancer accounting.
+
+use tokio::sync::OwnedSemaphorePermit;
+
+use super::*;
+use crate::runtime::connection::stream::RequestMode;
+
+const MIRROR_OUTCOME_RESPONSE: &str = "response";
+const MIRROR_OUTCOME_ERROR_STATUS: &str = "error_status";
+const MIRROR_OUTCOME_FAILED: &str = "failed";
+const MIRROR_OUTCOME_TIMEOUT: &str = "timeout";
+const MIRROR_OUTCOME_BODY_INCOMPLETE: &str = "body_incomplete";
+const MIRROR_OUTCOME_SKIPPED_BODY: &str = "skipped_body";
+const MIRROR_OUTCOME_SKIPPED_INFLIGHT: &str = "skipped_inflight";
+const MIRROR_OUTCOME_SKIPPED_BUFFER: &str = "skipped_buffer";
+const MIRROR_OUTCOME_NO_BACKEND: &str = "no_backend";
+
+/// A sampled copy holding its mirror inflight permit until the copy finishes.
+pub(super) struct MirrorCopy {
+    route: Arc<str>,
+    upstream: String,
+    backend: String,
+    endpoint: BackendEndpoint,
+    pending_forward: Arc<PendingForward>,
+    body_length: usize,
+    timeout: Duration,
+    buffer_cap_bytes: usize,
+    transport: Arc<UpstreamTransportPool>,
+    metrics: Arc<Metrics>,
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Bytes a copy holds against `request_buffer_global_cap_bytes`, released
+/// when the copy finishes or gives up.
+struct MirrorBufferReservation {
+    bytes: usize,
+    metrics: Arc<Metrics>,
+}
+
+impl MirrorBufferReservation {
+    fn try_grow(&mut self, bytes: usize, cap_bytes: usize) -> bool {
+        if !self.metrics.try_reserve_request_buffer(bytes, cap_bytes) {
+            return false;
+        }
+        self.bytes += bytes;
+        true
+    }
+
+    fn release(&mut self) {
+        self.metrics.release_request_buffer(self.bytes);
+        self.bytes = 0;
+    }
+}
+
+impl Drop for MirrorBufferReservation {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl MirrorCopy {
+    /// Decides whether this request is mirrored. Tunnels are never copied;
+    /// bodies are only copied when their declared length fits the policy.
+    pub(super) fn sample(
+        pending_forward: &Arc<PendingForward>,
+        request_mode: RequestMode,
+        exec_ctx: &ForwardingExecutionCtx<'_>,
+        shared_ctx: &ForwardingSharedCtx<'_>,
+    ) -> Option<Self> {
+        let mirror = pending_forward.mirror.as_ref()?;
+        let policy = mirror.policy.as_ref();
+        if request_mode.is_tunnel()
+            || !policy.samples(&pending_forward.method, rand::random::<u64>())
+        {
+            return None;
+        }
+        let metrics = &shared_ctx.metrics;
+        let skip = |outcome: &str| {
+            metrics.record_mirror_request(&mirror.route, &policy.upstream, outcome, None);
+            None
+        };
+
+        let body_length = if request_mode.bodyless_mode() {
+            Some(0)
+        } else {
+            extract_header_value(&pending_forward.headers, b"content-length")
+                .and_then(|value| value.parse::<usize>().ok())
+        };
+        let body_length = match body_length {
+            Some(length) if policy.body_fits(Some(length)) => length,
+            _ => return skip(MIRROR_OUTCOME_SKIPPED_BODY),
+        };
+        let Some(permit) = exec_ctx
+            .mirror_inflight
+            .get(&*mirror.route)
+            .and_then(|budget| Arc::clone(budget).try_acquire_owned().ok())
+        else {
+            return skip(MIRROR_OUTCOME_SKIPPED_INFLIGHT);
+        };
+        let backend = shared_ctx
+            .upstream_pools
+            .get(&policy.upstream)
+            .and_then(|pool| {
+                let mut pool = pool.write().ok()?;
+                let index = pool.pick_without_begin(&pending_forward.path)?;
+                pool.backend_address(index).map(str::to_string)
+            });
+        let Some((backend, endpoint)) = backend.and_then(|backend| {
+            let endpoint = exec_ctx.backend_endpoints.get(&backend)?;
+            Some((backend, endpoint))
+        }) else {
+            return skip(MIRROR_OUTCOME_NO_BACKEND);
+        };
+
+        Some(Self {
+            route: Arc::clone(&mirror.route),
+            upstream: policy.upstream.clone(),
+            backend,
+            endpoint,
+            pending_forward: Arc::clone(pending_forward),
+            body_length,
+            timeout: exec_ctx.backend_timeout,
+            buffer_cap_bytes: exec_ctx.request_buffer_global_cap_bytes,
+            transport: Arc::clone(&exec_ctx.transport_pool),
+            metrics: Arc::clone(&shared_ctx.metrics),
+            _permit: permit,
+        })
+    }
+
+    /// Starts the copy. Requests without a body are sent right away; otherwise
+    /// the client body is routed through a tee that feeds the primary first and
+    /// buffers at most `body_length` bytes for the copy, reserved against the
+    /// global request buffer cap. Returns the sender the stream should feed in
+    /// place of `body_tx`.
+    pub(super) fn start(self, body_tx: Option<mpsc::Sender<Bytes>>) -> Option<mpsc::Sender<Bytes>> {
+        let primary_tx = match body_tx {
+            Some(primary_tx) if self.body_length > 0 => primary_tx,
+            body_tx => {
+                self.spawn_send(Bytes::new());
+                return body_tx;
+            }
+        };
+
+        let (client_tx, client_rx) = mpsc::channel::<Bytes>(REQUEST_CHUNK_CHANNEL_CAPACITY);
+        let route = Arc::clone(&self.route);
+        let upstream = self.upstream.clone();
+        let metrics = Arc::clone(&self.metrics);
+        if spawn_async_task(
+            self.tee_body(client_rx, primary_tx.clone()),
+            "request_mirror_body",
+        ) {
+            Some(client_tx)
+        } else {
+            metrics.record_mirror_request(&route, &upstream, MIRROR_OUTCOME_FAILED, None);
+            Some(primary_tx)
+        }
+    }
+
+    fn spawn_send(self, body: Bytes) {
+        let route = Arc::clone(&self.route);
+        let upstream = self.upstream.clone();
+        let metrics = Arc::clone(&self.metrics);
+        if !spawn_async_task(self.send(body), "request_mirror") {
+            metrics.record_mirror_request(&route, &upstream, MIRROR_OUTCOME_FAILED, None);
+        }
+    }
+
+    async fn tee_body(self, mut client_rx: mpsc::Receiver<Bytes>, primary_tx: mpsc::Sender<Bytes>) {
+        let mut body = Vec::new();
+        let mut reservation = MirrorBufferReservation {
+            bytes: 0,
+            metrics: Arc::clone(&self.metrics),
+        };
+        let mut dropped = None;
+        while let Some(chunk) = client_rx.recv().await {
+            if dropped.is_none() {
+                if body.len() + chunk.len() > self.body_length {
+                    dropped = Some(MIRROR_OUTCOME_BODY_INCOMPLETE);
+                } else if !reservation.try_grow(chunk.len(), self.buffer_cap_bytes) {
+                    dropped = Some(MIRROR_OUTCOME_SKIPPED_BUFFER);
+                } else {
+                    body.extend_from_slice(&chunk);
+                }
+                if dropped.is_some() {
+                    reservation.release();
+                    body = Vec::new();
+                }
+            }
+            // The primary owns the stream: once its body is gone, stop reading so
+            // the stream sees the same closed channel it would without a mirror.
+            if primary_tx.send(chunk).await.is_err() {
+                dropped.get_or_insert(MIRROR_OUTCOME_BODY_INCOMPLETE);
+                break;
+            }
+        }
+        drop(client_rx);
+        drop(primary_tx);
+
+        if dropped.is_none() && body.len() != self.body_length {
+            dropped = Some(MIRROR_OUTCOME_BODY_INCOMPLETE);
+        }
+        if let Some(outcome) = dropped {
+            self.metrics
+                .record_mirror_request(&self.route, &self.upstream, outcome, None);
+            return;
+        }
+        // The reservation is held until the copy's exchange finishes.
+        self.send(Bytes::from(body)).await;
+        drop(reservation);
+    }
+
+    async fn send(self, body: Bytes) {
+        let start = Instant::now();
+        let content_length = body.len();
+        let outcome = match self.pending_forward.build_request(
+            &self.endpoint,
+            BoxBody::new(Full::new(body)),
+            Some(content_length),
+        ) {
+            Ok(request) => {
+                let exchange = async {
+                    let response = self
+                        .transport
+                        .send_backend_request(&self.backend, request)
+                        .await?;
+                    let status = response.status();
+                    let mut body = response.into_body();
+                    while let Some(frame) = body.frame().await {
+                        if frame.is_err() {
+                            break;
+                        }
+                    }
+                    Ok::<_, ProxyError>(status)
+                };
+                match tokio::time::timeout(self.timeout, exchange).await {
+                    Ok(Ok(status)) if status.is_server_error() => MIRROR_OUTCOME_ERROR_STATUS,
+                    Ok(Ok(_)) => MIRROR_OUTCOME_RESPONSE,
+                    Ok(Err(err)) => {
+                        debug!(
+                            "request_id={} mirror route={} upstream={} failed: {}",
+                            self.pending_forward.request_id, self.route, self.upstream, err
+                        );
+                        MIRROR_OUTCOME_FAILED
+                    }
+                    Err(_) => MIRROR_OUTCOME_TIMEOUT,
+                }
+            }
+            Err(err) => {
+                debug!(
+                    "request_id={} mirror route={} could not build request: {}",
+                    self.pending_forward.request_id, self.route, err
+                );
+                MIRROR_OUTCOME_FAILED
+            }
+        };
+        self.metrics.record_mirror_request(
+            &self.route,
+            &self.upstream,
+            outcome,
+            Some(start.elapsed()),
+        );
+    }
+}
@@ -1,9 +1,14 @@
 mod auth;
 mod dispatch;
+mod explain;
+mod fault;
 mod lb_key;
+mod mirror;
 mod prepare;
+mod replay;
 mod resolve;
 mod response;
+mod session_affinity;
 mod stream_progress;
 
 use std::convert::Infallible;
@@ -12,10 +17,17 @@ use http_body_util::Full;
 use spooky_config::config::ScopedRateLimitScope;
 use spooky_errors::ClassifiedUpstreamProxyError;
 
+pub(in crate::quic_listener) use self::explain::{
+    RouteExplainRequest, RouteExplainRuntime, RouteExplanation,
+};
+use self::mirror::MirrorCopy;
 use self::prepare::{RequestFinalizationConfig, StartedRequestEnvelope};
-pub(in crate::quic_listener) use self::resolve::BootstrapResolutionInput;
+use self::replay::ReplayBody;
 #[cfg(test)]
 pub(in crate::quic_listener) use self::resolve::RouteResolutionRequest as TestRouteResolutionRequest;
+pub(in crate::quic_listener) use self::resolve::{
+    BootstrapResolutionInput, LocalRoute, RouteResolution,
+};
 use super::*;
 use crate::runtime::connection::{
     outcome::{
@@ -66,11 +78,13 @@ pub(in crate::quic_listener) struct ForwardingSharedCtx<'a> {
 
 pub(in crate::quic_listener) struct ForwardingExecutionCtx<'a> {
     pub(in crate::quic_listener) transport_pool: Arc<UpstreamTransportPool>,
-    pub(in crate::quic_listener) backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
+    pub(in crate::quic_listener) backend_endpoints: Arc<BackendEndpoints>,
     pub(in crate::quic_listener) upstream_inflight: &'a HashMap<String, Arc<Semaphore>>,
+    pub(in crate::quic_listener) mirror_inflight: &'a HashMap<String, Arc<Semaphore>>,
     pub(in crate::quic_listener) global_inflight: Arc<Semaphore>,
     pub(in crate::quic_listener) backend_timeout: Duration,
     pub(in crate::quic_listener) inflight_acquire_wait: Duration,
+    pub(in crate::quic_listener) request_buffer_global_cap_bytes: usize,
 }
 
 pub(in crate::quic_listener) struct StreamProgressConfig {
@@ -213,7 +227,8 @@ impl QUICListener {
 
     fn request_outcome_route_target(req: &RequestEnvelope) -> OutcomeRouteTarget<'_> {
         OutcomeRouteTarget {
-            route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+            route: req.route_name.as_deref().unwrap_or("unrouted"),
+            priority: req.priority.as_deref(),
         }
     }
 
@@ -285,6 +300,11 @@ impl QUICListener {
             );
             return Ok(false);
         };
+        let route_name = req
+            .route_name
+            .clone()
+            .unwrap_or_else(|| upstream_name.clone());
+        let priority = req.priority.clone();
 
         let (
             backend_index,
@@ -296,6 +316,7 @@ impl QUICListener {
         ) = match crate::quic_listener::admission::execute_forwarding_post_auth_admission(
             resilience,
             &upstream_name,
+            resilience.priority.class_or_default(priority.as_deref()),
             req.upstream_pool.as_ref(),
             req.backend_index,
             pending_forward.backend_index,
@@ -325,7 +346,8 @@ impl QUICListener {
                 let _ = observe_admission_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: &upstream_name,
+                        route: &route_name,
+                        priority: priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: &upstream_name,
@@ -346,7 +368,7 @@ impl QUICListener {
                     decision.retry_after_seconds,
                 )?;
                 resilience
-                    .adaptive_admission
+                    .adaptive_admission_for(req.upstream_name.as_deref())
                     .observe(req.start.elapsed(), true);
                 req.set_terminal_overload_reason(Some(decision.reason.metrics_reason()));
                 req.mark_terminal_outcome_recorded();
@@ -372,7 +394,8 @@ impl QUICListener {
                 let _ = observe_admission_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: &upstream_name,
+                        route: &route_name,
+                        priority: priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: &upstream_name,
@@ -386,7 +409,7 @@ impl QUICListener {
                 Self::send_simple_response(h3, quic, stream_id, decision.status, decision.body)?;
                 if decision.observe_adaptive_overload {
                     resilience
-                        .adaptive_admission
+                        .adaptive_admission_for(req.upstream_name.as_deref())
                         .observe(req.start.elapsed(), true);
                 }
                 if let Some(reason) = decision.overload_reason {
@@ -411,12 +434,12 @@ impl QUICListener {
         let Some(backend_endpoint) = exec_ctx
             .backend_endpoints
             .get(pending_forward.backend_addr.as_ref())
-            .cloned()
         else {
             let _ = observe_proxy_error_outcome(
                 metrics,
                 OutcomeRouteTarget {
-                    route: &upstream_name,
+                    route: &route_name,
+                    priority: priority.as_deref(),
                 },
                 Some(OutcomeBackendTarget {
                     upstream: &upstream_name,
@@ -443,6 +466,22 @@ impl QUICListener {
             return Ok(false);
         };
 
+        let faults = fault::sample_faults(&pending_forward, metrics);
+        if let Some(action) = faults.abort {
+            Self::answer_fault_abort(
+                stream_id,
+                req,
+                h3,
+                quic,
+                action,
+                &upstream_name,
+                backend_index,
+                &pending_forward,
+                shared_ctx,
+            )?;
+            return Ok(false);
+        }
+
         let request_mode = req.request_mode();
         let websocket_h1_tunnel = req.tunnel_mode == TunnelMode::Websocket
             && backend_endpoint.scheme() == BackendScheme::Http;
@@ -472,7 +511,8 @@ impl QUICListener {
                     let _ = observe_proxy_error_outcome(
                         metrics,
                         OutcomeRouteTarget {
-                            route: &upstream_name,
+                            route: &route_name,
+                            priority: priority.as_deref(),
                         },
                         Some(OutcomeBackendTarget {
                             upstream: &upstream_name,
@@ -493,7 +533,7 @@ impl QUICListener {
                     )?;
                     error!("failed to build upstream request after auth: {}", err_text);
                     resilience
-                        .adaptive_admission
+                        .adaptive_admission_for(req.upstream_name.as_deref())
                         .observe(req.start.elapsed(), true);
                     terminalize_stream(
                         req,
@@ -505,12 +545,21 @@ impl QUICListener {
             }
         };
 
+        let (body_tx, replay) = ReplayBody::start(
+            &pending_forward,
+            request_mode,
+            body_tx,
+            exec_ctx,
+            &shared_ctx.metrics,
+        );
         let result_rx = match Self::spawn_upstream_forward_task(
             req,
             Arc::clone(&pending_forward),
             backend_endpoint,
             request,
             websocket_tunnel_body_rx,
+            replay,
+            faults,
             exec_ctx,
             shared_ctx,
         ) {
@@ -519,7 +568,8 @@ impl QUICListener {
                 let _ = observe_proxy_error_outcome(
                     metrics,
                     OutcomeRouteTarget {
-                        route: &upstream_name,
+                        route: &route_name,
+                        priority: priority.as_deref(),
                     },
                     Some(OutcomeBackendTarget {
                         upstream: &upstream_name,
@@ -541,7 +591,7 @@ impl QUICListener {
                 )?;
                 error!("failed to spawn upstream task after auth: {}", err);
                 resilience
-                    .adaptive_admission
+                    .adaptive_admission_for(req.upstream_name.as_deref())
                     .observe(req.start.elapsed(), true);
                 terminalize_stream(
                     req,
@@ -554,6 +604,11 @@ impl QUICListener {
         if let Ok(pool) = upstream_pool.write() {
             pool.begin_request_for_accounting(backend_index);
         }
+        let body_tx = match MirrorCopy::sample(&pending_forward, request_mode, exec_ctx, shared_ctx)
+        {
+            Some(copy) => copy.start(body_tx),
+            None => body_tx,
+        };
         req.transition_admitted_to_awaiting_upstream(body_tx, result_rx);
         let _ = Self::flush_request_buffer(req, metrics);
         Ok(true)
@@ -596,10 +651,11 @@ impl QUICListener {
     pub(super) fn handle_h3(
         connection: &mut QuicConnection,
         transport_pool: Arc<UpstreamTransportPool>,
-        backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
-        upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+        backend_endpoints: Arc<BackendEndpoints>,
+        route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
         upstream_inflight: &HashMap<String, Arc<Semaphore>>,
+        mirror_inflight: &HashMap<String, Arc<Semaphore>>,
         global_inflight: Arc<Semaphore>,
         backend_timeout: Duration,
         backend_body_idle_timeout: Duration,
@@ -643,9 +699,11 @@ impl QUICListener {
             transport_pool: Arc::clone(&transport_pool),
             backend_endpoints: Arc::clone(&backend_endpoints),
             upstream_inflight,
+            mirror_inflight,
             global_inflight: Arc::clone(&global_inflight),
             backend_timeout,
             inflight_acquire_wait,
+            request_buffer_global_cap_bytes,
         };
         let progress_config = StreamProgressConfig {
             backend_body_idle_timeout,
@@ -770,7 +828,7 @@ impl QUICListener {
                         sticky_cid_key.as_str(),
                         tracing_enabled,
                         routing_index,
-                        &upstream_policies,
+                        &route_policies,
                         upstream_pools,
                         &metrics,
                         resilience,
@@ -841,12 +899,14 @@ impl QUICListener {
                             let mut reject_body_for_bodyless = None::<(String, Duration)>;
                             let mut payload_too_large = None::<(String, Duration)>;
                             if let Some(req) = connection.streams.get_mut(&stream_id) {
+                                let max_request_body_bytes =
+                                    req.max_request_body_bytes.unwrap_or(max_request_body_bytes);
                                 if read > 0 {
                                     req.set_last_body_activity(Instant::now());
                                 }
                                 if req.request_mode().bodyless_mode() && read > 0 {
                                     reject_body_for_bodyless = Some((
-                                        req.upstream_name
+                                        req.route_name
                                             .clone()
                                             .unwrap_or_else(|| "unrouted".to_string()),
                                         req.start.elapsed(),
@@ -879,7 +939,7 @@ impl QUICListener {
                                             kind: BodyLimitKind::BodySize,
                                         }) => {
                                             payload_too_large = Some((
-                                                req.upstream_name
+                                                req.route_name
                                                     .clone()
                                                     .unwrap_or_else(|| "unrouted".to_string()),
                                                 req.start.elapsed(),
@@ -901,11 +961,9 @@ impl QUICListener {
                                                 ) {
                                                     if err == RequestBufferError::BodySize {
                                                         payload_too_large = Some((
-                                                            req.upstream_name
-                                                                .clone()
-                                                                .unwrap_or_else(|| {
-                                                                    "unrouted".to_string()
-                                                                }),
+                                                            req.route_name.clone().unwrap_or_else(
+                                                                || "unrouted".to_string(),
+                                                            ),
                                                             req.start.elapsed(),
                                                         ));
                                                     } else {
@@ -943,6 +1001,7 @@ impl QUICListener {
                                     &metrics,
                                     OutcomeRouteTarget {
                                         route: &route_label,
+                                        priority: None,
                                     },
                                     None,
                                     elapsed,
@@ -975,6 +1034,7 @@ impl QUICListener {
                                     &metrics,
                                     OutcomeRouteTarget {
                                         route: &route_label,
+                                        priority: None,
                                     },
                                     None,
                                     elapsed,
@@ -1008,7 +1068,8 @@ impl QUICListener {
                                 let _ = observe_proxy_error_outcome(
                                     &metrics,
                                     OutcomeRouteTarget {
-                                        route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                                        route: req.route_name.as_deref().unwrap_or("unrouted"),
+                                        priority: req.priority.as_deref(),
                                     },
                                     Some(OutcomeBackendTarget {
                                         upstream: req
@@ -1033,7 +1094,7 @@ impl QUICListener {
                                     resilience.shed_retry_after_seconds,
                                 )?;
                                 resilience
-                                    .adaptive_admission
+                                    .adaptive_admission_for(req.upstream_name.as_deref())
                                     .observe(req.start.elapsed(), true);
                                 if let Some(req) = connection.streams.get_mut(&stream_id) {
                                     req.set_terminal_overload_reason(Some(
@@ -1063,7 +1124,8 @@ impl QUICListener {
                                 let _ = observe_proxy_error_outcome(
                                     &metrics,
                                     OutcomeRouteTarget {
-                                        route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                                        route: req.route_name.as_deref().unwrap_or("unrouted"),
+                                        priority: req.priority.as_deref(),
                                     },
                                     Some(OutcomeBackendTarget {
                                         upstream: req
@@ -1082,7 +1144,7 @@ impl QUICListener {
                                     None,
                                 );
                                 resilience
-                                    .adaptive_admission
+                                    .adaptive_admission_for(req.upstream_name.as_deref())
                                     .observe(req.start.elapsed(), true);
                             }
                             if let Some(req) = connection.streams.get_mut(&stream_id) {
@@ -1221,6 +1283,7 @@ mod tests {
             path: Arc::<str>::from("/"),
             authority: Some(Arc::<str>::from("example.com")),
             headers: Arc::new(headers),
+            route_name: Arc::<str>::from("api"),
             upstream_name: Arc::<str>::from("api"),
             route_reason: Arc::<str>::from("path_prefix"),
             route_path_len: 1,
@@ -1235,6 +1298,10 @@ mod tests {
             traceparent: None,
             host_policy: Default::default(),
             forwarded_header_policy: Default::default(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: Default::default(),
             auth_header_mutations: Vec::new(),
         }
     }
@@ -1325,6 +1392,10 @@ mod tests {
             host: Default::default(),
             forwarded_headers: Default::default(),
             protocol: Default::default(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: Default::default(),
         };
         let headers = [("x-api-key".to_string(), "secret-key".to_string())]
             .into_iter()
@@ -1375,6 +1446,10 @@ mod tests {
             host: Default::default(),
             forwarded_headers: Default::default(),
             protocol: Default::default(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: Default::default(),
         };
         let headers = [("authorization".to_string(), format!("Bearer {token}"))]
             .into_iter()
@@ -1438,6 +1513,10 @@ mod tests {
             host: Default::default(),
             forwarded_headers: Default::default(),
             protocol: Default::default(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: Default::default(),
         };
         let allowed_claims = serde_json::json!({
             "scope": "read:fast write:slow",
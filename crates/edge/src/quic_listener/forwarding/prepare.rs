@@ -4,11 +4,21 @@ use http_body_util::Full;
 use spooky_config::runtime::RuntimeExternalAuth;
 use tokio::{sync::oneshot, task::AbortHandle};
 
-use super::{auth::start_external_auth_task, resolve::ForwardingResolvedTarget, *};
+use super::{
+    auth::start_external_auth_task,
+    resolve::{ForwardingResolvedTarget, LocalRoute, RouteResolution},
+    *,
+};
 use crate::{
-    quic_listener::admission::{
-        AdmissionPolicyDecision, AdmissionRejectionResponse, admission_rejection_response,
-        evaluate_forwarding_pre_admission_policy,
+    quic_listener::{
+        admission::{
+            AdmissionPolicyDecision, AdmissionRejectionResponse, admission_rejection_response,
+            classify_request_priority, evaluate_forwarding_pre_admission_policy,
+        },
+        protocol::is_head_method,
+        route_action::{
+            evaluate_route_action_admission, observe_route_action_response, route_action_response,
+        },
     },
     runtime::connection::{
         auth::ExternalAuthResult,
@@ -20,7 +30,7 @@ use crate::{
             AdmissionOutcomeClass, OutcomeBackendTarget, OutcomeRouteTarget,
             observe_admission_outcome,
         },
-        request::{PendingForward, RequestEnvelope},
+        request::{PendingFault, PendingForward, PendingMirror, RequestEnvelope},
         stream::{
             AwaitingAuthState, DispatchReadyState, RequestBodyRuntime, RequestContext,
             RequestIntakeState, RequestMode, RoutingSnapshot,
@@ -49,6 +59,10 @@ impl DispatchReadyCandidate {
         self.state.context.request_id
     }
 
+    fn route_name(&self) -> &str {
+        &self.routing.route_name
+    }
+
     fn upstream_name(&self) -> &str {
         &self.routing.upstream_name
     }
@@ -150,6 +164,16 @@ pub(super) struct IntakeRequestDescriptor<'a> {
     pub(super) tracing_enabled: bool,
 }
 
+/// Request inputs needed to answer an edge-answered route.
+struct LocalRouteRequest<'a> {
+    peer_address: SocketAddr,
+    request_start: Instant,
+    method: &'a str,
+    path: &'a str,
+    authority: Option<&'a str>,
+    header_lookup: &'a LbHeaderLookup<'a>,
+}
+
 /// Request-scoped configuration applied when finalizing the request envelope.
 pub(super) struct RequestFinalizationConfig {
     pub(super) routing_transparency_enabled: bool,
@@ -173,6 +197,7 @@ impl PendingForward {
             policies: spooky_bridge::request::RequestBuildPolicies {
                 host_policy: &self.host_policy,
                 forwarded_header_policy: &self.forwarded_header_policy,
+                path_rewrite: self.path_rewrite.as_ref(),
             },
         }
     }
@@ -225,11 +250,18 @@ impl PendingForward {
         }
     }
 
-    pub(super) fn build_bodyless_request(
+    /// Builds a retry or hedge request around a fully buffered body.
+    pub(super) fn build_replay_request(
         &self,
         endpoint: &BackendEndpoint,
+        body: Bytes,
     ) -> Result<Request<BoxBody<Bytes, Infallible>>, ProxyError> {
-        self.build_request(endpoint, BoxBody::new(Full::new(Bytes::new())), Some(0))
+        let content_length = body.len();
+        self.build_request(
+            endpoint,
+            BoxBody::new(Full::new(body)),
+            Some(content_length),
+        )
     }
 
     pub(super) fn build_http1_websocket_tunnel_request(
@@ -372,7 +404,7 @@ impl QUICListener {
         sticky_cid_key: &str,
         tracing_enabled: bool,
         routing_index: &RouteIndex,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
         metrics: &Metrics,
         resilience: &RuntimeResilience,
@@ -401,47 +433,87 @@ impl QUICListener {
             authority,
             tunnel_mode,
             sticky_cid_key,
+            peer_address,
             Some(&lb_header_lookup),
             routing_index,
             upstream_pools,
-            upstream_policies,
+            route_policies,
             metrics,
             request_start.elapsed(),
         );
 
         let prepared = match resolved {
-            Ok(ForwardingResolvedTarget {
+            Ok(RouteResolution::Local(local)) => {
+                Self::respond_with_route_action(
+                    stream_id,
+                    h3,
+                    quic,
+                    LocalRouteRequest {
+                        peer_address,
+                        request_start,
+                        method,
+                        path,
+                        authority,
+                        header_lookup: &lb_header_lookup,
+                    },
+                    &local,
+                    metrics,
+                    resilience,
+                )?;
+                None
+            }
+            Ok(RouteResolution::Backend(ForwardingResolvedTarget {
+                route_name,
                 upstream_name,
                 upstream_pool,
-                upstream_policy,
+                route_policy,
                 route_path_len,
                 route_host_specific,
                 route_reason,
                 backend_addr,
                 backend_index,
                 backend_lb,
-            }) => {
+                affinity_cookie,
+            })) => {
+                let mirror = route_policy.mirror.clone().map(|policy| PendingMirror {
+                    route: Arc::<str>::from(route_name.as_str()),
+                    policy,
+                });
+                let fault = route_policy.fault.clone().map(|policy| PendingFault {
+                    route: Arc::<str>::from(route_name.as_str()),
+                    policy,
+                });
+                let priority = classify_request_priority(
+                    resilience,
+                    &route_policy,
+                    Some(&lb_header_lookup),
+                    &route_name,
+                );
                 let routing = RoutingSnapshot {
                     backend_addr: backend_addr.clone(),
                     backend_index,
+                    route_name: route_name.clone(),
                     upstream_name: upstream_name.clone(),
                     route_reason: route_reason.clone(),
                     route_path_len,
                     route_host_specific,
                     backend_lb: Some(backend_lb.clone()),
+                    priority: Some(priority.name.clone()),
+                    affinity_cookie,
                 };
                 let admission = evaluate_forwarding_pre_admission_policy(
-                    &upstream_policy,
+                    &route_policy,
                     Some(&lb_header_lookup),
                     &resilience.brownout,
                     resilience.adaptive_admission.inflight_percent(),
-                    &upstream_name,
+                    &route_name,
+                    priority,
                     resilience.shed_retry_after_seconds,
                     &resilience.scoped_rate_limits,
                     |rule| {
                         Self::resolve_scoped_rate_limit_key(
                             rule,
-                            &upstream_name,
+                            &route_name,
                             method,
                             path,
                             authority,
@@ -459,7 +531,8 @@ impl QUICListener {
                         let _ = observe_admission_outcome(
                             metrics,
                             OutcomeRouteTarget {
-                                route: &upstream_name,
+                                route: &route_name,
+                                priority: Some(&priority.name),
                             },
                             Some(OutcomeBackendTarget {
                                 upstream: &upstream_name,
@@ -472,12 +545,12 @@ impl QUICListener {
                         );
                         warn!(
                             "request_id=unassigned route={} denied by local auth policy",
-                            upstream_name
+                            route_name
                         );
                         let Some(response) = rejection_response.as_ref() else {
                             warn!(
                                 "request_id=unassigned route={} missing admission rejection response for unauthorized decision",
-                                upstream_name
+                                route_name
                             );
                             Self::send_simple_response(
                                 h3,
@@ -499,7 +572,8 @@ impl QUICListener {
                         let _ = observe_admission_outcome(
                             metrics,
                             OutcomeRouteTarget {
-                                route: &upstream_name,
+                                route: &route_name,
+                                priority: Some(&priority.name),
                             },
                             Some(OutcomeBackendTarget {
                                 upstream: &upstream_name,
@@ -517,7 +591,7 @@ impl QUICListener {
                         let Some(response) = rejection_response.as_ref() else {
                             warn!(
                                 "request_id=unassigned route={} missing admission rejection response for rate-limited decision",
-                                upstream_name
+                                route_name
                             );
                             Self::send_simple_response(
                                 h3,
@@ -538,7 +612,8 @@ impl QUICListener {
                         let _ = observe_admission_outcome(
                             metrics,
                             OutcomeRouteTarget {
-                                route: &upstream_name,
+                                route: &route_name,
+                                priority: Some(&priority.name),
                             },
                             Some(OutcomeBackendTarget {
                                 upstream: &upstream_name,
@@ -554,7 +629,7 @@ impl QUICListener {
                         let Some(response) = rejection_response.as_ref() else {
                             warn!(
                                 "request_id=unassigned route={} missing admission rejection response for overload decision",
-                                upstream_name
+                                route_name
                             );
                             Self::send_simple_response(
                                 h3,
@@ -576,7 +651,7 @@ impl QUICListener {
                     }
                 }
 
-                let external_auth = upstream_policy.upstream_auth.external_auth.clone();
+                let external_auth = route_policy.upstream_auth.external_auth.clone();
                 let auth_disposition = external_auth
                     .as_ref()
                     .map(|auth| ExternalAuthTaskConfig::from_external_auth(auth).disposition);
@@ -586,6 +661,7 @@ impl QUICListener {
                     path: Arc::<str>::from(path),
                     authority: authority.map(Arc::<str>::from),
                     headers: Arc::new(headers.to_vec()),
+                    route_name: Arc::<str>::from(route_name.as_str()),
                     upstream_name: Arc::<str>::from(upstream_name.as_str()),
                     route_reason: Arc::<str>::from(route_reason.as_str()),
                     route_path_len,
@@ -613,8 +689,12 @@ impl QUICListener {
                         .traceparent
                         .as_deref()
                         .map(Arc::<str>::from),
-                    host_policy: upstream_policy.host.0.clone(),
-                    forwarded_header_policy: upstream_policy.forwarded_headers.0.clone(),
+                    host_policy: route_policy.host.0.clone(),
+                    forwarded_header_policy: route_policy.forwarded_headers.0.clone(),
+                    path_rewrite: route_policy.path_rewrite.clone(),
+                    mirror,
+                    fault,
+                    limits: route_policy.limits.clone(),
                     auth_header_mutations: Vec::new(),
                 });
                 let dispatch_ready = Self::build_dispatch_ready_candidate(
@@ -660,6 +740,58 @@ impl QUICListener {
         Ok(prepared)
     }
 
+    fn respond_with_route_action(
+        stream_id: u64,
+        h3: &mut quiche::h3::Connection,
+        quic: &mut quiche::Connection,
+        request: LocalRouteRequest<'_>,
+        local: &LocalRoute,
+        metrics: &Metrics,
+        resilience: &RuntimeResilience,
+    ) -> Result<(), quiche::h3::Error> {
+        let LocalRouteRequest {
+            peer_address,
+            request_start,
+            method,
+            path,
+            authority,
+            header_lookup,
+        } = request;
+        if let Some(rejection) = evaluate_route_action_admission(
+            local,
+            Some(header_lookup),
+            resilience,
+            metrics,
+            request_start,
+            |rule| {
+                Self::resolve_scoped_rate_limit_key(
+                    rule,
+                    &local.route_name,
+                    method,
+                    path,
+                    authority,
+                    peer_address,
+                    Some(header_lookup),
+                )
+            },
+        ) {
+            return Self::send_admission_rejection_response(h3, quic, stream_id, &rejection);
+        }
+
+        let response =
+            route_action_response(&local.action, path, authority, is_head_method(method));
+        Self::send_response_with_headers(
+            h3,
+            quic,
+            stream_id,
+            response.status,
+            &response.body,
+            &response.headers,
+        )?;
+        observe_route_action_response(metrics, local, request_start, response.status);
+        Ok(())
+    }
+
     pub(super) fn start_request_auth(
         stream_id: u64,
         h3: &mut quiche::h3::Connection,
@@ -681,8 +813,14 @@ impl QUICListener {
             }
             PreAdmissionNextState::ReadyForPostAuthAdmission(request) => {
                 let mut request = *request;
+                let total_request_timeout = request
+                    .state
+                    .pending_forward
+                    .limits
+                    .backend_total_request_timeout
+                    .unwrap_or(backend_total_request_timeout);
                 request.state.context.total_request_deadline =
-                    request.state.context.start + backend_total_request_timeout;
+                    request.state.context.start + total_request_timeout;
                 Ok(Some(StartedRequestEnvelope {
                     envelope: request.into_dispatch_ready_envelope(
                         routing_transparency_enabled,
@@ -693,8 +831,15 @@ impl QUICListener {
             }
             PreAdmissionNextState::RequiresExternalAuth(request) => {
                 let mut request = *request;
+                let total_request_timeout = request
+                    .request
+                    .state
+                    .pending_forward
+                    .limits
+                    .backend_total_request_timeout
+                    .unwrap_or(backend_total_request_timeout);
                 request.request.state.context.total_request_deadline =
-                    request.request.state.context.start + backend_total_request_timeout;
+                    request.request.state.context.start + total_request_timeout;
                 let auth_disposition = request.auth_disposition;
                 let pending_forward = Arc::clone(&request.request.state.pending_forward);
                 let auth_start = match start_external_auth_task(
@@ -710,13 +855,13 @@ impl QUICListener {
                                     warn!(
                                         "request_id={} route={} external auth startup failed open: timeout",
                                         request.request.request_id(),
-                                        request.request.upstream_name()
+                                        request.request.route_name()
                                     );
                                 } else if let Some(error) = error {
                                     warn!(
                                         "request_id={} route={} external auth startup failed open: {:?}",
                                         request.request.request_id(),
-                                        request.request.upstream_name(),
+                                        request.request.route_name(),
                                         error
                                     );
                                 }
@@ -732,7 +877,8 @@ impl QUICListener {
                                 let _ = observe_admission_outcome(
                                     metrics,
                                     OutcomeRouteTarget {
-                                        route: request.request.upstream_name(),
+                                        route: request.request.route_name(),
+                                        priority: None,
                                     },
                                     Some(OutcomeBackendTarget {
                                         upstream: request.request.upstream_name(),
@@ -747,14 +893,14 @@ impl QUICListener {
                                     error!(
                                         "request_id={} route={} external auth startup failed: {:?}",
                                         request.request.request_id(),
-                                        request.request.upstream_name(),
+                                        request.request.route_name(),
                                         error
                                     );
                                 } else {
                                     error!(
                                         "request_id={} route={} external auth startup failed",
                                         request.request.request_id(),
-                                        request.request.upstream_name()
+                                        request.request.route_name()
                                     );
                                 }
                                 Self::send_simple_response(h3, quic, stream_id, status, body)?;
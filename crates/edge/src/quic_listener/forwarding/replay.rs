@@ -0,0 +1,209 @@
+//! Request body replay for retries and hedges. On routes with
+//! `policy.replay_body_max_bytes`, eligible request bodies pass through a tee
+//! that feeds the primary first and keeps a copy, reserved against the global
+//! request buffer cap. A body that outgrows either limit keeps streaming to the
+//! primary but can no longer be resent.
+
+use tokio::sync::watch;
+
+use spooky_errors::{is_idempotent_method, is_idempotent_request};
+
+use super::*;
+use crate::runtime::connection::stream::RequestMode;
+
+/// A complete body. Its bytes stay reserved against the global request
+/// buffer cap until the last retry or hedge that could resend it is gone.
+struct ReplayBuffer {
+    body: Bytes,
+    metrics: Arc<Metrics>,
+}
+
+impl Drop for ReplayBuffer {
+    fn drop(&mut self) {
+        self.metrics.release_request_buffer(self.body.len());
+    }
+}
+
+enum ReplayState {
+    Buffering,
+    Complete(Arc<ReplayBuffer>),
+    Unavailable,
+}
+
+/// A request body that retries and hedges can resend.
+pub(super) struct ReplayBody {
+    state: watch::Receiver<ReplayState>,
+    admitted_by_key: bool,
+}
+
+impl ReplayBody {
+    /// Decides whether this request's body can be replayed. Requests without
+    /// a body always can; tunnels never can. Other requests need a route with
+    /// replay enabled and an idempotent method, or POST/PATCH carrying the
+    /// route's idempotency header. Returns the sender the stream should feed
+    /// in place of `body_tx`.
+    pub(super) fn start(
+        pending_forward: &PendingForward,
+        request_mode: RequestMode,
+        body_tx: Option<mpsc::Sender<Bytes>>,
+        exec_ctx: &ForwardingExecutionCtx<'_>,
+        metrics: &Arc<Metrics>,
+    ) -> (Option<mpsc::Sender<Bytes>>, Option<Self>) {
+        if request_mode.is_tunnel() {
+            return (body_tx, None);
+        }
+        if request_mode.bodyless_mode() {
+            return (body_tx, Some(Self::complete(Bytes::new(), metrics)));
+        }
+        let Some(policy) = pending_forward.limits.replay.as_ref() else {
+            return (body_tx, None);
+        };
+        let keyed = extract_header_value(
+            &pending_forward.headers,
+            policy.idempotency_header.as_bytes(),
+        )
+        .is_some();
+        if !is_idempotent_request(&pending_forward.method, keyed) {
+            return (body_tx, None);
+        }
+        let declared_length = extract_header_value(&pending_forward.headers, b"content-length")
+            .and_then(|value| value.parse::<usize>().ok());
+        if declared_length.is_some_and(|length| length > policy.max_body_bytes) {
+            metrics.inc_request_replay_fallback();
+            return (body_tx, None);
+        }
+        let Some(primary_tx) = body_tx else {
+            return (None, None);
+        };
+
+        let (client_tx, client_rx) = mpsc::channel::<Bytes>(REQUEST_CHUNK_CHANNEL_CAPACITY);
+        let (state_tx, state_rx) = watch::channel(ReplayState::Buffering);
+        let tee = ReplayTee {
+            max_body_bytes: policy.max_body_bytes,
+            declared_length,
+            global_cap_bytes: exec_ctx.request_buffer_global_cap_bytes,
+            metrics: Arc::clone(metrics),
+        };
+        if !spawn_async_task(
+            tee.run(client_rx, primary_tx.clone(), state_tx),
+            "request_replay_body",
+        ) {
+            return (Some(primary_tx), None);
+        }
+        let replay = Self {
+            state: state_rx,
+            admitted_by_key: !is_idempotent_method(&pending_forward.method),
+        };
+        (Some(client_tx), Some(replay))
+    }
+
+    fn complete(body: Bytes, metrics: &Arc<Metrics>) -> Self {
+        let buffer = ReplayBuffer {
+            body,
+            metrics: Arc::clone(metrics),
+        };
+        let (_, state) = watch::channel(ReplayState::Complete(Arc::new(buffer)));
+        Self {
+            state,
+            admitted_by_key: false,
+        }
+    }
+
+    /// True for POST and PATCH requests made retryable by an idempotency key.
+    pub(super) fn admitted_by_key(&self) -> bool {
+        self.admitted_by_key
+    }
+
+    /// The body if the client has finished sending it. Hedges use this and
+    /// never wait on an upload.
+    pub(super) fn ready(&self) -> Option<Bytes> {
+        match &*self.state.borrow() {
+            ReplayState::Complete(buffer) => Some(buffer.body.clone()),
+            ReplayState::Buffering | ReplayState::Unavailable => None,
+        }
+    }
+
+    /// Waits for the client to finish the body. `None` when it outgrew the
+    /// replay buffer or the upload was cut short.
+    pub(super) async fn wait(&self) -> Option<Bytes> {
+        let mut state = self.state.clone();
+        let state = state
+            .wait_for(|state| !matches!(state, ReplayState::Buffering))
+            .await
+            .ok()?;
+        match &*state {
+            ReplayState::Complete(buffer) => Some(buffer.body.clone()),
+            ReplayState::Buffering | ReplayState::Unavailable => None,
+        }
+    }
+}
+
+struct ReplayTee {
+    max_body_bytes: usize,
+    declared_length: Option<usize>,
+    global_cap_bytes: usize,
+    metrics: Arc<Metrics>,
+}
+
+impl ReplayTee {
+    async fn run(
+        self,
+        mut client_rx: mpsc::Receiver<Bytes>,
+        primary_tx: mpsc::Sender<Bytes>,
+        state_tx: watch::Sender<ReplayState>,
+    ) {
+        let mut body = Vec::new();
+        let mut buffering = true;
+        let mut primary_open = true;
+        while let Some(chunk) = client_rx.recv().await {
+            if buffering && !self.keep(&mut body, &chunk) {
+                buffering = false;
+                self.metrics.release_request_buffer(body.len());
+                body = Vec::new();
+                self.metrics.inc_request_replay_fallback();
+                state_tx.send_replace(ReplayState::Unavailable);
+            }
+            // Keep reading after the primary stops: a retry may still need the
+            // rest of the body.
+            if primary_open && primary_tx.send(chunk).await.is_err() {
+                primary_open = false;
+            }
+            if !primary_open && (!buffering || state_tx.is_closed()) {
+                break;
+            }
+        }
+        drop(client_rx);
+        drop(primary_tx);
+        if !buffering {
+            return;
+        }
+
+        // A channel closed short of the declared length is a cut-off upload.
+        if state_tx.is_closed()
+            || self
+                .declared_length
+                .is_some_and(|length| length != body.len())
+        {
+            self.metrics.release_request_buffer(body.len());
+            state_tx.send_replace(ReplayState::Unavailable);
+            return;
+        }
+        let buffer = ReplayBuffer {
+            body: Bytes::from(body),
+            metrics: self.metrics,
+        };
+        state_tx.send_replace(ReplayState::Complete(Arc::new(buffer)));
+    }
+
+    fn keep(&self, body: &mut Vec<u8>, chunk: &Bytes) -> bool {
+        if body.len() + chunk.len() > self.max_body_bytes
+            || !self
+                .metrics
+                .try_reserve_request_buffer(chunk.len(), self.global_cap_bytes)
+        {
+            return false;
+        }
+        body.extend_from_slice(chunk);
+        true
+    }
+}
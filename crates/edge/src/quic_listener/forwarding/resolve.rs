@@ -1,6 +1,6 @@
-use spooky_config::runtime::RuntimeUpstreamPolicy;
+use spooky_config::runtime::{RuntimeRouteAction, RuntimeTrafficSplit, RuntimeUpstreamPolicy};
 
-use super::{lb_key::ResolvedLbKey, *};
+use super::{lb_key::ResolvedLbKey, session_affinity, *};
 use crate::runtime::connection::outcome::{OutcomeRouteTarget, observe_proxy_error_outcome};
 
 pub(in crate::quic_listener) struct RouteResolutionRequest<'a> {
@@ -8,6 +8,7 @@ pub(in crate::quic_listener) struct RouteResolutionRequest<'a> {
     pub(in crate::quic_listener) path: &'a str,
     pub(in crate::quic_listener) authority: Option<&'a str>,
     pub(in crate::quic_listener) cid_key: Option<&'a str>,
+    pub(in crate::quic_listener) client_addr: Option<SocketAddr>,
     pub(in crate::quic_listener) header_lookup: Option<&'a LbHeaderLookup<'a>>,
 }
 
@@ -24,24 +25,58 @@ impl<'a> RouteResolutionRequest<'a> {
             path,
             authority,
             cid_key,
+            client_addr: None,
             header_lookup,
         }
     }
+
+    pub(in crate::quic_listener) fn with_client_addr(mut self, client_addr: SocketAddr) -> Self {
+        self.client_addr = Some(client_addr);
+        self
+    }
 }
 
 pub(in crate::quic_listener) struct ResolvedRoute {
+    pub(in crate::quic_listener) route_name: String,
     pub(in crate::quic_listener) upstream_name: String,
     pub(in crate::quic_listener) upstream_pool: Arc<RwLock<UpstreamPool>>,
-    pub(in crate::quic_listener) upstream_policy: RuntimeUpstreamPolicy,
+    pub(in crate::quic_listener) route_policy: RuntimeUpstreamPolicy,
     pub(in crate::quic_listener) route_path_len: usize,
     pub(in crate::quic_listener) route_host_specific: bool,
     pub(in crate::quic_listener) route_reason: RouteDecisionReason,
+    /// Set when a weighted traffic split chose `upstream_name`.
+    pub(in crate::quic_listener) route_split: bool,
+}
+
+/// Route answered by the edge through a `redirect` or `direct_response` action;
+/// no upstream pool or backend is involved.
+pub(in crate::quic_listener) struct LocalRoute {
+    pub(in crate::quic_listener) route_name: String,
+    pub(in crate::quic_listener) route_policy: RuntimeUpstreamPolicy,
+    pub(in crate::quic_listener) action: Arc<RuntimeRouteAction>,
+}
+
+/// Outcome of route resolution: a target on an upstream, or a route the edge
+/// answers itself.
+pub(in crate::quic_listener) enum RouteResolution<T> {
+    Backend(T),
+    Local(LocalRoute),
+}
+
+pub(super) enum RouteTarget {
+    Upstream(ResolvedRoute),
+    Local(LocalRoute),
 }
 
 pub(in crate::quic_listener) struct SelectedBackend {
     pub(in crate::quic_listener) backend_addr: String,
     pub(in crate::quic_listener) backend_index: usize,
     pub(in crate::quic_listener) backend_lb: String,
+    pub(in crate::quic_listener) backend_zone: Option<String>,
+    pub(in crate::quic_listener) local_zone: Option<String>,
+    /// `Set-Cookie` value pinning the client to this backend, when session
+    /// affinity is on and the request's cookie does not already name it.
+    pub(in crate::quic_listener) affinity_cookie: Option<String>,
 }
 
 pub(in crate::quic_listener) struct ResolvedBackend {
@@ -50,33 +85,38 @@ pub(in crate::quic_listener) struct ResolvedBackend {
 }
 
 pub(super) struct ForwardingResolvedTarget {
+    pub(super) route_name: String,
     pub(super) upstream_name: String,
     pub(super) upstream_pool: Arc<RwLock<UpstreamPool>>,
-    pub(super) upstream_policy: RuntimeUpstreamPolicy,
+    pub(super) route_policy: RuntimeUpstreamPolicy,
     pub(super) route_path_len: usize,
     pub(super) route_host_specific: bool,
     pub(super) route_reason: String,
     pub(super) backend_addr: String,
     pub(super) backend_index: usize,
     pub(super) backend_lb: String,
+    pub(super) affinity_cookie: Option<String>,
 }
 
 pub(in crate::quic_listener) struct BootstrapResolvedTarget {
+    pub(in crate::quic_listener) route_name: String,
     pub(in crate::quic_listener) upstream_name: String,
     pub(in crate::quic_listener) upstream_pool: Arc<RwLock<UpstreamPool>>,
-    pub(in crate::quic_listener) upstream_policy: RuntimeUpstreamPolicy,
+    pub(in crate::quic_listener) route_policy: RuntimeUpstreamPolicy,
     pub(in crate::quic_listener) backend_addr: String,
     pub(in crate::quic_listener) backend_index: usize,
+    pub(in crate::quic_listener) affinity_cookie: Option<String>,
 }
 
 pub(in crate::quic_listener) struct BootstrapResolutionInput<'a> {
     pub(in crate::quic_listener) method: &'a str,
     pub(in crate::quic_listener) path: &'a str,
     pub(in crate::quic_listener) authority: Option<&'a str>,
+    pub(in crate::quic_listener) client_addr: SocketAddr,
     pub(in crate::quic_listener) header_lookup: Option<&'a LbHeaderLookup<'a>>,
     pub(in crate::quic_listener) routing_index: &'a RouteIndex,
     pub(in crate::quic_listener) upstream_pools: &'a HashMap<String, Arc<RwLock<UpstreamPool>>>,
-    pub(in crate::quic_listener) upstream_policies: &'a HashMap<String, RuntimeUpstreamPolicy>,
+    pub(in crate::quic_listener) route_policies: &'a HashMap<String, RuntimeUpstreamPolicy>,
     pub(in crate::quic_listener) metrics: &'a Metrics,
     pub(in crate::quic_listener) elapsed: Duration,
 }
@@ -84,6 +124,8 @@ pub(in crate::quic_listener) struct BootstrapResolutionInput<'a> {
 struct BackendSelectionPlan {
     lb_type: String,
     lb_key: String,
+    /// Backend id from a valid session affinity cookie.
+    pinned_backend_id: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -194,13 +236,14 @@ impl QUICListener {
         authority: Option<&str>,
         tunnel_mode: TunnelMode,
         sticky_cid_key: &str,
+        peer_address: SocketAddr,
         header_lookup: Option<&LbHeaderLookup<'_>>,
         routing_index: &RouteIndex,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         metrics: &Metrics,
         elapsed: Duration,
-    ) -> Result<ForwardingResolvedTarget, ProxyError> {
+    ) -> Result<RouteResolution<ForwardingResolvedTarget>, ProxyError> {
         let route_method = if matches!(tunnel_mode, TunnelMode::Websocket) {
             "GET"
         } else {
@@ -212,15 +255,17 @@ impl QUICListener {
             authority,
             Some(sticky_cid_key),
             header_lookup,
-        );
+        )
+        .with_client_addr(peer_address);
         let ResolvedBackend { route, backend } =
             match Self::resolve_backend_without_inflight_request(
                 &resolution_request,
                 upstream_pools,
-                upstream_policies,
+                route_policies,
                 routing_index,
             ) {
-                Ok(resolved) => resolved,
+                Ok(RouteResolution::Backend(resolved)) => resolved,
+                Ok(RouteResolution::Local(local)) => return Ok(RouteResolution::Local(local)),
                 Err(err) => {
                     Self::observe_route_resolution_failure(
                         &resolution_request,
@@ -232,78 +277,106 @@ impl QUICListener {
                 }
             };
         let ResolvedRoute {
+            route_name,
             upstream_name,
             upstream_pool,
-            upstream_policy,
+            route_policy,
             route_path_len,
             route_host_specific,
             route_reason,
+            route_split,
         } = route;
+        if route_split {
+            metrics.record_route_split(&route_name, &upstream_name);
+        }
         let SelectedBackend {
             backend_addr,
             backend_index,
             backend_lb,
+            backend_zone,
+            local_zone,
+            affinity_cookie,
         } = backend;
+        metrics.record_upstream_zone_request(
+            &upstream_name,
+            backend_zone.as_deref(),
+            local_zone.as_deref(),
+        );
 
-        Ok(ForwardingResolvedTarget {
+        Ok(RouteResolution::Backend(ForwardingResolvedTarget {
+            route_name,
             upstream_name,
             upstream_pool,
-            upstream_policy,
+            route_policy,
             route_path_len,
             route_host_specific,
             route_reason: format!("{route_reason:?}"),
             backend_addr,
             backend_index,
             backend_lb,
-        })
+            affinity_cookie,
+        }))
     }
 
     pub(in crate::quic_listener) fn resolve_bootstrap_target(
         input: BootstrapResolutionInput<'_>,
-    ) -> Result<BootstrapResolvedTarget, ProxyError> {
+    ) -> Result<RouteResolution<BootstrapResolvedTarget>, ProxyError> {
         let BootstrapResolutionInput {
             method,
             path,
             authority,
+            client_addr,
             header_lookup,
             routing_index,
             upstream_pools,
-            upstream_policies,
+            route_policies,
             metrics,
             elapsed,
         } = input;
         let resolution_request =
-            RouteResolutionRequest::new(method, path, authority, None, header_lookup);
+            RouteResolutionRequest::new(method, path, authority, None, header_lookup)
+                .with_client_addr(client_addr);
         let ResolvedBackend { route, backend } = match Self::resolve_backend_internal(
             &resolution_request,
             upstream_pools,
-            upstream_policies,
+            route_policies,
             routing_index,
             true,
         ) {
-            Ok(resolved) => resolved,
+            Ok(RouteResolution::Backend(resolved)) => resolved,
+            Ok(RouteResolution::Local(local)) => return Ok(RouteResolution::Local(local)),
             Err(err) => {
                 Self::observe_route_resolution_failure(&resolution_request, &err, metrics, elapsed);
                 return Err(err);
             }
         };
+        if route.route_split {
+            metrics.record_route_split(&route.route_name, &route.upstream_name);
+        }
+        metrics.record_upstream_zone_request(
+            &route.upstream_name,
+            backend.backend_zone.as_deref(),
+            backend.local_zone.as_deref(),
+        );
 
-        Ok(BootstrapResolvedTarget {
+        Ok(RouteResolution::Backend(BootstrapResolvedTarget {
+            route_name: route.route_name,
             upstream_name: route.upstream_name,
             upstream_pool: route.upstream_pool,
-            upstream_policy: route.upstream_policy,
+            route_policy: route.route_policy,
             backend_addr: backend.backend_addr,
             backend_index: backend.backend_index,
-        })
+            affinity_cookie: backend.affinity_cookie,
+        }))
     }
 
     #[allow(clippy::type_complexity)]
-    fn resolve_route_target(
+    pub(super) fn resolve_route_target(
         request: &RouteResolutionRequest<'_>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         routing_index: &RouteIndex,
-    ) -> Result<ResolvedRoute, ProxyError> {
+    ) -> Result<RouteTarget, ProxyError> {
         if request.method.is_empty() || request.path.is_empty() {
             return Err(ProxyError::Transport("empty method or path".into()));
         }
@@ -311,29 +384,67 @@ impl QUICListener {
         let route_decision = routing_index
             .lookup_with_decision_for_method(request.path, request.authority, Some(request.method))
             .ok_or_else(|| ProxyError::Transport(format!("no route for {}", request.path)))?;
-        let upstream_name = route_decision.upstream.to_string();
+        let route_policy = route_policies
+            .get(route_decision.route)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(action) = route_decision.action {
+            debug!(
+                "Resolved edge-answered route method={} path={} authority={} route={} reason={:?}",
+                request.method,
+                request.path,
+                request.authority.unwrap_or("-"),
+                route_decision.route,
+                route_decision.reason
+            );
+            return Ok(RouteTarget::Local(LocalRoute {
+                route_name: route_decision.route.to_string(),
+                route_policy,
+                action: Arc::clone(action),
+            }));
+        }
+        let upstream_name = route_decision
+            .split
+            .map(|split| {
+                let point = Self::route_split_point(split, request);
+                match route_decision.canary_percent {
+                    Some(canary_percent) => split.pick_with_canary_percent(point, canary_percent),
+                    None => split.pick(point),
+                }
+            })
+            .unwrap_or(route_decision.upstream);
         let upstream_pool = upstream_pools
-            .get(route_decision.upstream)
+            .get(upstream_name)
             .ok_or_else(|| ProxyError::Transport(format!("pool not found: {upstream_name}")))?
             .clone();
-        let upstream_policy = upstream_policies
-            .get(route_decision.upstream)
-            .cloned()
-            .unwrap_or_default();
 
-        Ok(ResolvedRoute {
-            upstream_name,
+        Ok(RouteTarget::Upstream(ResolvedRoute {
+            route_name: route_decision.route.to_string(),
+            upstream_name: upstream_name.to_string(),
             upstream_pool,
-            upstream_policy,
+            route_policy,
             route_path_len: route_decision.matched_path_len,
             route_host_specific: route_decision.host_specific,
             route_reason: route_decision.reason,
-        })
+            route_split: route_decision.split.is_some(),
+        }))
+    }
+
+    /// Sticky splits hash the configured request key so one client keeps its
+    /// target; requests without a key (or splits without one) draw at random.
+    fn route_split_point(split: &RuntimeTrafficSplit, request: &RouteResolutionRequest<'_>) -> u64 {
+        split
+            .key_spec
+            .as_ref()
+            .and_then(|spec| Self::resolve_request_key_for_runtime_request(spec, request))
+            .map(|key| crate::hash::stable_hash64(key.as_bytes()))
+            .unwrap_or_else(rand::random::<u64>)
     }
 
     fn build_backend_selection_plan(
         request: &RouteResolutionRequest<'_>,
         pool: &UpstreamPool,
+        upstream_name: &str,
     ) -> BackendSelectionPlan {
         let ResolvedLbKey {
             value: lb_key,
@@ -344,8 +455,11 @@ impl QUICListener {
             request,
         );
         BackendSelectionPlan {
-            lb_type: pool.lb_strategy().canonical_name().to_string(),
+            lb_type: pool.lb_policy().strategy_name().to_string(),
             lb_key,
+            pinned_backend_id: pool.session_affinity().and_then(|affinity| {
+                session_affinity::pinned_backend_id(affinity, upstream_name, request.header_lookup)
+            }),
         }
     }
 
@@ -362,15 +476,26 @@ impl QUICListener {
         ProxyError::Transport("no healthy servers".into())
     }
 
+    /// Honours a session affinity pin while its backend is healthy, and
+    /// balances normally otherwise. The cookie is re-issued whenever the
+    /// chosen backend is not the one it names.
     fn select_backend_with_write_lock(
         pool: &mut UpstreamPool,
         plan: &BackendSelectionPlan,
+        upstream_name: &str,
         begin_request: bool,
     ) -> Result<SelectedBackend, ProxyError> {
-        let idx = if begin_request {
-            pool.pick(plan.lb_key.as_str())
-        } else {
-            pool.pick_without_begin(plan.lb_key.as_str())
+        let pinned = plan.pinned_backend_id.as_deref().and_then(|id| {
+            if begin_request {
+                pool.pick_by_id(id)
+            } else {
+                pool.pick_by_id_without_begin(id)
+            }
+        });
+        let idx = match pinned {
+            Some(idx) => Some(idx),
+            None if begin_request => pool.pick(plan.lb_key.as_str()),
+            None => pool.pick_without_begin(plan.lb_key.as_str()),
         }
         .ok_or_else(|| Self::no_healthy_servers_error(pool))?;
         let backend_addr = pool
@@ -381,11 +506,20 @@ impl QUICListener {
             backend_addr,
             backend_index: idx,
             backend_lb: plan.lb_type.clone(),
+            backend_zone: pool.backend_zone(idx).map(str::to_string),
+            local_zone: pool.local_zone().map(str::to_string),
+            affinity_cookie: pool.session_affinity().and_then(|affinity| {
+                let id = pool.backend_id(idx)?;
+                (plan.pinned_backend_id.as_deref() != Some(id))
+                    .then(|| session_affinity::set_cookie_header(affinity, upstream_name, id))
+                    .flatten()
+            }),
         })
     }
 
     fn select_backend_from_pool(
         request: &RouteResolutionRequest<'_>,
+        upstream_name: &str,
         upstream_pool: &Arc<RwLock<UpstreamPool>>,
         begin_request: bool,
     ) -> Result<SelectedBackend, ProxyError> {
@@ -395,24 +529,26 @@ impl QUICListener {
         if pool.is_empty() {
             return Err(Self::no_servers_in_upstream_error());
         }
-        let plan = Self::build_backend_selection_plan(request, &pool);
-        Self::select_backend_with_write_lock(&mut pool, &plan, begin_request)
+        let plan = Self::build_backend_selection_plan(request, &pool, upstream_name);
+        Self::select_backend_with_write_lock(&mut pool, &plan, upstream_name, begin_request)
     }
 
     fn log_backend_selection(
         request: &RouteResolutionRequest<'_>,
         backend_addr: &str,
         lb_type: &str,
+        route_name: &str,
         upstream_name: &str,
         route_path_len: usize,
         route_host_specific: bool,
         route_reason: &RouteDecisionReason,
     ) {
         debug!(
-            "Resolved backend method={} path={} authority={} route={} backend={} via={} path_len={} host_specific={} reason={:?}",
+            "Resolved backend method={} path={} authority={} route={} upstream={} backend={} via={} path_len={} host_specific={} reason={:?}",
             request.method,
             request.path,
             request.authority.unwrap_or("-"),
+            route_name,
             upstream_name,
             backend_addr,
             lb_type,
@@ -425,36 +561,49 @@ impl QUICListener {
     fn resolve_backend_internal(
         request: &RouteResolutionRequest<'_>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         routing_index: &RouteIndex,
         begin_request: bool,
-    ) -> Result<ResolvedBackend, ProxyError> {
-        let route =
-            Self::resolve_route_target(request, upstream_pools, upstream_policies, routing_index)?;
-        let backend = Self::select_backend_from_pool(request, &route.upstream_pool, begin_request)?;
+    ) -> Result<RouteResolution<ResolvedBackend>, ProxyError> {
+        let route = match Self::resolve_route_target(
+            request,
+            upstream_pools,
+            route_policies,
+            routing_index,
+        )? {
+            RouteTarget::Upstream(route) => route,
+            RouteTarget::Local(local) => return Ok(RouteResolution::Local(local)),
+        };
+        let backend = Self::select_backend_from_pool(
+            request,
+            &route.upstream_name,
+            &route.upstream_pool,
+            begin_request,
+        )?;
 
         Self::log_backend_selection(
             request,
             &backend.backend_addr,
             &backend.backend_lb,
+            &route.route_name,
             &route.upstream_name,
             route.route_path_len,
             route.route_host_specific,
             &route.route_reason,
         );
-        Ok(ResolvedBackend { route, backend })
+        Ok(RouteResolution::Backend(ResolvedBackend { route, backend }))
     }
 
     fn resolve_backend_without_inflight_request(
         request: &RouteResolutionRequest<'_>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         routing_index: &RouteIndex,
-    ) -> Result<ResolvedBackend, ProxyError> {
+    ) -> Result<RouteResolution<ResolvedBackend>, ProxyError> {
         Self::resolve_backend_internal(
             request,
             upstream_pools,
-            upstream_policies,
+            route_policies,
             routing_index,
             false,
         )
@@ -464,15 +613,21 @@ impl QUICListener {
     pub(in crate::quic_listener) fn resolve_backend_request_for_test(
         request: &RouteResolutionRequest<'_>,
         upstream_pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
-        upstream_policies: &HashMap<String, RuntimeUpstreamPolicy>,
+        route_policies: &HashMap<String, RuntimeUpstreamPolicy>,
         routing_index: &RouteIndex,
     ) -> Result<ResolvedBackend, ProxyError> {
-        Self::resolve_backend_internal(
+        match Self::resolve_backend_internal(
             request,
             upstream_pools,
-            upstream_policies,
+            route_policies,
             routing_index,
             true,
-        )
+        )? {
+            RouteResolution::Backend(resolved) => Ok(resolved),
+            RouteResolution::Local(local) => Err(ProxyError::Transport(format!(
+                "route {} is answered by the edge",
+                local.route_name
+            ))),
+        }
     }
 }
@@ -2,6 +2,7 @@ use bytes::Bytes;
 use spooky_errors::{UpstreamProxyErrorKind, classify_upstream_proxy_error};
 use tokio::sync::mpsc::error::TryRecvError;
 
+use super::fault::FAULT_TRUNCATED_BODY_ERROR;
 use super::*;
 use crate::runtime::connection::{
     auth::ExternalAuthDecision,
@@ -67,18 +68,20 @@ impl QUICListener {
         success: ForwardSuccess,
         progress_config: &StreamProgressConfig,
     ) -> ResponseStartDecision {
-        let (status, resp_headers, response_body, prebuilt_response_chunk_rx) = match success {
-            ForwardSuccess::Response {
-                status,
-                headers,
-                body,
-            } => (status, headers, Some(body), None),
-            ForwardSuccess::Tunnel {
-                status,
-                headers,
-                response_chunk_rx,
-            } => (status, headers, None, Some(response_chunk_rx)),
-        };
+        let (status, resp_headers, response_body, prebuilt_response_chunk_rx, truncate_after_bytes) =
+            match success {
+                ForwardSuccess::Response {
+                    status,
+                    headers,
+                    body,
+                    truncate_after_bytes,
+                } => (status, headers, Some(body), None, truncate_after_bytes),
+                ForwardSuccess::Tunnel {
+                    status,
+                    headers,
+                    response_chunk_rx,
+                } => (status, headers, None, Some(response_chunk_rx), None),
+            };
         let request_mode = req.request_mode();
         let tunnel_response = is_tunnel_response(req.tunnel_mode, status);
         let response_body_mode = if tunnel_response {
@@ -172,6 +175,9 @@ impl QUICListener {
             b"alt-svc".to_vec(),
             format!("h3=\":{}\"; ma=86400", progress_config.listen_port).into_bytes(),
         ));
+        if let Some(cookie) = req.affinity_cookie.as_ref() {
+            headers.push((b"set-cookie".to_vec(), cookie.as_bytes().to_vec()));
+        }
 
         let defer_headers_until_body_validated = matches!(
             preflight_guardrail,
@@ -229,6 +235,7 @@ impl QUICListener {
                 progressive_emission_allowed: progressive_body_emission_allowed,
                 defer_headers_until_body_validated,
                 tunnel_response,
+                truncate_after_bytes,
             },
             observation,
         }
@@ -762,7 +769,13 @@ impl QUICListener {
                         return;
                     }
                     Ok(Some(Ok(f))) => match f.into_data() {
-                        Ok(data) => {
+                        Ok(mut data) => {
+                            let truncated = pump.truncate_after_bytes.is_some_and(|limit| {
+                                response_bytes_received.saturating_add(data.len()) > limit
+                            });
+                            if let Some(limit) = pump.truncate_after_bytes.filter(|_| truncated) {
+                                data.truncate(limit.saturating_sub(response_bytes_received));
+                            }
                             if !data.is_empty() {
                                 last_body_progress_at = tokio::time::Instant::now();
                             }
@@ -812,6 +825,14 @@ impl QUICListener {
                                     ProgressiveEmissionPolicy::SuppressBody => {}
                                 }
                             }
+                            if truncated {
+                                let _ = chunk_tx
+                                    .send(ResponseChunk::Error(ProxyError::Transport(
+                                        FAULT_TRUNCATED_BODY_ERROR.into(),
+                                    )))
+                                    .await;
+                                return;
+                            }
                         }
                         Err(frame) => {
                             if let Ok(trailers) = frame.into_trailers() {
@@ -911,7 +932,7 @@ impl QUICListener {
                     *status,
                 );
                 resilience
-                    .adaptive_admission
+                    .adaptive_admission_for(req.upstream_name.as_deref())
                     .observe(req.start.elapsed(), false);
                 Self::log_access(req, status.as_u16());
             }
@@ -930,7 +951,7 @@ impl QUICListener {
                     *overload_reason,
                 );
                 resilience
-                    .adaptive_admission
+                    .adaptive_admission_for(req.upstream_name.as_deref())
                     .observe(req.start.elapsed(), true);
                 Self::log_access(req, status.as_u16());
             }
@@ -1085,7 +1106,7 @@ impl QUICListener {
                                 status,
                             );
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                             req.transition_streaming_to_backend_failed(
                                 BackendFailureReason::ResponseWriteFailed,
@@ -1120,7 +1141,7 @@ impl QUICListener {
                                 .unwrap_or(http::StatusCode::BAD_GATEWAY),
                         );
                         resilience
-                            .adaptive_admission
+                            .adaptive_admission_for(req.upstream_name.as_deref())
                             .observe(req.start.elapsed(), true);
                         req.transition_streaming_to_backend_failed(
                             BackendFailureReason::ResponseWriteFailed,
@@ -1159,7 +1180,7 @@ impl QUICListener {
                                     .unwrap_or(http::StatusCode::BAD_GATEWAY),
                             );
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                             req.transition_streaming_to_backend_failed(
                                 BackendFailureReason::ResponseWriteFailed,
@@ -1200,7 +1221,7 @@ impl QUICListener {
                                 .unwrap_or(http::StatusCode::BAD_GATEWAY),
                         );
                         resilience
-                            .adaptive_admission
+                            .adaptive_admission_for(req.upstream_name.as_deref())
                             .observe(req.start.elapsed(), true);
                         req.transition_streaming_to_backend_failed(
                             BackendFailureReason::ResponseWriteFailed,
@@ -1278,7 +1299,7 @@ impl QUICListener {
                                     None,
                                 );
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                             debug!(
                                 "Upstream {} body timeout latency_ms {}",
@@ -1309,7 +1330,7 @@ impl QUICListener {
                                     Some(overload_reason),
                                 );
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                             error!(
                                 "Upstream {} overload in response body path: {}",
@@ -1332,7 +1353,7 @@ impl QUICListener {
                                     None,
                                 );
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                             if let (Some(addr), Some(classified)) =
                                 (req.backend_addr.as_deref(), classified.as_ref())
@@ -1385,7 +1406,7 @@ impl QUICListener {
                         let _ = h3.send_body(quic, stream_id, b"", true);
                     }
                     resilience
-                        .adaptive_admission
+                        .adaptive_admission_for(req.upstream_name.as_deref())
                         .observe(req.start.elapsed(), true);
                     req.transition_streaming_to_timed_out(reason, metrics);
                     terminal = true;
@@ -0,0 +1,85 @@
+//! Cookie-issuing session affinity.
+//!
+//! The cookie value is base64url of a random nonce followed by the backend id
+//! sealed with ChaCha20-Poly1305, with the upstream name as associated data.
+//! Clients cannot read which backend they are pinned to or forge a pin, and
+//! binding the upstream keeps a cookie issued for one upstream from pinning
+//! another that shares the cookie name.
+
+use std::{fmt::Write as _, sync::OnceLock};
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use sha2::{Digest, Sha256};
+use spooky_config::runtime::RuntimeSessionAffinity;
+
+use super::{LbHeaderLookup, lb_key::extract_cookie_value};
+
+/// Key for upstreams without a `secret`. Fixed for the life of the process,
+/// so pins survive config reloads but not restarts.
+static PROCESS_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+
+fn cookie_key(affinity: &RuntimeSessionAffinity) -> Option<LessSafeKey> {
+    let key: [u8; 32] = match affinity.secret.as_deref() {
+        Some(secret) => Sha256::digest(secret.as_bytes()).into(),
+        None => *PROCESS_KEY.get_or_init(rand::random),
+    };
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &key).ok()?;
+    Some(LessSafeKey::new(key))
+}
+
+/// Backend id carried by the request's affinity cookie, when the cookie is
+/// present and opens under this upstream's key.
+pub(super) fn pinned_backend_id(
+    affinity: &RuntimeSessionAffinity,
+    upstream: &str,
+    header_lookup: Option<&LbHeaderLookup<'_>>,
+) -> Option<String> {
+    let cookie_header = header_lookup.and_then(|lookup| lookup(http::header::COOKIE.as_str()))?;
+    let value = extract_cookie_value(&cookie_header, &affinity.cookie_name)?;
+    let mut sealed = URL_SAFE_NO_PAD.decode(value).ok()?;
+    if sealed.len() < NONCE_LEN {
+        return None;
+    }
+    let mut sealed_id = sealed.split_off(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(&sealed).ok()?;
+    let id = cookie_key(affinity)?
+        .open_in_place(nonce, Aad::from(upstream.as_bytes()), &mut sealed_id)
+        .ok()?;
+    String::from_utf8(id.to_vec()).ok()
+}
+
+/// `Set-Cookie` value pinning the client to backend `id`.
+pub(super) fn set_cookie_header(
+    affinity: &RuntimeSessionAffinity,
+    upstream: &str,
+    id: &str,
+) -> Option<String> {
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let mut sealed_id = id.as_bytes().to_vec();
+    cookie_key(affinity)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(upstream.as_bytes()),
+            &mut sealed_id,
+        )
+        .ok()?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&sealed_id);
+
+    let mut header = format!(
+        "{}={}; Path={}",
+        affinity.cookie_name,
+        URL_SAFE_NO_PAD.encode(sealed),
+        affinity.path
+    );
+    if let Some(ttl) = affinity.ttl {
+        let _ = write!(header, "; Max-Age={}", ttl.as_secs());
+    }
+    let _ = write!(header, "; SameSite={}", affinity.same_site.as_attribute());
+    if affinity.secure {
+        header.push_str("; Secure");
+    }
+    header.push_str("; HttpOnly");
+    Some(header)
+}
@@ -10,16 +10,20 @@ use crate::runtime::connection::{
 
 fn record_forwarding_policy_metrics(metrics: &Metrics, policy: &ForwardingPolicyTelemetry) {
     if let Some(reason) = policy.hedge.trigger_reason {
-        metrics.inc_hedge_trigger(reason);
+        for _ in 0..policy.hedge.count {
+            metrics.inc_hedge_trigger(reason);
+        }
     }
     if let Some(reason) = policy.hedge.outcome_reason {
-        metrics.inc_hedge_outcome(reason);
+        metrics.inc_hedge_outcome(reason, policy.hedge.count);
     }
     if policy.hedge.primary_late_ms > 0 {
         metrics.observe_hedge_primary_late_ms(policy.hedge.primary_late_ms);
     }
     if let Some(reason) = policy.retry.attempt_reason {
-        metrics.inc_retry_attempt(reason);
+        for _ in 0..policy.retry.count {
+            metrics.inc_retry_attempt(reason);
+        }
     }
     if let Some(reason) = policy.retry.denial_reason {
         metrics.inc_retry_denied(reason);
@@ -249,7 +253,7 @@ impl QUICListener {
                         );
                     }
                     resilience
-                        .adaptive_admission
+                        .adaptive_admission_for(req.upstream_name.as_deref())
                         .observe(req.start.elapsed(), true);
                     if let Some(req) = streams.get_mut(&stream_id) {
                         terminalize_stream(
@@ -272,7 +276,8 @@ impl QUICListener {
                     let _ = crate::runtime::connection::outcome::observe_proxy_error_outcome(
                         metrics,
                         crate::runtime::connection::outcome::OutcomeRouteTarget {
-                            route: req.upstream_name.as_deref().unwrap_or("unrouted"),
+                            route: req.route_name.as_deref().unwrap_or("unrouted"),
+                            priority: req.priority.as_deref(),
                         },
                         Some(crate::runtime::connection::outcome::OutcomeBackendTarget {
                             upstream: req.upstream_name.as_deref().unwrap_or("unrouted"),
@@ -292,7 +297,7 @@ impl QUICListener {
                         b"request body idle timeout\n",
                     );
                     resilience
-                        .adaptive_admission
+                        .adaptive_admission_for(req.upstream_name.as_deref())
                         .observe(req.start.elapsed(), true);
                     if let Some(req) = streams.get_mut(&stream_id) {
                         req.mark_terminal_outcome_recorded();
@@ -324,7 +329,7 @@ impl QUICListener {
                     );
                 }
                 resilience
-                    .adaptive_admission
+                    .adaptive_admission_for(req.upstream_name.as_deref())
                     .observe(req.start.elapsed(), true);
                 if let Some(req) = streams.get_mut(&stream_id) {
                     terminalize_stream(
@@ -438,7 +443,7 @@ impl QUICListener {
                                         );
                                     }
                                     resilience
-                                        .adaptive_admission
+                                        .adaptive_admission_for(req.upstream_name.as_deref())
                                         .observe(req.start.elapsed(), true);
                                     true
                                 }
@@ -485,7 +490,7 @@ impl QUICListener {
                                 );
                             }
                             resilience
-                                .adaptive_admission
+                                .adaptive_admission_for(req.upstream_name.as_deref())
                                 .observe(req.start.elapsed(), true);
                         }
                         if let Some(req) = streams.get_mut(&stream_id) {
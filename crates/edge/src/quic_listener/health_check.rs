@@ -1,4 +1,7 @@
+use std::sync::PoisonError;
+
 use http_body_util::Full;
+use spooky_config::runtime::RuntimeBackendHealthCheck;
 use spooky_errors::classify_upstream_proxy_error;
 
 use super::*;
@@ -13,30 +16,71 @@ use crate::runtime::{
     connection::outcome::record_classified_backend_failure_metrics,
 };
 
+struct HealthCheckJob {
+    upstream_pool: Arc<RwLock<UpstreamPool>>,
+    index: usize,
+    // Backend revision the job was scheduled for. Updating or removing the
+    // backend through the control API moves the revision on and retires it.
+    revision: u64,
+    // Stable configured backend identity. DNS refresh changes connect targets
+    // underneath this identity without changing health ownership.
+    backend_identity: String,
+    health_uri: String,
+    base_interval_ms: u64,
+    consecutive_failures: u32,
+    next_due_at: Instant,
+}
+
+impl HealthCheckJob {
+    fn new(
+        upstream_pool: &Arc<RwLock<UpstreamPool>>,
+        index: usize,
+        revision: u64,
+        address: String,
+        endpoint: &BackendEndpoint,
+        health: &RuntimeBackendHealthCheck,
+    ) -> Self {
+        let base_interval_ms: u64 = health
+            .interval
+            .as_millis()
+            .try_into()
+            .unwrap_or(u64::MAX)
+            .max(1);
+        let initial_jitter_ms = if base_interval_ms > 1 {
+            crate::stable_hash64(address.as_bytes()) % base_interval_ms
+        } else {
+            0
+        };
+        Self {
+            upstream_pool: Arc::clone(upstream_pool),
+            index,
+            revision,
+            health_uri: endpoint.uri_for_path(&health.path),
+            backend_identity: address,
+            base_interval_ms,
+            consecutive_failures: 0,
+            next_due_at: Instant::now() + Duration::from_millis(initial_jitter_ms),
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        self.upstream_pool
+            .read()
+            .map(|pool| pool.backend_revision(self.index) == Some(self.revision))
+            .unwrap_or(true)
+    }
+}
+
 impl QUICListener {
     pub(super) fn spawn_health_checks(
         upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
         transport_pool: Arc<UpstreamTransportPool>,
-        backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
-        backend_health_checks: Arc<
-            HashMap<String, spooky_config::runtime::RuntimeBackendHealthCheck>,
-        >,
+        backend_endpoints: Arc<BackendEndpoints>,
+        backend_health_checks: Arc<HashMap<String, RuntimeBackendHealthCheck>>,
         backend_lifecycle: Arc<BackendLifecycleCoordinator>,
         metrics: Arc<Metrics>,
         task_registry: Arc<RuntimeTaskRegistry>,
     ) {
-        struct HealthCheckJob {
-            upstream_pool: Arc<RwLock<UpstreamPool>>,
-            index: usize,
-            // Stable configured backend identity. DNS refresh changes connect targets
-            // underneath this identity without changing health ownership.
-            backend_identity: String,
-            health_uri: String,
-            base_interval_ms: u64,
-            consecutive_failures: u32,
-            next_due_at: Instant,
-        }
-
         let mut grouped_jobs: HashMap<u64, Vec<HealthCheckJob>> = HashMap::new();
         #[allow(clippy::for_kv_map)]
         for (_upstream_name, upstream_pool) in upstream_pools.iter() {
@@ -61,28 +105,13 @@ impl QUICListener {
                         continue;
                     }
                 };
-                let base_interval_ms: u64 = health
-                    .interval
-                    .as_millis()
-                    .try_into()
-                    .unwrap_or(u64::MAX)
-                    .max(1);
-                let initial_jitter_ms = if base_interval_ms > 1 {
-                    crate::stable_hash64(address.as_bytes()) % base_interval_ms
-                } else {
-                    0
-                };
-                let next_due_at = Instant::now() + Duration::from_millis(initial_jitter_ms);
-                let job = HealthCheckJob {
-                    upstream_pool: Arc::clone(upstream_pool),
-                    index,
-                    backend_identity: address,
-                    health_uri: endpoint.uri_for_path(&health.path),
-                    base_interval_ms,
-                    consecutive_failures: 0,
-                    next_due_at,
-                };
-                grouped_jobs.entry(base_interval_ms).or_default().push(job);
+                let revision = pool.backend_revision(index).unwrap_or_default();
+                let job =
+                    HealthCheckJob::new(upstream_pool, index, revision, address, &endpoint, health);
+                grouped_jobs
+                    .entry(job.base_interval_ms)
+                    .or_default()
+                    .push(job);
             }
         }
 
@@ -98,113 +127,176 @@ impl QUICListener {
             }
         };
 
-        for (base_interval_ms, mut jobs) in grouped_jobs {
-            let transport_pool = Arc::clone(&transport_pool);
-            let backend_lifecycle = Arc::clone(&backend_lifecycle);
-            let task_metrics = Arc::clone(&metrics);
-            let handle = handle.clone();
-            let supervise_metrics = Arc::clone(&task_metrics);
-            let registration = spawn_supervised_async_task(
+        for (base_interval_ms, jobs) in grouped_jobs {
+            Self::spawn_health_check_group(
                 &handle,
-                "health-check-group",
-                Some(supervise_metrics),
-                async move {
-                    let scheduler_tick_ms = (base_interval_ms / 4).clamp(20, base_interval_ms);
-                    let mut ticker =
-                        tokio::time::interval(Duration::from_millis(scheduler_tick_ms.max(1)));
-                    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-                    loop {
-                        ticker.tick().await;
-                        let now = Instant::now();
-                        for job in jobs.iter_mut() {
-                            if now < job.next_due_at {
-                                continue;
-                            }
+                base_interval_ms,
+                jobs,
+                Arc::clone(&transport_pool),
+                Arc::clone(&backend_lifecycle),
+                Arc::clone(&metrics),
+                &task_registry,
+            );
+        }
+    }
 
-                            let request = match http::Request::builder()
-                                .method("GET")
-                                .uri(&job.health_uri)
-                                .body(BoxBody::new(Full::new(Bytes::new())))
-                            {
-                                Ok(req) => req,
-                                Err(_) => continue,
-                            };
-
-                            let result = transport_pool
-                                .send_backend_request(&job.backend_identity, request)
-                                .await;
-
-                            let evaluation = match result {
-                                Ok(response) => {
-                                    let outcome = match classify_active_health_check_response(
-                                        response.status(),
-                                    ) {
-                                        HealthClassification::Success => {
-                                            BackendHealthObservationOutcome::Success
-                                        }
-                                        HealthClassification::Failure => {
-                                            BackendHealthObservationOutcome::Failure
-                                        }
-                                        HealthClassification::Neutral => {
-                                            BackendHealthObservationOutcome::Neutral
-                                        }
-                                    };
-                                    evaluate_active_health_check(
-                                        BackendIdentity::new(job.backend_identity.clone()),
-                                        outcome,
-                                        None,
-                                        job.base_interval_ms,
-                                        job.consecutive_failures,
-                                    )
-                                }
-                                Err(proxy_err) => {
-                                    let failure_reason = if matches!(proxy_err, ProxyError::Timeout)
-                                    {
-                                        HealthFailureReason::Timeout
-                                    } else {
-                                        HealthFailureReason::Transport
-                                    };
-                                    Self::evaluate_failed_health_check(
-                                        &job.backend_identity,
-                                        task_metrics.as_ref(),
-                                        job.base_interval_ms,
-                                        job.consecutive_failures,
-                                        proxy_err,
-                                        failure_reason,
-                                    )
-                                }
-                            };
-
-                            let transition = backend_lifecycle.apply_health_observation(
-                                Some(&job.upstream_pool),
-                                Some(job.index),
-                                &evaluation.observation,
-                            );
-
-                            match evaluation.observation.outcome {
-                                BackendHealthObservationOutcome::Success => {
-                                    task_metrics.inc_health_check_success();
-                                }
-                                BackendHealthObservationOutcome::Failure => {
-                                    task_metrics.inc_health_check_failure();
-                                }
-                                BackendHealthObservationOutcome::Neutral => {}
+    /// Starts active checks for one backend added or updated through the
+    /// control API, replacing any job scheduled for an earlier revision.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn spawn_backend_health_check(
+        upstream_pool: &Arc<RwLock<UpstreamPool>>,
+        index: usize,
+        endpoint: &BackendEndpoint,
+        health: &RuntimeBackendHealthCheck,
+        transport_pool: Arc<UpstreamTransportPool>,
+        backend_lifecycle: Arc<BackendLifecycleCoordinator>,
+        metrics: Arc<Metrics>,
+        task_registry: &RuntimeTaskRegistry,
+    ) {
+        let job = {
+            let pool = upstream_pool.read().unwrap_or_else(PoisonError::into_inner);
+            let (Some(address), Some(revision)) =
+                (pool.backend_address(index), pool.backend_revision(index))
+            else {
+                return;
+            };
+            HealthCheckJob::new(
+                upstream_pool,
+                index,
+                revision,
+                address.to_string(),
+                endpoint,
+                health,
+            )
+        };
+        let Some(handle) = runtime_handle() else {
+            error!("Health checks disabled: no Tokio runtime available");
+            return;
+        };
+        Self::spawn_health_check_group(
+            &handle,
+            job.base_interval_ms,
+            vec![job],
+            transport_pool,
+            backend_lifecycle,
+            metrics,
+            task_registry,
+        );
+    }
+
+    fn spawn_health_check_group(
+        handle: &tokio::runtime::Handle,
+        base_interval_ms: u64,
+        mut jobs: Vec<HealthCheckJob>,
+        transport_pool: Arc<UpstreamTransportPool>,
+        backend_lifecycle: Arc<BackendLifecycleCoordinator>,
+        metrics: Arc<Metrics>,
+        task_registry: &RuntimeTaskRegistry,
+    ) {
+        let task_metrics = Arc::clone(&metrics);
+        let registration = spawn_supervised_async_task(
+            handle,
+            "health-check-group",
+            Some(metrics),
+            async move {
+                let scheduler_tick_ms = (base_interval_ms / 4).clamp(20, base_interval_ms);
+                let mut ticker =
+                    tokio::time::interval(Duration::from_millis(scheduler_tick_ms.max(1)));
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+                loop {
+                    ticker.tick().await;
+                    jobs.retain(HealthCheckJob::is_current);
+                    if jobs.is_empty() {
+                        return;
+                    }
+                    let now = Instant::now();
+                    for job in jobs.iter_mut() {
+                        if now < job.next_due_at {
+                            continue;
+                        }
+
+                        let request = match http::Request::builder()
+                            .method("GET")
+                            .uri(&job.health_uri)
+                            .body(BoxBody::new(Full::new(Bytes::new())))
+                        {
+                            Ok(req) => req,
+                            Err(_) => continue,
+                        };
+
+                        let result = transport_pool
+                            .send_backend_request(&job.backend_identity, request)
+                            .await;
+
+                        let evaluation = match result {
+                            Ok(response) => {
+                                let outcome = match classify_active_health_check_response(
+                                    response.status(),
+                                ) {
+                                    HealthClassification::Success => {
+                                        BackendHealthObservationOutcome::Success
+                                    }
+                                    HealthClassification::Failure => {
+                                        BackendHealthObservationOutcome::Failure
+                                    }
+                                    HealthClassification::Neutral => {
+                                        BackendHealthObservationOutcome::Neutral
+                                    }
+                                };
+                                evaluate_active_health_check(
+                                    BackendIdentity::new(job.backend_identity.clone()),
+                                    outcome,
+                                    None,
+                                    job.base_interval_ms,
+                                    job.consecutive_failures,
+                                )
+                            }
+                            Err(proxy_err) => {
+                                let failure_reason = if matches!(proxy_err, ProxyError::Timeout) {
+                                    HealthFailureReason::Timeout
+                                } else {
+                                    HealthFailureReason::Transport
+                                };
+                                Self::evaluate_failed_health_check(
+                                    &job.backend_identity,
+                                    task_metrics.as_ref(),
+                                    job.base_interval_ms,
+                                    job.consecutive_failures,
+                                    proxy_err,
+                                    failure_reason,
+                                )
                             }
+                        };
 
-                            job.consecutive_failures = evaluation.next_consecutive_failures;
-                            job.next_due_at = Instant::now() + evaluation.next_delay;
+                        let transition = backend_lifecycle.apply_health_observation(
+                            Some(&job.upstream_pool),
+                            Some(job.index),
+                            &evaluation.observation,
+                        );
 
-                            let _ = crate::runtime::connection::outcome::log_backend_health_transition_result(
-                                &job.backend_identity,
-                                transition,
-                            );
+                        match evaluation.observation.outcome {
+                            BackendHealthObservationOutcome::Success => {
+                                task_metrics.inc_health_check_success();
+                            }
+                            BackendHealthObservationOutcome::Failure => {
+                                task_metrics.inc_health_check_failure();
+                            }
+                            BackendHealthObservationOutcome::Neutral => {}
                         }
+
+                        job.consecutive_failures = evaluation.next_consecutive_failures;
+                        job.next_due_at = Instant::now() + evaluation.next_delay;
+
+                        let _ = crate::runtime::connection::outcome::log_backend_health_transition_result(
+                        &job.backend_identity,
+                        transition,
+                    );
                     }
-                },
-            );
-            task_registry.register(registration);
-        }
+                }
+            },
+        );
+        task_registry.register(registration);
     }
 }
 
@@ -74,6 +74,7 @@ use crate::{
     resilience::runtime::RuntimeResilience,
     routing::{decision::RouteDecisionReason, index::RouteIndex},
     runtime::{
+        backend::endpoints::BackendEndpoints,
         bundle::RuntimeBundle,
         connection::{
             guardrails::{
@@ -100,6 +101,7 @@ use crate::{
     watchdog::coordinator::WatchdogCoordinator,
 };
 
+mod adaptive_concurrency;
 mod admission;
 mod async_runtime;
 mod backend_resolution;
@@ -111,7 +113,9 @@ mod control_plane;
 mod forwarding;
 mod health_check;
 mod metrics;
+mod outlier_detection;
 mod protocol;
+mod route_action;
 mod runtime_endpoint;
 mod runtime_state;
 mod shutdown;
@@ -401,9 +405,10 @@ impl QUICListener {
                 &mut connection,
                 Arc::clone(&transport_pool),
                 Arc::clone(&self.backend_endpoints),
-                Arc::clone(&self.upstream_policies),
+                Arc::clone(&self.route_policies),
                 &self.upstream_pools,
                 &self.upstream_inflight,
+                &self.mirror_inflight,
                 Arc::clone(&self.global_inflight),
                 self.backend_timeout,
                 self.backend_body_idle_timeout,
@@ -443,9 +448,11 @@ impl QUICListener {
             transport_pool: Arc::clone(&self.transport_pool),
             backend_endpoints: Arc::clone(&self.backend_endpoints),
             upstream_inflight: &self.upstream_inflight,
+            mirror_inflight: &self.mirror_inflight,
             global_inflight: Arc::clone(&self.global_inflight),
             backend_timeout: self.backend_timeout,
             inflight_acquire_wait: self.inflight_acquire_wait,
+            request_buffer_global_cap_bytes: self.request_buffer_global_cap_bytes,
         };
         let progress_config = StreamProgressConfig {
             backend_body_idle_timeout: self.backend_body_idle_timeout,
@@ -499,9 +506,11 @@ impl QUICListener {
             transport_pool: Arc::clone(&self.transport_pool),
             backend_endpoints: Arc::clone(&self.backend_endpoints),
             upstream_inflight: &self.upstream_inflight,
+            mirror_inflight: &self.mirror_inflight,
             global_inflight: Arc::clone(&self.global_inflight),
             backend_timeout: self.backend_timeout,
             inflight_acquire_wait: self.inflight_acquire_wait,
+            request_buffer_global_cap_bytes: self.request_buffer_global_cap_bytes,
         };
         let progress_config = StreamProgressConfig {
             backend_body_idle_timeout: self.backend_body_idle_timeout,
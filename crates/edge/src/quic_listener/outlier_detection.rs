@@ -0,0 +1,62 @@
+use super::*;
+
+impl QUICListener {
+    /// Spawns one evaluation loop per upstream with `outlier_detection`. Each
+    /// tick closes the pool's detection window and records its ejections.
+    pub(super) fn spawn_outlier_detection(
+        upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
+        metrics: Arc<Metrics>,
+        task_registry: Arc<RuntimeTaskRegistry>,
+    ) {
+        let mut jobs = upstream_pools
+            .into_iter()
+            .filter_map(|(upstream_name, upstream_pool)| {
+                let interval = upstream_pool.read().ok()?.outlier_detection_interval()?;
+                Some((upstream_name, upstream_pool, interval))
+            })
+            .peekable();
+        if jobs.peek().is_none() {
+            return;
+        }
+
+        let handle = match runtime_handle() {
+            Some(handle) => handle,
+            None => {
+                error!("Outlier detection disabled: no Tokio runtime available");
+                return;
+            }
+        };
+
+        for (upstream_name, upstream_pool, interval) in jobs {
+            let task_metrics = Arc::clone(&metrics);
+            let registration = spawn_supervised_async_task(
+                &handle,
+                "outlier-detection",
+                Some(Arc::clone(&metrics)),
+                async move {
+                    let mut ticker =
+                        tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+                    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+                    loop {
+                        ticker.tick().await;
+                        let Ok(mut pool) = upstream_pool.write() else {
+                            continue;
+                        };
+                        for ejection in pool.evaluate_outliers(Instant::now()) {
+                            task_metrics.inc_outlier_ejection(&upstream_name, ejection.reason);
+                            warn!(
+                                "Backend {} ejected from upstream {} by outlier detection: reason={} ejection_ms={}",
+                                pool.backend_address(ejection.index).unwrap_or("<unknown>"),
+                                upstream_name,
+                                ejection.reason.as_str(),
+                                ejection.duration.as_millis()
+                            );
+                        }
+                    }
+                },
+            );
+            task_registry.register(registration);
+        }
+    }
+}
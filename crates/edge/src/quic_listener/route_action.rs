@@ -0,0 +1,178 @@
+//! Responses for routes the edge answers itself (`redirect` and
+//! `direct_response`). These routes skip backend selection but still pass local
+//! auth, brownout and scoped rate limits, and are recorded under the route name.
+
+use std::time::Instant;
+
+use bytes::Bytes;
+use http::StatusCode;
+use log::{debug, warn};
+use spooky_config::runtime::RuntimeRouteAction;
+
+use super::{
+    LbHeaderLookup,
+    admission::{
+        AdmissionPolicyDecision, AdmissionRejectionResponse, admission_rejection_response,
+        classify_request_priority, evaluate_forwarding_pre_admission_policy,
+    },
+    forwarding::LocalRoute,
+};
+use crate::{
+    Metrics,
+    resilience::{runtime::RuntimeResilience, scoped_rate_limit::ScopedRateLimitRule},
+    runtime::connection::outcome::{
+        AdmissionOutcomeClass, OutcomeRouteTarget, observe_admission_outcome,
+        observe_status_outcome,
+    },
+};
+
+const MISSING_REJECTION_RESPONSE: AdmissionRejectionResponse = AdmissionRejectionResponse {
+    status: StatusCode::INTERNAL_SERVER_ERROR,
+    body: b"internal proxy error\n",
+    www_authenticate: None,
+    retry_after_seconds: None,
+};
+
+pub(super) struct RouteActionResponse {
+    pub(super) status: StatusCode,
+    pub(super) headers: Vec<(String, String)>,
+    pub(super) body: Bytes,
+}
+
+/// Builds the response for an edge-answered route. `path` is the request
+/// target including its query string.
+pub(super) fn route_action_response(
+    action: &RuntimeRouteAction,
+    path: &str,
+    authority: Option<&str>,
+    suppress_body: bool,
+) -> RouteActionResponse {
+    let (status, headers, body) = match action {
+        RuntimeRouteAction::Redirect(redirect) => (
+            redirect.status,
+            vec![(
+                http::header::LOCATION.as_str().to_string(),
+                redirect.location(path, authority),
+            )],
+            Bytes::new(),
+        ),
+        RuntimeRouteAction::DirectResponse(direct) => (
+            direct.status,
+            direct.headers.clone(),
+            Bytes::copy_from_slice(&direct.body),
+        ),
+    };
+    RouteActionResponse {
+        status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        headers,
+        body: if suppress_body { Bytes::new() } else { body },
+    }
+}
+
+/// Runs pre-admission policy for an edge-answered route and records any
+/// rejection. Returns the rejection response to send, or `None` when admitted.
+pub(super) fn evaluate_route_action_admission<F>(
+    local: &LocalRoute,
+    header_lookup: Option<&LbHeaderLookup<'_>>,
+    resilience: &RuntimeResilience,
+    metrics: &Metrics,
+    request_start: Instant,
+    key_for_rule: F,
+) -> Option<AdmissionRejectionResponse>
+where
+    F: FnMut(&ScopedRateLimitRule) -> Option<String>,
+{
+    let priority = classify_request_priority(
+        resilience,
+        &local.route_policy,
+        header_lookup,
+        &local.route_name,
+    );
+    let admission = evaluate_forwarding_pre_admission_policy(
+        &local.route_policy,
+        header_lookup,
+        &resilience.brownout,
+        resilience.adaptive_admission.inflight_percent(),
+        &local.route_name,
+        priority,
+        resilience.shed_retry_after_seconds,
+        &resilience.scoped_rate_limits,
+        key_for_rule,
+    );
+    metrics.set_brownout_active(resilience.brownout.is_active());
+    let rejection_response = admission_rejection_response(&admission);
+    let (status, outcome) = match admission {
+        AdmissionPolicyDecision::AdmitReady => return None,
+        AdmissionPolicyDecision::Unauthorized(_) => {
+            metrics.inc_policy_denied();
+            warn!(
+                "request_id=unassigned route={} denied by local auth policy",
+                local.route_name
+            );
+            (StatusCode::UNAUTHORIZED, AdmissionOutcomeClass::AuthDenied)
+        }
+        AdmissionPolicyDecision::RateLimited(decision) => {
+            metrics.inc_request_rate_limited();
+            warn!(
+                "request_id=unassigned route={} scoped rate limit exceeded by rule={}",
+                decision.route, decision.rule_name
+            );
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                AdmissionOutcomeClass::RateLimited,
+            )
+        }
+        AdmissionPolicyDecision::Overloaded(decision) => {
+            resilience
+                .adaptive_admission
+                .observe(request_start.elapsed(), true);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                AdmissionOutcomeClass::OverloadShed {
+                    reason: Some(decision.reason.metrics_reason()),
+                },
+            )
+        }
+    };
+    let _ = observe_admission_outcome(
+        metrics,
+        OutcomeRouteTarget {
+            route: &local.route_name,
+            priority: Some(&priority.name),
+        },
+        None,
+        request_start.elapsed(),
+        status,
+        outcome,
+    );
+    Some(rejection_response.unwrap_or_else(|| {
+        warn!(
+            "request_id=unassigned route={} missing admission rejection response",
+            local.route_name
+        );
+        MISSING_REJECTION_RESPONSE
+    }))
+}
+
+pub(super) fn observe_route_action_response(
+    metrics: &Metrics,
+    local: &LocalRoute,
+    request_start: Instant,
+    status: StatusCode,
+) {
+    debug!(
+        "Answered route={} at the edge status={}",
+        local.route_name,
+        status.as_u16()
+    );
+    let _ = observe_status_outcome(
+        metrics,
+        OutcomeRouteTarget {
+            route: &local.route_name,
+            priority: None,
+        },
+        None,
+        request_start.elapsed(),
+        status,
+    );
+}
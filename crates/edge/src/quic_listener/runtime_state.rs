@@ -5,7 +5,7 @@ use std::{
     time::Instant,
 };
 
-use spooky_config::runtime::{ListenerRuntimeConfig, RuntimeConfig};
+use spooky_config::runtime::{ListenerRuntimeConfig, RuntimeConfig, RuntimeUpstreamPolicy};
 use spooky_errors::ProxyError;
 use spooky_lb::upstream_pool::UpstreamPool;
 use spooky_transport::{SharedDnsResolver, UpstreamTransportPool};
@@ -13,8 +13,13 @@ use spooky_transport::{SharedDnsResolver, UpstreamTransportPool};
 use crate::{
     Metrics,
     resilience::runtime::RuntimeResilience,
+    rollout::controller::RolloutController,
+    routing::index::RouteIndex,
     runtime::{
-        backend::lifecycle::BackendLifecycleCoordinator,
+        backend::{
+            admin::BackendAdminStore, lifecycle::BackendLifecycleCoordinator,
+            membership::BackendMembershipStore,
+        },
         bundle::{ActiveRuntimeGeneration, RuntimeBundleHandle},
         generation::RuntimeGenerationView,
         shared_state::SharedRuntimeState,
@@ -34,8 +39,13 @@ pub(super) struct ControlPlaneRuntimeView {
     transport_pool: Arc<UpstreamTransportPool>,
     backend_dns_resolver: SharedDnsResolver,
     upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
+    backend_admin: Arc<BackendAdminStore>,
+    backend_membership: Arc<BackendMembershipStore>,
+    routing_index: Arc<RouteIndex>,
+    route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    rollouts: Arc<RolloutController>,
     listener_runtime_configs: Arc<HashMap<String, ListenerRuntimeConfig>>,
-    backend_endpoints: Arc<HashMap<String, spooky_config::backend_endpoint::BackendEndpoint>>,
+    backend_endpoints: Arc<crate::runtime::backend::endpoints::BackendEndpoints>,
     backend_health_checks: Arc<HashMap<String, spooky_config::runtime::RuntimeBackendHealthCheck>>,
     generation_tasks: Arc<RuntimeTaskRegistry>,
     listener_tls_store: Arc<ListenerTlsReloadStore>,
@@ -59,6 +69,11 @@ impl ControlPlaneRuntimeView {
             transport_pool: Arc::clone(&shared.transport_pool),
             backend_dns_resolver: shared.backend_dns_resolver.clone(),
             upstream_pools: generation.upstream_pools.clone(),
+            backend_admin: Arc::clone(&generation.backend_admin),
+            backend_membership: Arc::clone(&generation.backend_membership),
+            routing_index: Arc::clone(&generation.routing_index),
+            route_policies: Arc::clone(&generation.route_policies),
+            rollouts: Arc::clone(&generation.rollouts),
             listener_runtime_configs: Arc::clone(&generation.listener_runtime_configs),
             backend_endpoints: Arc::clone(&generation.backend_endpoints),
             backend_health_checks: Arc::clone(&generation.backend_health_checks),
@@ -80,6 +95,11 @@ impl ControlPlaneRuntimeView {
             transport_pool: Arc::clone(&view.shared.transport_pool),
             backend_dns_resolver: view.shared.backend_dns_resolver.clone(),
             upstream_pools: view.state.upstream_pools.clone(),
+            backend_admin: Arc::clone(&view.state.backend_admin),
+            backend_membership: Arc::clone(&view.state.backend_membership),
+            routing_index: Arc::clone(&view.state.routing_index),
+            route_policies: Arc::clone(&view.state.route_policies),
+            rollouts: Arc::clone(&view.state.rollouts),
             listener_runtime_configs: Arc::clone(&view.state.listener_runtime_configs),
             backend_endpoints: Arc::clone(&view.state.backend_endpoints),
             backend_health_checks: Arc::clone(&view.state.backend_health_checks),
@@ -124,13 +144,33 @@ impl ControlPlaneRuntimeView {
         &self.upstream_pools
     }
 
+    pub(super) fn backend_admin(&self) -> Arc<BackendAdminStore> {
+        Arc::clone(&self.backend_admin)
+    }
+
+    pub(super) fn backend_membership(&self) -> Arc<BackendMembershipStore> {
+        Arc::clone(&self.backend_membership)
+    }
+
+    pub(super) fn routing_index(&self) -> &RouteIndex {
+        &self.routing_index
+    }
+
+    pub(super) fn route_policies(&self) -> &HashMap<String, RuntimeUpstreamPolicy> {
+        &self.route_policies
+    }
+
+    pub(super) fn rollouts(&self) -> Arc<RolloutController> {
+        Arc::clone(&self.rollouts)
+    }
+
     pub(super) fn listener_runtime_configs(&self) -> Arc<HashMap<String, ListenerRuntimeConfig>> {
         Arc::clone(&self.listener_runtime_configs)
     }
 
     pub(super) fn backend_endpoints(
         &self,
-    ) -> Arc<HashMap<String, spooky_config::backend_endpoint::BackendEndpoint>> {
+    ) -> Arc<crate::runtime::backend::endpoints::BackendEndpoints> {
         Arc::clone(&self.backend_endpoints)
     }
 
@@ -19,11 +19,13 @@ use crate::{
     constants::UDP_READ_TIMEOUT_MS,
     quic_listener::{ListenerRuntimeSettings, TokenBucket, runtime_state::PreparedListenerStartup},
     resilience::runtime::RuntimeResilience,
+    rollout::controller::RolloutController,
     routing::index::RouteIndex,
     runtime::{
         backend::{
-            lifecycle::BackendLifecycleCoordinator, resolution::RuntimeBackendResolution,
-            store::RuntimeBackendResolutionStore,
+            admin::BackendAdminStore, endpoints::BackendEndpoints,
+            lifecycle::BackendLifecycleCoordinator, membership::BackendMembershipStore,
+            resolution::RuntimeBackendResolution, store::RuntimeBackendResolutionStore,
         },
         bundle::{RuntimeBundle, RuntimeBundleHandle},
         generation::{RuntimeGenerationState, RuntimeSharedServices, StartupOwnedRuntimeState},
@@ -109,6 +111,23 @@ impl QUICListener {
     }
 
     pub fn build_shared_state(config: &RuntimeConfig) -> Result<SharedRuntimeState, ProxyError> {
+        let backend_membership = BackendMembershipStore::load(
+            config
+                .observability
+                .control_api
+                .membership_state_file
+                .as_deref(),
+        );
+        Self::build_shared_state_with_membership(config, backend_membership)
+    }
+
+    /// Builds a generation from `config` with the backend membership changes
+    /// in `backend_membership` layered over its upstreams.
+    pub(super) fn build_shared_state_with_membership(
+        config: &RuntimeConfig,
+        backend_membership: BackendMembershipStore,
+    ) -> Result<SharedRuntimeState, ProxyError> {
+        let config = &backend_membership.overlay(config);
         let transport_policy = &config.policies.transport;
         let timeout_policy = &config.policies.timeouts;
         let worker_threads = transport_policy.worker_threads.max(1);
@@ -241,9 +260,9 @@ impl QUICListener {
             }
         }
 
-        let mut route_labels = config.upstreams.keys().cloned().collect::<Vec<_>>();
+        let mut route_labels = config.routes.keys().cloned().collect::<Vec<_>>();
         route_labels.push("unrouted".to_string());
-        let routing_index = Arc::new(RouteIndex::from_runtime_upstreams(&config.upstreams));
+        let routing_index = Arc::new(RouteIndex::from_runtime_routes(&config.routes));
         let metrics = Arc::new(crate::Metrics::new(worker_slots, route_labels));
         let backend_dns_resolver = SharedDnsResolver::new();
         let backend_resolution_store =
@@ -282,6 +301,22 @@ impl QUICListener {
             upstream_pools.insert(name.clone(), Arc::new(RwLock::new(upstream_pool)));
             upstream_inflight.insert(name.clone(), Arc::new(Semaphore::new(per_upstream_limit)));
         }
+        let backend_admin = Arc::new(BackendAdminStore::load(
+            config
+                .observability
+                .control_api
+                .backend_state_file
+                .as_deref(),
+        ));
+        backend_admin.apply(&upstream_pools);
+        let mirror_inflight = config
+            .routes
+            .iter()
+            .filter_map(|(name, route)| {
+                let mirror = route.policy.mirror.as_ref()?;
+                Some((name.clone(), Arc::new(Semaphore::new(mirror.max_inflight))))
+            })
+            .collect::<HashMap<_, _>>();
 
         let mut effective_admission = config.policies.admission.clone();
         let default_route_cap_limit = per_upstream_limit.saturating_mul(2).max(1);
@@ -321,6 +356,7 @@ impl QUICListener {
         let resilience = Arc::new(RuntimeResilience::from_policies(
             &effective_admission,
             &config.policies.rate_limits,
+            &config.upstream_adaptive_admission(Duration::from_millis(tuned_high_latency)),
         ));
         let watchdog = Arc::new(WatchdogCoordinator::from_runtime_config(
             &WatchdogRuntimeConfig::from(&config.policies.admission.watchdog),
@@ -328,6 +364,11 @@ impl QUICListener {
         for (listener_label, inventory) in listener_tls_store.snapshot() {
             Self::update_listener_tls_expiry_metrics(&metrics, &listener_label, &inventory);
         }
+        let rollouts = Arc::new(RolloutController::new(
+            &config.routes,
+            &routing_index,
+            Arc::clone(&metrics),
+        ));
 
         Ok(SharedRuntimeState::from_parts(
             RuntimeSharedServices {
@@ -341,19 +382,23 @@ impl QUICListener {
             },
             RuntimeGenerationState {
                 listener_runtime_configs: Arc::new(listener_runtime_configs),
-                backend_endpoints: Arc::new(backend_endpoints),
+                backend_endpoints: Arc::new(BackendEndpoints::new(backend_endpoints)),
                 backend_health_checks: Arc::new(backend_health_checks),
-                upstream_policies: Arc::new(
+                route_policies: Arc::new(
                     config
-                        .upstreams
+                        .routes
                         .iter()
-                        .map(|(name, upstream)| (name.clone(), upstream.policy.clone()))
+                        .map(|(name, route)| (name.clone(), route.policy.clone()))
                         .collect(),
                 ),
                 upstream_pools,
+                backend_admin,
+                backend_membership: Arc::new(backend_membership),
                 upstream_inflight,
+                mirror_inflight,
                 global_inflight: Arc::new(Semaphore::new(global_inflight_limit)),
                 routing_index,
+                rollouts,
                 resilience,
                 generation_tasks: Arc::new(RuntimeTaskRegistry::new()),
             },
@@ -462,9 +507,10 @@ impl QUICListener {
             transport_pool: Arc::clone(&shared_services.transport_pool),
             backend_endpoints: Arc::clone(&generation_state.backend_endpoints),
             backend_dns_resolver: shared_services.backend_dns_resolver.clone(),
-            upstream_policies: Arc::clone(&generation_state.upstream_policies),
+            route_policies: Arc::clone(&generation_state.route_policies),
             upstream_pools: generation_state.upstream_pools.clone(),
             upstream_inflight: generation_state.upstream_inflight.clone(),
+            mirror_inflight: generation_state.mirror_inflight.clone(),
             global_inflight: Arc::clone(&generation_state.global_inflight),
             routing_index: Arc::clone(&generation_state.routing_index),
             metrics: Arc::clone(&shared_services.metrics),
@@ -6,13 +6,14 @@ use std::{
     time::Duration,
 };
 
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use http::{HeaderMap, HeaderValue, StatusCode};
 use rcgen::{Certificate, CertificateParams, SanType};
 use spooky_config::{
     config::{
         Backend, ClientAuth, Config as SpookyConfigConfig, Listen, LoadBalancing, Log,
-        Observability, Performance, Resilience, RouteMatch, Security, Tls, TlsCertificate,
-        Upstream, UpstreamTls,
+        Observability, Performance, Resilience, RouteMatch, Security, SessionAffinity, Tls,
+        TlsCertificate, Upstream, UpstreamTls,
     },
     runtime::{ListenerRuntimeConfig, RuntimeConfig},
 };
@@ -68,9 +69,19 @@ fn test_upstream_with(lb_type: &str, lb_key: Option<&str>, method: Option<&str>)
         load_balancing: LoadBalancing {
             lb_type: lb_type.to_string(),
             key: lb_key.map(str::to_string),
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -84,12 +95,16 @@ fn test_upstream_with(lb_type: &str, lb_key: Option<&str>, method: Option<&str>)
                 address: "127.0.0.1:7001".to_string(),
                 weight: 1,
                 health_check: None,
+                priority: 0,
+                zone: None,
             },
             Backend {
                 id: "b2".to_string(),
                 address: "127.0.0.1:7002".to_string(),
                 weight: 1,
                 health_check: None,
+                priority: 0,
+                zone: None,
             },
         ],
     }
@@ -109,6 +124,7 @@ fn runtime_config_with_upstreams(upstreams: HashMap<String, Upstream>) -> Runtim
         },
         listeners: Vec::new(),
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: None,
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -116,6 +132,7 @@ fn runtime_config_with_upstreams(upstreams: HashMap<String, Upstream>) -> Runtim
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     })
     .expect("runtime config")
 }
@@ -160,9 +177,14 @@ fn tls_test_config(
         },
         listeners: vec![],
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -170,6 +192,7 @@ fn tls_test_config(
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -188,9 +211,19 @@ fn dns_resolution_test_config(cert: String, key: String) -> SpookyConfigConfig {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -204,12 +237,16 @@ fn dns_resolution_test_config(cert: String, key: String) -> SpookyConfigConfig {
                     address: "backend.internal:8443".to_string(),
                     weight: 1,
                     health_check: None,
+                    priority: 0,
+                    zone: None,
                 },
                 Backend {
                     id: "ip".to_string(),
                     address: "10.0.0.10:9443".to_string(),
                     weight: 1,
                     health_check: None,
+                    priority: 0,
+                    zone: None,
                 },
             ],
         },
@@ -230,9 +267,14 @@ fn dns_resolution_test_config(cert: String, key: String) -> SpookyConfigConfig {
         },
         listeners: vec![],
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -240,6 +282,7 @@ fn dns_resolution_test_config(cert: String, key: String) -> SpookyConfigConfig {
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -476,7 +519,7 @@ fn bootstrap_connection_state_prefers_reloaded_runtime_settings() {
         listener_tls_store: Arc::clone(&startup_services.listener_tls_store),
         transport_pool: Arc::clone(&startup_services.transport_pool),
         backend_endpoints: Arc::clone(&startup_generation.backend_endpoints),
-        upstream_policies: Arc::clone(&startup_generation.upstream_policies),
+        route_policies: Arc::clone(&startup_generation.route_policies),
         metrics: Arc::clone(&startup_services.metrics),
         resilience: Arc::clone(&startup_generation.resilience),
         upstream_pools: startup_generation.upstream_pools.clone(),
@@ -800,10 +843,14 @@ type TestRoutingContext = (
 );
 
 fn test_routing_context(lb_type: &str) -> TestRoutingContext {
+    test_routing_context_with(test_upstream(lb_type))
+}
+
+fn test_routing_context_with(upstream: Upstream) -> TestRoutingContext {
     let mut upstreams = HashMap::new();
-    upstreams.insert("api_pool".to_string(), test_upstream(lb_type));
+    upstreams.insert("api_pool".to_string(), upstream);
     let runtime = runtime_config_with_upstreams(upstreams);
-    let routing_index = super::RouteIndex::from_runtime_upstreams(&runtime.upstreams);
+    let routing_index = super::RouteIndex::from_runtime_routes(&runtime.routes);
     let pool = super::UpstreamPool::from_runtime_upstream(
         runtime.upstreams.get("api_pool").expect("upstream"),
     )
@@ -811,8 +858,8 @@ fn test_routing_context(lb_type: &str) -> TestRoutingContext {
     let pool = Arc::new(RwLock::new(pool));
     let mut upstream_pools = HashMap::new();
     upstream_pools.insert("api_pool".to_string(), Arc::clone(&pool));
-    let mut upstream_policies = HashMap::new();
-    upstream_policies.insert(
+    let mut route_policies = HashMap::new();
+    route_policies.insert(
         "api_pool".to_string(),
         runtime
             .upstreams
@@ -821,12 +868,12 @@ fn test_routing_context(lb_type: &str) -> TestRoutingContext {
             .policy
             .clone(),
     );
-    (upstream_pools, upstream_policies, routing_index, pool)
+    (upstream_pools, route_policies, routing_index, pool)
 }
 
 #[test]
 fn resolve_backend_round_robin_is_not_pinned_to_first_backend() {
-    let (upstream_pools, upstream_policies, routing_index, _pool) =
+    let (upstream_pools, route_policies, routing_index, _pool) =
         test_routing_context("round-robin");
 
     let mut picks = Vec::new();
@@ -841,7 +888,7 @@ fn resolve_backend_round_robin_is_not_pinned_to_first_backend() {
         let resolved = super::QUICListener::resolve_backend_request_for_test(
             &request,
             &upstream_pools,
-            &upstream_policies,
+            &route_policies,
             &routing_index,
         )
         .expect("resolve backend");
@@ -858,8 +905,7 @@ fn resolve_backend_round_robin_is_not_pinned_to_first_backend() {
 
 #[test]
 fn resolve_backend_skips_unhealthy_backends() {
-    let (upstream_pools, upstream_policies, routing_index, pool) =
-        test_routing_context("round-robin");
+    let (upstream_pools, route_policies, routing_index, pool) = test_routing_context("round-robin");
     {
         let mut guard = pool.write().expect("pool write");
         guard.mark_backend_failure_from_active_check(0);
@@ -872,7 +918,7 @@ fn resolve_backend_skips_unhealthy_backends() {
     let resolved = super::QUICListener::resolve_backend_request_for_test(
         &request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("resolve backend");
@@ -885,7 +931,7 @@ fn resolve_backend_skips_unhealthy_backends() {
 
 #[test]
 fn resolve_backend_respects_least_connections_strategy() {
-    let (upstream_pools, upstream_policies, routing_index, pool) =
+    let (upstream_pools, route_policies, routing_index, pool) =
         test_routing_context("least-connections");
     {
         let guard = pool.read().expect("pool read");
@@ -898,7 +944,7 @@ fn resolve_backend_respects_least_connections_strategy() {
     let resolved = super::QUICListener::resolve_backend_request_for_test(
         &request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("resolve backend");
@@ -909,6 +955,88 @@ fn resolve_backend_respects_least_connections_strategy() {
     );
 }
 
+#[test]
+fn resolve_backend_issues_and_honours_session_affinity_cookie() {
+    let mut upstream = test_upstream("round-robin");
+    upstream.session_affinity = Some(SessionAffinity {
+        ttl_secs: Some(300),
+        secret: Some("affinity-test".to_string()),
+        ..SessionAffinity::default()
+    });
+    let (upstream_pools, route_policies, routing_index, pool) = test_routing_context_with(upstream);
+    let resolve = |cookie: Option<&str>| {
+        let header_lookup = |name: &str| {
+            cookie
+                .filter(|_| name.eq_ignore_ascii_case("cookie"))
+                .map(str::to_string)
+        };
+        let request = super::forwarding::TestRouteResolutionRequest::new(
+            "GET",
+            "/api/items",
+            None,
+            None,
+            Some(&header_lookup),
+        );
+        super::QUICListener::resolve_backend_request_for_test(
+            &request,
+            &upstream_pools,
+            &route_policies,
+            &routing_index,
+        )
+        .expect("resolve backend")
+        .backend
+    };
+
+    let first = resolve(None);
+    let set_cookie = first.affinity_cookie.expect("affinity cookie issued");
+    assert!(set_cookie.starts_with("spooky_affinity="), "{set_cookie}");
+    assert!(
+        set_cookie.ends_with("; Path=/; Max-Age=300; SameSite=Lax; Secure; HttpOnly"),
+        "{set_cookie}"
+    );
+    let cookie = set_cookie
+        .split(';')
+        .next()
+        .expect("cookie pair")
+        .to_string();
+    let backend_id = pool
+        .read()
+        .expect("pool read")
+        .backend_id(first.backend_index)
+        .expect("backend id")
+        .to_string();
+    assert!(!cookie.contains(&backend_id), "{cookie}");
+    assert!(
+        !cookie.contains(&URL_SAFE_NO_PAD.encode(&backend_id)),
+        "{cookie}"
+    );
+
+    for _ in 0..4 {
+        let pinned = resolve(Some(&format!("theme=dark; {cookie}")));
+        assert_eq!(pinned.backend_index, first.backend_index);
+        assert_eq!(pinned.affinity_cookie, None);
+    }
+
+    let (name, value) = cookie.split_once('=').expect("cookie value");
+    let flipped = if value.starts_with('A') { 'B' } else { 'A' };
+    let tampered = format!("{name}={flipped}{}", &value[1..]);
+    assert!(resolve(Some(&tampered)).affinity_cookie.is_some());
+
+    {
+        let mut guard = pool.write().expect("pool write");
+        for _ in 0..3 {
+            guard.mark_backend_failure_from_active_check(first.backend_index);
+        }
+    }
+    let fallback = resolve(Some(&cookie));
+    assert_ne!(fallback.backend_index, first.backend_index);
+    let reissued = fallback.affinity_cookie.expect("cookie re-issued");
+    let reissued = reissued.split(';').next().expect("cookie pair");
+    let repinned = resolve(Some(reissued));
+    assert_eq!(repinned.backend_index, fallback.backend_index);
+    assert_eq!(repinned.affinity_cookie, None);
+}
+
 #[test]
 fn resolve_backend_prefers_method_specific_route() {
     let mut upstreams = HashMap::new();
@@ -922,17 +1050,19 @@ fn resolve_backend_prefers_method_specific_route() {
         address: "127.0.0.1:7010".to_string(),
         weight: 1,
         health_check: None,
+        priority: 0,
+        zone: None,
     }];
     upstreams.insert("post_only".to_string(), post_only);
 
     let runtime = runtime_config_with_upstreams(upstreams);
-    let routing_index = super::RouteIndex::from_runtime_upstreams(&runtime.upstreams);
+    let routing_index = super::RouteIndex::from_runtime_routes(&runtime.routes);
     let mut upstream_pools = HashMap::new();
-    let mut upstream_policies = HashMap::new();
+    let mut route_policies = HashMap::new();
     for (name, upstream) in &runtime.upstreams {
         let pool = super::UpstreamPool::from_runtime_upstream(upstream).expect("pool");
         upstream_pools.insert(name.clone(), Arc::new(RwLock::new(pool)));
-        upstream_policies.insert(name.clone(), upstream.policy.clone());
+        route_policies.insert(name.clone(), upstream.policy.clone());
     }
 
     let request =
@@ -940,7 +1070,7 @@ fn resolve_backend_prefers_method_specific_route() {
     let resolved = super::QUICListener::resolve_backend_request_for_test(
         &request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("GET resolve");
@@ -951,7 +1081,7 @@ fn resolve_backend_prefers_method_specific_route() {
     let resolved = super::QUICListener::resolve_backend_request_for_test(
         &request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("POST resolve");
@@ -961,14 +1091,14 @@ fn resolve_backend_prefers_method_specific_route() {
 
 #[test]
 fn resolve_backend_uses_configured_header_lb_key() {
-    let (upstream_pools, upstream_policies, routing_index, _pool) = {
+    let (upstream_pools, route_policies, routing_index, _pool) = {
         let mut upstreams = HashMap::new();
         upstreams.insert(
             "api_pool".to_string(),
             test_upstream_with("consistent-hash", Some("header:x-user-id"), None),
         );
         let runtime = runtime_config_with_upstreams(upstreams);
-        let routing_index = super::RouteIndex::from_runtime_upstreams(&runtime.upstreams);
+        let routing_index = super::RouteIndex::from_runtime_routes(&runtime.routes);
         let pool = super::UpstreamPool::from_runtime_upstream(
             runtime.upstreams.get("api_pool").expect("upstream"),
         )
@@ -976,8 +1106,8 @@ fn resolve_backend_uses_configured_header_lb_key() {
         let pool = Arc::new(RwLock::new(pool));
         let mut upstream_pools = HashMap::new();
         upstream_pools.insert("api_pool".to_string(), Arc::clone(&pool));
-        let mut upstream_policies = HashMap::new();
-        upstream_policies.insert(
+        let mut route_policies = HashMap::new();
+        route_policies.insert(
             "api_pool".to_string(),
             runtime
                 .upstreams
@@ -986,7 +1116,7 @@ fn resolve_backend_uses_configured_header_lb_key() {
                 .policy
                 .clone(),
         );
-        (upstream_pools, upstream_policies, routing_index, pool)
+        (upstream_pools, route_policies, routing_index, pool)
     };
 
     let header_lookup = |name: &str| {
@@ -1007,7 +1137,7 @@ fn resolve_backend_uses_configured_header_lb_key() {
     let first = super::QUICListener::resolve_backend_request_for_test(
         &first_request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("first resolve");
@@ -1021,7 +1151,7 @@ fn resolve_backend_uses_configured_header_lb_key() {
     let second = super::QUICListener::resolve_backend_request_for_test(
         &second_request,
         &upstream_pools,
-        &upstream_policies,
+        &route_policies,
         &routing_index,
     )
     .expect("second resolve");
@@ -1032,6 +1162,174 @@ fn resolve_backend_uses_configured_header_lb_key() {
     );
 }
 
+#[test]
+fn resolve_backend_applies_sticky_route_split() {
+    let mut upstreams = HashMap::new();
+    upstreams.insert("stable".to_string(), test_upstream("round-robin"));
+    let mut canary = test_upstream("round-robin");
+    canary.route = RouteMatch::default();
+    canary.backends = vec![Backend {
+        id: "canary".to_string(),
+        address: "127.0.0.1:7010".to_string(),
+        weight: 1,
+        health_check: None,
+        priority: 0,
+        zone: None,
+    }];
+    upstreams.insert("canary".to_string(), canary);
+
+    let mut runtime = runtime_config_with_upstreams(upstreams);
+    runtime
+        .routes
+        .get_mut("stable")
+        .expect("stable route")
+        .split = Some(spooky_config::runtime::RuntimeTrafficSplit {
+        key_spec: Some(spooky_config::runtime::RuntimeRequestKeySpec::Header(
+            "x-user-id".to_string(),
+        )),
+        targets: vec![
+            spooky_config::runtime::RuntimeSplitTarget {
+                upstream: "stable".to_string(),
+                weight: 50,
+            },
+            spooky_config::runtime::RuntimeSplitTarget {
+                upstream: "canary".to_string(),
+                weight: 50,
+            },
+        ],
+        total_weight: 100,
+        rollout: None,
+    });
+    let routing_index = super::RouteIndex::from_runtime_routes(&runtime.routes);
+    let mut upstream_pools = HashMap::new();
+    for (name, upstream) in &runtime.upstreams {
+        let pool = super::UpstreamPool::from_runtime_upstream(upstream).expect("pool");
+        upstream_pools.insert(name.clone(), Arc::new(RwLock::new(pool)));
+    }
+    let mut route_policies = HashMap::new();
+    route_policies.insert(
+        "stable".to_string(),
+        runtime.routes["stable"].policy.clone(),
+    );
+
+    let mut seen_upstreams = std::collections::HashSet::new();
+    for user in 0..32 {
+        let user_id = format!("user-{user}");
+        let header_lookup = |name: &str| {
+            name.eq_ignore_ascii_case("x-user-id")
+                .then(|| user_id.clone())
+        };
+        let mut picks = Vec::new();
+        for _ in 0..3 {
+            let request = super::forwarding::TestRouteResolutionRequest::new(
+                "GET",
+                "/api/items",
+                None,
+                None,
+                Some(&header_lookup),
+            );
+            let resolved = super::QUICListener::resolve_backend_request_for_test(
+                &request,
+                &upstream_pools,
+                &route_policies,
+                &routing_index,
+            )
+            .expect("split resolve");
+            assert_eq!(resolved.route.route_name, "stable");
+            assert!(resolved.route.route_split);
+            picks.push(resolved.route.upstream_name);
+        }
+        assert!(
+            picks.windows(2).all(|pair| pair[0] == pair[1]),
+            "split should keep one user on one upstream: {picks:?}"
+        );
+        seen_upstreams.insert(picks.remove(0));
+    }
+
+    assert_eq!(
+        seen_upstreams.len(),
+        2,
+        "both split targets should receive users"
+    );
+}
+
+#[test]
+fn priority_rules_match_the_named_route_of_forwarded_requests() {
+    use spooky_config::config::{Priority, PriorityClass, PriorityRule};
+
+    let mut upstreams = HashMap::new();
+    upstreams.insert("shop".to_string(), test_upstream("round-robin"));
+    let mut runtime = runtime_config_with_upstreams(upstreams);
+    let mut checkout = runtime.routes.remove("shop").expect("shop route");
+    checkout.name = "checkout".to_string();
+    runtime.routes.insert(checkout.name.clone(), checkout);
+
+    let routing_index = super::RouteIndex::from_runtime_routes(&runtime.routes);
+    let pool =
+        super::UpstreamPool::from_runtime_upstream(&runtime.upstreams["shop"]).expect("pool");
+    let upstream_pools = HashMap::from([("shop".to_string(), Arc::new(RwLock::new(pool)))]);
+    let route_policies = HashMap::from([(
+        "checkout".to_string(),
+        runtime.routes["checkout"].policy.clone(),
+    )]);
+
+    let resilience = crate::resilience::runtime::RuntimeResilience::from_config(
+        &Resilience {
+            priority: Priority {
+                default_class: "standard".to_string(),
+                classes: vec![
+                    PriorityClass {
+                        name: "critical".to_string(),
+                        max_inflight_percent: 100,
+                        brownout_exempt: true,
+                    },
+                    PriorityClass {
+                        name: "standard".to_string(),
+                        max_inflight_percent: 50,
+                        brownout_exempt: false,
+                    },
+                ],
+                rules: vec![PriorityRule {
+                    class: "critical".to_string(),
+                    routes: vec!["checkout".to_string()],
+                    header: None,
+                    jwt_claim: None,
+                    values: Vec::new(),
+                }],
+            },
+            ..Resilience::default()
+        },
+        1024,
+    );
+
+    let request =
+        super::forwarding::TestRouteResolutionRequest::new("POST", "/api/cart", None, None, None);
+    let resolved = super::QUICListener::resolve_backend_request_for_test(
+        &request,
+        &upstream_pools,
+        &route_policies,
+        &routing_index,
+    )
+    .expect("checkout resolves");
+    assert_eq!(resolved.route.route_name, "checkout");
+    assert_eq!(resolved.route.upstream_name, "shop");
+
+    let priority = super::admission::classify_request_priority(
+        &resilience,
+        &resolved.route.route_policy,
+        None,
+        &resolved.route.route_name,
+    );
+    assert_eq!(priority.name, "critical");
+    let by_upstream = super::admission::classify_request_priority(
+        &resilience,
+        &resolved.route.route_policy,
+        None,
+        &resolved.route.upstream_name,
+    );
+    assert_eq!(by_upstream.name, "standard");
+}
+
 #[test]
 fn active_health_check_classification_matches_shared_policy() {
     assert!(matches!(
@@ -2001,11 +2299,14 @@ fn make_routing_snapshot() -> RoutingSnapshot {
     RoutingSnapshot {
         backend_addr: "http://127.0.0.1:8080".into(),
         backend_index: 0,
+        route_name: "api".into(),
         upstream_name: "api".into(),
         route_reason: "path_prefix".into(),
         route_path_len: 1,
         route_host_specific: false,
         backend_lb: None,
+        priority: None,
+        affinity_cookie: None,
     }
 }
 
@@ -2015,6 +2316,7 @@ fn make_pending_forward(method: &str) -> Arc<PendingForward> {
         path: Arc::<str>::from("/"),
         authority: None,
         headers: Arc::new(vec![quiche::h3::Header::new(b":method", method.as_bytes())]),
+        route_name: Arc::<str>::from("api"),
         upstream_name: Arc::<str>::from("api"),
         route_reason: Arc::<str>::from("path_prefix"),
         route_path_len: 1,
@@ -2029,6 +2331,10 @@ fn make_pending_forward(method: &str) -> Arc<PendingForward> {
         traceparent: None,
         host_policy: Default::default(),
         forwarded_header_policy: Default::default(),
+        path_rewrite: None,
+        mirror: None,
+        fault: None,
+        limits: Default::default(),
         auth_header_mutations: Vec::new(),
     })
 }
@@ -2082,11 +2388,14 @@ fn make_dispatch_ready_envelope(
         authority: context.authority.clone(),
         backend_addr: Some(routing.backend_addr.clone()),
         backend_index: Some(routing.backend_index),
+        route_name: Some(routing.route_name.clone()),
         upstream_name: Some(routing.upstream_name.clone()),
+        priority: routing.priority.clone(),
         route_reason: Some(routing.route_reason.clone()),
         route_path_len: Some(routing.route_path_len),
         route_host_specific: Some(routing.route_host_specific),
         backend_lb: routing.backend_lb.clone(),
+        affinity_cookie: routing.affinity_cookie.clone(),
         upstream_pool: None,
         routing_transparency_enabled: false,
         routing_transparency_include_reason: false,
@@ -2095,6 +2404,7 @@ fn make_dispatch_ready_envelope(
         total_request_deadline: context.total_request_deadline,
         bodyless_mode: false,
         tunnel_mode: TunnelMode::None,
+        max_request_body_bytes: None,
         retry_count: 0,
         error_kind: None,
         terminal_overload_reason: None,
@@ -2233,11 +2543,14 @@ fn abort_stream_waiting_for_auth_clears_async_auth_state() {
         routing: RoutingSnapshot {
             backend_addr: "http://127.0.0.1:8080".into(),
             backend_index: 0,
+            route_name: "api".into(),
             upstream_name: "api".into(),
             route_reason: "path_prefix".into(),
             route_path_len: 7,
             route_host_specific: false,
             backend_lb: None,
+            priority: None,
+            affinity_cookie: None,
         },
         request_mode: RequestMode::Normal,
         request_body: RequestBodyState::Open,
@@ -2253,6 +2566,7 @@ fn abort_stream_waiting_for_auth_clears_async_auth_state() {
             path: Arc::<str>::from("/upload"),
             authority: Some(Arc::<str>::from("example.com")),
             headers: Arc::new(vec![quiche::h3::Header::new(b":method", b"POST")]),
+            route_name: Arc::<str>::from("api"),
             upstream_name: Arc::<str>::from("api"),
             route_reason: Arc::<str>::from("path_prefix"),
             route_path_len: 7,
@@ -2267,6 +2581,10 @@ fn abort_stream_waiting_for_auth_clears_async_auth_state() {
             traceparent: None,
             host_policy: Default::default(),
             forwarded_header_policy: Default::default(),
+            path_rewrite: None,
+            mirror: None,
+            fault: None,
+            limits: Default::default(),
             auth_header_mutations: Vec::new(),
         }),
         auth_result_rx: auth_rx,
@@ -335,9 +335,10 @@ impl QUICListener {
         self.transport_pool = Arc::clone(&shared.transport_pool);
         self.backend_endpoints = Arc::clone(&generation.backend_endpoints);
         self.backend_dns_resolver = shared.backend_dns_resolver.clone();
-        self.upstream_policies = Arc::clone(&generation.upstream_policies);
+        self.route_policies = Arc::clone(&generation.route_policies);
         self.upstream_pools = generation.upstream_pools.clone();
         self.upstream_inflight = generation.upstream_inflight.clone();
+        self.mirror_inflight = generation.mirror_inflight.clone();
         self.global_inflight = Arc::clone(&generation.global_inflight);
         self.routing_index = Arc::clone(&generation.routing_index);
         self.metrics = Arc::clone(&shared.metrics);
@@ -1,10 +1,107 @@
 use std::{
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicUsize, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
+
+use spooky_config::{config::AdaptiveAdmissionAlgorithm, runtime::RuntimeAdaptiveAdmissionPolicy};
+
+/// Smallest factor a single gradient sample may scale the limit by.
+const MIN_GRADIENT: f64 = 0.5;
+
+/// Tuning for the gradient algorithm; see `resilience.adaptive_admission`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientSettings {
+    pub min_rtt_window: Duration,
+    pub rtt_tolerance_percent: u32,
+    pub smoothing_percent: u32,
+    pub queue_size: usize,
+}
+
+/// Gradient limiter state as last updated by [`AdaptiveAdmission::observe`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientSnapshot {
+    /// No-load RTT estimate: the minimum over the current and previous window.
+    pub min_rtt: Duration,
+    pub smoothed_rtt: Duration,
+    pub gradient: f64,
+}
+
+struct GradientLimiter {
+    settings: GradientSettings,
+    state: Mutex<GradientState>,
+}
+
+struct GradientState {
+    limit: f64,
+    smoothed_rtt_ms: Option<f64>,
+    window_min_rtt_ms: Option<f64>,
+    previous_min_rtt_ms: Option<f64>,
+    window_started: Instant,
+    gradient: f64,
+}
+
+impl GradientLimiter {
+    /// Folds one sample into the RTT estimates and returns the new limit.
+    /// Overload signals carry no usable RTT and scale by [`MIN_GRADIENT`].
+    fn observe(
+        &self,
+        now: Instant,
+        latency: Duration,
+        overloaded: bool,
+        min_limit: usize,
+        max_limit: usize,
+    ) -> Option<usize> {
+        let mut state = self.state.lock().ok()?;
+        let gradient = if overloaded {
+            MIN_GRADIENT
+        } else {
+            let sample = (latency.as_secs_f64() * 1_000.0).max(0.001);
+            if now.saturating_duration_since(state.window_started) >= self.settings.min_rtt_window {
+                state.previous_min_rtt_ms = state.window_min_rtt_ms.take();
+                state.window_started = now;
+            }
+            let window_min = state
+                .window_min_rtt_ms
+                .map_or(sample, |window_min| window_min.min(sample));
+            state.window_min_rtt_ms = Some(window_min);
+            // TCP-style SRTT with a 1/8 gain.
+            let smoothed = state
+                .smoothed_rtt_ms
+                .map_or(sample, |smoothed| smoothed + (sample - smoothed) / 8.0);
+            state.smoothed_rtt_ms = Some(smoothed);
+            let min_rtt = state
+                .previous_min_rtt_ms
+                .map_or(window_min, |previous| previous.min(window_min));
+            let tolerance = f64::from(self.settings.rtt_tolerance_percent) / 100.0;
+            (min_rtt * tolerance / smoothed).clamp(MIN_GRADIENT, 1.0)
+        };
+        state.gradient = gradient;
+
+        let smoothing = f64::from(self.settings.smoothing_percent) / 100.0;
+        let target = state.limit * gradient + self.settings.queue_size as f64;
+        state.limit = (state.limit * (1.0 - smoothing) + target * smoothing)
+            .clamp(min_limit as f64, max_limit as f64);
+        Some(state.limit.round() as usize)
+    }
+
+    fn snapshot(&self) -> Option<GradientSnapshot> {
+        let state = self.state.lock().ok()?;
+        let smoothed_rtt_ms = state.smoothed_rtt_ms?;
+        let window_min = state.window_min_rtt_ms.unwrap_or(smoothed_rtt_ms);
+        let min_rtt_ms = state
+            .previous_min_rtt_ms
+            .map_or(window_min, |previous| previous.min(window_min));
+        Some(GradientSnapshot {
+            min_rtt: Duration::from_secs_f64(min_rtt_ms / 1_000.0),
+            smoothed_rtt: Duration::from_secs_f64(smoothed_rtt_ms / 1_000.0),
+            gradient: state.gradient,
+        })
+    }
+}
+
 pub struct AdaptiveAdmission {
     enabled: bool,
     min_limit: usize,
@@ -12,6 +109,7 @@ pub struct AdaptiveAdmission {
     increase_step: usize,
     decrease_step: usize,
     high_latency_ms: u64,
+    gradient: Option<GradientLimiter>,
     current_limit: AtomicUsize,
     inflight: AtomicUsize,
 }
@@ -34,15 +132,71 @@ impl AdaptiveAdmission {
             increase_step: increase_step.max(1),
             decrease_step: decrease_step.max(1),
             high_latency_ms: high_latency_ms.max(1),
+            gradient: None,
             current_limit: AtomicUsize::new(max_limit),
             inflight: AtomicUsize::new(0),
         }
     }
 
+    pub fn from_policy(policy: &RuntimeAdaptiveAdmissionPolicy) -> Self {
+        let admission = Self::new(
+            policy.enabled,
+            policy.min_limit,
+            policy.max_limit.max(policy.min_limit),
+            policy.increase_step,
+            policy.decrease_step,
+            policy
+                .high_latency
+                .as_millis()
+                .try_into()
+                .unwrap_or(u64::MAX),
+        );
+        match policy.algorithm {
+            AdaptiveAdmissionAlgorithm::Aimd => admission,
+            AdaptiveAdmissionAlgorithm::Gradient => admission.with_gradient(GradientSettings {
+                min_rtt_window: policy.min_rtt_window,
+                rtt_tolerance_percent: policy.rtt_tolerance_percent,
+                smoothing_percent: policy.smoothing_percent,
+                queue_size: policy.queue_size,
+            }),
+        }
+    }
+
+    /// Switches the limiter from AIMD to the gradient algorithm.
+    pub fn with_gradient(mut self, settings: GradientSettings) -> Self {
+        let settings = GradientSettings {
+            min_rtt_window: settings.min_rtt_window.max(Duration::from_millis(1)),
+            rtt_tolerance_percent: settings.rtt_tolerance_percent.max(100),
+            smoothing_percent: settings.smoothing_percent.clamp(1, 100),
+            queue_size: settings.queue_size.max(1),
+        };
+        self.gradient = Some(GradientLimiter {
+            settings,
+            state: Mutex::new(GradientState {
+                limit: self.max_limit as f64,
+                smoothed_rtt_ms: None,
+                window_min_rtt_ms: None,
+                previous_min_rtt_ms: None,
+                window_started: Instant::now(),
+                gradient: 1.0,
+            }),
+        });
+        self
+    }
+
     pub fn try_acquire(self: &Arc<Self>) -> Option<AdaptivePermit> {
+        self.try_acquire_share(100)
+    }
+
+    /// Admits only while inflight stays below `percent` of the current
+    /// limit, keeping the rest for classes with a larger share.
+    pub fn try_acquire_share(self: &Arc<Self>, percent: u8) -> Option<AdaptivePermit> {
+        let percent = usize::from(percent.clamp(1, 100));
         loop {
             let current = self.inflight.load(Ordering::Relaxed);
-            let limit = self.current_limit.load(Ordering::Relaxed).max(1);
+            let limit = (self.current_limit.load(Ordering::Relaxed) * percent)
+                .div_ceil(100)
+                .max(1);
             if current >= limit {
                 return None;
             }
@@ -62,6 +216,18 @@ impl AdaptiveAdmission {
         if !self.enabled {
             return;
         }
+        if let Some(gradient) = self.gradient.as_ref() {
+            if let Some(next) = gradient.observe(
+                Instant::now(),
+                latency,
+                overloaded,
+                self.min_limit,
+                self.max_limit,
+            ) {
+                self.current_limit.store(next, Ordering::Relaxed);
+            }
+            return;
+        }
         let latency_ms = latency.as_millis() as u64;
         let decrease = overloaded || latency_ms >= self.high_latency_ms;
         loop {
@@ -102,6 +268,20 @@ impl AdaptiveAdmission {
     pub fn enabled(&self) -> bool {
         self.enabled
     }
+
+    pub fn algorithm(&self) -> AdaptiveAdmissionAlgorithm {
+        if self.gradient.is_some() {
+            AdaptiveAdmissionAlgorithm::Gradient
+        } else {
+            AdaptiveAdmissionAlgorithm::Aimd
+        }
+    }
+
+    /// RTT estimates and last gradient; `None` for AIMD limiters and before
+    /// the first latency sample.
+    pub fn gradient_snapshot(&self) -> Option<GradientSnapshot> {
+        self.gradient.as_ref()?.snapshot()
+    }
 }
 
 pub struct AdaptivePermit {
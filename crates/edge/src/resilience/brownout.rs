@@ -3,6 +3,8 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
+use crate::resilience::priority::RequestPriority;
+
 pub struct BrownoutController {
     enabled: bool,
     trigger_inflight_percent: u8,
@@ -53,4 +55,9 @@ impl BrownoutController {
         }
         self.core_routes.contains(route)
     }
+
+    /// Like [`Self::route_allowed`], also admitting brownout-exempt classes.
+    pub fn request_allowed(&self, route: &str, priority: &RequestPriority) -> bool {
+        priority.brownout_exempt || self.route_allowed(route)
+    }
 }
@@ -12,6 +12,30 @@ struct BreakerState {
     half_open: bool,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Breaker state for one backend as the next request would find it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CircuitProbe {
+    pub state: CircuitState,
+    pub allows_request: bool,
+}
+
 pub struct CircuitBreakers {
     enabled: bool,
     failure_threshold: u32,
@@ -67,6 +91,47 @@ impl CircuitBreakers {
         true
     }
 
+    /// What [`allow_request`](Self::allow_request) would answer for `backend`,
+    /// without moving an expired breaker to half-open or taking a probe slot.
+    pub fn peek(&self, backend: &str) -> CircuitProbe {
+        let closed = CircuitProbe {
+            state: CircuitState::Closed,
+            allows_request: true,
+        };
+        if !self.enabled {
+            return closed;
+        }
+        let states = match self.states.lock() {
+            Ok(guard) => guard,
+            Err(_) => return closed,
+        };
+        let Some(state) = states.get(backend) else {
+            return closed;
+        };
+
+        if let Some(until) = state.open_until {
+            if Instant::now() < until {
+                return CircuitProbe {
+                    state: CircuitState::Open,
+                    allows_request: false,
+                };
+            }
+            return CircuitProbe {
+                state: CircuitState::HalfOpen,
+                allows_request: true,
+            };
+        }
+
+        if state.half_open {
+            return CircuitProbe {
+                state: CircuitState::HalfOpen,
+                allows_request: state.half_open_inflight < self.half_open_max_probes,
+            };
+        }
+
+        closed
+    }
+
     pub fn record_success(&self, backend: &str) {
         if !self.enabled {
             return;
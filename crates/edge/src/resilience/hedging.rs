@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use spooky_config::{config::HedgingMode, runtime::RuntimeHedgingPolicy};
+
+use crate::metrics::RouteHealthSample;
+
+/// Tuning for percentile hedging; see `resilience.hedging`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentileDelay {
+    pub percentile: u8,
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    pub min_samples: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HedgeSettings {
+    /// Delay before the first hedge; in percentile mode, the delay of
+    /// routes without enough latency samples yet.
+    pub delay: Duration,
+    pub percentile: Option<PercentileDelay>,
+    pub window: Duration,
+    pub max_hedged_percent: u8,
+    pub max_hedges: u8,
+}
+
+#[derive(Default)]
+struct RouteHedgeState {
+    window_started: Option<Instant>,
+    requests: u64,
+    hedged: u64,
+    delay: Option<Duration>,
+    /// Route latency sample the current percentile window is measured from.
+    baseline: RouteHealthSample,
+}
+
+/// Per-route hedge delay and hedged-share accounting.
+pub struct HedgeController {
+    settings: HedgeSettings,
+    routes: Mutex<HashMap<String, RouteHedgeState>>,
+}
+
+impl HedgeController {
+    pub fn new(settings: HedgeSettings) -> Self {
+        Self {
+            settings: HedgeSettings {
+                window: settings.window.max(Duration::from_millis(1)),
+                max_hedged_percent: settings.max_hedged_percent.clamp(1, 100),
+                max_hedges: settings.max_hedges.max(1),
+                ..settings
+            },
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_policy(policy: &RuntimeHedgingPolicy) -> Self {
+        Self::new(HedgeSettings {
+            delay: policy.delay,
+            percentile: (policy.mode == HedgingMode::Percentile).then_some(PercentileDelay {
+                percentile: policy.percentile,
+                min_delay: policy.min_delay,
+                max_delay: policy.max_delay,
+                min_samples: policy.min_samples,
+            }),
+            window: policy.window,
+            max_hedged_percent: policy.max_hedged_percent,
+            max_hedges: policy.max_hedges,
+        })
+    }
+
+    pub fn max_hedges(&self) -> u8 {
+        self.settings.max_hedges
+    }
+
+    /// Counts a hedge-eligible request on `route` and returns the delay
+    /// before its first hedge. Once per window, percentile mode re-reads the
+    /// route latency through `sample` and moves the delay to the configured
+    /// percentile of the samples recorded since the last move, provided there
+    /// are at least `min_samples` of them.
+    pub fn start_request<F>(&self, route: &str, now: Instant, sample: F) -> Duration
+    where
+        F: FnOnce() -> Option<RouteHealthSample>,
+    {
+        let Ok(mut routes) = self.routes.lock() else {
+            return self.settings.delay;
+        };
+        if !routes.contains_key(route) {
+            routes.insert(route.to_string(), RouteHedgeState::default());
+        }
+        let Some(state) = routes.get_mut(route) else {
+            return self.settings.delay;
+        };
+        let window_elapsed = state
+            .window_started
+            .is_none_or(|started| now.saturating_duration_since(started) >= self.settings.window);
+        if window_elapsed {
+            state.window_started = Some(now);
+            state.requests = 0;
+            state.hedged = 0;
+            if let Some(percentile) = self.settings.percentile
+                && let Some(sample) = sample()
+            {
+                let window = sample.since(&state.baseline);
+                if window.latency_samples() >= percentile.min_samples {
+                    let delay = Duration::from_millis(window.percentile_ms(percentile.percentile));
+                    state.delay = Some(delay.clamp(percentile.min_delay, percentile.max_delay));
+                    state.baseline = sample;
+                }
+            }
+        }
+        state.requests = state.requests.saturating_add(1);
+        state.delay.unwrap_or(self.settings.delay)
+    }
+
+    /// Claims a hedge for one request of `route`, unless the route already
+    /// hedged `max_hedged_percent` of this window's requests.
+    pub fn try_hedge(&self, route: &str) -> bool {
+        if self.settings.max_hedged_percent >= 100 {
+            return true;
+        }
+        let Ok(mut routes) = self.routes.lock() else {
+            return false;
+        };
+        let Some(state) = routes.get_mut(route) else {
+            return false;
+        };
+        let allowed = state.hedged.saturating_mul(100)
+            < state
+                .requests
+                .saturating_mul(u64::from(self.settings.max_hedged_percent));
+        if allowed {
+            state.hedged = state.hedged.saturating_add(1);
+        }
+        allowed
+    }
+}
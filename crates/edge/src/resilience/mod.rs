@@ -2,6 +2,8 @@ pub mod adaptive_admission;
 pub mod brownout;
 pub mod circuit_breaker;
 pub mod connect;
+pub mod hedging;
+pub mod priority;
 pub mod retry_budget;
 pub mod route_queue;
 pub mod runtime;
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+
+use spooky_config::config::Priority;
+
+/// Shedding policy of one request class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestPriority {
+    pub name: String,
+    /// Share of each admission limiter this class may fill.
+    pub max_inflight_percent: u8,
+    pub brownout_exempt: bool,
+}
+
+enum PriorityMatch {
+    Header(String),
+    JwtClaim(String),
+}
+
+struct PriorityRule {
+    class: usize,
+    routes: HashSet<String>,
+    matcher: Option<(PriorityMatch, HashSet<String>)>,
+}
+
+/// Assigns each request a [`RequestPriority`] from `resilience.priority`.
+pub struct PriorityClassifier {
+    classes: Vec<RequestPriority>,
+    default_class: usize,
+    rules: Vec<PriorityRule>,
+}
+
+impl PriorityClassifier {
+    pub fn new(config: &Priority) -> Self {
+        let mut classes = config
+            .classes
+            .iter()
+            .map(|class| RequestPriority {
+                name: class.name.trim().to_string(),
+                max_inflight_percent: class.max_inflight_percent.clamp(1, 100),
+                brownout_exempt: class.brownout_exempt,
+            })
+            .collect::<Vec<_>>();
+        let default_name = config.default_class.trim();
+        let default_class = match classes.iter().position(|class| class.name == default_name) {
+            Some(index) => index,
+            None => {
+                classes.push(RequestPriority {
+                    name: default_name.to_string(),
+                    max_inflight_percent: 100,
+                    brownout_exempt: false,
+                });
+                classes.len() - 1
+            }
+        };
+        let rules = config
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let class = classes
+                    .iter()
+                    .position(|class| class.name == rule.class.trim())?;
+                let matcher = match (rule.header.as_deref(), rule.jwt_claim.as_deref()) {
+                    (Some(header), _) => {
+                        Some(PriorityMatch::Header(header.trim().to_ascii_lowercase()))
+                    }
+                    (None, Some(claim)) => Some(PriorityMatch::JwtClaim(claim.trim().to_string())),
+                    (None, None) => None,
+                };
+                let values = rule
+                    .values
+                    .iter()
+                    .map(|value| value.trim().to_string())
+                    .collect::<HashSet<_>>();
+                Some(PriorityRule {
+                    class,
+                    routes: rule
+                        .routes
+                        .iter()
+                        .map(|route| route.trim().to_string())
+                        .collect(),
+                    matcher: matcher.map(|matcher| (matcher, values)),
+                })
+            })
+            .collect();
+        Self {
+            classes,
+            default_class,
+            rules,
+        }
+    }
+
+    /// Returns the class of the first rule matching the request, or the
+    /// default class. `jwt_claim` yields the values of a claim of the
+    /// verified bearer token, and nothing when there is none.
+    pub fn classify<H, C>(&self, route: &str, mut header: H, mut jwt_claim: C) -> &RequestPriority
+    where
+        H: FnMut(&str) -> Option<String>,
+        C: FnMut(&str) -> Vec<String>,
+    {
+        let matched = self.rules.iter().find(|rule| {
+            if !rule.routes.is_empty() && !rule.routes.contains(route) {
+                return false;
+            }
+            match &rule.matcher {
+                None => true,
+                Some((PriorityMatch::Header(name), values)) => {
+                    header(name).is_some_and(|value| values.contains(value.trim()))
+                }
+                Some((PriorityMatch::JwtClaim(name), values)) => jwt_claim(name)
+                    .iter()
+                    .any(|value| values.contains(value.as_str())),
+            }
+        });
+        &self.classes[matched.map_or(self.default_class, |rule| rule.class)]
+    }
+
+    /// The class named `name`, falling back to the default class for
+    /// unclassified requests and names a reload removed.
+    pub fn class_or_default(&self, name: Option<&str>) -> &RequestPriority {
+        name.and_then(|name| self.classes.iter().find(|class| class.name == name))
+            .unwrap_or_else(|| self.default_class())
+    }
+
+    pub fn default_class(&self) -> &RequestPriority {
+        &self.classes[self.default_class]
+    }
+
+    /// Whether any rule reads a JWT claim, so callers can skip verifying
+    /// the token otherwise.
+    pub fn uses_jwt_claims(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| matches!(rule.matcher, Some((PriorityMatch::JwtClaim(_), _))))
+    }
+}
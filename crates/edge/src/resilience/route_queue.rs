@@ -36,17 +36,24 @@ impl RouteQueueLimiter {
         self: &Arc<Self>,
         route: &str,
     ) -> Result<RouteQueuePermit, RouteQueueRejection> {
-        let cap = self
-            .caps
-            .get(route)
-            .copied()
-            .unwrap_or(self.default_cap)
-            .max(1);
+        self.try_acquire_share(route, 100)
+    }
+
+    /// Like [`Self::try_acquire`] with the global and route caps scaled to
+    /// `percent`, keeping the rest for classes with a larger share.
+    pub fn try_acquire_share(
+        self: &Arc<Self>,
+        route: &str,
+        percent: u8,
+    ) -> Result<RouteQueuePermit, RouteQueueRejection> {
+        let percent = usize::from(percent.clamp(1, 100));
+        let share = |cap: usize| (cap * percent).div_ceil(100).max(1);
+        let cap = share(self.caps.get(route).copied().unwrap_or(self.default_cap));
         let mut guard = self
             .inflight
             .lock()
             .map_err(|_| RouteQueueRejection::GlobalCap)?;
-        if guard.total >= self.global_cap {
+        if guard.total >= share(self.global_cap) {
             return Err(RouteQueueRejection::GlobalCap);
         }
         let current = guard.by_route.get(route).copied().unwrap_or(0);
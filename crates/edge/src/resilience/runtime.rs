@@ -1,15 +1,21 @@
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use spooky_config::{
-    config::Resilience as ResilienceConfig,
-    runtime::{RuntimeAdmissionPolicy, RuntimeRateLimitPolicy},
+    config::{AdaptiveAdmissionAlgorithm, HedgingMode, Resilience as ResilienceConfig},
+    runtime::{RuntimeAdaptiveAdmissionPolicy, RuntimeAdmissionPolicy, RuntimeRateLimitPolicy},
 };
 
 use crate::resilience::{
-    adaptive_admission::AdaptiveAdmission,
+    adaptive_admission::{AdaptiveAdmission, GradientSettings},
     brownout::BrownoutController,
     circuit_breaker::CircuitBreakers,
     connect::{connect_authority_port, normalize_connect_authority},
+    hedging::{HedgeController, HedgeSettings, PercentileDelay},
+    priority::PriorityClassifier,
     retry_budget::RetryBudget,
     route_queue::RouteQueueLimiter,
     scoped_rate_limit::ScopedRateLimiters,
@@ -17,11 +23,14 @@ use crate::resilience::{
 
 pub struct RuntimeResilience {
     pub adaptive_admission: Arc<AdaptiveAdmission>,
+    /// Limiters of upstreams with their own `adaptive_admission` block.
+    pub upstream_adaptive_admission: HashMap<String, Arc<AdaptiveAdmission>>,
     pub route_queue: Arc<RouteQueueLimiter>,
     pub scoped_rate_limits: Arc<ScopedRateLimiters>,
     pub circuit_breakers: Arc<CircuitBreakers>,
     pub retry_budget: Arc<RetryBudget>,
     pub brownout: Arc<BrownoutController>,
+    pub priority: Arc<PriorityClassifier>,
     pub shed_retry_after_seconds: u32,
     pub allow_0rtt: bool,
     pub max_headers_count: usize,
@@ -29,7 +38,7 @@ pub struct RuntimeResilience {
     pub enforce_authority_host_match: bool,
     pub allow_connect: bool,
     pub hedging_enabled: bool,
-    pub hedging_delay: Duration,
+    pub hedging: Arc<HedgeController>,
     hedge_safe_methods: HashSet<String>,
     early_data_safe_methods: HashSet<String>,
     allowed_methods: HashSet<String>,
@@ -42,7 +51,7 @@ pub struct RuntimeResilience {
 impl RuntimeResilience {
     pub fn from_config(config: &ResilienceConfig, global_limit: usize) -> Self {
         let adaptive = &config.adaptive_admission;
-        let admission = Arc::new(AdaptiveAdmission::new(
+        let admission = AdaptiveAdmission::new(
             adaptive.enabled,
             adaptive.min_limit,
             adaptive
@@ -52,7 +61,16 @@ impl RuntimeResilience {
             adaptive.increase_step,
             adaptive.decrease_step,
             adaptive.high_latency_ms,
-        ));
+        );
+        let admission = Arc::new(match adaptive.algorithm {
+            AdaptiveAdmissionAlgorithm::Aimd => admission,
+            AdaptiveAdmissionAlgorithm::Gradient => admission.with_gradient(GradientSettings {
+                min_rtt_window: Duration::from_millis(adaptive.min_rtt_window_ms),
+                rtt_tolerance_percent: adaptive.rtt_tolerance_percent,
+                smoothing_percent: adaptive.smoothing_percent,
+                queue_size: adaptive.queue_size,
+            }),
+        });
         let route_queue = Arc::new(RouteQueueLimiter::new(
             config.route_queue.default_cap,
             config.route_queue.global_cap,
@@ -77,6 +95,20 @@ impl RuntimeResilience {
             config.brownout.recover_inflight_percent,
             config.brownout.core_routes.clone(),
         ));
+        let priority = Arc::new(PriorityClassifier::new(&config.priority));
+        let hedging = &config.hedging;
+        let hedge_controller = Arc::new(HedgeController::new(HedgeSettings {
+            delay: Duration::from_millis(hedging.delay_ms),
+            percentile: (hedging.mode == HedgingMode::Percentile).then_some(PercentileDelay {
+                percentile: hedging.percentile,
+                min_delay: Duration::from_millis(hedging.min_delay_ms),
+                max_delay: Duration::from_millis(hedging.max_delay_ms),
+                min_samples: hedging.min_samples,
+            }),
+            window: Duration::from_millis(hedging.window_ms),
+            max_hedged_percent: hedging.max_hedged_percent,
+            max_hedges: hedging.max_hedges,
+        }));
         let hedge_safe_methods = config
             .hedging
             .safe_methods
@@ -116,11 +148,13 @@ impl RuntimeResilience {
 
         Self {
             adaptive_admission: admission,
+            upstream_adaptive_admission: HashMap::new(),
             route_queue,
             scoped_rate_limits,
             circuit_breakers,
             retry_budget,
             brownout,
+            priority,
             shed_retry_after_seconds: config.route_queue.shed_retry_after_seconds.max(1),
             allow_0rtt: config.protocol.allow_0rtt,
             max_headers_count: config.protocol.max_headers_count.max(1),
@@ -128,7 +162,7 @@ impl RuntimeResilience {
             enforce_authority_host_match: config.protocol.enforce_authority_host_match,
             allow_connect: config.protocol.allow_connect,
             hedging_enabled: config.hedging.enabled,
-            hedging_delay: Duration::from_millis(config.hedging.delay_ms),
+            hedging: hedge_controller,
             hedge_safe_methods,
             early_data_safe_methods,
             allowed_methods,
@@ -142,20 +176,20 @@ impl RuntimeResilience {
     pub fn from_policies(
         admission_policy: &RuntimeAdmissionPolicy,
         rate_limit_policy: &RuntimeRateLimitPolicy,
+        upstream_adaptive_policies: &HashMap<String, RuntimeAdaptiveAdmissionPolicy>,
     ) -> Self {
-        let adaptive = &admission_policy.adaptive_admission;
-        let admission = Arc::new(AdaptiveAdmission::new(
-            adaptive.enabled,
-            adaptive.min_limit,
-            adaptive.max_limit.max(adaptive.min_limit),
-            adaptive.increase_step,
-            adaptive.decrease_step,
-            adaptive
-                .high_latency
-                .as_millis()
-                .try_into()
-                .unwrap_or(u64::MAX),
+        let admission = Arc::new(AdaptiveAdmission::from_policy(
+            &admission_policy.adaptive_admission,
         ));
+        let upstream_adaptive_admission = upstream_adaptive_policies
+            .iter()
+            .map(|(upstream, policy)| {
+                (
+                    upstream.clone(),
+                    Arc::new(AdaptiveAdmission::from_policy(policy)),
+                )
+            })
+            .collect();
         let route_queue = Arc::new(RouteQueueLimiter::new(
             admission_policy.route_queue.default_cap,
             admission_policy.route_queue.global_cap,
@@ -196,6 +230,7 @@ impl RuntimeResilience {
             admission_policy.brownout.recover_inflight_percent,
             admission_policy.brownout.core_routes.clone(),
         ));
+        let priority = Arc::new(PriorityClassifier::new(&admission_policy.priority.0));
         let hedge_safe_methods = admission_policy
             .hedging
             .safe_methods
@@ -239,11 +274,13 @@ impl RuntimeResilience {
 
         Self {
             adaptive_admission: admission,
+            upstream_adaptive_admission,
             route_queue,
             scoped_rate_limits,
             circuit_breakers,
             retry_budget,
             brownout,
+            priority,
             shed_retry_after_seconds: admission_policy.route_queue.shed_retry_after_seconds,
             allow_0rtt: admission_policy.protocol.0.allow_0rtt,
             max_headers_count: admission_policy.protocol.0.max_headers_count.max(1),
@@ -251,7 +288,7 @@ impl RuntimeResilience {
             enforce_authority_host_match: admission_policy.protocol.0.enforce_authority_host_match,
             allow_connect: admission_policy.protocol.0.allow_connect,
             hedging_enabled: admission_policy.hedging.enabled,
-            hedging_delay: admission_policy.hedging.delay,
+            hedging: Arc::new(HedgeController::from_policy(&admission_policy.hedging)),
             hedge_safe_methods,
             early_data_safe_methods,
             allowed_methods,
@@ -262,6 +299,14 @@ impl RuntimeResilience {
         }
     }
 
+    /// Limiter that admits and observes requests to `upstream`: its own one
+    /// when configured, otherwise the global limiter.
+    pub fn adaptive_admission_for(&self, upstream: Option<&str>) -> &Arc<AdaptiveAdmission> {
+        upstream
+            .and_then(|upstream| self.upstream_adaptive_admission.get(upstream))
+            .unwrap_or(&self.adaptive_admission)
+    }
+
     pub fn hedging_route_enabled_for(&self, route: &str) -> bool {
         if !self.hedging_enabled || self.brownout.is_active() {
             return false;
@@ -27,6 +27,14 @@ impl ScopedRateLimitBucket {
         }
     }
 
+    fn has_token(&self) -> bool {
+        let refill = Instant::now()
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64()
+            * self.rate_per_sec;
+        !refill.is_finite() || self.tokens + refill >= 1.0
+    }
+
     fn try_consume(&mut self) -> bool {
         let now = Instant::now();
         let refill = now
@@ -109,6 +117,16 @@ impl ScopedRateLimitRule {
             .or_insert_with(|| ScopedRateLimitBucket::new(self.rate_per_sec, self.burst));
         bucket.try_consume()
     }
+
+    fn would_allow(&self, key: &str) -> bool {
+        let buckets = match self.buckets.lock() {
+            Ok(guard) => guard,
+            Err(_) => return true,
+        };
+        buckets
+            .get(key)
+            .is_none_or(ScopedRateLimitBucket::has_token)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -118,6 +136,14 @@ pub struct ScopedRateLimitRejection {
     pub retry_after_seconds: u32,
 }
 
+/// One rule's verdict for a request, taken without spending a token.
+#[derive(Debug, Clone)]
+pub struct ScopedRateLimitProbe {
+    pub rule_name: String,
+    pub key: String,
+    pub allows_request: bool,
+}
+
 pub struct ScopedRateLimiters {
     rules: Vec<Arc<ScopedRateLimitRule>>,
 }
@@ -154,4 +180,30 @@ impl ScopedRateLimiters {
         }
         None
     }
+
+    /// Evaluates every rule that applies to `route` the way [`check`](Self::check)
+    /// would, reporting each rule's key and verdict. No tokens are consumed.
+    pub fn probe<F>(&self, route: &str, mut key_for_rule: F) -> Vec<ScopedRateLimitProbe>
+    where
+        F: FnMut(&ScopedRateLimitRule) -> Option<String>,
+    {
+        let mut probes = Vec::new();
+        for rule in &self.rules {
+            if !rule.applies_to_route(route) {
+                continue;
+            }
+            let Some(key) = key_for_rule(rule) else {
+                continue;
+            };
+            if key.is_empty() {
+                continue;
+            }
+            probes.push(ScopedRateLimitProbe {
+                rule_name: rule.name.clone(),
+                allows_request: rule.would_allow(&key),
+                key,
+            });
+        }
+        probes
+    }
 }
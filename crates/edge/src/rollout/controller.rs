@@ -0,0 +1,742 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use log::{info, warn};
+use spooky_config::runtime::{RuntimeCanaryRollout, RuntimeRoute};
+
+use crate::{Metrics, metrics::RouteHealthSample, routing::index::RouteIndex};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RolloutPhase {
+    Running,
+    /// Held at the current step; the canary keeps its share.
+    Paused,
+    Promoted,
+    RolledBack,
+    Aborted,
+}
+
+impl RolloutPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Paused => "paused",
+            Self::Promoted => "promoted",
+            Self::RolledBack => "rolled_back",
+            Self::Aborted => "aborted",
+        }
+    }
+
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Promoted | Self::RolledBack | Self::Aborted)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RolloutCommand {
+    Pause,
+    Resume,
+    Abort,
+}
+
+impl RolloutCommand {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "abort" => Some(Self::Abort),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.transition().as_str()
+    }
+
+    fn transition(self) -> RolloutTransition {
+        match self {
+            Self::Pause => RolloutTransition::Pause,
+            Self::Resume => RolloutTransition::Resume,
+            Self::Abort => RolloutTransition::Abort,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RolloutTransition {
+    Advance,
+    Promote,
+    RollBack,
+    Pause,
+    Resume,
+    Abort,
+}
+
+impl RolloutTransition {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Advance => "advance",
+            Self::Promote => "promote",
+            Self::RollBack => "rollback",
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Abort => "abort",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RolloutCommandError {
+    UnknownRoute(String),
+    InvalidPhase {
+        route: String,
+        phase: RolloutPhase,
+        command: RolloutCommand,
+    },
+}
+
+impl fmt::Display for RolloutCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRoute(route) => write!(f, "route '{route}' has no rollout"),
+            Self::InvalidPhase {
+                route,
+                phase,
+                command,
+            } => write!(
+                f,
+                "cannot {} rollout for route '{}' while {}",
+                command.as_str(),
+                route,
+                phase.as_str()
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolloutStatus {
+    pub route: String,
+    pub canary: String,
+    pub baselines: Vec<String>,
+    pub phase: RolloutPhase,
+    /// Index into `steps` of the current canary share.
+    pub step: usize,
+    pub steps: Vec<u32>,
+    pub canary_percent: u32,
+    /// Why the most recent transition happened.
+    pub last_reason: Option<String>,
+}
+
+/// Rollout state for every route of one runtime generation whose split has a
+/// `rollout`. Canary shares are published through the route index cells, so
+/// the data path never takes the progress locks.
+pub struct RolloutController {
+    rollouts: Vec<RouteRollout>,
+    metrics: Arc<Metrics>,
+}
+
+impl RolloutController {
+    pub fn new(
+        routes: &HashMap<String, RuntimeRoute>,
+        routing_index: &RouteIndex,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        let now = Instant::now();
+        let mut rollouts = routes
+            .iter()
+            .filter_map(|(name, route)| {
+                let policy = route.split.as_ref()?.rollout.clone()?;
+                let canary_percent = routing_index.canary_percent_cell(name)?;
+                Some(RouteRollout::start(
+                    name,
+                    policy,
+                    canary_percent,
+                    now,
+                    &metrics,
+                ))
+            })
+            .collect::<Vec<_>>();
+        rollouts.sort_by(|left, right| left.route.cmp(&right.route));
+
+        Self { rollouts, metrics }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rollouts.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rollouts.len()
+    }
+
+    pub fn shortest_step_interval(&self) -> Option<Duration> {
+        self.rollouts
+            .iter()
+            .map(|rollout| rollout.policy.step_interval)
+            .min()
+    }
+
+    pub fn statuses(&self) -> Vec<RolloutStatus> {
+        self.rollouts.iter().map(RouteRollout::status).collect()
+    }
+
+    pub fn status(&self, route: &str) -> Option<RolloutStatus> {
+        self.find(route).map(RouteRollout::status)
+    }
+
+    pub fn apply(
+        &self,
+        route: &str,
+        command: RolloutCommand,
+    ) -> Result<RolloutStatus, RolloutCommandError> {
+        let rollout = self
+            .find(route)
+            .ok_or_else(|| RolloutCommandError::UnknownRoute(route.to_string()))?;
+        rollout.apply(command, Instant::now(), &self.metrics)
+    }
+
+    /// Judges every running rollout whose current step has lasted a full
+    /// `step_interval`, then advances, promotes or rolls it back.
+    pub fn evaluate(&self, now: Instant) {
+        for rollout in &self.rollouts {
+            rollout.evaluate(now, &self.metrics);
+        }
+    }
+
+    /// Carries progress over from the previous generation for rollouts whose
+    /// schedule is unchanged, so a config reload does not restart them. The
+    /// current step's observation window restarts against the new metrics.
+    pub fn inherit_from(&self, previous: &RolloutController) {
+        for rollout in &self.rollouts {
+            let Some(prior) = previous.find(&rollout.route) else {
+                continue;
+            };
+            if prior.policy != rollout.policy {
+                info!(
+                    "Rollout route={} restarted: schedule changed on reload",
+                    rollout.route
+                );
+                continue;
+            }
+            let prior_progress = prior.lock_progress();
+            let mut progress = rollout.lock_progress();
+            progress.phase = prior_progress.phase;
+            progress.step = prior_progress.step;
+            progress.last_reason = prior_progress.last_reason.clone();
+            rollout.publish_percent(prior.canary_percent.load(Ordering::Relaxed), &self.metrics);
+            info!(
+                "Rollout route={} carried over reload phase={} canary_percent={}",
+                rollout.route,
+                progress.phase.as_str(),
+                rollout.canary_percent.load(Ordering::Relaxed)
+            );
+        }
+    }
+
+    fn find(&self, route: &str) -> Option<&RouteRollout> {
+        self.rollouts
+            .binary_search_by(|rollout| rollout.route.as_str().cmp(route))
+            .ok()
+            .map(|idx| &self.rollouts[idx])
+    }
+}
+
+struct RouteRollout {
+    route: String,
+    policy: RuntimeCanaryRollout,
+    canary_percent: Arc<AtomicU32>,
+    progress: Mutex<RolloutProgress>,
+}
+
+struct RolloutProgress {
+    phase: RolloutPhase,
+    step: usize,
+    window_started_at: Instant,
+    canary_start: RouteHealthSample,
+    baseline_start: RouteHealthSample,
+    last_reason: Option<String>,
+}
+
+impl RouteRollout {
+    fn start(
+        route: &str,
+        policy: RuntimeCanaryRollout,
+        canary_percent: Arc<AtomicU32>,
+        now: Instant,
+        metrics: &Metrics,
+    ) -> Self {
+        let (canary_start, baseline_start) = Self::samples(&policy, metrics);
+        // A single-step schedule has nothing left to judge.
+        let phase = if policy.steps.len() > 1 {
+            RolloutPhase::Running
+        } else {
+            RolloutPhase::Promoted
+        };
+        let rollout = Self {
+            route: route.to_string(),
+            progress: Mutex::new(RolloutProgress {
+                phase,
+                step: 0,
+                window_started_at: now,
+                canary_start,
+                baseline_start,
+                last_reason: None,
+            }),
+            canary_percent,
+            policy,
+        };
+        rollout.publish_percent(
+            rollout.policy.steps.first().copied().unwrap_or_default(),
+            metrics,
+        );
+        rollout
+    }
+
+    fn lock_progress(&self) -> MutexGuard<'_, RolloutProgress> {
+        match self.progress.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn samples(
+        policy: &RuntimeCanaryRollout,
+        metrics: &Metrics,
+    ) -> (RouteHealthSample, RouteHealthSample) {
+        let canary = metrics
+            .upstream_health_sample(&policy.canary)
+            .unwrap_or_default();
+        let mut baseline = RouteHealthSample::default();
+        for upstream in &policy.baselines {
+            if let Some(sample) = metrics.upstream_health_sample(upstream) {
+                baseline.accumulate(&sample);
+            }
+        }
+        (canary, baseline)
+    }
+
+    fn publish_percent(&self, percent: u32, metrics: &Metrics) {
+        self.canary_percent.store(percent, Ordering::Relaxed);
+        metrics.set_rollout_canary_percent(&self.route, percent);
+    }
+
+    fn restart_window(&self, progress: &mut RolloutProgress, now: Instant, metrics: &Metrics) {
+        let (canary_start, baseline_start) = Self::samples(&self.policy, metrics);
+        progress.window_started_at = now;
+        progress.canary_start = canary_start;
+        progress.baseline_start = baseline_start;
+    }
+
+    fn evaluate(&self, now: Instant, metrics: &Metrics) {
+        let mut progress = self.lock_progress();
+        if progress.phase != RolloutPhase::Running
+            || now.saturating_duration_since(progress.window_started_at) < self.policy.step_interval
+        {
+            return;
+        }
+
+        let (canary_now, baseline_now) = Self::samples(&self.policy, metrics);
+        let canary = canary_now.since(&progress.canary_start);
+        let baseline = baseline_now.since(&progress.baseline_start);
+        if canary.requests < self.policy.min_requests {
+            // Not enough canary traffic to judge yet; keep collecting.
+            return;
+        }
+
+        if let Some(reason) = self.regression(&canary, &baseline) {
+            progress.phase = RolloutPhase::RolledBack;
+            self.publish_percent(0, metrics);
+            self.record(&mut progress, RolloutTransition::RollBack, reason, metrics);
+            return;
+        }
+
+        let next_step = progress.step + 1;
+        let Some(percent) = self.policy.steps.get(next_step).copied() else {
+            return;
+        };
+        progress.step = next_step;
+        self.publish_percent(percent, metrics);
+        progress.window_started_at = now;
+        progress.canary_start = canary_now;
+        progress.baseline_start = baseline_now;
+        let reason = format!(
+            "canary healthy over {} requests: error rate {} (baseline {}), p95 {}ms (baseline {}ms)",
+            canary.requests,
+            format_basis_points(canary.error_rate_basis_points()),
+            format_basis_points(baseline.error_rate_basis_points()),
+            canary.p95_ms(),
+            baseline.p95_ms()
+        );
+        let transition = if next_step + 1 == self.policy.steps.len() {
+            progress.phase = RolloutPhase::Promoted;
+            RolloutTransition::Promote
+        } else {
+            RolloutTransition::Advance
+        };
+        self.record(&mut progress, transition, reason, metrics);
+    }
+
+    fn regression(
+        &self,
+        canary: &RouteHealthSample,
+        baseline: &RouteHealthSample,
+    ) -> Option<String> {
+        let canary_error_rate = canary.error_rate_basis_points();
+        let baseline_error_rate = baseline.error_rate_basis_points();
+        let allowed_increase = u64::from(self.policy.max_error_rate_increase_percent) * 100;
+        if canary_error_rate > baseline_error_rate.saturating_add(allowed_increase) {
+            return Some(format!(
+                "canary error rate {} exceeds baseline {} by more than {} points",
+                format_basis_points(canary_error_rate),
+                format_basis_points(baseline_error_rate),
+                self.policy.max_error_rate_increase_percent
+            ));
+        }
+
+        if canary.latency_samples() == 0 || baseline.latency_samples() == 0 {
+            return None;
+        }
+        let canary_p95 = canary.p95_ms();
+        let baseline_p95 = baseline.p95_ms();
+        if canary_p95.saturating_mul(100)
+            > baseline_p95.saturating_mul(u64::from(self.policy.max_p95_latency_ratio_percent))
+        {
+            return Some(format!(
+                "canary p95 {}ms exceeds {}% of baseline p95 {}ms",
+                canary_p95, self.policy.max_p95_latency_ratio_percent, baseline_p95
+            ));
+        }
+        None
+    }
+
+    fn apply(
+        &self,
+        command: RolloutCommand,
+        now: Instant,
+        metrics: &Metrics,
+    ) -> Result<RolloutStatus, RolloutCommandError> {
+        let mut progress = self.lock_progress();
+        match (command, progress.phase) {
+            (RolloutCommand::Pause, RolloutPhase::Running) => {
+                progress.phase = RolloutPhase::Paused;
+            }
+            (RolloutCommand::Resume, RolloutPhase::Paused) => {
+                progress.phase = RolloutPhase::Running;
+                // Judge the resumed step on a fresh, full window.
+                self.restart_window(&mut progress, now, metrics);
+            }
+            (RolloutCommand::Abort, RolloutPhase::Running | RolloutPhase::Paused) => {
+                progress.phase = RolloutPhase::Aborted;
+                self.publish_percent(0, metrics);
+            }
+            (command, phase) => {
+                return Err(RolloutCommandError::InvalidPhase {
+                    route: self.route.clone(),
+                    phase,
+                    command,
+                });
+            }
+        }
+        self.record(
+            &mut progress,
+            command.transition(),
+            format!("operator requested {}", command.as_str()),
+            metrics,
+        );
+        Ok(self.status_locked(&progress))
+    }
+
+    fn record(
+        &self,
+        progress: &mut RolloutProgress,
+        transition: RolloutTransition,
+        reason: String,
+        metrics: &Metrics,
+    ) {
+        metrics.record_rollout_transition(&self.route, transition.as_str());
+        let canary_percent = self.canary_percent.load(Ordering::Relaxed);
+        if transition == RolloutTransition::RollBack {
+            warn!(
+                "Rollout route={} canary={} transition={} phase={} canary_percent={} reason={}",
+                self.route,
+                self.policy.canary,
+                transition.as_str(),
+                progress.phase.as_str(),
+                canary_percent,
+                reason
+            );
+        } else {
+            info!(
+                "Rollout route={} canary={} transition={} phase={} canary_percent={} reason={}",
+                self.route,
+                self.policy.canary,
+                transition.as_str(),
+                progress.phase.as_str(),
+                canary_percent,
+                reason
+            );
+        }
+        progress.last_reason = Some(reason);
+    }
+
+    fn status(&self) -> RolloutStatus {
+        self.status_locked(&self.lock_progress())
+    }
+
+    fn status_locked(&self, progress: &RolloutProgress) -> RolloutStatus {
+        RolloutStatus {
+            route: self.route.clone(),
+            canary: self.policy.canary.clone(),
+            baselines: self.policy.baselines.clone(),
+            phase: progress.phase,
+            step: progress.step,
+            steps: self.policy.steps.clone(),
+            canary_percent: self.canary_percent.load(Ordering::Relaxed),
+            last_reason: progress.last_reason.clone(),
+        }
+    }
+}
+
+fn format_basis_points(value: u64) -> String {
+    format!("{}.{:02}%", value / 100, value % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use spooky_config::runtime::{
+        RuntimeRouteMatchPolicy, RuntimeSplitTarget, RuntimeTrafficSplit,
+    };
+
+    use super::*;
+    use crate::RouteOutcome;
+
+    const STEP: Duration = Duration::from_secs(60);
+
+    fn rollout_routes(steps: Vec<u32>) -> HashMap<String, RuntimeRoute> {
+        let split = RuntimeTrafficSplit {
+            key_spec: None,
+            targets: vec![
+                RuntimeSplitTarget {
+                    upstream: "shop".to_string(),
+                    weight: 1,
+                },
+                RuntimeSplitTarget {
+                    upstream: "shop-canary".to_string(),
+                    weight: 1,
+                },
+            ],
+            total_weight: 2,
+            rollout: Some(RuntimeCanaryRollout {
+                canary: "shop-canary".to_string(),
+                baselines: vec!["shop".to_string()],
+                steps,
+                step_interval: STEP,
+                min_requests: 10,
+                max_error_rate_increase_percent: 1,
+                max_p95_latency_ratio_percent: 150,
+            }),
+        };
+        HashMap::from([(
+            "checkout".to_string(),
+            RuntimeRoute {
+                name: "checkout".to_string(),
+                upstream: "shop".to_string(),
+                route: RuntimeRouteMatchPolicy {
+                    host: None,
+                    host_pattern: None,
+                    path_prefix: Some("/checkout".to_string()),
+                    method: None,
+                    path_len: "/checkout".len(),
+                    host_specific: false,
+                    method_specific: false,
+                },
+                policy: Default::default(),
+                split: Some(split),
+                action: None,
+            },
+        )])
+    }
+
+    fn build_controller(steps: Vec<u32>) -> (RolloutController, Arc<RouteIndex>, Arc<Metrics>) {
+        let routes = rollout_routes(steps);
+        let routing_index = Arc::new(RouteIndex::from_runtime_routes(&routes));
+        let metrics = Arc::new(Metrics::new(
+            1,
+            ["shop".to_string(), "shop-canary".to_string()],
+        ));
+        let controller = RolloutController::new(&routes, &routing_index, Arc::clone(&metrics));
+        (controller, routing_index, metrics)
+    }
+
+    fn record(metrics: &Metrics, upstream: &str, count: usize, latency_ms: u64, ok: bool) {
+        let (status, outcome) = if ok {
+            (200, RouteOutcome::Success)
+        } else {
+            (502, RouteOutcome::BackendError)
+        };
+        for _ in 0..count {
+            metrics.record_request_result(
+                upstream,
+                None,
+                Some(status),
+                outcome,
+                Duration::from_millis(latency_ms),
+            );
+        }
+    }
+
+    fn live_percent(routing_index: &RouteIndex) -> Option<u32> {
+        routing_index
+            .lookup_with_decision("/checkout", None)
+            .and_then(|decision| decision.canary_percent)
+    }
+
+    #[test]
+    fn healthy_canary_steps_through_schedule_and_promotes() {
+        let (controller, routing_index, metrics) = build_controller(vec![1, 25, 100]);
+        let started = Instant::now();
+        assert_eq!(live_percent(&routing_index), Some(1));
+
+        record(&metrics, "shop", 200, 20, true);
+        record(&metrics, "shop-canary", 5, 20, true);
+        controller.evaluate(started + STEP);
+        assert_eq!(
+            controller.status("checkout").expect("status").step,
+            0,
+            "below min_requests the step is held"
+        );
+
+        record(&metrics, "shop-canary", 10, 20, true);
+        controller.evaluate(started + STEP);
+        let status = controller.status("checkout").expect("status");
+        assert_eq!(status.phase, RolloutPhase::Running);
+        assert_eq!(status.canary_percent, 25);
+        assert_eq!(live_percent(&routing_index), Some(25));
+
+        record(&metrics, "shop-canary", 20, 20, true);
+        controller.evaluate(started + STEP + STEP / 2);
+        assert_eq!(live_percent(&routing_index), Some(25));
+        controller.evaluate(started + STEP * 2);
+        let status = controller.status("checkout").expect("status");
+        assert_eq!(status.phase, RolloutPhase::Promoted);
+        assert_eq!(status.canary_percent, 100);
+
+        let output = metrics.render_prometheus();
+        assert!(output.contains(
+            "spooky_rollout_transitions_total{route=\"checkout\",transition=\"advance\"} 1"
+        ));
+        assert!(output.contains(
+            "spooky_rollout_transitions_total{route=\"checkout\",transition=\"promote\"} 1"
+        ));
+        assert!(output.contains("spooky_rollout_canary_percent{route=\"checkout\"} 100"));
+    }
+
+    #[test]
+    fn canary_regression_rolls_back_to_zero() {
+        let (controller, routing_index, metrics) = build_controller(vec![5, 50, 100]);
+        let started = Instant::now();
+        record(&metrics, "shop", 100, 20, true);
+        record(&metrics, "shop-canary", 18, 20, true);
+        record(&metrics, "shop-canary", 2, 20, false);
+
+        controller.evaluate(started + STEP);
+        let status = controller.status("checkout").expect("status");
+        assert_eq!(status.phase, RolloutPhase::RolledBack);
+        assert_eq!(status.canary_percent, 0);
+        assert_eq!(live_percent(&routing_index), Some(0));
+        assert!(
+            status
+                .last_reason
+                .as_deref()
+                .is_some_and(|reason| reason.contains("error rate 10.00%"))
+        );
+
+        let (controller, _, metrics) = build_controller(vec![5, 50, 100]);
+        record(&metrics, "shop", 100, 20, true);
+        record(&metrics, "shop-canary", 20, 400, true);
+        controller.evaluate(Instant::now() + STEP);
+        let status = controller.status("checkout").expect("status");
+        assert_eq!(status.phase, RolloutPhase::RolledBack);
+        assert!(
+            status
+                .last_reason
+                .as_deref()
+                .is_some_and(|reason| reason.contains("p95 500ms"))
+        );
+    }
+
+    #[test]
+    fn operator_commands_pause_resume_and_abort() {
+        let (controller, routing_index, metrics) = build_controller(vec![5, 50, 100]);
+        let started = Instant::now();
+
+        let status = controller
+            .apply("checkout", RolloutCommand::Pause)
+            .expect("pause");
+        assert_eq!(status.phase, RolloutPhase::Paused);
+        record(&metrics, "shop", 100, 20, true);
+        record(&metrics, "shop-canary", 20, 20, true);
+        controller.evaluate(started + STEP * 10);
+        assert_eq!(live_percent(&routing_index), Some(5));
+
+        assert_eq!(
+            controller.apply("checkout", RolloutCommand::Pause),
+            Err(RolloutCommandError::InvalidPhase {
+                route: "checkout".to_string(),
+                phase: RolloutPhase::Paused,
+                command: RolloutCommand::Pause,
+            })
+        );
+        controller
+            .apply("checkout", RolloutCommand::Resume)
+            .expect("resume");
+        let status = controller
+            .apply("checkout", RolloutCommand::Abort)
+            .expect("abort");
+        assert_eq!(status.phase, RolloutPhase::Aborted);
+        assert_eq!(live_percent(&routing_index), Some(0));
+        assert!(
+            controller
+                .apply("checkout", RolloutCommand::Resume)
+                .is_err()
+        );
+        assert_eq!(
+            controller.apply("cart", RolloutCommand::Abort),
+            Err(RolloutCommandError::UnknownRoute("cart".to_string()))
+        );
+    }
+
+    #[test]
+    fn reload_inherits_progress_for_unchanged_schedule() {
+        let (previous, _, metrics) = build_controller(vec![5, 50, 100]);
+        record(&metrics, "shop", 100, 20, true);
+        record(&metrics, "shop-canary", 20, 20, true);
+        previous.evaluate(Instant::now() + STEP);
+        previous
+            .apply("checkout", RolloutCommand::Pause)
+            .expect("pause");
+
+        let (next, routing_index, _) = build_controller(vec![5, 50, 100]);
+        next.inherit_from(&previous);
+        let status = next.status("checkout").expect("status");
+        assert_eq!(status.phase, RolloutPhase::Paused);
+        assert_eq!(status.step, 1);
+        assert_eq!(live_percent(&routing_index), Some(50));
+
+        let (changed, routing_index, _) = build_controller(vec![10, 100]);
+        changed.inherit_from(&previous);
+        assert_eq!(
+            changed.status("checkout").expect("status").phase,
+            RolloutPhase::Running
+        );
+        assert_eq!(live_percent(&routing_index), Some(10));
+    }
+}
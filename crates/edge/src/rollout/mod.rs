@@ -0,0 +1,8 @@
+//! Progressive canary rollouts for weighted route splits.
+//!
+//! The controller owns per-route rollout state and the canary share cells the
+//! route index reads on every request. The service steps rollouts forward on
+//! their schedule and rolls them back when the canary regresses.
+
+pub mod controller;
+pub(crate) mod service;
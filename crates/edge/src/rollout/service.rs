@@ -0,0 +1,29 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use log::info;
+
+use crate::rollout::controller::RolloutController;
+
+/// Upper bound on how late a due step is judged.
+const MAX_EVALUATION_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) async fn run_rollout_service(controller: Arc<RolloutController>) {
+    let Some(shortest_step) = controller.shortest_step_interval() else {
+        return;
+    };
+    info!(
+        "Rollout controller enabled: routes={} shortest_step_interval_ms={}",
+        controller.len(),
+        shortest_step.as_millis()
+    );
+
+    let mut interval = tokio::time::interval(shortest_step.min(MAX_EVALUATION_INTERVAL));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        controller.evaluate(Instant::now());
+    }
+}
@@ -1,4 +1,6 @@
-use std::fmt;
+use std::{fmt, sync::Arc};
+
+use spooky_config::runtime::{RuntimeRouteAction, RuntimeTrafficSplit};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum RoutePreference {
@@ -25,8 +27,17 @@ pub enum RouteDecisionReason {
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RouteDecision<'a> {
-    /// Name of the upstream selected by route matching; auth/policy stays attached to that upstream.
+    /// Name of the matched route; route-level auth/policy is keyed by this name.
+    pub route: &'a str,
+    /// Name of the upstream pool the matched route targets.
     pub upstream: &'a str,
+    /// Weighted split configured on the matched route; when set, the split picks
+    /// the serving upstream instead of `upstream`.
+    pub split: Option<&'a RuntimeTrafficSplit>,
+    /// Current canary share when `split` has a rollout in progress.
+    pub canary_percent: Option<u32>,
+    /// Edge-generated response for routes that skip backend selection.
+    pub action: Option<&'a Arc<RuntimeRouteAction>>,
     pub matched_path_len: usize,
     pub host_specific: bool,
     pub reason: RouteDecisionReason,
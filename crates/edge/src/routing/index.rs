@@ -1,8 +1,14 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+};
 
 use spooky_config::{
     config::Upstream,
-    runtime::{RuntimeRouteHostPattern, RuntimeUpstream},
+    runtime::{RuntimeRoute, RuntimeRouteAction, RuntimeRouteHostPattern, RuntimeTrafficSplit},
 };
 
 use crate::routing::{
@@ -17,18 +23,33 @@ pub struct RouteIndex {
     pub wildcard_host_tries: HashMap<String, RouteTrie>,
     pub default_trie: RouteTrie,
     pub default_max_path_len: usize,
+    pub route_names: Vec<String>,
+    /// Upstream targeted by each indexed route, parallel to `route_names`.
     pub upstream_names: Vec<String>,
-    pub upstream_methods: Vec<Option<String>>,
+    pub route_methods: Vec<Option<String>>,
+    /// Weighted upstream split per indexed route, parallel to `route_names`.
+    pub route_splits: Vec<Option<RuntimeTrafficSplit>>,
+    /// Edge-generated response per indexed route, parallel to `route_names`.
+    pub route_actions: Vec<Option<Arc<RuntimeRouteAction>>>,
+    /// Live canary share for routes whose split has a rollout, parallel to
+    /// `route_names`. The rollout controller moves these between steps.
+    route_canary_percents: Vec<Option<Arc<AtomicU32>>>,
 }
 
 impl RouteIndex {
+    /// Builds an index from `upstream.<name>.route` shorthand routes only; each
+    /// route is named after its upstream.
     pub fn from_upstreams(upstreams: &HashMap<String, Upstream>) -> Self {
-        let mut ordered: Vec<(&String, &Upstream)> = upstreams.iter().collect();
+        let mut ordered: Vec<(&String, &Upstream)> = upstreams
+            .iter()
+            .filter(|(_, upstream)| !upstream.route.is_empty())
+            .collect();
         ordered.sort_by_key(|(left, _)| *left);
         Self::from_ordered_routes(ordered.into_iter().enumerate().map(
             |(order, (name, upstream))| {
                 IndexedRouteSource {
                     name: name.clone(),
+                    upstream: name.clone(),
                     method: upstream
                         .route
                         .method
@@ -51,27 +72,35 @@ impl RouteIndex {
                         .as_deref()
                         .and_then(parse_configured_host_pattern)
                         .map(RuntimeRouteHostPattern::from),
+                    split: None,
+                    action: None,
                     order,
                 }
             },
         ))
     }
 
-    pub fn from_runtime_upstreams(upstreams: &HashMap<String, RuntimeUpstream>) -> Self {
-        let mut ordered: Vec<(&String, &RuntimeUpstream)> = upstreams.iter().collect();
+    pub fn from_runtime_routes(routes: &HashMap<String, RuntimeRoute>) -> Self {
+        let mut ordered: Vec<(&String, &RuntimeRoute)> = routes.iter().collect();
         ordered.sort_by_key(|(left, _)| *left);
-        Self::from_ordered_routes(ordered.into_iter().enumerate().map(
-            |(order, (name, upstream))| IndexedRouteSource {
-                name: name.clone(),
-                method: upstream.route.method.clone(),
-                path_prefix: upstream.route.path_prefix.clone(),
-                path_len: upstream.route.path_len,
-                host_specific: upstream.route.host_specific,
-                method_specific: upstream.route.method_specific,
-                host_pattern: upstream.route.host_pattern.clone(),
-                order,
-            },
-        ))
+        Self::from_ordered_routes(
+            ordered
+                .into_iter()
+                .enumerate()
+                .map(|(order, (name, route))| IndexedRouteSource {
+                    name: name.clone(),
+                    upstream: route.upstream.clone(),
+                    method: route.route.method.clone(),
+                    path_prefix: route.route.path_prefix.clone(),
+                    path_len: route.route.path_len,
+                    host_specific: route.route.host_specific,
+                    method_specific: route.route.method_specific,
+                    host_pattern: route.route.host_pattern.clone(),
+                    split: route.split.clone(),
+                    action: route.action.clone(),
+                    order,
+                }),
+        )
     }
 
     fn from_ordered_routes(routes: impl IntoIterator<Item = IndexedRouteSource>) -> Self {
@@ -79,16 +108,34 @@ impl RouteIndex {
         let mut wildcard_host_tries = HashMap::new();
         let mut default_trie = RouteTrie::default();
         let mut default_max_path_len = 0usize;
+        let mut route_names = Vec::new();
         let mut upstream_names = Vec::new();
-        let mut upstream_methods = Vec::new();
+        let mut route_methods = Vec::new();
+        let mut route_splits = Vec::new();
+        let mut route_actions = Vec::new();
+        let mut route_canary_percents = Vec::new();
         for route_source in routes {
             let path_prefix = route_source.path_prefix.as_deref();
-            let upstream_idx = upstream_names.len();
-            upstream_names.push(route_source.name);
-            upstream_methods.push(route_source.method);
+            let route_idx = route_names.len();
+            route_names.push(route_source.name);
+            upstream_names.push(route_source.upstream);
+            route_methods.push(route_source.method);
+            route_canary_percents.push(
+                route_source
+                    .split
+                    .as_ref()
+                    .and_then(|split| split.rollout.as_ref())
+                    .map(|rollout| {
+                        Arc::new(AtomicU32::new(
+                            rollout.steps.first().copied().unwrap_or_default(),
+                        ))
+                    }),
+            );
+            route_splits.push(route_source.split);
+            route_actions.push(route_source.action);
 
             let route = IndexedRoute {
-                upstream_idx,
+                route_idx,
                 path_len: route_source.path_len,
                 host_specific: route_source.host_specific,
                 method_specific: route_source.method_specific,
@@ -116,11 +163,27 @@ impl RouteIndex {
             wildcard_host_tries,
             default_trie,
             default_max_path_len,
+            route_names,
             upstream_names,
-            upstream_methods,
+            route_methods,
+            route_splits,
+            route_actions,
+            route_canary_percents,
         }
     }
 
+    /// Shared canary share cell for `route`, if its split has a rollout.
+    pub fn canary_percent_cell(&self, route: &str) -> Option<Arc<AtomicU32>> {
+        let route_idx = self.route_names.iter().position(|name| name == route)?;
+        self.route_canary_percents[route_idx].clone()
+    }
+
+    fn canary_percent(&self, route_idx: usize) -> Option<u32> {
+        self.route_canary_percents[route_idx]
+            .as_ref()
+            .map(|percent| percent.load(Ordering::Relaxed))
+    }
+
     pub fn lookup<'a>(&'a self, path: &str, host: Option<&str>) -> Option<&'a str> {
         self.lookup_for_method(path, host, None)
     }
@@ -140,12 +203,12 @@ impl RouteIndex {
         if let Some(best) = host_best
             && best.candidate.route.path_len >= self.default_max_path_len
         {
-            return Some(self.upstream_names[best.candidate.route.upstream_idx].as_str());
+            return Some(self.upstream_names[best.candidate.route.route_idx].as_str());
         }
 
         let best = prefer_route_candidate(
             self.default_trie
-                .longest_prefix(path, method, &self.upstream_methods)
+                .longest_prefix(path, method, &self.route_methods)
                 .map(|route| RouteCandidate {
                     route,
                     host_match_kind: HostMatchKind::Default,
@@ -153,7 +216,7 @@ impl RouteIndex {
                 }),
             host_best.map(|value| value.candidate),
         );
-        best.map(|candidate| self.upstream_names[candidate.route.upstream_idx].as_str())
+        best.map(|candidate| self.upstream_names[candidate.route.route_idx].as_str())
     }
 
     #[allow(dead_code)]
@@ -179,7 +242,7 @@ impl RouteIndex {
 
         let default_best = self
             .default_trie
-            .longest_prefix_with_reason(path, method, &self.upstream_methods)
+            .longest_prefix_with_reason(path, method, &self.route_methods)
             .map(|(route, decision_reason)| HostLookupResult {
                 candidate: RouteCandidate {
                     route,
@@ -215,7 +278,11 @@ impl RouteIndex {
                 }
             };
             return Some(RouteDecision {
-                upstream: self.upstream_names[best.candidate.route.upstream_idx].as_str(),
+                route: self.route_names[best.candidate.route.route_idx].as_str(),
+                upstream: self.upstream_names[best.candidate.route.route_idx].as_str(),
+                split: self.route_splits[best.candidate.route.route_idx].as_ref(),
+                action: self.route_actions[best.candidate.route.route_idx].as_ref(),
+                canary_percent: self.canary_percent(best.candidate.route.route_idx),
                 matched_path_len: best.candidate.route.path_len,
                 host_specific: best.candidate.route.host_specific,
                 reason: best.decision_reason.unwrap_or(fallback_reason),
@@ -224,7 +291,11 @@ impl RouteIndex {
 
         match (default_best, host_best) {
             (Some(default_route), None) => Some(RouteDecision {
-                upstream: self.upstream_names[default_route.candidate.route.upstream_idx].as_str(),
+                route: self.route_names[default_route.candidate.route.route_idx].as_str(),
+                upstream: self.upstream_names[default_route.candidate.route.route_idx].as_str(),
+                split: self.route_splits[default_route.candidate.route.route_idx].as_ref(),
+                action: self.route_actions[default_route.candidate.route.route_idx].as_ref(),
+                canary_percent: self.canary_percent(default_route.candidate.route.route_idx),
                 matched_path_len: default_route.candidate.route.path_len,
                 host_specific: default_route.candidate.route.host_specific,
                 reason: default_route
@@ -232,7 +303,11 @@ impl RouteIndex {
                     .unwrap_or(RouteDecisionReason::DefaultPathLonger),
             }),
             (None, Some(host_route)) => Some(RouteDecision {
-                upstream: self.upstream_names[host_route.candidate.route.upstream_idx].as_str(),
+                route: self.route_names[host_route.candidate.route.route_idx].as_str(),
+                upstream: self.upstream_names[host_route.candidate.route.route_idx].as_str(),
+                split: self.route_splits[host_route.candidate.route.route_idx].as_ref(),
+                action: self.route_actions[host_route.candidate.route.route_idx].as_ref(),
+                canary_percent: self.canary_percent(host_route.candidate.route.route_idx),
                 matched_path_len: host_route.candidate.route.path_len,
                 host_specific: host_route.candidate.route.host_specific,
                 reason: host_route
@@ -267,7 +342,11 @@ impl RouteIndex {
                     _ => candidate.candidate,
                 };
                 Some(RouteDecision {
-                    upstream: self.upstream_names[selected.route.upstream_idx].as_str(),
+                    route: self.route_names[selected.route.route_idx].as_str(),
+                    upstream: self.upstream_names[selected.route.route_idx].as_str(),
+                    split: self.route_splits[selected.route.route_idx].as_ref(),
+                    action: self.route_actions[selected.route.route_idx].as_ref(),
+                    canary_percent: self.canary_percent(selected.route.route_idx),
                     matched_path_len: selected.route.path_len,
                     host_specific: selected.route.host_specific,
                     reason: if selected == candidate.candidate {
@@ -291,7 +370,7 @@ impl RouteIndex {
             .host_tries
             .get(normalized_host)
             .and_then(|host_trie| {
-                host_trie.longest_prefix_with_reason(path, method, &self.upstream_methods)
+                host_trie.longest_prefix_with_reason(path, method, &self.route_methods)
             })
             .map(|(route, decision_reason)| HostLookupResult {
                 candidate: RouteCandidate {
@@ -312,7 +391,7 @@ impl RouteIndex {
 
             if let Some(trie) = self.wildcard_host_tries.get(suffix) {
                 let candidate = trie
-                    .longest_prefix_with_reason(path, method, &self.upstream_methods)
+                    .longest_prefix_with_reason(path, method, &self.route_methods)
                     .map(|(route, decision_reason)| HostLookupResult {
                         candidate: RouteCandidate {
                             route,
@@ -332,12 +411,15 @@ impl RouteIndex {
 
 struct IndexedRouteSource {
     name: String,
+    upstream: String,
     method: Option<String>,
     path_prefix: Option<String>,
     path_len: usize,
     host_specific: bool,
     method_specific: bool,
     host_pattern: Option<RuntimeRouteHostPattern>,
+    split: Option<RuntimeTrafficSplit>,
+    action: Option<Arc<RuntimeRouteAction>>,
     order: usize,
 }
 
@@ -352,9 +434,20 @@ impl From<ConfiguredHostPattern> for RuntimeRouteHostPattern {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
-    use spooky_config::config::{Backend, LoadBalancing, RouteMatch, Upstream};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, atomic::Ordering},
+        time::Duration,
+    };
+
+    use spooky_config::{
+        config::{Backend, LoadBalancing, RouteMatch, Upstream},
+        runtime::{
+            RuntimeCanaryRollout, RuntimeDirectResponse, RuntimeRoute, RuntimeRouteAction,
+            RuntimeRouteHostPattern, RuntimeRouteMatchPolicy, RuntimeSplitTarget,
+            RuntimeTrafficSplit,
+        },
+    };
 
     use crate::routing::{decision::RouteDecisionReason, index::RouteIndex};
 
@@ -363,9 +456,19 @@ mod tests {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -378,6 +481,8 @@ mod tests {
                 address: "http://127.0.0.1:7001".to_string(),
                 weight: 1,
                 health_check: None,
+                priority: 0,
+                zone: None,
             }],
         }
     }
@@ -505,4 +610,180 @@ mod tests {
         assert_eq!(decision.upstream, "default_long");
         assert_eq!(decision.reason, RouteDecisionReason::DefaultPathLonger);
     }
+
+    fn runtime_route(
+        name: &str,
+        upstream: &str,
+        path_prefix: &str,
+        host: Option<&str>,
+    ) -> (String, RuntimeRoute) {
+        (
+            name.to_string(),
+            RuntimeRoute {
+                name: name.to_string(),
+                upstream: upstream.to_string(),
+                route: RuntimeRouteMatchPolicy {
+                    host: host.map(str::to_string),
+                    host_pattern: host
+                        .map(|value| RuntimeRouteHostPattern::Exact(value.to_string())),
+                    path_prefix: Some(path_prefix.to_string()),
+                    method: None,
+                    path_len: path_prefix.len(),
+                    host_specific: host.is_some(),
+                    method_specific: false,
+                },
+                policy: Default::default(),
+                split: None,
+                action: None,
+            },
+        )
+    }
+
+    #[test]
+    fn lookup_with_decision_reports_route_and_target_upstream() {
+        let routes = HashMap::from([
+            runtime_route("checkout", "shop", "/checkout", None),
+            runtime_route("cart", "shop", "/cart", None),
+            runtime_route("admin", "backoffice", "/cart", Some("admin.example.com")),
+        ]);
+        let index = RouteIndex::from_runtime_routes(&routes);
+
+        let decision = index
+            .lookup_with_decision("/checkout/pay", None)
+            .expect("route decision");
+        assert_eq!(decision.route, "checkout");
+        assert_eq!(decision.upstream, "shop");
+
+        let decision = index
+            .lookup_with_decision("/cart", Some("admin.example.com"))
+            .expect("route decision");
+        assert_eq!(decision.route, "admin");
+        assert_eq!(decision.upstream, "backoffice");
+
+        assert_eq!(index.lookup("/cart/items", None), Some("shop"));
+    }
+
+    #[test]
+    fn lookup_with_decision_reports_edge_answered_route_action() {
+        let (_, mut maintenance) = runtime_route("maintenance", "", "/status", None);
+        maintenance.action = Some(Arc::new(RuntimeRouteAction::DirectResponse(
+            RuntimeDirectResponse {
+                status: 503,
+                headers: Vec::new(),
+                body: b"down\n".to_vec(),
+            },
+        )));
+        let routes = HashMap::from([
+            ("maintenance".to_string(), maintenance),
+            runtime_route("checkout", "shop", "/checkout", None),
+        ]);
+        let index = RouteIndex::from_runtime_routes(&routes);
+
+        let decision = index
+            .lookup_with_decision("/status", None)
+            .expect("route decision");
+        assert_eq!(decision.route, "maintenance");
+        assert!(matches!(
+            decision.action.map(|action| action.as_ref()),
+            Some(RuntimeRouteAction::DirectResponse(direct)) if direct.status == 503
+        ));
+
+        let decision = index
+            .lookup_with_decision("/checkout", None)
+            .expect("route decision");
+        assert!(decision.action.is_none());
+    }
+
+    #[test]
+    fn from_upstreams_names_shorthand_routes_after_their_upstream() {
+        let upstreams = HashMap::from([("api".to_string(), upstream("/api", None, None))]);
+        let index = RouteIndex::from_upstreams(&upstreams);
+
+        let decision = index
+            .lookup_with_decision("/api", None)
+            .expect("route decision");
+        assert_eq!(decision.route, "api");
+        assert_eq!(decision.upstream, "api");
+    }
+
+    #[test]
+    fn lookup_with_decision_carries_route_split() {
+        let (name, mut checkout) = runtime_route("checkout", "shop", "/checkout", None);
+        checkout.split = Some(RuntimeTrafficSplit {
+            key_spec: None,
+            targets: vec![
+                RuntimeSplitTarget {
+                    upstream: "shop".to_string(),
+                    weight: 95,
+                },
+                RuntimeSplitTarget {
+                    upstream: "shop-canary".to_string(),
+                    weight: 5,
+                },
+            ],
+            total_weight: 100,
+            rollout: None,
+        });
+        let routes = HashMap::from([
+            (name, checkout),
+            runtime_route("cart", "shop", "/cart", None),
+        ]);
+        let index = RouteIndex::from_runtime_routes(&routes);
+
+        let decision = index
+            .lookup_with_decision("/checkout", None)
+            .expect("route decision");
+        let split = decision.split.expect("route split");
+        assert_eq!(split.pick(97), "shop-canary");
+        assert_eq!(decision.canary_percent, None);
+
+        let decision = index
+            .lookup_with_decision("/cart", None)
+            .expect("route decision");
+        assert!(decision.split.is_none());
+    }
+
+    #[test]
+    fn lookup_with_decision_reads_live_canary_percent() {
+        let (name, mut checkout) = runtime_route("checkout", "shop", "/checkout", None);
+        checkout.split = Some(RuntimeTrafficSplit {
+            key_spec: None,
+            targets: vec![
+                RuntimeSplitTarget {
+                    upstream: "shop".to_string(),
+                    weight: 1,
+                },
+                RuntimeSplitTarget {
+                    upstream: "shop-canary".to_string(),
+                    weight: 1,
+                },
+            ],
+            total_weight: 2,
+            rollout: Some(RuntimeCanaryRollout {
+                canary: "shop-canary".to_string(),
+                baselines: vec!["shop".to_string()],
+                steps: vec![5, 50, 100],
+                step_interval: Duration::from_secs(60),
+                min_requests: 0,
+                max_error_rate_increase_percent: 1,
+                max_p95_latency_ratio_percent: 150,
+            }),
+        });
+        let index = RouteIndex::from_runtime_routes(&HashMap::from([(name, checkout)]));
+
+        let decision = index
+            .lookup_with_decision("/checkout", None)
+            .expect("route decision");
+        assert_eq!(decision.canary_percent, Some(5));
+
+        index
+            .canary_percent_cell("checkout")
+            .expect("canary cell")
+            .store(50, Ordering::Relaxed);
+        let decision = index
+            .lookup_with_decision("/checkout", None)
+            .expect("route decision");
+        assert_eq!(decision.canary_percent, Some(50));
+        assert!(index.canary_percent_cell("cart").is_none());
+    }
 }
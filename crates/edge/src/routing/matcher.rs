@@ -135,13 +135,13 @@ pub fn prefer_host_lookup_result(
 fn route_matches_method(
     route: IndexedRoute,
     method: Option<&str>,
-    upstream_methods: &[Option<String>],
+    route_methods: &[Option<String>],
 ) -> bool {
     let Some(method) = method else {
         return true;
     };
-    match upstream_methods
-        .get(route.upstream_idx)
+    match route_methods
+        .get(route.route_idx)
         .and_then(|value| value.as_deref())
     {
         Some(expected) => expected.eq_ignore_ascii_case(method),
@@ -153,7 +153,7 @@ pub fn best_matching_route_with_reason(
     routes: &[IndexedRoute],
     path: &str,
     method: Option<&str>,
-    upstream_methods: &[Option<String>],
+    route_methods: &[Option<String>],
     current: Option<(IndexedRoute, Option<RouteDecisionReason>)>,
 ) -> Option<(IndexedRoute, Option<RouteDecisionReason>)> {
     let mut best = current;
@@ -161,7 +161,7 @@ pub fn best_matching_route_with_reason(
         if !prefix_boundary_matches(path, route.path_len) {
             continue;
         }
-        if !route_matches_method(route, method, upstream_methods) {
+        if !route_matches_method(route, method, route_methods) {
             continue;
         }
         best = match best {
@@ -188,14 +188,14 @@ mod tests {
     };
 
     fn indexed_route(
-        upstream_idx: usize,
+        route_idx: usize,
         path_len: usize,
         host_specific: bool,
         method_specific: bool,
         order: usize,
     ) -> IndexedRoute {
         IndexedRoute {
-            upstream_idx,
+            route_idx,
             path_len,
             host_specific,
             method_specific,
@@ -204,7 +204,7 @@ mod tests {
     }
 
     fn candidate(
-        upstream_idx: usize,
+        route_idx: usize,
         path_len: usize,
         host_specific: bool,
         host_match_kind: HostMatchKind,
@@ -213,13 +213,7 @@ mod tests {
         order: usize,
     ) -> RouteCandidate {
         RouteCandidate {
-            route: indexed_route(
-                upstream_idx,
-                path_len,
-                host_specific,
-                method_specific,
-                order,
-            ),
+            route: indexed_route(route_idx, path_len, host_specific, method_specific, order),
             host_match_kind,
             wildcard_suffix_len,
         }
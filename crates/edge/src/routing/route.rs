@@ -1,7 +1,7 @@
 use crate::routing::decision::RouteDecisionReason;
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct IndexedRoute {
-    pub upstream_idx: usize,
+    pub route_idx: usize,
     pub path_len: usize,
     pub host_specific: bool,
     pub method_specific: bool,
@@ -29,6 +29,9 @@ pub fn scan_lookup_for_method<'a>(
     let mut best_match: Option<(&str, usize, bool, HostMatchKind, usize, bool)> = None;
 
     for (upstream_name, upstream) in upstreams {
+        if upstream.route.is_empty() {
+            continue;
+        }
         let has_method_match = match (
             upstream.route.method.as_deref().map(str::trim),
             method.map(str::trim),
@@ -176,9 +179,19 @@ mod tests {
             load_balancing: LoadBalancing {
                 lb_type: "round-robin".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -191,6 +204,8 @@ mod tests {
                 address: "http://127.0.0.1:7001".to_string(),
                 weight: 1,
                 health_check: None,
+                priority: 0,
+                zone: None,
             }],
         }
     }
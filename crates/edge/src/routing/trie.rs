@@ -24,7 +24,7 @@ impl TrieNode {
         if let Some(existing) = self
             .routes
             .iter_mut()
-            .find(|route| route.upstream_idx == candidate.upstream_idx)
+            .find(|route| route.route_idx == candidate.route_idx)
         {
             *existing = candidate;
             return;
@@ -79,9 +79,9 @@ impl RouteTrie {
         &self,
         path: &str,
         method: Option<&str>,
-        upstream_methods: &[Option<String>],
+        route_methods: &[Option<String>],
     ) -> Option<IndexedRoute> {
-        self.longest_prefix_with_reason(path, method, upstream_methods)
+        self.longest_prefix_with_reason(path, method, route_methods)
             .map(|(route, _)| route)
     }
 
@@ -89,19 +89,18 @@ impl RouteTrie {
         &self,
         path: &str,
         method: Option<&str>,
-        upstream_methods: &[Option<String>],
+        route_methods: &[Option<String>],
     ) -> Option<(IndexedRoute, Option<RouteDecisionReason>)> {
         let mut node = &self.root;
         let mut best =
-            best_matching_route_with_reason(&node.routes, path, method, upstream_methods, None);
+            best_matching_route_with_reason(&node.routes, path, method, route_methods, None);
 
         for byte in path.as_bytes() {
             let Some(next) = node.child(*byte) else {
                 break;
             };
             node = next;
-            best =
-                best_matching_route_with_reason(&node.routes, path, method, upstream_methods, best);
+            best = best_matching_route_with_reason(&node.routes, path, method, route_methods, best);
         }
 
         best
@@ -112,24 +111,24 @@ impl RouteTrie {
 mod tests {
     use crate::routing::{decision::RouteDecisionReason, route::IndexedRoute, trie::RouteTrie};
 
-    fn route(upstream_idx: usize, path_len: usize) -> IndexedRoute {
+    fn route(route_idx: usize, path_len: usize) -> IndexedRoute {
         IndexedRoute {
-            upstream_idx,
+            route_idx,
             path_len,
             host_specific: false,
             method_specific: false,
-            order: upstream_idx,
+            order: route_idx,
         }
     }
 
     #[test]
     fn longest_prefix_returns_none_when_trie_is_empty() {
         let trie = RouteTrie::default();
-        let upstream_methods = Vec::new();
+        let route_methods = Vec::new();
 
-        assert_eq!(trie.longest_prefix("/api", None, &upstream_methods), None);
+        assert_eq!(trie.longest_prefix("/api", None, &route_methods), None);
         assert_eq!(
-            trie.longest_prefix_with_reason("/api", None, &upstream_methods),
+            trie.longest_prefix_with_reason("/api", None, &route_methods),
             None
         );
     }
@@ -137,12 +136,12 @@ mod tests {
     #[test]
     fn longest_prefix_prefers_longest_overlapping_prefix() {
         let mut trie = RouteTrie::default();
-        let upstream_methods = vec![None, None];
+        let route_methods = vec![None, None];
         trie.insert(Some("/api"), route(0, "/api".len()));
         trie.insert(Some("/api/v1"), route(1, "/api/v1".len()));
 
         assert_eq!(
-            trie.longest_prefix("/api/v1/users", None, &upstream_methods),
+            trie.longest_prefix("/api/v1/users", None, &route_methods),
             Some(route(1, "/api/v1".len()))
         );
     }
@@ -150,11 +149,11 @@ mod tests {
     #[test]
     fn longest_prefix_uses_root_fallback_when_no_child_matches() {
         let mut trie = RouteTrie::default();
-        let upstream_methods = vec![None];
+        let route_methods = vec![None];
         trie.insert(None, route(0, 0));
 
         assert_eq!(
-            trie.longest_prefix("/unmatched", None, &upstream_methods),
+            trie.longest_prefix("/unmatched", None, &route_methods),
             Some(route(0, 0))
         );
     }
@@ -162,12 +161,12 @@ mod tests {
     #[test]
     fn longest_prefix_with_reason_reports_longer_prefix_preference() {
         let mut trie = RouteTrie::default();
-        let upstream_methods = vec![None, None];
+        let route_methods = vec![None, None];
         trie.insert(None, route(0, 0));
         trie.insert(Some("/api"), route(1, "/api".len()));
 
         assert_eq!(
-            trie.longest_prefix_with_reason("/api/users", None, &upstream_methods),
+            trie.longest_prefix_with_reason("/api/users", None, &route_methods),
             Some((
                 route(1, "/api".len()),
                 Some(RouteDecisionReason::HostPathLongerOrEqual)
@@ -0,0 +1,224 @@
+//! Operator drain/disable overrides, keyed by upstream name and backend id.
+//!
+//! Overrides outlive the pools they are applied to: a reload carries them
+//! over to backends that keep their id, and with a state file they survive
+//! restarts too.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use spooky_lb::{health::BackendAdminState, upstream_pool::UpstreamPool};
+
+use super::state_file::{self, PendingStateWrite, StateFileWriter};
+
+type BackendKey = (String, String);
+
+const STATE_FILE_WHAT: &str = "backend state file";
+
+#[derive(Debug)]
+pub enum BackendAdminError {
+    UnknownUpstream(String),
+    UnknownBackend { upstream: String, backend: String },
+}
+
+impl fmt::Display for BackendAdminError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownUpstream(upstream) => write!(f, "unknown upstream '{upstream}'"),
+            Self::UnknownBackend { upstream, backend } => {
+                write!(f, "upstream '{upstream}' has no backend '{backend}'")
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct BackendStateFile {
+    backends: Vec<BackendStateEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct BackendStateEntry {
+    upstream: String,
+    id: String,
+    state: String,
+}
+
+#[derive(Default)]
+pub struct BackendAdminStore {
+    state_file: Option<StateFileWriter>,
+    // Backends not `Enabled`; enabling one removes its entry.
+    overrides: Mutex<BTreeMap<BackendKey, BackendAdminState>>,
+}
+
+impl BackendAdminStore {
+    /// Store backed by `state_file`, starting from the overrides it holds.
+    /// A missing file starts empty; an unreadable one is logged and ignored.
+    pub fn load(state_file: Option<&str>) -> Self {
+        let Some(path) = state_file.map(PathBuf::from) else {
+            return Self::default();
+        };
+
+        let mut overrides = BTreeMap::new();
+        let file = state_file::read::<BackendStateFile>(&path, STATE_FILE_WHAT);
+        for entry in file.map(|file| file.backends).unwrap_or_default() {
+            match BackendAdminState::parse(&entry.state) {
+                Some(BackendAdminState::Enabled) => {}
+                Some(state) => {
+                    overrides.insert((entry.upstream, entry.id), state);
+                }
+                None => warn!(
+                    "Ignoring backend state '{}' for upstream={} backend={} in {}",
+                    entry.state,
+                    entry.upstream,
+                    entry.id,
+                    path.display()
+                ),
+            }
+        }
+
+        Self {
+            state_file: Some(StateFileWriter::new(path, STATE_FILE_WHAT)),
+            overrides: Mutex::new(overrides),
+        }
+    }
+
+    /// Take over the overrides of the generation being replaced; they are
+    /// newer than anything read from the state file.
+    pub fn inherit_from(&self, previous: &BackendAdminStore) {
+        let inherited = previous.lock_overrides().clone();
+        *self.lock_overrides() = inherited;
+    }
+
+    /// Push every override into `pools`, dropping those whose upstream or
+    /// backend id no longer exists.
+    pub fn apply(&self, pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>) {
+        let mut overrides = self.lock_overrides();
+        overrides.retain(|(upstream, backend), state| {
+            let Some(pool) = pools.get(upstream) else {
+                info!(
+                    "Dropping backend {} override for upstream={} backend={}: upstream removed",
+                    state.as_str(),
+                    upstream,
+                    backend
+                );
+                return false;
+            };
+            let mut pool = pool.write().unwrap_or_else(PoisonError::into_inner);
+            let Some(index) = pool.backend_index_by_id(backend) else {
+                info!(
+                    "Dropping backend {} override for upstream={} backend={}: backend removed",
+                    state.as_str(),
+                    upstream,
+                    backend
+                );
+                return false;
+            };
+            pool.set_backend_admin_state(index, *state);
+            true
+        });
+    }
+
+    /// Set the admin state of `backend` in `upstream`, writing the state file
+    /// in the background when one is configured. Returns whether the state
+    /// changed.
+    pub fn set(
+        &self,
+        pools: &HashMap<String, Arc<RwLock<UpstreamPool>>>,
+        upstream: &str,
+        backend: &str,
+        state: BackendAdminState,
+    ) -> Result<bool, BackendAdminError> {
+        let pool = pools
+            .get(upstream)
+            .ok_or_else(|| BackendAdminError::UnknownUpstream(upstream.to_string()))?;
+
+        let mut overrides = self.lock_overrides();
+        let changed = {
+            let mut pool = pool.write().unwrap_or_else(PoisonError::into_inner);
+            let index = pool.backend_index_by_id(backend).ok_or_else(|| {
+                BackendAdminError::UnknownBackend {
+                    upstream: upstream.to_string(),
+                    backend: backend.to_string(),
+                }
+            })?;
+            pool.set_backend_admin_state(index, state)
+        };
+
+        let key = (upstream.to_string(), backend.to_string());
+        if state.is_enabled() {
+            overrides.remove(&key);
+        } else {
+            overrides.insert(key, state);
+        }
+        if changed {
+            info!(
+                "Backend admin state upstream={} backend={} state={}",
+                upstream,
+                backend,
+                state.as_str()
+            );
+            let write = self.snapshot(&overrides);
+            drop(overrides);
+            if let Some(write) = write {
+                write.spawn();
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Drop any override for a backend that left its upstream, writing the
+    /// state file when one was held.
+    pub fn forget(&self, upstream: &str, backend: &str) {
+        let mut overrides = self.lock_overrides();
+        if overrides
+            .remove(&(upstream.to_string(), backend.to_string()))
+            .is_none()
+        {
+            return;
+        }
+        let write = self.snapshot(&overrides);
+        drop(overrides);
+        if let Some(write) = write {
+            write.spawn();
+        }
+    }
+
+    /// Wait for state file writes already started.
+    pub fn flush(&self) {
+        if let Some(writer) = self.state_file.as_ref() {
+            writer.flush();
+        }
+    }
+
+    fn snapshot(
+        &self,
+        overrides: &BTreeMap<BackendKey, BackendAdminState>,
+    ) -> Option<PendingStateWrite<BackendStateFile>> {
+        let writer = self.state_file.as_ref()?;
+        Some(
+            writer.snapshot(BackendStateFile {
+                backends: overrides
+                    .iter()
+                    .map(|((upstream, id), state)| BackendStateEntry {
+                        upstream: upstream.clone(),
+                        id: id.clone(),
+                        state: state.as_str().to_string(),
+                    })
+                    .collect(),
+            }),
+        )
+    }
+
+    fn lock_overrides(&self) -> std::sync::MutexGuard<'_, BTreeMap<BackendKey, BackendAdminState>> {
+        self.overrides
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
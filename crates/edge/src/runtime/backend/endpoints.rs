@@ -0,0 +1,58 @@
+//! Canonical endpoints of the backends a generation routes to, keyed by the
+//! configured backend address.
+//!
+//! The table starts from the runtime config and changes in place when the
+//! control API adds or removes backends.
+
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+use spooky_config::backend_endpoint::BackendEndpoint;
+
+#[derive(Debug, Default)]
+pub struct BackendEndpoints {
+    endpoints: RwLock<HashMap<String, BackendEndpoint>>,
+}
+
+impl BackendEndpoints {
+    pub fn new(endpoints: HashMap<String, BackendEndpoint>) -> Self {
+        Self {
+            endpoints: RwLock::new(endpoints),
+        }
+    }
+
+    pub fn get(&self, address: &str) -> Option<BackendEndpoint> {
+        self.endpoints
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(address)
+            .cloned()
+    }
+
+    /// Address of the backend already serving `endpoint`'s origin, if any.
+    pub fn address_for_origin(&self, endpoint: &BackendEndpoint) -> Option<String> {
+        let origin = endpoint.origin();
+        self.endpoints
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find(|(_, existing)| existing.origin() == origin)
+            .map(|(address, _)| address.clone())
+    }
+
+    pub fn insert(&self, address: String, endpoint: BackendEndpoint) {
+        self.endpoints
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(address, endpoint);
+    }
+
+    pub fn remove(&self, address: &str) -> Option<BackendEndpoint> {
+        self.endpoints
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(address)
+    }
+}
@@ -195,9 +195,18 @@ impl BackendLifecycleCoordinator {
                 entry.placements.push(BackendPoolPlacementSnapshot {
                     upstream_name: upstream_name.clone(),
                     backend_index,
+                    backend_id: guard
+                        .backend_id(backend_index)
+                        .unwrap_or_default()
+                        .to_string(),
                     healthy: backend.healthy,
+                    admin_state: backend.admin_state,
                     active_requests: backend.active_requests,
                     ewma_latency_ms: backend.ewma_latency_ms,
+                    priority: backend.priority,
+                    zone: guard.backend_zone(backend_index).map(str::to_string),
+                    weight: backend.weight,
+                    effective_weight: backend.effective_weight,
                     membership_epoch: membership_summary.membership_epoch,
                 });
             }
@@ -564,9 +573,19 @@ mod tests {
                 load_balancing: LoadBalancing {
                     lb_type: "round-robin".to_string(),
                     key: None,
+                    table_size: None,
+                    hash_balance_factor: None,
+                    p2c: None,
+                    panic_threshold_percent: None,
                 },
                 auth: Default::default(),
                 host_policy: Default::default(),
+                policy: Default::default(),
+                outlier_detection: None,
+                slow_start: None,
+                zone_aware: None,
+                session_affinity: None,
+                adaptive_admission: None,
                 forwarded_headers: Default::default(),
                 route: RouteMatch::default(),
                 backends: vec![Backend {
@@ -581,6 +600,8 @@ mod tests {
                         success_threshold: 1,
                         cooldown_ms: 0,
                     }),
+                    priority: 0,
+                    zone: None,
                 }],
             },
         );
@@ -598,6 +619,7 @@ mod tests {
             },
             listeners: Vec::new(),
             upstream: upstreams,
+            routes: Vec::new(),
             load_balancing: None,
             upstream_tls: Default::default(),
             log: Default::default(),
@@ -605,6 +627,7 @@ mod tests {
             observability: Default::default(),
             resilience: Default::default(),
             security: Default::default(),
+            locality: Default::default(),
         })
         .expect("runtime config");
 
@@ -0,0 +1,314 @@
+//! Backends added, updated or removed through the control API, keyed by
+//! upstream name and backend id.
+//!
+//! Changes are layered over the configured backends each time a generation
+//! is built, so they survive reloads, and with a state file restarts too.
+//! A recorded change wins over the config file for the same backend id.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use spooky_config::{
+    config::Backend,
+    runtime::{RuntimeBackend, RuntimeConfig},
+};
+
+use super::state_file::{self, PendingStateWrite, StateFileWriter};
+
+type BackendKey = (String, String);
+
+const STATE_FILE_WHAT: &str = "backend membership file";
+
+#[derive(Debug)]
+pub enum BackendMembershipError {
+    UnknownUpstream(String),
+    UnknownBackend {
+        upstream: String,
+        backend: String,
+    },
+    /// The backend spec does not pass config normalization.
+    Invalid(String),
+    /// The change clashes with the live membership, e.g. a duplicate origin.
+    Conflict(String),
+}
+
+impl fmt::Display for BackendMembershipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownUpstream(upstream) => write!(f, "unknown upstream '{upstream}'"),
+            Self::UnknownBackend { upstream, backend } => {
+                write!(f, "upstream '{upstream}' has no backend '{backend}'")
+            }
+            Self::Invalid(reason) | Self::Conflict(reason) => f.write_str(reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum MembershipChange {
+    Upsert(Backend),
+    Removed,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MembershipStateFile {
+    backends: Vec<MembershipStateEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MembershipStateEntry {
+    upstream: String,
+    id: String,
+    /// Absent for a removed backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backend: Option<Backend>,
+}
+
+#[derive(Default)]
+pub struct BackendMembershipStore {
+    state_file: Option<StateFileWriter>,
+    changes: Mutex<BTreeMap<BackendKey, MembershipChange>>,
+}
+
+impl BackendMembershipStore {
+    /// Store backed by `state_file`, starting from the changes it holds.
+    /// A missing file starts empty; an unreadable one is logged and ignored.
+    pub fn load(state_file: Option<&str>) -> Self {
+        let Some(path) = state_file.map(PathBuf::from) else {
+            return Self::default();
+        };
+
+        let mut changes = BTreeMap::new();
+        let file = state_file::read::<MembershipStateFile>(&path, STATE_FILE_WHAT);
+        for entry in file.map(|file| file.backends).unwrap_or_default() {
+            let change = match entry.backend {
+                Some(backend) if backend.id != entry.id => {
+                    warn!(
+                        "Ignoring backend membership entry for upstream={} backend={} in {}: id does not match backend.id '{}'",
+                        entry.upstream,
+                        entry.id,
+                        path.display(),
+                        backend.id
+                    );
+                    continue;
+                }
+                Some(backend) => MembershipChange::Upsert(backend),
+                None => MembershipChange::Removed,
+            };
+            changes.insert((entry.upstream, entry.id), change);
+        }
+
+        Self {
+            state_file: Some(StateFileWriter::new(path, STATE_FILE_WHAT)),
+            changes: Mutex::new(changes),
+        }
+    }
+
+    /// Take over the changes of the generation being replaced; they are
+    /// newer than anything read from the state file.
+    pub fn inherit_from(&self, previous: &BackendMembershipStore) {
+        let inherited = previous.lock_changes().clone();
+        *self.lock_changes() = inherited;
+    }
+
+    /// Copy of `config` with every change applied. Changes that no longer
+    /// fit, because their upstream is gone, the backend is invalid or clashes
+    /// with another origin, or removals would empty the upstream, are logged
+    /// and dropped.
+    pub fn overlay(&self, config: &RuntimeConfig) -> RuntimeConfig {
+        let original = config;
+        let mut config = config.clone();
+        let mut changes = self.lock_changes();
+        let mut dropped = Vec::new();
+        // Removals go first so an added backend may take over the origin of
+        // one it replaces.
+        for removals in [true, false] {
+            for ((upstream, id), change) in changes.iter() {
+                if matches!(change, MembershipChange::Removed) == removals
+                    && !apply_change(&mut config, upstream, id, change)
+                {
+                    dropped.push((upstream.clone(), id.clone()));
+                }
+            }
+        }
+        for (name, upstream) in config.upstreams.iter_mut() {
+            if !upstream.backends.is_empty() {
+                continue;
+            }
+            let Some(configured) = original.upstreams.get(name) else {
+                continue;
+            };
+            warn!(
+                "Dropping backend removals for upstream={}: they would leave it without backends",
+                name
+            );
+            upstream.backends = configured.backends.clone();
+            dropped.extend(
+                changes
+                    .keys()
+                    .filter(|(upstream, _)| upstream == name)
+                    .cloned(),
+            );
+        }
+        for key in dropped {
+            changes.remove(&key);
+        }
+        drop(changes);
+        config
+    }
+
+    /// Run `apply` with other membership changes held off. When it succeeds,
+    /// record `backend` as the state of `id` in `upstream`, or its removal
+    /// for `None`, and write the state file when one is configured. The
+    /// write happens on the blocking pool after the lock is released.
+    pub fn commit<T>(
+        &self,
+        upstream: &str,
+        id: &str,
+        backend: Option<&Backend>,
+        apply: impl FnOnce() -> Result<T, BackendMembershipError>,
+    ) -> Result<T, BackendMembershipError> {
+        let mut changes = self.lock_changes();
+        let applied = apply()?;
+
+        let change = match backend {
+            Some(backend) => MembershipChange::Upsert(backend.clone()),
+            None => MembershipChange::Removed,
+        };
+        info!(
+            "Backend membership upstream={} backend={} change={}",
+            upstream,
+            id,
+            if backend.is_some() {
+                "upsert"
+            } else {
+                "remove"
+            }
+        );
+        changes.insert((upstream.to_string(), id.to_string()), change);
+        let write = self.snapshot(&changes);
+        drop(changes);
+        if let Some(write) = write {
+            write.spawn();
+        }
+        Ok(applied)
+    }
+
+    /// Wait for state file writes already started.
+    pub fn flush(&self) {
+        if let Some(writer) = self.state_file.as_ref() {
+            writer.flush();
+        }
+    }
+
+    fn snapshot(
+        &self,
+        changes: &BTreeMap<BackendKey, MembershipChange>,
+    ) -> Option<PendingStateWrite<MembershipStateFile>> {
+        let writer = self.state_file.as_ref()?;
+        Some(
+            writer.snapshot(MembershipStateFile {
+                backends: changes
+                    .iter()
+                    .map(|((upstream, id), change)| MembershipStateEntry {
+                        upstream: upstream.clone(),
+                        id: id.clone(),
+                        backend: match change {
+                            MembershipChange::Upsert(backend) => Some(backend.clone()),
+                            MembershipChange::Removed => None,
+                        },
+                    })
+                    .collect(),
+            }),
+        )
+    }
+
+    fn lock_changes(&self) -> std::sync::MutexGuard<'_, BTreeMap<BackendKey, MembershipChange>> {
+        self.changes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Applies one change to `config`; returns whether it still fits.
+fn apply_change(
+    config: &mut RuntimeConfig,
+    upstream: &str,
+    id: &str,
+    change: &MembershipChange,
+) -> bool {
+    let runtime_backend = match change {
+        MembershipChange::Upsert(backend) => match RuntimeBackend::normalize(upstream, backend) {
+            Ok(runtime_backend) => Some(runtime_backend),
+            Err(err) => {
+                warn!(
+                    "Dropping backend membership change for upstream={} backend={}: {}",
+                    upstream, id, err
+                );
+                return false;
+            }
+        },
+        MembershipChange::Removed => None,
+    };
+    if let Some(runtime_backend) = &runtime_backend
+        && let Some((other_upstream, other_id)) = origin_owner(config, runtime_backend, upstream)
+    {
+        warn!(
+            "Dropping backend membership change for upstream={} backend={}: origin {} is used by upstream '{}' backend '{}'",
+            upstream, id, runtime_backend.endpoint.origin, other_upstream, other_id
+        );
+        return false;
+    }
+    let Some(runtime_upstream) = config.upstreams.get_mut(upstream) else {
+        info!(
+            "Dropping backend membership change for upstream={} backend={}: upstream removed",
+            upstream, id
+        );
+        return false;
+    };
+
+    let backends = &mut runtime_upstream.backends;
+    let mut positions =
+        (0..backends.len()).filter(|&position| backends[position].backend.id == *id);
+    let position = positions.next();
+    if positions.next().is_some() {
+        warn!(
+            "Dropping backend membership change for upstream={} backend={}: id names more than one backend",
+            upstream, id
+        );
+        return false;
+    }
+    match (runtime_backend, position) {
+        (Some(runtime_backend), Some(position)) => backends[position] = runtime_backend,
+        (Some(runtime_backend), None) => backends.push(runtime_backend),
+        // Nothing left to remove: the config file dropped the id too.
+        (None, None) => return false,
+        (None, Some(position)) => {
+            backends.remove(position);
+        }
+    }
+    true
+}
+
+/// Upstream and id of a backend other than `backend` itself that already
+/// serves its origin.
+fn origin_owner(
+    config: &RuntimeConfig,
+    backend: &RuntimeBackend,
+    upstream: &str,
+) -> Option<(String, String)> {
+    config.upstreams.iter().find_map(|(name, other)| {
+        other
+            .backends
+            .iter()
+            .find(|other| {
+                other.endpoint.origin == backend.endpoint.origin
+                    && !(name == upstream && other.backend.id == backend.backend.id)
+            })
+            .map(|other| (name.clone(), other.backend.id.clone()))
+    })
+}
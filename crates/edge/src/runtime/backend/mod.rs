@@ -4,9 +4,13 @@
 //! inventory types. Lifecycle mutation coordinators and refresh update helpers
 //! remain internal implementation details.
 
+pub mod admin;
+pub mod endpoints;
 pub mod event;
 pub(crate) mod lifecycle;
+pub mod membership;
 pub mod resolution;
 pub mod state;
+pub(crate) mod state_file;
 pub mod store;
 pub(crate) mod update;
@@ -1,6 +1,6 @@
 use std::{net::SocketAddr, time::SystemTime};
 
-use spooky_lb::health::HealthFailureReason;
+use spooky_lb::health::{BackendAdminState, HealthFailureReason};
 
 use super::resolution::{RuntimeBackendAddressKind, RuntimeBackendResolution};
 
@@ -78,9 +78,15 @@ pub struct BackendLifecycleSnapshot {
 pub struct BackendPoolPlacementSnapshot {
     pub upstream_name: String,
     pub backend_index: usize,
+    pub backend_id: String,
     pub healthy: bool,
+    pub admin_state: BackendAdminState,
     pub active_requests: usize,
     pub ewma_latency_ms: Option<f64>,
+    pub priority: u32,
+    pub zone: Option<String>,
+    pub weight: u32,
+    pub effective_weight: f64,
     pub membership_epoch: u64,
 }
 
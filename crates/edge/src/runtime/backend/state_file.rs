@@ -0,0 +1,154 @@
+//! JSON state files the control API keeps so backend overrides and
+//! membership changes survive restarts.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Condvar, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use log::error;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Contents of the state file at `path`. A missing file reads as `None`; an
+/// unreadable one is logged as `what` and reads as `None` too.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path, what: &str) -> Option<T> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            error!("Failed to read {} {}: {}", what, path.display(), err);
+            return None;
+        }
+    };
+    match serde_json::from_str(&raw) {
+        Ok(contents) => Some(contents),
+        Err(err) => {
+            error!("Failed to parse {} {}: {}", what, path.display(), err);
+            None
+        }
+    }
+}
+
+/// Writes one state file on the blocking pool. Snapshots are numbered when
+/// taken and written one at a time, so a snapshot older than the one already
+/// on disk is skipped.
+pub(crate) struct StateFileWriter {
+    path: PathBuf,
+    what: &'static str,
+    taken: AtomicU64,
+    // Number of the last snapshot handled, and a signal for `flush`.
+    written: Arc<(Mutex<u64>, Condvar)>,
+}
+
+impl StateFileWriter {
+    pub(crate) fn new(path: PathBuf, what: &'static str) -> Self {
+        Self {
+            path,
+            what,
+            taken: AtomicU64::new(0),
+            written: Arc::new((Mutex::new(0), Condvar::new())),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number `contents` as the latest snapshot. Take it with the state's
+    /// lock held so numbers follow the order of changes, then
+    /// [`spawn`](PendingStateWrite::spawn) the write once the lock is gone.
+    pub(crate) fn snapshot<T>(&self, contents: T) -> PendingStateWrite<T> {
+        PendingStateWrite {
+            path: self.path.clone(),
+            what: self.what,
+            sequence: self.taken.fetch_add(1, Ordering::Relaxed) + 1,
+            contents,
+            written: Arc::clone(&self.written),
+        }
+    }
+
+    /// Block until every snapshot taken so far has been handled.
+    pub(crate) fn flush(&self) {
+        let (written, done) = &*self.written;
+        let mut written = written.lock().unwrap_or_else(PoisonError::into_inner);
+        while *written < self.taken.load(Ordering::Relaxed) {
+            written = done.wait(written).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+pub(crate) struct PendingStateWrite<T> {
+    path: PathBuf,
+    what: &'static str,
+    sequence: u64,
+    contents: T,
+    written: Arc<(Mutex<u64>, Condvar)>,
+}
+
+impl<T: Serialize + Send + 'static> PendingStateWrite<T> {
+    /// Write on Tokio's blocking pool, or right away outside a runtime.
+    pub(crate) fn spawn(self) {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(move || self.run());
+            }
+            Err(_) => self.run(),
+        }
+    }
+
+    fn run(self) {
+        let (written, done) = &*self.written;
+        let mut written = written.lock().unwrap_or_else(PoisonError::into_inner);
+        if *written < self.sequence {
+            write(&self.path, self.what, &self.contents);
+            *written = self.sequence;
+        }
+        done.notify_all();
+    }
+}
+
+/// Replace the state file at `path` with `contents`, logging failures as
+/// `what`.
+fn write<T: Serialize>(path: &Path, what: &str, contents: &T) {
+    let result = serde_json::to_vec_pretty(contents)
+        .map_err(|err| err.to_string())
+        .and_then(|raw| {
+            // Write beside the target and rename so readers never see a
+            // partial file.
+            let tmp = path.with_extension("tmp");
+            std::fs::write(&tmp, raw).map_err(|err| err.to_string())?;
+            std::fs::rename(&tmp, path).map_err(|err| err.to_string())
+        });
+    if let Err(err) = result {
+        error!("Failed to write {} {}: {}", what, path.display(), err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn older_snapshots_never_overwrite_newer_ones() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("state.json");
+        let writer = StateFileWriter::new(path.clone(), "test state file");
+
+        let first = writer.snapshot(vec![1]);
+        let second = writer.snapshot(vec![1, 2]);
+        second.spawn();
+        first.spawn();
+        writer.flush();
+
+        assert_eq!(read::<Vec<u32>>(&path, "test state file"), Some(vec![1, 2]));
+        assert_eq!(
+            read::<Vec<u32>>(&dir.path().join("missing.json"), "test"),
+            None
+        );
+    }
+}
@@ -94,10 +94,15 @@ enum HealthEffectHint {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) struct OutcomeRouteTarget<'a> {
     pub(crate) route: &'a str,
+    /// Priority class, labelling overload sheds.
+    pub(crate) priority: Option<&'a str>,
 }
 
 impl<'a> OutcomeRouteTarget<'a> {
-    pub(crate) const UNROUTED: Self = Self { route: "unrouted" };
+    pub(crate) const UNROUTED: Self = Self {
+        route: "unrouted",
+        priority: None,
+    };
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -284,7 +289,7 @@ pub(crate) fn record_request_metrics_observation(
 
     if matches!(metrics_outcome, RouteOutcome::OverloadShed) {
         if let Some(reason) = overload_reason {
-            metrics.inc_overload_shed_reason(reason);
+            metrics.inc_overload_shed_reason(reason, route_target.priority);
         } else {
             metrics.inc_overload_shed();
         }
@@ -292,7 +297,7 @@ pub(crate) fn record_request_metrics_observation(
 
     metrics.record_route(route_target.route, elapsed, metrics_outcome);
     metrics.record_request_result(
-        route_target.route,
+        backend_target.map_or("unrouted", |target| target.upstream),
         backend_target.and_then(|target| target.backend_addr),
         status,
         metrics_outcome,
@@ -390,7 +395,8 @@ fn terminal_route_target(state: &TerminalState) -> OutcomeRouteTarget<'_> {
         TerminalState::BackendFailed(state) => state.snapshot.routing.as_ref(),
     };
     routing.map_or(OutcomeRouteTarget::UNROUTED, |routing| OutcomeRouteTarget {
-        route: &routing.upstream_name,
+        route: &routing.route_name,
+        priority: routing.priority.as_deref(),
     })
 }
 
@@ -767,9 +773,19 @@ mod tests {
                 load_balancing: LoadBalancing {
                     lb_type: "round-robin".to_string(),
                     key: None,
+                    table_size: None,
+                    hash_balance_factor: None,
+                    p2c: None,
+                    panic_threshold_percent: None,
                 },
                 auth: RouteAuth::default(),
                 host_policy: UpstreamHostPolicy::default(),
+                policy: Default::default(),
+                outlier_detection: None,
+                slow_start: None,
+                zone_aware: None,
+                session_affinity: None,
+                adaptive_admission: None,
                 forwarded_headers: ForwardedHeaderPolicy::default(),
                 tls: None,
                 route: RouteMatch {
@@ -789,6 +805,8 @@ mod tests {
                         success_threshold: 1,
                         cooldown_ms: 0,
                     }),
+                    priority: 0,
+                    zone: None,
                 }],
             },
         );
@@ -805,6 +823,7 @@ mod tests {
             },
             listeners: Vec::new(),
             upstream: upstreams,
+            routes: Vec::new(),
             load_balancing: None,
             upstream_tls: Default::default(),
             log: Default::default(),
@@ -812,6 +831,7 @@ mod tests {
             observability: Default::default(),
             resilience: Default::default(),
             security: Default::default(),
+            locality: Default::default(),
         })
         .expect("runtime config");
 
@@ -911,7 +931,10 @@ mod tests {
 
         let decision = observe_status_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -941,13 +964,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn named_route_outcomes_keep_route_and_upstream_labels_apart() {
+        let metrics = Metrics::new(1, [String::from("checkout")]);
+
+        let _ = observe_status_outcome(
+            &metrics,
+            OutcomeRouteTarget {
+                route: "checkout",
+                priority: None,
+            },
+            Some(OutcomeBackendTarget {
+                upstream: "shop",
+                backend_addr: Some("backend-a"),
+                backend_index: Some(0),
+            }),
+            Duration::from_millis(4),
+            StatusCode::OK,
+        );
+
+        let route = metrics
+            .route_health_sample("checkout")
+            .expect("route stats");
+        assert_eq!(route.requests, 1);
+        assert_eq!(
+            metrics
+                .route_health_sample("unrouted")
+                .expect("unrouted stats")
+                .requests,
+            0
+        );
+        assert_eq!(
+            upstream_request_count(&metrics, "shop", "2xx", "success"),
+            1
+        );
+        assert_eq!(
+            upstream_request_count(&metrics, "checkout", "2xx", "success"),
+            0
+        );
+        assert_eq!(
+            metrics
+                .upstream_health_sample("shop")
+                .expect("upstream stats")
+                .requests,
+            1
+        );
+    }
+
     #[test]
     fn observe_proxy_error_outcome_records_timeout_and_unrouted_failure() {
         let metrics = test_metrics();
 
         let timeout = observe_proxy_error_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -992,7 +1065,10 @@ mod tests {
 
         let overload = observe_admission_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -1006,7 +1082,10 @@ mod tests {
         );
         let auth = observe_admission_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -1018,7 +1097,10 @@ mod tests {
         );
         let rate_limited = observe_admission_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -1124,7 +1206,10 @@ mod tests {
 
         let forwarding = observe_proxy_error_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -1137,7 +1222,10 @@ mod tests {
         );
         let bootstrap = observe_proxy_error_outcome(
             &metrics,
-            OutcomeRouteTarget { route: "api" },
+            OutcomeRouteTarget {
+                route: "api",
+                priority: None,
+            },
             Some(OutcomeBackendTarget {
                 upstream: "api",
                 backend_addr: Some("backend-a"),
@@ -6,7 +6,10 @@ use std::{
 };
 
 use bytes::Bytes;
-use spooky_config::config::{ForwardedHeaderPolicy, UpstreamHostPolicy};
+use spooky_config::{
+    config::{ForwardedHeaderPolicy, UpstreamHostPolicy},
+    runtime::{RuntimeFaultPolicy, RuntimeMirrorPolicy, RuntimePathRewrite, RuntimeRouteLimits},
+};
 use spooky_lb::upstream_pool::UpstreamPool;
 use tokio::sync::{mpsc, oneshot};
 use tracing::Span;
@@ -45,11 +48,16 @@ pub struct RequestEnvelope {
     /// Resolved backend address and index (for health marking on response).
     pub backend_addr: Option<String>,
     pub backend_index: Option<usize>,
+    /// Matched route, labelling route metrics; `upstream_name` is its pool.
+    pub route_name: Option<String>,
     pub upstream_name: Option<String>,
+    /// Priority class assigned at pre-admission.
+    pub priority: Option<String>,
     pub route_reason: Option<String>,
     pub route_path_len: Option<usize>,
     pub route_host_specific: Option<bool>,
     pub backend_lb: Option<String>,
+    pub affinity_cookie: Option<String>,
     pub upstream_pool: Option<Arc<RwLock<UpstreamPool>>>,
     pub routing_transparency_enabled: bool,
     pub routing_transparency_include_reason: bool,
@@ -58,6 +66,8 @@ pub struct RequestEnvelope {
     pub total_request_deadline: Instant,
     pub bodyless_mode: bool,
     pub tunnel_mode: TunnelMode,
+    /// Route override of the listener request body cap.
+    pub max_request_body_bytes: Option<usize>,
 
     pub retry_count: u8,
     pub error_kind: Option<&'static str>,
@@ -89,7 +99,7 @@ impl RequestEnvelope {
             request_mode,
             request_body: _,
             request_body_runtime: _,
-            pending_forward: _,
+            pending_forward,
         } = &state;
 
         Self {
@@ -103,11 +113,14 @@ impl RequestEnvelope {
             authority: context.authority.clone(),
             backend_addr: Some(routing.backend_addr.clone()),
             backend_index: Some(routing.backend_index),
+            route_name: Some(routing.route_name.clone()),
             upstream_name: Some(routing.upstream_name.clone()),
+            priority: routing.priority.clone(),
             route_reason: Some(routing.route_reason.clone()),
             route_path_len: Some(routing.route_path_len),
             route_host_specific: Some(routing.route_host_specific),
             backend_lb: routing.backend_lb.clone(),
+            affinity_cookie: routing.affinity_cookie.clone(),
             upstream_pool: Some(upstream_pool),
             routing_transparency_enabled,
             routing_transparency_include_reason,
@@ -116,6 +129,7 @@ impl RequestEnvelope {
             total_request_deadline: context.total_request_deadline,
             bodyless_mode: request_mode.bodyless_mode(),
             tunnel_mode: request_mode.tunnel_mode(),
+            max_request_body_bytes: pending_forward.limits.max_request_body_bytes,
             retry_count,
             error_kind,
             terminal_overload_reason: None,
@@ -138,7 +152,7 @@ impl RequestEnvelope {
             request_mode,
             request_body: _,
             request_body_runtime: _,
-            pending_forward: _,
+            pending_forward,
             auth_result_rx: _,
             auth_abort: _,
             auth_deadline: _,
@@ -156,11 +170,14 @@ impl RequestEnvelope {
             authority: context.authority.clone(),
             backend_addr: Some(routing.backend_addr.clone()),
             backend_index: Some(routing.backend_index),
+            route_name: Some(routing.route_name.clone()),
             upstream_name: Some(routing.upstream_name.clone()),
+            priority: routing.priority.clone(),
             route_reason: Some(routing.route_reason.clone()),
             route_path_len: Some(routing.route_path_len),
             route_host_specific: Some(routing.route_host_specific),
             backend_lb: routing.backend_lb.clone(),
+            affinity_cookie: routing.affinity_cookie.clone(),
             upstream_pool: Some(upstream_pool),
             routing_transparency_enabled,
             routing_transparency_include_reason,
@@ -169,6 +186,7 @@ impl RequestEnvelope {
             total_request_deadline: context.total_request_deadline,
             bodyless_mode: request_mode.bodyless_mode(),
             tunnel_mode: request_mode.tunnel_mode(),
+            max_request_body_bytes: pending_forward.limits.max_request_body_bytes,
             retry_count,
             error_kind,
             terminal_overload_reason: None,
@@ -938,6 +956,7 @@ pub struct PendingForward {
     pub path: Arc<str>,
     pub authority: Option<Arc<str>>,
     pub headers: Arc<Vec<quiche::h3::Header>>,
+    pub route_name: Arc<str>,
     pub upstream_name: Arc<str>,
     pub route_reason: Arc<str>,
     pub route_path_len: usize,
@@ -952,5 +971,24 @@ pub struct PendingForward {
     pub traceparent: Option<Arc<str>>,
     pub host_policy: UpstreamHostPolicy,
     pub forwarded_header_policy: ForwardedHeaderPolicy,
+    pub path_rewrite: Option<RuntimePathRewrite>,
+    pub mirror: Option<PendingMirror>,
+    pub fault: Option<PendingFault>,
+    pub limits: RuntimeRouteLimits,
     pub(crate) auth_header_mutations: Vec<PendingHeaderMutation>,
 }
+
+/// Route mirror policy carried to dispatch. `route` keys the mirror inflight
+/// budget and the mirror metrics.
+#[derive(Debug, Clone)]
+pub struct PendingMirror {
+    pub route: Arc<str>,
+    pub policy: Arc<RuntimeMirrorPolicy>,
+}
+
+/// Route fault policy carried to dispatch. `route` labels the fault metrics.
+#[derive(Debug, Clone)]
+pub struct PendingFault {
+    pub route: Arc<str>,
+    pub policy: Arc<RuntimeFaultPolicy>,
+}
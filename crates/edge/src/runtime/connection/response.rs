@@ -17,6 +17,8 @@ pub(crate) enum ForwardSuccess {
         status: http::StatusCode,
         headers: http::HeaderMap,
         body: hyper::body::Incoming,
+        /// Injected fault: fail the body once this many bytes were received.
+        truncate_after_bytes: Option<usize>,
     },
     Tunnel {
         status: http::StatusCode,
@@ -101,6 +103,7 @@ pub(crate) struct ResponseBodyPumpPlan {
     pub(crate) progressive_emission_allowed: bool,
     pub(crate) defer_headers_until_body_validated: bool,
     pub(crate) tunnel_response: bool,
+    pub(crate) truncate_after_bytes: Option<usize>,
 }
 
 pub(crate) enum ResponseStartObservation {
@@ -137,6 +140,8 @@ pub(crate) enum ResponseStartDecision {
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct HedgeTelemetry {
     pub(crate) trigger_reason: Option<HedgeTriggerTelemetryReason>,
+    /// Hedges sent for the request.
+    pub(crate) count: u8,
     pub(crate) outcome_reason: Option<HedgeOutcomeTelemetryReason>,
     pub(crate) primary_late_ms: u64,
 }
@@ -144,6 +149,7 @@ pub(crate) struct HedgeTelemetry {
 impl HedgeTelemetry {
     pub(crate) fn record_trigger(&mut self, reason: HedgeTriggerTelemetryReason) {
         self.trigger_reason = Some(reason);
+        self.count = self.count.saturating_add(1);
     }
 
     pub(crate) fn record_outcome(&mut self, reason: HedgeOutcomeTelemetryReason) {
@@ -196,16 +202,18 @@ mod tests {
         let mut telemetry = HedgeTelemetry::default();
 
         telemetry.record_trigger(HedgeTriggerTelemetryReason::DelayElapsed);
-        telemetry.record_outcome(HedgeOutcomeTelemetryReason::HedgeWon);
+        telemetry.record_trigger(HedgeTriggerTelemetryReason::DelayElapsed);
+        telemetry.record_outcome(HedgeOutcomeTelemetryReason::HedgeWon { attempt: 2 });
         telemetry.observe_primary_late_ms(42);
 
         assert_eq!(
             telemetry.trigger_reason,
             Some(HedgeTriggerTelemetryReason::DelayElapsed)
         );
+        assert_eq!(telemetry.count, 2);
         assert_eq!(
             telemetry.outcome_reason,
-            Some(HedgeOutcomeTelemetryReason::HedgeWon)
+            Some(HedgeOutcomeTelemetryReason::HedgeWon { attempt: 2 })
         );
         assert_eq!(telemetry.primary_late_ms, 42);
     }
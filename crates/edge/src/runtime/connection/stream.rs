@@ -282,11 +282,16 @@ pub struct RequestContext {
 pub struct RoutingSnapshot {
     pub backend_addr: String,
     pub backend_index: usize,
+    pub route_name: String,
     pub upstream_name: String,
     pub route_reason: String,
     pub route_path_len: usize,
     pub route_host_specific: bool,
     pub backend_lb: Option<String>,
+    /// Priority class assigned at pre-admission.
+    pub priority: Option<String>,
+    /// `Set-Cookie` value for session affinity, added to the response.
+    pub affinity_cookie: Option<String>,
 }
 
 #[allow(dead_code)]
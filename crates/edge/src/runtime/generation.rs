@@ -4,7 +4,6 @@ use std::{
 };
 
 use spooky_config::{
-    backend_endpoint::BackendEndpoint,
     config::Log,
     runtime::{
         ListenerRuntimeConfig, RuntimeBackendHealthCheck, RuntimeConfig, RuntimeUpstreamPolicy,
@@ -17,9 +16,14 @@ use tokio::sync::Semaphore;
 use crate::{
     Metrics,
     resilience::runtime::RuntimeResilience,
+    rollout::controller::RolloutController,
     routing::index::RouteIndex,
     runtime::{
-        backend::{lifecycle::BackendLifecycleCoordinator, store::RuntimeBackendResolutionStore},
+        backend::{
+            admin::BackendAdminStore, endpoints::BackendEndpoints,
+            lifecycle::BackendLifecycleCoordinator, membership::BackendMembershipStore,
+            store::RuntimeBackendResolutionStore,
+        },
         tasks::RuntimeTaskRegistry,
         tls::store::ListenerTlsReloadStore,
     },
@@ -46,13 +50,20 @@ pub struct RuntimeSharedServices {
 #[derive(Clone)]
 pub struct RuntimeGenerationState {
     pub listener_runtime_configs: Arc<HashMap<String, ListenerRuntimeConfig>>,
-    pub backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
+    pub backend_endpoints: Arc<BackendEndpoints>,
     pub backend_health_checks: Arc<HashMap<String, RuntimeBackendHealthCheck>>,
-    pub upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    pub route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
     pub upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
+    /// Drain/disable overrides applied to `upstream_pools`.
+    pub backend_admin: Arc<BackendAdminStore>,
+    /// Backends added, updated or removed through the control API.
+    pub backend_membership: Arc<BackendMembershipStore>,
     pub upstream_inflight: HashMap<String, Arc<Semaphore>>,
+    /// Inflight budget for mirror copies, keyed by route name.
+    pub mirror_inflight: HashMap<String, Arc<Semaphore>>,
     pub global_inflight: Arc<Semaphore>,
     pub routing_index: Arc<RouteIndex>,
+    pub rollouts: Arc<RolloutController>,
     pub resilience: Arc<RuntimeResilience>,
     pub generation_tasks: Arc<RuntimeTaskRegistry>,
 }
@@ -5,10 +5,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use spooky_config::{
-    backend_endpoint::BackendEndpoint,
-    runtime::{ListenerRuntimeConfig, RuntimeUpstreamPolicy},
-};
+use spooky_config::runtime::{ListenerRuntimeConfig, RuntimeUpstreamPolicy};
 use spooky_lb::upstream_pool::UpstreamPool;
 use spooky_transport::{SharedDnsResolver, UpstreamTransportPool};
 use tokio::sync::Semaphore;
@@ -20,8 +17,8 @@ use crate::{
     resilience::runtime::RuntimeResilience,
     routing::index::RouteIndex,
     runtime::{
-        bundle::RuntimeBundleHandle, connection::quic::QuicConnection,
-        tls::store::ListenerTlsReloadStore,
+        backend::endpoints::BackendEndpoints, bundle::RuntimeBundleHandle,
+        connection::quic::QuicConnection, tls::store::ListenerTlsReloadStore,
     },
     watchdog::coordinator::WatchdogCoordinator,
 };
@@ -38,11 +35,12 @@ pub struct QUICListener {
     pub quic_config: quiche::Config,
     pub h3_config: Arc<quiche::h3::Config>,
     pub transport_pool: Arc<UpstreamTransportPool>,
-    pub backend_endpoints: Arc<HashMap<String, BackendEndpoint>>,
+    pub backend_endpoints: Arc<BackendEndpoints>,
     pub backend_dns_resolver: SharedDnsResolver,
-    pub upstream_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
+    pub route_policies: Arc<HashMap<String, RuntimeUpstreamPolicy>>,
     pub upstream_pools: HashMap<String, Arc<RwLock<UpstreamPool>>>,
     pub upstream_inflight: HashMap<String, Arc<Semaphore>>,
+    pub mirror_inflight: HashMap<String, Arc<Semaphore>>,
     pub global_inflight: Arc<Semaphore>,
     pub(crate) routing_index: Arc<RouteIndex>,
     pub metrics: Arc<Metrics>,
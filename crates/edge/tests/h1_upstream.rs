@@ -19,8 +19,8 @@ use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
 use serial_test::serial;
 use spooky_config::{
     config::{
-        Backend, ClientAuth, Config, Listen, LoadBalancing, Log, LogFormat, RouteMatch, Security,
-        Tls, Upstream, UpstreamTls,
+        Backend, ClientAuth, Config, Listen, LoadBalancing, Log, LogFormat, RetryOn, RouteMatch,
+        Security, Tls, Upstream, UpstreamTls,
     },
     validator::validate,
 };
@@ -91,9 +91,14 @@ fn make_config(
         },
         listeners: Vec::new(),
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls,
         log: Log {
@@ -105,6 +110,7 @@ fn make_config(
         observability: spooky_config::config::Observability::default(),
         resilience: spooky_config::config::Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -118,9 +124,19 @@ fn make_upstream(
         load_balancing: LoadBalancing {
             lb_type: lb_type.to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls,
         route: RouteMatch {
@@ -138,6 +154,8 @@ fn make_backend(id: &str, address: String) -> Backend {
         address,
         weight: 1,
         health_check: None,
+        priority: 0,
+        zone: None,
     }
 }
 
@@ -621,6 +639,52 @@ fn http_only_upstream_retries_bodyless_requests_on_alternate_backend() {
     assert_eq!(String::from_utf8_lossy(&response.body), "retry ok\n");
 }
 
+#[test]
+#[serial]
+fn hedged_route_retries_listed_statuses_on_alternate_backend() {
+    if !local_tcp_bind_available() {
+        return;
+    }
+    let dir = tempdir().expect("tempdir");
+    let (cert, key) = write_test_certs(&dir);
+    let rt = tokio::runtime::Runtime::new().expect("runtime");
+    let unavailable_backend = rt.block_on(start_h1_backend(|_req| {
+        let mut response = Response::new(Full::new(Bytes::from_static(b"unavailable\n")));
+        *response.status_mut() = hyper::StatusCode::SERVICE_UNAVAILABLE;
+        response
+    }));
+    let healthy_backend = rt.block_on(start_h1_backend(|_req| {
+        Response::new(Full::new(Bytes::from_static(b"retry ok\n")))
+    }));
+
+    let mut upstream = make_upstream(
+        "/retry",
+        vec![
+            make_backend("unavailable", format!("http://{unavailable_backend}")),
+            make_backend("healthy", format!("http://{healthy_backend}")),
+        ],
+        None,
+        "round-robin",
+    );
+    upstream.policy.retry_on = Some(vec![RetryOn::Status(503)]);
+    let mut upstreams = HashMap::new();
+    upstreams.insert("retry".to_string(), upstream);
+    let mut config = make_config(cert, key, upstreams, UpstreamTls::default());
+    // The 503 arrives long before a hedge would fire.
+    config.resilience.hedging.enabled = true;
+    config.resilience.hedging.delay_ms = 5_000;
+
+    validate(&config).expect("config should validate");
+    let listener = QUICListener::new(config).expect("listener");
+    let listen_addr = listener.socket.local_addr().expect("listen addr");
+    let _listener_task = ListenerTaskGuard::spawn(&rt, listener);
+
+    let response =
+        run_h3_get(listen_addr, "retry.example.com", "/retry", &[]).expect("retry request");
+    assert_eq!(response.status, "200");
+    assert_eq!(String::from_utf8_lossy(&response.body), "retry ok\n");
+}
+
 #[test]
 #[serial]
 fn mixed_http_and_https_upstreams_route_by_scheme() {
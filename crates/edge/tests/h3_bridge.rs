@@ -166,9 +166,19 @@ fn make_config(port: u32, backend_addr: String, cert: String, key: String) -> Co
             load_balancing: LoadBalancing {
                 lb_type: "random".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -187,6 +197,8 @@ fn make_config(port: u32, backend_addr: String, cert: String, key: String) -> Co
                     success_threshold: 1,
                     cooldown_ms: 0,
                 }),
+                priority: 0,
+                zone: None,
             }],
         },
     );
@@ -206,9 +218,14 @@ fn make_config(port: u32, backend_addr: String, cert: String, key: String) -> Co
         },
         listeners: vec![],
         upstream,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "random".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log {
@@ -220,6 +237,7 @@ fn make_config(port: u32, backend_addr: String, cert: String, key: String) -> Co
         observability: spooky_config::config::Observability::default(),
         resilience: spooky_config::config::Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -3941,6 +3959,8 @@ fn chaos_partial_outage_preserves_some_availability() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
         Backend {
             id: "healthy-backend".to_string(),
@@ -3954,6 +3974,8 @@ fn chaos_partial_outage_preserves_some_availability() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
     ];
     let mut config = make_config_with_backends(0, backends, "round-robin", cert, key);
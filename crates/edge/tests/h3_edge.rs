@@ -68,9 +68,19 @@ fn make_config(port: u32, cert: String, key: String, backend_address: String) ->
             load_balancing: LoadBalancing {
                 lb_type: "random".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -89,6 +99,8 @@ fn make_config(port: u32, cert: String, key: String, backend_address: String) ->
                     success_threshold: 1,
                     cooldown_ms: 0,
                 }),
+                priority: 0,
+                zone: None,
             }],
         },
     );
@@ -108,9 +120,14 @@ fn make_config(port: u32, cert: String, key: String, backend_address: String) ->
         },
         listeners: vec![],
         upstream,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "random".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log {
@@ -122,6 +139,7 @@ fn make_config(port: u32, cert: String, key: String, backend_address: String) ->
         observability: spooky_config::config::Observability::default(),
         resilience: spooky_config::config::Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -751,9 +769,19 @@ fn make_config_with_rate_limit(
             load_balancing: LoadBalancing {
                 lb_type: "random".to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -772,6 +800,8 @@ fn make_config_with_rate_limit(
                     success_threshold: 1,
                     cooldown_ms: 0,
                 }),
+                priority: 0,
+                zone: None,
             }],
         },
     );
@@ -791,9 +821,14 @@ fn make_config_with_rate_limit(
         },
         listeners: vec![],
         upstream,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: "random".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log {
@@ -809,6 +844,7 @@ fn make_config_with_rate_limit(
         observability: spooky_config::config::Observability::default(),
         resilience: spooky_config::config::Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
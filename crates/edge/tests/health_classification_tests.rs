@@ -17,6 +17,8 @@ fn create_test_backend_pool() -> BackendPool {
             success_threshold: 2,
             cooldown_ms: 10000,
         }),
+        priority: 0,
+        zone: None,
     }];
     let backend_states = backends
         .iter()
@@ -93,9 +93,19 @@ fn make_config(
             load_balancing: LoadBalancing {
                 lb_type: lb_type.to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             tls: None,
             route: RouteMatch {
@@ -127,9 +137,14 @@ fn make_config(
         },
         listeners: vec![],
         upstream,
+        routes: Vec::new(),
         load_balancing: Some(LoadBalancing {
             lb_type: lb_type.to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         }),
         upstream_tls: UpstreamTls::default(),
         log: Log {
@@ -141,6 +156,7 @@ fn make_config(
         observability: spooky_config::config::Observability::default(),
         resilience: spooky_config::config::Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     }
 }
 
@@ -309,6 +325,8 @@ fn round_robin_across_backends() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
         Backend {
             id: "b".to_string(),
@@ -322,6 +340,8 @@ fn round_robin_across_backends() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
     ];
 
@@ -383,6 +403,8 @@ fn consistent_hash_is_stable_per_authority() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
         Backend {
             id: "b".to_string(),
@@ -396,6 +418,8 @@ fn consistent_hash_is_stable_per_authority() {
                 success_threshold: 1,
                 cooldown_ms: 0,
             }),
+            priority: 0,
+            zone: None,
         },
     ];
 
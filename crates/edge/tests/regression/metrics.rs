@@ -2,11 +2,12 @@
 
 use std::{sync::atomic::Ordering, time::Duration};
 
-use spooky_edge::{Metrics, OverloadShedReason, RouteOutcome};
+use spooky_edge::{Metrics, OverloadShedReason, RouteOutcome, metrics::AdaptiveConcurrencyGauges};
 use spooky_errors::{
     HedgeOutcomeTelemetryReason, HedgeTriggerTelemetryReason, RetryAttemptTelemetryReason,
     RetryPolicyDenialReason,
 };
+use spooky_lb::outlier::OutlierEjectionReason;
 
 #[test]
 fn metrics_render_includes_route_percentiles() {
@@ -66,6 +67,121 @@ fn metrics_render_includes_route_percentiles() {
     ));
 }
 
+#[test]
+fn metrics_render_includes_route_split_counts() {
+    let metrics = Metrics::new(1, [String::from("api")]);
+    for _ in 0..19 {
+        metrics.record_route_split("checkout", "api");
+    }
+    metrics.record_route_split("checkout", "api-canary");
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_route_split_total counter"));
+    assert!(output.contains("spooky_route_split_total{route=\"checkout\",upstream=\"api\"} 19"));
+    assert!(
+        output.contains("spooky_route_split_total{route=\"checkout\",upstream=\"api-canary\"} 1")
+    );
+}
+
+#[test]
+fn metrics_render_includes_mirror_outcomes_and_latency() {
+    let metrics = Metrics::new(1, [String::from("api")]);
+    metrics.record_mirror_request(
+        "checkout",
+        "api-shadow",
+        "response",
+        Some(Duration::from_millis(7)),
+    );
+    metrics.record_mirror_request(
+        "checkout",
+        "api-shadow",
+        "response",
+        Some(Duration::from_millis(40)),
+    );
+    metrics.record_mirror_request("checkout", "api-shadow", "skipped_inflight", None);
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_mirror_requests_total counter"));
+    assert!(output.contains(
+        "spooky_mirror_requests_total{route=\"checkout\",upstream=\"api-shadow\",outcome=\"response\"} 2"
+    ));
+    assert!(output.contains(
+        "spooky_mirror_requests_total{route=\"checkout\",upstream=\"api-shadow\",outcome=\"skipped_inflight\"} 1"
+    ));
+    assert!(output.contains(
+        "spooky_mirror_request_latency_ms_count{route=\"checkout\",upstream=\"api-shadow\",outcome=\"response\"} 2"
+    ));
+    assert!(output.contains(
+        "spooky_mirror_request_latency_ms_sum{route=\"checkout\",upstream=\"api-shadow\",outcome=\"response\"} 47"
+    ));
+    assert!(
+        !output.contains("spooky_mirror_request_latency_ms_count{route=\"checkout\",upstream=\"api-shadow\",outcome=\"skipped_inflight\"}"),
+        "skipped copies have no latency"
+    );
+}
+
+#[test]
+fn metrics_render_includes_fault_injections() {
+    let metrics = Metrics::new(1, [String::from("api")]);
+    metrics.record_fault_injection("checkout", "delay");
+    metrics.record_fault_injection("checkout", "delay");
+    metrics.record_fault_injection("checkout", "reset");
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_fault_injections_total counter"));
+    assert!(output.contains("spooky_fault_injections_total{route=\"checkout\",fault=\"delay\"} 2"));
+    assert!(output.contains("spooky_fault_injections_total{route=\"checkout\",fault=\"reset\"} 1"));
+}
+
+#[test]
+fn metrics_render_includes_rollout_state() {
+    let metrics = Metrics::new(1, [String::from("api")]);
+    metrics.set_rollout_canary_percent("checkout", 25);
+    metrics.record_rollout_transition("checkout", "advance");
+    metrics.record_rollout_transition("checkout", "advance");
+    metrics.record_rollout_transition("checkout", "rollback");
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_rollout_canary_percent gauge"));
+    assert!(output.contains("spooky_rollout_canary_percent{route=\"checkout\"} 25"));
+    assert!(
+        output.contains(
+            "spooky_rollout_transitions_total{route=\"checkout\",transition=\"advance\"} 2"
+        )
+    );
+    assert!(output.contains(
+        "spooky_rollout_transitions_total{route=\"checkout\",transition=\"rollback\"} 1"
+    ));
+}
+
+#[test]
+fn route_health_sample_diffs_errors_and_latency() {
+    let metrics = Metrics::new(1, [String::from("api")]);
+    metrics.record_route("api", Duration::from_millis(3), RouteOutcome::Success);
+    let start = metrics.route_health_sample("api").expect("api sample");
+
+    for _ in 0..18 {
+        metrics.record_route("api", Duration::from_millis(40), RouteOutcome::Success);
+    }
+    metrics.record_route(
+        "api",
+        Duration::from_millis(400),
+        RouteOutcome::BackendError,
+    );
+    metrics.record_route("api", Duration::from_millis(1), RouteOutcome::RateLimited);
+
+    let window = metrics
+        .route_health_sample("api")
+        .expect("api sample")
+        .since(&start);
+    assert_eq!(window.requests, 20);
+    assert_eq!(window.errors, 1);
+    assert_eq!(window.error_rate_basis_points(), 500);
+    assert_eq!(window.latency_samples(), 20);
+    assert_eq!(window.p95_ms(), 50);
+    assert!(metrics.route_health_sample("missing").is_none());
+}
+
 #[test]
 fn metrics_render_collects_routes_from_multiple_shards() {
     let routes: Vec<String> = (0..128).map(|idx| format!("route-{idx:03}")).collect();
@@ -103,28 +219,133 @@ fn request_buffer_reservation_respects_cap_and_releases() {
     assert_eq!(metrics.request_buffered_bytes.load(Ordering::Relaxed), 0);
 }
 
+#[test]
+fn metrics_render_includes_request_replay_fallbacks() {
+    let metrics = Metrics::default();
+    metrics.inc_request_replay_fallback();
+    metrics.inc_request_replay_fallback();
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_request_replay_fallbacks counter"));
+    assert!(output.contains("spooky_request_replay_fallbacks 2"));
+}
+
+#[test]
+fn metrics_render_includes_outlier_ejections_by_upstream_and_reason() {
+    let metrics = Metrics::default();
+    metrics.inc_outlier_ejection("api", OutlierEjectionReason::SuccessRateStdev);
+    metrics.inc_outlier_ejection("api", OutlierEjectionReason::SuccessRateStdev);
+    metrics.inc_outlier_ejection("search", OutlierEjectionReason::LatencyP95);
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_outlier_ejections_total counter"));
+    assert!(output.contains(
+        "spooky_outlier_ejections_total{upstream=\"api\",reason=\"success_rate_stdev\"} 2"
+    ));
+    assert!(
+        output.contains(
+            "spooky_outlier_ejections_total{upstream=\"search\",reason=\"latency_p95\"} 1"
+        )
+    );
+}
+
+#[test]
+fn metrics_render_includes_upstream_zone_requests() {
+    let metrics = Metrics::default();
+    metrics.record_upstream_zone_request("api", Some("us-east-1a"), Some("us-east-1a"));
+    metrics.record_upstream_zone_request("api", Some("us-east-1a"), Some("us-east-1a"));
+    metrics.record_upstream_zone_request("api", Some("us-east-1b"), Some("us-east-1a"));
+    metrics.record_upstream_zone_request("search", None, Some("us-east-1a"));
+
+    let output = metrics.render_prometheus();
+    assert!(output.contains("# TYPE spooky_upstream_zone_requests_total counter"));
+    assert!(output.contains(
+        "spooky_upstream_zone_requests_total{upstream=\"api\",zone=\"us-east-1a\",locality=\"local\"} 2"
+    ));
+    assert!(output.contains(
+        "spooky_upstream_zone_requests_total{upstream=\"api\",zone=\"us-east-1b\",locality=\"remote\"} 1"
+    ));
+    assert!(output.contains(
+        "spooky_upstream_zone_requests_total{upstream=\"search\",zone=\"unknown\",locality=\"unknown\"} 1"
+    ));
+}
+
+#[test]
+fn metrics_render_includes_adaptive_concurrency_gauges() {
+    let metrics = Metrics::default();
+    metrics.set_adaptive_concurrency(
+        None,
+        AdaptiveConcurrencyGauges {
+            limit: 512,
+            min_rtt: None,
+            smoothed_rtt: None,
+            gradient: None,
+        },
+    );
+    metrics.set_adaptive_concurrency(
+        Some("api"),
+        AdaptiveConcurrencyGauges {
+            limit: 40,
+            min_rtt: Some(Duration::from_millis(12)),
+            smoothed_rtt: Some(Duration::from_millis(20)),
+            gradient: Some(0.9),
+        },
+    );
+
+    let output = metrics.render_prometheus();
+    assert!(
+        output.contains("spooky_adaptive_concurrency_limit{scope=\"global\",upstream=\"\"} 512")
+    );
+    assert!(
+        output
+            .contains("spooky_adaptive_concurrency_limit{scope=\"upstream\",upstream=\"api\"} 40")
+    );
+    assert!(output.contains(
+        "spooky_adaptive_concurrency_min_rtt_ms{scope=\"upstream\",upstream=\"api\"} 12.000"
+    ));
+    assert!(output.contains(
+        "spooky_adaptive_concurrency_smoothed_rtt_ms{scope=\"upstream\",upstream=\"api\"} 20.000"
+    ));
+    assert!(output.contains(
+        "spooky_adaptive_concurrency_gradient{scope=\"upstream\",upstream=\"api\"} 0.900"
+    ));
+    assert!(!output.contains("spooky_adaptive_concurrency_gradient{scope=\"global\""));
+}
+
 #[test]
 fn metrics_render_includes_overload_reasons_and_hedge_counters() {
     let metrics = Metrics::default();
-    metrics.inc_overload_shed_reason(OverloadShedReason::GlobalInflight);
-    metrics.inc_overload_shed_reason(OverloadShedReason::BackendInflight);
-    metrics.inc_overload_shed_reason(OverloadShedReason::CircuitOpen);
+    metrics.inc_overload_shed_reason(OverloadShedReason::GlobalInflight, None);
+    metrics.inc_overload_shed_reason(OverloadShedReason::BackendInflight, Some("bulk"));
+    metrics.inc_overload_shed_reason(OverloadShedReason::CircuitOpen, Some("default"));
     metrics.set_active_connections(7);
     metrics.inc_connection_cap_reject();
     metrics.inc_hedge_trigger(HedgeTriggerTelemetryReason::DelayElapsed);
-    metrics.inc_hedge_outcome(HedgeOutcomeTelemetryReason::HedgeWon);
-    metrics.inc_hedge_outcome(HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger);
+    metrics.inc_hedge_outcome(HedgeOutcomeTelemetryReason::HedgeWon { attempt: 1 }, 1);
+    metrics.inc_hedge_outcome(HedgeOutcomeTelemetryReason::PrimaryWonAfterTrigger, 1);
     metrics.observe_hedge_primary_late_ms(42);
     metrics.inc_control_api_connection_limit_drop();
 
     let output = metrics.render_prometheus();
-    assert!(output.contains("spooky_overload_shed_by_reason_total{reason=\"global_inflight\"} 1"));
-    assert!(output.contains("spooky_overload_shed_by_reason_total{reason=\"backend_inflight\"} 1"));
-    assert!(output.contains("spooky_overload_shed_by_reason_total{reason=\"circuit_open\"} 1"));
+    assert!(output.contains(
+        "spooky_overload_shed_by_reason_total{reason=\"global_inflight\",priority=\"\"} 1"
+    ));
+    assert!(output.contains(
+        "spooky_overload_shed_by_reason_total{reason=\"backend_inflight\",priority=\"bulk\"} 1"
+    ));
+    assert!(output.contains(
+        "spooky_overload_shed_by_reason_total{reason=\"circuit_open\",priority=\"default\"} 1"
+    ));
+    assert!(
+        output
+            .contains("spooky_overload_shed_by_reason_total{reason=\"brownout\",priority=\"\"} 0")
+    );
     assert!(output.contains("spooky_active_connections 7"));
     assert!(output.contains("spooky_connection_cap_rejects 1"));
     assert!(output.contains("spooky_hedge_triggered_total 1"));
     assert!(output.contains("spooky_hedge_won_total 1"));
+    assert!(output.contains("spooky_hedge_won_by_attempt_total{attempt=\"1\"} 1"));
+    assert!(output.contains("spooky_hedge_won_by_attempt_total{attempt=\"2\"} 0"));
     assert!(output.contains("spooky_hedge_wasted_total 1"));
     assert!(output.contains("spooky_hedge_primary_won_after_trigger_total 1"));
     assert!(output.contains("spooky_hedge_primary_late_ms_total 42"));
@@ -158,6 +379,13 @@ fn resilience_metrics_increment_correctly() {
     metrics.set_brownout_active(false);
     let output2 = metrics.render_prometheus();
     assert!(output2.contains("spooky_brownout_active 0\n"));
+
+    metrics.inc_retry_attempt(RetryAttemptTelemetryReason::Status);
+    metrics.inc_retry_attempt(RetryAttemptTelemetryReason::ConnectFailure);
+    let output3 = metrics.render_prometheus();
+    assert!(output3.contains("spooky_retries_total 3\n"));
+    assert!(output3.contains("spooky_retry_attempts_total{reason=\"status\"} 1\n"));
+    assert!(output3.contains("spooky_retry_attempts_total{reason=\"connect_failure\"} 1\n"));
 }
 
 #[test]
@@ -1,16 +1,26 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use spooky_config::config::{
-    Resilience as ResilienceConfig, ScopedRateLimit as ScopedRateLimitConfig, ScopedRateLimitScope,
+    Priority as PriorityConfig, PriorityClass, PriorityRule, Resilience as ResilienceConfig,
+    ScopedRateLimit as ScopedRateLimitConfig, ScopedRateLimitScope,
 };
-use spooky_edge::resilience::{
-    adaptive_admission::AdaptiveAdmission,
-    brownout::BrownoutController,
-    circuit_breaker::CircuitBreakers,
-    retry_budget::RetryBudget,
-    route_queue::{RouteQueueLimiter, RouteQueueRejection},
-    runtime::RuntimeResilience,
-    scoped_rate_limit::ScopedRateLimiters,
+use spooky_edge::{
+    Metrics, RouteOutcome,
+    resilience::{
+        adaptive_admission::{AdaptiveAdmission, GradientSettings},
+        brownout::BrownoutController,
+        circuit_breaker::{CircuitBreakers, CircuitState},
+        hedging::{HedgeController, HedgeSettings, PercentileDelay},
+        priority::PriorityClassifier,
+        retry_budget::RetryBudget,
+        route_queue::{RouteQueueLimiter, RouteQueueRejection},
+        runtime::RuntimeResilience,
+        scoped_rate_limit::ScopedRateLimiters,
+    },
 };
 
 #[test]
@@ -31,6 +41,50 @@ fn runtime_resilience_honors_adaptive_max_limit_override() {
     assert_eq!(runtime.adaptive_admission.current_limit(), 256);
 }
 
+fn gradient_admission(min_limit: usize, max_limit: usize) -> AdaptiveAdmission {
+    AdaptiveAdmission::new(true, min_limit, max_limit, 1, 1, 100).with_gradient(GradientSettings {
+        min_rtt_window: Duration::from_secs(60),
+        rtt_tolerance_percent: 100,
+        smoothing_percent: 100,
+        queue_size: 1,
+    })
+}
+
+#[test]
+fn gradient_admission_tracks_min_rtt_and_shrinks_as_latency_rises() {
+    let admission = gradient_admission(10, 100);
+    for _ in 0..5 {
+        admission.observe(Duration::from_millis(10), false);
+    }
+    assert_eq!(admission.current_limit(), 100);
+    let snapshot = admission.gradient_snapshot().expect("gradient snapshot");
+    assert_eq!(snapshot.min_rtt, Duration::from_millis(10));
+    assert_eq!(snapshot.gradient, 1.0);
+
+    admission.observe(Duration::from_millis(50), false);
+    let snapshot = admission.gradient_snapshot().expect("gradient snapshot");
+    assert_eq!(snapshot.min_rtt, Duration::from_millis(10));
+    assert!(snapshot.smoothed_rtt > Duration::from_millis(10));
+    assert!(snapshot.gradient < 1.0);
+    assert!(admission.current_limit() < 100);
+
+    for _ in 0..50 {
+        admission.observe(Duration::from_millis(500), false);
+    }
+    assert_eq!(admission.current_limit(), 10);
+}
+
+#[test]
+fn gradient_admission_halves_on_overload_and_regrows() {
+    let admission = gradient_admission(10, 100);
+    admission.observe(Duration::from_millis(10), true);
+    assert_eq!(admission.current_limit(), 51);
+    assert!(admission.gradient_snapshot().is_none());
+
+    admission.observe(Duration::from_millis(10), false);
+    assert_eq!(admission.current_limit(), 52);
+}
+
 #[test]
 fn route_queue_cap_enforced() {
     let limiter = Arc::new(RouteQueueLimiter::new(1, 10, HashMap::new()));
@@ -52,6 +106,27 @@ fn route_queue_global_cap_enforced() {
     ));
 }
 
+#[test]
+fn route_queue_share_reserves_capacity_for_larger_shares() {
+    let limiter = Arc::new(RouteQueueLimiter::new(10, 4, HashMap::new()));
+    let _p1 = limiter.try_acquire_share("api", 50).expect("first permit");
+    let _p2 = limiter.try_acquire_share("api", 50).expect("second permit");
+    assert!(matches!(
+        limiter.try_acquire_share("api", 50),
+        Err(RouteQueueRejection::GlobalCap)
+    ));
+    let _p3 = limiter.try_acquire("api").expect("full share permit");
+}
+
+#[test]
+fn adaptive_admission_share_reserves_capacity_for_larger_shares() {
+    let admission = Arc::new(AdaptiveAdmission::new(true, 1, 4, 1, 1, 100));
+    let _p1 = admission.try_acquire_share(25).expect("first permit");
+    assert!(admission.try_acquire_share(25).is_none());
+    let _p2 = admission.try_acquire_share(50).expect("half share permit");
+    let _p3 = admission.try_acquire().expect("full share permit");
+}
+
 #[test]
 fn circuit_breaker_opens_after_threshold() {
     let cb = CircuitBreakers::new(true, 2, Duration::from_secs(1), 1);
@@ -62,6 +137,24 @@ fn circuit_breaker_opens_after_threshold() {
     assert!(!cb.allow_request("b1"));
 }
 
+#[test]
+fn circuit_breaker_peek_does_not_take_half_open_probes() {
+    let cb = CircuitBreakers::new(true, 1, Duration::from_millis(0), 1);
+    assert_eq!(cb.peek("b1").state, CircuitState::Closed);
+    cb.record_failure("b1");
+
+    let probe = cb.peek("b1");
+    assert_eq!(probe.state, CircuitState::HalfOpen);
+    assert!(probe.allows_request);
+    assert!(cb.peek("b1").allows_request);
+
+    assert!(cb.allow_request("b1"));
+    let probe = cb.peek("b1");
+    assert_eq!(probe.state, CircuitState::HalfOpen);
+    assert!(!probe.allows_request);
+    assert!(!cb.allow_request("b1"));
+}
+
 #[test]
 fn retry_budget_respects_ratio() {
     let rb = RetryBudget::new(true, 50, HashMap::new());
@@ -98,6 +191,33 @@ fn scoped_rate_limit_enforces_per_route_tokens() {
     assert_eq!(rejection.route, "api");
 }
 
+#[test]
+fn scoped_rate_limit_probe_reports_verdicts_without_consuming_tokens() {
+    let rule = ScopedRateLimitConfig {
+        name: "route-cap".to_string(),
+        scope: ScopedRateLimitScope::Route,
+        requests_per_sec: 1,
+        burst: 1,
+        key: None,
+        route_allowlist: Vec::new(),
+        idle_ttl_secs: 300,
+    };
+    let limiters = ScopedRateLimiters::new(&[rule]);
+
+    for _ in 0..3 {
+        let probes = limiters.probe("api", |_| Some("api".to_string()));
+        assert_eq!(probes.len(), 1);
+        assert_eq!(probes[0].rule_name, "route-cap");
+        assert_eq!(probes[0].key, "api");
+        assert!(probes[0].allows_request);
+    }
+
+    assert!(limiters.check("api", |_| Some("api".to_string())).is_none());
+    let probes = limiters.probe("api", |_| Some("api".to_string()));
+    assert!(!probes[0].allows_request);
+    assert!(limiters.probe("api", |_| None).is_empty());
+}
+
 #[test]
 fn scoped_rate_limit_skips_rules_outside_route_allowlist() {
     let rule = ScopedRateLimitConfig {
@@ -127,6 +247,191 @@ fn brownout_preserves_core_routes() {
     assert!(!controller.route_allowed("non_core"));
 }
 
+fn priority_config() -> PriorityConfig {
+    PriorityConfig {
+        default_class: "standard".to_string(),
+        classes: vec![
+            PriorityClass {
+                name: "critical".to_string(),
+                max_inflight_percent: 100,
+                brownout_exempt: true,
+            },
+            PriorityClass {
+                name: "standard".to_string(),
+                max_inflight_percent: 80,
+                brownout_exempt: false,
+            },
+            PriorityClass {
+                name: "bulk".to_string(),
+                max_inflight_percent: 40,
+                brownout_exempt: false,
+            },
+        ],
+        rules: vec![
+            PriorityRule {
+                class: "critical".to_string(),
+                routes: vec!["checkout".to_string()],
+                header: None,
+                jwt_claim: None,
+                values: Vec::new(),
+            },
+            PriorityRule {
+                class: "bulk".to_string(),
+                routes: Vec::new(),
+                header: Some("X-Traffic-Class".to_string()),
+                jwt_claim: None,
+                values: vec!["beacon".to_string()],
+            },
+            PriorityRule {
+                class: "critical".to_string(),
+                routes: Vec::new(),
+                header: None,
+                jwt_claim: Some("tier".to_string()),
+                values: vec!["gold".to_string()],
+            },
+        ],
+    }
+}
+
+#[test]
+fn priority_classifier_matches_route_header_and_jwt_rules() {
+    let classifier = PriorityClassifier::new(&priority_config());
+    assert!(classifier.uses_jwt_claims());
+
+    let no_header = |_: &str| None;
+    let no_claims = |_: &str| Vec::new();
+    assert_eq!(
+        classifier.classify("checkout", no_header, no_claims).name,
+        "critical"
+    );
+    assert_eq!(
+        classifier.classify("catalog", no_header, no_claims).name,
+        "standard"
+    );
+
+    let beacon = classifier.classify(
+        "catalog",
+        |name| (name == "x-traffic-class").then(|| "beacon".to_string()),
+        no_claims,
+    );
+    assert_eq!(beacon.name, "bulk");
+    assert_eq!(beacon.max_inflight_percent, 40);
+
+    let gold = classifier.classify("catalog", no_header, |claim| {
+        if claim == "tier" {
+            vec!["gold".to_string()]
+        } else {
+            Vec::new()
+        }
+    });
+    assert_eq!(gold.name, "critical");
+
+    assert_eq!(classifier.class_or_default(Some("bulk")).name, "bulk");
+    assert_eq!(
+        classifier.class_or_default(Some("removed")).name,
+        "standard"
+    );
+    assert_eq!(classifier.class_or_default(None).name, "standard");
+}
+
+#[test]
+fn priority_classifier_defaults_to_implicit_full_share_class() {
+    let classifier = PriorityClassifier::new(&PriorityConfig::default());
+    let class = classifier.classify("api", |_| None, |_| Vec::new());
+    assert_eq!(class.name, "default");
+    assert_eq!(class.max_inflight_percent, 100);
+    assert!(!class.brownout_exempt);
+    assert!(!classifier.uses_jwt_claims());
+}
+
+#[test]
+fn brownout_admits_exempt_priority_classes() {
+    let controller = BrownoutController::new(true, 90, 60, vec!["core".to_string()]);
+    controller.observe_admission_pressure(95);
+    let classifier = PriorityClassifier::new(&priority_config());
+    let critical = classifier.class_or_default(Some("critical"));
+    let standard = classifier.class_or_default(Some("standard"));
+    assert!(controller.request_allowed("non_core", critical));
+    assert!(!controller.request_allowed("non_core", standard));
+    assert!(controller.request_allowed("core", standard));
+}
+
+fn hedge_settings(percentile: Option<PercentileDelay>, max_hedged_percent: u8) -> HedgeSettings {
+    HedgeSettings {
+        delay: Duration::from_millis(50),
+        percentile,
+        window: Duration::from_secs(10),
+        max_hedged_percent,
+        max_hedges: 2,
+    }
+}
+
+#[test]
+fn hedge_controller_uses_fixed_delay_without_percentile() {
+    let controller = HedgeController::new(hedge_settings(None, 100));
+    let now = Instant::now();
+    let delay = controller.start_request("api", now, || panic!("fixed mode samples latency"));
+    assert_eq!(delay, Duration::from_millis(50));
+    assert_eq!(controller.max_hedges(), 2);
+    assert!(controller.try_hedge("api"));
+}
+
+#[test]
+fn hedge_controller_tracks_route_latency_percentile() {
+    let metrics = Metrics::new(1, ["api".to_string()]);
+    let controller = HedgeController::new(hedge_settings(
+        Some(PercentileDelay {
+            percentile: 90,
+            min_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(200),
+            min_samples: 10,
+        }),
+        100,
+    ));
+    let sample = || metrics.route_health_sample("api");
+    let start = Instant::now();
+
+    for _ in 0..5 {
+        metrics.record_route("api", Duration::from_millis(20), RouteOutcome::Success);
+    }
+    assert_eq!(
+        controller.start_request("api", start, sample),
+        Duration::from_millis(50)
+    );
+
+    for _ in 0..5 {
+        metrics.record_route("api", Duration::from_millis(20), RouteOutcome::Success);
+    }
+    let next_window = start + Duration::from_secs(10);
+    assert_eq!(
+        controller.start_request("api", next_window, sample),
+        Duration::from_millis(25)
+    );
+
+    for _ in 0..20 {
+        metrics.record_route("api", Duration::from_millis(900), RouteOutcome::Success);
+    }
+    assert_eq!(
+        controller.start_request("api", next_window + Duration::from_secs(10), sample),
+        Duration::from_millis(200)
+    );
+}
+
+#[test]
+fn hedge_controller_caps_hedged_share_per_window() {
+    let controller = HedgeController::new(hedge_settings(None, 25));
+    let now = Instant::now();
+    for _ in 0..4 {
+        controller.start_request("api", now, || None);
+    }
+    assert!(controller.try_hedge("api"));
+    assert!(!controller.try_hedge("api"));
+    assert!(!controller.try_hedge("other"));
+
+    controller.start_request("api", now + Duration::from_secs(10), || None);
+    assert!(controller.try_hedge("api"));
+}
+
 #[test]
 fn runtime_resilience_method_and_path_policy_checks() {
     let mut cfg = ResilienceConfig::default();
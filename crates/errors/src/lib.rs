@@ -19,9 +19,10 @@ pub use proxy::{
 pub use retry::{
     HedgeOutcomeTelemetryReason, HedgePolicyDecision, HedgePolicyDenialReason, HedgePolicyFacts,
     HedgePrimaryState, HedgeTriggerTelemetryReason, RetryAttemptTelemetryReason,
-    RetryPolicyDecision, RetryPolicyDenialReason, RetryPolicyFacts, UpstreamRetryReason,
-    UpstreamRetryability, UpstreamTerminalErrorKind, classify_retryability, evaluate_hedge_policy,
-    evaluate_retry_policy, is_idempotent_method, is_retryable,
+    RetryErrorConditions, RetryPolicyDecision, RetryPolicyDenialReason, RetryPolicyFacts,
+    UpstreamRetryReason, UpstreamRetryability, UpstreamTerminalErrorKind, classify_retryability,
+    classify_retryability_for, evaluate_hedge_policy, evaluate_retry_policy, is_idempotent_method,
+    is_idempotent_request, is_retryable,
 };
 pub use upstream::{
     UpstreamErrorCategory, UpstreamErrorClassification, UpstreamHealthFailureMapping,
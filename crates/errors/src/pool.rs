@@ -27,8 +27,8 @@ mod tests {
 
     use super::PoolError;
     use crate::{
-        ProxyError, UpstreamRetryReason, UpstreamRetryability, UpstreamTerminalErrorKind,
-        classify_retryability,
+        ProxyError, RetryErrorConditions, UpstreamRetryReason, UpstreamRetryability,
+        UpstreamTerminalErrorKind, classify_retryability, classify_retryability_for,
     };
 
     async fn connect_send_error() -> hyper_util::client::legacy::Error {
@@ -101,4 +101,26 @@ mod tests {
             UpstreamRetryability::Terminal(UpstreamTerminalErrorKind::PoolSend)
         );
     }
+
+    #[tokio::test]
+    async fn connect_send_errors_are_retryable_only_when_route_opts_in() {
+        let connect_only = RetryErrorConditions {
+            reset_before_headers: false,
+            connect_failure: true,
+        };
+        assert_eq!(
+            classify_retryability_for(
+                &ProxyError::Pool(PoolError::Send(connect_send_error().await)),
+                connect_only,
+            ),
+            UpstreamRetryability::Retryable(UpstreamRetryReason::ConnectFailure)
+        );
+        assert_eq!(
+            classify_retryability_for(
+                &ProxyError::Pool(PoolError::Send(connect_send_error().await)),
+                RetryErrorConditions::default(),
+            ),
+            UpstreamRetryability::Terminal(UpstreamTerminalErrorKind::PoolSend)
+        );
+    }
 }
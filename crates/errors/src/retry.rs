@@ -7,6 +7,43 @@ pub enum UpstreamRetryReason {
     Timeout,
     Transport,
     Pool,
+    /// Connection to the backend was never established.
+    ConnectFailure,
+    /// The backend answered with a status listed in the route's `retry_on`.
+    Status,
+}
+
+/// Failed-attempt errors a route retries. `Default` keeps the historical
+/// behavior: failures before response headers, but not connect failures
+/// surfaced by the pooled client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryErrorConditions {
+    /// Timeouts, transport errors and pool rejections.
+    pub reset_before_headers: bool,
+    /// Connect failures and pool rejections; the request never left the edge.
+    pub connect_failure: bool,
+}
+
+impl Default for RetryErrorConditions {
+    fn default() -> Self {
+        Self {
+            reset_before_headers: true,
+            connect_failure: false,
+        }
+    }
+}
+
+impl RetryErrorConditions {
+    pub fn allows(self, reason: UpstreamRetryReason) -> bool {
+        match reason {
+            UpstreamRetryReason::Timeout | UpstreamRetryReason::Transport => {
+                self.reset_before_headers
+            }
+            UpstreamRetryReason::Pool => self.reset_before_headers || self.connect_failure,
+            UpstreamRetryReason::ConnectFailure => self.connect_failure,
+            UpstreamRetryReason::Status => true,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -31,11 +68,14 @@ pub enum RetryPolicyDenialReason {
     AttemptLimitReached,
     BudgetDenied,
     AlternateBackendUnavailable(AlternateBackendFailureReason),
+    /// The failure is retryable but not listed in the route's `retry_on`.
+    ConditionNotEnabled,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RetryPolicyFacts {
     pub retryability: UpstreamRetryability,
+    pub conditions: RetryErrorConditions,
     pub method_idempotent: bool,
     pub request_body_replayable: bool,
     pub attempt_count: u8,
@@ -60,6 +100,8 @@ pub enum RetryAttemptTelemetryReason {
     Timeout,
     Transport,
     Pool,
+    ConnectFailure,
+    Status,
 }
 
 impl From<UpstreamRetryReason> for RetryAttemptTelemetryReason {
@@ -68,6 +110,8 @@ impl From<UpstreamRetryReason> for RetryAttemptTelemetryReason {
             UpstreamRetryReason::Timeout => Self::Timeout,
             UpstreamRetryReason::Transport => Self::Transport,
             UpstreamRetryReason::Pool => Self::Pool,
+            UpstreamRetryReason::ConnectFailure => Self::ConnectFailure,
+            UpstreamRetryReason::Status => Self::Status,
         }
     }
 }
@@ -80,6 +124,8 @@ pub struct HedgePolicyFacts {
     pub tunnel_request: bool,
     pub alternate_backend_available: bool,
     pub alternate_backend_failure: Option<AlternateBackendFailureReason>,
+    /// False once the route hedged its `max_hedged_percent` share of requests.
+    pub hedge_share_available: bool,
     pub budget_available: bool,
     pub primary_state: HedgePrimaryState,
 }
@@ -99,6 +145,7 @@ pub enum HedgePolicyDenialReason {
     TunnelRequest,
     MethodNotAllowed,
     AlternateBackendUnavailable(AlternateBackendFailureReason),
+    HedgeShareExceeded,
     BudgetDenied,
 }
 
@@ -117,7 +164,10 @@ pub enum HedgeTriggerTelemetryReason {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum HedgeOutcomeTelemetryReason {
     PrimaryWonAfterTrigger,
-    HedgeWon,
+    /// The `attempt`-th hedge, counting from 1, answered first.
+    HedgeWon {
+        attempt: u8,
+    },
 }
 
 pub fn is_idempotent_method(method: &str) -> bool {
@@ -127,6 +177,13 @@ pub fn is_idempotent_method(method: &str) -> bool {
     )
 }
 
+/// Idempotent methods, plus POST and PATCH carrying an idempotency key.
+pub fn is_idempotent_request(method: &str, idempotency_key_present: bool) -> bool {
+    is_idempotent_method(method)
+        || (idempotency_key_present
+            && matches!(method.to_ascii_uppercase().as_str(), "POST" | "PATCH"))
+}
+
 pub fn classify_retryability(err: &ProxyError) -> UpstreamRetryability {
     match err {
         ProxyError::Transport(_) => UpstreamRetryability::Retryable(UpstreamRetryReason::Transport),
@@ -143,13 +200,33 @@ pub fn classify_retryability(err: &ProxyError) -> UpstreamRetryability {
     }
 }
 
+/// Like [`classify_retryability`], but promotes pooled-client connect
+/// failures to retryable when the route retries them.
+pub fn classify_retryability_for(
+    err: &ProxyError,
+    conditions: RetryErrorConditions,
+) -> UpstreamRetryability {
+    match err {
+        ProxyError::Pool(PoolError::Send(send))
+            if conditions.connect_failure && send.is_connect() =>
+        {
+            UpstreamRetryability::Retryable(UpstreamRetryReason::ConnectFailure)
+        }
+        _ => classify_retryability(err),
+    }
+}
+
 pub fn evaluate_retry_policy(input: RetryPolicyFacts) -> RetryPolicyDecision {
     match input.retryability {
         UpstreamRetryability::Terminal(kind) => RetryPolicyDecision::DoNotRetry {
             denial: Some(RetryPolicyDenialReason::TerminalError(kind)),
         },
         UpstreamRetryability::Retryable(reason) => {
-            if !input.method_idempotent {
+            if !input.conditions.allows(reason) {
+                RetryPolicyDecision::DoNotRetry {
+                    denial: Some(RetryPolicyDenialReason::ConditionNotEnabled),
+                }
+            } else if !input.method_idempotent {
                 RetryPolicyDecision::DoNotRetry {
                     denial: Some(RetryPolicyDenialReason::MethodNotIdempotent),
                 }
@@ -221,7 +298,11 @@ pub fn evaluate_hedge_policy(input: HedgePolicyFacts) -> HedgePolicyDecision {
         },
         HedgePrimaryState::InFlightBeforeDelay => HedgePolicyDecision::WaitForPrimary,
         HedgePrimaryState::InFlightAfterDelay => {
-            if !input.budget_available {
+            if !input.hedge_share_available {
+                HedgePolicyDecision::DoNotHedge {
+                    denial: HedgePolicyDenialReason::HedgeShareExceeded,
+                }
+            } else if !input.budget_available {
                 HedgePolicyDecision::DoNotHedge {
                     denial: HedgePolicyDenialReason::BudgetDenied,
                 }
@@ -248,6 +329,7 @@ mod tests {
     fn retry_facts() -> RetryPolicyFacts {
         RetryPolicyFacts {
             retryability: UpstreamRetryability::Retryable(UpstreamRetryReason::Timeout),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: true,
             attempt_count: 0,
@@ -266,6 +348,15 @@ mod tests {
         assert!(!is_idempotent_method("PATCH"));
     }
 
+    #[test]
+    fn idempotency_key_admits_post_and_patch_only() {
+        assert!(is_idempotent_request("post", true));
+        assert!(is_idempotent_request("PATCH", true));
+        assert!(!is_idempotent_request("POST", false));
+        assert!(!is_idempotent_request("CONNECT", true));
+        assert!(is_idempotent_request("PUT", false));
+    }
+
     #[test]
     fn terminal_errors_return_explicit_denial() {
         let mut facts = retry_facts();
@@ -407,6 +498,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn retry_conditions_gate_error_reasons_but_not_statuses() {
+        let mut facts = retry_facts();
+        facts.conditions = RetryErrorConditions {
+            reset_before_headers: false,
+            connect_failure: true,
+        };
+        assert_eq!(
+            evaluate_retry_policy(facts),
+            RetryPolicyDecision::DoNotRetry {
+                denial: Some(RetryPolicyDenialReason::ConditionNotEnabled),
+            }
+        );
+
+        facts.retryability = UpstreamRetryability::Retryable(UpstreamRetryReason::ConnectFailure);
+        assert_eq!(
+            evaluate_retry_policy(facts),
+            RetryPolicyDecision::Retry {
+                reason: UpstreamRetryReason::ConnectFailure,
+            }
+        );
+
+        facts.conditions.connect_failure = false;
+        facts.retryability = UpstreamRetryability::Retryable(UpstreamRetryReason::Status);
+        assert_eq!(
+            evaluate_retry_policy(facts),
+            RetryPolicyDecision::Retry {
+                reason: UpstreamRetryReason::Status,
+            }
+        );
+    }
+
     #[test]
     fn retryable_timeout_allows_retry() {
         assert_eq!(
@@ -425,6 +548,7 @@ mod tests {
             tunnel_request: false,
             alternate_backend_available: true,
             alternate_backend_failure: None,
+            hedge_share_available: true,
             budget_available: true,
             primary_state: HedgePrimaryState::InFlightAfterDelay,
         }
@@ -560,4 +684,17 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn hedge_policy_rejects_when_hedge_share_exceeded_after_delay() {
+        let mut facts = hedge_facts();
+        facts.hedge_share_available = false;
+
+        assert_eq!(
+            evaluate_hedge_policy(facts),
+            HedgePolicyDecision::DoNotHedge {
+                denial: HedgePolicyDenialReason::HedgeShareExceeded,
+            }
+        );
+    }
 }
@@ -1,8 +1,9 @@
 use spooky_errors::{
-    PoolError, ProxyError, RetryPolicyDecision, RetryPolicyDenialReason, RetryPolicyFacts,
-    UpstreamErrorClassification, UpstreamHealthFailureMapping, UpstreamProxyErrorKind,
-    UpstreamRetryReason, UpstreamRetryability, UpstreamTerminalErrorKind, UpstreamTlsReason,
-    classify_retryability, classify_upstream_proxy_error, evaluate_retry_policy, is_retryable,
+    PoolError, ProxyError, RetryErrorConditions, RetryPolicyDecision, RetryPolicyDenialReason,
+    RetryPolicyFacts, UpstreamErrorClassification, UpstreamHealthFailureMapping,
+    UpstreamProxyErrorKind, UpstreamRetryReason, UpstreamRetryability, UpstreamTerminalErrorKind,
+    UpstreamTlsReason, classify_retryability, classify_upstream_proxy_error, evaluate_retry_policy,
+    is_retryable,
 };
 use spooky_lb::alternate_backend::AlternateBackendFailureReason;
 
@@ -53,6 +54,7 @@ fn retry_policy_evaluation_preserves_existing_denial_behavior() {
     assert_eq!(
         evaluate_retry_policy(RetryPolicyFacts {
             retryability: UpstreamRetryability::Terminal(UpstreamTerminalErrorKind::Protocol),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: true,
             attempt_count: 0,
@@ -70,6 +72,7 @@ fn retry_policy_evaluation_preserves_existing_denial_behavior() {
     assert_eq!(
         evaluate_retry_policy(RetryPolicyFacts {
             retryability: UpstreamRetryability::Retryable(UpstreamRetryReason::Transport),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: false,
             attempt_count: 0,
@@ -85,6 +88,7 @@ fn retry_policy_evaluation_preserves_existing_denial_behavior() {
     assert_eq!(
         evaluate_retry_policy(RetryPolicyFacts {
             retryability: UpstreamRetryability::Retryable(UpstreamRetryReason::Pool),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: true,
             attempt_count: 0,
@@ -100,6 +104,7 @@ fn retry_policy_evaluation_preserves_existing_denial_behavior() {
     assert_eq!(
         evaluate_retry_policy(RetryPolicyFacts {
             retryability: UpstreamRetryability::Retryable(UpstreamRetryReason::Timeout),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: true,
             attempt_count: 0,
@@ -116,6 +121,7 @@ fn retry_policy_evaluation_preserves_existing_denial_behavior() {
     assert_eq!(
         evaluate_retry_policy(RetryPolicyFacts {
             retryability: UpstreamRetryability::Retryable(UpstreamRetryReason::Timeout),
+            conditions: RetryErrorConditions::default(),
             method_idempotent: true,
             request_body_replayable: true,
             attempt_count: 0,
@@ -0,0 +1,119 @@
+use crate::{
+    algorithms::consistent_hash::ConsistentHash, backend_pool::BackendPool, balancer::Balancer,
+};
+
+/// Consistent hashing with bounded loads. A key goes to the first backend
+/// clockwise from its ring position whose active requests stay below
+/// `balance_factor` percent of its weighted share of all active requests,
+/// counting the request being placed.
+pub struct BoundedConsistentHash {
+    inner: ConsistentHash,
+    balance_factor: u32,
+}
+
+impl BoundedConsistentHash {
+    pub fn new(replicas: u32, balance_factor: u32) -> Self {
+        Self {
+            inner: ConsistentHash::new(replicas),
+            balance_factor: balance_factor.max(100),
+        }
+    }
+
+    pub fn balance_factor(&self) -> u32 {
+        self.balance_factor
+    }
+
+    pub fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        if pool.is_empty() {
+            return None;
+        }
+
+        self.inner.refresh(pool);
+        Self::walk(&self.inner.ring, key, pool, self.balance_factor, &[])
+    }
+
+    /// Backend [`pick`](Self::pick) would return for `key`, without caching a
+    /// rebuilt ring.
+    pub fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        self.pick_excluding(key, pool, &[])
+    }
+
+    /// Like [`peek`](Self::peek), skipping the backends in `excluded`.
+    pub fn pick_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        if pool.healthy.iter().all(|idx| excluded.contains(idx)) {
+            return None;
+        }
+
+        let ring = self.inner.current_ring(pool);
+        Self::walk(&ring, key, pool, self.balance_factor, excluded)
+    }
+
+    fn walk(
+        ring: &[(u64, usize)],
+        key: &str,
+        pool: &BackendPool,
+        balance_factor: u32,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        let start = ConsistentHash::ring_position(ring, key)?;
+        let total_active = pool
+            .healthy
+            .iter()
+            .map(|&idx| pool.backends[idx].active_requests() as u128)
+            .sum::<u128>();
+        let total_weight = u128::from(pool.healthy_weight_total());
+        let below_cap = |idx: usize| {
+            let backend = &pool.backends[idx];
+            // active / weight < factor% * (total + 1) / total_weight
+            backend.active_requests() as u128 * total_weight * 100
+                < u128::from(balance_factor)
+                    * (total_active + 1)
+                    * u128::from(backend.effective_weight())
+        };
+
+        let mut first_allowed = None;
+        for step in 0..ring.len() {
+            let idx = ring[(start + step) % ring.len()].1;
+            if excluded.contains(&idx) {
+                continue;
+            }
+            if below_cap(idx) {
+                return Some(idx);
+            }
+            first_allowed.get_or_insert(idx);
+        }
+        first_allowed
+    }
+}
+
+impl Balancer for BoundedConsistentHash {
+    fn name(&self) -> &str {
+        "consistent-hash-bounded"
+    }
+
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        BoundedConsistentHash::pick(self, key, pool)
+    }
+
+    fn pick_readonly(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        BoundedConsistentHash::peek(self, key, pool)
+    }
+
+    fn pick_readonly_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        BoundedConsistentHash::pick_excluding(self, key, pool, excluded)
+    }
+
+    fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        BoundedConsistentHash::peek(self, key, pool)
+    }
+}
@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+
 use crate::{
     backend_pool::BackendPool,
-    hash::{expected_ring_entries, hash_backend_replica, hash64},
+    balancer::Balancer,
+    hash::{backend_replicas, expected_ring_entries, hash_backend_replica, hash64},
 };
 
 pub struct ConsistentHash {
@@ -25,44 +28,95 @@ impl ConsistentHash {
             return None;
         }
 
+        self.refresh(pool);
+        Self::lookup(&self.ring, key)
+    }
+
+    /// Backend [`pick`](Self::pick) would return for `key`, without caching a
+    /// rebuilt ring when membership has changed since the last pick.
+    pub fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        if pool.is_empty() {
+            return None;
+        }
+
+        Self::lookup(&self.current_ring(pool), key)
+    }
+
+    /// Rebuilds the cached ring if membership changed since it was built.
+    pub(crate) fn refresh(&mut self, pool: &BackendPool) {
         let epoch = pool.membership_epoch();
         if self.ring_epoch != Some(epoch) {
             self.rebuild_ring(pool);
             self.ring_epoch = Some(epoch);
             self.ring_rebuilds = self.ring_rebuilds.wrapping_add(1);
         }
+    }
+
+    /// The cached ring when it matches `pool`, otherwise a freshly built one.
+    pub(crate) fn current_ring(&self, pool: &BackendPool) -> Cow<'_, [(u64, usize)]> {
+        if self.ring_epoch == Some(pool.membership_epoch()) {
+            return Cow::Borrowed(&self.ring);
+        }
 
-        if self.ring.is_empty() {
+        let mut ring = Vec::new();
+        Self::fill_ring(&mut ring, self.replicas, pool);
+        Cow::Owned(ring)
+    }
+
+    /// Ring entry `key` hashes onto.
+    pub(crate) fn ring_position(ring: &[(u64, usize)], key: &str) -> Option<usize> {
+        if ring.is_empty() {
             return None;
         }
 
         let key_hash = hash64(key.as_bytes());
-        let lookup_idx = match self.ring.binary_search_by(|(hash, _)| hash.cmp(&key_hash)) {
-            Ok(idx) => idx,
-            Err(idx) if idx < self.ring.len() => idx,
-            Err(_) => 0,
-        };
+        Some(
+            match ring.binary_search_by(|(hash, _)| hash.cmp(&key_hash)) {
+                Ok(idx) => idx,
+                Err(idx) if idx < ring.len() => idx,
+                Err(_) => 0,
+            },
+        )
+    }
 
-        Some(self.ring[lookup_idx].1)
+    fn lookup(ring: &[(u64, usize)], key: &str) -> Option<usize> {
+        Self::ring_position(ring, key).map(|position| ring[position].1)
     }
 
     fn rebuild_ring(&mut self, pool: &BackendPool) {
         self.ring.clear();
+        Self::fill_ring(&mut self.ring, self.replicas, pool);
+    }
 
-        let expected = expected_ring_entries(pool, self.replicas);
-        if self.ring.capacity() < expected {
-            self.ring.reserve(expected - self.ring.capacity());
+    fn fill_ring(ring: &mut Vec<(u64, usize)>, replicas: u32, pool: &BackendPool) {
+        let expected = expected_ring_entries(pool, replicas);
+        if ring.capacity() < expected {
+            ring.reserve(expected - ring.capacity());
         }
 
         for &idx in &pool.healthy {
             let backend = &pool.backends[idx];
-            let replicas = self.replicas.saturating_mul(backend.weight());
-            for replica in 0..replicas {
-                self.ring
-                    .push((hash_backend_replica(backend.address(), replica), idx));
+            for replica in 0..backend_replicas(backend, replicas) {
+                ring.push((hash_backend_replica(backend.address(), replica), idx));
             }
         }
 
-        self.ring.sort_unstable();
+        ring.sort_unstable();
+    }
+}
+
+impl Balancer for ConsistentHash {
+    fn name(&self) -> &str {
+        "consistent-hash"
+    }
+
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        ConsistentHash::pick(self, key, pool)
+    }
+
+    // No read-only pick: the ring cache is rebuilt under `&mut`.
+
+    fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        ConsistentHash::peek(self, key, pool)
     }
 }
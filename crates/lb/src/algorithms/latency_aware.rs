@@ -1,4 +1,4 @@
-use crate::backend_pool::BackendPool;
+use crate::{backend_pool::BackendPool, balancer::Balancer};
 
 pub struct LatencyAware;
 
@@ -56,3 +56,21 @@ impl Default for LatencyAware {
         Self::new()
     }
 }
+
+impl Balancer for LatencyAware {
+    fn name(&self) -> &str {
+        "latency-aware"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LatencyAware::pick(self, pool)
+    }
+
+    fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LatencyAware::pick_readonly(self, pool)
+    }
+
+    fn peek(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LatencyAware::pick_readonly(self, pool)
+    }
+}
@@ -1,5 +1,7 @@
-use crate::backend_pool::BackendPool;
+use crate::{backend_pool::BackendPool, balancer::Balancer};
 
+/// Picks the healthy backend with the fewest active requests per unit of
+/// weight; ties go to the lowest index.
 pub struct LeastConnections;
 
 impl LeastConnections {
@@ -12,19 +14,24 @@ impl LeastConnections {
     }
 
     pub fn pick_readonly(&self, pool: &BackendPool) -> Option<usize> {
-        let mut best: Option<(usize, usize)> = None;
+        let mut best: Option<(u128, u128, usize)> = None;
         for &idx in &pool.healthy {
-            let active = pool.backends[idx].active_requests();
+            let backend = &pool.backends[idx];
+            let active = backend.active_requests() as u128;
+            let weight = u128::from(backend.effective_weight());
             match best {
-                Some((best_active, best_idx)) => {
-                    if active < best_active || (active == best_active && idx < best_idx) {
-                        best = Some((active, idx));
+                Some((best_active, best_weight, best_idx)) => {
+                    // Compare active / weight by cross-multiplying.
+                    let load = active * best_weight;
+                    let best_load = best_active * weight;
+                    if load < best_load || (load == best_load && idx < best_idx) {
+                        best = Some((active, weight, idx));
                     }
                 }
-                None => best = Some((active, idx)),
+                None => best = Some((active, weight, idx)),
             }
         }
-        best.map(|(_, idx)| idx)
+        best.map(|(_, _, idx)| idx)
     }
 }
 
@@ -33,3 +40,21 @@ impl Default for LeastConnections {
         Self::new()
     }
 }
+
+impl Balancer for LeastConnections {
+    fn name(&self) -> &str {
+        "least-connections"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LeastConnections::pick(self, pool)
+    }
+
+    fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LeastConnections::pick_readonly(self, pool)
+    }
+
+    fn peek(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        LeastConnections::pick_readonly(self, pool)
+    }
+}
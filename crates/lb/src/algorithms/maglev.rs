@@ -0,0 +1,165 @@
+use crate::{
+    backend_pool::BackendPool,
+    balancer::Balancer,
+    hash::{hash_backend_replica, hash64},
+};
+
+/// Maglev hashing. Each healthy backend claims slots of a prime-sized lookup
+/// table in its own permutation order, taking turns in proportion to its
+/// weight, and a key maps to the slot its hash lands on. Removing a backend
+/// only reassigns that backend's slots plus a small share of the rest.
+pub struct Maglev {
+    pub table_size: u64,
+    pub table: Vec<u32>,
+    pub table_epoch: Option<u64>,
+    pub table_rebuilds: u64,
+}
+
+impl Maglev {
+    pub fn new(table_size: u64) -> Self {
+        Self {
+            table_size: table_size.max(2),
+            table: Vec::new(),
+            table_epoch: None,
+            table_rebuilds: 0,
+        }
+    }
+
+    pub fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        if pool.healthy.is_empty() {
+            return None;
+        }
+
+        let epoch = pool.membership_epoch();
+        if self.table_epoch != Some(epoch) {
+            self.table = Self::build_table(self.table_size, pool);
+            self.table_epoch = Some(epoch);
+            self.table_rebuilds = self.table_rebuilds.wrapping_add(1);
+        }
+
+        Self::lookup(&self.table, key, &[])
+    }
+
+    /// Backend [`pick`](Self::pick) would return for `key`, without caching a
+    /// rebuilt table.
+    pub fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        self.pick_excluding(key, pool, &[])
+    }
+
+    /// Like [`peek`](Self::peek), walking on from the key's slot past the
+    /// backends in `excluded`.
+    pub fn pick_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        if pool.healthy.iter().all(|idx| excluded.contains(idx)) {
+            return None;
+        }
+
+        if self.table_epoch == Some(pool.membership_epoch()) {
+            return Self::lookup(&self.table, key, excluded);
+        }
+        Self::lookup(&Self::build_table(self.table_size, pool), key, excluded)
+    }
+
+    fn lookup(table: &[u32], key: &str, excluded: &[usize]) -> Option<usize> {
+        if table.is_empty() {
+            return None;
+        }
+
+        let start = (hash64(key.as_bytes()) % table.len() as u64) as usize;
+        (0..table.len())
+            .map(|step| table[(start + step) % table.len()] as usize)
+            .find(|idx| !excluded.contains(idx))
+    }
+
+    fn build_table(table_size: u64, pool: &BackendPool) -> Vec<u32> {
+        struct Permutation {
+            index: usize,
+            weight: u64,
+            offset: u64,
+            skip: u64,
+            next: u64,
+            credit: u64,
+        }
+
+        let mut healthy = pool.healthy.clone();
+        healthy.sort_unstable();
+        let mut permutations: Vec<Permutation> = healthy
+            .into_iter()
+            .map(|index| {
+                let backend = &pool.backends[index];
+                Permutation {
+                    index,
+                    weight: backend.effective_weight(),
+                    offset: hash_backend_replica(backend.address(), 0) % table_size,
+                    skip: hash_backend_replica(backend.address(), 1) % (table_size - 1) + 1,
+                    next: 0,
+                    credit: 0,
+                }
+            })
+            .collect();
+        let Some(max_weight) = permutations.iter().map(|p| p.weight).max() else {
+            return Vec::new();
+        };
+
+        let size = table_size as usize;
+        let mut table = vec![u32::MAX; size];
+        let mut filled = 0;
+        while filled < size {
+            for permutation in &mut permutations {
+                // The heaviest backend claims a slot every round, lighter
+                // ones once their weight adds up to the heaviest's.
+                permutation.credit += permutation.weight;
+                if permutation.credit < max_weight {
+                    continue;
+                }
+                permutation.credit -= max_weight;
+
+                loop {
+                    let slot = ((permutation.offset + permutation.next * permutation.skip)
+                        % table_size) as usize;
+                    permutation.next += 1;
+                    if table[slot] == u32::MAX {
+                        table[slot] = permutation.index as u32;
+                        filled += 1;
+                        break;
+                    }
+                }
+                if filled == size {
+                    break;
+                }
+            }
+        }
+        table
+    }
+}
+
+impl Balancer for Maglev {
+    fn name(&self) -> &str {
+        "maglev"
+    }
+
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        Maglev::pick(self, key, pool)
+    }
+
+    fn pick_readonly(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        Maglev::peek(self, key, pool)
+    }
+
+    fn pick_readonly_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        Maglev::pick_excluding(self, key, pool, excluded)
+    }
+
+    fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        Maglev::peek(self, key, pool)
+    }
+}
@@ -1,6 +1,9 @@
+pub mod bounded_consistent_hash;
 pub mod consistent_hash;
 pub mod latency_aware;
 pub mod least_connections;
+pub mod maglev;
+pub mod p2c;
 pub mod random;
 pub mod round_robin;
 pub mod sticky_cid;
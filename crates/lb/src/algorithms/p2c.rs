@@ -0,0 +1,173 @@
+use std::cell::RefCell;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use spooky_config::config::{MAX_P2C_CHOICES, P2cMetric};
+
+use crate::{backend_pool::BackendPool, balancer::Balancer};
+
+thread_local! {
+    static LB_P2C_RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Power-of-two-choices: samples `choices` distinct healthy backends and
+/// picks the least loaded of them, so a pick costs the same at any pool size.
+pub struct PowerOfTwoChoices {
+    choices: usize,
+    metric: P2cMetric,
+    weighted: bool,
+}
+
+impl PowerOfTwoChoices {
+    pub fn new(choices: usize, metric: P2cMetric, weighted: bool) -> Self {
+        Self {
+            choices: choices.clamp(2, MAX_P2C_CHOICES as usize),
+            metric,
+            weighted,
+        }
+    }
+
+    pub fn pick(&mut self, pool: &BackendPool) -> Option<usize> {
+        self.pick_readonly(pool)
+    }
+
+    pub fn pick_readonly(&self, pool: &BackendPool) -> Option<usize> {
+        self.pick_excluding(pool, &[])
+    }
+
+    /// Like [`pick_readonly`](Self::pick_readonly), sampling only backends
+    /// not in `excluded`.
+    pub fn pick_excluding(&self, pool: &BackendPool, excluded: &[usize]) -> Option<usize> {
+        let mut sampled = [0usize; MAX_P2C_CHOICES as usize];
+        let count = self.sample(pool, excluded, &mut sampled);
+        sampled[..count].iter().copied().reduce(|best, idx| {
+            if self.less_loaded(pool, idx, best) {
+                idx
+            } else {
+                best
+            }
+        })
+    }
+
+    /// Fills `out` with distinct eligible backends. Pools no larger than
+    /// `choices` are taken whole.
+    fn sample(&self, pool: &BackendPool, excluded: &[usize], out: &mut [usize]) -> usize {
+        let excluded_healthy = excluded
+            .iter()
+            .enumerate()
+            .filter(|&(pos, idx)| pool.is_healthy_index(*idx) && !excluded[..pos].contains(idx))
+            .count();
+        let eligible = pool.healthy.len() - excluded_healthy;
+        if eligible <= self.choices {
+            let mut count = 0;
+            for &idx in pool.healthy.iter().filter(|idx| !excluded.contains(idx)) {
+                out[count] = idx;
+                count += 1;
+            }
+            return count;
+        }
+
+        let weighted = self.weighted && !pool.has_uniform_weights();
+        let total = pool.healthy_weight_total();
+        // Rejection sampling; heavily skewed weights may repeat a backend, so
+        // stop after a bounded number of draws.
+        let max_draws = self.choices * 8;
+        let count = LB_P2C_RNG.with(|state| {
+            let mut rng = state.borrow_mut();
+            let mut count = 0;
+            for _ in 0..max_draws {
+                let idx = if weighted {
+                    match pool.healthy_at_weight_offset(rng.gen_range(0..total)) {
+                        Some(idx) => idx,
+                        None => continue,
+                    }
+                } else {
+                    pool.healthy[rng.gen_range(0..pool.healthy.len())]
+                };
+                if excluded.contains(&idx) || out[..count].contains(&idx) {
+                    continue;
+                }
+                out[count] = idx;
+                count += 1;
+                if count == self.choices {
+                    break;
+                }
+            }
+            count
+        });
+        if count > 0 {
+            return count;
+        }
+        // Only excluded backends were drawn.
+        match pool.healthy.iter().find(|idx| !excluded.contains(idx)) {
+            Some(&idx) => {
+                out[0] = idx;
+                1
+            }
+            None => 0,
+        }
+    }
+
+    /// Whether `idx` should win over `best`. Ties keep `best`, the earlier
+    /// draw, so idle pools do not herd onto low indices.
+    fn less_loaded(&self, pool: &BackendPool, idx: usize, best: usize) -> bool {
+        let candidate = &pool.backends[idx];
+        let current = &pool.backends[best];
+        match self.metric {
+            P2cMetric::LeastRequest => {
+                let (active, best_active) = (
+                    candidate.active_requests() as u128,
+                    current.active_requests() as u128,
+                );
+                let (weight, best_weight) = if self.weighted {
+                    (
+                        u128::from(candidate.effective_weight()),
+                        u128::from(current.effective_weight()),
+                    )
+                } else {
+                    (1, 1)
+                };
+                // Compare active / weight by cross-multiplying.
+                let load = active * best_weight;
+                let best_load = best_active * weight;
+                load < best_load
+            }
+            P2cMetric::Latency => {
+                // Unsampled backends go first so they get a latency sample.
+                match (candidate.ewma_latency_ms(), current.ewma_latency_ms()) {
+                    (None, Some(_)) => true,
+                    (Some(_), None) => false,
+                    (ewma, best_ewma) => {
+                        let active = candidate.active_requests();
+                        let best_active = current.active_requests();
+                        let score = ewma.unwrap_or(0.0) + active as f64 * 10.0;
+                        let best_score = best_ewma.unwrap_or(0.0) + best_active as f64 * 10.0;
+                        score < best_score || (score == best_score && active < best_active)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Balancer for PowerOfTwoChoices {
+    fn name(&self) -> &str {
+        "p2c"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        PowerOfTwoChoices::pick(self, pool)
+    }
+
+    fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        PowerOfTwoChoices::pick_readonly(self, pool)
+    }
+
+    fn pick_readonly_excluding(
+        &self,
+        _key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        PowerOfTwoChoices::pick_excluding(self, pool, excluded)
+    }
+}
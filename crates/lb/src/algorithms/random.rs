@@ -2,12 +2,13 @@ use std::cell::RefCell;
 
 use rand::{Rng, SeedableRng, rngs::StdRng};
 
-use crate::backend_pool::BackendPool;
+use crate::{backend_pool::BackendPool, balancer::Balancer};
 
 thread_local! {
     static LB_RANDOM_RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
 }
 
+/// Picks a healthy backend with probability proportional to its weight.
 pub struct Random;
 
 impl Random {
@@ -24,11 +25,17 @@ impl Random {
             return None;
         }
 
-        let idx = LB_RANDOM_RNG.with(|state| {
-            let mut rng = state.borrow_mut();
-            rng.gen_range(0..pool.healthy.len())
-        });
-        Some(pool.healthy[idx])
+        if pool.has_uniform_weights() {
+            let idx = LB_RANDOM_RNG.with(|state| {
+                let mut rng = state.borrow_mut();
+                rng.gen_range(0..pool.healthy.len())
+            });
+            return Some(pool.healthy[idx]);
+        }
+
+        let total = pool.healthy_weight_total();
+        let offset = LB_RANDOM_RNG.with(|state| state.borrow_mut().gen_range(0..total));
+        pool.healthy_at_weight_offset(offset)
     }
 }
 
@@ -37,3 +44,17 @@ impl Default for Random {
         Self::new()
     }
 }
+
+impl Balancer for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        Random::pick(self, pool)
+    }
+
+    fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        Random::pick_readonly(self, pool)
+    }
+}
@@ -1,10 +1,22 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{
+    Mutex, PoisonError,
+    atomic::{AtomicUsize, Ordering},
+};
 
-use crate::backend_pool::BackendPool;
+use crate::{backend_pool::BackendPool, balancer::Balancer};
 
+/// Smooth weighted round-robin. Each pick adds every healthy backend's weight
+/// to its running score and takes the highest score, lowest index first,
+/// which then pays back the total weight. Equal weights reduce to plain
+/// rotation over the healthy list.
 pub struct RoundRobin {
     next: usize,
     next_read: AtomicUsize,
+    current_weights: Vec<i64>,
+    /// Scores of read-only picks, kept apart from `current_weights` so
+    /// retries and hedges follow the same smooth order without shifting
+    /// the primary rotation.
+    read_weights: Mutex<Vec<i64>>,
 }
 
 impl RoundRobin {
@@ -12,6 +24,8 @@ impl RoundRobin {
         Self {
             next: 0,
             next_read: AtomicUsize::new(0),
+            current_weights: Vec::new(),
+            read_weights: Mutex::new(Vec::new()),
         }
     }
 
@@ -20,20 +34,78 @@ impl RoundRobin {
             return None;
         }
 
-        let idx = pool.healthy[self.next % pool.healthy.len()];
-        self.next = self.next.wrapping_add(1);
-        Some(idx)
+        if pool.has_uniform_weights() {
+            let idx = pool.healthy[self.next % pool.healthy.len()];
+            self.next = self.next.wrapping_add(1);
+            return Some(idx);
+        }
+
+        Self::smooth_pick(pool, &mut self.current_weights)
     }
 
+    /// Backend the next [`pick`](Self::pick) would return, leaving the cursor
+    /// where it is.
+    pub fn peek(&self, pool: &BackendPool) -> Option<usize> {
+        if pool.healthy.is_empty() {
+            return None;
+        }
+
+        if pool.has_uniform_weights() {
+            return Some(pool.healthy[self.next % pool.healthy.len()]);
+        }
+
+        Self::highest_current(pool, |idx| {
+            self.current_weights.get(idx).copied().unwrap_or(0)
+                + pool.backends[idx].effective_weight() as i64
+        })
+    }
+
+    /// Pick from a rotation shared by read-only callers, in the same smooth
+    /// weighted order as [`pick`](Self::pick).
     pub fn pick_readonly(&self, pool: &BackendPool) -> Option<usize> {
         if pool.healthy.is_empty() {
             return None;
         }
 
-        let next = self.next_read.fetch_add(1, Ordering::Relaxed);
-        let idx = pool.healthy[next % pool.healthy.len()];
+        if pool.has_uniform_weights() {
+            let next = self.next_read.fetch_add(1, Ordering::Relaxed);
+            return Some(pool.healthy[next % pool.healthy.len()]);
+        }
+
+        let mut read_weights = self
+            .read_weights
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Self::smooth_pick(pool, &mut read_weights)
+    }
+
+    fn smooth_pick(pool: &BackendPool, current_weights: &mut Vec<i64>) -> Option<usize> {
+        if current_weights.len() != pool.len() {
+            current_weights.resize(pool.len(), 0);
+        }
+        let mut total = 0i64;
+        for &idx in &pool.healthy {
+            let weight = pool.backends[idx].effective_weight() as i64;
+            current_weights[idx] += weight;
+            total += weight;
+        }
+        let idx = Self::highest_current(pool, |idx| current_weights[idx])?;
+        current_weights[idx] -= total;
         Some(idx)
     }
+
+    fn highest_current(pool: &BackendPool, current: impl Fn(usize) -> i64) -> Option<usize> {
+        let mut best: Option<(i64, usize)> = None;
+        for &idx in &pool.healthy {
+            let score = current(idx);
+            match best {
+                Some((best_score, best_idx))
+                    if score < best_score || (score == best_score && idx > best_idx) => {}
+                _ => best = Some((score, idx)),
+            }
+        }
+        best.map(|(_, idx)| idx)
+    }
 }
 
 impl Default for RoundRobin {
@@ -41,3 +113,21 @@ impl Default for RoundRobin {
         Self::new()
     }
 }
+
+impl Balancer for RoundRobin {
+    fn name(&self) -> &str {
+        "round-robin"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        RoundRobin::pick(self, pool)
+    }
+
+    fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        RoundRobin::pick_readonly(self, pool)
+    }
+
+    fn peek(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        RoundRobin::peek(self, pool)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{algorithms::consistent_hash::ConsistentHash, backend_pool::BackendPool};
+use crate::{
+    algorithms::consistent_hash::ConsistentHash, backend_pool::BackendPool, balancer::Balancer,
+};
 
 pub struct StickyCid {
     inner: ConsistentHash,
@@ -17,4 +19,25 @@ impl StickyCid {
         }
         self.inner.pick(key, pool)
     }
+
+    pub fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        if key.is_empty() {
+            return pool.healthy.first().copied();
+        }
+        self.inner.peek(key, pool)
+    }
+}
+
+impl Balancer for StickyCid {
+    fn name(&self) -> &str {
+        "sticky-cid"
+    }
+
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        StickyCid::pick(self, key, pool)
+    }
+
+    fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        StickyCid::peek(self, key, pool)
+    }
 }
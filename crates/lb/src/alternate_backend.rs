@@ -46,9 +46,8 @@ pub fn choose_alternate_backend(
     let policy = pool.alternate_backend_policy();
 
     if policy.readonly_lb_pick {
-        let readonly_candidate = pool
-            .pick_readonly(lb_key.unwrap_or_default())
-            .filter(|index| !is_excluded(*index, excluded_indices));
+        let readonly_candidate =
+            pool.pick_readonly_excluding(lb_key.unwrap_or_default(), excluded_indices);
         if let Some(index) = readonly_candidate {
             return AlternateBackendDecision::Select(AlternateBackendChoice {
                 index,
@@ -96,9 +95,19 @@ mod tests {
             load_balancing: LoadBalancing {
                 lb_type: lb_type.to_string(),
                 key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
             },
             auth: Default::default(),
             host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
             forwarded_headers: Default::default(),
             route: RouteMatch::default(),
             backends: backends
@@ -116,6 +125,8 @@ mod tests {
                         success_threshold: 1,
                         cooldown_ms: 1000,
                     }),
+                    priority: 0,
+                    zone: None,
                 })
                 .collect(),
         }
@@ -138,6 +149,7 @@ mod tests {
             },
             listeners: Vec::new(),
             upstream: upstreams,
+            routes: Vec::new(),
             load_balancing: None,
             upstream_tls: Default::default(),
             log: Default::default(),
@@ -145,6 +157,7 @@ mod tests {
             observability: Default::default(),
             resilience: Default::default(),
             security: Default::default(),
+            locality: Default::default(),
         })
         .expect("runtime config")
         .upstreams
@@ -206,6 +219,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hash_strategies_pick_next_backend_for_key_past_excluded_ones() {
+        for lb_type in ["maglev", "consistent-hash-bounded"] {
+            let mut pool = UpstreamPool::from_runtime_upstream(&runtime_upstream(upstream(
+                lb_type,
+                &["http://a", "http://b", "http://c"],
+            )))
+            .expect("pool");
+            let primary = pool.pick("user:42").expect("primary");
+
+            let AlternateBackendDecision::Select(choice) =
+                choose_alternate_backend(&pool, &[primary], Some("user:42"))
+            else {
+                panic!("{lb_type} found no alternate");
+            };
+            assert_ne!(choice.index, primary);
+            assert_eq!(
+                choice.mode,
+                AlternateBackendSelectionMode::LoadBalancerReadonly
+            );
+        }
+    }
+
     #[test]
     fn reports_when_only_excluded_backends_are_healthy() {
         let pool = UpstreamPool::from_runtime_upstream(&runtime_upstream(upstream(
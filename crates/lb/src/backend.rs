@@ -8,36 +8,107 @@ use std::{
 
 use spooky_config::config::{Backend, HealthCheck};
 
-use crate::health::HealthFailureReason;
+use crate::health::{BackendAdminState, HealthFailureReason};
+
+/// Fixed-point `1.0` for [`BackendState`]'s tier scale.
+pub(crate) const TIER_SCALE_ONE: u64 = 1 << 20;
 
 #[derive(Clone)]
 pub struct BackendState {
+    pub id: String,
     pub address: String,
     pub weight: u32,
+    pub priority: u32,
+    pub zone: Option<String>,
     pub health_check: Option<HealthCheck>,
     pub consecutive_failures: u32,
     health_state: HealthState,
+    admin_state: BackendAdminState,
+    // Taken out of the upstream at runtime; the slot stays so indices held
+    // by in-flight requests keep pointing at it, until a new backend reuses
+    // it once none are left.
+    removed: bool,
+    // Bumped whenever the spec changes, so work scheduled for the old one
+    // (health checks) can tell it is stale.
+    revision: u64,
     pub active_requests: Arc<AtomicUsize>,
     pub ewma_latency_ms: Option<f64>,
+    // Share of `weight` in effect, in percent; below 100 during slow start.
+    weight_percent: u32,
+    // Factor over `TIER_SCALE_ONE` that makes a tier's members carry the
+    // tier's share while traffic spans several priority or locality tiers.
+    tier_scale: u64,
 }
 
 impl BackendState {
     pub fn new(backend: &Backend) -> Self {
         Self {
+            id: backend.id.clone(),
             address: backend.address.clone(),
             weight: backend.weight.max(1),
+            priority: backend.priority,
+            zone: backend.zone.clone(),
             health_check: backend.health_check.clone(),
             consecutive_failures: 0,
             health_state: HealthState::Healthy,
+            admin_state: BackendAdminState::Enabled,
+            removed: false,
+            revision: 0,
             active_requests: Arc::new(AtomicUsize::new(0)),
             ewma_latency_ms: None,
+            weight_percent: 100,
+            tier_scale: TIER_SCALE_ONE,
         }
     }
 
+    /// Passes its health checks and is not drained, disabled or removed.
     pub fn is_healthy(&self) -> bool {
+        !self.removed && self.admin_state.is_enabled() && self.passes_health_checks()
+    }
+
+    /// Health-check verdict alone, ignoring the admin state.
+    pub fn passes_health_checks(&self) -> bool {
         matches!(self.health_state, HealthState::Healthy)
     }
 
+    pub fn admin_state(&self) -> BackendAdminState {
+        self.admin_state
+    }
+
+    pub(crate) fn set_admin_state(&mut self, state: BackendAdminState) {
+        self.admin_state = state;
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Take on the weight, priority, zone and health check of `backend`.
+    /// Identity and address stay as they are.
+    pub(crate) fn update_spec(&mut self, backend: &Backend) {
+        self.weight = backend.weight.max(1);
+        self.priority = backend.priority;
+        self.zone = backend.zone.clone();
+        self.health_check = backend.health_check.clone();
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// Take over the slot `previous` was removed from, with a revision past
+    /// its last so work scheduled for it stays stale.
+    pub(crate) fn succeed(&mut self, previous: &BackendState) {
+        self.revision = previous.revision.wrapping_add(1);
+    }
+
+    pub(crate) fn remove(&mut self) {
+        self.removed = true;
+        self.admin_state = BackendAdminState::Disabled;
+        self.revision = self.revision.wrapping_add(1);
+    }
+
     /// Returns true when an active health-check loop is running for this backend.
     /// When active checks are present, only the health-check loop should drive
     /// consecutive_failures — request-path failures should not contribute.
@@ -45,6 +116,10 @@ impl BackendState {
         self.health_check.as_ref().is_some_and(|hc| hc.interval > 0)
     }
 
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
     pub fn address(&self) -> &str {
         &self.address
     }
@@ -57,6 +132,44 @@ impl BackendState {
         self.weight
     }
 
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    pub fn zone(&self) -> Option<&str> {
+        self.zone.as_deref()
+    }
+
+    /// Weight in hundredths after any slow-start ramp, so a ramping backend
+    /// can carry a fraction of one weight unit, and scaled to its tier's share
+    /// when traffic overflows across priority tiers. Weighted strategies
+    /// compare these rather than [`weight`](Self::weight).
+    pub fn effective_weight(&self) -> u64 {
+        let ramped = self.ramped_weight();
+        if self.tier_scale == TIER_SCALE_ONE {
+            return ramped;
+        }
+        let scaled = u128::from(ramped) * u128::from(self.tier_scale) / u128::from(TIER_SCALE_ONE);
+        u64::try_from(scaled).unwrap_or(u64::MAX).max(1)
+    }
+
+    /// [`effective_weight`](Self::effective_weight) before tier scaling.
+    pub(crate) fn ramped_weight(&self) -> u64 {
+        u64::from(self.weight) * u64::from(self.weight_percent)
+    }
+
+    pub fn weight_percent(&self) -> u32 {
+        self.weight_percent
+    }
+
+    pub(crate) fn set_weight_percent(&mut self, percent: u32) {
+        self.weight_percent = percent.clamp(1, 100);
+    }
+
+    pub(crate) fn set_tier_scale(&mut self, scale: u64) {
+        self.tier_scale = scale;
+    }
+
     pub fn active_requests(&self) -> usize {
         self.active_requests.load(Ordering::Relaxed)
     }
@@ -117,6 +230,17 @@ impl BackendState {
         Some(HealthTransition::BecameUnhealthy)
     }
 
+    /// Take this backend out of rotation until `until`, regardless of its
+    /// failure count.
+    pub fn eject(&mut self, until: Instant, reason: HealthFailureReason) {
+        self.consecutive_failures = 0;
+        self.health_state = HealthState::Unhealthy {
+            until,
+            successes: 0,
+            reason,
+        };
+    }
+
     /// Cooldown expiry, if this backend is currently unhealthy.
     pub fn cooldown_until(&self) -> Option<Instant> {
         if let HealthState::Unhealthy { until, .. } = self.health_state {
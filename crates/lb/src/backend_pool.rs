@@ -3,21 +3,65 @@ use std::{
     time::{Duration, Instant},
 };
 
-use spooky_config::config::HealthCheck;
+use spooky_config::{
+    config::{Backend, HealthCheck},
+    runtime::RuntimeSlowStart,
+};
 
 use crate::{
-    backend::{BackendState, HealthTransition},
-    health::HealthFailureReason,
+    backend::{BackendState, HealthTransition, TIER_SCALE_ONE},
+    health::{BackendAdminState, HealthFailureReason},
+    slow_start::{ramp_percent, ramp_tick},
 };
 
+/// Healthy share of its capacity at which a priority tier takes its full
+/// share of traffic: 100 / 140, roughly 72%.
+const OVERPROVISIONING_PERCENT: u64 = 140;
+
+/// Share of the in-flight limit local backends must drop below before a
+/// saturated zone takes its traffic back.
+const ZONE_RECOVERY_PERCENT: u64 = 80;
+
+/// Health of the backends sharing one `priority`, split into local and
+/// other-zone backends under zone-aware balancing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TierHealth {
+    pub priority: u32,
+    /// Backends outside the instance's zone.
+    pub remote: bool,
+    pub total_backends: usize,
+    pub healthy_backends: usize,
+    /// Share of traffic the tier takes, in percent.
+    pub load_percent: u32,
+}
+
 pub struct BackendPool {
     pub backends: Vec<BackendState>,
+    // Backends in rotation, the set strategies pick from: the healthy members
+    // of the tiers taking traffic, or every backend while panicking.
     pub healthy: Vec<usize>,
     pub healthy_pos: Vec<Option<usize>>,
     pub membership_epoch: u64,
     // Earliest cooldown expiry among passively-ejected backends (no active
     // health check), driving time-based re-admission. `None` when none pending.
     pub earliest_readmit: Option<Instant>,
+    uniform_weights: bool,
+    // Running effective-weight totals over `healthy`, for weighted picks by
+    // offset.
+    healthy_weight_prefix: Vec<u64>,
+    slow_start: Option<RuntimeSlowStart>,
+    // Backends below full weight and when their ramp began.
+    ramping: Vec<(usize, Instant)>,
+    next_ramp_step: Option<Instant>,
+    // Distinct (priority, other zone) tier keys, local first within a
+    // priority.
+    tiers: Vec<(u32, bool)>,
+    panic_threshold_percent: u32,
+    panic: bool,
+    local_zone: Option<String>,
+    zone_inflight_limit: Option<u32>,
+    // Local backends are out of in-flight headroom; zones share the tiers.
+    zone_saturated: bool,
 }
 
 impl BackendPool {
@@ -32,15 +76,113 @@ impl BackendPool {
             }
         }
 
-        Self {
+        let mut pool = Self {
             backends,
             healthy,
             healthy_pos,
             membership_epoch: 0,
             earliest_readmit: None,
+            uniform_weights: true,
+            healthy_weight_prefix: Vec::new(),
+            slow_start: None,
+            ramping: Vec::new(),
+            next_ramp_step: None,
+            tiers: Vec::new(),
+            panic_threshold_percent: 0,
+            panic: false,
+            local_zone: None,
+            zone_inflight_limit: None,
+            zone_saturated: false,
+        };
+        pool.rebuild_tiers();
+        pool.refresh_weights();
+        pool
+    }
+
+    /// Prefer backends in `zone` within each priority tier; other zones take
+    /// the overflow when the local ones lack healthy capacity or, with
+    /// `max_inflight_per_backend`, in-flight headroom.
+    pub fn with_locality(mut self, zone: &str, max_inflight_per_backend: Option<u32>) -> Self {
+        self.local_zone = Some(zone.to_string());
+        self.zone_inflight_limit = max_inflight_per_backend;
+        self.rebuild_tiers();
+        self.refresh_weights();
+        self
+    }
+
+    /// Put every backend in rotation, healthy or not, while less than
+    /// `percent` of the upstream is healthy; `0` never panics.
+    pub fn with_panic_threshold(mut self, percent: u32) -> Self {
+        self.panic_threshold_percent = percent.min(100);
+        self.refresh_weights();
+        self
+    }
+
+    /// Ramp backends that become healthy from now on over `slow_start`.
+    /// Backends healthy at construction start at full weight.
+    pub fn with_slow_start(mut self, slow_start: Option<RuntimeSlowStart>) -> Self {
+        self.slow_start = slow_start;
+        self
+    }
+
+    /// Restart the slow-start ramp of a healthy backend, e.g. after DNS
+    /// refresh added addresses behind it. Returns false without a slow-start
+    /// policy or when the backend is out of rotation.
+    pub fn start_slow_start(&mut self, index: usize, now: Instant) -> bool {
+        if self.slow_start.is_none() || !self.is_backend_healthy(index) {
+            return false;
+        }
+
+        self.begin_ramp(index, now);
+        self.refresh_weights();
+        self.membership_epoch = self.membership_epoch.wrapping_add(1);
+        true
+    }
+
+    /// True while any backend is below full weight. Clock-free, like
+    /// [`readmit_due`](Self::readmit_due).
+    pub fn slow_start_active(&self) -> bool {
+        !self.ramping.is_empty()
+    }
+
+    /// Move ramping backends to their current weight. Reads the clock only
+    /// while a ramp is in progress.
+    pub fn advance_slow_start(&mut self) {
+        if !self.ramping.is_empty() {
+            self.advance_slow_start_at(Instant::now());
+        }
+    }
+
+    /// Core of [`advance_slow_start`](Self::advance_slow_start) with an
+    /// injectable clock. Weight changes rebuild the weight prefix and bump the
+    /// membership epoch so hash tables pick up the new shares.
+    pub fn advance_slow_start_at(&mut self, now: Instant) {
+        let Some(policy) = self.slow_start else {
+            return;
+        };
+        if self.ramping.is_empty() || self.next_ramp_step.is_some_and(|next| now < next) {
+            return;
+        }
+
+        let mut changed = false;
+        let backends = &mut self.backends;
+        self.ramping.retain(|&(index, started)| {
+            let percent = ramp_percent(&policy, now.saturating_duration_since(started));
+            if backends[index].weight_percent() != percent {
+                backends[index].set_weight_percent(percent);
+                changed = true;
+            }
+            percent < 100
+        });
+        self.next_ramp_step = (!self.ramping.is_empty()).then(|| now + ramp_tick(&policy));
+
+        if changed {
+            self.refresh_weights();
+            self.membership_epoch = self.membership_epoch.wrapping_add(1);
         }
     }
 
+    /// Backend slots, removed ones included; indices run up to this.
     pub fn len(&self) -> usize {
         self.backends.len()
     }
@@ -49,6 +191,80 @@ impl BackendPool {
         self.backends.is_empty()
     }
 
+    /// Backends still part of the upstream.
+    pub fn member_count(&self) -> usize {
+        self.backends.iter().filter(|b| !b.is_removed()).count()
+    }
+
+    /// Whether `index` holds a backend that has not been removed.
+    pub fn is_member(&self, index: usize) -> bool {
+        self.backends.get(index).is_some_and(|b| !b.is_removed())
+    }
+
+    /// Add a backend without moving the indices of the others. It takes the
+    /// slot of a removed backend no request still holds, or one after the
+    /// existing ones. It joins the rotation at once when healthy, ramping up
+    /// under slow start. Returns its index.
+    pub fn add_backend(&mut self, mut backend: BackendState) -> usize {
+        let reusable = self
+            .backends
+            .iter()
+            .position(|b| b.is_removed() && b.active_requests() == 0);
+        let index = match reusable {
+            Some(index) => {
+                backend.succeed(&self.backends[index]);
+                self.backends[index] = backend;
+                index
+            }
+            None => {
+                self.backends.push(backend);
+                self.healthy_pos.push(None);
+                self.backends.len() - 1
+            }
+        };
+        if self.backends[index].is_healthy() {
+            self.begin_ramp(index, Instant::now());
+        }
+        self.rebuild_membership();
+        index
+    }
+
+    /// Apply a new weight, priority, zone and health check to the backend at
+    /// `index`. Returns false for removed or unknown backends.
+    pub fn update_backend(&mut self, index: usize, spec: &Backend) -> bool {
+        if !self.is_member(index) {
+            return false;
+        }
+
+        let backend = &mut self.backends[index];
+        backend.update_spec(spec);
+        // Without an active check left to recover it, an ejected backend
+        // falls back to time-based re-admission.
+        if !backend.has_active_health_check()
+            && let Some(until) = backend.cooldown_until()
+        {
+            self.earliest_readmit = Some(self.earliest_readmit.map_or(until, |e| e.min(until)));
+        }
+        self.rebuild_membership();
+        true
+    }
+
+    /// Take the backend at `index` out of the upstream. Its slot stays, out
+    /// of rotation and out of every count, so requests still holding the
+    /// index finish normally; once they have, a later
+    /// [`add_backend`](Self::add_backend) may reuse it. Returns false when
+    /// already removed.
+    pub fn remove_backend(&mut self, index: usize) -> bool {
+        if !self.is_member(index) {
+            return false;
+        }
+
+        self.backends[index].remove();
+        self.end_ramp(index);
+        self.rebuild_membership();
+        true
+    }
+
     pub fn address(&self, index: usize) -> Option<&str> {
         self.backends.get(index).map(|b| b.address())
     }
@@ -68,9 +284,11 @@ impl BackendPool {
 
         if was_healthy != is_healthy {
             if is_healthy {
-                debug_assert!(self.mark_healthy(index));
+                let added = self.mark_healthy(index);
+                debug_assert!(added);
             } else {
-                debug_assert!(self.mark_unhealthy(index));
+                let removed = self.mark_unhealthy(index);
+                debug_assert!(removed);
             }
             self.membership_epoch = self.membership_epoch.wrapping_add(1);
         }
@@ -116,9 +334,11 @@ impl BackendPool {
 
         if was_healthy != is_healthy {
             if is_healthy {
-                debug_assert!(self.mark_healthy(index));
+                let added = self.mark_healthy(index);
+                debug_assert!(added);
             } else {
-                debug_assert!(self.mark_unhealthy(index));
+                let removed = self.mark_unhealthy(index);
+                debug_assert!(removed);
                 // Passive ejections have no active loop to recover them; record
                 // the cooldown so `reconcile_readmit` can re-admit on expiry.
                 if !self.backends[index].has_active_health_check()
@@ -134,6 +354,25 @@ impl BackendPool {
         transition
     }
 
+    /// Eject a healthy backend until `until`. Backends without an active health
+    /// check are re-admitted by [`reconcile_readmit`](Self::reconcile_readmit);
+    /// the others once their checks pass again. Returns false when the backend
+    /// was already out of rotation.
+    pub fn eject(&mut self, index: usize, until: Instant, reason: HealthFailureReason) -> bool {
+        if !self.is_backend_healthy(index) {
+            return false;
+        }
+
+        self.backends[index].eject(until, reason);
+        let removed = self.mark_unhealthy(index);
+        debug_assert!(removed);
+        if !self.backends[index].has_active_health_check() {
+            self.earliest_readmit = Some(self.earliest_readmit.map_or(until, |e| e.min(until)));
+        }
+        self.membership_epoch = self.membership_epoch.wrapping_add(1);
+        true
+    }
+
     /// True when any backend is passively ejected and pending re-admission.
     /// Clock-free so the read-locked hot path pays only a branch (no syscall):
     /// while something is pending, callers take the write-locked slow path where
@@ -166,8 +405,11 @@ impl BackendPool {
                 continue;
             }
             if self.backends[index].readmit_if_expired(now) {
-                debug_assert!(self.mark_healthy(index));
-                self.membership_epoch = self.membership_epoch.wrapping_add(1);
+                if self.backends[index].is_healthy() {
+                    let added = self.mark_healthy(index);
+                    debug_assert!(added);
+                    self.membership_epoch = self.membership_epoch.wrapping_add(1);
+                }
             } else if let Some(until) = self.backends[index].cooldown_until() {
                 next = Some(next.map_or(until, |e| e.min(until)));
             }
@@ -175,6 +417,42 @@ impl BackendPool {
         self.earliest_readmit = next;
     }
 
+    /// Drain, disable or re-enable the backend at `index`. Anything but
+    /// `Enabled` takes it out of rotation at once, even while panicking;
+    /// re-enabling a healthy backend ramps it up under slow start. Returns
+    /// whether the state changed.
+    pub fn set_admin_state(&mut self, index: usize, state: BackendAdminState) -> bool {
+        let Some(backend) = self.backends.get_mut(index).filter(|b| !b.is_removed()) else {
+            return false;
+        };
+        if backend.admin_state() == state {
+            return false;
+        }
+
+        let was_healthy = backend.is_healthy();
+        backend.set_admin_state(state);
+        let is_healthy = backend.is_healthy();
+        if was_healthy != is_healthy {
+            if is_healthy {
+                let added = self.mark_healthy(index);
+                debug_assert!(added);
+            } else {
+                let removed = self.mark_unhealthy(index);
+                debug_assert!(removed);
+            }
+        } else if self.tiered() {
+            // Panic mode rotates unhealthy backends too, and drained ones
+            // leave the tier capacity.
+            self.rebuild_rotation();
+        }
+        self.membership_epoch = self.membership_epoch.wrapping_add(1);
+        true
+    }
+
+    pub fn admin_state(&self, index: usize) -> Option<BackendAdminState> {
+        self.backends.get(index).map(BackendState::admin_state)
+    }
+
     pub fn health_check(&self, index: usize) -> Option<HealthCheck> {
         self.backends
             .get(index)
@@ -193,8 +471,30 @@ impl BackendPool {
         self.healthy.iter().copied()
     }
 
+    /// True when every backend has the same effective weight, so weighted
+    /// strategies can pick uniformly.
+    pub fn has_uniform_weights(&self) -> bool {
+        self.uniform_weights
+    }
+
+    pub fn healthy_weight_total(&self) -> u64 {
+        self.healthy_weight_prefix.last().copied().unwrap_or(0)
+    }
+
+    /// Healthy backend covering `offset` when the healthy backends are laid
+    /// out back to back, each spanning its effective weight.
+    pub fn healthy_at_weight_offset(&self, offset: u64) -> Option<usize> {
+        let pos = self
+            .healthy_weight_prefix
+            .partition_point(|&end| end <= offset);
+        self.healthy.get(pos).copied()
+    }
+
+    /// Indices of every backend not removed.
     pub fn all_indices(&self) -> Vec<usize> {
-        (0..self.backends.len()).collect()
+        (0..self.backends.len())
+            .filter(|&index| self.is_member(index))
+            .collect()
     }
 
     pub fn backend(&self, index: usize) -> Option<&BackendState> {
@@ -205,10 +505,94 @@ impl BackendPool {
         self.membership_epoch
     }
 
+    /// Whether the backend at `index` is in rotation.
     pub fn is_healthy_index(&self, index: usize) -> bool {
         self.healthy_pos.get(index).copied().flatten().is_some()
     }
 
+    /// Whether the backend at `index` passes its health checks, whether or
+    /// not its tier currently takes traffic.
+    pub fn is_backend_healthy(&self, index: usize) -> bool {
+        self.backends
+            .get(index)
+            .is_some_and(BackendState::is_healthy)
+    }
+
+    pub fn healthy_backend_count(&self) -> usize {
+        self.backends.iter().filter(|b| b.is_healthy()).count()
+    }
+
+    /// True while too few backends are healthy and every backend is in
+    /// rotation regardless of health.
+    pub fn is_panicking(&self) -> bool {
+        self.panic
+    }
+
+    /// True while local backends are out of in-flight headroom and traffic
+    /// is spread over every zone.
+    pub fn is_zone_saturated(&self) -> bool {
+        self.zone_saturated
+    }
+
+    /// Re-evaluate local in-flight headroom against the zone-aware limit:
+    /// spill to every zone once local backends average the limit, and return
+    /// once they fall below [`ZONE_RECOVERY_PERCENT`] of it. A change
+    /// rebuilds the rotation and bumps the membership epoch.
+    pub fn refresh_zone_saturation(&mut self) {
+        let (Some(zone), Some(limit)) = (self.local_zone.as_deref(), self.zone_inflight_limit)
+        else {
+            return;
+        };
+
+        let (mut local, mut inflight) = (0u64, 0u64);
+        for backend in &self.backends {
+            if backend.is_healthy() && backend.zone() == Some(zone) {
+                local += 1;
+                inflight += backend.active_requests() as u64;
+            }
+        }
+        let capacity = local * u64::from(limit);
+        let saturated = local > 0
+            && if self.zone_saturated {
+                inflight * 100 >= capacity * ZONE_RECOVERY_PERCENT
+            } else {
+                inflight >= capacity
+            };
+        if saturated == self.zone_saturated {
+            return;
+        }
+
+        self.zone_saturated = saturated;
+        self.rebuild_tiers();
+        self.rebuild_rotation();
+        self.membership_epoch = self.membership_epoch.wrapping_add(1);
+    }
+
+    /// Per-tier health and traffic share, lowest priority first and local
+    /// before other zones.
+    pub fn tier_health(&self) -> Vec<TierHealth> {
+        let mut tiers: Vec<TierHealth> = self
+            .tiers
+            .iter()
+            .map(|&(priority, remote)| TierHealth {
+                priority,
+                remote,
+                total_backends: 0,
+                healthy_backends: 0,
+                load_percent: 0,
+            })
+            .collect();
+        for backend in self.backends.iter().filter(|b| !b.is_removed()) {
+            let tier = &mut tiers[self.tier_of(backend)];
+            tier.total_backends += 1;
+            tier.healthy_backends += usize::from(backend.is_healthy());
+        }
+        for (tier, load) in tiers.iter_mut().zip(tier_loads(&self.tier_capacity())) {
+            tier.load_percent = load;
+        }
+        tiers
+    }
+
     pub fn begin_request(&self, index: usize) {
         if let Some(backend) = self.backends.get(index) {
             backend.active_requests.fetch_add(1, Ordering::Relaxed);
@@ -244,13 +628,27 @@ impl BackendPool {
             return false;
         }
 
+        if self.tiered() {
+            if self.slow_start.is_some() {
+                self.begin_ramp(index, Instant::now());
+            }
+            self.rebuild_rotation();
+            return true;
+        }
+
         if self.healthy_pos[index].is_some() {
             return false;
         }
 
+        if self.slow_start.is_some() {
+            self.begin_ramp(index, Instant::now());
+            self.refresh_uniform_weights();
+        }
+
         let pos = self.healthy.len();
         self.healthy.push(index);
         self.healthy_pos[index] = Some(pos);
+        self.rebuild_weight_prefix_from(pos);
         true
     }
 
@@ -259,6 +657,12 @@ impl BackendPool {
             return false;
         }
 
+        if self.tiered() {
+            self.end_ramp(index);
+            self.rebuild_rotation();
+            return true;
+        }
+
         let Some(pos) = self.healthy_pos[index] else {
             return false;
         };
@@ -272,6 +676,226 @@ impl BackendPool {
         }
 
         self.healthy_pos[index] = None;
+        if self.end_ramp(index) {
+            self.refresh_uniform_weights();
+        }
+        self.rebuild_weight_prefix_from(pos);
+        true
+    }
+
+    /// Several tiers or a panic threshold: membership changes recompute the
+    /// rotation instead of editing it in place.
+    fn tiered(&self) -> bool {
+        self.tiers.len() > 1 || self.panic_threshold_percent > 0
+    }
+
+    /// A backend's priority, and whether it sits outside the local zone
+    /// while zone preference applies.
+    fn tier_key(&self, backend: &BackendState) -> (u32, bool) {
+        let remote = !self.zone_saturated
+            && self
+                .local_zone
+                .as_deref()
+                .is_some_and(|zone| backend.zone() != Some(zone));
+        (backend.priority(), remote)
+    }
+
+    fn rebuild_tiers(&mut self) {
+        let mut tiers: Vec<(u32, bool)> = self
+            .backends
+            .iter()
+            .filter(|backend| !backend.is_removed())
+            .map(|backend| self.tier_key(backend))
+            .collect();
+        tiers.sort_unstable();
+        tiers.dedup();
+        self.tiers = tiers;
+    }
+
+    fn tier_of(&self, backend: &BackendState) -> usize {
+        self.tiers
+            .binary_search(&self.tier_key(backend))
+            .unwrap_or_default()
+    }
+
+    /// Healthy and total configured weight per tier, leaving out drained and
+    /// disabled backends.
+    fn tier_capacity(&self) -> Vec<(u64, u64)> {
+        let mut capacity = vec![(0u64, 0u64); self.tiers.len()];
+        for backend in self
+            .backends
+            .iter()
+            .filter(|b| b.admin_state().is_enabled())
+        {
+            let (healthy, total) = &mut capacity[self.tier_of(backend)];
+            *total += u64::from(backend.weight());
+            if backend.is_healthy() {
+                *healthy += u64::from(backend.weight());
+            }
+        }
+        capacity
+    }
+
+    fn refresh_weights(&mut self) {
+        if self.tiered() {
+            self.rebuild_rotation();
+        } else {
+            self.refresh_uniform_weights();
+            self.rebuild_weight_prefix_from(0);
+        }
+    }
+
+    /// Recompute the rotation from backend health: the healthy members of
+    /// the tiers with a traffic share, each scaled so its tier carries that
+    /// share, or every backend at full weight while panicking.
+    fn rebuild_rotation(&mut self) {
+        let capacity = self.tier_capacity();
+        // A priority split into local and remote tiers counts once.
+        let mut priority_capacity: Vec<(u32, u64, u64)> = Vec::new();
+        for (&(priority, _), &(healthy, total)) in self.tiers.iter().zip(&capacity) {
+            match priority_capacity.last_mut() {
+                Some((last, last_healthy, last_total)) if *last == priority => {
+                    *last_healthy += healthy;
+                    *last_total += total;
+                }
+                _ => priority_capacity.push((priority, healthy, total)),
+            }
+        }
+        let healthy_percent = priority_capacity
+            .iter()
+            .map(|&(_, healthy, total)| healthy * 100 / total.max(1))
+            .sum::<u64>()
+            .min(100);
+        self.panic = healthy_percent < u64::from(self.panic_threshold_percent);
+        let loads = tier_loads(&capacity);
+
+        self.healthy.clear();
+        self.healthy_pos.fill(None);
+        let mut tier_weights = vec![0u64; self.tiers.len()];
+        for index in 0..self.backends.len() {
+            let tier = self.tier_of(&self.backends[index]);
+            let backend = &mut self.backends[index];
+            backend.set_tier_scale(TIER_SCALE_ONE);
+            if backend.is_removed() {
+                continue;
+            }
+            let in_rotation = if self.panic {
+                backend.admin_state().is_enabled()
+            } else {
+                backend.is_healthy() && loads[tier] > 0
+            };
+            if in_rotation {
+                tier_weights[tier] += backend.ramped_weight();
+                self.healthy_pos[index] = Some(self.healthy.len());
+                self.healthy.push(index);
+            }
+        }
+
+        let loaded_tiers = tier_weights.iter().filter(|&&weight| weight > 0).count();
+        if !self.panic && loaded_tiers > 1 {
+            // Keep the rotation's total weight, split by tier share.
+            let total = u128::from(tier_weights.iter().sum::<u64>());
+            for pos in 0..self.healthy.len() {
+                let index = self.healthy[pos];
+                let tier = self.tier_of(&self.backends[index]);
+                let scale = u128::from(loads[tier]) * total * u128::from(TIER_SCALE_ONE)
+                    / (100 * u128::from(tier_weights[tier]));
+                self.backends[index].set_tier_scale(u64::try_from(scale).unwrap_or(u64::MAX));
+            }
+        }
+
+        self.refresh_uniform_weights();
+        self.rebuild_weight_prefix_from(0);
+    }
+
+    /// Recompute tiers and the rotation after backends joined, left or
+    /// changed, and let strategies know.
+    fn rebuild_membership(&mut self) {
+        self.rebuild_tiers();
+        self.rebuild_rotation();
+        self.membership_epoch = self.membership_epoch.wrapping_add(1);
+    }
+
+    /// Drop `index` from the ramp at full weight; true when it was ramping.
+    fn end_ramp(&mut self, index: usize) -> bool {
+        let Some(ramp) = self.ramping.iter().position(|&(idx, _)| idx == index) else {
+            return false;
+        };
+        self.ramping.swap_remove(ramp);
+        self.backends[index].set_weight_percent(100);
         true
     }
+
+    fn begin_ramp(&mut self, index: usize, now: Instant) {
+        let Some(policy) = self.slow_start else {
+            return;
+        };
+        self.backends[index].set_weight_percent(ramp_percent(&policy, Duration::ZERO));
+        match self.ramping.iter_mut().find(|(idx, _)| *idx == index) {
+            Some(entry) => entry.1 = now,
+            None => self.ramping.push((index, now)),
+        }
+        let next = now + ramp_tick(&policy);
+        self.next_ramp_step = Some(self.next_ramp_step.map_or(next, |step| step.min(next)));
+    }
+
+    fn refresh_uniform_weights(&mut self) {
+        let mut weights = self
+            .backends
+            .iter()
+            .filter(|b| !b.is_removed())
+            .map(BackendState::effective_weight);
+        let first = weights.next();
+        self.uniform_weights = weights.all(|weight| Some(weight) == first);
+    }
+
+    fn rebuild_weight_prefix_from(&mut self, pos: usize) {
+        self.healthy_weight_prefix.truncate(pos);
+        let mut total = pos
+            .checked_sub(1)
+            .map_or(0, |last| self.healthy_weight_prefix[last]);
+        for &idx in &self.healthy[pos..] {
+            total += self.backends[idx].effective_weight();
+            self.healthy_weight_prefix.push(total);
+        }
+    }
+}
+
+/// Traffic share per tier, in percent. Each tier takes up to its
+/// overprovisioned healthy capacity and passes the rest to the next; when
+/// all tiers together fall short, the shares are scaled up to sum to 100.
+fn tier_loads(capacity: &[(u64, u64)]) -> Vec<u32> {
+    let health: Vec<u64> = capacity
+        .iter()
+        .map(|&(healthy, total)| {
+            // Round up so a tier with any healthy backend keeps some share.
+            (healthy * OVERPROVISIONING_PERCENT)
+                .div_ceil(total.max(1))
+                .min(100)
+        })
+        .collect();
+    let total_health = health.iter().sum::<u64>();
+    if total_health == 0 {
+        return vec![0; capacity.len()];
+    }
+
+    let mut remaining = 100u64;
+    let mut loads: Vec<u64> = health
+        .iter()
+        .map(|&tier_health| {
+            let load = tier_health.min(remaining);
+            remaining -= load;
+            load
+        })
+        .collect();
+    if total_health < 100 {
+        for load in &mut loads {
+            *load = *load * 100 / total_health;
+        }
+        let assigned = loads.iter().sum::<u64>();
+        if let Some(first) = loads.iter_mut().find(|load| **load > 0) {
+            *first += 100 - assigned;
+        }
+    }
+    loads.into_iter().map(|load| load as u32).collect()
 }
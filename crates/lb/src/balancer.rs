@@ -0,0 +1,148 @@
+//! Balancer extension point.
+//!
+//! Every built-in algorithm implements [`Balancer`]; [`LoadBalancing`]
+//! dispatches to them statically. Embedding binaries can add strategies of
+//! their own with [`register_balancer`] and select them per upstream with
+//! `type: "custom:<name>"`.
+//!
+//! [`LoadBalancing`]: crate::load_balancing::LoadBalancing
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, PoisonError, RwLock},
+    time::Duration,
+};
+
+use spooky_config::runtime::RuntimeLoadBalancingPolicy;
+
+use crate::backend_pool::BackendPool;
+
+/// A backend selection strategy.
+///
+/// Indices refer to `pool.backends`; implementations should only return
+/// indices in `pool.healthy`, which already accounts for priority tiers,
+/// locality and panic mode. An upstream replaces any other pick with a
+/// round-robin one and logs the first such pick.
+pub trait Balancer: Send + Sync {
+    /// Name reported in diagnostics and request logs.
+    fn name(&self) -> &str;
+
+    /// Choose a backend for a request with load-balancing key `key`.
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize>;
+
+    /// Pick without `&mut` access, for retries and hedges. `None` makes
+    /// callers fall back to the first healthy backend.
+    fn pick_readonly(&self, _key: &str, _pool: &BackendPool) -> Option<usize> {
+        None
+    }
+
+    /// Read-only pick that never returns a backend in `excluded`.
+    fn pick_readonly_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        self.pick_readonly(key, pool)
+            .filter(|index| !excluded.contains(index))
+    }
+
+    /// Backend the next [`pick`](Self::pick) for `key` would choose, for
+    /// diagnostics. `None` when there is no stable answer.
+    fn peek(&self, _key: &str, _pool: &BackendPool) -> Option<usize> {
+        None
+    }
+
+    /// Called before the next pick whenever backend health, weights or
+    /// tier membership changed since the previous one.
+    fn on_membership_change(&mut self, _pool: &BackendPool) {}
+
+    /// Called as each request to backend `index` finishes. `status` is
+    /// `None` when no response arrived.
+    fn on_request_complete(&mut self, _index: usize, _latency: Duration, _status: Option<u16>) {}
+}
+
+impl<B: Balancer + ?Sized> Balancer for Box<B> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
+        (**self).pick(key, pool)
+    }
+
+    fn pick_readonly(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        (**self).pick_readonly(key, pool)
+    }
+
+    fn pick_readonly_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        (**self).pick_readonly_excluding(key, pool, excluded)
+    }
+
+    fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        (**self).peek(key, pool)
+    }
+
+    fn on_membership_change(&mut self, pool: &BackendPool) {
+        (**self).on_membership_change(pool)
+    }
+
+    fn on_request_complete(&mut self, index: usize, latency: Duration, status: Option<u16>) {
+        (**self).on_request_complete(index, latency, status)
+    }
+}
+
+/// Builds a balancer for one upstream from its load-balancing policy.
+pub type BalancerFactory =
+    Arc<dyn Fn(&RuntimeLoadBalancingPolicy) -> Box<dyn Balancer> + Send + Sync>;
+
+static REGISTRY: OnceLock<RwLock<HashMap<String, BalancerFactory>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<HashMap<String, BalancerFactory>> {
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Make `factory` available as `custom:<name>`. Register before loading
+/// config; pools built afterwards call the factory once per upstream.
+pub fn register_balancer<F>(name: &str, factory: F) -> Result<(), String>
+where
+    F: Fn(&RuntimeLoadBalancingPolicy) -> Box<dyn Balancer> + Send + Sync + 'static,
+{
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("balancer name must not be empty".to_string());
+    }
+
+    let mut registry = registry().write().unwrap_or_else(PoisonError::into_inner);
+    if registry.contains_key(name) {
+        return Err(format!("balancer '{name}' is already registered"));
+    }
+    registry.insert(name.to_string(), Arc::new(factory));
+    Ok(())
+}
+
+/// Names passed to [`register_balancer`], sorted.
+pub fn registered_balancers() -> Vec<String> {
+    let registry = registry().read().unwrap_or_else(PoisonError::into_inner);
+    let mut names = registry.keys().cloned().collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+pub(crate) fn build_registered(
+    name: &str,
+    policy: &RuntimeLoadBalancingPolicy,
+) -> Result<Box<dyn Balancer>, String> {
+    let factory = registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("no balancer registered as '{name}'"))?;
+    Ok(factory(policy))
+}
@@ -1,4 +1,4 @@
-use crate::backend_pool::BackendPool;
+use crate::{backend::BackendState, backend_pool::BackendPool};
 
 pub const DEFAULT_REPLICAS: u32 = 64;
 pub const FNV_OFFSET: u64 = 0xcbf29ce484222325;
@@ -7,10 +7,18 @@ pub const FNV_PRIME: u64 = 0x00000100000001b3;
 pub fn expected_ring_entries(pool: &BackendPool, replicas: u32) -> usize {
     pool.healthy
         .iter()
-        .map(|&idx| replicas.saturating_mul(pool.backends[idx].weight()) as usize)
+        .map(|&idx| backend_replicas(&pool.backends[idx], replicas) as usize)
         .sum()
 }
 
+/// Ring points for `backend`: `replicas` per unit of effective weight, rounded
+/// up so a ramping backend keeps at least one. Replica numbers are stable, so
+/// a ramp only ever adds points.
+pub fn backend_replicas(backend: &BackendState, replicas: u32) -> u32 {
+    let points = (u64::from(replicas) * backend.effective_weight()).div_ceil(100);
+    u32::try_from(points).unwrap_or(u32::MAX).max(1)
+}
+
 pub fn hash_backend_replica(address: &str, replica: u32) -> u64 {
     let mut hash = FNV_OFFSET;
     for &byte in address.as_bytes() {
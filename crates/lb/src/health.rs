@@ -5,4 +5,39 @@ pub enum HealthFailureReason {
     Transport,
     Tls,
     CircuitOpen,
+    Outlier,
+}
+
+/// Operator override on top of health checks. Anything but `Enabled` keeps
+/// a backend out of rotation, healthy or not, and out of panic mode too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BackendAdminState {
+    #[default]
+    Enabled,
+    /// No new requests; in-flight ones run to completion.
+    Draining,
+    Disabled,
+}
+
+impl BackendAdminState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::Draining => "draining",
+            Self::Disabled => "disabled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "enabled" => Some(Self::Enabled),
+            "draining" => Some(Self::Draining),
+            "disabled" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        self == Self::Enabled
+    }
 }
@@ -1,9 +1,10 @@
 //! Load-balancing primitives for runtime-selected backend picking.
 //!
 //! Canonical consumers should depend on [`upstream_pool`], [`load_balancing`],
-//! [`alternate_backend`], and [`health`]. The remaining modules are kept
-//! visible only as compatibility/testing substrate and are not intended as
-//! orchestration entrypoints.
+//! [`alternate_backend`], [`health`], [`outlier`], and [`slow_start`], and on
+//! [`balancer`] to add strategies. The remaining modules are kept visible only
+//! as compatibility/testing substrate and are not intended as orchestration
+//! entrypoints.
 
 #[doc(hidden)]
 pub mod algorithms;
@@ -12,7 +13,10 @@ pub mod alternate_backend;
 pub mod backend;
 #[doc(hidden)]
 pub mod backend_pool;
+pub mod balancer;
 pub(crate) mod hash;
 pub mod health;
 pub mod load_balancing;
+pub mod outlier;
+pub mod slow_start;
 pub mod upstream_pool;
@@ -4,23 +4,58 @@
 //! query strategies through [`LoadBalancing`] instead of depending on
 //! algorithm-specific state.
 
+use std::time::Duration;
+
+use spooky_config::{
+    config::{
+        DEFAULT_HASH_BALANCE_FACTOR, DEFAULT_MAGLEV_TABLE_SIZE, PowerOfTwoChoices as P2cConfig,
+    },
+    runtime::{RuntimeLoadBalancingPolicy, RuntimeLoadBalancingStrategy},
+};
+
 use crate::{
     algorithms::{
-        consistent_hash::ConsistentHash, latency_aware::LatencyAware,
-        least_connections::LeastConnections, random::Random, round_robin::RoundRobin,
-        sticky_cid::StickyCid,
+        bounded_consistent_hash::BoundedConsistentHash, consistent_hash::ConsistentHash,
+        latency_aware::LatencyAware, least_connections::LeastConnections, maglev::Maglev,
+        p2c::PowerOfTwoChoices, random::Random, round_robin::RoundRobin, sticky_cid::StickyCid,
     },
     backend_pool::BackendPool,
+    balancer::{self, Balancer},
     hash::DEFAULT_REPLICAS,
 };
 
 pub enum LoadBalancing {
     RoundRobin(RoundRobin),
     ConsistentHash(ConsistentHash),
+    BoundedConsistentHash(BoundedConsistentHash),
+    Maglev(Maglev),
     Random(Random),
     LeastConnections(LeastConnections),
     LatencyAware(LatencyAware),
+    PowerOfTwoChoices(PowerOfTwoChoices),
     StickyCid(StickyCid),
+    /// A strategy from the [`balancer`] registry.
+    Custom(Box<dyn Balancer>),
+}
+
+/// Calls a [`Balancer`] method on whichever strategy is active. Built-ins
+/// are matched on their concrete type so the call is static and inlined;
+/// only `Custom` goes through the vtable.
+macro_rules! dispatch {
+    ($lb:expr, $balancer:ident => $call:expr) => {
+        match $lb {
+            LoadBalancing::RoundRobin($balancer) => $call,
+            LoadBalancing::ConsistentHash($balancer) => $call,
+            LoadBalancing::BoundedConsistentHash($balancer) => $call,
+            LoadBalancing::Maglev($balancer) => $call,
+            LoadBalancing::Random($balancer) => $call,
+            LoadBalancing::LeastConnections($balancer) => $call,
+            LoadBalancing::LatencyAware($balancer) => $call,
+            LoadBalancing::PowerOfTwoChoices($balancer) => $call,
+            LoadBalancing::StickyCid($balancer) => $call,
+            LoadBalancing::Custom($balancer) => $call,
+        }
+    };
 }
 
 impl LoadBalancing {
@@ -31,11 +66,26 @@ impl LoadBalancing {
             "consistent-hash" | "consistent_hash" | "ch" => {
                 Ok(Self::ConsistentHash(ConsistentHash::new(DEFAULT_REPLICAS)))
             }
+            "consistent-hash-bounded" | "consistent_hash_bounded" | "chb" => {
+                Ok(Self::BoundedConsistentHash(BoundedConsistentHash::new(
+                    DEFAULT_REPLICAS,
+                    DEFAULT_HASH_BALANCE_FACTOR,
+                )))
+            }
+            "maglev" => Ok(Self::Maglev(Maglev::new(DEFAULT_MAGLEV_TABLE_SIZE))),
             "random" => Ok(Self::Random(Random::new())),
             "least-connections" | "least_connections" | "lc" => {
                 Ok(Self::LeastConnections(LeastConnections::new()))
             }
             "latency-aware" | "latency_aware" | "la" => Ok(Self::LatencyAware(LatencyAware::new())),
+            "p2c" | "power-of-two-choices" | "power_of_two_choices" => {
+                let defaults = P2cConfig::default();
+                Ok(Self::PowerOfTwoChoices(PowerOfTwoChoices::new(
+                    defaults.choices as usize,
+                    defaults.metric,
+                    defaults.weighted,
+                )))
+            }
             "sticky-cid" | "sticky_cid" | "cid-sticky" | "cid_sticky" => {
                 Ok(Self::StickyCid(StickyCid::new(DEFAULT_REPLICAS)))
             }
@@ -44,41 +94,77 @@ impl LoadBalancing {
     }
 
     pub fn pick(&mut self, key: &str, pool: &BackendPool) -> Option<usize> {
-        match self {
-            LoadBalancing::RoundRobin(rr) => rr.pick(pool),
-            LoadBalancing::ConsistentHash(ch) => ch.pick(key, pool),
-            LoadBalancing::Random(rand) => rand.pick(pool),
-            LoadBalancing::LeastConnections(lc) => lc.pick(pool),
-            LoadBalancing::LatencyAware(la) => la.pick(pool),
-            LoadBalancing::StickyCid(sticky) => sticky.pick(key, pool),
-        }
+        dispatch!(self, balancer => Balancer::pick(balancer, key, pool))
     }
 
-    pub fn pick_readonly(&self, _key: &str, pool: &BackendPool) -> Option<usize> {
-        match self {
-            LoadBalancing::RoundRobin(rr) => rr.pick_readonly(pool),
-            LoadBalancing::Random(rand) => rand.pick_readonly(pool),
-            LoadBalancing::LeastConnections(lc) => lc.pick_readonly(pool),
-            LoadBalancing::LatencyAware(la) => la.pick_readonly(pool),
-            // ConsistentHash and StickyCid keep mutable ring caches.
-            LoadBalancing::ConsistentHash(_) | LoadBalancing::StickyCid(_) => None,
-        }
+    /// `None` for `consistent-hash` and `sticky-cid`, which keep mutable
+    /// ring caches.
+    pub fn pick_readonly(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        dispatch!(self, balancer => Balancer::pick_readonly(balancer, key, pool))
     }
 
-    pub fn name(&self) -> &'static str {
-        match self {
-            LoadBalancing::RoundRobin(_) => "round-robin",
-            LoadBalancing::ConsistentHash(_) => "consistent-hash",
-            LoadBalancing::Random(_) => "random",
-            LoadBalancing::LeastConnections(_) => "least-connections",
-            LoadBalancing::LatencyAware(_) => "latency-aware",
-            LoadBalancing::StickyCid(_) => "sticky-cid",
-        }
+    /// Read-only pick that skips the backends in `excluded`. Maglev and
+    /// bounded-load hashing walk on from the key's position to the next
+    /// backend not excluded, and P2C samples only the others; other
+    /// strategies discard an excluded pick.
+    pub fn pick_readonly_excluding(
+        &self,
+        key: &str,
+        pool: &BackendPool,
+        excluded: &[usize],
+    ) -> Option<usize> {
+        dispatch!(self, balancer => {
+            Balancer::pick_readonly_excluding(balancer, key, pool, excluded)
+        })
     }
 
-    pub fn from_runtime_strategy(
-        strategy: spooky_config::runtime::RuntimeLoadBalancingStrategy,
-    ) -> Result<Self, String> {
+    /// Backend the next [`pick`](Self::pick) for `key` would choose, leaving
+    /// all strategy state untouched. `random` and `p2c` have no stable answer
+    /// and return `None`.
+    pub fn peek(&self, key: &str, pool: &BackendPool) -> Option<usize> {
+        dispatch!(self, balancer => Balancer::peek(balancer, key, pool))
+    }
+
+    pub fn on_membership_change(&mut self, pool: &BackendPool) {
+        dispatch!(self, balancer => Balancer::on_membership_change(balancer, pool))
+    }
+
+    pub fn on_request_complete(&mut self, index: usize, latency: Duration, status: Option<u16>) {
+        dispatch!(self, balancer => {
+            Balancer::on_request_complete(balancer, index, latency, status)
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        dispatch!(self, balancer => Balancer::name(balancer))
+    }
+
+    pub fn from_runtime_strategy(strategy: RuntimeLoadBalancingStrategy) -> Result<Self, String> {
         Self::from_config(strategy.canonical_name())
     }
+
+    /// Like [`from_runtime_strategy`](Self::from_runtime_strategy), applying
+    /// the policy's Maglev table size, bounded-load factor, and P2C sampling,
+    /// and building `custom` strategies from the registry.
+    pub fn from_runtime_policy(policy: &RuntimeLoadBalancingPolicy) -> Result<Self, String> {
+        match policy.strategy {
+            RuntimeLoadBalancingStrategy::Custom => {
+                let name = policy
+                    .custom
+                    .as_deref()
+                    .ok_or("custom load balancing type without a name")?;
+                Ok(Self::Custom(balancer::build_registered(name, policy)?))
+            }
+            RuntimeLoadBalancingStrategy::Maglev => {
+                Ok(Self::Maglev(Maglev::new(policy.table_size)))
+            }
+            RuntimeLoadBalancingStrategy::BoundedConsistentHash => Ok(Self::BoundedConsistentHash(
+                BoundedConsistentHash::new(DEFAULT_REPLICAS, policy.hash_balance_factor),
+            )),
+            RuntimeLoadBalancingStrategy::P2c => Ok(Self::PowerOfTwoChoices(
+                PowerOfTwoChoices::new(policy.p2c.choices, policy.p2c.metric, policy.p2c.weighted),
+            )),
+            strategy => Self::from_runtime_strategy(strategy),
+        }
+    }
 }
@@ -0,0 +1,253 @@
+//! Outlier detection over per-interval request windows.
+//!
+//! [`OutlierDetector`] counts each backend's requests, 5xx responses and
+//! latencies between evaluations. An evaluation judges the backends that served
+//! at least `min_requests`, ejects those failing a check through the pool's
+//! health state, and starts a fresh window.
+
+use std::time::{Duration, Instant};
+
+use spooky_config::runtime::RuntimeOutlierDetection;
+
+use crate::{backend_pool::BackendPool, health::HealthFailureReason};
+
+/// Quarter-octave latency buckets; the last one holds everything above ~17 min.
+const LATENCY_BUCKETS: usize = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutlierEjectionReason {
+    /// Success rate under `success_rate_min_percent`.
+    SuccessRate,
+    /// Success rate too many standard deviations under the pool mean.
+    SuccessRateStdev,
+    /// p95 latency too far above the pool median p95.
+    LatencyP95,
+}
+
+impl OutlierEjectionReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SuccessRate => "success_rate",
+            Self::SuccessRateStdev => "success_rate_stdev",
+            Self::LatencyP95 => "latency_p95",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutlierEjection {
+    pub index: usize,
+    pub reason: OutlierEjectionReason,
+    pub duration: Duration,
+}
+
+#[derive(Clone)]
+struct BackendWindow {
+    requests: u64,
+    failures: u64,
+    latency_buckets: [u64; LATENCY_BUCKETS],
+}
+
+impl Default for BackendWindow {
+    fn default() -> Self {
+        Self {
+            requests: 0,
+            failures: 0,
+            latency_buckets: [0; LATENCY_BUCKETS],
+        }
+    }
+}
+
+impl BackendWindow {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.requests = self.requests.saturating_add(1);
+        if failed {
+            self.failures = self.failures.saturating_add(1);
+            return;
+        }
+        let ms = latency.as_secs_f64() * 1_000.0;
+        let bucket = ((ms + 1.0).log2() * 4.0) as usize;
+        let bucket = bucket.min(LATENCY_BUCKETS - 1);
+        self.latency_buckets[bucket] = self.latency_buckets[bucket].saturating_add(1);
+    }
+
+    fn success_rate(&self) -> f64 {
+        (self.requests - self.failures) as f64 / self.requests.max(1) as f64
+    }
+
+    /// Upper bound of the bucket holding the 95th percentile successful
+    /// request, in milliseconds.
+    fn p95_ms(&self) -> Option<f64> {
+        let samples: u64 = self.latency_buckets.iter().sum();
+        if samples == 0 {
+            return None;
+        }
+        let rank = samples - samples / 20;
+        let mut seen = 0u64;
+        let bucket = self
+            .latency_buckets
+            .iter()
+            .position(|count| {
+                seen += count;
+                seen >= rank
+            })
+            .unwrap_or(LATENCY_BUCKETS - 1);
+        Some(((bucket + 1) as f64 / 4.0).exp2() - 1.0)
+    }
+}
+
+pub(crate) struct OutlierDetector {
+    policy: RuntimeOutlierDetection,
+    windows: Vec<BackendWindow>,
+    // Ejections not yet offset by intervals the backend was judged and passed;
+    // each one doubles the next ejection time.
+    ejection_streaks: Vec<u32>,
+}
+
+impl OutlierDetector {
+    pub(crate) fn new(policy: RuntimeOutlierDetection, backends: usize) -> Self {
+        Self {
+            policy,
+            windows: vec![BackendWindow::default(); backends],
+            ejection_streaks: vec![0; backends],
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.policy.interval
+    }
+
+    /// Drop what was recorded for the backend at `index`, which now holds a
+    /// different one.
+    pub(crate) fn forget(&mut self, index: usize) {
+        if let Some(window) = self.windows.get_mut(index) {
+            *window = BackendWindow::default();
+        }
+        if let Some(streak) = self.ejection_streaks.get_mut(index) {
+            *streak = 0;
+        }
+    }
+
+    pub(crate) fn record(&mut self, index: usize, latency: Duration, status: Option<u16>) {
+        if let Some(window) = self.windows.get_mut(index) {
+            window.record(
+                latency,
+                status.is_some_and(|code| (500..=599).contains(&code)),
+            );
+        }
+    }
+
+    /// Judges the window that just ended and ejects outliers from `pool`,
+    /// keeping the share of backends out of rotation within
+    /// `max_ejection_percent` and never ejecting the last healthy backend.
+    pub(crate) fn evaluate(
+        &mut self,
+        pool: &mut BackendPool,
+        now: Instant,
+    ) -> Vec<OutlierEjection> {
+        let windows = std::mem::replace(
+            &mut self.windows,
+            vec![BackendWindow::default(); pool.len()],
+        );
+        // Backends added since the last evaluation.
+        self.ejection_streaks.resize(pool.len(), 0);
+        let judged = windows
+            .iter()
+            .enumerate()
+            .filter(|(index, window)| {
+                window.requests >= self.policy.min_requests && pool.is_backend_healthy(*index)
+            })
+            .collect::<Vec<_>>();
+
+        let mut candidates: Vec<(usize, OutlierEjectionReason)> = Vec::new();
+        let mut flag = |index: usize, reason: OutlierEjectionReason| {
+            if !candidates.iter().any(|(flagged, _)| *flagged == index) {
+                candidates.push((index, reason));
+            }
+        };
+
+        if let Some(min_percent) = self.policy.success_rate_min_percent {
+            for (index, window) in &judged {
+                if window.success_rate() * 100.0 < f64::from(min_percent) {
+                    flag(*index, OutlierEjectionReason::SuccessRate);
+                }
+            }
+        }
+
+        if let Some(factor) = self.policy.success_rate_stdev_factor
+            && judged.len() >= self.policy.min_backends
+        {
+            let count = judged.len() as f64;
+            let mean = judged
+                .iter()
+                .map(|(_, window)| window.success_rate())
+                .sum::<f64>()
+                / count;
+            let variance = judged
+                .iter()
+                .map(|(_, window)| (window.success_rate() - mean).powi(2))
+                .sum::<f64>()
+                / count;
+            let threshold = mean - factor * variance.sqrt();
+            for (index, window) in &judged {
+                if window.success_rate() < threshold {
+                    flag(*index, OutlierEjectionReason::SuccessRateStdev);
+                }
+            }
+        }
+
+        if let Some(ratio_percent) = self.policy.latency_p95_ratio_percent {
+            let mut p95s = judged
+                .iter()
+                .filter_map(|(index, window)| window.p95_ms().map(|p95| (*index, p95)))
+                .collect::<Vec<_>>();
+            if p95s.len() >= self.policy.min_backends {
+                p95s.sort_by(|left, right| left.1.total_cmp(&right.1));
+                let median = p95s[(p95s.len() - 1) / 2].1;
+                let limit = median * f64::from(ratio_percent) / 100.0;
+                for (index, p95) in p95s {
+                    if p95 > limit {
+                        flag(index, OutlierEjectionReason::LatencyP95);
+                    }
+                }
+            }
+        }
+
+        let max_ejection_percent = self.policy.max_ejection_percent as usize;
+        let members = pool.member_count();
+        let max_out_of_rotation =
+            (members * max_ejection_percent / 100).max(usize::from(max_ejection_percent > 0));
+        let mut healthy_backends = pool.healthy_backend_count();
+        let mut out_of_rotation = members - healthy_backends;
+        let mut ejections = Vec::new();
+        for (index, reason) in candidates {
+            if out_of_rotation >= max_out_of_rotation || healthy_backends <= 1 {
+                break;
+            }
+            let streak = self.ejection_streaks[index].saturating_add(1);
+            let duration = self
+                .policy
+                .base_ejection
+                .saturating_mul(1 << (streak - 1).min(31))
+                .min(self.policy.max_ejection);
+            if pool.eject(index, now + duration, HealthFailureReason::Outlier) {
+                self.ejection_streaks[index] = streak;
+                out_of_rotation += 1;
+                healthy_backends -= 1;
+                ejections.push(OutlierEjection {
+                    index,
+                    reason,
+                    duration,
+                });
+            }
+        }
+
+        for (index, _) in judged {
+            if !ejections.iter().any(|ejection| ejection.index == index) {
+                self.ejection_streaks[index] = self.ejection_streaks[index].saturating_sub(1);
+            }
+        }
+
+        ejections
+    }
+}
@@ -0,0 +1,37 @@
+//! Slow-start weight ramp.
+//!
+//! A backend that becomes healthy, or is added by DNS refresh, starts at
+//! `min_weight_percent` of its weight and reaches full weight once the window
+//! elapses. The ramp moves in [`RAMP_STEP_PERCENT`] steps so consistent-hash
+//! tables are rebuilt a bounded number of times per window.
+
+use std::time::Duration;
+
+use spooky_config::{config::SlowStartCurve, runtime::RuntimeSlowStart};
+
+/// Granularity of the ramp, in percent of the configured weight.
+pub const RAMP_STEP_PERCENT: u32 = 10;
+
+/// Weight percent in effect `elapsed` into the slow-start window.
+pub fn ramp_percent(policy: &RuntimeSlowStart, elapsed: Duration) -> u32 {
+    let min = policy.min_weight_percent.clamp(1, 100);
+    if policy.window.is_zero() || elapsed >= policy.window {
+        return 100;
+    }
+
+    let progress = elapsed.as_secs_f64() / policy.window.as_secs_f64();
+    let curve = match policy.curve {
+        SlowStartCurve::Linear => progress,
+        // Front-loads the ramp: half the headroom is reached a quarter of the
+        // way through the window.
+        SlowStartCurve::Aggressive => progress.sqrt(),
+    };
+    let raw = f64::from(min) + f64::from(100 - min) * curve;
+    let stepped = (raw as u32 / RAMP_STEP_PERCENT) * RAMP_STEP_PERCENT;
+    stepped.clamp(min, 100)
+}
+
+/// How often a ramping pool re-evaluates its weights: twice per step.
+pub fn ramp_tick(policy: &RuntimeSlowStart) -> Duration {
+    policy.window / (2 * (100 / RAMP_STEP_PERCENT))
+}
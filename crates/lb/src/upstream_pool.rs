@@ -4,72 +4,196 @@
 //! mutation surface that edge/runtime code is allowed to use. Strategy state is
 //! encapsulated here; callers should not reach into [`BackendPool`] directly.
 
-use std::time::Duration;
-
-use spooky_config::runtime::{
-    RuntimeAlternateBackendPolicy, RuntimeLoadBalancingPolicy, RuntimeLoadBalancingStrategy,
-    RuntimeRequestKeySpec, RuntimeUpstream,
+use std::time::{Duration, Instant};
+
+use log::warn;
+use spooky_config::{
+    config::Backend,
+    runtime::{
+        RuntimeAlternateBackendPolicy, RuntimeLoadBalancingPolicy, RuntimeLoadBalancingStrategy,
+        RuntimeLocality, RuntimeRequestKeySpec, RuntimeSessionAffinity, RuntimeUpstream,
+    },
 };
 
 use crate::{
+    algorithms::round_robin::RoundRobin,
     backend::{BackendState, HealthTransition},
-    backend_pool::BackendPool,
-    health::HealthFailureReason,
+    backend_pool::{BackendPool, TierHealth},
+    health::{BackendAdminState, HealthFailureReason},
     load_balancing::LoadBalancing,
+    outlier::{OutlierDetector, OutlierEjection},
 };
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UpstreamPoolMembershipSummary {
     pub total_backends: usize,
     pub healthy_backends: usize,
     pub membership_epoch: u64,
+    /// Every backend is in rotation because too few are healthy.
+    pub panic: bool,
+    /// Local backends are out of in-flight headroom and every zone shares
+    /// the traffic.
+    pub zone_saturated: bool,
+    /// Health per priority tier, lowest priority first.
+    pub tiers: Vec<TierHealth>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UpstreamBackendRuntimeState {
+    /// In rotation as far as health and the admin state go.
     pub healthy: bool,
+    pub admin_state: BackendAdminState,
     pub active_requests: usize,
     pub ewma_latency_ms: Option<f64>,
+    pub priority: u32,
+    pub weight: u32,
+    /// `weight` scaled by the backend's slow-start ramp, leaving out the
+    /// share priority and zone tiers give it; see
+    /// [`UpstreamPoolMembershipSummary::tiers`] for those.
+    pub effective_weight: f64,
 }
 
 pub struct UpstreamPool {
     pool: BackendPool,
     load_balancer: LoadBalancing,
+    /// Membership epoch the load balancer last saw.
+    balancer_epoch: u64,
+    /// Takes over picks a custom balancer returns outside `pool.healthy`;
+    /// `None` for built-in strategies.
+    custom_fallback: Option<RoundRobin>,
+    custom_fallback_warned: bool,
     lb_policy: RuntimeLoadBalancingPolicy,
+    outlier_detector: Option<OutlierDetector>,
+    locality: Option<RuntimeLocality>,
+    session_affinity: Option<RuntimeSessionAffinity>,
 }
 
 impl UpstreamPool {
     pub fn from_runtime_upstream(upstream: &RuntimeUpstream) -> Result<Self, String> {
+        // Affinity cookies, admin overrides and membership changes find
+        // backends by id, so one id must not name two of them.
+        for (position, backend) in upstream.backends.iter().enumerate() {
+            let id = &backend.backend.id;
+            if upstream.backends[..position]
+                .iter()
+                .any(|earlier| earlier.backend.id == *id)
+            {
+                return Err(format!(
+                    "upstream '{}' contains duplicate backend id '{}'",
+                    upstream.name, id
+                ));
+            }
+        }
+
         let backends = upstream
             .backends
             .iter()
             .map(|backend| BackendState::new(&backend.backend))
-            .collect();
+            .collect::<Vec<_>>();
+        let outlier_detector = upstream
+            .outlier_detection
+            .clone()
+            .map(|policy| OutlierDetector::new(policy, backends.len()));
 
         let lb_policy = upstream.load_balancing.clone();
-        let load_balancer = LoadBalancing::from_runtime_strategy(lb_policy.strategy)?;
+        let load_balancer = LoadBalancing::from_runtime_policy(&lb_policy)?;
 
+        let mut pool = BackendPool::new_from_states(backends)
+            .with_slow_start(upstream.slow_start)
+            .with_panic_threshold(lb_policy.panic_threshold_percent);
+        if let Some(locality) = upstream.locality.as_ref().filter(|l| l.zone_aware) {
+            pool = pool.with_locality(&locality.zone, locality.max_inflight_per_backend);
+        }
+
+        let custom_fallback =
+            matches!(load_balancer, LoadBalancing::Custom(_)).then(RoundRobin::new);
         Ok(Self {
-            pool: BackendPool::new_from_states(backends),
+            balancer_epoch: pool.membership_epoch(),
+            pool,
+            custom_fallback,
+            custom_fallback_warned: false,
             load_balancer,
             lb_policy,
+            outlier_detector,
+            locality: upstream.locality.clone(),
+            session_affinity: upstream.session_affinity.clone(),
         })
     }
 
     pub fn pick(&mut self, key: &str) -> Option<usize> {
-        self.pool.reconcile_readmit();
-        let selected = self.load_balancer.pick(key, &self.pool)?;
+        let selected = self.pick_without_begin(key)?;
         self.pool.begin_request(selected);
         Some(selected)
     }
 
     pub fn pick_readonly(&self, key: &str) -> Option<usize> {
-        self.load_balancer.pick_readonly(key, &self.pool)
+        self.in_rotation(self.load_balancer.pick_readonly(key, &self.pool))
+    }
+
+    /// Read-only pick for retries and hedges that never returns a backend in
+    /// `excluded`.
+    pub fn pick_readonly_excluding(&self, key: &str, excluded: &[usize]) -> Option<usize> {
+        self.in_rotation(
+            self.load_balancer
+                .pick_readonly_excluding(key, &self.pool, excluded),
+        )
+    }
+
+    /// Backend [`pick`](Self::pick) would choose for `key`, for diagnostics.
+    /// Neither strategy state nor backend in-flight counts change.
+    pub fn peek(&self, key: &str) -> Option<usize> {
+        self.in_rotation(self.load_balancer.peek(key, &self.pool))
+    }
+
+    /// Drops a custom balancer's pick that is not in rotation.
+    fn in_rotation(&self, selected: Option<usize>) -> Option<usize> {
+        if self.custom_fallback.is_none() {
+            return selected;
+        }
+        selected.filter(|&index| self.pool.is_healthy_index(index))
     }
 
     pub fn pick_without_begin(&mut self, key: &str) -> Option<usize> {
         self.pool.reconcile_readmit();
-        self.load_balancer.pick(key, &self.pool)
+        self.pool.advance_slow_start();
+        self.pool.refresh_zone_saturation();
+        let epoch = self.pool.membership_epoch();
+        if epoch != self.balancer_epoch {
+            self.balancer_epoch = epoch;
+            self.load_balancer.on_membership_change(&self.pool);
+        }
+        let selected = self.load_balancer.pick(key, &self.pool);
+        let Some(fallback) = self.custom_fallback.as_mut() else {
+            return selected;
+        };
+        match selected {
+            Some(index) if !self.pool.is_healthy_index(index) => {
+                if !self.custom_fallback_warned {
+                    self.custom_fallback_warned = true;
+                    warn!(
+                        "balancer '{}' picked backend {} outside the healthy set; using round-robin instead",
+                        self.load_balancer.name(),
+                        index
+                    );
+                }
+                fallback.pick(&self.pool)
+            }
+            selected => selected,
+        }
+    }
+
+    /// Backend with `id` when it is healthy, counted in flight like
+    /// [`pick`](Self::pick). Session affinity uses it to honour a pin.
+    pub fn pick_by_id(&mut self, id: &str) -> Option<usize> {
+        let selected = self.pick_by_id_without_begin(id)?;
+        self.pool.begin_request(selected);
+        Some(selected)
+    }
+
+    pub fn pick_by_id_without_begin(&mut self, id: &str) -> Option<usize> {
+        self.pool.reconcile_readmit();
+        let index = self.backend_index_by_id(id)?;
+        self.pool.is_backend_healthy(index).then_some(index)
     }
 
     pub fn begin_request_if_healthy(&self, index: usize) -> bool {
@@ -83,6 +207,91 @@ impl UpstreamPool {
 
     pub fn finish_request(&mut self, index: usize, latency: Duration, status: Option<u16>) {
         self.pool.finish_request(index, latency, status);
+        self.load_balancer
+            .on_request_complete(index, latency, status);
+        if let Some(detector) = self.outlier_detector.as_mut() {
+            detector.record(index, latency, status);
+        }
+    }
+
+    /// How often [`evaluate_outliers`](Self::evaluate_outliers) should run;
+    /// `None` when the upstream has no outlier detection.
+    pub fn outlier_detection_interval(&self) -> Option<Duration> {
+        self.outlier_detector
+            .as_ref()
+            .map(OutlierDetector::interval)
+    }
+
+    /// Closes the current outlier detection window and ejects the backends
+    /// that failed a check.
+    pub fn evaluate_outliers(&mut self, now: Instant) -> Vec<OutlierEjection> {
+        let Some(detector) = self.outlier_detector.as_mut() else {
+            return Vec::new();
+        };
+        self.pool.reconcile_readmit_at(now);
+        detector.evaluate(&mut self.pool, now)
+    }
+
+    /// Restart the slow-start ramp for the backend at `address`, e.g. after
+    /// DNS refresh added addresses behind it. Returns whether a ramp started.
+    pub fn start_backend_slow_start(&mut self, address: &str, now: Instant) -> bool {
+        let Some(index) = self
+            .backend_indices()
+            .into_iter()
+            .find(|&idx| self.pool.address(idx) == Some(address))
+        else {
+            return false;
+        };
+        self.pool.start_slow_start(index, now)
+    }
+
+    /// Add `backend` to the upstream without disturbing the indices of the
+    /// others, possibly in the slot of a removed one. Fails when its id is
+    /// already taken.
+    pub fn add_backend(&mut self, backend: &Backend) -> Result<usize, String> {
+        if self.backend_index_by_id(&backend.id).is_some() {
+            return Err(format!("backend '{}' already exists", backend.id));
+        }
+        let index = self.pool.add_backend(BackendState::new(backend));
+        if let Some(detector) = self.outlier_detector.as_mut() {
+            detector.forget(index);
+        }
+        Ok(index)
+    }
+
+    /// Apply `backend`'s weight, priority, zone and health check to the
+    /// backend with the same id. Its address cannot change; remove it and
+    /// add a new one instead.
+    pub fn update_backend(&mut self, backend: &Backend) -> Result<usize, String> {
+        let index = self
+            .backend_index_by_id(&backend.id)
+            .ok_or_else(|| format!("no backend '{}'", backend.id))?;
+        if self.pool.address(index) != Some(backend.address.as_str()) {
+            return Err(format!(
+                "backend '{}' cannot change address; remove it and add it again",
+                backend.id
+            ));
+        }
+        self.pool.update_backend(index, backend);
+        Ok(index)
+    }
+
+    /// Remove the backend with `id`. Its index stays reserved until
+    /// in-flight requests finish; the last backend of an upstream cannot go.
+    pub fn remove_backend(&mut self, id: &str) -> Result<usize, String> {
+        let index = self
+            .backend_index_by_id(id)
+            .ok_or_else(|| format!("no backend '{id}'"))?;
+        if self.pool.member_count() == 1 {
+            return Err(format!("backend '{id}' is the last one in the upstream"));
+        }
+        self.pool.remove_backend(index);
+        Ok(index)
+    }
+
+    /// Changes whenever the backend at `index` is updated or removed.
+    pub fn backend_revision(&self, index: usize) -> Option<u64> {
+        self.pool.backend(index).map(BackendState::revision)
     }
 
     pub fn mark_backend_healthy(&mut self, index: usize) -> Option<HealthTransition> {
@@ -104,16 +313,52 @@ impl UpstreamPool {
         self.pool.mark_request_failure(index, reason)
     }
 
+    /// Drain, disable or re-enable the backend at `index`; see
+    /// [`BackendPool::set_admin_state`]. Returns whether the state changed.
+    pub fn set_backend_admin_state(&mut self, index: usize, state: BackendAdminState) -> bool {
+        self.pool.set_admin_state(index, state)
+    }
+
+    pub fn backend_admin_state(&self, index: usize) -> Option<BackendAdminState> {
+        self.pool.admin_state(index)
+    }
+
     pub fn backend_address(&self, index: usize) -> Option<&str> {
         self.pool.address(index)
     }
 
+    pub fn backend_id(&self, index: usize) -> Option<&str> {
+        self.pool.backend(index).map(BackendState::id)
+    }
+
+    pub fn backend_index_by_id(&self, id: &str) -> Option<usize> {
+        (0..self.pool.len())
+            .find(|&idx| self.pool.is_member(idx) && self.backend_id(idx) == Some(id))
+    }
+
+    /// Zone of the backend at `index`, when configured.
+    pub fn backend_zone(&self, index: usize) -> Option<&str> {
+        self.pool.backend(index).and_then(BackendState::zone)
+    }
+
+    /// Zone this instance runs in, whether or not the upstream is
+    /// zone-aware.
+    pub fn local_zone(&self) -> Option<&str> {
+        self.locality
+            .as_ref()
+            .map(|locality| locality.zone.as_str())
+    }
+
+    pub fn session_affinity(&self) -> Option<&RuntimeSessionAffinity> {
+        self.session_affinity.as_ref()
+    }
+
     pub fn backend_count(&self) -> usize {
-        self.pool.len()
+        self.pool.member_count()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.pool.is_empty()
+        self.backend_count() == 0
     }
 
     pub fn backend_indices(&self) -> Vec<usize> {
@@ -121,7 +366,7 @@ impl UpstreamPool {
     }
 
     pub fn is_backend_healthy(&self, index: usize) -> bool {
-        self.pool.is_healthy_index(index)
+        self.pool.is_backend_healthy(index)
     }
 
     pub fn begin_request_for_accounting(&self, index: usize) {
@@ -131,9 +376,13 @@ impl UpstreamPool {
     pub fn backend_runtime_state(&self, index: usize) -> Option<UpstreamBackendRuntimeState> {
         let backend = self.pool.backend(index)?;
         Some(UpstreamBackendRuntimeState {
-            healthy: self.pool.is_healthy_index(index),
+            healthy: self.pool.is_backend_healthy(index),
+            admin_state: backend.admin_state(),
             active_requests: backend.active_requests(),
             ewma_latency_ms: backend.ewma_latency_ms(),
+            priority: backend.priority(),
+            weight: backend.weight(),
+            effective_weight: backend.ramped_weight() as f64 / 100.0,
         })
     }
 
@@ -143,9 +392,12 @@ impl UpstreamPool {
 
     pub fn membership_summary(&self) -> UpstreamPoolMembershipSummary {
         UpstreamPoolMembershipSummary {
-            total_backends: self.pool.len(),
-            healthy_backends: self.pool.healthy_len(),
+            total_backends: self.pool.member_count(),
+            healthy_backends: self.pool.healthy_backend_count(),
             membership_epoch: self.pool.membership_epoch(),
+            panic: self.pool.is_panicking(),
+            zone_saturated: self.pool.is_zone_saturated(),
+            tiers: self.pool.tier_health(),
         }
     }
 
@@ -157,7 +409,7 @@ impl UpstreamPool {
         self.lb_policy.strategy
     }
 
-    pub fn load_balancer_name(&self) -> &'static str {
+    pub fn load_balancer_name(&self) -> &str {
         self.load_balancer.name()
     }
 
@@ -0,0 +1,72 @@
+mod common;
+use spooky_lb::{algorithms::round_robin::RoundRobin, health::BackendAdminState};
+
+use crate::common::{backend_pool, fail};
+
+#[test]
+fn drained_backend_leaves_rotation_until_enabled() {
+    let mut pool = backend_pool(3);
+    let mut rr = RoundRobin::new();
+
+    assert!(pool.set_admin_state(1, BackendAdminState::Draining));
+    assert!(!pool.set_admin_state(1, BackendAdminState::Draining));
+    assert_eq!(pool.membership_epoch(), 1);
+    assert_eq!(pool.admin_state(1), Some(BackendAdminState::Draining));
+    assert!(!pool.is_backend_healthy(1));
+    assert!(pool.backend(1).unwrap().passes_health_checks());
+    for _ in 0..6 {
+        assert_ne!(rr.pick(&pool), Some(1));
+    }
+
+    // Passing checks do not bring a drained backend back.
+    pool.mark_success(1);
+    assert!(!pool.is_healthy_index(1));
+
+    assert!(pool.set_admin_state(1, BackendAdminState::Enabled));
+    assert!(pool.is_healthy_index(1));
+    assert_eq!(pool.healthy_len(), 3);
+}
+
+#[test]
+fn enabling_an_unhealthy_backend_waits_for_its_checks() {
+    let mut pool = backend_pool(2);
+    pool.set_admin_state(0, BackendAdminState::Disabled);
+    fail(&mut pool, 0);
+
+    pool.set_admin_state(0, BackendAdminState::Enabled);
+    assert!(!pool.is_healthy_index(0));
+
+    pool.mark_success(0);
+    assert!(pool.is_healthy_index(0));
+}
+
+#[test]
+fn disabled_backends_stay_out_of_panic_rotation() {
+    let mut pool = backend_pool(4).with_panic_threshold(50);
+    fail(&mut pool, 0);
+    fail(&mut pool, 1);
+    fail(&mut pool, 2);
+    assert!(pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2, 3]);
+
+    pool.set_admin_state(1, BackendAdminState::Disabled);
+    assert_eq!(pool.healthy_indices(), vec![0, 2, 3]);
+
+    // Draining the failed backends leaves a fully healthy remainder.
+    pool.set_admin_state(0, BackendAdminState::Draining);
+    pool.set_admin_state(2, BackendAdminState::Draining);
+    assert!(!pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![3]);
+}
+
+#[test]
+fn admin_state_names_round_trip() {
+    for state in [
+        BackendAdminState::Enabled,
+        BackendAdminState::Draining,
+        BackendAdminState::Disabled,
+    ] {
+        assert_eq!(BackendAdminState::parse(state.as_str()), Some(state));
+    }
+    assert_eq!(BackendAdminState::parse("maintenance"), None);
+}
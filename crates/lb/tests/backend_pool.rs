@@ -61,3 +61,40 @@ fn backend_recovers_after_success_threshold() {
     pool.mark_success(0);
     assert_eq!(pool.healthy_indices(), vec![0]);
 }
+
+#[test]
+fn weight_offsets_follow_membership_changes() {
+    let mut pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 1),
+        create_backend_state("10.0.0.2:1", 2),
+        create_backend_state("10.0.0.3:1", 3),
+    ]);
+    // Offsets are in hundredths of a weight unit.
+    assert_eq!(pool.healthy_weight_total(), 600);
+    assert_eq!(pool.healthy_at_weight_offset(0), Some(0));
+    assert_eq!(pool.healthy_at_weight_offset(99), Some(0));
+    assert_eq!(pool.healthy_at_weight_offset(200), Some(1));
+    assert_eq!(pool.healthy_at_weight_offset(599), Some(2));
+    assert_eq!(pool.healthy_at_weight_offset(600), None);
+
+    for _ in 0..3 {
+        pool.mark_failure(0);
+    }
+    assert_eq!(pool.healthy_weight_total(), 500);
+    let owners: Vec<_> = (0..500)
+        .step_by(100)
+        .filter_map(|offset| pool.healthy_at_weight_offset(offset))
+        .collect();
+    assert_eq!(owners.iter().filter(|&&idx| idx == 1).count(), 2);
+    assert_eq!(owners.iter().filter(|&&idx| idx == 2).count(), 3);
+
+    pool.mark_success(0);
+    assert_eq!(pool.healthy_weight_total(), 600);
+    assert_eq!(
+        (0..600)
+            .step_by(100)
+            .filter(|&offset| pool.healthy_at_weight_offset(offset) == Some(0))
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,57 @@
+mod common;
+use spooky_lb::{
+    algorithms::{bounded_consistent_hash::BoundedConsistentHash, consistent_hash::ConsistentHash},
+    backend_pool::BackendPool,
+};
+
+use crate::common::create_backend_state;
+
+fn pool() -> BackendPool {
+    BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:80", 1),
+        create_backend_state("10.0.0.2:80", 1),
+        create_backend_state("10.0.0.3:80", 1),
+        create_backend_state("10.0.0.4:80", 1),
+    ])
+}
+
+#[test]
+fn bounded_hash_matches_consistent_hash_when_idle() {
+    let pool = pool();
+    let mut bounded = BoundedConsistentHash::new(16, 125);
+    let mut ch = ConsistentHash::new(16);
+
+    for idx in 0..100 {
+        let key = format!("user:{idx}");
+        assert_eq!(bounded.pick(&key, &pool), ch.pick(&key, &pool));
+    }
+}
+
+#[test]
+fn bounded_hash_spills_a_hot_key_past_loaded_backends() {
+    let pool = pool();
+    let mut bounded = BoundedConsistentHash::new(16, 125);
+
+    let mut counts = [0usize; 4];
+    for _ in 0..400 {
+        let idx = bounded.pick("hot-key", &pool).expect("pick");
+        pool.begin_request(idx);
+        counts[idx] += 1;
+    }
+    // Each backend stays within 125% of the 100-request average (+1).
+    assert!(counts.iter().all(|&count| count <= 126), "{counts:?}");
+    assert_eq!(counts.iter().sum::<usize>(), 400);
+}
+
+#[test]
+fn bounded_hash_readonly_pick_skips_excluded_backends() {
+    let pool = pool();
+    let bounded = BoundedConsistentHash::new(16, 150);
+    let primary = bounded.peek("user:1", &pool).expect("primary");
+
+    let alternate = bounded
+        .pick_excluding("user:1", &pool, &[primary])
+        .expect("alternate");
+    assert_ne!(alternate, primary);
+    assert_eq!(bounded.pick_excluding("user:1", &pool, &[0, 1, 2, 3]), None);
+}
@@ -1,5 +1,15 @@
-use spooky_config::config::{Backend, HealthCheck};
-use spooky_lb::backend::BackendState;
+// Each test binary uses a different subset of these helpers.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use spooky_config::{
+    config::{
+        Backend, Config, HealthCheck, Listen, LoadBalancing, Locality, RouteMatch, Tls, Upstream,
+    },
+    runtime::{RuntimeConfig, RuntimeUpstream},
+};
+use spooky_lb::{backend::BackendState, backend_pool::BackendPool, upstream_pool::UpstreamPool};
 
 pub fn create_backend_state(address: &str, weight: u32) -> BackendState {
     let backend = Backend {
@@ -14,6 +24,130 @@ pub fn create_backend_state(address: &str, weight: u32) -> BackendState {
             success_threshold: 1,
             cooldown_ms: 0,
         }),
+        priority: 0,
+        zone: None,
     };
     BackendState::new(&backend)
 }
+
+/// `count` weight-1 backends at `10.0.0.{i + 1}:1`.
+pub fn backend_pool(count: usize) -> BackendPool {
+    BackendPool::new_from_states(
+        (0..count)
+            .map(|idx| create_backend_state(&format!("10.0.0.{}:1", idx + 1), 1))
+            .collect(),
+    )
+}
+
+/// Fails `index` enough times to reach `create_backend_state`'s threshold.
+pub fn fail(pool: &mut BackendPool, index: usize) {
+    for _ in 0..3 {
+        pool.mark_failure(index);
+    }
+}
+
+/// Builds the `api` upstream's pool through `RuntimeConfig::from_config`.
+/// Backends are `backend{i}` at `127.0.0.1:{8001 + i}` with weight 1 and
+/// the upstream balances round-robin until a test overrides it.
+pub struct UpstreamPoolBuilder {
+    upstream: Upstream,
+    locality: Locality,
+}
+
+impl UpstreamPoolBuilder {
+    pub fn new(backends: usize) -> Self {
+        let upstream = Upstream {
+            load_balancing: LoadBalancing {
+                lb_type: "round-robin".to_string(),
+                key: None,
+                table_size: None,
+                hash_balance_factor: None,
+                p2c: None,
+                panic_threshold_percent: None,
+            },
+            auth: Default::default(),
+            host_policy: Default::default(),
+            policy: Default::default(),
+            outlier_detection: None,
+            slow_start: None,
+            zone_aware: None,
+            session_affinity: None,
+            adaptive_admission: None,
+            forwarded_headers: Default::default(),
+            tls: None,
+            route: RouteMatch {
+                path_prefix: Some("/".to_string()),
+                ..Default::default()
+            },
+            backends: (0..backends)
+                .map(|index| Backend {
+                    id: format!("backend{index}"),
+                    address: format!("127.0.0.1:{}", 8001 + index),
+                    weight: 1,
+                    health_check: None,
+                    priority: 0,
+                    zone: None,
+                })
+                .collect(),
+        };
+        Self {
+            upstream,
+            locality: Locality::default(),
+        }
+    }
+
+    pub fn upstream(mut self, configure: impl FnOnce(&mut Upstream)) -> Self {
+        configure(&mut self.upstream);
+        self
+    }
+
+    pub fn backends(mut self, mut configure: impl FnMut(usize, &mut Backend)) -> Self {
+        for (index, backend) in self.upstream.backends.iter_mut().enumerate() {
+            configure(index, backend);
+        }
+        self
+    }
+
+    pub fn locality(mut self, locality: Locality) -> Self {
+        self.locality = locality;
+        self
+    }
+
+    /// The lowered `api` upstream, for tests that build the pool themselves.
+    pub fn runtime_upstream(self) -> RuntimeUpstream {
+        let mut runtime = RuntimeConfig::from_config(&Config {
+            version: 1,
+            listen: Listen {
+                protocol: "http1".to_string(),
+                tls: Tls {
+                    cert: "/tmp/test-cert.pem".to_string(),
+                    key: "/tmp/test-key.pem".to_string(),
+                    ..Tls::default()
+                },
+                ..Listen::default()
+            },
+            listeners: Vec::new(),
+            upstream: HashMap::from([("api".to_string(), self.upstream)]),
+            routes: Vec::new(),
+            load_balancing: None,
+            upstream_tls: Default::default(),
+            log: Default::default(),
+            performance: Default::default(),
+            observability: Default::default(),
+            resilience: Default::default(),
+            security: Default::default(),
+            locality: self.locality,
+        })
+        .unwrap();
+
+        runtime.upstreams.remove("api").unwrap()
+    }
+
+    pub fn try_build(self) -> Result<UpstreamPool, String> {
+        UpstreamPool::from_runtime_upstream(&self.runtime_upstream())
+    }
+
+    pub fn build(self) -> UpstreamPool {
+        self.try_build().unwrap()
+    }
+}
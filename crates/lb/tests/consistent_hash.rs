@@ -66,3 +66,22 @@ fn consistent_hash_ring_size_matches_weighted_healthy_membership() {
     let _ = ch.pick("user:2", &pool);
     assert_eq!(ch.ring.len(), (8 * 3) as usize);
 }
+
+#[test]
+fn consistent_hash_peek_matches_pick_without_caching_the_ring() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 1),
+        create_backend_state("10.0.0.2:1", 1),
+        create_backend_state("10.0.0.3:1", 1),
+    ]);
+
+    let mut ch = ConsistentHash::new(16);
+    let peeked = ch.peek("user:123", &pool);
+    assert!(peeked.is_some());
+    assert_eq!(ch.ring_rebuilds, 0);
+    assert!(ch.ring.is_empty());
+
+    assert_eq!(ch.pick("user:123", &pool), peeked);
+    assert_eq!(ch.peek("user:123", &pool), peeked);
+    assert_eq!(ch.ring_rebuilds, 1);
+}
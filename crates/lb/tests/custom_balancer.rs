@@ -0,0 +1,153 @@
+mod common;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use spooky_lb::{
+    algorithms::round_robin::RoundRobin,
+    backend_pool::BackendPool,
+    balancer::{Balancer, register_balancer, registered_balancers},
+    load_balancing::LoadBalancing,
+    upstream_pool::UpstreamPool,
+};
+
+use crate::common::{UpstreamPoolBuilder, create_backend_state};
+
+type Completions = Arc<Mutex<Vec<(usize, Option<u16>)>>>;
+
+/// Sends everything to the highest healthy index and records its hooks.
+struct LastHealthy {
+    membership_changes: Arc<AtomicUsize>,
+    completions: Completions,
+}
+
+impl Balancer for LastHealthy {
+    fn name(&self) -> &str {
+        "last-healthy"
+    }
+
+    fn pick(&mut self, _key: &str, pool: &BackendPool) -> Option<usize> {
+        pool.healthy.iter().copied().max()
+    }
+
+    fn on_membership_change(&mut self, _pool: &BackendPool) {
+        self.membership_changes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_request_complete(&mut self, index: usize, _latency: Duration, status: Option<u16>) {
+        self.completions.lock().unwrap().push((index, status));
+    }
+}
+
+fn upstream_pool(lb_type: &str) -> Result<UpstreamPool, String> {
+    UpstreamPoolBuilder::new(3)
+        .upstream(|upstream| upstream.load_balancing.lb_type = lb_type.to_string())
+        .try_build()
+}
+
+#[test]
+fn registered_balancer_drives_upstream_picks_and_sees_feedback() {
+    let membership_changes = Arc::new(AtomicUsize::new(0));
+    let completions = Completions::default();
+    let (changes, done) = (membership_changes.clone(), completions.clone());
+    register_balancer("last-healthy", move |policy| {
+        assert_eq!(policy.strategy_name(), "last-healthy");
+        Box::new(LastHealthy {
+            membership_changes: changes.clone(),
+            completions: done.clone(),
+        })
+    })
+    .unwrap();
+    assert!(registered_balancers().contains(&"last-healthy".to_string()));
+
+    let mut pool = upstream_pool("custom:last-healthy").unwrap();
+    assert_eq!(pool.load_balancer_name(), "last-healthy");
+    assert_eq!(pool.lb_policy().strategy_name(), "last-healthy");
+    assert_eq!(pool.pick("/"), Some(2));
+    assert_eq!(membership_changes.load(Ordering::Relaxed), 0);
+
+    for _ in 0..3 {
+        pool.mark_backend_failure_from_active_check(2);
+    }
+    assert_eq!(pool.pick("/"), Some(1));
+    assert_eq!(pool.pick("/"), Some(1));
+    assert_eq!(membership_changes.load(Ordering::Relaxed), 1);
+
+    // Without a read-only pick, alternates fall back to the healthy list.
+    assert_eq!(pool.pick_readonly("/"), None);
+
+    pool.finish_request(1, Duration::from_millis(5), Some(503));
+    pool.finish_request(1, Duration::from_millis(5), None);
+    assert_eq!(
+        *completions.lock().unwrap(),
+        vec![(1, Some(503)), (1, None)]
+    );
+}
+
+/// Always answers backend 0, healthy or not, or an index past the pool.
+struct Stale(usize);
+
+impl Balancer for Stale {
+    fn name(&self) -> &str {
+        "stale"
+    }
+
+    fn pick(&mut self, _key: &str, _pool: &BackendPool) -> Option<usize> {
+        Some(self.0)
+    }
+
+    fn pick_readonly(&self, _key: &str, _pool: &BackendPool) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+#[test]
+fn picks_outside_the_healthy_set_fall_back_to_round_robin() {
+    register_balancer("stale-first", |_| Box::new(Stale(0))).unwrap();
+    register_balancer("stale-missing", |_| Box::new(Stale(7))).unwrap();
+
+    let mut pool = upstream_pool("custom:stale-first").unwrap();
+    assert_eq!(pool.pick("/"), Some(0));
+    for _ in 0..3 {
+        pool.mark_backend_failure_from_active_check(0);
+    }
+    assert_eq!(pool.pick_readonly("/"), None);
+    let mut picked = [pool.pick("/").unwrap(), pool.pick("/").unwrap()];
+    picked.sort_unstable();
+    assert_eq!(picked, [1, 2]);
+
+    let mut pool = upstream_pool("custom:stale-missing").unwrap();
+    assert_eq!(pool.peek("/"), None);
+    assert!(pool.pick("/").is_some_and(|index| index < 3));
+}
+
+#[test]
+fn registry_rejects_duplicate_and_unknown_names() {
+    register_balancer("dup", |_| Box::new(RoundRobin::new())).unwrap();
+    assert!(register_balancer("dup", |_| Box::new(RoundRobin::new())).is_err());
+    assert!(register_balancer("  ", |_| Box::new(RoundRobin::new())).is_err());
+
+    let err = upstream_pool("custom:missing").err().expect("unregistered");
+    assert!(err.contains("no balancer registered as 'missing'"), "{err}");
+}
+
+#[test]
+fn built_ins_pick_the_same_behind_dyn_balancer() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 3),
+        create_backend_state("10.0.0.2:1", 1),
+        create_backend_state("10.0.0.3:1", 2),
+    ]);
+    let mut boxed: Box<dyn Balancer> = Box::new(RoundRobin::new());
+    let mut built_in = LoadBalancing::from_config("round-robin").unwrap();
+    assert_eq!(boxed.name(), built_in.name());
+
+    for _ in 0..12 {
+        assert_eq!(boxed.peek("k", &pool), built_in.peek("k", &pool));
+        assert_eq!(boxed.pick("k", &pool), built_in.pick("k", &pool));
+    }
+}
@@ -17,3 +17,21 @@ fn least_connections_picks_lowest_active() {
     let mut lb = LeastConnections::new();
     assert_eq!(lb.pick(&pool), Some(2));
 }
+
+#[test]
+fn least_connections_divides_active_requests_by_weight() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 1),
+        create_backend_state("10.0.0.2:1", 4),
+    ]);
+    pool.begin_request(0);
+    for _ in 0..3 {
+        pool.begin_request(1);
+    }
+
+    let mut lb = LeastConnections::new();
+    assert_eq!(lb.pick(&pool), Some(1));
+
+    pool.begin_request(1);
+    assert_eq!(lb.pick(&pool), Some(0));
+}
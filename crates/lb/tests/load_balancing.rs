@@ -7,6 +7,9 @@ fn load_balancing_from_config() {
     assert!(LoadBalancing::from_config("random").is_ok());
     assert!(LoadBalancing::from_config("least-connections").is_ok());
     assert!(LoadBalancing::from_config("latency-aware").is_ok());
+    assert!(LoadBalancing::from_config("maglev").is_ok());
+    assert!(LoadBalancing::from_config("consistent-hash-bounded").is_ok());
+    assert!(LoadBalancing::from_config("p2c").is_ok());
     assert!(LoadBalancing::from_config("sticky-cid").is_ok());
     assert!(LoadBalancing::from_config("unknown").is_err());
 }
@@ -0,0 +1,80 @@
+mod common;
+use spooky_lb::{algorithms::maglev::Maglev, backend_pool::BackendPool};
+
+use crate::common::create_backend_state;
+
+fn pool(weights: &[u32]) -> BackendPool {
+    BackendPool::new_from_states(
+        weights
+            .iter()
+            .enumerate()
+            .map(|(idx, &weight)| create_backend_state(&format!("10.0.0.{}:80", idx + 1), weight))
+            .collect(),
+    )
+}
+
+#[test]
+fn maglev_is_stable_and_rebuilds_only_on_membership_change() {
+    let mut pool = pool(&[1, 1, 1]);
+    let mut maglev = Maglev::new(251);
+
+    let first = maglev.pick("user:123", &pool);
+    assert_eq!(maglev.pick("user:123", &pool), first);
+    assert_eq!(maglev.peek("user:123", &pool), first);
+    assert_eq!(maglev.table.len(), 251);
+    assert_eq!(maglev.table_rebuilds, 1);
+
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    let _ = maglev.pick("user:123", &pool);
+    assert_eq!(maglev.table_rebuilds, 2);
+    assert!(!maglev.table.contains(&0));
+}
+
+#[test]
+fn maglev_table_shares_follow_weights() {
+    let pool = pool(&[1, 2, 3]);
+    let mut maglev = Maglev::new(65_537);
+    let _ = maglev.pick("user:1", &pool);
+
+    let mut slots = [0usize; 3];
+    for &idx in &maglev.table {
+        slots[idx as usize] += 1;
+    }
+    for (idx, weight) in [1usize, 2, 3].into_iter().enumerate() {
+        let expected = 65_537 * weight / 6;
+        assert!(slots[idx].abs_diff(expected) <= 3, "{slots:?}");
+    }
+}
+
+#[test]
+fn maglev_moves_few_keys_when_a_backend_leaves() {
+    let mut pool = pool(&[1; 10]);
+    let mut maglev = Maglev::new(65_537);
+    let keys: Vec<String> = (0..10_000).map(|idx| format!("user:{idx}")).collect();
+    let before: Vec<_> = keys.iter().map(|key| maglev.pick(key, &pool)).collect();
+
+    pool.mark_failure(3);
+    pool.mark_failure(3);
+    pool.mark_failure(3);
+    let moved = keys
+        .iter()
+        .zip(&before)
+        .filter(|(key, before)| **before != Some(3) && maglev.pick(key, &pool) != **before)
+        .count();
+    assert!(moved < keys.len() / 50, "moved {moved} keys");
+}
+
+#[test]
+fn maglev_pick_excluding_walks_past_excluded_backends() {
+    let pool = pool(&[1, 1, 1]);
+    let mut maglev = Maglev::new(251);
+    let primary = maglev.pick("user:9", &pool).expect("primary");
+
+    let alternate = maglev
+        .pick_excluding("user:9", &pool, &[primary])
+        .expect("alternate");
+    assert_ne!(alternate, primary);
+    assert_eq!(maglev.pick_excluding("user:9", &pool, &[0, 1, 2]), None);
+}
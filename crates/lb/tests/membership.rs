@@ -0,0 +1,152 @@
+mod common;
+use std::time::Duration;
+
+use spooky_config::config::Backend;
+use spooky_lb::{
+    algorithms::round_robin::RoundRobin, health::BackendAdminState, upstream_pool::UpstreamPool,
+};
+
+use crate::common::{UpstreamPoolBuilder, backend_pool, create_backend_state};
+
+fn spec(id: &str, address: &str, weight: u32, priority: u32) -> Backend {
+    Backend {
+        id: id.to_string(),
+        address: address.to_string(),
+        weight,
+        health_check: None,
+        priority,
+        zone: None,
+    }
+}
+
+#[test]
+fn removed_backend_keeps_its_slot_out_of_rotation() {
+    let mut pool = backend_pool(3);
+    let mut rr = RoundRobin::new();
+    pool.begin_request(1);
+
+    assert!(pool.remove_backend(1));
+    assert!(!pool.remove_backend(1));
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.member_count(), 2);
+    assert_eq!(pool.all_indices(), vec![0, 2]);
+    assert_eq!(pool.healthy_indices(), vec![0, 2]);
+    assert_eq!(pool.membership_epoch(), 1);
+    for _ in 0..6 {
+        assert_ne!(rr.pick(&pool), Some(1));
+    }
+
+    // The request picked before removal still completes against its index.
+    pool.finish_request(1, Duration::from_millis(5), Some(200));
+    assert_eq!(pool.backend(1).unwrap().active_requests(), 0);
+
+    // Removed backends cannot be brought back through the admin state.
+    assert!(!pool.set_admin_state(1, BackendAdminState::Enabled));
+    pool.mark_success(1);
+    assert!(!pool.is_healthy_index(1));
+}
+
+#[test]
+fn added_backend_joins_rotation_after_existing_indices() {
+    let mut pool = backend_pool(2);
+    let mut rr = RoundRobin::new();
+
+    let index = pool.add_backend(create_backend_state("10.0.0.9:1", 1));
+    assert_eq!(index, 2);
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2]);
+    assert!((0..6).any(|_| rr.pick(&pool) == Some(2)));
+}
+
+#[test]
+fn updates_reshape_weights_and_tiers() {
+    let mut pool = backend_pool(2);
+    let revision = pool.backend(1).unwrap().revision();
+
+    assert!(pool.update_backend(1, &spec("b", "10.0.0.2:1", 3, 0)));
+    assert_ne!(pool.backend(1).unwrap().revision(), revision);
+    assert!(!pool.has_uniform_weights());
+    assert_eq!(pool.healthy_weight_total(), 400);
+
+    // A backend moved to a standby tier leaves the rotation while the
+    // primary tier is healthy.
+    assert!(pool.update_backend(1, &spec("b", "10.0.0.2:1", 3, 1)));
+    assert_eq!(pool.tier_health().len(), 2);
+    assert_eq!(pool.healthy_indices(), vec![0]);
+
+    assert!(pool.remove_backend(1));
+    assert!(!pool.update_backend(1, &spec("b", "10.0.0.2:1", 1, 0)));
+    assert_eq!(pool.tier_health().len(), 1);
+}
+
+#[test]
+fn upstream_membership_is_keyed_by_backend_id() {
+    let mut pool = UpstreamPoolBuilder::new(2).build();
+
+    assert!(
+        pool.add_backend(&spec("backend0", "127.0.0.1:9000", 1, 0))
+            .is_err()
+    );
+    assert_eq!(
+        pool.add_backend(&spec("extra", "127.0.0.1:9000", 1, 0)),
+        Ok(2)
+    );
+    assert_eq!(pool.backend_count(), 3);
+
+    let err = pool
+        .update_backend(&spec("extra", "127.0.0.1:9001", 1, 0))
+        .unwrap_err();
+    assert!(err.contains("cannot change address"), "{err}");
+    assert_eq!(
+        pool.update_backend(&spec("extra", "127.0.0.1:9000", 5, 0)),
+        Ok(2)
+    );
+    assert_eq!(pool.backend_runtime_state(2).unwrap().weight, 5);
+
+    assert_eq!(pool.remove_backend("backend0"), Ok(0));
+    assert_eq!(pool.backend_index_by_id("backend0"), None);
+    assert_eq!(pool.backend_indices(), vec![1, 2]);
+    assert_eq!(pool.membership_summary().total_backends, 2);
+
+    // Nothing is in flight to the removed slot, so re-adding takes it back.
+    assert_eq!(
+        pool.add_backend(&spec("backend0", "127.0.0.1:8001", 1, 0)),
+        Ok(0)
+    );
+    assert!(pool.remove_backend("backend1").is_ok());
+    assert!(pool.remove_backend("extra").is_ok());
+    let err = pool.remove_backend("backend0").unwrap_err();
+    assert!(err.contains("last one"), "{err}");
+    assert_eq!(pool.pick("/"), Some(0));
+}
+
+#[test]
+fn removed_slots_are_reused_once_no_request_holds_them() {
+    let mut pool = backend_pool(3);
+    pool.begin_request(1);
+    assert!(pool.remove_backend(1));
+    let revision = pool.backend(1).unwrap().revision();
+
+    // A request still holds slot 1.
+    assert_eq!(pool.add_backend(create_backend_state("10.0.0.8:1", 1)), 3);
+    assert_eq!(pool.len(), 4);
+
+    pool.finish_request(1, Duration::from_millis(5), Some(200));
+    assert!(pool.remove_backend(3));
+    assert_eq!(pool.add_backend(create_backend_state("10.0.0.9:1", 1)), 1);
+    assert_eq!(pool.add_backend(create_backend_state("10.0.0.10:1", 1)), 3);
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.address(1), Some("10.0.0.9:1"));
+    assert!(pool.backend(1).unwrap().revision() > revision);
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn pools_reject_backend_ids_used_twice() {
+    let mut upstream = UpstreamPoolBuilder::new(2).runtime_upstream();
+    upstream.backends[1].backend.id = upstream.backends[0].backend.id.clone();
+
+    let err = UpstreamPool::from_runtime_upstream(&upstream)
+        .err()
+        .expect("duplicate id");
+    assert!(err.contains("duplicate backend id 'backend0'"), "{err}");
+}
@@ -0,0 +1,156 @@
+mod common;
+use std::time::{Duration, Instant};
+
+use spooky_config::config::OutlierDetection;
+use spooky_lb::{outlier::OutlierEjectionReason, upstream_pool::UpstreamPool};
+
+use crate::common::UpstreamPoolBuilder;
+
+fn detection() -> OutlierDetection {
+    OutlierDetection {
+        interval_ms: 10_000,
+        min_requests: 10,
+        min_backends: 3,
+        success_rate_min_percent: None,
+        success_rate_stdev_factor: None,
+        latency_p95_ratio_percent: None,
+        max_ejection_percent: 100,
+        base_ejection_ms: 1_000,
+        max_ejection_ms: 3_000,
+    }
+}
+
+fn pool_with(backends: usize, detection: OutlierDetection) -> UpstreamPool {
+    UpstreamPoolBuilder::new(backends)
+        .backends(|_, backend| backend.weight = 100)
+        .upstream(|upstream| upstream.outlier_detection = Some(detection))
+        .build()
+}
+
+/// Records `requests` responses for `index`, the first `failures` of them 5xx.
+fn serve(pool: &mut UpstreamPool, index: usize, requests: u32, failures: u32, latency_ms: u64) {
+    for request in 0..requests {
+        let status = if request < failures { 503 } else { 200 };
+        pool.begin_request_for_accounting(index);
+        pool.finish_request(index, Duration::from_millis(latency_ms), Some(status));
+    }
+}
+
+#[test]
+fn success_rate_far_below_pool_mean_is_ejected() {
+    let mut pool = pool_with(
+        5,
+        OutlierDetection {
+            success_rate_stdev_factor: Some(1.5),
+            ..detection()
+        },
+    );
+    serve(&mut pool, 0, 20, 10, 5);
+    for index in 1..5 {
+        serve(&mut pool, index, 20, 0, 5);
+    }
+
+    let ejections = pool.evaluate_outliers(Instant::now());
+    assert_eq!(ejections.len(), 1);
+    assert_eq!(ejections[0].index, 0);
+    assert_eq!(ejections[0].reason, OutlierEjectionReason::SuccessRateStdev);
+    assert!(!pool.is_backend_healthy(0));
+    for _ in 0..8 {
+        assert_ne!(
+            pool.pick("key"),
+            Some(0),
+            "ejected backend must not be picked"
+        );
+    }
+}
+
+#[test]
+fn backends_below_min_requests_are_not_judged() {
+    let mut pool = pool_with(
+        3,
+        OutlierDetection {
+            success_rate_min_percent: Some(90),
+            ..detection()
+        },
+    );
+    serve(&mut pool, 0, 9, 9, 5);
+    serve(&mut pool, 1, 20, 0, 5);
+
+    assert!(pool.evaluate_outliers(Instant::now()).is_empty());
+    assert!(pool.is_backend_healthy(0));
+}
+
+#[test]
+fn slow_p95_against_pool_median_is_ejected() {
+    let mut pool = pool_with(
+        3,
+        OutlierDetection {
+            latency_p95_ratio_percent: Some(300),
+            ..detection()
+        },
+    );
+    serve(&mut pool, 0, 20, 0, 10);
+    serve(&mut pool, 1, 20, 0, 12);
+    serve(&mut pool, 2, 20, 0, 400);
+
+    let ejections = pool.evaluate_outliers(Instant::now());
+    assert_eq!(ejections.len(), 1);
+    assert_eq!(ejections[0].index, 2);
+    assert_eq!(ejections[0].reason, OutlierEjectionReason::LatencyP95);
+}
+
+#[test]
+fn ejections_respect_max_percent_and_keep_one_backend_healthy() {
+    let mut pool = pool_with(
+        4,
+        OutlierDetection {
+            success_rate_min_percent: Some(90),
+            max_ejection_percent: 50,
+            ..detection()
+        },
+    );
+    for index in 0..4 {
+        serve(&mut pool, index, 20, 20, 5);
+    }
+    assert_eq!(pool.evaluate_outliers(Instant::now()).len(), 2);
+    assert_eq!(pool.membership_summary().healthy_backends, 2);
+
+    let mut pool = pool_with(
+        2,
+        OutlierDetection {
+            success_rate_min_percent: Some(90),
+            ..detection()
+        },
+    );
+    serve(&mut pool, 0, 20, 20, 5);
+    serve(&mut pool, 1, 20, 20, 5);
+    assert_eq!(pool.evaluate_outliers(Instant::now()).len(), 1);
+    assert_eq!(pool.membership_summary().healthy_backends, 1);
+}
+
+#[test]
+fn repeat_ejections_double_up_to_the_max_and_backends_are_readmitted() {
+    let mut pool = pool_with(
+        3,
+        OutlierDetection {
+            success_rate_min_percent: Some(90),
+            ..detection()
+        },
+    );
+    let mut now = Instant::now();
+    let mut durations = Vec::new();
+    for _ in 0..3 {
+        serve(&mut pool, 0, 20, 20, 5);
+        let ejections = pool.evaluate_outliers(now);
+        assert_eq!(ejections.len(), 1);
+        durations.push(ejections[0].duration);
+
+        now += ejections[0].duration;
+        assert!(pool.evaluate_outliers(now).is_empty());
+        assert!(pool.is_backend_healthy(0), "re-admitted once ejection ends");
+    }
+    assert_eq!(
+        durations,
+        [1_000, 2_000, 3_000].map(Duration::from_millis).to_vec()
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+use std::time::Duration;
+
+use spooky_config::config::P2cMetric;
+use spooky_lb::{algorithms::p2c::PowerOfTwoChoices, backend_pool::BackendPool};
+
+use crate::common::create_backend_state;
+
+fn pool_of(weights: &[u32]) -> BackendPool {
+    BackendPool::new_from_states(
+        weights
+            .iter()
+            .enumerate()
+            .map(|(idx, &weight)| create_backend_state(&format!("10.0.0.{}:1", idx + 1), weight))
+            .collect(),
+    )
+}
+
+#[test]
+fn p2c_compares_the_whole_pool_when_it_is_small() {
+    let pool = pool_of(&[1, 1]);
+    pool.begin_request(0);
+
+    let mut lb = PowerOfTwoChoices::new(2, P2cMetric::LeastRequest, true);
+    assert!((0..50).all(|_| lb.pick(&pool) == Some(1)));
+}
+
+#[test]
+fn p2c_never_picks_the_most_loaded_backend() {
+    let pool = pool_of(&[1, 1, 1, 1, 1]);
+    for _ in 0..10 {
+        pool.begin_request(3);
+    }
+
+    let lb = PowerOfTwoChoices::new(2, P2cMetric::LeastRequest, false);
+    let mut counts = [0usize; 5];
+    for _ in 0..2_000 {
+        counts[lb.pick_readonly(&pool).expect("pick")] += 1;
+    }
+    assert_eq!(counts[3], 0, "{counts:?}");
+    assert!(
+        counts
+            .iter()
+            .enumerate()
+            .all(|(idx, &count)| idx == 3 || count > 300)
+    );
+}
+
+#[test]
+fn p2c_weighted_sampling_favours_heavy_backends() {
+    let pool = pool_of(&[1, 1, 8]);
+    let share = |weighted: bool| {
+        let lb = PowerOfTwoChoices::new(2, P2cMetric::LeastRequest, weighted);
+        (0..3_000)
+            .filter(|_| lb.pick_readonly(&pool) == Some(2))
+            .count()
+    };
+
+    assert!(share(true) > 2_000);
+    assert!((800..1_200).contains(&share(false)));
+}
+
+#[test]
+fn p2c_latency_metric_prefers_unsampled_then_faster_backends() {
+    let mut pool = pool_of(&[1, 1]);
+    let lb = PowerOfTwoChoices::new(2, P2cMetric::Latency, true);
+
+    pool.begin_request(0);
+    pool.finish_request(0, Duration::from_millis(5), Some(200));
+    assert_eq!(lb.pick_readonly(&pool), Some(1));
+
+    pool.begin_request(1);
+    pool.finish_request(1, Duration::from_millis(50), Some(200));
+    assert_eq!(lb.pick_readonly(&pool), Some(0));
+}
+
+#[test]
+fn p2c_excluding_samples_only_the_remaining_backends() {
+    let pool = pool_of(&[1, 1, 1, 1]);
+    let lb = PowerOfTwoChoices::new(3, P2cMetric::LeastRequest, true);
+
+    for _ in 0..200 {
+        let picked = lb.pick_excluding(&pool, &[0, 2]).expect("pick");
+        assert!(picked == 1 || picked == 3);
+    }
+    assert_eq!(lb.pick_excluding(&pool, &[0, 1, 2]), Some(3));
+    assert_eq!(lb.pick_excluding(&pool, &[0, 1, 2, 3]), None);
+}
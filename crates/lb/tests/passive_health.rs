@@ -19,6 +19,8 @@ fn passively_ejected_backend_recovers_after_cooldown() {
             success_threshold: 1,
             cooldown_ms: 10_000,
         }),
+        priority: 0,
+        zone: None,
     };
     let mut pool = BackendPool::new_from_states(vec![BackendState::new(&backend)]);
     assert_eq!(pool.healthy_len(), 1);
@@ -0,0 +1,175 @@
+mod common;
+use spooky_lb::{
+    algorithms::round_robin::RoundRobin,
+    backend_pool::{BackendPool, TierHealth},
+};
+
+use crate::common::{UpstreamPoolBuilder, create_backend_state, fail};
+
+/// Equal-weight backends, `priorities[i]` being backend `i`'s tier.
+fn tiered_pool(priorities: &[u32]) -> BackendPool {
+    BackendPool::new_from_states(
+        priorities
+            .iter()
+            .enumerate()
+            .map(|(idx, &priority)| {
+                let mut state = create_backend_state(&format!("10.0.0.{}:1", idx + 1), 1);
+                state.priority = priority;
+                state
+            })
+            .collect(),
+    )
+}
+
+fn loads(pool: &BackendPool) -> Vec<u32> {
+    pool.tier_health()
+        .iter()
+        .map(|tier| tier.load_percent)
+        .collect()
+}
+
+#[test]
+fn backups_stay_idle_while_primaries_have_capacity() {
+    let mut pool = tiered_pool(&[0, 0, 0, 0, 1, 1]);
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2, 3]);
+    assert_eq!(pool.healthy_backend_count(), 6);
+    assert!(pool.is_backend_healthy(4));
+
+    // 3 of 4 healthy is above the overprovisioned 1 / 1.4.
+    fail(&mut pool, 0);
+    assert_eq!(loads(&pool), vec![100, 0]);
+    assert_eq!(pool.healthy_indices(), vec![1, 2, 3]);
+}
+
+#[test]
+fn traffic_overflows_proportionally_to_the_next_tier() {
+    let mut pool = tiered_pool(&[0, 0, 0, 0, 1, 1]);
+    fail(&mut pool, 0);
+    fail(&mut pool, 1);
+    assert_eq!(loads(&pool), vec![70, 30]);
+    assert_eq!(pool.healthy_indices(), vec![2, 3, 4, 5]);
+
+    let mut lb = RoundRobin::new();
+    let backups = (0..1_000)
+        .filter(|_| lb.pick(&pool).expect("pick") >= 4)
+        .count();
+    assert!((280..=320).contains(&backups), "{backups}");
+
+    fail(&mut pool, 2);
+    fail(&mut pool, 3);
+    assert_eq!(loads(&pool), vec![0, 100]);
+    assert_eq!(pool.healthy_indices(), vec![4, 5]);
+
+    pool.mark_success(0);
+    pool.mark_success(1);
+    pool.mark_success(2);
+    assert_eq!(loads(&pool), vec![100, 0]);
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2]);
+}
+
+#[test]
+fn tier_shares_are_scaled_up_when_every_tier_is_short() {
+    let mut pool = tiered_pool(&[0, 0, 0, 0, 1, 1, 1, 1]);
+    for index in [0, 1, 2, 4, 5, 6] {
+        fail(&mut pool, index);
+    }
+
+    assert_eq!(
+        pool.tier_health(),
+        vec![
+            TierHealth {
+                priority: 0,
+                remote: false,
+                total_backends: 4,
+                healthy_backends: 1,
+                load_percent: 50,
+            },
+            TierHealth {
+                priority: 1,
+                remote: false,
+                total_backends: 4,
+                healthy_backends: 1,
+                load_percent: 50,
+            },
+        ]
+    );
+}
+
+#[test]
+fn panic_puts_every_backend_in_rotation() {
+    let mut pool = tiered_pool(&[0, 0, 0, 0]).with_panic_threshold(50);
+    fail(&mut pool, 0);
+    fail(&mut pool, 1);
+    assert!(!pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![2, 3]);
+
+    fail(&mut pool, 2);
+    assert!(pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2, 3]);
+    assert_eq!(pool.healthy_backend_count(), 1);
+
+    pool.mark_success(0);
+    assert!(!pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![0, 3]);
+}
+
+#[test]
+fn healthy_backups_keep_the_pool_out_of_panic() {
+    let mut pool = tiered_pool(&[0, 0, 1, 1]).with_panic_threshold(50);
+    fail(&mut pool, 0);
+    fail(&mut pool, 1);
+
+    assert!(!pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![2, 3]);
+}
+
+#[test]
+fn membership_summary_reports_tiers_and_panic() {
+    let pool = UpstreamPoolBuilder::new(3)
+        .backends(|index, backend| backend.priority = [0, 0, 5][index])
+        .upstream(|upstream| upstream.load_balancing.panic_threshold_percent = Some(30))
+        .build();
+
+    let summary = pool.membership_summary();
+    assert!(!summary.panic);
+    assert_eq!(summary.healthy_backends, 3);
+    assert_eq!(
+        summary
+            .tiers
+            .iter()
+            .map(|tier| (tier.priority, tier.total_backends, tier.load_percent))
+            .collect::<Vec<_>>(),
+        vec![(0, 2, 100), (5, 1, 0)]
+    );
+    assert_eq!(pool.backend_runtime_state(2).unwrap().priority, 5);
+    assert!(pool.is_backend_healthy(2));
+}
+
+#[test]
+fn runtime_state_reports_weight_without_tier_scaling() {
+    let mut pool = UpstreamPoolBuilder::new(6)
+        .backends(|index, backend| backend.priority = [0, 0, 0, 0, 1, 1][index])
+        .build();
+    for index in [0, 1] {
+        for _ in 0..3 {
+            pool.mark_backend_failure_from_active_check(index);
+        }
+    }
+
+    // The tiers now split traffic 70 / 30, which scales every member's
+    // balancing weight, but the reported weight stays the configured one.
+    assert_eq!(
+        pool.membership_summary()
+            .tiers
+            .iter()
+            .map(|tier| tier.load_percent)
+            .collect::<Vec<_>>(),
+        vec![70, 30]
+    );
+    for index in [2, 4] {
+        assert_eq!(
+            pool.backend_runtime_state(index).unwrap().effective_weight,
+            1.0
+        );
+    }
+}
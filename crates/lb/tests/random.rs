@@ -0,0 +1,33 @@
+mod common;
+use spooky_lb::{algorithms::random::Random, backend_pool::BackendPool};
+
+use crate::common::create_backend_state;
+
+#[test]
+fn random_picks_in_proportion_to_weight() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 1),
+        create_backend_state("10.0.0.2:1", 3),
+    ]);
+    let mut lb = Random::new();
+
+    let mut counts = [0usize; 2];
+    for _ in 0..8_000 {
+        counts[lb.pick(&pool).expect("pick")] += 1;
+    }
+    assert!((1_600..2_400).contains(&counts[0]), "{counts:?}");
+}
+
+#[test]
+fn random_skips_unhealthy_backends() {
+    let mut pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 5),
+        create_backend_state("10.0.0.2:1", 1),
+    ]);
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    let lb = Random::new();
+
+    assert!((0..100).all(|_| lb.pick_readonly(&pool) == Some(1)));
+}
@@ -42,3 +42,75 @@ fn no_healthy_backends_returns_none() {
     let mut rr = RoundRobin::new();
     assert!(rr.pick(&pool).is_none());
 }
+
+#[test]
+fn peek_reports_next_pick_without_advancing() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("127.0.0.1:1", 1),
+        create_backend_state("127.0.0.1:2", 1),
+    ]);
+    let mut rr = RoundRobin::new();
+
+    assert_eq!(rr.peek(&pool), Some(0));
+    assert_eq!(rr.peek(&pool), Some(0));
+    assert_eq!(rr.pick(&pool), Some(0));
+    assert_eq!(rr.peek(&pool), Some(1));
+    assert_eq!(rr.pick(&pool), Some(1));
+}
+
+#[test]
+fn weighted_round_robin_interleaves_by_weight() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("127.0.0.1:1", 5),
+        create_backend_state("127.0.0.1:2", 1),
+        create_backend_state("127.0.0.1:3", 1),
+    ]);
+    let mut rr = RoundRobin::new();
+
+    assert_eq!(rr.peek(&pool), Some(0));
+    let picks: Vec<usize> = (0..14).filter_map(|_| rr.pick(&pool)).collect();
+    assert_eq!(picks, vec![0, 0, 1, 0, 2, 0, 0, 0, 0, 1, 0, 2, 0, 0]);
+}
+
+#[test]
+fn weighted_round_robin_skips_unhealthy_backends() {
+    let mut pool = BackendPool::new_from_states(vec![
+        create_backend_state("127.0.0.1:1", 3),
+        create_backend_state("127.0.0.1:2", 2),
+        create_backend_state("127.0.0.1:3", 1),
+    ]);
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    pool.mark_failure(0);
+    let mut rr = RoundRobin::new();
+
+    let picks: Vec<usize> = (0..6).filter_map(|_| rr.pick(&pool)).collect();
+    assert_eq!(picks, vec![1, 2, 1, 1, 2, 1]);
+}
+
+#[test]
+fn weighted_readonly_pick_follows_weights() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("127.0.0.1:1", 3),
+        create_backend_state("127.0.0.1:2", 1),
+    ]);
+    let rr = RoundRobin::new();
+
+    let picks: Vec<usize> = (0..8).filter_map(|_| rr.pick_readonly(&pool)).collect();
+    assert_eq!(picks, vec![0, 0, 1, 0, 0, 0, 1, 0]);
+}
+
+#[test]
+fn readonly_picks_interleave_like_picks() {
+    let pool = BackendPool::new_from_states(vec![
+        create_backend_state("127.0.0.1:1", 1),
+        create_backend_state("127.0.0.1:2", 2),
+        create_backend_state("127.0.0.1:3", 3),
+    ]);
+    let mut rr = RoundRobin::new();
+
+    let readonly: Vec<usize> = (0..12).filter_map(|_| rr.pick_readonly(&pool)).collect();
+    let picks: Vec<usize> = (0..12).filter_map(|_| rr.pick(&pool)).collect();
+    assert_eq!(readonly, picks);
+    assert_eq!(&readonly[..6], &[2, 1, 0, 2, 1, 2]);
+}
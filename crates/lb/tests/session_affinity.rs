@@ -0,0 +1,53 @@
+mod common;
+use std::time::Duration;
+
+use spooky_config::config::SessionAffinity;
+use spooky_lb::upstream_pool::UpstreamPool;
+
+use crate::common::UpstreamPoolBuilder;
+
+fn upstream_pool(ids: &[&str]) -> UpstreamPool {
+    UpstreamPoolBuilder::new(ids.len())
+        .backends(|index, backend| {
+            backend.id = ids[index].to_string();
+            backend.address = format!("{}.internal:8080", ids[index]);
+        })
+        .upstream(|upstream| {
+            upstream.session_affinity = Some(SessionAffinity {
+                ttl_secs: Some(3_600),
+                ..SessionAffinity::default()
+            });
+        })
+        .build()
+}
+
+#[test]
+fn pins_resolve_by_backend_id_across_reordering() {
+    let mut pool = upstream_pool(&["alpha", "beta", "gamma"]);
+    let affinity = pool.session_affinity().expect("affinity");
+    assert_eq!(affinity.cookie_name, "spooky_affinity");
+    assert_eq!(affinity.ttl, Some(Duration::from_secs(3_600)));
+    assert_eq!(pool.backend_id(1), Some("beta"));
+    assert_eq!(pool.pick_by_id("beta"), Some(1));
+    assert_eq!(pool.backend_runtime_state(1).unwrap().active_requests, 1);
+
+    let mut reordered = upstream_pool(&["gamma", "beta", "alpha"]);
+    assert_eq!(reordered.pick_by_id_without_begin("alpha"), Some(2));
+    assert_eq!(
+        reordered.backend_runtime_state(2).unwrap().active_requests,
+        0
+    );
+    assert_eq!(reordered.pick_by_id("delta"), None);
+}
+
+#[test]
+fn pins_to_unhealthy_backends_are_not_honoured() {
+    let mut pool = upstream_pool(&["alpha", "beta"]);
+    for _ in 0..3 {
+        pool.mark_backend_failure_from_active_check(0);
+    }
+
+    assert_eq!(pool.pick_by_id("alpha"), None);
+    assert_eq!(pool.pick("/"), Some(1));
+    assert_eq!(pool.pick_by_id("beta"), Some(1));
+}
@@ -0,0 +1,133 @@
+mod common;
+use std::time::{Duration, Instant};
+
+use spooky_config::{
+    config::{SlowStart, SlowStartCurve},
+    runtime::RuntimeSlowStart,
+};
+use spooky_lb::{
+    algorithms::round_robin::RoundRobin, backend_pool::BackendPool, slow_start::ramp_percent,
+    upstream_pool::UpstreamPool,
+};
+
+use crate::common::{UpstreamPoolBuilder, create_backend_state, fail};
+
+const WINDOW: Duration = Duration::from_secs(100);
+
+fn policy(curve: SlowStartCurve) -> RuntimeSlowStart {
+    RuntimeSlowStart {
+        window: WINDOW,
+        min_weight_percent: 10,
+        curve,
+    }
+}
+
+fn recovered_pool() -> (BackendPool, Instant) {
+    let mut pool = BackendPool::new_from_states(vec![
+        create_backend_state("10.0.0.1:1", 1),
+        create_backend_state("10.0.0.2:1", 1),
+    ])
+    .with_slow_start(Some(policy(SlowStartCurve::Linear)));
+    fail(&mut pool, 0);
+    let start = Instant::now();
+    pool.mark_success(0);
+    (pool, start)
+}
+
+#[test]
+fn ramp_percent_follows_the_curve_in_steps() {
+    let linear = policy(SlowStartCurve::Linear);
+    let aggressive = policy(SlowStartCurve::Aggressive);
+    let at = |secs| Duration::from_secs(secs);
+
+    assert_eq!(ramp_percent(&linear, at(0)), 10);
+    assert_eq!(ramp_percent(&linear, at(25)), 30);
+    assert_eq!(ramp_percent(&aggressive, at(25)), 50);
+    assert_eq!(ramp_percent(&linear, at(50)), 50);
+    assert_eq!(ramp_percent(&linear, at(99)), 90);
+    assert_eq!(ramp_percent(&linear, WINDOW), 100);
+    assert_eq!(ramp_percent(&aggressive, at(500)), 100);
+}
+
+#[test]
+fn recovered_backend_ramps_to_full_weight() {
+    let (mut pool, start) = recovered_pool();
+    assert!(pool.slow_start_active());
+    assert_eq!(pool.backends[0].effective_weight(), 10);
+    assert_eq!(pool.healthy_weight_total(), 110);
+    assert!(!pool.has_uniform_weights());
+
+    let epoch = pool.membership_epoch();
+    pool.advance_slow_start_at(start + WINDOW / 2);
+    assert_eq!(pool.backends[0].weight_percent(), 50);
+    assert_eq!(pool.healthy_weight_total(), 150);
+    assert_ne!(pool.membership_epoch(), epoch);
+
+    // Within the same step nothing changes.
+    let epoch = pool.membership_epoch();
+    pool.advance_slow_start_at(start + WINDOW / 2 + Duration::from_secs(1));
+    assert_eq!(pool.membership_epoch(), epoch);
+
+    pool.advance_slow_start_at(start + WINDOW + Duration::from_secs(1));
+    assert!(!pool.slow_start_active());
+    assert_eq!(pool.backends[0].weight_percent(), 100);
+    assert!(pool.has_uniform_weights());
+}
+
+#[test]
+fn ramping_backend_gets_its_share_of_weighted_picks() {
+    let (pool, _) = recovered_pool();
+    let mut lb = RoundRobin::new();
+
+    let mut counts = [0usize; 2];
+    for _ in 0..1_100 {
+        counts[lb.pick(&pool).expect("pick")] += 1;
+    }
+    assert_eq!(counts, [100, 1_000]);
+}
+
+#[test]
+fn ejection_during_ramp_resets_the_backend() {
+    let (mut pool, _) = recovered_pool();
+    fail(&mut pool, 0);
+
+    assert!(!pool.slow_start_active());
+    assert_eq!(pool.backends[0].weight_percent(), 100);
+    assert_eq!(pool.healthy_weight_total(), 100);
+}
+
+#[test]
+fn pools_without_slow_start_never_ramp() {
+    let mut pool = BackendPool::new_from_states(vec![create_backend_state("10.0.0.1:1", 2)]);
+    fail(&mut pool, 0);
+    pool.mark_success(0);
+
+    assert!(!pool.slow_start_active());
+    assert_eq!(pool.backends[0].effective_weight(), 200);
+    assert!(!pool.start_slow_start(0, Instant::now()));
+}
+
+fn upstream_pool(slow_start: SlowStart) -> UpstreamPool {
+    UpstreamPoolBuilder::new(2)
+        .backends(|_, backend| backend.weight = 4)
+        .upstream(|upstream| upstream.slow_start = Some(slow_start))
+        .build()
+}
+
+#[test]
+fn runtime_state_reports_effective_weight_of_added_backend() {
+    let mut pool = upstream_pool(SlowStart {
+        window_ms: 60_000,
+        min_weight_percent: 25,
+        curve: SlowStartCurve::Linear,
+    });
+    let state = pool.backend_runtime_state(1).unwrap();
+    assert_eq!((state.weight, state.effective_weight), (4, 4.0));
+
+    assert!(pool.start_backend_slow_start("127.0.0.1:8002", Instant::now()));
+    assert!(!pool.start_backend_slow_start("127.0.0.1:9999", Instant::now()));
+
+    let state = pool.backend_runtime_state(1).unwrap();
+    assert_eq!((state.weight, state.effective_weight), (4, 1.0));
+    assert_eq!(pool.backend_runtime_state(0).unwrap().effective_weight, 4.0);
+}
@@ -12,9 +12,19 @@ fn upstream_pool_from_config() {
         load_balancing: spooky_config::config::LoadBalancing {
             lb_type: "round-robin".to_string(),
             key: None,
+            table_size: None,
+            hash_balance_factor: None,
+            p2c: None,
+            panic_threshold_percent: None,
         },
         auth: Default::default(),
         host_policy: Default::default(),
+        policy: Default::default(),
+        outlier_detection: None,
+        slow_start: None,
+        zone_aware: None,
+        session_affinity: None,
+        adaptive_admission: None,
         forwarded_headers: Default::default(),
         tls: None,
         route: RouteMatch {
@@ -34,6 +44,8 @@ fn upstream_pool_from_config() {
                     success_threshold: 2,
                     cooldown_ms: 10000,
                 }),
+                priority: 0,
+                zone: None,
             },
             Backend {
                 id: "backend2".to_string(),
@@ -47,6 +59,8 @@ fn upstream_pool_from_config() {
                     success_threshold: 2,
                     cooldown_ms: 10000,
                 }),
+                priority: 0,
+                zone: None,
             },
         ],
     };
@@ -66,6 +80,7 @@ fn upstream_pool_from_config() {
         },
         listeners: Vec::new(),
         upstream: upstreams,
+        routes: Vec::new(),
         load_balancing: None,
         upstream_tls: Default::default(),
         log: Default::default(),
@@ -73,6 +88,7 @@ fn upstream_pool_from_config() {
         observability: Default::default(),
         resilience: Default::default(),
         security: Default::default(),
+        locality: Default::default(),
     })
     .unwrap();
 
@@ -0,0 +1,155 @@
+mod common;
+use spooky_config::config::{Locality, ZoneAware};
+use spooky_lb::{
+    algorithms::round_robin::RoundRobin, backend_pool::BackendPool, upstream_pool::UpstreamPool,
+};
+
+use crate::common::{UpstreamPoolBuilder, create_backend_state, fail};
+
+/// Equal-weight backends, `zones[i]` being backend `i`'s zone and priority.
+fn zoned_pool(zones: &[(&str, u32)]) -> BackendPool {
+    BackendPool::new_from_states(
+        zones
+            .iter()
+            .enumerate()
+            .map(|(idx, &(zone, priority))| {
+                let mut state = create_backend_state(&format!("10.0.0.{}:1", idx + 1), 1);
+                state.zone = Some(zone.to_string());
+                state.priority = priority;
+                state
+            })
+            .collect(),
+    )
+}
+
+fn loads(pool: &BackendPool) -> Vec<(u32, bool, u32)> {
+    pool.tier_health()
+        .iter()
+        .map(|tier| (tier.priority, tier.remote, tier.load_percent))
+        .collect()
+}
+
+#[test]
+fn local_zone_takes_traffic_while_it_has_capacity() {
+    let mut pool = zoned_pool(&[("a", 0), ("b", 0), ("a", 0), ("b", 0), ("a", 0), ("a", 0)])
+        .with_locality("a", None);
+    assert_eq!(pool.healthy_indices(), vec![0, 2, 4, 5]);
+    assert_eq!(loads(&pool), vec![(0, false, 100), (0, true, 0)]);
+
+    // 3 of 4 local is above the overprovisioned 1 / 1.4.
+    fail(&mut pool, 0);
+    assert_eq!(pool.healthy_indices(), vec![2, 4, 5]);
+}
+
+#[test]
+fn traffic_spills_to_other_zones_as_local_capacity_drops() {
+    let mut pool = zoned_pool(&[("a", 0), ("a", 0), ("a", 0), ("a", 0), ("b", 0), ("b", 0)])
+        .with_locality("a", None);
+    fail(&mut pool, 0);
+    fail(&mut pool, 1);
+    assert_eq!(loads(&pool), vec![(0, false, 70), (0, true, 30)]);
+
+    let mut lb = RoundRobin::new();
+    let remote = (0..1_000)
+        .filter(|_| lb.pick(&pool).expect("pick") >= 4)
+        .count();
+    assert!((280..=320).contains(&remote), "{remote}");
+
+    pool.mark_success(0);
+    assert_eq!(pool.healthy_indices(), vec![0, 2, 3]);
+}
+
+#[test]
+fn panic_threshold_weighs_a_priority_across_both_zones() {
+    let mut pool = zoned_pool(&[("a", 0), ("a", 0), ("b", 0), ("b", 0)])
+        .with_panic_threshold(60)
+        .with_locality("a", None);
+    fail(&mut pool, 0);
+    fail(&mut pool, 2);
+
+    // Half of each zone is up: the priority is 50% healthy, not 100%.
+    assert!(pool.is_panicking());
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2, 3]);
+
+    pool.mark_success(0);
+    assert!(!pool.is_panicking());
+}
+
+#[test]
+fn priority_outranks_locality() {
+    let mut pool = zoned_pool(&[("b", 0), ("a", 1), ("a", 0)]).with_locality("a", None);
+    assert_eq!(pool.healthy_indices(), vec![2]);
+
+    fail(&mut pool, 2);
+    assert_eq!(pool.healthy_indices(), vec![0]);
+}
+
+#[test]
+fn inflight_saturation_spreads_traffic_until_local_load_recovers() {
+    let mut pool = zoned_pool(&[("a", 0), ("a", 0), ("b", 0)]).with_locality("a", Some(2));
+    let epoch = pool.membership_epoch();
+    for _ in 0..3 {
+        pool.begin_request(0);
+        pool.begin_request(1);
+    }
+    pool.refresh_zone_saturation();
+    assert!(pool.is_zone_saturated());
+    assert_eq!(pool.healthy_indices(), vec![0, 1, 2]);
+    assert_ne!(pool.membership_epoch(), epoch);
+
+    // 4 in flight is 100% of the limit: still saturated.
+    finish(&mut pool, 0);
+    finish(&mut pool, 1);
+    pool.refresh_zone_saturation();
+    assert!(pool.is_zone_saturated());
+
+    // 3 is 75%, below the 80% recovery mark.
+    finish(&mut pool, 0);
+    pool.refresh_zone_saturation();
+    assert!(!pool.is_zone_saturated());
+    assert_eq!(pool.healthy_indices(), vec![0, 1]);
+}
+
+fn finish(pool: &mut BackendPool, index: usize) {
+    pool.finish_request(index, std::time::Duration::from_millis(1), Some(200));
+}
+
+fn upstream_pool(zone_aware: Option<ZoneAware>) -> UpstreamPool {
+    UpstreamPoolBuilder::new(2)
+        .backends(|index, backend| {
+            backend.zone = Some(["us-east-1b", "us-east-1a"][index].to_string());
+        })
+        .upstream(|upstream| upstream.zone_aware = zone_aware)
+        .locality(Locality {
+            zone: Some("us-east-1a".to_string()),
+            ..Locality::default()
+        })
+        .build()
+}
+
+#[test]
+fn upstream_pool_prefers_the_instance_zone() {
+    let mut pool = upstream_pool(None);
+    assert_eq!(pool.local_zone(), Some("us-east-1a"));
+    assert_eq!(pool.backend_zone(0), Some("us-east-1b"));
+    assert_eq!(pool.pick("/"), Some(1));
+    assert_eq!(pool.pick("/"), Some(1));
+
+    let summary = pool.membership_summary();
+    assert!(!summary.zone_saturated);
+    assert_eq!(summary.tiers.len(), 2);
+}
+
+#[test]
+fn disabled_zone_awareness_balances_across_zones() {
+    let mut pool = upstream_pool(Some(ZoneAware {
+        enabled: false,
+        max_inflight_per_backend: None,
+    }));
+    assert_eq!(pool.local_zone(), Some("us-east-1a"));
+
+    let mut picked = [pool.pick("/").unwrap(), pool.pick("/").unwrap()];
+    picked.sort_unstable();
+    assert_eq!(picked, [0, 1]);
+    assert_eq!(pool.membership_summary().tiers.len(), 1);
+}
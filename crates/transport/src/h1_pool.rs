@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     convert::Infallible,
-    sync::{Arc, RwLock},
+    sync::{Arc, PoisonError, RwLock},
     time::Duration,
 };
 
@@ -28,8 +28,11 @@ struct BackendHandle {
     inflight: Arc<Semaphore>,
 }
 
+// Handles are shared so requests in flight keep theirs when the backend is
+// removed underneath them.
 pub(crate) struct H1Pool {
-    backends: HashMap<String, BackendHandle>,
+    backends: RwLock<HashMap<String, Arc<BackendHandle>>>,
+    max_inflight: usize,
     max_idle_per_backend: usize,
     pool_idle_timeout: Duration,
     connect_timeout: Duration,
@@ -50,34 +53,46 @@ impl H1Pool {
     where
         I: IntoIterator<Item = String>,
     {
-        let inflight = max_inflight.max(1);
-        let max_idle_per_backend = max_idle_per_backend.max(1);
-        let mut map = HashMap::new();
-        for backend in backends {
-            let client = Arc::new(H1Client::new_with_observer(
-                max_idle_per_backend,
-                pool_idle_timeout,
-                connect_timeout,
-                dns_resolver.clone(),
-                connect_observer.clone(),
-            ));
-            map.insert(
-                backend,
-                BackendHandle {
-                    state: RwLock::new(BackendClientState { client }),
-                    inflight: Arc::new(Semaphore::new(inflight)),
-                },
-            );
-        }
-
-        Self {
-            backends: map,
-            max_idle_per_backend,
+        let pool = Self {
+            backends: RwLock::new(HashMap::new()),
+            max_inflight: max_inflight.max(1),
+            max_idle_per_backend: max_idle_per_backend.max(1),
             pool_idle_timeout,
             connect_timeout,
             dns_resolver,
             connect_observer,
+        };
+        for backend in backends {
+            pool.add_backend(backend);
         }
+        pool
+    }
+
+    /// Start serving `backend`, replacing any handle it already had.
+    pub(crate) fn add_backend(&self, backend: String) {
+        let client = Arc::new(H1Client::new_with_observer(
+            self.max_idle_per_backend,
+            self.pool_idle_timeout,
+            self.connect_timeout,
+            self.dns_resolver.clone(),
+            self.connect_observer.clone(),
+        ));
+        let handle = Arc::new(BackendHandle {
+            state: RwLock::new(BackendClientState { client }),
+            inflight: Arc::new(Semaphore::new(self.max_inflight)),
+        });
+        self.backends
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(backend, handle);
+    }
+
+    pub(crate) fn remove_backend(&self, backend: &str) -> bool {
+        self.backends
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(backend)
+            .is_some()
     }
 
     pub(crate) async fn send(
@@ -86,8 +101,8 @@ impl H1Pool {
         req: Request<BoxBody<Bytes, Infallible>>,
     ) -> Result<hyper::Response<Incoming>, PoolError> {
         let handle = self.backend_handle(backend)?;
-        let _permit = Self::acquire_inflight_permit(handle, backend)?;
-        let client = Self::current_client(handle)?;
+        let _permit = Self::acquire_inflight_permit(&handle, backend)?;
+        let client = Self::current_client(&handle)?;
 
         client.send(req).await.map_err(PoolError::Send)
     }
@@ -96,7 +111,7 @@ impl H1Pool {
         &self,
         backend: &str,
     ) -> Result<BackendClientRotation, String> {
-        let Some(handle) = self.backends.get(backend) else {
+        let Ok(handle) = self.backend_handle(backend) else {
             return Ok(BackendClientRotation::missing_backend());
         };
 
@@ -116,9 +131,12 @@ impl H1Pool {
         Ok(BackendClientRotation::recreated())
     }
 
-    fn backend_handle(&self, backend: &str) -> Result<&BackendHandle, PoolError> {
+    fn backend_handle(&self, backend: &str) -> Result<Arc<BackendHandle>, PoolError> {
         self.backends
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
             .get(backend)
+            .cloned()
             .ok_or_else(|| PoolError::UnknownBackend(backend.to_string()))
     }
 
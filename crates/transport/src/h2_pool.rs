@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     convert::Infallible,
-    sync::{Arc, RwLock},
+    sync::{Arc, PoisonError, RwLock},
     time::Duration,
 };
 
@@ -33,8 +33,11 @@ struct BackendHandle {
 // When DNS refresh updates a hostname's address set, new connects pick up the
 // refreshed resolver results, while already-pooled H2 connections may continue
 // using older addresses until Hyper retires them via the normal idle timeout.
+// Handles are shared so requests in flight keep theirs when the backend is
+// removed underneath them.
 pub(crate) struct H2Pool {
-    backends: HashMap<String, BackendHandle>,
+    backends: RwLock<HashMap<String, Arc<BackendHandle>>>,
+    max_inflight: usize,
     max_idle_per_backend: usize,
     pool_idle_timeout: Duration,
     connect_timeout: Duration,
@@ -57,51 +60,66 @@ impl H2Pool {
     where
         I: IntoIterator<Item = String>,
     {
-        let inflight = max_inflight.max(1);
-        let max_idle_per_backend = max_idle_per_backend.max(1);
-        let mut map = HashMap::new();
-        for backend in backends {
-            let tls = backend_tls.get(&backend).cloned().unwrap_or_default();
-            let client = Arc::new(H2Client::new_with_observer(
-                max_idle_per_backend,
-                pool_idle_timeout,
-                connect_timeout,
-                tls.clone(),
-                dns_resolver.clone(),
-                connect_observer.clone(),
-            )?);
-            map.insert(
-                backend,
-                BackendHandle {
-                    tls,
-                    state: RwLock::new(BackendClientState {
-                        client,
-                        generation: 0,
-                    }),
-                    inflight: Arc::new(Semaphore::new(inflight)),
-                },
-            );
-        }
-        Ok(Self {
-            backends: map,
-            max_idle_per_backend,
+        let pool = Self {
+            backends: RwLock::new(HashMap::new()),
+            max_inflight: max_inflight.max(1),
+            max_idle_per_backend: max_idle_per_backend.max(1),
             pool_idle_timeout,
             connect_timeout,
             dns_resolver,
             connect_observer,
-        })
+        };
+        for backend in backends {
+            let tls = backend_tls.get(&backend).cloned().unwrap_or_default();
+            pool.add_backend(backend, tls)?;
+        }
+        Ok(pool)
+    }
+
+    /// Start serving `backend` over `tls`, replacing any handle it already
+    /// had.
+    pub(crate) fn add_backend(&self, backend: String, tls: TlsClientConfig) -> Result<(), String> {
+        let client = Arc::new(H2Client::new_with_observer(
+            self.max_idle_per_backend,
+            self.pool_idle_timeout,
+            self.connect_timeout,
+            tls.clone(),
+            self.dns_resolver.clone(),
+            self.connect_observer.clone(),
+        )?);
+        let handle = Arc::new(BackendHandle {
+            tls,
+            state: RwLock::new(BackendClientState {
+                client,
+                generation: 0,
+            }),
+            inflight: Arc::new(Semaphore::new(self.max_inflight)),
+        });
+        self.backends
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(backend, handle);
+        Ok(())
+    }
+
+    pub(crate) fn remove_backend(&self, backend: &str) -> bool {
+        self.backends
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(backend)
+            .is_some()
     }
 
     #[allow(dead_code)]
     pub(crate) fn has_backend(&self, backend: &str) -> bool {
-        self.backends.contains_key(backend)
+        self.backend_handle(backend).is_ok()
     }
 
     pub(crate) fn rotate_backend_client(
         &self,
         backend: &str,
     ) -> Result<BackendClientRotation, String> {
-        let Some(handle) = self.backends.get(backend) else {
+        let Ok(handle) = self.backend_handle(backend) else {
             return Ok(BackendClientRotation::missing_backend());
         };
 
@@ -133,14 +151,17 @@ impl H2Pool {
         req: Request<BoxBody<Bytes, Infallible>>,
     ) -> Result<hyper::Response<Incoming>, PoolError> {
         let handle = self.backend_handle(backend)?;
-        let _permit = Self::acquire_inflight_permit(handle, backend)?;
-        let client = Self::current_client(handle)?;
+        let _permit = Self::acquire_inflight_permit(&handle, backend)?;
+        let client = Self::current_client(&handle)?;
         client.send(req).await.map_err(PoolError::Send)
     }
 
-    fn backend_handle(&self, backend: &str) -> Result<&BackendHandle, PoolError> {
+    fn backend_handle(&self, backend: &str) -> Result<Arc<BackendHandle>, PoolError> {
         self.backends
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
             .get(backend)
+            .cloned()
             .ok_or_else(|| PoolError::UnknownBackend(backend.to_string()))
     }
 
@@ -5,7 +5,12 @@
 //! should hand it a backend identity plus a canonical request and avoid
 //! reconstructing H1/H2 selection logic themselves.
 
-use std::{collections::HashMap, convert::Infallible, time::Duration};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{PoisonError, RwLock},
+    time::Duration,
+};
 
 use http_body_util::combinators::BoxBody;
 use hyper::{
@@ -13,7 +18,7 @@ use hyper::{
     body::{Bytes, Incoming},
 };
 use spooky_config::runtime::{
-    RuntimeBackendConnectionPolicy, RuntimeBackendTransportKind, RuntimeUpstream,
+    RuntimeBackend, RuntimeBackendConnectionPolicy, RuntimeBackendTransportKind, RuntimeUpstream,
 };
 use spooky_errors::{PoolError, ProxyError};
 
@@ -49,7 +54,7 @@ impl TransportClientRotation {
 
 /// Canonical transport façade used by edge/runtime code for backend execution.
 pub struct UpstreamTransportPool {
-    backend_entries: HashMap<String, BackendTransportEntry>,
+    backend_entries: RwLock<HashMap<String, BackendTransportEntry>>,
     h1_pool: H1Pool,
     h2_pool: H2Pool,
     execution_timeout: Duration,
@@ -62,7 +67,18 @@ impl UpstreamTransportPool {
         backend: &str,
         req: Request<BoxBody<Bytes, Infallible>>,
     ) -> Result<hyper::Response<Incoming>, ProxyError> {
-        self.execute(backend, req).await
+        self.execute(backend, req, self.execution_timeout).await
+    }
+
+    /// Like [`Self::send_backend_request`], bounded by `timeout` instead of
+    /// the pool-wide execution timeout.
+    pub async fn send_backend_request_with_timeout(
+        &self,
+        backend: &str,
+        req: Request<BoxBody<Bytes, Infallible>>,
+        timeout: Duration,
+    ) -> Result<hyper::Response<Incoming>, ProxyError> {
+        self.execute(backend, req, timeout).await
     }
 
     /// Build a transport pool from already-interpreted backend transport entries.
@@ -137,7 +153,7 @@ impl UpstreamTransportPool {
         )?;
 
         Ok(Self {
-            backend_entries,
+            backend_entries: RwLock::new(backend_entries),
             h1_pool,
             h2_pool,
             execution_timeout,
@@ -193,22 +209,75 @@ impl UpstreamTransportPool {
         )
     }
 
+    /// Start serving a backend added to `upstream` after the pool was built.
+    /// Replaces the client of a backend already known under that address.
+    pub fn add_runtime_backend(
+        &self,
+        upstream: &RuntimeUpstream,
+        backend: &RuntimeBackend,
+    ) -> Result<(), String> {
+        let address = backend.backend.address.clone();
+        let entry = Self::resolve_runtime_transport(backend.endpoint.transport_kind);
+        match entry {
+            BackendTransportEntry::Http1 => {
+                self.h2_pool.remove_backend(&address);
+                self.h1_pool.add_backend(address.clone());
+            }
+            BackendTransportEntry::H2 => {
+                self.h2_pool.add_backend(
+                    address.clone(),
+                    TlsClientConfig::from(upstream.backend_tls_policy()),
+                )?;
+                self.h1_pool.remove_backend(&address);
+            }
+        }
+        self.backend_entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(address, entry);
+        Ok(())
+    }
+
+    /// Stop serving `backend`. Requests already sending finish on the client
+    /// they hold. Returns false for unknown backends.
+    pub fn remove_backend(&self, backend: &str) -> bool {
+        let removed = self
+            .backend_entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(backend);
+        match removed {
+            Some(BackendTransportEntry::Http1) => self.h1_pool.remove_backend(backend),
+            Some(BackendTransportEntry::H2) => self.h2_pool.remove_backend(backend),
+            None => false,
+        }
+    }
+
+    pub fn has_backend(&self, backend: &str) -> bool {
+        self.backend_entry(backend).is_some()
+    }
+
     fn backend_entry(&self, backend: &str) -> Option<BackendTransportEntry> {
-        self.backend_entries.get(backend).copied()
+        self.backend_entries
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(backend)
+            .copied()
     }
 
     async fn execute(
         &self,
         backend: &str,
         req: Request<BoxBody<Bytes, Infallible>>,
+        timeout: Duration,
     ) -> Result<hyper::Response<Incoming>, ProxyError> {
         match self.backend_entry(backend) {
             Some(BackendTransportEntry::Http1) => {
-                self.execute_with_timeout(backend, self.h1_pool.send(backend, req))
+                self.execute_with_timeout(backend, self.h1_pool.send(backend, req), timeout)
                     .await
             }
             Some(BackendTransportEntry::H2) => {
-                self.execute_with_timeout(backend, self.h2_pool.send(backend, req))
+                self.execute_with_timeout(backend, self.h2_pool.send(backend, req), timeout)
                     .await
             }
             None => Err(ProxyError::Pool(PoolError::UnknownBackend(
@@ -241,11 +310,12 @@ impl UpstreamTransportPool {
         &self,
         _backend: &str,
         send: F,
+        timeout: Duration,
     ) -> Result<hyper::Response<Incoming>, ProxyError>
     where
         F: std::future::Future<Output = Result<hyper::Response<Incoming>, PoolError>>,
     {
-        tokio::time::timeout(self.execution_timeout, send)
+        tokio::time::timeout(timeout, send)
             .await
             .map_err(|_| ProxyError::Timeout)?
             .map_err(ProxyError::Pool)
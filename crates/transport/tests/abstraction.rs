@@ -17,7 +17,7 @@ use spooky_config::{
         ClientAuth, Config, Listen, LoadBalancing, Log, Observability, Performance, Resilience,
         RouteMatch, Security, Tls, Upstream, UpstreamTls,
     },
-    runtime::{RuntimeBackendTransportKind, RuntimeConfig},
+    runtime::{RuntimeBackend, RuntimeBackendTransportKind, RuntimeConfig},
 };
 use spooky_errors::{PoolError, ProxyError};
 use spooky_transport::{SharedDnsResolver, UpstreamTransportPool};
@@ -199,6 +199,7 @@ fn transport_test_config(http_backend: &str, https_backend: &str) -> Config {
         },
         listeners: Vec::new(),
         upstream: HashMap::new(),
+        routes: Vec::new(),
         load_balancing: None,
         upstream_tls: UpstreamTls::default(),
         log: Log::default(),
@@ -206,6 +207,7 @@ fn transport_test_config(http_backend: &str, https_backend: &str) -> Config {
         observability: Observability::default(),
         resilience: Resilience::default(),
         security: Security::default(),
+        locality: Default::default(),
     };
 
     for (name, route_host, backend) in [
@@ -218,9 +220,19 @@ fn transport_test_config(http_backend: &str, https_backend: &str) -> Config {
                 load_balancing: LoadBalancing {
                     lb_type: "round-robin".to_string(),
                     key: None,
+                    table_size: None,
+                    hash_balance_factor: None,
+                    p2c: None,
+                    panic_threshold_percent: None,
                 },
                 auth: Default::default(),
                 host_policy: Default::default(),
+                policy: Default::default(),
+                outlier_detection: None,
+                slow_start: None,
+                zone_aware: None,
+                session_affinity: None,
+                adaptive_admission: None,
                 forwarded_headers: Default::default(),
                 tls: None,
                 route: RouteMatch {
@@ -233,6 +245,8 @@ fn transport_test_config(http_backend: &str, https_backend: &str) -> Config {
                     address: backend.to_string(),
                     weight: 100,
                     health_check: None,
+                    priority: 0,
+                    zone: None,
                 }],
             },
         );
@@ -267,6 +281,53 @@ fn runtime_upstream_interpretation_selects_protocol_internally() {
     assert_eq!(h2_rotation.generations(), Some((0, 1)));
 }
 
+#[tokio::test]
+async fn runtime_backends_join_and_leave_a_built_pool() {
+    let config = transport_test_config("http://127.0.0.1:8080", "https://127.0.0.1:8443");
+    let runtime = RuntimeConfig::from_config(&config).expect("runtime config");
+    let upstream = runtime.upstreams.get("secure").expect("secure upstream");
+    let pool = UpstreamTransportPool::from_runtime_upstreams(
+        runtime.upstreams.values(),
+        &runtime.policies.transport.backend_connections,
+        SharedDnsResolver::new(),
+        None,
+    )
+    .expect("transport pool");
+
+    let added = RuntimeBackend::normalize(
+        "secure",
+        &spooky_config::config::Backend {
+            id: "secure-2".to_string(),
+            address: "https://127.0.0.1:9443".to_string(),
+            weight: 100,
+            health_check: None,
+            priority: 0,
+            zone: None,
+        },
+    )
+    .expect("runtime backend");
+    assert!(!pool.has_backend("https://127.0.0.1:9443"));
+    pool.add_runtime_backend(upstream, &added)
+        .expect("add backend");
+    assert!(pool.has_backend("https://127.0.0.1:9443"));
+    let rotation = pool
+        .rotate_backend_client("https://127.0.0.1:9443")
+        .expect("h2 rotation");
+    assert_eq!(rotation.generations(), Some((0, 1)));
+
+    assert!(pool.remove_backend("https://127.0.0.1:9443"));
+    assert!(!pool.remove_backend("https://127.0.0.1:9443"));
+    let err = pool
+        .send_backend_request("https://127.0.0.1:9443", request("https://127.0.0.1:9443/"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ProxyError::Pool(PoolError::UnknownBackend(ref backend)) if backend == "https://127.0.0.1:9443"
+    ));
+    assert!(pool.has_backend("https://127.0.0.1:8443"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn request_execution_routes_without_protocol_branching() {
     let h1_port = match start_h1_server(b"h1", Duration::ZERO).await {